@@ -11,6 +11,7 @@ use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH},
     multi_ed25519::{self, MultiEd25519PublicKey, BITMAP_NUM_OF_BYTES, MAX_NUM_OF_KEYS},
+    secp256k1_ecdsa, secp256r1_ecdsa,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -18,6 +19,7 @@ use aptos_types::{
     contract_event::{ContractEvent, EventWithVersion},
     transaction::{
         authenticator::{AccountAuthenticator, TransactionAuthenticator, MAX_NUM_OF_SIGS},
+        webauthn::PartialAuthenticatorAssertionResponse,
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
@@ -848,6 +850,7 @@ pub enum TransactionSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
     MultiAgentSignature(MultiAgentSignature),
+    SingleSenderSignature(SingleSenderSignature),
 }
 
 impl VerifyInput for TransactionSignature {
@@ -856,6 +859,7 @@ impl VerifyInput for TransactionSignature {
             TransactionSignature::Ed25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiEd25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiAgentSignature(inner) => inner.verify(),
+            TransactionSignature::SingleSenderSignature(inner) => inner.verify(),
         }
     }
 }
@@ -868,6 +872,7 @@ impl TryFrom<TransactionSignature> for TransactionAuthenticator {
             TransactionSignature::Ed25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiEd25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiAgentSignature(sig) => sig.try_into()?,
+            TransactionSignature::SingleSenderSignature(sig) => sig.try_into()?,
         })
     }
 }
@@ -1064,18 +1069,134 @@ impl TryFrom<MultiEd25519Signature> for AccountAuthenticator {
     }
 }
 
+/// A single secp256k1 ECDSA signature
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct Secp256k1EcdsaSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+}
+
+impl VerifyInput for Secp256k1EcdsaSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        let public_key_len = self.public_key.inner().len();
+        let signature_len = self.signature.inner().len();
+        if public_key_len != secp256k1_ecdsa::PUBLIC_KEY_LENGTH {
+            bail!(
+                "Secp256k1 ECDSA signature's public key is an invalid number of bytes, should be {} bytes but found {}",
+                secp256k1_ecdsa::PUBLIC_KEY_LENGTH, public_key_len
+            )
+        } else if signature_len != secp256k1_ecdsa::SIGNATURE_LENGTH {
+            bail!(
+                "Secp256k1 ECDSA signature length is an invalid number of bytes, should be {} bytes but found {}",
+                secp256k1_ecdsa::SIGNATURE_LENGTH, signature_len
+            )
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TryFrom<Secp256k1EcdsaSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256k1EcdsaSignature) -> Result<Self, Self::Error> {
+        let Secp256k1EcdsaSignature {
+            public_key,
+            signature,
+        } = value;
+        Ok(AccountAuthenticator::secp256k1_ecdsa(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256k1EcdsaPublicKey")?,
+            signature
+                .inner()
+                .try_into()
+                .context("Failed to parse given signature as a Secp256k1EcdsaSignature")?,
+        ))
+    }
+}
+
+/// A WebAuthn (e.g., passkey) signature
+///
+/// This holds the fields of the authenticator's assertion response that are required to verify
+/// the secp256r1 ECDSA signature over `authenticator_data || SHA2-256(client_data_json)`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct WebAuthnSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+    pub authenticator_data: HexEncodedBytes,
+    pub client_data_json: HexEncodedBytes,
+}
+
+impl VerifyInput for WebAuthnSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        let public_key_len = self.public_key.inner().len();
+        let signature_len = self.signature.inner().len();
+        if public_key_len != secp256r1_ecdsa::PUBLIC_KEY_LENGTH {
+            bail!(
+                "WebAuthn signature's public key is an invalid number of bytes, should be {} bytes but found {}",
+                secp256r1_ecdsa::PUBLIC_KEY_LENGTH, public_key_len
+            )
+        } else if signature_len != secp256r1_ecdsa::SIGNATURE_LENGTH {
+            bail!(
+                "WebAuthn signature length is an invalid number of bytes, should be {} bytes but found {}",
+                secp256r1_ecdsa::SIGNATURE_LENGTH, signature_len
+            )
+        } else if self.authenticator_data.inner().is_empty() {
+            bail!("WebAuthn signature has no authenticator data")
+        } else if self.client_data_json.inner().is_empty() {
+            bail!("WebAuthn signature has no client data JSON")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TryFrom<WebAuthnSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: WebAuthnSignature) -> Result<Self, Self::Error> {
+        let WebAuthnSignature {
+            public_key,
+            signature,
+            authenticator_data,
+            client_data_json,
+        } = value;
+        let signature = signature
+            .inner()
+            .try_into()
+            .context("Failed to parse given signature as a Secp256r1EcdsaSignature")?;
+        Ok(AccountAuthenticator::webauthn(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256r1EcdsaPublicKey")?,
+            PartialAuthenticatorAssertionResponse::new(
+                signature,
+                authenticator_data.into(),
+                client_data_json.into(),
+            ),
+        ))
+    }
+}
+
 /// Account signature scheme
 ///
-/// The account signature scheme allows you to have two types of accounts:
+/// The account signature scheme allows you to have the following types of accounts:
 ///
 ///   1. A single Ed25519 key account, one private key
 ///   2. A k-of-n multi-Ed25519 key account, multiple private keys, such that k-of-n must sign a transaction.
+///   3. A single secp256k1 ECDSA key account, one private key
+///   4. A WebAuthn (e.g., passkey) account, with a secp256r1 ECDSA key held by an authenticator
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
 pub enum AccountSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
+    Secp256k1EcdsaSignature(Secp256k1EcdsaSignature),
+    WebAuthnSignature(WebAuthnSignature),
 }
 
 impl VerifyInput for AccountSignature {
@@ -1083,6 +1204,8 @@ impl VerifyInput for AccountSignature {
         match self {
             AccountSignature::Ed25519Signature(inner) => inner.verify(),
             AccountSignature::MultiEd25519Signature(inner) => inner.verify(),
+            AccountSignature::Secp256k1EcdsaSignature(inner) => inner.verify(),
+            AccountSignature::WebAuthnSignature(inner) => inner.verify(),
         }
     }
 }
@@ -1094,6 +1217,8 @@ impl TryFrom<AccountSignature> for AccountAuthenticator {
         Ok(match sig {
             AccountSignature::Ed25519Signature(s) => s.try_into()?,
             AccountSignature::MultiEd25519Signature(s) => s.try_into()?,
+            AccountSignature::Secp256k1EcdsaSignature(s) => s.try_into()?,
+            AccountSignature::WebAuthnSignature(s) => s.try_into()?,
         })
     }
 }
@@ -1152,6 +1277,30 @@ impl TryFrom<MultiAgentSignature> for TransactionAuthenticator {
     }
 }
 
+/// Single sender signature for transactions signed by a single account
+///
+/// This allows the sender to use any of the supported account signature schemes
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SingleSenderSignature {
+    pub sender: AccountSignature,
+}
+
+impl VerifyInput for SingleSenderSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.sender.verify()
+    }
+}
+
+impl TryFrom<SingleSenderSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SingleSenderSignature) -> Result<Self, Self::Error> {
+        Ok(TransactionAuthenticator::single_sender(
+            value.sender.try_into()?,
+        ))
+    }
+}
+
 impl From<(&Ed25519PublicKey, &ed25519::Ed25519Signature)> for Ed25519Signature {
     fn from((pk, sig): (&Ed25519PublicKey, &ed25519::Ed25519Signature)) -> Self {
         Self {
@@ -1190,6 +1339,46 @@ impl
     }
 }
 
+impl
+    From<(
+        &secp256k1_ecdsa::Secp256k1EcdsaPublicKey,
+        &secp256k1_ecdsa::Secp256k1EcdsaSignature,
+    )> for Secp256k1EcdsaSignature
+{
+    fn from(
+        (pk, sig): (
+            &secp256k1_ecdsa::Secp256k1EcdsaPublicKey,
+            &secp256k1_ecdsa::Secp256k1EcdsaSignature,
+        ),
+    ) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.to_bytes().to_vec().into(),
+        }
+    }
+}
+
+impl
+    From<(
+        &secp256r1_ecdsa::Secp256r1EcdsaPublicKey,
+        &PartialAuthenticatorAssertionResponse,
+    )> for WebAuthnSignature
+{
+    fn from(
+        (pk, sig): (
+            &secp256r1_ecdsa::Secp256r1EcdsaPublicKey,
+            &PartialAuthenticatorAssertionResponse,
+        ),
+    ) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.signature().to_bytes().to_vec().into(),
+            authenticator_data: sig.authenticator_data().to_vec().into(),
+            client_data_json: sig.client_data_json().to_vec().into(),
+        }
+    }
+}
+
 impl From<&AccountAuthenticator> for AccountSignature {
     fn from(auth: &AccountAuthenticator) -> Self {
         use AccountAuthenticator::*;
@@ -1202,6 +1391,14 @@ impl From<&AccountAuthenticator> for AccountSignature {
                 public_key,
                 signature,
             } => Self::MultiEd25519Signature((public_key, signature).into()),
+            Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256k1EcdsaSignature((public_key, signature).into()),
+            WebAuthn {
+                public_key,
+                signature,
+            } => Self::WebAuthnSignature((public_key, signature).into()),
         }
     }
 }
@@ -1247,6 +1444,9 @@ impl From<TransactionAuthenticator> for TransactionSignature {
             } => Self::MultiAgentSignature(
                 (sender, secondary_signer_addresses, secondary_signers).into(),
             ),
+            SingleSender { sender } => Self::SingleSenderSignature(SingleSenderSignature {
+                sender: sender.into(),
+            }),
         }
     }
 }
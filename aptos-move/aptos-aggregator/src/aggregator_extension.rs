@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::delta_change_set::{addition, deserialize, subtraction};
+use crate::delta_change_set::{addition, deserialize, subtraction, DeltaOp, DeltaUpdate};
 use aptos_types::vm_status::StatusCode;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::account_address::AccountAddress;
@@ -19,11 +19,11 @@ pub enum AggregatorState {
     NegativeDelta,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AggregatorHandle(pub AccountAddress);
 
 /// Uniquely identifies each aggregator instance in storage.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AggregatorID {
     // A handle that is shared across all aggregator instances created by the
     // same `AggregatorFactory` and which is used for fine-grained storage
@@ -275,12 +275,43 @@ impl Aggregator {
             )
     }
 
+    /// Returns the value of the aggregator if it is known, i.e. if it is not
+    /// in a delta state.
+    pub fn value_if_known(&self) -> Option<u128> {
+        (self.state == AggregatorState::Data).then_some(self.value)
+    }
+
+    /// Returns the delta of the aggregator as a delta op if it is in a delta
+    /// state, i.e. if its value is not known.
+    pub fn delta_op(&self) -> Option<DeltaOp> {
+        let update = match self.state {
+            AggregatorState::Data => return None,
+            AggregatorState::PositiveDelta => DeltaUpdate::Plus(self.value),
+            AggregatorState::NegativeDelta => DeltaUpdate::Minus(self.value),
+        };
+        let (max_positive, min_negative) = self.history.as_ref().map_or((0, 0), |history| {
+            (history.max_positive, history.min_negative)
+        });
+        Some(DeltaOp::new(update, self.limit, max_positive, min_negative))
+    }
+
     /// Unpacks aggregator into its fields.
     pub fn into(self) -> (u128, AggregatorState, u128, Option<History>) {
         (self.value, self.state, self.limit, self.history)
     }
 }
 
+/// Describes the value of an aggregator snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotValue {
+    // If the value of the aggregator was known when the snapshot was taken.
+    Data(u128),
+    // If the aggregator stored a delta when the snapshot was taken. The value
+    // of the snapshot is this delta applied to the value of the aggregator
+    // with the given id before the current transaction.
+    Delta(AggregatorID, DeltaOp),
+}
+
 /// Stores all information about aggregators (how many have been created or
 /// removed), what are their states, etc. per single transaction).
 #[derive(Default)]
@@ -293,6 +324,8 @@ pub struct AggregatorData {
     destroyed_aggregators: BTreeSet<AggregatorID>,
     // All aggregator instances that exist in the current transaction.
     aggregators: BTreeMap<AggregatorID, Aggregator>,
+    // All snapshots taken in the current transaction, mapped to their values.
+    snapshots: BTreeMap<AggregatorID, SnapshotValue>,
 }
 
 impl AggregatorData {
@@ -347,6 +380,46 @@ impl AggregatorData {
         }
     }
 
+    /// Returns the number of snapshots taken in the current transaction.
+    pub fn num_snapshots(&self) -> u128 {
+        self.snapshots.len() as u128
+    }
+
+    /// Records a snapshot with a given `id` of the aggregator with `source_id`
+    /// and a `limit`. The value of the snapshot is the current value of the
+    /// aggregator: if it is not known, the current delta is recorded instead.
+    pub fn create_snapshot(&mut self, id: AggregatorID, source_id: AggregatorID, limit: u128) {
+        let aggregator = self.get_aggregator(source_id, limit);
+        let value = match aggregator.delta_op() {
+            Some(delta_op) => SnapshotValue::Delta(source_id, delta_op),
+            None => SnapshotValue::Data(aggregator.value),
+        };
+        self.snapshots.insert(id, value);
+    }
+
+    /// Returns true if the snapshot with `id` was taken in the current
+    /// transaction, and thus its value is not yet known.
+    pub fn contains_snapshot(&self, id: &AggregatorID) -> bool {
+        self.snapshots.contains_key(id)
+    }
+
+    /// Returns true if a snapshot of the aggregator with `id` was taken in the
+    /// current transaction.
+    pub fn has_snapshots_of(&self, id: &AggregatorID) -> bool {
+        self.snapshots.values().any(|value| match value {
+            SnapshotValue::Data(_) => false,
+            SnapshotValue::Delta(source_id, _) => source_id == id,
+        })
+    }
+
+    /// If snapshot has been taken in this transaction, it is removed. Otherwise,
+    /// it is marked for deletion.
+    pub fn remove_snapshot(&mut self, id: AggregatorID) {
+        if self.snapshots.remove(&id).is_none() {
+            self.destroyed_aggregators.insert(id);
+        }
+    }
+
     /// Unpacks aggregator data.
     pub fn into(
         self,
@@ -354,11 +427,13 @@ impl AggregatorData {
         BTreeSet<AggregatorID>,
         BTreeSet<AggregatorID>,
         BTreeMap<AggregatorID, Aggregator>,
+        BTreeMap<AggregatorID, SnapshotValue>,
     ) {
         (
             self.new_aggregators,
             self.destroyed_aggregators,
             self.aggregators,
+            self.snapshots,
         )
     }
 }
@@ -504,4 +579,91 @@ mod test {
         assert_eq!(aggregator.history.as_ref().unwrap().min_negative, 100);
         assert_eq!(aggregator.state, AggregatorState::PositiveDelta);
     }
+
+    #[test]
+    fn test_snapshots() {
+        let mut aggregator_data = AggregatorData::default();
+
+        aggregator_data.create_snapshot(
+            aggregator_id_for_test(100),
+            aggregator_id_for_test(600),
+            600,
+        );
+        aggregator_data.create_snapshot(
+            aggregator_id_for_test(200),
+            aggregator_id_for_test(600),
+            600,
+        );
+        assert_eq!(aggregator_data.num_snapshots(), 2);
+        assert!(aggregator_data.contains_snapshot(&aggregator_id_for_test(100)));
+        assert!(aggregator_data.has_snapshots_of(&aggregator_id_for_test(600)));
+        assert!(!aggregator_data.has_snapshots_of(&aggregator_id_for_test(100)));
+
+        // Snapshot taken in this transaction has no side-effects when removed,
+        // while other snapshots are deleted from storage.
+        aggregator_data.remove_snapshot(aggregator_id_for_test(100));
+        aggregator_data.remove_snapshot(aggregator_id_for_test(300));
+        assert!(!aggregator_data.contains_snapshot(&aggregator_id_for_test(100)));
+
+        let (_, destroyed_aggregators, _, snapshots) = aggregator_data.into();
+        assert!(destroyed_aggregators
+            .into_iter()
+            .eq([aggregator_id_for_test(300)]));
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(
+            snapshots.get(&aggregator_id_for_test(200)),
+            Some(&SnapshotValue::Delta(
+                aggregator_id_for_test(600),
+                DeltaOp::new(DeltaUpdate::Plus(0), 600, 0, 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_snapshot_values() {
+        let mut aggregator_data = AggregatorData::default();
+        aggregator_data.create_new_aggregator(aggregator_id_for_test(200), 200);
+
+        // Snapshots take the value or the delta of the aggregator at the time
+        // they are taken, and are not affected by later changes.
+        let aggregator = aggregator_data.get_aggregator(aggregator_id_for_test(200), 200);
+        assert_ok!(aggregator.add(100));
+        aggregator_data.create_snapshot(
+            aggregator_id_for_test(100),
+            aggregator_id_for_test(200),
+            200,
+        );
+
+        let aggregator = aggregator_data.get_aggregator(aggregator_id_for_test(600), 600);
+        assert_ok!(aggregator.add(400));
+        assert_ok!(aggregator.sub(300));
+        aggregator_data.create_snapshot(
+            aggregator_id_for_test(300),
+            aggregator_id_for_test(600),
+            600,
+        );
+
+        assert_ok!(aggregator_data
+            .get_aggregator(aggregator_id_for_test(200), 200)
+            .add(50));
+        assert_ok!(aggregator_data
+            .get_aggregator(aggregator_id_for_test(600), 600)
+            .add(50));
+
+        assert!(!aggregator_data.has_snapshots_of(&aggregator_id_for_test(200)));
+        assert!(aggregator_data.has_snapshots_of(&aggregator_id_for_test(600)));
+
+        let (_, _, _, snapshots) = aggregator_data.into();
+        assert_eq!(
+            snapshots.get(&aggregator_id_for_test(100)),
+            Some(&SnapshotValue::Data(100))
+        );
+        assert_eq!(
+            snapshots.get(&aggregator_id_for_test(300)),
+            Some(&SnapshotValue::Delta(
+                aggregator_id_for_test(600),
+                DeltaOp::new(DeltaUpdate::Plus(100), 600, 400, 0)
+            ))
+        );
+    }
 }
@@ -268,25 +268,34 @@ pub fn delta_add(v: u128, limit: u128) -> DeltaOp {
 }
 
 /// `DeltaChangeSet` contains all access paths that one transaction wants to update with deltas.
+/// It also contains the snapshots of aggregators taken by the transaction, whose values are only
+/// known once the deltas recorded by them are applied to the values of the aggregators.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DeltaChangeSet {
     delta_change_set: BTreeMap<StateKey, DeltaOp>,
+    // Maps the access path of every snapshot to the access path of the aggregator it was
+    // taken of, and the delta of the aggregator when the snapshot was taken.
+    snapshots: BTreeMap<StateKey, (StateKey, DeltaOp)>,
 }
 
 impl DeltaChangeSet {
     pub fn empty() -> Self {
         DeltaChangeSet {
             delta_change_set: BTreeMap::new(),
+            snapshots: BTreeMap::new(),
         }
     }
 
+    /// Returns the number of writes produced by materializing this change set, i.e. the
+    /// number of deltas and snapshots.
     pub fn len(&self) -> usize {
-        self.delta_change_set.len()
+        self.delta_change_set.len() + self.snapshots.len()
     }
 
     pub fn new(delta_change_set: impl IntoIterator<Item = (StateKey, DeltaOp)>) -> Self {
         DeltaChangeSet {
             delta_change_set: delta_change_set.into_iter().collect(),
+            snapshots: BTreeMap::new(),
         }
     }
 
@@ -305,13 +314,23 @@ impl DeltaChangeSet {
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.delta_change_set.is_empty()
+        self.delta_change_set.is_empty() && self.snapshots.is_empty()
     }
 
     pub fn as_inner_mut(&mut self) -> &mut BTreeMap<StateKey, DeltaOp> {
         &mut self.delta_change_set
     }
 
+    /// Records a snapshot at `snapshot.0` of the aggregator at `snapshot.1`, which had the
+    /// delta `snapshot.2` when the snapshot was taken.
+    pub fn insert_snapshot(&mut self, snapshot: (StateKey, StateKey, DeltaOp)) {
+        self.snapshots.insert(snapshot.0, (snapshot.1, snapshot.2));
+    }
+
+    pub fn snapshots(&self) -> &BTreeMap<StateKey, (StateKey, DeltaOp)> {
+        &self.snapshots
+    }
+
     /// Consumes the delta change set and tries to materialize it. Returns a
     /// mutable write set if materialization succeeds (mutability since we want
     /// to merge these writes with transaction outputs).
@@ -325,6 +344,12 @@ impl DeltaChangeSet {
             materialized_write_set.push((state_key, write_op));
         }
 
+        // Snapshots apply their deltas to the values of their aggregators.
+        for (state_key, (source, delta_op)) in self.snapshots {
+            let write_op = delta_op.try_into_write_op(state_view, &source)?;
+            materialized_write_set.push((state_key, write_op));
+        }
+
         // All deltas are applied successfully.
         Ok(WriteSetMut::new(materialized_write_set))
     }
@@ -576,4 +601,42 @@ mod test {
             Err(VMStatus::MoveAbort(_, ESUB_UNDERFLOW))
         );
     }
+
+    #[test]
+    fn test_snapshot_materialization() {
+        let mut state_view = FakeDataStore::default();
+        state_view.set(KEY.clone(), serialize(&100));
+        let snapshot_key = StateKey::raw(String::from("test-snapshot-key").into_bytes());
+
+        let mut delta_change_set = DeltaChangeSet::new([(KEY.clone(), delta_add(5, 200))]);
+        delta_change_set.insert_snapshot((snapshot_key.clone(), KEY.clone(), delta_add(2, 200)));
+        assert_eq!(delta_change_set.len(), 2);
+
+        // The snapshot takes the value of the aggregator when it was taken.
+        let write_set = assert_ok!(delta_change_set.try_into_write_set_mut(&state_view))
+            .freeze()
+            .unwrap();
+        assert_eq!(
+            write_set.get(&KEY),
+            Some(&WriteOp::Modification(serialize(&105)))
+        );
+        assert_eq!(
+            write_set.get(&snapshot_key),
+            Some(&WriteOp::Modification(serialize(&102)))
+        );
+    }
+
+    #[test]
+    fn test_unsuccessful_snapshot_materialization() {
+        let mut state_view = FakeDataStore::default();
+        state_view.set(KEY.clone(), serialize(&100));
+        let snapshot_key = StateKey::raw(String::from("test-snapshot-key").into_bytes());
+
+        let mut delta_change_set = DeltaChangeSet::empty();
+        delta_change_set.insert_snapshot((snapshot_key, KEY.clone(), delta_add(15, 100)));
+        assert_matches!(
+            delta_change_set.try_into_write_set_mut(&state_view),
+            Err(VMStatus::MoveAbort(_, EADD_OVERFLOW))
+        );
+    }
 }
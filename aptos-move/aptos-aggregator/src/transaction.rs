@@ -72,9 +72,31 @@ impl ChangeSetExt {
         let (write_set, events) = change_set.into_inner();
         let mut write_set = write_set.into_mut();
 
-        let delta_ops = delta_set.as_inner_mut();
         let write_ops = write_set.as_inner_mut();
 
+        // Snapshots apply their deltas to the values of aggregators before `other`, so they
+        // are resolved before the deltas of `other` are squashed.
+        for (key, (source, mut op)) in other.snapshots().clone() {
+            match write_ops.get(&source) {
+                Some(write_op) => {
+                    let data = match write_op.extract_raw_bytes() {
+                        Some(data) => data,
+                        None => bail!("Failed to snapshot Aggregator -- value already deleted"),
+                    };
+                    let val: u128 = bcs::from_bytes(&data)?;
+                    write_ops.insert(key, Modification(bcs::to_bytes(&op.apply_to(val)?)?));
+                },
+                None => {
+                    if let Some(previous_op) = delta_set.as_inner_mut().get(&source) {
+                        op.merge_onto(*previous_op)?;
+                    }
+                    delta_set.insert_snapshot((key, source, op));
+                },
+            }
+        }
+
+        let delta_ops = delta_set.as_inner_mut();
+
         for (key, mut op) in other.into_iter() {
             if let Some(r) = write_ops.get_mut(&key) {
                 match r {
@@ -146,9 +168,11 @@ impl ChangeSetExt {
     }
 
     pub fn squash(self, other: Self) -> anyhow::Result<Self> {
+        // Deltas and snapshots of `other` are squashed first, so that snapshots are not
+        // affected by the writes of `other`.
         let (delta_change_set, change_set) = other.into_inner();
-        self.squash_change_set(change_set)?
-            .squash_delta_change_set(delta_change_set)
+        self.squash_delta_change_set(delta_change_set)?
+            .squash_change_set(change_set)
     }
 }
 
@@ -218,7 +242,7 @@ impl TransactionOutputExt {
             return txn_output;
         }
 
-        // We should have a delta write for every delta in the output.
+        // We should have a delta write for every delta and snapshot in the output.
         assert_eq!(delta_change_set.len(), delta_writes.len());
 
         Self::merge_delta_writes(txn_output, delta_writes)
@@ -14,6 +14,7 @@ use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
     on_chain_config::{Features, OnChainConfig, TimedFeatures},
+    state_store::state_key::StateKey,
     transaction::{
         ChangeSet, SignedTransaction, Transaction, TransactionInfo, TransactionOutput,
         TransactionPayload, Version,
@@ -24,6 +25,7 @@ use aptos_validator_interface::{
     AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
 };
 use aptos_vm::{
+    block_executor::{BlockAptosVM, BlockExecutionReport},
     data_cache::StorageAdapter,
     move_vm_ext::{MoveVmExt, SessionExt, SessionId},
     AptosVM, VMExecutor,
@@ -61,6 +63,28 @@ impl AptosDebugger {
             .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
     }
 
+    /// Executes the transactions in parallel, returning the outputs along with a report of the
+    /// parallel execution (i.e., re-executions, conflicting keys and dependency waits).
+    pub fn execute_transactions_at_version_with_report(
+        &self,
+        version: Version,
+        txns: Vec<Transaction>,
+        concurrency_level: usize,
+    ) -> Result<(
+        Vec<TransactionOutput>,
+        Option<BlockExecutionReport<StateKey>>,
+    )> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let (ret, report) = BlockAptosVM::execute_block_with_report(
+            txns,
+            &state_view,
+            concurrency_level,
+            AptosVM::fetch_block_gas_limit(&state_view),
+        );
+        let outputs = ret.map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+        Ok((outputs, report))
+    }
+
     pub fn execute_transaction_at_version_with_gas_profiler(
         &self,
         version: Version,
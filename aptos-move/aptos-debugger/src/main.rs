@@ -29,6 +29,10 @@ pub struct Argument {
 
     #[clap(long, default_value = "1")]
     concurrency_level: usize,
+
+    /// Log a report of the parallel execution statistics and conflicts of every block.
+    #[clap(long)]
+    execution_report: bool,
 }
 
 #[tokio::main]
@@ -36,6 +40,9 @@ async fn main() -> Result<()> {
     aptos_logger::Logger::new().init();
     let args = Argument::parse();
     AptosVM::set_concurrency_level_once(args.concurrency_level);
+    if args.execution_report {
+        AptosVM::set_block_execution_reports_once();
+    }
 
     let debugger = match args.target {
         Target::Rest { endpoint } => {
@@ -73,6 +73,85 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.algebra.ark_h2c_bls12381g2_xmd_sha256_sswu_per_msg_byte, { 8.. => "algebra.ark_h2c_bls12381g2_xmd_sha256_sswu_per_msg_byte" }, 48 * MUL],
     // Algebra gas parameters end.
 
+    // BN254 algebra gas parameters begin.
+    // Initial estimates scaled from the BLS12-381 parameters above. Not covered by `scripts/algebra-gas/update_algebra_gas_params.py` yet.
+    [.algebra.ark_bn254_fq12_add, { 9.. => "algebra.ark_bn254_fq12_add" }, 1_092 * MUL],
+    [.algebra.ark_bn254_fq12_clone, { 9.. => "algebra.ark_bn254_fq12_clone" }, 211 * MUL],
+    [.algebra.ark_bn254_fq12_deser, { 9.. => "algebra.ark_bn254_fq12_deser" }, 6_708 * MUL],
+    [.algebra.ark_bn254_fq12_div, { 9.. => "algebra.ark_bn254_fq12_div" }, 150_488 * MUL],
+    [.algebra.ark_bn254_fq12_eq, { 9.. => "algebra.ark_bn254_fq12_eq" }, 436 * MUL],
+    [.algebra.ark_bn254_fq12_from_u64, { 9.. => "algebra.ark_bn254_fq12_from_u64" }, 541 * MUL],
+    [.algebra.ark_bn254_fq12_inv, { 9.. => "algebra.ark_bn254_fq12_inv" }, 120_314 * MUL],
+    [.algebra.ark_bn254_fq12_mul, { 9.. => "algebra.ark_bn254_fq12_mul" }, 29_932 * MUL],
+    [.algebra.ark_bn254_fq12_neg, { 9.. => "algebra.ark_bn254_fq12_neg" }, 709 * MUL],
+    [.algebra.ark_bn254_fq12_one, { 9.. => "algebra.ark_bn254_fq12_one" }, 11 * MUL],
+    [.algebra.ark_bn254_fq12_pow_u256, { 9.. => "algebra.ark_bn254_fq12_pow_u256" }, 8_798_525 * MUL],
+    [.algebra.ark_bn254_fq12_serialize, { 9.. => "algebra.ark_bn254_fq12_serialize" }, 4_847 * MUL],
+    [.algebra.ark_bn254_fq12_square, { 9.. => "algebra.ark_bn254_fq12_square" }, 21_087 * MUL],
+    [.algebra.ark_bn254_fq12_sub, { 9.. => "algebra.ark_bn254_fq12_sub" }, 1_055 * MUL],
+    [.algebra.ark_bn254_fq12_zero, { 9.. => "algebra.ark_bn254_fq12_zero" }, 211 * MUL],
+    [.algebra.ark_bn254_fq_add, { 9.. => "algebra.ark_bn254_fq_add" }, 211 * MUL],
+    [.algebra.ark_bn254_fq_deser, { 9.. => "algebra.ark_bn254_fq_deser" }, 452 * MUL],
+    [.algebra.ark_bn254_fq_div, { 9.. => "algebra.ark_bn254_fq_div" }, 35_664 * MUL],
+    [.algebra.ark_bn254_fq_eq, { 9.. => "algebra.ark_bn254_fq_eq" }, 128 * MUL],
+    [.algebra.ark_bn254_fq_from_u64, { 9.. => "algebra.ark_bn254_fq_from_u64" }, 297 * MUL],
+    [.algebra.ark_bn254_fq_inv, { 9.. => "algebra.ark_bn254_fq_inv" }, 35_166 * MUL],
+    [.algebra.ark_bn254_fq_mul, { 9.. => "algebra.ark_bn254_fq_mul" }, 302 * MUL],
+    [.algebra.ark_bn254_fq_neg, { 9.. => "algebra.ark_bn254_fq_neg" }, 128 * MUL],
+    [.algebra.ark_bn254_fq_one, { 9.. => "algebra.ark_bn254_fq_one" }, 211 * MUL],
+    [.algebra.ark_bn254_fq_serialize, { 9.. => "algebra.ark_bn254_fq_serialize" }, 662 * MUL],
+    [.algebra.ark_bn254_fq_square, { 9.. => "algebra.ark_bn254_fq_square" }, 285 * MUL],
+    [.algebra.ark_bn254_fq_sub, { 9.. => "algebra.ark_bn254_fq_sub" }, 174 * MUL],
+    [.algebra.ark_bn254_fq_zero, { 9.. => "algebra.ark_bn254_fq_zero" }, 211 * MUL],
+    [.algebra.ark_bn254_fr_add, { 9.. => "algebra.ark_bn254_fr_add" }, 211 * MUL],
+    [.algebra.ark_bn254_fr_deser, { 9.. => "algebra.ark_bn254_fr_deser" }, 452 * MUL],
+    [.algebra.ark_bn254_fr_div, { 9.. => "algebra.ark_bn254_fr_div" }, 35_664 * MUL],
+    [.algebra.ark_bn254_fr_eq, { 9.. => "algebra.ark_bn254_fr_eq" }, 128 * MUL],
+    [.algebra.ark_bn254_fr_from_u64, { 9.. => "algebra.ark_bn254_fr_from_u64" }, 297 * MUL],
+    [.algebra.ark_bn254_fr_inv, { 9.. => "algebra.ark_bn254_fr_inv" }, 35_166 * MUL],
+    [.algebra.ark_bn254_fr_mul, { 9.. => "algebra.ark_bn254_fr_mul" }, 302 * MUL],
+    [.algebra.ark_bn254_fr_neg, { 9.. => "algebra.ark_bn254_fr_neg" }, 128 * MUL],
+    [.algebra.ark_bn254_fr_one, { 9.. => "algebra.ark_bn254_fr_one" }, 211 * MUL],
+    [.algebra.ark_bn254_fr_serialize, { 9.. => "algebra.ark_bn254_fr_serialize" }, 662 * MUL],
+    [.algebra.ark_bn254_fr_square, { 9.. => "algebra.ark_bn254_fr_square" }, 285 * MUL],
+    [.algebra.ark_bn254_fr_sub, { 9.. => "algebra.ark_bn254_fr_sub" }, 174 * MUL],
+    [.algebra.ark_bn254_fr_zero, { 9.. => "algebra.ark_bn254_fr_zero" }, 211 * MUL],
+    [.algebra.ark_bn254_g1_affine_deser_comp, { 9.. => "algebra.ark_bn254_g1_affine_deser_comp" }, 617_760 * MUL],
+    [.algebra.ark_bn254_g1_affine_deser_uncomp, { 9.. => "algebra.ark_bn254_g1_affine_deser_uncomp" }, 432_383 * MUL],
+    [.algebra.ark_bn254_g1_affine_serialize_comp, { 9.. => "algebra.ark_bn254_g1_affine_serialize_comp" }, 1_209 * MUL],
+    [.algebra.ark_bn254_g1_affine_serialize_uncomp, { 9.. => "algebra.ark_bn254_g1_affine_serialize_uncomp" }, 1_460 * MUL],
+    [.algebra.ark_bn254_g1_proj_add, { 9.. => "algebra.ark_bn254_g1_proj_add" }, 6_484 * MUL],
+    [.algebra.ark_bn254_g1_proj_double, { 9.. => "algebra.ark_bn254_g1_proj_double" }, 3_159 * MUL],
+    [.algebra.ark_bn254_g1_proj_eq, { 9.. => "algebra.ark_bn254_g1_proj_eq" }, 3_021 * MUL],
+    [.algebra.ark_bn254_g1_proj_generator, { 9.. => "algebra.ark_bn254_g1_proj_generator" }, 11 * MUL],
+    [.algebra.ark_bn254_g1_proj_infinity, { 9.. => "algebra.ark_bn254_g1_proj_infinity" }, 11 * MUL],
+    [.algebra.ark_bn254_g1_proj_neg, { 9.. => "algebra.ark_bn254_g1_proj_neg" }, 11 * MUL],
+    [.algebra.ark_bn254_g1_proj_scalar_mul, { 9.. => "algebra.ark_bn254_g1_proj_scalar_mul" }, 1_514_113 * MUL],
+    [.algebra.ark_bn254_g1_proj_sub, { 9.. => "algebra.ark_bn254_g1_proj_sub" }, 6_689 * MUL],
+    [.algebra.ark_bn254_g1_proj_to_affine, { 9.. => "algebra.ark_bn254_g1_proj_to_affine" }, 72_621 * MUL],
+    [.algebra.ark_bn254_g2_affine_deser_comp, { 9.. => "algebra.ark_bn254_g2_affine_deser_comp" }, 1_236_041 * MUL],
+    [.algebra.ark_bn254_g2_affine_deser_uncomp, { 9.. => "algebra.ark_bn254_g2_affine_deser_uncomp" }, 610_788 * MUL],
+    [.algebra.ark_bn254_g2_affine_serialize_comp, { 9.. => "algebra.ark_bn254_g2_affine_serialize_comp" }, 2_027 * MUL],
+    [.algebra.ark_bn254_g2_affine_serialize_uncomp, { 9.. => "algebra.ark_bn254_g2_affine_serialize_uncomp" }, 2_531 * MUL],
+    [.algebra.ark_bn254_g2_proj_add, { 9.. => "algebra.ark_bn254_g2_proj_add" }, 19_441 * MUL],
+    [.algebra.ark_bn254_g2_proj_double, { 9.. => "algebra.ark_bn254_g2_proj_double" }, 8_904 * MUL],
+    [.algebra.ark_bn254_g2_proj_eq, { 9.. => "algebra.ark_bn254_g2_proj_eq" }, 9_093 * MUL],
+    [.algebra.ark_bn254_g2_proj_generator, { 9.. => "algebra.ark_bn254_g2_proj_generator" }, 11 * MUL],
+    [.algebra.ark_bn254_g2_proj_infinity, { 9.. => "algebra.ark_bn254_g2_proj_infinity" }, 11 * MUL],
+    [.algebra.ark_bn254_g2_proj_neg, { 9.. => "algebra.ark_bn254_g2_proj_neg" }, 11 * MUL],
+    [.algebra.ark_bn254_g2_proj_scalar_mul, { 9.. => "algebra.ark_bn254_g2_proj_scalar_mul" }, 4_515_905 * MUL],
+    [.algebra.ark_bn254_g2_proj_sub, { 9.. => "algebra.ark_bn254_g2_proj_sub" }, 19_722 * MUL],
+    [.algebra.ark_bn254_g2_proj_to_affine, { 9.. => "algebra.ark_bn254_g2_proj_to_affine" }, 77_315 * MUL],
+    [.algebra.ark_bn254_multi_pairing_base, { 9.. => "algebra.ark_bn254_multi_pairing_base" }, 5_399_190 * MUL],
+    [.algebra.ark_bn254_multi_pairing_per_pair, { 9.. => "algebra.ark_bn254_multi_pairing_per_pair" }, 2_761_586 * MUL],
+    [.algebra.ark_bn254_pairing, { 9.. => "algebra.ark_bn254_pairing" }, 8_899_332 * MUL],
+    // The hash-to-curve parameters are regenerated from the `ark_bn254/hash_to_g1_proj` bench by `scripts/algebra-gas/update_algebra_gas_params.py`.
+    // Until then, the base is the operation count of `Bn254G1SvdwMap` priced with the parameters above (per map in the worst case: an inversion,
+    // 2 Legendre symbols and a square root, each a 254-bit exponentiation), and the per-byte cost is that of the SHA2-256 `expand_message_xmd` shared with BLS12-381.
+    [.algebra.ark_h2c_bn254g1_xmd_sha256_svdw_base, { 9.. => "algebra.ark_h2c_bn254g1_xmd_sha256_svdw_base" }, 1_077_529 * MUL],
+    [.algebra.ark_h2c_bn254g1_xmd_sha256_svdw_per_msg_byte, { 9.. => "algebra.ark_h2c_bn254g1_xmd_sha256_svdw_per_msg_byte" }, 48 * MUL],
+    // BN254 algebra gas parameters end.
+
     [.bls12381.base, "bls12381.base", 150 * MUL],
 
     [.bls12381.per_pubkey_deserialize, "bls12381.per_pubkey_deserialize", 109_000 * MUL],
@@ -159,6 +238,20 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.hash.ripemd160.per_byte, { 4.. => "hash.ripemd160.per_byte" }, 50],
     [.hash.blake2b_256.base, { 6.. => "hash.blake2b_256.base" }, 1750],
     [.hash.blake2b_256.per_byte, { 6.. => "hash.blake2b_256.per_byte" }, 15],
+    // Derived from the BN254 and BLS12-381 scalar field costs of the algebra natives: the base cost
+    // covers serializing the output, the per-input cost covers deserializing an input, and the
+    // per-multiplication cost is a third of `fr_mul`, which also covers the overhead of a native call.
+    // The per-parameter cost covers sampling a round constant or MDS matrix entry from the Grain LFSR,
+    // which takes about a thousand LFSR clocks, plus a share of the MDS matrix inversions. It is
+    // charged on every call, since whether the parameters are already cached differs across nodes.
+    [.hash.poseidon_bn254.base, { 10.. => "hash.poseidon_bn254.base" }, 662 * MUL],
+    [.hash.poseidon_bn254.per_input, { 10.. => "hash.poseidon_bn254.per_input" }, 452 * MUL],
+    [.hash.poseidon_bn254.per_parameter, { 10.. => "hash.poseidon_bn254.per_parameter" }, 3_020 * MUL],
+    [.hash.poseidon_bn254.per_field_mul, { 10.. => "hash.poseidon_bn254.per_field_mul" }, 100 * MUL],
+    [.hash.poseidon_bls12381.base, { 10.. => "hash.poseidon_bls12381.base" }, 1_103 * MUL],
+    [.hash.poseidon_bls12381.per_input, { 10.. => "hash.poseidon_bls12381.per_input" }, 752 * MUL],
+    [.hash.poseidon_bls12381.per_parameter, { 10.. => "hash.poseidon_bls12381.per_parameter" }, 5_020 * MUL],
+    [.hash.poseidon_bls12381.per_field_mul, { 10.. => "hash.poseidon_bls12381.per_field_mul" }, 167 * MUL],
 
     [.util.from_bytes.base, "util.from_bytes.base", 300 * MUL],
     [.util.from_bytes.per_byte, "util.from_bytes.per_byte", 5 * MUL],
@@ -179,6 +272,9 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.aggregator.read.base, "aggregator.read.base", 300 * MUL],
     [.aggregator.sub.base, "aggregator.sub.base", 300 * MUL],
     [.aggregator.destroy.base, "aggregator.destroy.base", 500 * MUL],
+    [.aggregator.snapshot.base, { 11.. => "aggregator.snapshot.base" }, 500 * MUL],
+    [.aggregator.read_snapshot.base, { 11.. => "aggregator.read_snapshot.base" }, 300 * MUL],
+    [.aggregator.destroy_snapshot.base, { 11.. => "aggregator.destroy_snapshot.base" }, 500 * MUL],
     [.aggregator_factory.new_aggregator.base, "aggregator_factory.new_aggregator.base", 500 * MUL],
 
     [.object.exists_at.base, { 7.. => "object.exists_at.base" }, 250 * MUL],
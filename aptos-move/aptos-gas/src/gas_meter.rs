@@ -33,6 +33,12 @@ use move_vm_types::{
 use std::collections::BTreeMap;
 
 // Change log:
+// - V11
+//   - Added aggregator snapshot natives.
+// - V10
+//   - Added the Poseidon hash natives.
+// - V9
+//   - Added BN254 operations.
 // - V8
 //   - Added BLS12-381 operations.
 // - V7
@@ -59,7 +65,7 @@ use std::collections::BTreeMap;
 //       global operations.
 // - V1
 //   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = 8;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = 11;
 
 pub(crate) const EXECUTION_GAS_MULTIPLIER: u64 = 20;
 
@@ -414,6 +420,21 @@ impl StandardGasMeter {
     pub fn feature_version(&self) -> u64 {
         self.feature_version
     }
+
+    /// Returns the amount of gas charged for execution so far, in internal gas units.
+    pub fn execution_gas_used(&self) -> InternalGas {
+        self.execution_gas_used
+    }
+
+    /// Returns the amount of gas charged for IO so far, in internal gas units.
+    pub fn io_gas_used(&self) -> InternalGas {
+        self.io_gas_used
+    }
+
+    /// Returns the storage fee charged so far, in Octas.
+    pub fn storage_fee_used(&self) -> Fee {
+        self.storage_fee_used
+    }
 }
 
 impl MoveGasMeter for StandardGasMeter {
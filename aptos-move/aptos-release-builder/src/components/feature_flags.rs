@@ -34,6 +34,11 @@ pub enum FeatureFlag {
     StructConstructors,
     PeriodicalRewardRateReduction,
     PartialGovernanceVoting,
+    Secp256k1EcdsaAuthenticator,
+    WebAuthnAuthenticator,
+    Bn254Structures,
+    PoseidonHashNatives,
+    AggregatorApi,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -146,6 +151,13 @@ impl From<FeatureFlag> for AptosFeatureFlag {
                 AptosFeatureFlag::PERIODICAL_REWARD_RATE_DECREASE
             },
             FeatureFlag::PartialGovernanceVoting => AptosFeatureFlag::PARTIAL_GOVERNANCE_VOTING,
+            FeatureFlag::Secp256k1EcdsaAuthenticator => {
+                AptosFeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR
+            },
+            FeatureFlag::WebAuthnAuthenticator => AptosFeatureFlag::WEBAUTHN_AUTHENTICATOR,
+            FeatureFlag::Bn254Structures => AptosFeatureFlag::BN254_STRUCTURES,
+            FeatureFlag::PoseidonHashNatives => AptosFeatureFlag::POSEIDON_HASH_NATIVES,
+            FeatureFlag::AggregatorApi => AptosFeatureFlag::AGGREGATOR_API,
         }
     }
 }
@@ -183,6 +195,13 @@ impl From<AptosFeatureFlag> for FeatureFlag {
                 FeatureFlag::PeriodicalRewardRateReduction
             },
             AptosFeatureFlag::PARTIAL_GOVERNANCE_VOTING => FeatureFlag::PartialGovernanceVoting,
+            AptosFeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR => {
+                FeatureFlag::Secp256k1EcdsaAuthenticator
+            },
+            AptosFeatureFlag::WEBAUTHN_AUTHENTICATOR => FeatureFlag::WebAuthnAuthenticator,
+            AptosFeatureFlag::BN254_STRUCTURES => FeatureFlag::Bn254Structures,
+            AptosFeatureFlag::POSEIDON_HASH_NATIVES => FeatureFlag::PoseidonHashNatives,
+            AptosFeatureFlag::AGGREGATOR_API => FeatureFlag::AggregatorApi,
         }
     }
 }
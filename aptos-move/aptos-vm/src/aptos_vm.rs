@@ -13,6 +13,7 @@ use crate::{
     delta_state_view::DeltaStateView,
     errors::expect_only_successful_execution,
     move_vm_ext::{MoveResolverExt, SessionExt, SessionId},
+    pre_execution_cache::PreExecutionCache,
     system_module_names::*,
     transaction_metadata::TransactionMetadata,
     verifier, VMExecutor, VMValidator,
@@ -34,12 +35,15 @@ use aptos_types::{
     account_config,
     account_config::new_block_event_key,
     block_metadata::BlockMetadata,
-    on_chain_config::{new_epoch_event_key, FeatureFlag, TimedFeatureOverride},
+    on_chain_config::{
+        new_epoch_event_key, FeatureFlag, OnChainConfig, OnChainExecutionConfig,
+        TimedFeatureOverride,
+    },
     transaction::{
-        ChangeSet, EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
-        MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction, Transaction,
-        TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
-        WriteSetPayload,
+        authenticator::AccountAuthenticator, ChangeSet, EntryFunction, ExecutionError,
+        ExecutionStatus, ModuleBundle, Multisig, MultisigTransactionPayload,
+        SignatureCheckedTransaction, SignedTransaction, Transaction, TransactionOutput,
+        TransactionPayload, TransactionStatus, VMValidatorResult, WriteSetPayload,
     },
     vm_status::{AbortLocation, DiscardedVMStatus, StatusCode, VMStatus},
     write_set::WriteSet,
@@ -79,6 +83,8 @@ static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
 static PARANOID_TYPE_CHECKS: OnceCell<bool> = OnceCell::new();
 static PROCESSED_TRANSACTIONS_DETAILED_COUNTERS: OnceCell<bool> = OnceCell::new();
+static BLOCK_EXECUTION_REPORTS: OnceCell<bool> = OnceCell::new();
+static PRE_EXECUTION_CACHE: OnceCell<PreExecutionCache> = OnceCell::new();
 static TIMED_FEATURE_OVERRIDE: OnceCell<TimedFeatureOverride> = OnceCell::new();
 
 /// Remove this once the bundle is removed from the code.
@@ -186,6 +192,43 @@ impl AptosVM {
         }
     }
 
+    /// Enables logging a report of the parallel execution of every block (i.e., the number of
+    /// incarnations of every transaction, the conflicting keys and the dependency waits), when
+    /// invoked the first time.
+    pub fn set_block_execution_reports_once() {
+        // Only the first call succeeds, due to OnceCell semantics.
+        BLOCK_EXECUTION_REPORTS.set(true).ok();
+    }
+
+    /// Get whether we should log a report of the parallel execution of every block
+    pub fn get_block_execution_reports() -> bool {
+        match BLOCK_EXECUTION_REPORTS.get() {
+            Some(value) => *value,
+            None => false,
+        }
+    }
+
+    /// Enables the reuse of transaction outputs executed ahead of time (e.g. by mempool) during
+    /// block execution, with a cache of the given capacity, when invoked the first time.
+    pub fn set_pre_execution_cache_once(capacity: usize) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        PRE_EXECUTION_CACHE
+            .set(PreExecutionCache::new(capacity))
+            .ok();
+    }
+
+    /// Get the cache of pre-executed transaction outputs, if enabled.
+    pub fn get_pre_execution_cache() -> Option<&'static PreExecutionCache> {
+        PRE_EXECUTION_CACHE.get()
+    }
+
+    /// Returns the block gas limit from the on-chain execution config, if one is set.
+    pub fn fetch_block_gas_limit(state_view: &impl StateView) -> Option<u64> {
+        OnChainExecutionConfig::fetch_config(&StorageAdapter::new(state_view))
+            .unwrap_or_default()
+            .block_gas_limit()
+    }
+
     pub fn internals(&self) -> AptosVMInternals {
         AptosVMInternals::new(&self.0)
     }
@@ -1483,8 +1526,26 @@ impl VMExecutor for AptosVM {
         );
 
         let count = transactions.len();
-        let ret =
-            BlockAptosVM::execute_block(transactions, state_view, Self::get_concurrency_level());
+        let maybe_block_gas_limit = Self::fetch_block_gas_limit(state_view);
+        let ret = if Self::get_block_execution_reports() {
+            let (ret, report) = BlockAptosVM::execute_block_with_report(
+                transactions,
+                state_view,
+                Self::get_concurrency_level(),
+                maybe_block_gas_limit,
+            );
+            if let Some(report) = report {
+                info!(log_context, "Block execution report:\n{}", report);
+            }
+            ret
+        } else {
+            BlockAptosVM::execute_block(
+                transactions,
+                state_view,
+                Self::get_concurrency_level(),
+                maybe_block_gas_limit,
+            )
+        };
         if ret.is_ok() {
             // Record the histogram count for transactions per block.
             BLOCK_TRANSACTION_COUNT.observe(count as f64);
@@ -1572,6 +1633,27 @@ impl VMAdapter for AptosVM {
             ));
         }
 
+        // Only accept the newer signature schemes once they have been enabled on chain
+        let authenticator = txn.authenticator();
+        let features = self.0.get_features();
+        for account_authenticator in std::iter::once(authenticator.sender())
+            .chain(authenticator.secondary_signers().into_iter())
+        {
+            let is_enabled = match account_authenticator {
+                AccountAuthenticator::Ed25519 { .. }
+                | AccountAuthenticator::MultiEd25519 { .. } => true,
+                AccountAuthenticator::Secp256k1Ecdsa { .. } => {
+                    features.is_enabled(FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR)
+                },
+                AccountAuthenticator::WebAuthn { .. } => {
+                    features.is_enabled(FeatureFlag::WEBAUTHN_AUTHENTICATOR)
+                },
+            };
+            if !is_enabled {
+                return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING, None));
+            }
+        }
+
         Ok(())
     }
 
@@ -14,6 +14,7 @@ use crate::{
     AptosVM,
 };
 use aptos_aggregator::{delta_change_set::DeltaOp, transaction::TransactionOutputExt};
+pub use aptos_block_executor::stats::BlockExecutionReport;
 use aptos_block_executor::{
     errors::Error,
     executor::{BlockExecutor, RAYON_EXEC_POOL},
@@ -72,6 +73,19 @@ impl BlockExecutorTransactionOutput for AptosTransactionOutput {
             .collect()
     }
 
+    fn get_snapshots(&self) -> Vec<(StateKey, StateKey, DeltaOp)> {
+        self.0
+            .delta_change_set()
+            .snapshots()
+            .iter()
+            .map(|(key, (source, op))| (key.clone(), source.clone(), *op))
+            .collect()
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.0.txn_output().gas_used()
+    }
+
     /// Execution output for transactions that comes after SkipRest signal.
     fn skip_output() -> Self {
         Self(TransactionOutputExt::from(TransactionOutput::new(
@@ -90,7 +104,48 @@ impl BlockAptosVM {
         transactions: Vec<Transaction>,
         state_view: &S,
         concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Self::execute_block_impl(
+            transactions,
+            state_view,
+            concurrency_level,
+            maybe_block_gas_limit,
+            false,
+        )
+        .0
+    }
+
+    /// Same as `execute_block`, but also returns the statistics and conflicts of the parallel
+    /// execution of the block (if it was executed in parallel, i.e., concurrency_level > 1).
+    pub fn execute_block_with_report<S: StateView + Sync>(
+        transactions: Vec<Transaction>,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> (
+        Result<Vec<TransactionOutput>, VMStatus>,
+        Option<BlockExecutionReport<StateKey>>,
+    ) {
+        Self::execute_block_impl(
+            transactions,
+            state_view,
+            concurrency_level,
+            maybe_block_gas_limit,
+            true,
+        )
+    }
+
+    fn execute_block_impl<S: StateView + Sync>(
+        transactions: Vec<Transaction>,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+        collect_report: bool,
+    ) -> (
+        Result<Vec<TransactionOutput>, VMStatus>,
+        Option<BlockExecutionReport<StateKey>>,
+    ) {
         let _timer = BLOCK_EXECUTOR_EXECUTE_BLOCK_SECONDS.start_timer();
         // Verify the signatures of all the transactions in parallel.
         // This is time consuming so don't wait and do the checking
@@ -112,34 +167,42 @@ impl BlockAptosVM {
         BLOCK_EXECUTOR_CONCURRENCY.set(concurrency_level as i64);
         let executor = BlockExecutor::<PreprocessedTransaction, AptosExecutorTask<S>, S>::new(
             concurrency_level,
+            maybe_block_gas_limit,
         );
 
-        let ret = executor
-            .execute_block(state_view, signature_verified_block, state_view)
-            .map(|results| {
-                // Process the outputs in parallel, combining delta writes with other writes.
-                // TODO: merge with rolling commit_hook (via trait) inside parallel executor.
-                RAYON_EXEC_POOL.install(|| {
-                    results
-                        .into_par_iter()
-                        .map(|(output, delta_writes)| {
-                            output      // AptosTransactionOutput
-                            .into()     // TransactionOutputExt
-                            .output_with_delta_writes(WriteSetMut::new(delta_writes))
-                        })
-                        .collect()
-                })
-            });
+        let (ret, report) = if collect_report {
+            executor.execute_block_with_report(state_view, signature_verified_block, state_view)
+        } else {
+            (
+                executor.execute_block(state_view, signature_verified_block, state_view),
+                None,
+            )
+        };
+        let ret = ret.map(|results| {
+            // Process the outputs in parallel, combining delta writes with other writes.
+            // TODO: merge with rolling commit_hook (via trait) inside parallel executor.
+            RAYON_EXEC_POOL.install(|| {
+                results
+                    .into_par_iter()
+                    .map(|(output, delta_writes)| {
+                        output      // AptosTransactionOutput
+                        .into()     // TransactionOutputExt
+                        .output_with_delta_writes(WriteSetMut::new(delta_writes))
+                    })
+                    .collect()
+            })
+        });
 
         flush_speculative_logs();
 
-        match ret {
+        let ret = match ret {
             Ok(outputs) => Ok(outputs),
-            Err(Error::ModulePathReadWrite) => {
+            Err(Error::ModulePathReadWrite) | Err(Error::SnapshotResolutionFailure) => {
                 unreachable!("[Execution]: Must be handled by sequential fallback")
             },
             Err(Error::UserError(err)) => Err(err),
-        }
+        };
+        (ret, report)
     }
 
     pub fn execute_block_benchmark<S: StateView + Sync>(
@@ -174,6 +237,7 @@ impl BlockAptosVM {
         BLOCK_EXECUTOR_CONCURRENCY.set(concurrency_level as i64);
         let executor = BlockExecutor::<PreprocessedTransaction, AptosExecutorTask<S>, S>::new(
             concurrency_level,
+            None,
         );
         println!("Parallel execution starts...");
         let timer = Instant::now();
@@ -188,7 +252,7 @@ impl BlockAptosVM {
 
         // sequentially execute the block and check if the results match
         let seq_executor =
-            BlockExecutor::<PreprocessedTransaction, AptosExecutorTask<S>, S>::new(1);
+            BlockExecutor::<PreprocessedTransaction, AptosExecutorTask<S>, S>::new(1, None);
         println!("Sequential execution starts...");
         let seq_timer = Instant::now();
         let seq_ret =
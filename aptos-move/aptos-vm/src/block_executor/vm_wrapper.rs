@@ -13,6 +13,7 @@ use aptos_block_executor::task::{ExecutionStatus, ExecutorTask};
 use aptos_logger::{enabled, Level};
 use aptos_mvhashmap::types::TxnIndex;
 use aptos_state_view::StateView;
+use aptos_types::transaction::SignedTransaction;
 use aptos_vm_logging::{log_schema::AdapterLogSchema, prelude::*};
 use move_core_types::{
     ident_str,
@@ -65,10 +66,13 @@ impl<'a, S: 'a + StateView + Sync> ExecutorTask for AptosExecutorTask<'a, S> {
     ) -> ExecutionStatus<AptosTransactionOutput, VMStatus> {
         let log_context = AdapterLogSchema::new(self.base_view.id(), txn_idx as usize);
 
-        match self
-            .vm
-            .execute_single_transaction(txn, &view.as_move_resolver(), &log_context)
-        {
+        let execution_result = match Self::get_pre_executed_output(view, txn) {
+            Some(output_ext) => Ok((VMStatus::Executed, output_ext, None)),
+            None => self
+                .vm
+                .execute_single_transaction(txn, &view.as_move_resolver(), &log_context),
+        };
+        match execution_result {
             Ok((vm_status, mut output_ext, sender)) => {
                 if materialize_deltas {
                     // Keep TransactionOutputExt type for wrapper.
@@ -109,3 +113,21 @@ impl<'a, S: 'a + StateView + Sync> ExecutorTask for AptosExecutorTask<'a, S> {
         }
     }
 }
+
+impl<'a, S: 'a + StateView + Sync> AptosExecutorTask<'a, S> {
+    /// Returns the output of a user transaction executed ahead of time, if it is cached and
+    /// every value read by its execution is unchanged in the view.
+    fn get_pre_executed_output(
+        view: &impl StateView,
+        txn: &PreprocessedTransaction,
+    ) -> Option<TransactionOutputExt> {
+        let cache = AptosVM::get_pre_execution_cache()?;
+        match txn {
+            PreprocessedTransaction::UserTransaction(user_txn) => {
+                let txn_hash = SignedTransaction::clone(user_txn).committed_hash();
+                cache.get_valid_output(&txn_hash, view)
+            },
+            _ => None,
+        }
+    }
+}
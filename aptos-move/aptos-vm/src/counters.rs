@@ -105,3 +105,23 @@ pub static TXN_VALIDATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
 pub static TXN_GAS_USAGE: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!("aptos_vm_txn_gas_usage", "Gas used per transaction").unwrap()
 });
+
+/// Count the number of lookups of pre-executed transaction outputs by block execution, with a
+/// "result" label to distinguish reused ("hit"), missing ("miss") and invalidated ("stale") outputs.
+pub static PRE_EXECUTION_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_vm_pre_execution_cache_lookups",
+        "Number of lookups of pre-executed transaction outputs",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// The number of cached pre-executed transaction outputs.
+pub static PRE_EXECUTION_CACHE_SIZE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_vm_pre_execution_cache_size",
+        "Number of cached pre-executed transaction outputs"
+    )
+    .unwrap()
+});
@@ -117,9 +117,12 @@ mod delta_state_view;
 mod errors;
 pub mod move_vm_ext;
 pub mod natives;
+pub mod pre_execution_cache;
 pub mod read_write_set_analysis;
 pub mod system_module_names;
 pub mod transaction_metadata;
+#[cfg(feature = "testing")]
+pub mod unit_test_gas;
 mod verifier;
 
 pub use crate::aptos_vm::AptosVM;
@@ -313,6 +313,13 @@ where
                     write_set_mut.insert((state_key, write_op));
                 },
                 AggregatorChange::Merge(delta_op) => delta_change_set.insert((state_key, delta_op)),
+                AggregatorChange::Snapshot(source_id, delta_op) => {
+                    let source_key = StateKey::table_item(
+                        TableHandle::from(source_id.handle),
+                        source_id.key.0.to_vec(),
+                    );
+                    delta_change_set.insert_snapshot((state_key, source_key, delta_op));
+                },
                 AggregatorChange::Delete => {
                     let write_op = WriteOp::Deletion;
                     write_set_mut.insert((state_key, write_op));
@@ -0,0 +1,358 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A cache of the outputs of user transactions executed ahead of time (i.e., while they wait in
+//! mempool) against the latest committed state. Block execution reuses a cached output instead
+//! of executing the transaction, but only if every value read by the ahead of time execution is
+//! unchanged in the state the transaction is executed against. As the execution is
+//! deterministic, the output is then the same as if the transaction was executed again.
+//!
+//! The only exception is the current time, which changes with every block: if it was only read by
+//! the prologue, it is enough for the transaction to still be unexpired.
+
+use crate::{
+    adapter_common::{PreprocessedTransaction, VMAdapter},
+    aptos_vm::AptosVM,
+    counters::{PRE_EXECUTION_CACHE_LOOKUPS, PRE_EXECUTION_CACHE_SIZE},
+    data_cache::AsMoveResolver,
+};
+use anyhow::Result;
+use aptos_aggregator::transaction::TransactionOutputExt;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_state_view::{StateViewId, TStateView};
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::CORE_CODE_ADDRESS,
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+    },
+    timestamp::TimestampResource,
+    transaction::{SignedTransaction, TransactionStatus, Version},
+};
+use aptos_vm_logging::log_schema::AdapterLogSchema;
+use move_core_types::move_resource::MoveResource;
+use once_cell::sync::Lazy;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    sync::Arc,
+};
+
+/// The key of the current time, which changes with every block.
+static TIMESTAMP_STATE_KEY: Lazy<StateKey> = Lazy::new(|| {
+    StateKey::access_path(AccessPath::new(
+        CORE_CODE_ADDRESS,
+        TimestampResource::resource_path(),
+    ))
+});
+
+/// The output of a user transaction executed ahead of time, along with the values it read.
+pub struct PreExecutedOutput {
+    /// The version of the state the transaction was executed against.
+    version: Option<Version>,
+    /// The values read by the execution, once per key.
+    reads: Vec<(StateKey, Option<StateValue>)>,
+    /// Set if the current time was only read to check that the transaction has not expired, in
+    /// which case it is not part of `reads`: the output is valid as long as the transaction has
+    /// not expired.
+    expiration_timestamp_secs: Option<u64>,
+    output: TransactionOutputExt,
+}
+
+impl PreExecutedOutput {
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    pub fn num_reads(&self) -> usize {
+        self.reads.len()
+    }
+
+    pub fn output(&self) -> &TransactionOutputExt {
+        &self.output
+    }
+
+    /// Returns true if the current time in `state_view` is still before the expiration time of
+    /// the transaction, if the output only depends on that.
+    fn is_unexpired(&self, state_view: &impl TStateView<Key = StateKey>) -> bool {
+        let expiration_timestamp_secs = match self.expiration_timestamp_secs {
+            Some(expiration_timestamp_secs) => expiration_timestamp_secs,
+            None => return true,
+        };
+        match state_view.get_state_value(&TIMESTAMP_STATE_KEY) {
+            Ok(Some(value)) => bcs::from_bytes::<TimestampResource>(value.bytes())
+                .map_or(false, |resource| {
+                    resource.timestamp.microseconds / 1_000_000 < expiration_timestamp_secs
+                }),
+            _ => false,
+        }
+    }
+}
+
+struct PreExecutionCacheInner {
+    outputs: HashMap<HashValue, Arc<PreExecutedOutput>>,
+    // The transaction hashes in insertion order, used to evict the oldest outputs.
+    insertion_order: VecDeque<HashValue>,
+}
+
+/// Pre-executed transaction outputs, keyed by the (committed) transaction hash. When full, the
+/// oldest outputs are evicted.
+pub struct PreExecutionCache {
+    capacity: usize,
+    inner: Mutex<PreExecutionCacheInner>,
+}
+
+impl PreExecutionCache {
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "Pre-execution cache capacity must be positive"
+        );
+        Self {
+            capacity,
+            inner: Mutex::new(PreExecutionCacheInner {
+                outputs: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().outputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if an output of the transaction executed against the state at `version` is
+    /// cached.
+    pub fn contains(&self, txn_hash: &HashValue, version: Option<Version>) -> bool {
+        self.inner
+            .lock()
+            .outputs
+            .get(txn_hash)
+            .map_or(false, |cached| cached.version == version)
+    }
+
+    /// Caches the output of the transaction, replacing any previously cached one.
+    pub fn insert(&self, txn_hash: HashValue, output: PreExecutedOutput) {
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        match inner.outputs.entry(txn_hash) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(Arc::new(output));
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(Arc::new(output));
+                inner.insertion_order.push_back(txn_hash);
+                // Every cached output has an entry in insertion_order, so bounding its length
+                // also bounds the number of cached outputs.
+                while inner.insertion_order.len() > self.capacity {
+                    if let Some(evicted) = inner.insertion_order.pop_front() {
+                        inner.outputs.remove(&evicted);
+                    }
+                }
+            },
+        }
+        PRE_EXECUTION_CACHE_SIZE.set(inner.outputs.len() as i64);
+    }
+
+    /// Returns the cached output of the transaction if every value read by its execution is
+    /// unchanged in `state_view`. The values are read through `state_view`, so that they are
+    /// recorded (and later validated by parallel execution) as if the transaction was executed.
+    pub fn get_valid_output(
+        &self,
+        txn_hash: &HashValue,
+        state_view: &impl TStateView<Key = StateKey>,
+    ) -> Option<TransactionOutputExt> {
+        // Do not hold the lock while reading, as reads may wait on other transactions.
+        let cached = self.inner.lock().outputs.get(txn_hash).cloned();
+        let cached = match cached {
+            Some(cached) => cached,
+            None => {
+                PRE_EXECUTION_CACHE_LOOKUPS
+                    .with_label_values(&["miss"])
+                    .inc();
+                return None;
+            },
+        };
+
+        let is_valid = cached.reads.iter().all(|(state_key, value)| {
+            match state_view.get_state_value(state_key) {
+                Ok(current) => current == *value,
+                // Never reuse the output if the read set might have changed.
+                Err(_) => false,
+            }
+        }) && cached.is_unexpired(state_view);
+        if !is_valid {
+            PRE_EXECUTION_CACHE_LOOKUPS
+                .with_label_values(&["stale"])
+                .inc();
+            return None;
+        }
+
+        PRE_EXECUTION_CACHE_LOOKUPS
+            .with_label_values(&["hit"])
+            .inc();
+        Some(cached.output.clone())
+    }
+}
+
+/// Executes user transactions ahead of time against the state at a given version, recording the
+/// values they read. As in block execution, a single VM executes all the transactions.
+pub struct PreExecutor<'a, S> {
+    state_view: &'a S,
+    version: Option<Version>,
+    vm: AptosVM,
+    // The values every output depends on, besides the ones read by its own execution: the
+    // on-chain configs read when creating the VM, and the code of the modules loaded by previous
+    // executions, which the VM caches. None if the VM creation read the storage usage.
+    shared_reads: RefCell<Option<HashMap<StateKey, Option<StateValue>>>>,
+}
+
+impl<'a, S: TStateView<Key = StateKey>> PreExecutor<'a, S> {
+    /// Creates an executor for the state at `version`, which `state_view` must be a view of.
+    pub fn new(state_view: &'a S, version: Option<Version>) -> Self {
+        let view = ReadRecordingView::new(state_view);
+        let vm = AptosVM::new(&view);
+        Self {
+            state_view,
+            version,
+            vm,
+            shared_reads: RefCell::new(view.into_reads()),
+        }
+    }
+
+    /// Executes the user transaction. Returns None if the output must not be cached, i.e. if the
+    /// transaction is discarded or triggers a reconfiguration, or if its execution depends on
+    /// more than the values of the keys it read.
+    pub fn execute(&self, txn: SignedTransaction) -> Option<PreExecutedOutput> {
+        let expiration_timestamp_secs = txn.expiration_timestamp_secs();
+        let txn = txn.check_signature().ok()?;
+        let view = ReadRecordingView::new(self.state_view);
+        let log_context = AdapterLogSchema::new(self.state_view.id(), 0);
+
+        let execution_result = self.vm.execute_single_transaction(
+            &PreprocessedTransaction::UserTransaction(txn),
+            &view.as_move_resolver(),
+            &log_context,
+        );
+        let num_timestamp_reads = view.num_timestamp_reads.get();
+        let reads = view.into_reads();
+
+        let mut shared_reads = self.shared_reads.borrow_mut();
+        let mut reads = match reads {
+            Some(reads) if shared_reads.is_some() => reads,
+            _ => {
+                // The modules loaded by this execution cannot be recorded, so no later output is
+                // valid either.
+                *shared_reads = None;
+                return None;
+            },
+        };
+        let shared_reads = shared_reads.as_mut()?;
+        // The VM caches the loaded modules, so later executions depend on them too.
+        shared_reads.extend(
+            reads
+                .iter()
+                .filter(|(state_key, _)| is_code(state_key))
+                .map(|(state_key, value)| (state_key.clone(), value.clone())),
+        );
+
+        let (_, output, _) = execution_result.ok()?;
+        if !matches!(output.txn_output().status(), TransactionStatus::Keep(_))
+            || AptosVM::should_restart_execution(output.txn_output())
+        {
+            return None;
+        }
+
+        // The prologue reads the current time once, to check that the transaction has not
+        // expired. The payload runs in a new session, so it reads the current time again if it
+        // uses it, in which case the output depends on its exact value.
+        let expiration_timestamp_secs = if num_timestamp_reads == 1 {
+            reads.remove(&*TIMESTAMP_STATE_KEY);
+            Some(expiration_timestamp_secs)
+        } else {
+            None
+        };
+        for (state_key, value) in shared_reads.iter() {
+            reads
+                .entry(state_key.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        Some(PreExecutedOutput {
+            version: self.version,
+            reads: reads.into_iter().collect(),
+            expiration_timestamp_secs,
+            output,
+        })
+    }
+}
+
+fn is_code(state_key: &StateKey) -> bool {
+    matches!(state_key.inner(), StateKeyInner::AccessPath(path) if path.is_code())
+}
+
+/// A state view that records the first value read for every key.
+struct ReadRecordingView<'a, S> {
+    base_view: &'a S,
+    reads: RefCell<HashMap<StateKey, Option<StateValue>>>,
+    // The number of times the current time was read.
+    num_timestamp_reads: Cell<usize>,
+    // Whether the storage usage was read, which is not validated by the cache.
+    usage_read: Cell<bool>,
+}
+
+impl<'a, S: TStateView<Key = StateKey>> ReadRecordingView<'a, S> {
+    fn new(base_view: &'a S) -> Self {
+        Self {
+            base_view,
+            reads: RefCell::new(HashMap::new()),
+            num_timestamp_reads: Cell::new(0),
+            usage_read: Cell::new(false),
+        }
+    }
+
+    /// Returns the recorded reads, or None if the execution read the storage usage.
+    fn into_reads(self) -> Option<HashMap<StateKey, Option<StateValue>>> {
+        if self.usage_read.get() {
+            return None;
+        }
+        Some(self.reads.into_inner())
+    }
+}
+
+impl<'a, S: TStateView<Key = StateKey>> TStateView for ReadRecordingView<'a, S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.base_view.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        let value = self.base_view.get_state_value(state_key)?;
+        if state_key == &*TIMESTAMP_STATE_KEY {
+            self.num_timestamp_reads
+                .set(self.num_timestamp_reads.get() + 1);
+        }
+        self.reads
+            .borrow_mut()
+            .entry(state_key.clone())
+            .or_insert_with(|| value.clone());
+        Ok(value)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base_view.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.usage_read.set(true);
+        self.base_view.get_usage()
+    }
+}
@@ -0,0 +1,387 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the cost of Move unit tests under the Aptos gas schedule.
+//!
+//! The unit test runner bounds tests with an instruction-counting cost table and runs natives
+//! with zero gas parameters. The profiler in this module is installed into the runner and
+//! re-executes every test with the `StandardGasMeter` and the given gas parameters, charging IO
+//! gas and storage fees for the resulting write set as if it was committed by a transaction.
+
+use crate::{access_path_cache::AccessPathCache, natives};
+use aptos_aggregator::delta_change_set::serialize;
+use aptos_framework::natives::aggregator_natives::{AggregatorChange, NativeAggregatorContext};
+use aptos_gas::{
+    AptosGasMeter, AptosGasParameters, NumBytes, StandardGasMeter, StorageGasParameters,
+};
+use aptos_infallible::Mutex;
+use aptos_types::{
+    contract_event::ContractEvent,
+    on_chain_config::{Features, TimedFeatures},
+    state_store::{state_key::StateKey, table::TableHandle},
+    write_set::WriteOp,
+};
+use move_binary_format::errors::{Location, PartialVMError, VMResult};
+use move_core_types::{
+    effects::{ChangeSet as MoveChangeSet, Event as MoveEvent, Op as MoveStorageOp},
+    identifier::IdentStr,
+    language_storage::ModuleId,
+    vm_status::{StatusCode, StatusType},
+};
+use move_table_extension::NativeTableContext;
+use move_unit_test::gas_profiler::TestGasProfiler;
+use move_vm_runtime::{
+    move_vm::MoveVM, native_extensions::NativeContextExtensions,
+    native_functions::NativeFunctionTable,
+};
+use move_vm_test_utils::InMemoryStorage;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Gas charged for a single unit test.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TestGasUsage {
+    /// Execution gas, in gas units.
+    pub execution_gas: u64,
+    /// IO gas for the test's write set, in gas units.
+    pub io_gas: u64,
+    /// Storage fee for the test's write set and events, in Octas.
+    pub storage_fee: u64,
+    /// Whether the test ran to completion. Tests which abort or run out of gas are only charged
+    /// for execution.
+    pub completed: bool,
+}
+
+/// A `TestGasProfiler` recording the gas usage of every test, keyed by
+/// `<address>::<module>::<function>`.
+pub struct UnitTestGasProfiler {
+    gas_feature_version: u64,
+    gas_params: AptosGasParameters,
+    storage_gas_params: StorageGasParameters,
+    natives: NativeFunctionTable,
+    usages: Mutex<BTreeMap<String, TestGasUsage>>,
+}
+
+impl UnitTestGasProfiler {
+    pub fn new(gas_feature_version: u64, gas_params: AptosGasParameters) -> Self {
+        // Without an on-chain storage gas schedule, IO is priced by the gas parameters alone.
+        let storage_gas_params =
+            StorageGasParameters::new(gas_feature_version, Some(&gas_params), None)
+                .expect("storage gas parameters must exist for a non-zero feature version");
+        let natives = natives::aptos_natives(
+            gas_params.natives.clone(),
+            gas_params.misc.abs_val.clone(),
+            gas_feature_version,
+            TimedFeatures::enable_all(),
+            Arc::new(Features::default()),
+        );
+        Self {
+            gas_feature_version,
+            gas_params,
+            storage_gas_params,
+            natives,
+            usages: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Installs `profiler` into the unit test runner, together with the Aptos native
+    /// extensions, and returns it so its results can be collected once the tests have run.
+    pub fn install(profiler: Self) -> Arc<Self> {
+        natives::configure_for_unit_test();
+        let profiler = Arc::new(profiler);
+        move_unit_test::gas_profiler::set_gas_profiler_hook(Some(profiler.clone()));
+        profiler
+    }
+
+    /// Returns the gas usage of all tests profiled so far.
+    pub fn usages(&self) -> BTreeMap<String, TestGasUsage> {
+        self.usages.lock().clone()
+    }
+
+    fn execute<'a>(
+        &self,
+        storage: &'a InMemoryStorage,
+        extensions: NativeContextExtensions<'a>,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        args: Vec<Vec<u8>>,
+    ) -> VMResult<TestGasUsage> {
+        let vm = MoveVM::new(self.natives.clone())?;
+        let mut session = vm.new_session_with_extensions(storage, extensions);
+        let mut gas_meter = StandardGasMeter::new(
+            self.gas_feature_version,
+            self.gas_params.clone(),
+            self.storage_gas_params.clone(),
+            self.gas_params.txn.maximum_number_of_gas_units,
+        );
+
+        let mut completed = is_completed(session.execute_function_bypass_visibility(
+            module_id,
+            function_name,
+            vec![],
+            args,
+            &mut gas_meter,
+        ))?;
+        if completed {
+            let (write_ops, events) = convert_session_output(session.finish_with_extensions()?)?;
+            // Failing to charge for the write set (e.g. by exceeding the storage fee limit)
+            // counts as not completing the test, like it would fail the transaction.
+            completed = is_completed(self.charge_write_set(&write_ops, &events, &mut gas_meter))?;
+        }
+
+        let txn_params = &self.gas_params.txn;
+        Ok(TestGasUsage {
+            execution_gas: gas_meter
+                .execution_gas_used()
+                .to_unit_round_up_with_params(txn_params)
+                .into(),
+            io_gas: gas_meter
+                .io_gas_used()
+                .to_unit_round_up_with_params(txn_params)
+                .into(),
+            storage_fee: gas_meter.storage_fee_used().into(),
+            completed,
+        })
+    }
+
+    fn charge_write_set(
+        &self,
+        write_ops: &[(StateKey, WriteOp)],
+        events: &[ContractEvent],
+        gas_meter: &mut StandardGasMeter,
+    ) -> VMResult<()> {
+        gas_meter.charge_io_gas_for_write_set(write_ops.iter().map(|(key, op)| (key, op)))?;
+        gas_meter.charge_storage_fee_for_all(
+            write_ops.iter().map(|(key, op)| (key, op)),
+            events.iter(),
+            NumBytes::zero(),
+            self.gas_params.txn.min_price_per_gas_unit,
+        )
+    }
+}
+
+impl TestGasProfiler for UnitTestGasProfiler {
+    fn profile_test<'a>(
+        &self,
+        storage: &'a InMemoryStorage,
+        extensions: NativeContextExtensions<'a>,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        args: Vec<Vec<u8>>,
+    ) -> VMResult<()> {
+        let usage = self.execute(storage, extensions, module_id, function_name, args)?;
+        self.usages.lock().insert(
+            format!(
+                "0x{}::{}::{}",
+                module_id.address().short_str_lossless(),
+                module_id.name(),
+                function_name
+            ),
+            usage,
+        );
+        Ok(())
+    }
+}
+
+/// Returns whether the execution completed. Execution failures (e.g. aborts or running out of
+/// gas) are an outcome of the test, while other errors come from the VM and are returned.
+fn is_completed<T>(result: VMResult<T>) -> VMResult<bool> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(err) if err.status_type() == StatusType::Execution => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Converts the output of a session into the write set and events of a transaction.
+fn convert_session_output(
+    (change_set, events, mut extensions): (
+        MoveChangeSet,
+        Vec<MoveEvent>,
+        NativeContextExtensions<'_>,
+    ),
+) -> VMResult<(Vec<(StateKey, WriteOp)>, Vec<ContractEvent>)> {
+    let table_change_set = extensions
+        .remove::<NativeTableContext>()
+        .into_change_set()
+        .map_err(|e| e.finish(Location::Undefined))?;
+    let aggregator_change_set = extensions
+        .remove::<NativeAggregatorContext>()
+        .into_change_set();
+
+    let mut write_ops = convert_change_set(change_set);
+    for (handle, change) in table_change_set.changes {
+        for (key, op) in change.entries {
+            let state_key = StateKey::table_item(handle.into(), key);
+            write_ops.push((state_key, convert_write_op(op)));
+        }
+    }
+    for (id, change) in aggregator_change_set.changes {
+        let state_key = StateKey::table_item(TableHandle::from(id.handle), id.key.0.to_vec());
+        let op = match change {
+            AggregatorChange::Write(value) => WriteOp::Modification(serialize(&value)),
+            // Deltas are applied to the stored value, and snapshots store the
+            // value of an aggregator, which has a fixed size.
+            AggregatorChange::Merge(_) | AggregatorChange::Snapshot(_) => {
+                WriteOp::Modification(serialize(&0))
+            },
+            AggregatorChange::Delete => WriteOp::Deletion,
+        };
+        write_ops.push((state_key, op));
+    }
+    Ok((write_ops, convert_events(events)?))
+}
+
+fn convert_change_set(change_set: MoveChangeSet) -> Vec<(StateKey, WriteOp)> {
+    let mut write_ops = vec![];
+    for (addr, account_change_set) in change_set.into_inner() {
+        let (modules, resources) = account_change_set.into_inner();
+        for (struct_tag, op) in resources {
+            let state_key = StateKey::access_path(().get_resource_path(addr, struct_tag));
+            write_ops.push((state_key, convert_write_op(op)));
+        }
+        for (name, op) in modules {
+            let state_key = StateKey::access_path(().get_module_path(ModuleId::new(addr, name)));
+            write_ops.push((state_key, convert_write_op(op)));
+        }
+    }
+    write_ops
+}
+
+fn convert_write_op(op: MoveStorageOp<Vec<u8>>) -> WriteOp {
+    match op {
+        MoveStorageOp::New(blob) => WriteOp::Creation(blob),
+        MoveStorageOp::Modify(blob) => WriteOp::Modification(blob),
+        MoveStorageOp::Delete => WriteOp::Deletion,
+    }
+}
+
+fn convert_events(events: Vec<MoveEvent>) -> VMResult<Vec<ContractEvent>> {
+    events
+        .into_iter()
+        .map(|(guid, seq_num, ty_tag, blob)| {
+            let key = bcs::from_bytes(guid.as_slice()).map_err(|_| {
+                PartialVMError::new(StatusCode::EVENT_KEY_MISMATCH).finish(Location::Undefined)
+            })?;
+            Ok(ContractEvent::new(key, seq_num, ty_tag, blob))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_framework::{BuildOptions, BuiltPackage};
+    use aptos_gas::LATEST_GAS_FEATURE_VERSION;
+    use move_core_types::{account_address::AccountAddress, value::MoveValue};
+    use move_vm_test_utils::BlankStorage;
+
+    const SOURCE: &str = r#"
+        module 0xcafe::m {
+            struct R has key { v: vector<u8> }
+
+            public fun noop() {}
+
+            public fun store(account: &signer) {
+                move_to(account, R { v: vector[1, 2, 3] })
+            }
+
+            public fun aborts() {
+                abort 1
+            }
+        }
+    "#;
+
+    /// Returns a storage containing the compiled `SOURCE`, and the id of its module.
+    fn storage() -> (InMemoryStorage, ModuleId) {
+        let package_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            package_dir.path().join("Move.toml"),
+            "[package]\nname = \"test\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        let sources_dir = package_dir.path().join("sources");
+        std::fs::create_dir(&sources_dir).unwrap();
+        std::fs::write(sources_dir.join("m.move"), SOURCE).unwrap();
+        let pack =
+            BuiltPackage::build(package_dir.path().to_path_buf(), BuildOptions::default()).unwrap();
+
+        let module = pack.modules().next().unwrap();
+        let mut bytes = vec![];
+        module.serialize(&mut bytes).unwrap();
+        let mut storage = InMemoryStorage::new();
+        storage.publish_or_overwrite_module(module.self_id(), bytes);
+        (storage, module.self_id())
+    }
+
+    /// Returns the extensions of the unit test runner.
+    fn extensions<'a>() -> NativeContextExtensions<'a> {
+        let mut extensions = NativeContextExtensions::default();
+        natives::unit_test_extensions_hook(&mut extensions);
+        extensions.add(NativeTableContext::new([0; 32], &BlankStorage));
+        extensions
+    }
+
+    fn profiler() -> UnitTestGasProfiler {
+        UnitTestGasProfiler::new(LATEST_GAS_FEATURE_VERSION, AptosGasParameters::initial())
+    }
+
+    #[test]
+    fn test_profile_tests() {
+        let (storage, module_id) = storage();
+        let profiler = profiler();
+        let signer = MoveValue::Signer(AccountAddress::from_hex_literal("0xcafe").unwrap())
+            .simple_serialize()
+            .unwrap();
+        for (function_name, args) in [
+            ("noop", vec![]),
+            ("store", vec![signer]),
+            ("aborts", vec![]),
+        ] {
+            profiler
+                .profile_test(
+                    &storage,
+                    extensions(),
+                    &module_id,
+                    IdentStr::new(function_name).unwrap(),
+                    args,
+                )
+                .unwrap();
+        }
+
+        let usages = profiler.usages();
+        let noop = usages["0xcafe::m::noop"];
+        assert!(noop.completed, "{:?}", noop);
+        assert_eq!(noop.storage_fee, 0);
+
+        // The new resource is charged like in a transaction
+        let store = usages["0xcafe::m::store"];
+        assert!(store.completed, "{:?}", store);
+        assert!(store.execution_gas > 0, "{:?}", store);
+        assert!(store.io_gas > 0, "{:?}", store);
+        assert!(store.storage_fee > 0, "{:?}", store);
+
+        let aborts = usages["0xcafe::m::aborts"];
+        assert!(!aborts.completed, "{:?}", aborts);
+        assert_eq!(aborts.storage_fee, 0);
+    }
+
+    #[test]
+    fn test_profile_vm_errors() {
+        let (storage, module_id) = storage();
+        let profiler = profiler();
+
+        // Failing to resolve the function is an error of the VM, not an outcome of the test
+        let err = profiler
+            .profile_test(
+                &storage,
+                extensions(),
+                &module_id,
+                IdentStr::new("missing").unwrap(),
+                vec![],
+            )
+            .unwrap_err();
+        assert_eq!(err.major_status(), StatusCode::FUNCTION_RESOLUTION_FAILURE);
+        assert!(profiler.usages().is_empty());
+    }
+}
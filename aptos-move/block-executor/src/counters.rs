@@ -15,6 +15,15 @@ pub static MODULE_PUBLISHING_FALLBACK_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of blocks whose execution was cut off early because the block gas limit was reached.
+pub static BLOCK_GAS_LIMIT_REACHED_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_execution_block_gas_limit_reached_count",
+        "Count of blocks whose remaining transactions were skipped due to the block gas limit"
+    )
+    .unwrap()
+});
+
 /// Count of speculative transaction re-executions due to a failed validation.
 pub static SPECULATIVE_ABORT_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
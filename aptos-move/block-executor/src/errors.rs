@@ -10,6 +10,10 @@ pub enum Error<E> {
     /// TODO: (short-med term) relax the limitation, and (mid-long term) provide proper multi-versioning
     /// for code (like data) for the cache.
     ModulePathReadWrite,
+    /// The value of a snapshot taken by a committed transaction cannot be resolved from the
+    /// outputs of the parallel execution. Sequential execution materializes snapshots as part
+    /// of each transaction output, and is used as a fallback.
+    SnapshotResolutionFailure,
     /// Execution of a thread yields a non-recoverable error, such error will be propagated back to
     /// the caller.
     UserError(E),
@@ -11,6 +11,7 @@ use crate::{
     errors::*,
     output_delta_resolver::OutputDeltaResolver,
     scheduler::{Scheduler, SchedulerTask, Wave},
+    stats::{BlockExecutionReport, ExecutionStatsCollector},
     task::{ExecutionStatus, ExecutorTask, Transaction, TransactionOutput},
     txn_last_input_output::TxnLastInputOutput,
     view::{LatestView, MVHashMapView},
@@ -42,10 +43,21 @@ pub static RAYON_EXEC_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
         .unwrap()
 });
 
+type BlockExecutionResult<T, E> = Result<
+    Vec<(
+        <E as ExecutorTask>::Output,
+        Vec<(<T as Transaction>::Key, WriteOp)>,
+    )>,
+    <E as ExecutorTask>::Error,
+>;
+
 pub struct BlockExecutor<T, E, S> {
     // number of active concurrent tasks, corresponding to the maximum number of rayon
     // threads that may be concurrently participating in parallel execution.
     concurrency_level: usize,
+    // Once the gas used by the committed prefix of the block exceeds the limit (if provided),
+    // the remaining transactions are skipped.
+    maybe_block_gas_limit: Option<u64>,
     phantom: PhantomData<(T, E, S)>,
 }
 
@@ -57,7 +69,10 @@ where
 {
     /// The caller needs to ensure that concurrency_level > 1 (0 is illegal and 1 should
     /// be handled by sequential execution) and that concurrency_level <= num_cpus.
-    pub fn new(concurrency_level: usize) -> Self {
+    /// If maybe_block_gas_limit is provided, the transactions following the first one at which
+    /// the accumulated gas exceeds the limit are skipped, in both parallel and sequential
+    /// execution.
+    pub fn new(concurrency_level: usize, maybe_block_gas_limit: Option<u64>) -> Self {
         assert!(
             concurrency_level > 0 && concurrency_level <= num_cpus::get(),
             "Parallel execution concurrency level {} should be between 1 and number of CPUs",
@@ -65,6 +80,7 @@ where
         );
         Self {
             concurrency_level,
+            maybe_block_gas_limit,
             phantom: PhantomData,
         }
     }
@@ -78,12 +94,16 @@ where
         scheduler: &Scheduler,
         executor: &E,
         base_view: &S,
+        stats: Option<&ExecutionStatsCollector<T::Key>>,
     ) -> SchedulerTask {
         let _timer = TASK_EXECUTE_SECONDS.start_timer();
         let (idx_to_execute, incarnation) = version;
         let txn = &signature_verified_block[idx_to_execute as usize];
+        if let Some(stats) = stats {
+            stats.record_execution(idx_to_execute, incarnation);
+        }
 
-        let speculative_view = MVHashMapView::new(versioned_cache, scheduler);
+        let speculative_view = MVHashMapView::new(versioned_cache, scheduler, stats);
 
         // VM execution.
         let execute_result = executor.execute_transaction(
@@ -92,6 +112,11 @@ where
             idx_to_execute,
             false,
         );
+        if scheduler.is_halted() {
+            // The execution was halted (e.g. the block gas limit was reached) while the
+            // transaction was executing, so its output is discarded.
+            return SchedulerTask::NoTask;
+        }
         let mut prev_modified_keys = last_input_output.modified_keys(idx_to_execute);
 
         // For tracking whether the recent execution wrote outside of the previous write/delta set.
@@ -113,6 +138,14 @@ where
                 }
                 versioned_cache.add_delta(&k, idx_to_execute, d);
             }
+
+            // Finally, apply snapshots.
+            for (k, source, d) in output.get_snapshots().into_iter() {
+                if !prev_modified_keys.remove(&k) {
+                    updates_outside = true;
+                }
+                versioned_cache.add_snapshot(&k, write_version, source, d);
+            }
         };
 
         let result = match execute_result {
@@ -152,6 +185,7 @@ where
         last_input_output: &TxnLastInputOutput<T::Key, E::Output, E::Error>,
         versioned_cache: &MVHashMap<T::Key, T::Value, ExecutableTestType>,
         scheduler: &Scheduler,
+        stats: Option<&ExecutionStatsCollector<T::Key>>,
     ) -> SchedulerTask {
         use MVDataError::*;
         use MVDataOutput::*;
@@ -162,27 +196,45 @@ where
             .read_set(idx_to_validate)
             .expect("Prior read-set must be recorded");
 
-        let valid = read_set.iter().all(|r| {
-            match versioned_cache.fetch_data(r.path(), idx_to_validate) {
-                Ok(Versioned(version, _)) => r.validate_version(version),
-                Ok(Resolved(value)) => r.validate_resolved(value),
-                Err(Dependency(_)) => false, // Dependency implies a validation failure.
-                Err(Unresolved(delta)) => r.validate_unresolved(delta),
-                Err(NotFound) => r.validate_storage(),
+        // Finds the first read that fails validation, along with the index of the transaction
+        // that (if known) caused the failure.
+        let invalid_read = read_set.iter().find_map(|r| {
+            // Reads performed from the perspective of a prior transaction (e.g. to resolve
+            // a snapshot) are validated from the same perspective.
+            let read_idx = r.read_idx().unwrap_or(idx_to_validate);
+            let (valid, invalidated_by) = match versioned_cache.fetch_data(r.path(), read_idx) {
+                Ok(Versioned(version, _)) => (r.validate_version(version), Some(version.0)),
+                Err(Snapshot(version)) => (r.validate_version(version), Some(version.0)),
+                Ok(Resolved(value)) => (r.validate_resolved(value), r.writer_txn_idx()),
+                // Dependency implies a validation failure.
+                Err(Dependency(dep_idx)) => (false, Some(dep_idx)),
+                Err(Unresolved(delta)) => (r.validate_unresolved(delta), r.writer_txn_idx()),
+                Err(NotFound) => (r.validate_storage(), r.writer_txn_idx()),
                 // We successfully validate when read (again) results in a delta application
                 // failure. If the failure is speculative, a later validation will fail due to
                 // a read without this error. However, if the failure is real, passing
                 // validation here allows to avoid infinitely looping and instead panic when
                 // materializing deltas as writes in the final output preparation state. Panic
                 // is also preferrable as it allows testing for this scenario.
-                Err(DeltaApplicationFailure) => r.validate_delta_application_failure(),
-            }
+                Err(DeltaApplicationFailure) => {
+                    (r.validate_delta_application_failure(), r.writer_txn_idx())
+                },
+            };
+            (!valid).then_some((r, invalidated_by))
         });
 
-        let aborted = !valid && scheduler.try_abort(idx_to_validate, incarnation);
+        let aborted = invalid_read.is_some() && scheduler.try_abort(idx_to_validate, incarnation);
 
         if aborted {
             counters::SPECULATIVE_ABORT_COUNT.inc();
+            if let (Some(stats), Some((read, invalidated_by))) = (stats, invalid_read) {
+                stats.record_invalidation(
+                    read.path().clone(),
+                    idx_to_validate,
+                    incarnation,
+                    invalidated_by,
+                );
+            }
 
             // Any logs from the aborted execution should be cleared and not reported.
             clear_speculative_txn_logs(idx_to_validate as usize);
@@ -208,6 +260,7 @@ where
         scheduler: &Scheduler,
         base_view: &S,
         committing: bool,
+        stats: Option<&ExecutionStatsCollector<T::Key>>,
     ) {
         // Make executor for each task. TODO: fast concurrent executor.
         let init_timer = VM_INIT_SECONDS.start_timer();
@@ -216,6 +269,8 @@ where
 
         let _timer = WORK_WITH_TASK_SECONDS.start_timer();
         let mut scheduler_task = SchedulerTask::NoTask;
+        // Gas used by the committed transactions, only tracked by the committing thread.
+        let mut accumulated_gas = 0u64;
         loop {
             // Only one thread try_commit to avoid contention.
             if committing {
@@ -226,6 +281,19 @@ where
                         scheduler_task = SchedulerTask::Done;
                         break;
                     }
+
+                    if let Some(block_gas_limit) = self.maybe_block_gas_limit {
+                        // The output of a committed transaction is final.
+                        accumulated_gas =
+                            accumulated_gas.saturating_add(last_input_output.gas_used(txn_idx));
+                        if accumulated_gas > block_gas_limit {
+                            // Skip the rest of the block, the same way as sequential execution.
+                            counters::BLOCK_GAS_LIMIT_REACHED_COUNT.inc();
+                            scheduler.halt();
+                            scheduler_task = SchedulerTask::Done;
+                            break;
+                        }
+                    }
                 }
             }
             scheduler_task = match scheduler_task {
@@ -235,6 +303,7 @@ where
                     last_input_output,
                     versioned_cache,
                     scheduler,
+                    stats,
                 ),
                 SchedulerTask::ExecutionTask(version_to_execute, None) => self.execute(
                     version_to_execute,
@@ -244,6 +313,7 @@ where
                     scheduler,
                     &executor,
                     base_view,
+                    stats,
                 ),
                 SchedulerTask::ExecutionTask(_, Some(condvar)) => {
                     let (lock, cvar) = &*condvar;
@@ -267,7 +337,8 @@ where
         executor_initial_arguments: E::Argument,
         signature_verified_block: &Vec<T>,
         base_view: &S,
-    ) -> Result<Vec<(E::Output, Vec<(T::Key, WriteOp)>)>, E::Error> {
+        stats: Option<&ExecutionStatsCollector<T::Key>>,
+    ) -> BlockExecutionResult<T, E> {
         let _timer = PARALLEL_EXECUTION_SECONDS.start_timer();
         assert!(self.concurrency_level > 1, "Must use sequential execution");
 
@@ -294,12 +365,15 @@ where
                         &scheduler,
                         base_view,
                         committing.swap(false, Ordering::SeqCst),
+                        stats,
                     );
                 });
             }
         });
         drop(timer);
 
+        // Less than num_txns transactions are committed if the execution was halted.
+        let num_committed = scheduler.num_committed() as usize;
         let num_txns = num_txns as usize;
         // TODO: for large block sizes and many cores, extract outputs in parallel.
         let mut final_results = Vec::with_capacity(num_txns);
@@ -309,7 +383,7 @@ where
             Some(Error::ModulePathReadWrite)
         } else {
             let mut ret = None;
-            for idx in 0..num_txns {
+            for idx in 0..num_committed {
                 match last_input_output.take_output(idx as TxnIndex) {
                     ExecutionStatus::Success(t) => final_results.push(t),
                     ExecutionStatus::SkipRest(t) => {
@@ -335,14 +409,21 @@ where
             Some(err) => Err(err),
             None => {
                 final_results.resize_with(num_txns, E::Output::skip_output);
+                let snapshots = final_results
+                    .iter()
+                    .map(|output| output.get_snapshots())
+                    .collect();
                 let (mv_data_cache, _mv_code_cache) = versioned_cache.take();
                 let delta_resolver: OutputDeltaResolver<T> =
                     OutputDeltaResolver::new(mv_data_cache);
                 // TODO: parallelize when necessary.
-                Ok(final_results
-                    .into_iter()
-                    .zip(delta_resolver.resolve(base_view, num_txns).into_iter())
-                    .collect())
+                match delta_resolver.resolve(base_view, snapshots, num_committed, num_txns) {
+                    Ok(delta_writes) => Ok(final_results.into_iter().zip(delta_writes).collect()),
+                    Err(err) => {
+                        debug!("[Execution]: Failed to resolve snapshots: {:?}", err);
+                        Err(Error::SnapshotResolutionFailure)
+                    },
+                }
             },
         }
     }
@@ -352,12 +433,13 @@ where
         executor_arguments: E::Argument,
         signature_verified_block: &[T],
         base_view: &S,
-    ) -> Result<Vec<(E::Output, Vec<(T::Key, WriteOp)>)>, E::Error> {
+    ) -> BlockExecutionResult<T, E> {
         let num_txns = signature_verified_block.len();
         let executor = E::init(executor_arguments);
         let mut data_map = BTreeMap::new();
 
         let mut ret = Vec::with_capacity(num_txns);
+        let mut accumulated_gas = 0u64;
         for (idx, txn) in signature_verified_block.iter().enumerate() {
             let res = executor.execute_transaction(
                 &LatestView::<T, S>::new_btree_view(base_view, &data_map, idx as TxnIndex),
@@ -375,6 +457,11 @@ where
                         0,
                         "Sequential execution must materialize deltas"
                     );
+                    assert_eq!(
+                        output.get_snapshots().len(),
+                        0,
+                        "Sequential execution must materialize snapshots"
+                    );
                     // Apply the writes.
                     for (ap, write_op) in output.get_writes().into_iter() {
                         data_map.insert(ap, write_op);
@@ -390,6 +477,14 @@ where
             if must_skip {
                 break;
             }
+
+            if let Some(block_gas_limit) = self.maybe_block_gas_limit {
+                accumulated_gas = accumulated_gas.saturating_add(ret[idx].gas_used());
+                if accumulated_gas > block_gas_limit && idx + 1 < num_txns {
+                    counters::BLOCK_GAS_LIMIT_REACHED_COUNT.inc();
+                    break;
+                }
+            }
         }
 
         ret.resize_with(num_txns, E::Output::skip_output);
@@ -401,13 +496,58 @@ where
         executor_arguments: E::Argument,
         signature_verified_block: Vec<T>,
         base_view: &S,
-    ) -> Result<Vec<(E::Output, Vec<(T::Key, WriteOp)>)>, E::Error> {
+    ) -> BlockExecutionResult<T, E> {
+        self.execute_block_impl(
+            executor_arguments,
+            signature_verified_block,
+            base_view,
+            false,
+        )
+        .0
+    }
+
+    /// Same as `execute_block`, but additionally records the number of incarnations of every
+    /// transaction, the reads that failed validation and the waits on dependencies. The report
+    /// is only produced when the block is executed in parallel (i.e., concurrency_level > 1).
+    pub fn execute_block_with_report(
+        &self,
+        executor_arguments: E::Argument,
+        signature_verified_block: Vec<T>,
+        base_view: &S,
+    ) -> (
+        BlockExecutionResult<T, E>,
+        Option<BlockExecutionReport<T::Key>>,
+    ) {
+        self.execute_block_impl(
+            executor_arguments,
+            signature_verified_block,
+            base_view,
+            true,
+        )
+    }
+
+    fn execute_block_impl(
+        &self,
+        executor_arguments: E::Argument,
+        signature_verified_block: Vec<T>,
+        base_view: &S,
+        collect_stats: bool,
+    ) -> (
+        BlockExecutionResult<T, E>,
+        Option<BlockExecutionReport<T::Key>>,
+    ) {
+        let mut report = None;
         let mut ret = if self.concurrency_level > 1 {
-            self.execute_transactions_parallel(
+            let stats =
+                collect_stats.then(|| ExecutionStatsCollector::new(signature_verified_block.len()));
+            let ret = self.execute_transactions_parallel(
                 executor_arguments,
                 &signature_verified_block,
                 base_view,
-            )
+                stats.as_ref(),
+            );
+            report = stats.map(|stats| stats.into_report(self.concurrency_level));
+            ret
         } else {
             self.execute_transactions_sequential(
                 executor_arguments,
@@ -416,8 +556,16 @@ where
             )
         };
 
-        if matches!(ret, Err(Error::ModulePathReadWrite)) {
-            debug!("[Execution]: Module read & written, sequential fallback");
+        if matches!(
+            ret,
+            Err(Error::ModulePathReadWrite) | Err(Error::SnapshotResolutionFailure)
+        ) {
+            debug!(
+                "[Execution]: Module read & written or snapshot unresolved, sequential fallback"
+            );
+            if let Some(report) = report.as_mut() {
+                report.sequential_fallback = true;
+            }
 
             // All logs from the parallel execution should be cleared and not reported.
             // Clear by re-initializing the speculative logs.
@@ -435,6 +583,6 @@ where
             drop(signature_verified_block);
         });
 
-        ret
+        (ret, report)
     }
 }
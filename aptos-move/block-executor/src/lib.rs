@@ -143,6 +143,7 @@ pub mod output_delta_resolver;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
 mod scheduler;
+pub mod stats;
 pub mod task;
 mod txn_last_input_output;
 #[cfg(test)]
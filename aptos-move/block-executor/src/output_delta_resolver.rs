@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{executor::RAYON_EXEC_POOL, task::Transaction};
-use aptos_aggregator::delta_change_set::{deserialize, serialize};
-use aptos_mvhashmap::versioned_data::VersionedData;
+use anyhow::{anyhow, bail};
+use aptos_aggregator::delta_change_set::{deserialize, serialize, DeltaOp};
+use aptos_mvhashmap::{
+    types::{MVDataError, MVDataOutput, TxnIndex},
+    versioned_data::VersionedData,
+};
 use aptos_state_view::TStateView;
-use aptos_types::write_set::WriteOp;
+use aptos_types::write_set::{TransactionWrite, WriteOp};
 
 pub(crate) struct OutputDeltaResolver<T: Transaction> {
     versioned_outputs: VersionedData<T::Key, T::Value>,
@@ -19,13 +23,33 @@ impl<T: Transaction> OutputDeltaResolver<T> {
     /// Takes Self, vector of all involved aggregator keys (each with at least one
     /// delta to resolve in the output), resolved values from storage for each key,
     /// and blocksize, and returns a Vec of materialized deltas per transaction index.
+    /// Only the deltas of the first num_committed transactions are materialized, as the
+    /// (speculative) outputs of the following transactions are discarded.
+    ///
+    /// Snapshots (snapshot key, aggregator key and delta per transaction index) are
+    /// materialized as writes of the delta applied to the aggregator value before the
+    /// snapshotting transaction. Returns an error if the value of a snapshot cannot be
+    /// resolved.
     pub(crate) fn resolve(
         self,
         base_view: &impl TStateView<Key = T::Key>,
+        snapshots: Vec<Vec<(T::Key, T::Key, DeltaOp)>>,
+        num_committed: usize,
         block_size: usize,
-    ) -> Vec<Vec<(T::Key, WriteOp)>> {
+    ) -> anyhow::Result<Vec<Vec<(T::Key, WriteOp)>>> {
         let mut ret: Vec<Vec<(T::Key, WriteOp)>> = vec![vec![]; block_size];
 
+        // Snapshots must be resolved before the deltas are materialized (and taken).
+        for (idx, txn_snapshots) in snapshots.into_iter().enumerate().take(num_committed) {
+            for (key, source, delta) in txn_snapshots {
+                let base = self.aggregator_value(base_view, &source, idx as TxnIndex)?;
+                let value = delta
+                    .apply_to(base)
+                    .map_err(|e| anyhow!("Failed to apply the delta of a snapshot: {:?}", e))?;
+                ret[idx].push((key, WriteOp::Modification(serialize(&value))));
+            }
+        }
+
         // TODO: with more deltas, re-use executor threads and process in parallel.
         for key in self.versioned_outputs.take_aggregator_keys() {
             for (idx, value) in self.versioned_outputs.take_materialized_deltas(
@@ -34,6 +58,7 @@ impl<T: Transaction> OutputDeltaResolver<T> {
                     .get_state_value_bytes(&key)
                     .ok() // Was anything found in storage
                     .and_then(|value| value.map(|bytes| deserialize(&bytes))),
+                num_committed as TxnIndex,
             ) {
                 ret[idx as usize].push((key.clone(), WriteOp::Modification(serialize(&value))));
             }
@@ -41,6 +66,40 @@ impl<T: Transaction> OutputDeltaResolver<T> {
 
         RAYON_EXEC_POOL.spawn(move || drop(self));
 
-        ret
+        Ok(ret)
+    }
+
+    // Returns the value of the aggregator at 'key' from the perspective of transaction
+    // 'txn_idx', which must only observe the (final) outputs of committed transactions.
+    fn aggregator_value(
+        &self,
+        base_view: &impl TStateView<Key = T::Key>,
+        key: &T::Key,
+        txn_idx: TxnIndex,
+    ) -> anyhow::Result<u128> {
+        use MVDataError::*;
+        use MVDataOutput::*;
+
+        let from_storage = || -> anyhow::Result<u128> {
+            base_view
+                .get_state_value_bytes(key)?
+                .map(|bytes| deserialize(&bytes))
+                .ok_or_else(|| anyhow!("Aggregator of a snapshot does not exist in storage"))
+        };
+
+        match self.versioned_outputs.fetch_data(key, txn_idx) {
+            Ok(Versioned(_, v)) => v
+                .extract_raw_bytes()
+                .map(|bytes| deserialize(&bytes))
+                .ok_or_else(|| anyhow!("Aggregator of a snapshot has been deleted")),
+            Ok(Resolved(value)) => Ok(value),
+            Err(Unresolved(delta)) => delta
+                .apply_to(from_storage()?)
+                .map_err(|e| anyhow!("Failed to apply the delta of an aggregator: {:?}", e)),
+            Err(NotFound) => from_storage(),
+            Err(Dependency(_)) | Err(DeltaApplicationFailure) | Err(Snapshot(_)) => {
+                bail!("Committed outputs do not resolve the value of the aggregator")
+            },
+        }
     }
 }
@@ -117,8 +117,8 @@ where
             Transaction<KeyType<K>, ValueType<V>>,
             Task<KeyType<K>, ValueType<V>>,
             EmptyDataView<KeyType<K>, ValueType<V>>,
-        >::new(num_cpus::get())
-        .execute_transactions_parallel((), &self.transactions, &data_view)
+        >::new(num_cpus::get(), None)
+        .execute_transactions_parallel((), &self.transactions, &data_view, None)
         .map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
 
         self.expected_output.assert_output(&output);
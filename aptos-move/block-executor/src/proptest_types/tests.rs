@@ -28,6 +28,7 @@ fn run_transactions<K, V>(
     skip_rest_transactions: Vec<Index>,
     num_repeat: usize,
     module_access: (bool, bool),
+    maybe_block_gas_limit: Option<u64>,
 ) where
     K: Hash + Clone + Debug + Eq + Send + Sync + PartialOrd + Ord + 'static,
     V: Clone + Eq + Send + Sync + Arbitrary + 'static,
@@ -55,8 +56,8 @@ fn run_transactions<K, V>(
             Transaction<KeyType<K>, ValueType<V>>,
             Task<KeyType<K>, ValueType<V>>,
             EmptyDataView<KeyType<K>, ValueType<V>>,
-        >::new(num_cpus::get())
-        .execute_transactions_parallel((), &transactions, &data_view)
+        >::new(num_cpus::get(), maybe_block_gas_limit)
+        .execute_transactions_parallel((), &transactions, &data_view, None)
         .map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
 
         if module_access.0 && module_access.1 {
@@ -64,12 +65,56 @@ fn run_transactions<K, V>(
             continue;
         }
 
-        let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+        let baseline = ExpectedOutput::generate_baseline_with_block_gas_limit(
+            &transactions,
+            None,
+            maybe_block_gas_limit,
+        );
 
         baseline.assert_output(&output);
     }
 }
 
+fn run_transactions_sequential<K, V>(
+    key_universe: &[K],
+    transaction_gens: Vec<TransactionGen<V>>,
+    skip_rest_transactions: Vec<Index>,
+    maybe_block_gas_limit: Option<u64>,
+) where
+    K: Hash + Clone + Debug + Eq + Send + Sync + PartialOrd + Ord + 'static,
+    V: Clone + Eq + Send + Sync + Arbitrary + 'static,
+    Vec<u8>: From<V>,
+{
+    let mut transactions: Vec<_> = transaction_gens
+        .into_iter()
+        .map(|txn_gen| txn_gen.materialize(key_universe, (false, false)))
+        .collect();
+
+    let length = transactions.len();
+    for i in skip_rest_transactions {
+        *transactions.get_mut(i.index(length)).unwrap() = Transaction::SkipRest;
+    }
+
+    let data_view = EmptyDataView::<KeyType<K>, ValueType<V>> {
+        phantom: PhantomData,
+    };
+
+    let output = BlockExecutor::<
+        Transaction<KeyType<K>, ValueType<V>>,
+        Task<KeyType<K>, ValueType<V>>,
+        EmptyDataView<KeyType<K>, ValueType<V>>,
+    >::new(1, maybe_block_gas_limit)
+    .execute_transactions_sequential((), &transactions, &data_view)
+    .map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
+
+    let baseline = ExpectedOutput::generate_baseline_with_block_gas_limit(
+        &transactions,
+        None,
+        maybe_block_gas_limit,
+    );
+    baseline.assert_output(&output);
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(32))]
     #[test]
@@ -79,7 +124,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -89,7 +134,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -99,7 +144,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -109,7 +154,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
     }
 
     #[test]
@@ -119,7 +164,28 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 3),
         skip_rest_transactions in vec(any::<Index>(), 3),
     ) {
-        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+    }
+
+    #[test]
+    fn block_gas_limit(
+        universe in vec(any::<[u8; 32]>(), 100),
+        transaction_gen in vec(any_with::<TransactionGen<[u8;32]>>(TransactionGenParams::new_dynamic()), 3000).no_shrink(),
+        abort_transactions in vec(any::<Index>(), 3),
+        skip_rest_transactions in vec(any::<Index>(), 3),
+        block_gas_limit in 1000u64..20_000,
+    ) {
+        run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(block_gas_limit));
+    }
+
+    #[test]
+    fn block_gas_limit_sequential(
+        universe in vec(any::<[u8; 32]>(), 100),
+        transaction_gen in vec(any::<TransactionGen<[u8;32]>>(), 3000).no_shrink(),
+        skip_rest_transactions in vec(any::<Index>(), 3),
+        block_gas_limit in 1000u64..20_000,
+    ) {
+        run_transactions_sequential(&universe, transaction_gen, skip_rest_transactions, Some(block_gas_limit));
     }
 }
 
@@ -146,6 +212,7 @@ fn dynamic_read_writes() {
         vec![],
         100,
         (false, false),
+        None,
     );
 }
 
@@ -181,8 +248,8 @@ fn deltas_writes_mixed() {
             Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             DeltaDataView<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-        >::new(num_cpus::get())
-        .execute_transactions_parallel((), &transactions, &data_view)
+        >::new(num_cpus::get(), None)
+        .execute_transactions_parallel((), &transactions, &data_view, None)
         .map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
 
         let baseline = ExpectedOutput::generate_baseline(&transactions, None);
@@ -222,8 +289,8 @@ fn deltas_resolver() {
             Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             DeltaDataView<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-        >::new(num_cpus::get())
-        .execute_transactions_parallel((), &transactions, &data_view)
+        >::new(num_cpus::get(), None)
+        .execute_transactions_parallel((), &transactions, &data_view, None)
         .unwrap()
         .into_iter()
         .unzip();
@@ -257,6 +324,7 @@ fn dynamic_read_writes_contended() {
         vec![],
         100,
         (false, false),
+        None,
     );
 }
 
@@ -283,6 +351,7 @@ fn module_publishing_fallback() {
         vec![],
         2,
         (false, true),
+        None,
     );
     run_transactions(
         &universe,
@@ -291,8 +360,17 @@ fn module_publishing_fallback() {
         vec![],
         2,
         (false, true),
+        None,
+    );
+    run_transactions(
+        &universe,
+        transaction_gen,
+        vec![],
+        vec![],
+        2,
+        (true, true),
+        None,
     );
-    run_transactions(&universe, transaction_gen, vec![], vec![], 2, (true, true));
 }
 
 fn publishing_fixed_params() {
@@ -359,8 +437,8 @@ fn publishing_fixed_params() {
         Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
         Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
         DeltaDataView<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-    >::new(num_cpus::get())
-    .execute_transactions_parallel((), &transactions, &data_view);
+    >::new(num_cpus::get(), None)
+    .execute_transactions_parallel((), &transactions, &data_view, None);
     assert_ok!(output);
 
     // Adjust the reads of txn indices[2] to contain module read to key 42.
@@ -395,8 +473,8 @@ fn publishing_fixed_params() {
             Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             Task<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
             DeltaDataView<KeyType<[u8; 32]>, ValueType<[u8; 32]>>,
-        >::new(num_cpus::get())
-        .execute_transactions_parallel((), &transactions, &data_view);
+        >::new(num_cpus::get(), None)
+        .execute_transactions_parallel((), &transactions, &data_view, None);
 
         assert_eq!(output.unwrap_err(), Error::ModulePathReadWrite);
     }
@@ -481,6 +481,15 @@ where
         self.1.clone()
     }
 
+    fn get_snapshots(&self) -> Vec<(K, K, DeltaOp)> {
+        vec![]
+    }
+
+    fn gas_used(&self) -> u64 {
+        // Charge one unit of gas per read, write and delta.
+        (self.0.len() + self.1.len() + self.2.len()) as u64
+    }
+
     fn skip_output() -> Self {
         Self(vec![], vec![], vec![])
     }
@@ -503,6 +512,16 @@ impl<V: Debug + Clone + PartialEq + Eq + TransactionWrite> ExpectedOutput<V> {
     pub fn generate_baseline<K: Hash + Clone + Eq>(
         txns: &[Transaction<K, V>],
         resolved_deltas: Option<Vec<Vec<(K, WriteOp)>>>,
+    ) -> Self {
+        Self::generate_baseline_with_block_gas_limit(txns, resolved_deltas, None)
+    }
+
+    /// Same as generate_baseline, but skips the rest of the block once the gas used by the
+    /// executed transactions (one unit per read, write and delta) exceeds the block gas limit.
+    pub fn generate_baseline_with_block_gas_limit<K: Hash + Clone + Eq>(
+        txns: &[Transaction<K, V>],
+        resolved_deltas: Option<Vec<Vec<(K, WriteOp)>>>,
+        maybe_block_gas_limit: Option<u64>,
     ) -> Self {
         let mut current_world = HashMap::new();
         // Delta world stores the latest u128 value of delta aggregator. When empty, the
@@ -511,6 +530,7 @@ impl<V: Debug + Clone + PartialEq + Eq + TransactionWrite> ExpectedOutput<V> {
         let mut delta_world = HashMap::new();
 
         let mut result_vec = vec![];
+        let mut accumulated_gas = 0u64;
         for (idx, txn) in txns.iter().enumerate() {
             let delta_writes_at_idx = resolved_deltas.as_ref().map(|delta_writes| {
                 delta_writes[idx]
@@ -601,7 +621,14 @@ impl<V: Debug + Clone + PartialEq + Eq + TransactionWrite> ExpectedOutput<V> {
                         }
                     }
 
-                    result_vec.push(result)
+                    result_vec.push(result);
+
+                    accumulated_gas += (read_set.len() + write_set.len() + delta_set.len()) as u64;
+                    if let Some(block_gas_limit) = maybe_block_gas_limit {
+                        if accumulated_gas > block_gas_limit && idx + 1 < txns.len() {
+                            return Self::SkipRest(idx + 1, result_vec);
+                        }
+                    }
                 },
                 Transaction::SkipRest => return Self::SkipRest(idx, result_vec),
             }
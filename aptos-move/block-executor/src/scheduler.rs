@@ -283,6 +283,40 @@ impl Scheduler {
         (commit_state.0, commit_state.1)
     }
 
+    /// Returns the number of committed transactions, i.e. the index of the next transaction
+    /// to commit.
+    pub fn num_committed(&self) -> TxnIndex {
+        self.commit_state.lock().0
+    }
+
+    /// Stops the parallel execution before all transactions are committed (e.g. when the
+    /// block gas limit is reached): no more tasks are created, and every transaction waiting
+    /// on a dependency is woken up, so that its ongoing (now irrelevant) execution can finish.
+    pub fn halt(&self) {
+        self.done_marker.store(true, Ordering::SeqCst);
+
+        for txn_status in self.txn_status.iter() {
+            // A transaction suspended after this check observes the done marker (set above)
+            // before waiting, see 'is_halted'. A resumed transaction may also still be waiting,
+            // as no more execution tasks (that would notify it) are going to be created.
+            match &*txn_status.0.read() {
+                ExecutionStatus::Suspended(_, dep_condvar)
+                | ExecutionStatus::ReadyToExecute(_, Some(dep_condvar)) => {
+                    let (lock, cvar) = &**dep_condvar;
+                    *lock.lock() = true;
+                    cvar.notify_one();
+                },
+                _ => (),
+            }
+        }
+    }
+
+    /// Returns true iff the scheduler was halted, or all transactions were committed. In both
+    /// cases, the output of any ongoing execution is irrelevant.
+    pub fn is_halted(&self) -> bool {
+        self.done()
+    }
+
     /// Try to abort version = (txn_idx, incarnation), called upon validation failure.
     /// When the invocation manages to update the status of the transaction, it changes
     /// Executed(incarnation) => Aborting(incarnation), it returns true. Otherwise,
@@ -721,7 +755,8 @@ impl Scheduler {
         *status = ExecutionStatus::ReadyToExecute(incarnation + 1, None);
     }
 
-    /// Checks whether the done marker is set. The marker can only be set by 'try_commit'.
+    /// Checks whether the done marker is set. The marker can only be set by 'try_commit' (once
+    /// all transactions are committed) or by 'halt'.
     fn done(&self) -> bool {
         self.done_marker.load(Ordering::Acquire)
     }
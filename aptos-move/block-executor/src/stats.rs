@@ -0,0 +1,210 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in instrumentation of parallel execution. When enabled, the block executor records which
+//! transactions were re-executed and why, and returns a [`BlockExecutionReport`] for the block.
+
+use aptos_infallible::Mutex;
+use aptos_mvhashmap::types::{Incarnation, TxnIndex};
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The number of most conflicting keys displayed in the summary of a report.
+const NUM_DISPLAYED_CONFLICTING_KEYS: usize = 10;
+
+/// A read of `key` by an incarnation of `txn_idx` that failed validation, causing the incarnation
+/// to be aborted and the transaction to be re-executed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadInvalidation<K> {
+    pub key: K,
+    pub txn_idx: TxnIndex,
+    pub incarnation: Incarnation,
+    /// The transaction whose (re-)execution changed the value of `key` observed by `txn_idx`, if
+    /// it is known. It is unknown when the value changed due to a different aggregation of deltas.
+    pub invalidated_by: Option<TxnIndex>,
+}
+
+/// A wait of an incarnation of `txn_idx` on the re-execution of `dep_idx`, which was estimated to
+/// write to a key read by `txn_idx`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DependencyWait {
+    pub txn_idx: TxnIndex,
+    pub dep_idx: TxnIndex,
+    pub duration: Duration,
+}
+
+/// The execution statistics of a single transaction in the block.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TransactionExecutionStats {
+    /// The number of times the transaction was (speculatively) executed.
+    pub num_incarnations: u32,
+    /// The total time the executions of the transaction spent waiting on dependencies.
+    pub total_dependency_wait: Duration,
+}
+
+/// The statistics and conflicts of the parallel execution of a block.
+#[derive(Clone, Debug)]
+pub struct BlockExecutionReport<K> {
+    pub concurrency_level: usize,
+    /// The wall-clock time of the parallel execution, including the preparation of outputs.
+    pub execution_time: Duration,
+    /// Whether parallel execution was discarded in favor of sequential execution, because a
+    /// module was both read and written in the block.
+    pub sequential_fallback: bool,
+    /// The statistics of every transaction, indexed by the transaction index.
+    pub transactions: Vec<TransactionExecutionStats>,
+    /// The failed validations, in the order they were recorded.
+    pub invalidations: Vec<ReadInvalidation<K>>,
+    /// The waits on dependencies, in the order they were resolved.
+    pub dependency_waits: Vec<DependencyWait>,
+}
+
+impl<K: Clone + Eq + Hash> BlockExecutionReport<K> {
+    pub fn num_txns(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns the total number of executions, including re-executions.
+    pub fn total_incarnations(&self) -> u64 {
+        self.transactions
+            .iter()
+            .map(|stats| stats.num_incarnations as u64)
+            .sum()
+    }
+
+    pub fn total_dependency_wait(&self) -> Duration {
+        self.dependency_waits.iter().map(|wait| wait.duration).sum()
+    }
+
+    /// Returns the keys whose reads were invalidated, along with the number of invalidations,
+    /// from the most to the least invalidated.
+    pub fn conflicting_keys(&self) -> Vec<(K, usize)> {
+        let mut counts: HashMap<&K, usize> = HashMap::new();
+        for invalidation in &self.invalidations {
+            *counts.entry(&invalidation.key).or_default() += 1;
+        }
+        let mut keys: Vec<(K, usize)> = counts
+            .into_iter()
+            .map(|(key, count)| (key.clone(), count))
+            .collect();
+        keys.sort_by(|(_, count1), (_, count2)| count2.cmp(count1));
+        keys
+    }
+}
+
+impl<K: Clone + Eq + Hash + fmt::Debug> fmt::Display for BlockExecutionReport<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Executed {} transactions with concurrency level {} in {:?}{}",
+            self.num_txns(),
+            self.concurrency_level,
+            self.execution_time,
+            if self.sequential_fallback {
+                " (discarded, fell back to sequential execution)"
+            } else {
+                ""
+            }
+        )?;
+        writeln!(
+            f,
+            "Incarnations: {}, invalidated reads: {}, dependency waits: {} ({:?} in total)",
+            self.total_incarnations(),
+            self.invalidations.len(),
+            self.dependency_waits.len(),
+            self.total_dependency_wait()
+        )?;
+        let conflicting_keys = self.conflicting_keys();
+        if !conflicting_keys.is_empty() {
+            writeln!(f, "Most conflicting keys:")?;
+            for (key, count) in conflicting_keys.iter().take(NUM_DISPLAYED_CONFLICTING_KEYS) {
+                writeln!(f, "  {:?}: {} invalidations", key, count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the statistics of a parallel execution from all worker threads.
+pub(crate) struct ExecutionStatsCollector<K> {
+    start_time: Instant,
+    num_incarnations: Vec<AtomicU32>,
+    dependency_wait_nanos: Vec<AtomicU64>,
+    invalidations: Mutex<Vec<ReadInvalidation<K>>>,
+    dependency_waits: Mutex<Vec<DependencyWait>>,
+}
+
+impl<K> ExecutionStatsCollector<K> {
+    pub(crate) fn new(num_txns: usize) -> Self {
+        Self {
+            start_time: Instant::now(),
+            num_incarnations: (0..num_txns).map(|_| AtomicU32::new(0)).collect(),
+            dependency_wait_nanos: (0..num_txns).map(|_| AtomicU64::new(0)).collect(),
+            invalidations: Mutex::new(Vec::new()),
+            dependency_waits: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn record_execution(&self, txn_idx: TxnIndex, incarnation: Incarnation) {
+        self.num_incarnations[txn_idx as usize].fetch_max(incarnation + 1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_invalidation(
+        &self,
+        key: K,
+        txn_idx: TxnIndex,
+        incarnation: Incarnation,
+        invalidated_by: Option<TxnIndex>,
+    ) {
+        self.invalidations.lock().push(ReadInvalidation {
+            key,
+            txn_idx,
+            incarnation,
+            invalidated_by,
+        });
+    }
+
+    pub(crate) fn record_dependency_wait(
+        &self,
+        txn_idx: TxnIndex,
+        dep_idx: TxnIndex,
+        duration: Duration,
+    ) {
+        self.dependency_wait_nanos[txn_idx as usize]
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+        self.dependency_waits.lock().push(DependencyWait {
+            txn_idx,
+            dep_idx,
+            duration,
+        });
+    }
+
+    /// Consumes the collector, producing the report. Must be called once all worker threads
+    /// are done.
+    pub(crate) fn into_report(self, concurrency_level: usize) -> BlockExecutionReport<K> {
+        let execution_time = self.start_time.elapsed();
+        let transactions = self
+            .num_incarnations
+            .into_iter()
+            .zip(self.dependency_wait_nanos)
+            .map(|(num_incarnations, wait_nanos)| TransactionExecutionStats {
+                num_incarnations: num_incarnations.into_inner(),
+                total_dependency_wait: Duration::from_nanos(wait_nanos.into_inner()),
+            })
+            .collect();
+
+        BlockExecutionReport {
+            concurrency_level,
+            execution_time,
+            sequential_fallback: false,
+            transactions,
+            invalidations: self.invalidations.into_inner(),
+            dependency_waits: self.dependency_waits.into_inner(),
+        }
+    }
+}
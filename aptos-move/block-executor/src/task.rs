@@ -79,6 +79,21 @@ pub trait TransactionOutput: Send + Sync {
     /// Get the deltas of a transaction from its output.
     fn get_deltas(&self) -> Vec<(<Self::Txn as Transaction>::Key, DeltaOp)>;
 
+    /// Get the snapshots of aggregators taken by a transaction from its output, as the
+    /// snapshot key, the aggregator key and the delta of the aggregator when the snapshot
+    /// was taken. The value of a snapshot is the delta applied to the value of the aggregator
+    /// before the transaction.
+    fn get_snapshots(
+        &self,
+    ) -> Vec<(
+        <Self::Txn as Transaction>::Key,
+        <Self::Txn as Transaction>::Key,
+        DeltaOp,
+    )>;
+
+    /// Get the amount of gas used by the transaction, counted towards the block gas limit.
+    fn gas_used(&self) -> u64;
+
     /// Execution output for transactions that comes after SkipRest signal.
     fn skip_output() -> Self;
 }
@@ -45,6 +45,10 @@ pub struct ReadDescriptor<K> {
     access_path: K,
 
     kind: ReadKind,
+
+    // Index of the transaction from whose perspective the read was performed, if it differs
+    // from the reading transaction (e.g. aggregator reads made to resolve a snapshot).
+    read_idx: Option<TxnIndex>,
 }
 
 impl<K: ModulePath> ReadDescriptor<K> {
@@ -52,6 +56,7 @@ impl<K: ModulePath> ReadDescriptor<K> {
         Self {
             access_path,
             kind: ReadKind::Version(txn_idx, incarnation),
+            read_idx: None,
         }
     }
 
@@ -59,6 +64,7 @@ impl<K: ModulePath> ReadDescriptor<K> {
         Self {
             access_path,
             kind: ReadKind::Resolved(value),
+            read_idx: None,
         }
     }
 
@@ -66,6 +72,7 @@ impl<K: ModulePath> ReadDescriptor<K> {
         Self {
             access_path,
             kind: ReadKind::Unresolved(delta),
+            read_idx: None,
         }
     }
 
@@ -73,6 +80,7 @@ impl<K: ModulePath> ReadDescriptor<K> {
         Self {
             access_path,
             kind: ReadKind::Storage,
+            read_idx: None,
         }
     }
 
@@ -80,9 +88,23 @@ impl<K: ModulePath> ReadDescriptor<K> {
         Self {
             access_path,
             kind: ReadKind::DeltaApplicationFailure,
+            read_idx: None,
         }
     }
 
+    /// Records that the read was performed from the perspective of transaction 'read_idx',
+    /// so that validation must also be performed from its perspective.
+    pub fn at_read_idx(mut self, read_idx: TxnIndex) -> Self {
+        self.read_idx = Some(read_idx);
+        self
+    }
+
+    /// Returns the index of the transaction from whose perspective the read was performed,
+    /// if it was recorded explicitly.
+    pub fn read_idx(&self) -> Option<TxnIndex> {
+        self.read_idx
+    }
+
     fn module_path(&self) -> Option<AccessPath> {
         self.access_path.module_path()
     }
@@ -91,6 +113,15 @@ impl<K: ModulePath> ReadDescriptor<K> {
         &self.access_path
     }
 
+    /// Returns the index of the transaction whose write was read, if the read returned a value
+    /// from the multi-version data-structure.
+    pub fn writer_txn_idx(&self) -> Option<TxnIndex> {
+        match self.kind {
+            ReadKind::Version(txn_idx, _) => Some(txn_idx),
+            _ => None,
+        }
+    }
+
     // Does the read descriptor describe a read from MVHashMap w. a specified version.
     pub fn validate_version(&self, version: Version) -> bool {
         let (txn_idx, incarnation) = version;
@@ -215,7 +246,7 @@ impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K,
     }
 
     // Extracts a set of paths written or updated during execution from transaction
-    // output: (modified by writes, modified by deltas).
+    // output: (modified by writes, modified by deltas, modified by snapshots).
     pub fn modified_keys(&self, txn_idx: TxnIndex) -> KeySet<T> {
         match &self.outputs[txn_idx as usize].load_full() {
             None => HashSet::new(),
@@ -225,12 +256,25 @@ impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K,
                     .into_iter()
                     .map(|(k, _)| k)
                     .chain(t.get_deltas().into_iter().map(|(k, _)| k))
+                    .chain(t.get_snapshots().into_iter().map(|(k, ..)| k))
                     .collect(),
                 ExecutionStatus::Abort(_) => HashSet::new(),
             },
         }
     }
 
+    // Returns the gas used by the recorded output of the transaction, which is counted
+    // towards the block gas limit (zero if the transaction was aborted).
+    pub fn gas_used(&self, txn_idx: TxnIndex) -> u64 {
+        match &self.outputs[txn_idx as usize].load_full() {
+            None => 0,
+            Some(txn_output) => match txn_output.as_ref() {
+                ExecutionStatus::Success(t) | ExecutionStatus::SkipRest(t) => t.gas_used(),
+                ExecutionStatus::Abort(_) => 0,
+            },
+        }
+    }
+
     // Must be executed after parallel execution is done, grabs outputs. Will panic if
     // other outstanding references to the recorded outputs exist.
     pub fn take_output(&self, txn_idx: TxnIndex) -> ExecutionStatus<T, Error<E>> {
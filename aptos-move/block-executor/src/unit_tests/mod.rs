@@ -25,17 +25,33 @@ fn run_and_assert<K, V>(transactions: Vec<Transaction<K, V>>)
 where
     K: PartialOrd + Ord + Send + Sync + Clone + Hash + Eq + ModulePath + Debug + 'static,
     V: Send + Sync + Debug + Clone + Eq + TransactionWrite + 'static,
+{
+    run_and_assert_with_block_gas_limit(transactions, None)
+}
+
+fn run_and_assert_with_block_gas_limit<K, V>(
+    transactions: Vec<Transaction<K, V>>,
+    maybe_block_gas_limit: Option<u64>,
+) where
+    K: PartialOrd + Ord + Send + Sync + Clone + Hash + Eq + ModulePath + Debug + 'static,
+    V: Send + Sync + Debug + Clone + Eq + TransactionWrite + 'static,
 {
     let data_view = DeltaDataView::<K, V> {
         phantom: PhantomData,
     };
 
-    let output =
-        BlockExecutor::<Transaction<K, V>, Task<K, V>, DeltaDataView<K, V>>::new(num_cpus::get())
-            .execute_transactions_parallel((), &transactions, &data_view)
-            .map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
+    let output = BlockExecutor::<Transaction<K, V>, Task<K, V>, DeltaDataView<K, V>>::new(
+        num_cpus::get(),
+        maybe_block_gas_limit,
+    )
+    .execute_transactions_parallel((), &transactions, &data_view, None)
+    .map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
 
-    let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+    let baseline = ExpectedOutput::generate_baseline_with_block_gas_limit(
+        &transactions,
+        None,
+        maybe_block_gas_limit,
+    );
 
     baseline.assert_output(&output);
 }
@@ -85,8 +101,7 @@ fn delta_counters() {
     run_and_assert(transactions)
 }
 
-#[test]
-fn delta_chains() {
+fn delta_chain_transactions() -> Vec<Transaction<KeyType<[u8; 32]>, ValueType<[u8; 32]>>> {
     let mut transactions = vec![];
     // Generate a series of transactions add and subtract from an aggregator.
 
@@ -127,7 +142,19 @@ fn delta_chains() {
         )
     }
 
-    run_and_assert(transactions)
+    transactions
+}
+
+#[test]
+fn delta_chains() {
+    run_and_assert(delta_chain_transactions())
+}
+
+#[test]
+fn delta_chains_block_gas_limit() {
+    // Every transaction reads 10 keys and updates 5 of them, i.e. uses 15 units of gas,
+    // so the execution is halted after the first 67 transactions.
+    run_and_assert_with_block_gas_limit(delta_chain_transactions(), Some(1000))
 }
 
 const TOTAL_KEY_NUM: u64 = 50;
@@ -249,6 +276,68 @@ fn early_skips() {
     run_and_assert(transactions)
 }
 
+#[test]
+fn execution_report() {
+    let mut transactions = vec![];
+    let keys: Vec<_> = (0..TOTAL_KEY_NUM)
+        .map(|_| KeyType(random::<[u8; 32]>(), false))
+        .collect();
+    for _ in 0..WRITES_PER_KEY {
+        for key in &keys {
+            transactions.push(Transaction::Write {
+                incarnation: Arc::new(AtomicUsize::new(0)),
+                reads: vec![vec![*key]],
+                writes_and_deltas: vec![(vec![(*key, random_value(false))], vec![])],
+            })
+        }
+    }
+
+    let data_view = DeltaDataView::<KeyType<[u8; 32]>, ValueType<Vec<u8>>> {
+        phantom: PhantomData,
+    };
+    let concurrency_level = num_cpus::get();
+    let (output, report) = BlockExecutor::<
+        Transaction<KeyType<[u8; 32]>, ValueType<Vec<u8>>>,
+        Task<KeyType<[u8; 32]>, ValueType<Vec<u8>>>,
+        DeltaDataView<KeyType<[u8; 32]>, ValueType<Vec<u8>>>,
+    >::new(concurrency_level, None)
+    .execute_block_with_report((), transactions.clone(), &data_view);
+    let output = output.map(|zipped| zipped.into_iter().map(|(res, _)| res).collect());
+    ExpectedOutput::generate_baseline(&transactions, None).assert_output(&output);
+
+    // The report is only produced by parallel execution.
+    if concurrency_level < 2 {
+        assert!(report.is_none());
+        return;
+    }
+    let report = report.unwrap();
+    assert_eq!(report.num_txns(), transactions.len());
+    assert!(!report.sequential_fallback);
+    // Every transaction is executed at least once, and re-executed once per invalidation.
+    assert!(report
+        .transactions
+        .iter()
+        .all(|stats| stats.num_incarnations >= 1));
+    assert_eq!(
+        report.total_incarnations(),
+        (transactions.len() + report.invalidations.len()) as u64
+    );
+    for invalidation in &report.invalidations {
+        assert!(keys.contains(&invalidation.key));
+        if let Some(invalidated_by) = invalidation.invalidated_by {
+            assert!(invalidated_by < invalidation.txn_idx);
+        }
+    }
+    assert_eq!(
+        report
+            .conflicting_keys()
+            .iter()
+            .map(|(_, count)| count)
+            .sum::<usize>(),
+        report.invalidations.len()
+    );
+}
+
 #[test]
 fn scheduler_tasks() {
     let s = Scheduler::new(5);
@@ -436,6 +525,28 @@ fn scheduler_dependency() {
     ));
 }
 
+#[test]
+fn scheduler_halt() {
+    let s = Scheduler::new(10);
+
+    for i in 0..5 {
+        assert!(matches!(
+            s.next_task(false),
+            SchedulerTask::ExecutionTask((j, 0), None) if j == i
+        ));
+    }
+
+    // Transaction 4 waits on a dependency on transaction 2.
+    let dep_condvar = s.wait_for_dependency(4, 2).unwrap();
+    assert!(!s.is_halted());
+
+    s.halt();
+    assert!(s.is_halted());
+    // The waiting transaction is woken up, and no more tasks are created.
+    assert!(*dep_condvar.0.lock());
+    assert!(matches!(s.next_task(false), SchedulerTask::Done));
+}
+
 // Will return a scheduler in a state where all transactions are scheduled for
 // for execution, validation index = num_txns, and wave = 0.
 fn incarnation_one_scheduler(num_txns: TxnIndex) -> Scheduler {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters, scheduler::Scheduler, task::Transaction, txn_last_input_output::ReadDescriptor,
+    counters, scheduler::Scheduler, stats::ExecutionStatsCollector, task::Transaction,
+    txn_last_input_output::ReadDescriptor,
 };
 use anyhow::Result;
 use aptos_aggregator::delta_change_set::{deserialize, serialize, DeltaOp};
@@ -20,7 +21,7 @@ use aptos_types::{
 };
 use aptos_vm_logging::{log_schema::AdapterLogSchema, prelude::*};
 use move_binary_format::errors::Location;
-use std::{cell::RefCell, collections::BTreeMap, hash::Hash, sync::Arc};
+use std::{cell::RefCell, collections::BTreeMap, hash::Hash, sync::Arc, time::Instant};
 
 /// A struct that is always used by a single thread performing an execution task. The struct is
 /// passed to the VM and acts as a proxy to resolve reads first in the shared multi-version
@@ -33,20 +34,27 @@ pub(crate) struct MVHashMapView<'a, K, V: TransactionWrite> {
     versioned_map: &'a MVHashMap<K, V, ExecutableTestType>, // TODO: proper generic type
     scheduler: &'a Scheduler,
     captured_reads: RefCell<Vec<ReadDescriptor<K>>>,
+    stats: Option<&'a ExecutionStatsCollector<K>>,
 }
 
 /// A struct which describes the result of the read from the proxy. The client
 /// can interpret these types to further resolve the reads.
 #[derive(Debug)]
-pub(crate) enum ReadResult<V> {
+pub(crate) enum ReadResult<K, V> {
     // Successful read of a value.
     Value(Arc<V>),
     // Similar to above, but the value was aggregated and is an integer.
     U128(u128),
     // Read failed while resolving a delta.
     Unresolved(DeltaOp),
+    // Read returned a snapshot of the aggregator at the given key. The value of the snapshot
+    // is the given delta applied to the value of the aggregator from the perspective of the
+    // transaction with the given index.
+    Snapshot(K, DeltaOp, TxnIndex),
     // Read did not return anything.
     None,
+    // Read waited on a dependency, but the parallel execution was halted in the meantime.
+    ExecutionHalted,
 }
 
 impl<
@@ -58,11 +66,13 @@ impl<
     pub(crate) fn new(
         versioned_map: &'a MVHashMap<K, V, ExecutableTestType>,
         scheduler: &'a Scheduler,
+        stats: Option<&'a ExecutionStatsCollector<K>>,
     ) -> Self {
         Self {
             versioned_map,
             scheduler,
             captured_reads: RefCell::new(Vec::new()),
+            stats,
         }
     }
 
@@ -87,37 +97,56 @@ impl<
     }
 
     /// Captures a read from the VM execution.
-    fn fetch_data(&self, key: &K, txn_idx: TxnIndex) -> ReadResult<V> {
+    fn fetch_data(&self, key: &K, txn_idx: TxnIndex) -> ReadResult<K, V> {
+        self.fetch_data_at(key, txn_idx, txn_idx)
+    }
+
+    /// Captures a read by transaction 'txn_idx' performed from the perspective of transaction
+    /// 'read_idx' (not higher than 'txn_idx'), e.g. to resolve the value of a snapshot.
+    fn fetch_data_at(&self, key: &K, txn_idx: TxnIndex, read_idx: TxnIndex) -> ReadResult<K, V> {
         use MVDataError::*;
         use MVDataOutput::*;
 
+        let capture = |read: ReadDescriptor<K>| {
+            let read = if read_idx == txn_idx {
+                read
+            } else {
+                read.at_read_idx(read_idx)
+            };
+            self.captured_reads.borrow_mut().push(read);
+        };
+
         loop {
-            match self.versioned_map.fetch_data(key, txn_idx) {
+            match self.versioned_map.fetch_data(key, read_idx) {
                 Ok(Versioned(version, v)) => {
                     let (idx, incarnation) = version;
-                    self.captured_reads
-                        .borrow_mut()
-                        .push(ReadDescriptor::from_version(key.clone(), idx, incarnation));
+                    capture(ReadDescriptor::from_version(key.clone(), idx, incarnation));
                     return ReadResult::Value(v);
                 },
                 Ok(Resolved(value)) => {
-                    self.captured_reads
-                        .borrow_mut()
-                        .push(ReadDescriptor::from_resolved(key.clone(), value));
+                    capture(ReadDescriptor::from_resolved(key.clone(), value));
                     return ReadResult::U128(value);
                 },
                 Err(NotFound) => {
-                    self.captured_reads
-                        .borrow_mut()
-                        .push(ReadDescriptor::from_storage(key.clone()));
+                    capture(ReadDescriptor::from_storage(key.clone()));
                     return ReadResult::None;
                 },
                 Err(Unresolved(delta)) => {
-                    self.captured_reads
-                        .borrow_mut()
-                        .push(ReadDescriptor::from_unresolved(key.clone(), delta));
+                    capture(ReadDescriptor::from_unresolved(key.clone(), delta));
                     return ReadResult::Unresolved(delta);
                 },
+                Err(Snapshot(version)) => {
+                    let (idx, incarnation) = version;
+                    // The entry may have been overwritten by a re-execution in the meantime,
+                    // in which case the read is retried.
+                    if let Some((source, delta)) = self.versioned_map.snapshot_source(key, version)
+                    {
+                        capture(ReadDescriptor::from_version(key.clone(), idx, incarnation));
+                        // The delta of the snapshot applies to the value of the aggregator
+                        // before the snapshotting transaction.
+                        return ReadResult::Snapshot(source, delta, idx);
+                    }
+                },
                 Err(Dependency(dep_idx)) => {
                     // `self.txn_idx` estimated to depend on a write from `dep_idx`.
                     match self.scheduler.wait_for_dependency(txn_idx, dep_idx) {
@@ -137,11 +166,30 @@ impl<
                             // thread that aborted dep_idx was alive, and again, since lower txns
                             // than txn_idx are not blocked, so the execution of dep_idx will
                             // eventually finish and lead to unblocking txn_idx, contradiction.
+                            //
+                            // If the scheduler is halted, the dependency may never be resolved,
+                            // but all waiting transactions are notified by the halt. Checking
+                            // after the suspension (in wait_for_dependency) guarantees that a
+                            // transaction suspended after the halt does not wait.
+                            if self.scheduler.is_halted() {
+                                return ReadResult::ExecutionHalted;
+                            }
+                            let wait_start = self.stats.map(|_| Instant::now());
                             let (lock, cvar) = &*dep_condition;
                             let mut dep_resolved = lock.lock();
                             while !*dep_resolved {
                                 dep_resolved = cvar.wait(dep_resolved).unwrap();
                             }
+                            if let (Some(stats), Some(wait_start)) = (self.stats, wait_start) {
+                                stats.record_dependency_wait(
+                                    txn_idx,
+                                    dep_idx,
+                                    wait_start.elapsed(),
+                                );
+                            }
+                            if self.scheduler.is_halted() {
+                                return ReadResult::ExecutionHalted;
+                            }
                         },
                         None => continue,
                     }
@@ -150,9 +198,7 @@ impl<
                     // Delta application failure currently should never happen. Here, we assume it
                     // happened because of speculation and return 0 to the Move-VM. Validation will
                     // ensure the transaction re-executes if 0 wasn't the right number.
-                    self.captured_reads
-                        .borrow_mut()
-                        .push(ReadDescriptor::from_delta_application_failure(key.clone()));
+                    capture(ReadDescriptor::from_delta_application_failure(key.clone()));
                     return ReadResult::U128(0);
                 },
             };
@@ -196,6 +242,37 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>> LatestView<'a, T, S> {
         }
     }
 
+    fn resolve_read_result(
+        &self,
+        state_key: &T::Key,
+        result: ReadResult<T::Key, T::Value>,
+    ) -> anyhow::Result<Option<StateValue>> {
+        match result {
+            ReadResult::Value(v) => Ok(v.as_state_value()),
+            ReadResult::U128(v) => Ok(Some(StateValue::new_legacy(serialize(&v)))),
+            ReadResult::Unresolved(delta) => {
+                let from_storage = self.base_view.get_state_value_bytes(state_key)?.map_or(
+                    Err(VMStatus::Error(StatusCode::STORAGE_ERROR, None)),
+                    |bytes| Ok(deserialize(&bytes)),
+                )?;
+                let result = delta
+                    .apply_to(from_storage)
+                    .map_err(|pe| pe.finish(Location::Undefined).into_vm_status())?;
+                Ok(Some(StateValue::new_legacy(serialize(&result))))
+            },
+            ReadResult::None => self.get_base_value(state_key),
+            ReadResult::Snapshot(..) => {
+                // Snapshots are only ever taken of aggregators, never of other snapshots.
+                Err(VMStatus::Error(StatusCode::STORAGE_ERROR, None).into())
+            },
+            ReadResult::ExecutionHalted => {
+                // Return anything (e.g. the value does not exist), because the output
+                // of the ongoing execution is discarded once the execution is halted.
+                Ok(None)
+            },
+        }
+    }
+
     fn get_base_value(&self, state_key: &T::Key) -> anyhow::Result<Option<StateValue>> {
         let ret = self.base_view.get_state_value(state_key);
 
@@ -235,20 +312,23 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>> TStateView for LatestView<
                     }
                 },
                 None => match map.fetch_data(state_key, self.txn_idx) {
-                    ReadResult::Value(v) => Ok(v.as_state_value()),
-                    ReadResult::U128(v) => Ok(Some(StateValue::new_legacy(serialize(&v)))),
-                    ReadResult::Unresolved(delta) => {
-                        let from_storage =
-                            self.base_view.get_state_value_bytes(state_key)?.map_or(
-                                Err(VMStatus::Error(StatusCode::STORAGE_ERROR, None)),
-                                |bytes| Ok(deserialize(&bytes)),
-                            )?;
+                    ReadResult::Snapshot(source, delta, read_idx) => {
+                        // Resolve the snapshot by applying its delta to the aggregator it
+                        // was taken of.
+                        let result = map.fetch_data_at(&source, self.txn_idx, read_idx);
+                        if let ReadResult::ExecutionHalted = result {
+                            return Ok(None);
+                        }
+                        let from_source = self.resolve_read_result(&source, result)?.map_or(
+                            Err(VMStatus::Error(StatusCode::STORAGE_ERROR, None)),
+                            |value| Ok(deserialize(value.bytes())),
+                        )?;
                         let result = delta
-                            .apply_to(from_storage)
+                            .apply_to(from_source)
                             .map_err(|pe| pe.finish(Location::Undefined).into_vm_status())?;
                         Ok(Some(StateValue::new_legacy(serialize(&result))))
                     },
-                    ReadResult::None => self.get_base_value(state_key),
+                    result => self.resolve_read_result(state_key, result),
                 },
             },
             ViewMapKind::BTree(map) => map.get(state_key).map_or_else(
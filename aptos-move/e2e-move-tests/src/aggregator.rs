@@ -191,3 +191,71 @@ pub fn sub_and_materialize(
         ],
     )
 }
+
+pub fn check_snapshot(
+    harness: &mut MoveHarness,
+    account: &Account,
+    index: u64,
+    expected: u128,
+) -> SignedTransaction {
+    harness.create_entry_function(
+        account,
+        str::parse("0x1::aggregator_test::check_snapshot").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(&index).unwrap(),
+            bcs::to_bytes(&expected).unwrap(),
+        ],
+    )
+}
+
+pub fn add_and_snapshot(
+    harness: &mut MoveHarness,
+    account: &Account,
+    index: u64,
+    snapshot_index: u64,
+    value: u128,
+) -> SignedTransaction {
+    harness.create_entry_function(
+        account,
+        str::parse("0x1::aggregator_test::add_and_snapshot").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(&index).unwrap(),
+            bcs::to_bytes(&snapshot_index).unwrap(),
+            bcs::to_bytes(&value).unwrap(),
+        ],
+    )
+}
+
+pub fn snapshot_and_add(
+    harness: &mut MoveHarness,
+    account: &Account,
+    index: u64,
+    snapshot_index: u64,
+    value: u128,
+) -> SignedTransaction {
+    harness.create_entry_function(
+        account,
+        str::parse("0x1::aggregator_test::snapshot_and_add").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(&index).unwrap(),
+            bcs::to_bytes(&snapshot_index).unwrap(),
+            bcs::to_bytes(&value).unwrap(),
+        ],
+    )
+}
+
+pub fn snapshot_and_read(
+    harness: &mut MoveHarness,
+    account: &Account,
+    index: u64,
+) -> SignedTransaction {
+    harness.create_entry_function(
+        account,
+        str::parse("0x1::aggregator_test::snapshot_and_read").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&index).unwrap()],
+    )
+}
@@ -1,7 +1,7 @@
 module 0x1::aggregator_test {
     use std::signer;
 
-    use aptos_framework::aggregator::{Self, Aggregator};
+    use aptos_framework::aggregator::{Self, Aggregator, AggregatorSnapshot};
     use aptos_framework::aggregator_factory;
     use aptos_std::table::{Self, Table};
 
@@ -12,12 +12,14 @@ module 0x1::aggregator_test {
     /// determinictic integer value, for testing purposes.
     struct AggregatorStore has key, store {
         aggregators: Table<u64, Aggregator>,
+        snapshots: Table<u64, AggregatorSnapshot>,
     }
 
     /// Initializes a fake resource which holds aggregators.
     public entry fun initialize(account: &signer) {
         let aggregators = table::new();
-        let store = AggregatorStore { aggregators };
+        let snapshots = table::new();
+        let store = AggregatorStore { aggregators, snapshots };
         move_to(account, store);
     }
 
@@ -31,6 +33,15 @@ module 0x1::aggregator_test {
         assert!(actual == expected, ENOT_EQUAL)
     }
 
+    /// Checks that the jth snapshot has expected value.
+    public entry fun check_snapshot(account: &signer, j: u64, expected: u128) acquires AggregatorStore {
+        let addr = signer::address_of(account);
+        let snapshots = &borrow_global<AggregatorStore>(addr).snapshots;
+        let snapshot = table::borrow(snapshots, j);
+        let actual = aggregator::read_snapshot(snapshot);
+        assert!(actual == expected, ENOT_EQUAL)
+    }
+
     //
     // Testing scripts.
     //
@@ -110,4 +121,31 @@ module 0x1::aggregator_test {
         aggregator::sub(aggregator, value);
         aggregator::read(aggregator);
     }
+
+    public entry fun add_and_snapshot(account: &signer, i: u64, j: u64, value: u128) acquires AggregatorStore {
+        let addr = signer::address_of(account);
+        let store = borrow_global_mut<AggregatorStore>(addr);
+        let aggregator = table::borrow_mut(&mut store.aggregators, i);
+        aggregator::add(aggregator, value);
+        let snapshot = aggregator::snapshot(aggregator);
+        table::add(&mut store.snapshots, j, snapshot);
+    }
+
+    public entry fun snapshot_and_add(account: &signer, i: u64, j: u64, value: u128) acquires AggregatorStore {
+        let addr = signer::address_of(account);
+        let store = borrow_global_mut<AggregatorStore>(addr);
+        let aggregator = table::borrow_mut(&mut store.aggregators, i);
+        let snapshot = aggregator::snapshot(aggregator);
+        aggregator::add(aggregator, value);
+        table::add(&mut store.snapshots, j, snapshot);
+    }
+
+    public entry fun snapshot_and_read(account: &signer, i: u64) acquires AggregatorStore {
+        let addr = signer::address_of(account);
+        let aggregators = &borrow_global<AggregatorStore>(addr).aggregators;
+        let aggregator = table::borrow(aggregators, i);
+        let snapshot = aggregator::snapshot(aggregator);
+        aggregator::read_snapshot(&snapshot);
+        aggregator::destroy_snapshot(snapshot);
+    }
 }
@@ -3,15 +3,16 @@
 
 use crate::{
     aggregator::{
-        add, add_and_materialize, check, destroy, initialize, materialize, materialize_and_add,
-        materialize_and_sub, new, sub, sub_add, sub_and_materialize,
+        add, add_and_materialize, add_and_snapshot, check, check_snapshot, destroy, initialize,
+        materialize, materialize_and_add, materialize_and_sub, new, snapshot_and_add,
+        snapshot_and_read, sub, sub_add, sub_and_materialize,
     },
     assert_abort, assert_success,
     tests::common,
     MoveHarness,
 };
 use aptos_language_e2e_tests::account::Account;
-use aptos_types::transaction::SignedTransaction;
+use aptos_types::{on_chain_config::FeatureFlag, transaction::SignedTransaction};
 
 fn setup() -> (MoveHarness, Account) {
     initialize(common::test_dir_path("aggregator.data/pack"))
@@ -161,3 +162,88 @@ fn test_aggregator_materialize_overflow() {
     assert_success!(h.run(txn1));
     assert_abort!(h.run(txn2), 131073);
 }
+
+#[test]
+fn test_aggregator_snapshots() {
+    let (mut h, acc) = setup();
+    h.enable_features(vec![FeatureFlag::AGGREGATOR_API], vec![]);
+    let block_size = 100;
+
+    let txn = new(&mut h, &acc, 0, 1000);
+    assert_success!(h.run(txn));
+
+    // All transactions modify and snapshot the same aggregator, but can still be
+    // executed in parallel.
+    let txns: Vec<SignedTransaction> = (0..block_size)
+        .map(|j| add_and_snapshot(&mut h, &acc, 0, j, 2))
+        .collect();
+    let outputs = h.run_block(txns);
+    for status in outputs {
+        assert_success!(status);
+    }
+
+    // Every snapshot observes the value of the aggregator when it was taken.
+    let mut txns: Vec<SignedTransaction> = (0..block_size)
+        .map(|j| check_snapshot(&mut h, &acc, j, (j as u128 + 1) * 2))
+        .collect();
+    txns.push(check(&mut h, &acc, 0, (block_size as u128) * 2));
+    let outputs = h.run_block(txns);
+    for status in outputs {
+        assert_success!(status);
+    }
+}
+
+#[test]
+fn test_aggregator_snapshots_before_add() {
+    let (mut h, acc) = setup();
+    h.enable_features(vec![FeatureFlag::AGGREGATOR_API], vec![]);
+    let block_size = 100;
+
+    let txn = new(&mut h, &acc, 0, 1000);
+    assert_success!(h.run(txn));
+
+    // Snapshots are taken before the aggregator is modified by the same transaction.
+    let txns: Vec<SignedTransaction> = (0..block_size)
+        .map(|j| snapshot_and_add(&mut h, &acc, 0, j, 2))
+        .collect();
+    let outputs = h.run_block(txns);
+    for status in outputs {
+        assert_success!(status);
+    }
+
+    // Every snapshot observes the value of the aggregator before the modification.
+    let mut txns: Vec<SignedTransaction> = (0..block_size)
+        .map(|j| check_snapshot(&mut h, &acc, j, j as u128 * 2))
+        .collect();
+    txns.push(check(&mut h, &acc, 0, (block_size as u128) * 2));
+    let outputs = h.run_block(txns);
+    for status in outputs {
+        assert_success!(status);
+    }
+}
+
+#[test]
+fn test_aggregator_snapshot_not_ready() {
+    let (mut h, acc) = setup();
+    h.enable_features(vec![FeatureFlag::AGGREGATOR_API], vec![]);
+
+    let txn1 = new(&mut h, &acc, 0, 1000);
+    let txn2 = snapshot_and_read(&mut h, &acc, 0);
+
+    // Reading a snapshot in the transaction which took it aborts with ESNAPSHOT_NOT_READY.
+    assert_success!(h.run(txn1));
+    assert_abort!(h.run(txn2), 196613);
+}
+
+#[test]
+fn test_aggregator_snapshot_disabled() {
+    let (mut h, acc) = setup();
+    h.enable_features(vec![], vec![FeatureFlag::AGGREGATOR_API]);
+
+    let txn1 = new(&mut h, &acc, 0, 1000);
+    let txn2 = add_and_snapshot(&mut h, &acc, 0, 0, 1);
+
+    // Snapshots abort with EAGGREGATOR_API_NOT_ENABLED unless the feature is enabled.
+    assert_success!(h.run(txn1));
+    assert_abort!(h.run(txn2), 196612);
+}
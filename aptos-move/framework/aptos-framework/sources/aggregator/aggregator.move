@@ -8,9 +8,17 @@
 /// dependency.
 /// However, reading the aggregator value (i.e. calling `read(X)`) is an expensive
 /// operation and should be avoided as much as possible because it reduces the
-/// parallelism. Moreover, **aggregators can only be created by Aptos Framework (0x1)
-/// at the moment.**
+/// parallelism. Instead, a transaction which only needs to record the value, e.g. to
+/// assign an index to a newly minted token, can take a snapshot (`snapshot(X)`). A
+/// snapshot does not read the aggregator: its value is the value of the aggregator at
+/// the time the snapshot was taken, and can be read by later transactions
+/// with `read_snapshot`.
+/// Aggregators can only be created by Aptos Framework (0x1), unless the aggregator API
+/// feature is enabled (see `std::features::aggregator_api_enabled`), in which case any
+/// account can create them and take snapshots of them.
 module aptos_framework::aggregator {
+    use std::error;
+    use std::features;
 
     /// The value of aggregator overflows. Raised by native code.
     const EAGGREGATOR_OVERFLOW: u64 = 1;
@@ -21,6 +29,17 @@ module aptos_framework::aggregator {
     /// Aggregator feature is not supported. Raised by native code.
     const ENOT_SUPPORTED: u64 = 3;
 
+    /// Snapshots of aggregators are not enabled.
+    const EAGGREGATOR_API_NOT_ENABLED: u64 = 4;
+
+    /// The value of a snapshot is not known until the transaction which took it
+    /// commits. Raised by native code.
+    const ESNAPSHOT_NOT_READY: u64 = 5;
+
+    /// An aggregator cannot be destroyed by the transaction which took a snapshot of
+    /// it without knowing its value. Raised by native code.
+    const EAGGREGATOR_HAS_SNAPSHOTS: u64 = 6;
+
     /// Represents an integer which supports parallel additions and subtractions
     /// across multiple transactions. See the module description for more details.
     struct Aggregator has store {
@@ -29,6 +48,12 @@ module aptos_framework::aggregator {
         limit: u128,
     }
 
+    /// Represents the value of an aggregator at the time the snapshot was taken.
+    struct AggregatorSnapshot has store {
+        handle: address,
+        key: address,
+    }
+
     /// Returns `limit` exceeding which aggregator overflows.
     public fun limit(aggregator: &Aggregator): u128 {
         aggregator.limit
@@ -45,4 +70,20 @@ module aptos_framework::aggregator {
 
     /// Destroys an aggregator and removes it from its `AggregatorFactory`.
     public native fun destroy(aggregator: Aggregator);
+
+    /// Takes a snapshot of an aggregator. Unlike `read`, this does not make the
+    /// transaction depend on other transactions modifying the aggregator.
+    public fun snapshot(aggregator: &Aggregator): AggregatorSnapshot {
+        assert!(features::aggregator_api_enabled(), error::invalid_state(EAGGREGATOR_API_NOT_ENABLED));
+        snapshot_internal(aggregator)
+    }
+
+    /// Returns the value of the aggregator at the time the snapshot was taken.
+    /// Aborts if called by the transaction which took the snapshot.
+    public native fun read_snapshot(snapshot: &AggregatorSnapshot): u128;
+
+    /// Destroys a snapshot.
+    public native fun destroy_snapshot(snapshot: AggregatorSnapshot);
+
+    native fun snapshot_internal(aggregator: &Aggregator): AggregatorSnapshot;
 }
@@ -32,6 +32,25 @@ spec aptos_framework::aggregator {
         aborts_if false;
     }
 
+    spec snapshot(aggregator: &Aggregator): AggregatorSnapshot {
+        use std::features;
+        pragma opaque;
+        aborts_if !features::spec_is_enabled(features::AGGREGATOR_API);
+    }
+
+    spec snapshot_internal(aggregator: &Aggregator): AggregatorSnapshot {
+        pragma opaque;
+    }
+
+    spec read_snapshot(snapshot: &AggregatorSnapshot): u128 {
+        pragma opaque;
+    }
+
+    spec destroy_snapshot(snapshot: AggregatorSnapshot) {
+        pragma opaque;
+        aborts_if false;
+    }
+
     spec native fun spec_read(aggregator: Aggregator): u128;
     spec native fun spec_get_limit(a: Aggregator): u128;
     spec native fun spec_get_handle(a: Aggregator): u128;
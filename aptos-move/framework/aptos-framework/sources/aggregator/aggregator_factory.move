@@ -1,10 +1,10 @@
-/// This module provides foundations to create aggregators. Currently only
-/// Aptos Framework (0x1) can create them, so this module helps to wrap
-/// the constructor of `Aggregator` struct so that only a system account
-/// can initialize one. In the future, this might change and aggregators
-/// can be enabled for the public.
+/// This module provides foundations to create aggregators. Unless the aggregator
+/// API feature is enabled, only Aptos Framework (0x1) can create them, so this
+/// module helps to wrap the constructor of `Aggregator` struct so that only a
+/// system account can initialize one.
 module aptos_framework::aggregator_factory {
     use std::error;
+    use std::features;
 
     use aptos_framework::system_addresses;
     use aptos_std::aggregator::Aggregator;
@@ -17,8 +17,7 @@ module aptos_framework::aggregator_factory {
     const EAGGREGATOR_FACTORY_NOT_FOUND: u64 = 1;
 
     /// Creates new aggregators. Used to control the numbers of aggregators in the
-    /// system and who can create them. At the moment, only Aptos Framework (0x1)
-    /// account can.
+    /// system and who can create them.
     struct AggregatorFactory has key {
         phantom_table: Table<address, u128>,
     }
@@ -43,11 +42,12 @@ module aptos_framework::aggregator_factory {
         new_aggregator(aggregator_factory, limit)
     }
 
-    /// This is currently a function closed for public. This can be updated in the future by on-chain governance
-    /// to allow any signer to call.
+    /// Creates a new aggregator instance which overflows on exceeding a `limit`. Only Aptos Framework (0x1)
+    /// can call this, unless the aggregator API feature is enabled by on-chain governance.
     public fun create_aggregator(account: &signer, limit: u128): Aggregator acquires AggregatorFactory {
-        // Only Aptos Framework (0x1) account can call this for now.
-        system_addresses::assert_aptos_framework(account);
+        if (!features::aggregator_api_enabled()) {
+            system_addresses::assert_aptos_framework(account);
+        };
         create_aggregator_internal(limit)
     }
 
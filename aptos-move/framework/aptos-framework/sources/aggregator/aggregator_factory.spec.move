@@ -30,12 +30,13 @@ spec aptos_framework::aggregator_factory {
         aborts_if !exists<AggregatorFactory>(@aptos_framework);
     }
 
-    /// Make sure the caller is @aptos_framework.
+    /// Make sure the caller is @aptos_framework, unless the aggregator API is enabled.
     /// AggregatorFactory existed under the @aptos_framework when Creating a new aggregator.
     spec create_aggregator(account: &signer, limit: u128): Aggregator {
         use std::signer;
+        use std::features;
         let addr = signer::address_of(account);
-        aborts_if addr != @aptos_framework;
+        aborts_if !features::spec_is_enabled(features::AGGREGATOR_API) && addr != @aptos_framework;
         aborts_if !exists<AggregatorFactory>(@aptos_framework);
     }
 
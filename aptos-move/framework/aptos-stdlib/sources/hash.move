@@ -6,6 +6,9 @@
 ///
 /// Non-cryptograhic hashes:
 /// - SipHash: an add-rotate-xor (ARX) based family of pseudorandom functions created by Jean-Philippe Aumasson and Daniel J. Bernstein in 2012
+///
+/// Arithmetic (i.e., SNARK-friendly) hashes:
+/// - Poseidon: see https://eprint.iacr.org/2019/458.pdf, over the BN254 and BLS12-381 scalar fields
 module aptos_std::aptos_hash {
     use std::bcs;
     use std::features;
@@ -17,6 +20,15 @@ module aptos_std::aptos_hash {
     /// A newly-added native function is not yet enabled.
     const E_NATIVE_FUN_NOT_AVAILABLE: u64 = 1;
 
+    /// The number of inputs to a Poseidon hash is not between 1 and `POSEIDON_MAX_NUM_INPUTS`.
+    const E_POSEIDON_INVALID_NUM_INPUTS: u64 = 2;
+
+    /// An input to a Poseidon hash is not a canonically-serialized scalar field element.
+    const E_POSEIDON_INVALID_INPUT: u64 = 3;
+
+    /// The maximum number of inputs to a Poseidon hash.
+    const POSEIDON_MAX_NUM_INPUTS: u64 = 16;
+
     //
     // Functions
     //
@@ -74,6 +86,34 @@ module aptos_std::aptos_hash {
         blake2b_256_internal(bytes)
     }
 
+    /// Returns the Poseidon hash of `inputs` over the BN254 scalar field, which is the same as the output of
+    /// circomlib's `Poseidon(n)` template.
+    ///
+    /// Each input, as well as the output, is a BN254 scalar field element serialized into 32 bytes with the least
+    /// significant byte first (i.e., the `aptos_std::bn254_algebra::FormatFrLsb` format).
+    /// Aborts if there are not between 1 and 16 inputs, or if an input is not a canonically-serialized element.
+    public fun poseidon_bn254(inputs: vector<vector<u8>>): vector<u8> {
+        if(!features::poseidon_hash_natives_enabled()) {
+            abort(std::error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        poseidon_bn254_internal(inputs)
+    }
+
+    /// Returns the Poseidon hash of `inputs` over the BLS12-381 scalar field, using the same instantiation as
+    /// `poseidon_bn254`.
+    ///
+    /// Each input, as well as the output, is a BLS12-381 scalar field element serialized into 32 bytes with the least
+    /// significant byte first (i.e., the `aptos_std::bls12381_algebra::FormatFrLsb` format).
+    /// Aborts if there are not between 1 and 16 inputs, or if an input is not a canonically-serialized element.
+    public fun poseidon_bls12381(inputs: vector<vector<u8>>): vector<u8> {
+        if(!features::poseidon_hash_natives_enabled()) {
+            abort(std::error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        poseidon_bls12381_internal(inputs)
+    }
+
     //
     // Private native functions
     //
@@ -94,6 +134,12 @@ module aptos_std::aptos_hash {
     /// Returns the BLAKE2B-256 hash of `bytes`.
     native fun blake2b_256_internal(bytes: vector<u8>): vector<u8>;
 
+    /// Returns the Poseidon hash of `inputs` over the BN254 scalar field.
+    native fun poseidon_bn254_internal(inputs: vector<vector<u8>>): vector<u8>;
+
+    /// Returns the Poseidon hash of `inputs` over the BLS12-381 scalar field.
+    native fun poseidon_bls12381_internal(inputs: vector<vector<u8>>): vector<u8>;
+
     //
     // Testing
     //
@@ -250,4 +296,125 @@ module aptos_std::aptos_hash {
             i = i + 1;
         };
     }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 196609, location = Self)]
+    fun poseidon_bn254_aborts(fx: signer) {
+        // We disable the feature to make sure the `poseidon_bn254` call aborts
+        features::change_feature_flags(&fx, vector[], vector[features::get_poseidon_hash_natives_feature()]);
+
+        poseidon_bn254(vector[x"0100000000000000000000000000000000000000000000000000000000000000"]);
+    }
+
+    #[test_only]
+    // Returns the inputs `1, 2, ..., n`, serialized with the least significant byte first.
+    fun poseidon_test_inputs(n: u64): vector<vector<u8>> {
+        let inputs = vector[];
+        let i = 1;
+        while (i <= n) {
+            let input = std::vector::singleton((i as u8));
+            let j = 1;
+            while (j < 32) {
+                std::vector::push_back(&mut input, 0);
+                j = j + 1;
+            };
+            std::vector::push_back(&mut inputs, input);
+            i = i + 1;
+        };
+        inputs
+    }
+
+    #[test(fx = @aptos_std)]
+    fun poseidon_bn254_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        let num_inputs = vector[1, 2, 4, POSEIDON_MAX_NUM_INPUTS];
+
+        // Computed with circomlibjs, e.g., `poseidon([1, 2])`, serialized with the least significant byte first.
+        // The outputs for 2 and 4 inputs are also the first elements of the `poseidonperm_x5_254_3` and
+        // `poseidonperm_x5_254_5` test vectors of the reference implementation.
+        let outputs = vector[
+        x"33018202c57d898b84338b16d1a4960e133c6a4d656cfec1bd62a9ea00611729",
+        x"9a1817447a60199e51453274f217362acfe962966b4cf63d4190d6e7f5c05c11",
+        x"65042565df25a5ba3d66e01cbb0ee637980b51e440face9dd7fdc1b67d869c29",
+        x"65b719b996fb2fde06ecf2f1d7af08ae49f9ff9980a481821066bb1c559a1516",
+        ];
+
+        let i = 0;
+        while (i < std::vector::length(&num_inputs)) {
+            let inputs = poseidon_test_inputs(*std::vector::borrow(&num_inputs, i));
+            let hash_expected = *std::vector::borrow(&outputs, i);
+            let hash = poseidon_bn254(inputs);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    fun poseidon_bls12381_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        let num_inputs = vector[1, 2, 4, POSEIDON_MAX_NUM_INPUTS];
+
+        // The outputs for 2 and 4 inputs are the first elements of the `poseidonperm_x5_255_3` and
+        // `poseidonperm_x5_255_5` test vectors of the reference implementation, whose inputs are `[0, 1, 2]` and
+        // `[0, 1, 2, 3, 4]`. The others were computed with a Python port of the reference parameter generation script
+        // and permutation. All are serialized with the least significant byte first.
+        let outputs = vector[
+        x"ea6ce53dad0362cf20a921c8f2169942947b020e925f1a0d44c6db016b6fa649",
+        x"8aa7d27d314e4bcbe4e9182cbe6671d6c9f5988c1ead5355a046c20f4219ce28",
+        x"182e20220b1a90131beedc9373fcf607577b291ec8319350bbd79b9f9c8b912a",
+        x"3e60cded77daa91e80a888b6652bf54fe00f799ff2de74ce9a73f1d0a8f4955b",
+        ];
+
+        let i = 0;
+        while (i < std::vector::length(&num_inputs)) {
+            let inputs = poseidon_test_inputs(*std::vector::borrow(&num_inputs, i));
+            let hash_expected = *std::vector::borrow(&outputs, i);
+            let hash = poseidon_bls12381(inputs);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun poseidon_bn254_aborts_on_no_inputs(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        poseidon_bn254(vector[]);
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun poseidon_bn254_aborts_on_too_many_inputs(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        poseidon_bn254(poseidon_test_inputs(POSEIDON_MAX_NUM_INPUTS + 1));
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010003, location = Self)]
+    fun poseidon_bn254_aborts_on_non_canonical_input(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        // The BN254 scalar field order, which is not a canonically-serialized element.
+        poseidon_bn254(vector[x"010000f093f5e1439170b97948e833285d588181b64550b829a031e1724e6430"]);
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010003, location = Self)]
+    fun poseidon_bls12381_aborts_on_non_canonical_input(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        // The BLS12-381 scalar field order, which is not a canonically-serialized element.
+        poseidon_bls12381(vector[x"01000000fffffffffe5bfeff02a4bd5305d8a10908d83933487d9d2953a7ed73"]);
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010003, location = Self)]
+    fun poseidon_bls12381_aborts_on_wrong_input_size(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_hash_natives_feature()], vector[]);
+        poseidon_bls12381(vector[x"0100"]);
+    }
 }
@@ -27,6 +27,12 @@ spec aptos_std::aptos_hash {
         fun spec_blake2b_256_internal(bytes: vector<u8>): vector<u8>;
         axiom forall b1: vector<u8>, b2: vector<u8>:
             (spec_blake2b_256_internal(b1) == spec_blake2b_256_internal(b2) ==> b1 == b2);
+
+        /// `spec_poseidon_bn254_internal` is not assumed to be injective, as it hashes field elements.
+        fun spec_poseidon_bn254_internal(inputs: vector<vector<u8>>): vector<u8>;
+
+        /// `spec_poseidon_bls12381_internal` is not assumed to be injective, as it hashes field elements.
+        fun spec_poseidon_bls12381_internal(inputs: vector<vector<u8>>): vector<u8>;
     }
 
     spec sip_hash(bytes: vector<u8>): u64 {
@@ -94,4 +100,27 @@ spec aptos_std::aptos_hash {
         ensures result == spec_blake2b_256_internal(bytes);
     }
 
+    spec poseidon_bn254_internal(inputs: vector<vector<u8>>): vector<u8> {
+        pragma opaque;
+        ensures [abstract] result == spec_poseidon_bn254_internal(inputs);
+    }
+
+    spec poseidon_bn254(inputs: vector<vector<u8>>): vector<u8> {
+        pragma opaque;
+        pragma aborts_if_is_partial;
+        aborts_if !features::spec_is_enabled(features::POSEIDON_HASH_NATIVES);
+        ensures result == spec_poseidon_bn254_internal(inputs);
+    }
+
+    spec poseidon_bls12381_internal(inputs: vector<vector<u8>>): vector<u8> {
+        pragma opaque;
+        ensures [abstract] result == spec_poseidon_bls12381_internal(inputs);
+    }
+
+    spec poseidon_bls12381(inputs: vector<vector<u8>>): vector<u8> {
+        pragma opaque;
+        pragma aborts_if_is_partial;
+        aborts_if !features::spec_is_enabled(features::POSEIDON_HASH_NATIVES);
+        ensures result == spec_poseidon_bls12381_internal(inputs);
+    }
 }
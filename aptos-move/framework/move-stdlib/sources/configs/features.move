@@ -183,6 +183,45 @@ module std::features {
         is_enabled(PARTIAL_GOVERNANCE_VOTING)
     }
 
+    /// Whether transactions can be authenticated with secp256k1 ECDSA signatures.
+    /// Lifetime: transient
+    const SECP256K1_ECDSA_AUTHENTICATOR: u64 = 18;
+
+    /// Whether transactions can be authenticated with WebAuthn (secp256r1 ECDSA) assertions.
+    /// Lifetime: transient
+    const WEBAUTHN_AUTHENTICATOR: u64 = 19;
+
+    /// Whether the generic algebra implementation for BN254 operations are enabled.
+    ///
+    /// Lifetime: transient
+    const BN254_STRUCTURES: u64 = 20;
+    public fun get_bn254_structures_feature(): u64 { BN254_STRUCTURES }
+    public fun bn254_structures_enabled(): bool acquires Features {
+        is_enabled(BN254_STRUCTURES)
+    }
+
+    /// Whether the Poseidon hash natives in `aptos_hash` are enabled.
+    /// This is needed because of the introduction of new native functions.
+    /// Lifetime: transient
+    const POSEIDON_HASH_NATIVES: u64 = 21;
+
+    public fun get_poseidon_hash_natives_feature(): u64 { POSEIDON_HASH_NATIVES }
+
+    public fun poseidon_hash_natives_enabled(): bool acquires Features {
+        is_enabled(POSEIDON_HASH_NATIVES)
+    }
+
+    /// Whether any account can create aggregators and take snapshots of them, using the API in
+    /// `aptos_framework::aggregator_factory` and `aptos_framework::aggregator`.
+    /// Lifetime: transient
+    const AGGREGATOR_API: u64 = 22;
+
+    public fun get_aggregator_api_feature(): u64 { AGGREGATOR_API }
+
+    public fun aggregator_api_enabled(): bool acquires Features {
+        is_enabled(AGGREGATOR_API)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
@@ -4,15 +4,24 @@
 use crate::{
     natives::{
         aggregator_natives::{
-            helpers::{aggregator_info, unpack_aggregator_struct},
+            helpers::{
+                aggregator_info, snapshot_id, unpack_aggregator_struct, unpack_snapshot_struct,
+            },
             NativeAggregatorContext,
         },
-        helpers::{make_safe_native, SafeNativeContext, SafeNativeResult},
+        helpers::{make_safe_native, SafeNativeContext, SafeNativeError, SafeNativeResult},
     },
     safely_pop_arg,
 };
-use aptos_aggregator::aggregator_extension::AggregatorID;
-use aptos_types::on_chain_config::{Features, TimedFeatures};
+use aptos_aggregator::{
+    aggregator_extension::{extension_error, AggregatorHandle, AggregatorID},
+    delta_change_set::deserialize,
+};
+use aptos_crypto::hash::DefaultHasher;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::{Features, TimedFeatures},
+};
 use move_core_types::gas_algebra::InternalGas;
 use move_vm_runtime::native_functions::NativeFunction;
 use move_vm_types::{
@@ -22,6 +31,19 @@ use move_vm_types::{
 use smallvec::{smallvec, SmallVec};
 use std::{collections::VecDeque, sync::Arc};
 
+/// The value of a snapshot is not known until the transaction which took it
+/// commits (`error::invalid_state(ESNAPSHOT_NOT_READY)` in Move).
+const ESNAPSHOT_NOT_READY: u64 = 0x03_0005;
+
+/// An aggregator cannot be destroyed by the transaction which took a snapshot
+/// of it without knowing its value (`error::invalid_state(EAGGREGATOR_HAS_SNAPSHOTS)`
+/// in Move).
+const EAGGREGATOR_HAS_SNAPSHOTS: u64 = 0x03_0006;
+
+/// Salt used when deriving the keys of snapshots, so that they never collide
+/// with the keys of aggregators created by the same transaction.
+const SNAPSHOT_KEY_SALT: &[u8] = b"AggregatorSnapshot";
+
 /***************************************************************************************************
  * native fun add(aggregator: &mut Aggregator, value: u128);
  *
@@ -155,13 +177,150 @@ fn native_destroy(
     let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
     let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
 
-    // Actually remove the aggregator.
+    // Snapshots taken by this transaction which recorded a delta still need
+    // the value of the aggregator from storage.
     let id = AggregatorID::new(handle, key);
+    if aggregator_data.has_snapshots_of(&id) {
+        return Err(SafeNativeError::Abort {
+            abort_code: EAGGREGATOR_HAS_SNAPSHOTS,
+        });
+    }
+
+    // Actually remove the aggregator.
     aggregator_data.remove_aggregator(id);
 
     Ok(smallvec![])
 }
 
+/***************************************************************************************************
+ * native fun snapshot_internal(aggregator: &Aggregator): AggregatorSnapshot;
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct SnapshotGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_snapshot(
+    gas_params: &SnapshotGasParameters,
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(args.len(), 1);
+
+    context.charge(gas_params.base)?;
+
+    // Extract information from aggregator struct reference.
+    let (source_id, limit) = aggregator_info(&safely_pop_arg!(args, StructRef))?;
+
+    // Get aggregator data.
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    // Snapshots are stored in the same table as the aggregator. Similarly to
+    // aggregators, the key is the hash of transaction and the number of
+    // snapshots taken so far.
+    let num_snapshots = aggregator_data.num_snapshots() as u32;
+
+    let mut hasher = DefaultHasher::new(SNAPSHOT_KEY_SALT);
+    hasher.update(&aggregator_context.txn_hash());
+    hasher.update(&num_snapshots.to_be_bytes());
+    let hash = hasher.finish().to_vec();
+    let key = AggregatorHandle(
+        AccountAddress::from_bytes(hash)
+            .map_err(|_| extension_error("unable to create snapshot key"))?,
+    );
+
+    // The snapshot records the current value of the aggregator, or its
+    // current delta if the value is not known.
+    let id = AggregatorID::new(source_id.handle, key);
+    aggregator_data.create_snapshot(id, source_id, limit);
+
+    Ok(smallvec![Value::struct_(Struct::pack(vec![
+        Value::address(id.handle.0),
+        Value::address(key.0),
+    ]))])
+}
+
+/***************************************************************************************************
+ * native fun read_snapshot(snapshot: &AggregatorSnapshot): u128;
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct ReadSnapshotGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_read_snapshot(
+    gas_params: &ReadSnapshotGasParameters,
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(args.len(), 1);
+
+    context.charge(gas_params.base)?;
+
+    let id = snapshot_id(&safely_pop_arg!(args, StructRef))?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    if aggregator_context
+        .aggregator_data
+        .borrow()
+        .contains_snapshot(&id)
+    {
+        return Err(SafeNativeError::Abort {
+            abort_code: ESNAPSHOT_NOT_READY,
+        });
+    }
+
+    // The snapshot has been taken by a previous transaction, so its value
+    // is in storage.
+    let value = aggregator_context
+        .resolver
+        .resolve_table_entry(&id.handle, &id.key.0.to_vec())
+        .map_err(|_| extension_error("could not find the value of the snapshot"))?
+        .map(|bytes| deserialize(&bytes))
+        .ok_or_else(|| extension_error("could not find the value of the snapshot"))?;
+
+    Ok(smallvec![Value::u128(value)])
+}
+
+/***************************************************************************************************
+ * native fun destroy_snapshot(snapshot: AggregatorSnapshot);
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct DestroySnapshotGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_destroy_snapshot(
+    gas_params: &DestroySnapshotGasParameters,
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert_eq!(args.len(), 1);
+
+    context.charge(gas_params.base)?;
+
+    let id = unpack_snapshot_struct(safely_pop_arg!(args, Struct))?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    aggregator_data.remove_snapshot(id);
+
+    Ok(smallvec![])
+}
+
 /***************************************************************************************************
  * module
  *
@@ -172,6 +331,9 @@ pub struct GasParameters {
     pub read: ReadGasParameters,
     pub sub: SubGasParameters,
     pub destroy: DestroyGasParameters,
+    pub snapshot: SnapshotGasParameters,
+    pub read_snapshot: ReadSnapshotGasParameters,
+    pub destroy_snapshot: DestroySnapshotGasParameters,
 }
 
 pub fn make_all(
@@ -209,7 +371,39 @@ pub fn make_all(
         ),
         (
             "destroy",
-            make_safe_native(gas_params.destroy, timed_features, features, native_destroy),
+            make_safe_native(
+                gas_params.destroy,
+                timed_features.clone(),
+                features.clone(),
+                native_destroy,
+            ),
+        ),
+        (
+            "snapshot_internal",
+            make_safe_native(
+                gas_params.snapshot,
+                timed_features.clone(),
+                features.clone(),
+                native_snapshot,
+            ),
+        ),
+        (
+            "read_snapshot",
+            make_safe_native(
+                gas_params.read_snapshot,
+                timed_features.clone(),
+                features.clone(),
+                native_read_snapshot,
+            ),
+        ),
+        (
+            "destroy_snapshot",
+            make_safe_native(
+                gas_params.destroy_snapshot,
+                timed_features,
+                features,
+                native_destroy_snapshot,
+            ),
         ),
     ];
 
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_aggregator::{
-    aggregator_extension::{AggregatorData, AggregatorID, AggregatorState},
-    delta_change_set::{DeltaOp, DeltaUpdate},
+    aggregator_extension::{extension_error, AggregatorData, AggregatorID, SnapshotValue},
+    delta_change_set::DeltaOp,
 };
 use aptos_types::vm_status::VMStatus;
 use better_any::{Tid, TidAble};
@@ -23,6 +23,9 @@ pub enum AggregatorChange {
     Merge(DeltaOp),
     // A value should be deleted from the storage.
     Delete,
+    // A snapshot should be written with the value of the given aggregator
+    // from storage, after the delta is applied to it.
+    Snapshot(AggregatorID, DeltaOp),
 }
 
 /// Represents changes made by all aggregators during this context. This change
@@ -64,29 +67,30 @@ impl<'a> NativeAggregatorContext<'a> {
         let NativeAggregatorContext {
             aggregator_data, ..
         } = self;
-        let (_, destroyed_aggregators, aggregators) = aggregator_data.into_inner().into();
+        let (_, destroyed_aggregators, aggregators, snapshots) =
+            aggregator_data.into_inner().into();
 
         let mut changes = BTreeMap::new();
 
+        // Snapshots of aggregators which knew their value can be written
+        // directly, while others wait for the value from storage.
+        for (id, value) in snapshots {
+            let change = match value {
+                SnapshotValue::Data(value) => AggregatorChange::Write(value),
+                SnapshotValue::Delta(source_id, delta_op) => {
+                    AggregatorChange::Snapshot(source_id, delta_op)
+                },
+            };
+            changes.insert(id, change);
+        }
+
         // First, process all writes and deltas.
         for (id, aggregator) in aggregators {
-            let (value, state, limit, history) = aggregator.into();
-
-            let change = match state {
-                AggregatorState::Data => AggregatorChange::Write(value),
-                AggregatorState::PositiveDelta => {
-                    let history = history.unwrap();
-                    let plus = DeltaUpdate::Plus(value);
-                    let delta_op =
-                        DeltaOp::new(plus, limit, history.max_positive, history.min_negative);
-                    AggregatorChange::Merge(delta_op)
-                },
-                AggregatorState::NegativeDelta => {
-                    let history = history.unwrap();
-                    let minus = DeltaUpdate::Minus(value);
-                    let delta_op =
-                        DeltaOp::new(minus, limit, history.max_positive, history.min_negative);
-                    AggregatorChange::Merge(delta_op)
+            let change = match aggregator.delta_op() {
+                Some(delta_op) => AggregatorChange::Merge(delta_op),
+                None => {
+                    let (value, ..) = aggregator.into();
+                    AggregatorChange::Write(value)
                 },
             };
             changes.insert(id, change);
@@ -103,7 +107,36 @@ impl<'a> NativeAggregatorContext<'a> {
 
 impl AggregatorChangeSet {
     pub fn squash(&mut self, other: Self) -> Result<(), VMStatus> {
-        for (other_id, other_change) in other.changes {
+        // Snapshots taken in `other` session apply their deltas to the values
+        // of aggregators before it, and therefore have to be resolved against
+        // the changes of this session first.
+        let mut other_changes = other.changes;
+        for other_change in other_changes.values_mut() {
+            if let AggregatorChange::Snapshot(source_id, mut delta) = *other_change {
+                match self.changes.get(&source_id) {
+                    Some(AggregatorChange::Write(data)) => {
+                        let value = delta
+                            .apply_to(*data)
+                            .map_err(|e| e.finish(Location::Undefined).into_vm_status())?;
+                        *other_change = AggregatorChange::Write(value);
+                    },
+                    Some(AggregatorChange::Merge(previous_delta)) => {
+                        delta
+                            .merge_onto(*previous_delta)
+                            .map_err(|e| e.finish(Location::Undefined).into_vm_status())?;
+                        *other_change = AggregatorChange::Snapshot(source_id, delta);
+                    },
+                    Some(AggregatorChange::Delete) => {
+                        return Err(extension_error("snapshot of a destroyed aggregator")
+                            .finish(Location::Undefined)
+                            .into_vm_status());
+                    },
+                    Some(AggregatorChange::Snapshot(..)) | None => (),
+                }
+            }
+        }
+
+        for (other_id, other_change) in other_changes {
             match self.changes.entry(other_id) {
                 // If something was changed only in `other` session, add it.
                 btree_map::Entry::Vacant(entry) => {
@@ -115,8 +148,17 @@ impl AggregatorChangeSet {
 
                     let entry_mut = entry.get_mut();
                     match (*entry_mut, other_change) {
-                        (Write(_) | Merge(_), Write(data)) => *entry_mut = Write(data),
-                        (Write(_) | Merge(_), Delete) => *entry_mut = Delete,
+                        (Write(_) | Merge(_) | Snapshot(..), Write(data)) => {
+                            *entry_mut = Write(data)
+                        },
+                        (Write(_) | Merge(_) | Snapshot(..), Delete) => *entry_mut = Delete,
+                        // Snapshots are only taken once, by the session which
+                        // created them, and are never modified.
+                        (_, Snapshot(..)) | (Snapshot(..), Merge(_)) => {
+                            return Err(extension_error("snapshot cannot be modified")
+                                .finish(Location::Undefined)
+                                .into_vm_status());
+                        },
                         (Write(data), Merge(delta)) => {
                             let new_data = delta
                                 .apply_to(data)
@@ -145,7 +187,9 @@ impl AggregatorChangeSet {
 #[cfg(test)]
 mod test {
     use super::*;
-    use aptos_aggregator::aggregator_extension::aggregator_id_for_test;
+    use aptos_aggregator::{
+        aggregator_extension::aggregator_id_for_test, delta_change_set::delta_add,
+    };
     use claims::assert_matches;
     use move_table_extension::TableHandle;
 
@@ -213,4 +257,119 @@ mod test {
         assert!(changes.contains_key(&aggregator_id_for_test(700)));
         assert_matches!(changes.get(&aggregator_id_for_test(800)).unwrap(), Delete);
     }
+
+    #[test]
+    fn test_snapshots_into_change_set() {
+        let context = NativeAggregatorContext::new([0; 32], &EmptyStorage);
+        use AggregatorChange::*;
+
+        {
+            let mut aggregator_data = context.aggregator_data.borrow_mut();
+            aggregator_data.create_new_aggregator(aggregator_id_for_test(100), 100);
+            assert!(aggregator_data
+                .get_aggregator(aggregator_id_for_test(200), 200)
+                .add(5)
+                .is_ok());
+
+            // Snapshot of an aggregator with a known value, and of an
+            // aggregator with a delta.
+            aggregator_data.create_snapshot(
+                aggregator_id_for_test(300),
+                aggregator_id_for_test(100),
+                100,
+            );
+            aggregator_data.create_snapshot(
+                aggregator_id_for_test(400),
+                aggregator_id_for_test(200),
+                200,
+            );
+
+            // Changes after the snapshots are taken do not affect them.
+            let aggregator = aggregator_data.get_aggregator(aggregator_id_for_test(100), 100);
+            assert!(aggregator.add(10).is_ok());
+            let aggregator = aggregator_data.get_aggregator(aggregator_id_for_test(200), 200);
+            assert!(aggregator.add(10).is_ok());
+        }
+
+        let AggregatorChangeSet { changes } = context.into_change_set();
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(100)).unwrap(),
+            Write(10)
+        );
+        assert_matches!(changes.get(&aggregator_id_for_test(300)).unwrap(), Write(0));
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(200)).unwrap(),
+            Merge(delta) if *delta == delta_add(15, 200)
+        );
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(400)).unwrap(),
+            Snapshot(id, delta) if *id == aggregator_id_for_test(200) && *delta == delta_add(5, 200)
+        );
+    }
+
+    #[test]
+    fn test_squash_snapshots() {
+        use AggregatorChange::*;
+
+        let mut change_set = AggregatorChangeSet {
+            changes: BTreeMap::from([
+                (aggregator_id_for_test(100), Write(50)),
+                (aggregator_id_for_test(200), Merge(delta_add(5, 200))),
+            ]),
+        };
+        let other = AggregatorChangeSet {
+            changes: BTreeMap::from([
+                (aggregator_id_for_test(100), Write(70)),
+                (
+                    aggregator_id_for_test(300),
+                    Snapshot(aggregator_id_for_test(100), delta_add(10, 100)),
+                ),
+                (
+                    aggregator_id_for_test(400),
+                    Snapshot(aggregator_id_for_test(200), delta_add(10, 200)),
+                ),
+                (
+                    aggregator_id_for_test(500),
+                    Snapshot(aggregator_id_for_test(600), delta_add(10, 600)),
+                ),
+            ]),
+        };
+
+        // Snapshots taken by the other session are resolved against the
+        // changes of this session, and are not affected by later writes.
+        assert!(change_set.squash(other).is_ok());
+        let changes = change_set.changes;
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(100)).unwrap(),
+            Write(70)
+        );
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(300)).unwrap(),
+            Write(60)
+        );
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(400)).unwrap(),
+            Snapshot(id, delta) if *id == aggregator_id_for_test(200) && *delta == delta_add(15, 200)
+        );
+        assert_matches!(
+            changes.get(&aggregator_id_for_test(500)).unwrap(),
+            Snapshot(id, delta) if *id == aggregator_id_for_test(600) && *delta == delta_add(10, 600)
+        );
+    }
+
+    #[test]
+    fn test_squash_snapshot_of_destroyed_aggregator() {
+        use AggregatorChange::*;
+
+        let mut change_set = AggregatorChangeSet {
+            changes: BTreeMap::from([(aggregator_id_for_test(100), Delete)]),
+        };
+        let other = AggregatorChangeSet {
+            changes: BTreeMap::from([(
+                aggregator_id_for_test(200),
+                Snapshot(aggregator_id_for_test(100), delta_add(10, 100)),
+            )]),
+        };
+        assert!(change_set.squash(other).is_err());
+    }
 }
@@ -58,6 +58,38 @@ pub fn get_aggregator_fields(
     Ok((TableHandle(handle), AggregatorHandle(key), limit))
 }
 
+/// Returns ID of a snapshot based on a reference to `AggregatorSnapshot` Move struct.
+pub(crate) fn snapshot_id(snapshot: &StructRef) -> PartialVMResult<AggregatorID> {
+    let handle =
+        get_aggregator_field(snapshot, HANDLE_FIELD_INDEX)?.value_as::<AccountAddress>()?;
+    let key = get_aggregator_field(snapshot, KEY_FIELD_INDEX)?.value_as::<AccountAddress>()?;
+    Ok(AggregatorID::new(
+        TableHandle(handle),
+        AggregatorHandle(key),
+    ))
+}
+
+/// Given an `AggregatorSnapshot` Move struct, unpacks it into its ID.
+pub(crate) fn unpack_snapshot_struct(snapshot_struct: Struct) -> PartialVMResult<AggregatorID> {
+    let mut fields: Vec<Value> = snapshot_struct.unpack()?.collect();
+    assert!(fields.len() == 2);
+
+    let key = fields
+        .pop()
+        .map_or(Err(extension_error("unable to pop `key` field")), |v| {
+            v.value_as::<AccountAddress>()
+        })?;
+    let handle = fields
+        .pop()
+        .map_or(Err(extension_error("unable to pop `handle` field")), |v| {
+            v.value_as::<AccountAddress>()
+        })?;
+    Ok(AggregatorID::new(
+        TableHandle(handle),
+        AggregatorHandle(key),
+    ))
+}
+
 /// Given an `Aggregator` Move struct, unpacks it into fields: (`handle`, `key`, `limit`).
 pub(crate) fn unpack_aggregator_struct(
     aggregator_struct: Struct,
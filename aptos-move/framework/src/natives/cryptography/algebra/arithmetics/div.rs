@@ -60,6 +60,30 @@ pub fn div_internal(
             gas_params.ark_bls12_381_fq12_eq * NumArgs::one(),
             gas_params.ark_bls12_381_fq12_div * NumArgs::one()
         ),
+        Some(Structure::BN254Fr) => ark_div_internal!(
+            context,
+            args,
+            ark_bn254::Fr,
+            div,
+            gas_params.ark_bn254_fr_eq * NumArgs::one(),
+            gas_params.ark_bn254_fr_div * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => ark_div_internal!(
+            context,
+            args,
+            ark_bn254::Fq,
+            div,
+            gas_params.ark_bn254_fq_eq * NumArgs::one(),
+            gas_params.ark_bn254_fq_div * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => ark_div_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            div,
+            gas_params.ark_bn254_fq12_eq * NumArgs::one(),
+            gas_params.ark_bn254_fq12_div * NumArgs::one()
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
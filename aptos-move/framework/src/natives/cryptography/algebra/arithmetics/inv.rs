@@ -53,6 +53,24 @@ pub fn inv_internal(
             ark_bls12_381::Fq12,
             gas_params.ark_bls12_381_fq12_inv * NumArgs::one()
         ),
+        Some(Structure::BN254Fr) => ark_inverse_internal!(
+            context,
+            args,
+            ark_bn254::Fr,
+            gas_params.ark_bn254_fr_inv * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => ark_inverse_internal!(
+            context,
+            args,
+            ark_bn254::Fq,
+            gas_params.ark_bn254_fq_inv * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => ark_inverse_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            gas_params.ark_bn254_fq12_inv * NumArgs::one()
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -41,6 +41,27 @@ pub fn mul_internal(
             mul,
             gas_params.ark_bls12_381_fq12_mul * NumArgs::one()
         ),
+        Some(Structure::BN254Fr) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fr,
+            mul,
+            gas_params.ark_bn254_fr_mul * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq,
+            mul,
+            gas_params.ark_bn254_fq_mul * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            mul,
+            gas_params.ark_bn254_fq12_mul * NumArgs::one()
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
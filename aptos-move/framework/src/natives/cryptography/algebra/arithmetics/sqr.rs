@@ -41,6 +41,27 @@ pub fn sqr_internal(
             square,
             gas_params.ark_bls12_381_fq12_square * NumArgs::one()
         ),
+        Some(Structure::BN254Fr) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fr,
+            square,
+            gas_params.ark_bn254_fr_square * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq,
+            square,
+            gas_params.ark_bn254_fq_square * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            square,
+            gas_params.ark_bn254_fq12_square * NumArgs::one()
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
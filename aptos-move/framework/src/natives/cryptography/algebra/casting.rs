@@ -5,7 +5,7 @@ use crate::{
     natives::{
         cryptography::algebra::{
             abort_invariant_violated, gas::GasParameters, AlgebraContext, Structure,
-            BLS12381_R_SCALAR, MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+            BLS12381_R_SCALAR, BN254_R_SCALAR, MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         },
         helpers::{SafeNativeContext, SafeNativeError, SafeNativeResult},
     },
@@ -27,6 +27,9 @@ fn feature_flag_of_casting(
         (Some(Structure::BLS12381Fq12), Some(Structure::BLS12381Gt)) => {
             Some(FeatureFlag::BLS12_381_STRUCTURES)
         },
+        (Some(Structure::BN254Fq12), Some(Structure::BN254Gt)) => {
+            Some(FeatureFlag::BN254_STRUCTURES)
+        },
         _ => None,
     }
 }
@@ -59,6 +62,16 @@ pub fn downcast_internal(
                 Ok(smallvec![Value::bool(false), Value::u64(handle as u64)])
             }
         },
+        (Some(Structure::BN254Fq12), Some(Structure::BN254Gt)) => {
+            let handle = safely_pop_arg!(args, u64) as usize;
+            safe_borrow_element!(context, handle, ark_bn254::Fq12, element_ptr, element);
+            context.charge(gas_params.ark_bn254_fq12_pow_u256 * NumArgs::one())?;
+            if element.pow(BN254_R_SCALAR.0) == ark_bn254::Fq12::one() {
+                Ok(smallvec![Value::bool(true), Value::u64(handle as u64)])
+            } else {
+                Ok(smallvec![Value::bool(false), Value::u64(handle as u64)])
+            }
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -80,6 +93,10 @@ pub fn upcast_internal(
             let handle = safely_pop_arg!(args, u64);
             Ok(smallvec![Value::u64(handle)])
         },
+        (Some(Structure::BN254Gt), Some(Structure::BN254Fq12)) => {
+            let handle = safely_pop_arg!(args, u64);
+            Ok(smallvec![Value::u64(handle)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
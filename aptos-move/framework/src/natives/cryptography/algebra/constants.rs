@@ -5,8 +5,8 @@ use crate::{
     natives::{
         cryptography::algebra::{
             feature_flag_from_structure, gas::GasParameters, AlgebraContext, Structure,
-            BLS12381_GT_GENERATOR, BLS12381_Q12_LENDIAN, BLS12381_R_LENDIAN,
-            MOVE_ABORT_CODE_NOT_IMPLEMENTED,
+            BLS12381_GT_GENERATOR, BLS12381_Q12_LENDIAN, BLS12381_R_LENDIAN, BN254_GT_GENERATOR,
+            BN254_Q12_LENDIAN, BN254_Q_LENDIAN, BN254_R_LENDIAN, MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         },
         helpers::{SafeNativeContext, SafeNativeError, SafeNativeResult},
     },
@@ -68,6 +68,42 @@ pub fn zero_internal(
             one,
             gas_params.ark_bls12_381_fq12_one * NumArgs::one()
         ),
+        Some(Structure::BN254Fr) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fr,
+            zero,
+            gas_params.ark_bn254_fr_zero * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fq,
+            zero,
+            gas_params.ark_bn254_fq_zero * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fq12,
+            zero,
+            gas_params.ark_bn254_fq12_zero * NumArgs::one()
+        ),
+        Some(Structure::BN254G1) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G1Projective,
+            zero,
+            gas_params.ark_bn254_g1_proj_infinity * NumArgs::one()
+        ),
+        Some(Structure::BN254G2) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G2Projective,
+            zero,
+            gas_params.ark_bn254_g2_proj_infinity * NumArgs::one()
+        ),
+        Some(Structure::BN254Gt) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fq12,
+            one,
+            gas_params.ark_bn254_fq12_one * NumArgs::one()
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -113,6 +149,42 @@ pub fn one_internal(
             let handle = store_element!(context, element);
             Ok(smallvec![Value::u64(handle as u64)])
         },
+        Some(Structure::BN254Fr) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fr,
+            one,
+            gas_params.ark_bn254_fr_one * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fq,
+            one,
+            gas_params.ark_bn254_fq_one * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => ark_constant_op_internal!(
+            context,
+            ark_bn254::Fq12,
+            one,
+            gas_params.ark_bn254_fq12_one * NumArgs::one()
+        ),
+        Some(Structure::BN254G1) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G1Projective,
+            generator,
+            gas_params.ark_bn254_g1_proj_generator * NumArgs::one()
+        ),
+        Some(Structure::BN254G2) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G2Projective,
+            generator,
+            gas_params.ark_bn254_g2_proj_generator * NumArgs::one()
+        ),
+        Some(Structure::BN254Gt) => {
+            context.charge(gas_params.ark_bn254_fq12_clone * NumArgs::one())?;
+            let element = *Lazy::force(&BN254_GT_GENERATOR);
+            let handle = store_element!(context, element);
+            Ok(smallvec![Value::u64(handle as u64)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -138,6 +210,12 @@ pub fn order_internal(
         Some(Structure::BLS12381Fq12) => {
             Ok(smallvec![Value::vector_u8(BLS12381_Q12_LENDIAN.clone())])
         },
+        Some(Structure::BN254Fr)
+        | Some(Structure::BN254G1)
+        | Some(Structure::BN254G2)
+        | Some(Structure::BN254Gt) => Ok(smallvec![Value::vector_u8(BN254_R_LENDIAN.clone())]),
+        Some(Structure::BN254Fq) => Ok(smallvec![Value::vector_u8(BN254_Q_LENDIAN.clone())]),
+        Some(Structure::BN254Fq12) => Ok(smallvec![Value::vector_u8(BN254_Q12_LENDIAN.clone())]),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
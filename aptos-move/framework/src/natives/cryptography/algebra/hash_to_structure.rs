@@ -11,6 +11,7 @@ use crate::{
     },
     safely_pop_arg, store_element, structure_from_ty_arg,
 };
+use aptos_crypto::arkworks::hash_to_curve::Bn254G1SvdwMap;
 use aptos_types::on_chain_config::FeatureFlag;
 use ark_ec::hashing::HashToCurve;
 use move_core_types::gas_algebra::{
@@ -32,6 +33,9 @@ fn feature_flag_of_hash_to_structure(
         | (Some(Structure::BLS12381G2), Some(HashToStructureSuite::Bls12381g2XmdSha256SswuRo)) => {
             Some(FeatureFlag::BLS12_381_STRUCTURES)
         },
+        (Some(Structure::BN254G1), Some(HashToStructureSuite::Bn254g1XmdSha256SvdwRo)) => {
+            Some(FeatureFlag::BN254_STRUCTURES)
+        },
         _ => None,
     }
 }
@@ -50,7 +54,7 @@ macro_rules! suite_from_ty_arg {
     }};
 }
 
-fn hash_to_bls12381gx_cost(
+fn hash_to_curve_cost(
     dst_len: usize,
     msg_len: usize,
     dst_shortening_base: InternalGas,
@@ -90,7 +94,7 @@ pub fn hash_to_internal(
     let dst = bytes_ref.as_slice();
     match (structure_opt, suite_opt) {
         (Some(Structure::BLS12381G1), Some(HashToStructureSuite::Bls12381g1XmdSha256SswuRo)) => {
-            context.charge(hash_to_bls12381gx_cost(
+            context.charge(hash_to_curve_cost(
                 dst.len(),
                 msg.len(),
                 gas_params.sha2.base,
@@ -111,7 +115,7 @@ pub fn hash_to_internal(
             Ok(smallvec![Value::u64(new_handle as u64)])
         },
         (Some(Structure::BLS12381G2), Some(HashToStructureSuite::Bls12381g2XmdSha256SswuRo)) => {
-            context.charge(hash_to_bls12381gx_cost(
+            context.charge(hash_to_curve_cost(
                 dst.len(),
                 msg.len(),
                 gas_params.sha2.base,
@@ -131,6 +135,27 @@ pub fn hash_to_internal(
             let new_handle = store_element!(context, new_element);
             Ok(smallvec![Value::u64(new_handle as u64)])
         },
+        (Some(Structure::BN254G1), Some(HashToStructureSuite::Bn254g1XmdSha256SvdwRo)) => {
+            context.charge(hash_to_curve_cost(
+                dst.len(),
+                msg.len(),
+                gas_params.sha2.base,
+                gas_params.sha2.per_byte,
+                gas_params.algebra.ark_h2c_bn254g1_xmd_sha256_svdw_base,
+                gas_params
+                    .algebra
+                    .ark_h2c_bn254g1_xmd_sha256_svdw_per_msg_byte,
+            ))?;
+            let mapper = ark_ec::hashing::map_to_curve_hasher::MapToCurveBasedHasher::<
+                ark_bn254::G1Projective,
+                ark_ff::fields::field_hashers::DefaultFieldHasher<sha2_0_10_6::Sha256, 128>,
+                Bn254G1SvdwMap,
+            >::new(dst)
+            .unwrap();
+            let new_element = <ark_bn254::G1Projective>::from(mapper.hash(msg).unwrap());
+            let new_handle = store_element!(context, new_element);
+            Ok(smallvec![Value::u64(new_handle as u64)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -48,6 +48,24 @@ pub fn from_u64_internal(
             ark_bls12_381::Fq12,
             gas_params.ark_bls12_381_fq12_from_u64 * NumArgs::one()
         ),
+        Some(Structure::BN254Fr) => from_u64_internal!(
+            context,
+            args,
+            ark_bn254::Fr,
+            gas_params.ark_bn254_fr_from_u64 * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq) => from_u64_internal!(
+            context,
+            args,
+            ark_bn254::Fq,
+            gas_params.ark_bn254_fq_from_u64 * NumArgs::one()
+        ),
+        Some(Structure::BN254Fq12) => from_u64_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            gas_params.ark_bn254_fq12_from_u64 * NumArgs::one()
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -0,0 +1,451 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Poseidon hash function (https://eprint.iacr.org/2019/458.pdf) over the scalar fields of
+//! BN254 and BLS12-381, as used by the `aptos_std::aptos_hash::poseidon_*` natives.
+//!
+//! The instantiation follows circomlib: the S-box is `x^5`, there are 8 full rounds, the number of
+//! partial rounds depends on the width, and both the round constants and the MDS matrix are sampled
+//! from the Grain LFSR as done by the reference `generate_parameters_grain.sage` script.
+//! Hashing `n` inputs uses a permutation of width `n + 1`, whose initial state is `[0, inputs...]`,
+//! and outputs the first element of the final state.
+//!
+//! The round numbers are the ones output by the reference `calc_round_numbers.py` script for
+//! 128-bit security, with the number of partial rounds rounded up to a multiple of the width, as
+//! done by circomlib and by the reference test vectors. Over BN254, this produces the same outputs
+//! as circomlib's `Poseidon(n)` template.
+
+use ark_ff::{BigInteger, PrimeField};
+use once_cell::sync::{Lazy, OnceCell};
+
+/// The maximum number of inputs that can be hashed at once.
+pub const MAX_NUM_INPUTS: usize = 16;
+
+/// The number of full rounds, for all widths.
+const NUM_FULL_ROUNDS: usize = 8;
+
+/// The number of partial rounds over the 254-bit BN254 scalar field, indexed by the number of
+/// inputs minus one.
+const BN254_NUM_PARTIAL_ROUNDS: [usize; MAX_NUM_INPUTS] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// The number of partial rounds over the 255-bit BLS12-381 scalar field, indexed by the number of
+/// inputs minus one. The extra bit does not change any of the round numbers, so this matches the
+/// BN254 table (e.g., the reference `poseidonperm_x5_255_3` and `poseidonperm_x5_255_5`
+/// instances use 57 and 60 partial rounds).
+const BLS12381_NUM_PARTIAL_ROUNDS: [usize; MAX_NUM_INPUTS] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// The number of bits in the Grain LFSR state.
+const GRAIN_STATE_NUM_BITS: usize = 80;
+
+/// The number of initial LFSR outputs discarded before sampling any parameters.
+const GRAIN_NUM_WARMUP_CLOCKS: usize = 160;
+
+/// A scalar field for which Poseidon parameters are available.
+pub trait PoseidonField: PrimeField {
+    /// The number of partial rounds, indexed by the number of inputs minus one.
+    const NUM_PARTIAL_ROUNDS: [usize; MAX_NUM_INPUTS];
+
+    /// Returns the (lazily-generated) parameters for hashing `num_inputs` elements.
+    /// `num_inputs` must be between 1 and `MAX_NUM_INPUTS`.
+    fn parameters(num_inputs: usize) -> &'static PoseidonParameters<Self>;
+}
+
+static BN254_PARAMETERS: Lazy<Vec<OnceCell<PoseidonParameters<ark_bn254::Fr>>>> =
+    Lazy::new(|| (0..MAX_NUM_INPUTS).map(|_| OnceCell::new()).collect());
+
+static BLS12381_PARAMETERS: Lazy<Vec<OnceCell<PoseidonParameters<ark_bls12_381::Fr>>>> =
+    Lazy::new(|| (0..MAX_NUM_INPUTS).map(|_| OnceCell::new()).collect());
+
+impl PoseidonField for ark_bn254::Fr {
+    const NUM_PARTIAL_ROUNDS: [usize; MAX_NUM_INPUTS] = BN254_NUM_PARTIAL_ROUNDS;
+
+    fn parameters(num_inputs: usize) -> &'static PoseidonParameters<Self> {
+        BN254_PARAMETERS[num_inputs - 1].get_or_init(|| PoseidonParameters::new(num_inputs + 1))
+    }
+}
+
+impl PoseidonField for ark_bls12_381::Fr {
+    const NUM_PARTIAL_ROUNDS: [usize; MAX_NUM_INPUTS] = BLS12381_NUM_PARTIAL_ROUNDS;
+
+    fn parameters(num_inputs: usize) -> &'static PoseidonParameters<Self> {
+        BLS12381_PARAMETERS[num_inputs - 1].get_or_init(|| PoseidonParameters::new(num_inputs + 1))
+    }
+}
+
+/// The round constants and MDS matrix of a Poseidon permutation of a given width.
+pub struct PoseidonParameters<F: PoseidonField> {
+    width: usize,
+    num_partial_rounds: usize,
+    round_constants: Vec<F>,
+    mds: Vec<Vec<F>>,
+}
+
+impl<F: PoseidonField> PoseidonParameters<F> {
+    /// Generates the parameters for a permutation of the given width (i.e., the number of inputs
+    /// plus one), exactly as done by the reference `generate_parameters_grain.sage` script.
+    fn new(width: usize) -> Self {
+        let num_partial_rounds = F::NUM_PARTIAL_ROUNDS[width - 2];
+        let mut grain = GrainLfsr::new(
+            F::MODULUS_BIT_SIZE as usize,
+            width,
+            NUM_FULL_ROUNDS,
+            num_partial_rounds,
+        );
+
+        let round_constants = (0..(NUM_FULL_ROUNDS + num_partial_rounds) * width)
+            .map(|_| grain.next_field_element_by_rejection())
+            .collect();
+
+        // Sample a Cauchy matrix `M[i][j] = 1 / (x_i + y_j)` from distinct `x_i`'s and `y_j`'s.
+        let mds = loop {
+            let mut elements: Vec<F> = (0..2 * width)
+                .map(|_| grain.next_field_element_mod_order())
+                .collect();
+            while !all_distinct(&elements) {
+                elements = (0..2 * width)
+                    .map(|_| grain.next_field_element_mod_order())
+                    .collect();
+            }
+            let (xs, ys) = elements.split_at(width);
+            let mds: Option<Vec<Vec<F>>> = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| (*x + y).inverse()).collect())
+                .collect();
+            if let Some(mds) = mds {
+                break mds;
+            }
+        };
+
+        Self {
+            width,
+            num_partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// Applies the permutation to `state` in place.
+    fn permute(&self, state: &mut [F]) {
+        debug_assert_eq!(state.len(), self.width);
+        let first_partial_round = NUM_FULL_ROUNDS / 2;
+        let last_partial_round = first_partial_round + self.num_partial_rounds;
+
+        for (round, constants) in self.round_constants.chunks_exact(self.width).enumerate() {
+            for (element, constant) in state.iter_mut().zip(constants) {
+                *element += constant;
+            }
+
+            if (first_partial_round..last_partial_round).contains(&round) {
+                state[0] = sbox(state[0]);
+            } else {
+                for element in state.iter_mut() {
+                    *element = sbox(*element);
+                }
+            }
+
+            let mixed: Vec<F> = self
+                .mds
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(state.iter())
+                        .fold(F::zero(), |acc, (m, s)| acc + *m * s)
+                })
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+    }
+}
+
+/// Returns the Poseidon hash of `inputs`, which must contain between 1 and `MAX_NUM_INPUTS`
+/// elements.
+pub fn hash<F: PoseidonField>(inputs: &[F]) -> F {
+    debug_assert!(!inputs.is_empty() && inputs.len() <= MAX_NUM_INPUTS);
+    let parameters = F::parameters(inputs.len());
+    let mut state = Vec::with_capacity(inputs.len() + 1);
+    state.push(F::zero());
+    state.extend_from_slice(inputs);
+    parameters.permute(&mut state);
+    state[0]
+}
+
+/// Returns the number of field multiplications performed when hashing `num_inputs` elements,
+/// which is what the natives charge gas for.
+pub fn num_field_muls<F: PoseidonField>(num_inputs: usize) -> usize {
+    let width = num_inputs + 1;
+    let num_partial_rounds = F::NUM_PARTIAL_ROUNDS[num_inputs - 1];
+    // Each S-box takes 3 multiplications, and each MDS mixing takes `width^2` of them.
+    let num_sboxes = NUM_FULL_ROUNDS * width + num_partial_rounds;
+    3 * num_sboxes + (NUM_FULL_ROUNDS + num_partial_rounds) * width * width
+}
+
+/// Returns the number of parameters (i.e., round constants and MDS matrix entries) used when
+/// hashing `num_inputs` elements.
+///
+/// The natives charge for generating them on every call: whether the parameters are already cached
+/// differs across nodes, so it must not affect the gas charged.
+pub fn num_parameters<F: PoseidonField>(num_inputs: usize) -> usize {
+    let width = num_inputs + 1;
+    let num_partial_rounds = F::NUM_PARTIAL_ROUNDS[num_inputs - 1];
+    (NUM_FULL_ROUNDS + num_partial_rounds) * width + width * width
+}
+
+fn sbox<F: PrimeField>(x: F) -> F {
+    let x2 = x.square();
+    x2.square() * x
+}
+
+fn all_distinct<F: PrimeField>(elements: &[F]) -> bool {
+    elements
+        .iter()
+        .enumerate()
+        .all(|(i, x)| elements[i + 1..].iter().all(|y| x != y))
+}
+
+/// The self-shrinking Grain LFSR used to sample Poseidon parameters.
+struct GrainLfsr {
+    /// The state bits, where bit `i` is the `i`-th oldest one.
+    state: u128,
+    /// The number of bits in a sampled field element.
+    num_bits: usize,
+}
+
+impl GrainLfsr {
+    fn new(
+        num_bits: usize,
+        width: usize,
+        num_full_rounds: usize,
+        num_partial_rounds: usize,
+    ) -> Self {
+        // The initial state encodes the field type (prime field), the S-box type (`x^alpha`), the
+        // field size, the width and the round numbers, padded with ones.
+        let mut bits = Vec::with_capacity(GRAIN_STATE_NUM_BITS);
+        let mut push = |value: usize, len: usize| {
+            bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+        };
+        push(1, 2);
+        push(0, 4);
+        push(num_bits, 12);
+        push(width, 12);
+        push(num_full_rounds, 10);
+        push(num_partial_rounds, 10);
+        push((1 << 30) - 1, 30);
+
+        let state = bits
+            .iter()
+            .enumerate()
+            .fold(0u128, |acc, (i, bit)| acc | ((*bit as u128) << i));
+        let mut lfsr = Self { state, num_bits };
+        for _ in 0..GRAIN_NUM_WARMUP_CLOCKS {
+            lfsr.clock();
+        }
+        lfsr
+    }
+
+    fn clock(&mut self) -> bool {
+        let bit = |i: usize| (self.state >> i) & 1;
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state = (self.state >> 1) | (new_bit << (GRAIN_STATE_NUM_BITS - 1));
+        new_bit == 1
+    }
+
+    /// Outputs the next bit, discarding LFSR output pairs whose first bit is zero.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.clock();
+            let bit = self.clock();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    fn next_bits(&mut self) -> Vec<bool> {
+        (0..self.num_bits).map(|_| self.next_bit()).collect()
+    }
+
+    /// Samples a field element by rejecting samples that are not smaller than the modulus.
+    fn next_field_element_by_rejection<F: PrimeField>(&mut self) -> F {
+        loop {
+            if let Some(element) = F::from_bigint(F::BigInt::from_bits_be(&self.next_bits())) {
+                return element;
+            }
+        }
+    }
+
+    /// Samples a field element by reducing the sample modulo the field order.
+    fn next_field_element_mod_order<F: PrimeField>(&mut self) -> F {
+        let bits = self.next_bits();
+        let padding = (8 - bits.len() % 8) % 8;
+        let bytes: Vec<u8> = std::iter::repeat(false)
+            .take(padding)
+            .chain(bits)
+            .collect::<Vec<_>>()
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8))
+            .collect();
+        F::from_be_bytes_mod_order(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn field_elements<F: PrimeField>(hex_strings: &[&str]) -> Vec<F> {
+        hex_strings
+            .iter()
+            .map(|s| F::from_be_bytes_mod_order(&hex::decode(s).unwrap()))
+            .collect()
+    }
+
+    /// Checks the permutation against the `poseidonperm_x5_*` test vectors of the reference
+    /// implementation, whose input is `[0, 1, ..., width - 1]`.
+    fn check_reference_permutation<F: PoseidonField>(expected: &[&str]) {
+        let width = expected.len();
+        let mut state: Vec<F> = (0..width as u64).map(Into::into).collect();
+        F::parameters(width - 1).permute(&mut state);
+        assert_eq!(
+            state,
+            field_elements::<F>(expected),
+            "Unexpected permutation output for width {}",
+            width
+        );
+    }
+
+    #[test]
+    fn test_bn254_matches_reference_permutation() {
+        // `poseidonperm_x5_254_3`
+        check_reference_permutation::<ark_bn254::Fr>(&[
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+            "0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29",
+            "0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c",
+        ]);
+        // `poseidonperm_x5_254_5`
+        check_reference_permutation::<ark_bn254::Fr>(&[
+            "299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465",
+            "1148aaef609aa338b27dafd89bb98862d8bb2b429aceac47d86206154ffe053d",
+            "24febb87fed7462e23f6665ff9a0111f4044c38ee1672c1ac6b0637d34f24907",
+            "0eb08f6d809668a981c186beaf6110060707059576406b248e5d9cf6e78b3d3e",
+            "07748bc6877c9b82c8b98666ee9d0626ec7f5be4205f79ee8528ef1c4a376fc7",
+        ]);
+    }
+
+    #[test]
+    fn test_bls12381_matches_reference_permutation() {
+        // `poseidonperm_x5_255_3`
+        check_reference_permutation::<ark_bls12_381::Fr>(&[
+            "28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a",
+            "51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4",
+            "3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a",
+        ]);
+        // `poseidonperm_x5_255_5`
+        check_reference_permutation::<ark_bls12_381::Fr>(&[
+            "2a918b9c9f9bd7bb509331c81e297b5707f6fc7393dcee1b13901a0b22202e18",
+            "65ebf8671739eeb11fb217f2d5c5bf4a0c3f210e3f3cd3b08b5db75675d797f7",
+            "2cc176fc26bc70737a696a9dfd1b636ce360ee76926d182390cdb7459cf585ce",
+            "4dc4e29d283afd2a491fe6aef122b9a968e74eff05341f3cc23fda1781dcb566",
+            "03ff622da276830b9451b88b85e6184fd6ae15c8ab3ee25a5667be8592cce3b1",
+        ]);
+    }
+
+    /// Returns the round numbers `(R_F, R_P)` output by the reference `calc_round_numbers.py`
+    /// script for `x^5` and 128-bit security, which minimize the number of S-boxes.
+    fn reference_round_numbers<F: PrimeField>(width: usize) -> (usize, usize) {
+        const SECURITY_LEVEL: f64 = 128.0;
+        let alpha = 5f64;
+        let t = width as f64;
+        let num_bits = F::MODULUS_BIT_SIZE as f64;
+        // The modulus is not a power of two, so this is `floor(log2(p))`, and `log2(p)` itself
+        // only ever appears in the conditions below through `min(SECURITY_LEVEL, log2(p))`.
+        let log2_modulus_floor = num_bits - 1.0;
+        let is_secure = |num_full_rounds: usize, num_partial_rounds: usize| {
+            let (r_f, r_p) = (num_full_rounds as f64, num_partial_rounds as f64);
+            let r_f_1: f64 = if SECURITY_LEVEL <= (log2_modulus_floor - 2.0).floor() * (t + 1.0) {
+                6.0
+            } else {
+                10.0
+            };
+            let r_f_2 = 1.0
+                + (2f64.ln() / alpha.ln() * SECURITY_LEVEL.min(num_bits)).ceil()
+                + (t.ln() / alpha.ln()).ceil()
+                - r_p;
+            let r_f_3 = 2f64.ln() / alpha.ln() * SECURITY_LEVEL - r_p;
+            let r_f_4 = t - 1.0 + 2f64.ln() / alpha.ln() * (SECURITY_LEVEL / (t + 1.0)) - r_p;
+            r_f >= [r_f_1, r_f_2, r_f_3, r_f_4]
+                .iter()
+                .map(|r| r.ceil())
+                .fold(f64::MIN, f64::max)
+        };
+
+        let mut best: Option<(usize, usize, usize)> = None;
+        for num_partial_rounds in 1..500 {
+            if let Some(num_full_rounds) = (4..100)
+                .step_by(2)
+                .find(|num_full_rounds| is_secure(*num_full_rounds, num_partial_rounds))
+            {
+                // Add the security margin of the reference script.
+                let num_full_rounds = num_full_rounds + 2;
+                let num_partial_rounds = (num_partial_rounds as f64 * 1.075).ceil() as usize;
+                let num_sboxes = width * num_full_rounds + num_partial_rounds;
+                if best.map_or(true, |(min_num_sboxes, min_num_full_rounds, _)| {
+                    num_sboxes < min_num_sboxes
+                        || (num_sboxes == min_num_sboxes && num_full_rounds < min_num_full_rounds)
+                }) {
+                    best = Some((num_sboxes, num_full_rounds, num_partial_rounds));
+                }
+            }
+        }
+        let (_, num_full_rounds, num_partial_rounds) = best.unwrap();
+        (num_full_rounds, num_partial_rounds)
+    }
+
+    fn check_round_numbers<F: PoseidonField>() {
+        for num_inputs in 1..=MAX_NUM_INPUTS {
+            let width = num_inputs + 1;
+            let (num_full_rounds, num_partial_rounds) = reference_round_numbers::<F>(width);
+            assert_eq!(num_full_rounds, NUM_FULL_ROUNDS);
+            // The number of partial rounds is rounded up to a multiple of the width.
+            assert_eq!(
+                F::NUM_PARTIAL_ROUNDS[num_inputs - 1],
+                (num_partial_rounds + width - 1) / width * width,
+                "Unexpected number of partial rounds for width {}",
+                width
+            );
+        }
+    }
+
+    #[test]
+    fn test_bn254_round_numbers() {
+        check_round_numbers::<ark_bn254::Fr>();
+    }
+
+    #[test]
+    fn test_bls12381_round_numbers() {
+        check_round_numbers::<ark_bls12_381::Fr>();
+    }
+
+    #[test]
+    fn test_bn254_matches_circomlib() {
+        // Test vectors from circomlibjs (`poseidon([1])`, `poseidon([1, 2])`, `poseidon([1, 2, 3, 4])`).
+        let expected = [
+            "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+            "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+            "18821383157269793795438455681495246036402687001665670618754263018637548127333",
+        ];
+        for (num_inputs, expected) in [1usize, 2, 4].into_iter().zip(expected) {
+            let inputs: Vec<ark_bn254::Fr> = (1..=num_inputs as u64).map(Into::into).collect();
+            assert_eq!(
+                hash(&inputs),
+                ark_bn254::Fr::from_str(expected).unwrap(),
+                "Unexpected hash for {} inputs",
+                num_inputs
+            );
+        }
+    }
+}
@@ -2,11 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    natives::helpers::{make_safe_native, SafeNativeContext, SafeNativeResult},
-    safely_assert_eq, safely_pop_arg,
+    natives::{
+        cryptography::poseidon::{self, PoseidonField},
+        helpers::{make_safe_native, SafeNativeContext, SafeNativeError, SafeNativeResult},
+    },
+    safely_assert_eq, safely_pop_arg, safely_pop_vec_arg,
 };
 use aptos_types::on_chain_config::{Features, TimedFeatures};
-use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::CanonicalDeserialize;
+use move_core_types::gas_algebra::{
+    InternalGas, InternalGasPerArg, InternalGasPerByte, NumArgs, NumBytes,
+};
 use move_vm_runtime::native_functions::NativeFunction;
 use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
 use ripemd::Digest as OtherDigest;
@@ -184,6 +191,77 @@ fn native_ripemd160(
     Ok(smallvec![Value::vector_u8(output)])
 }
 
+/***************************************************************************************************
+ * native fun poseidon_bn254_internal / poseidon_bls12381_internal
+ *
+ *   gas cost: base_cost + per_input_cost * num_inputs + per_parameter_cost * num_parameters
+ *             + per_field_mul_cost * num_field_muls
+ *
+ **************************************************************************************************/
+
+/// Abort codes for the Poseidon natives (0x01 == INVALID_ARGUMENT).
+/// NOTE: These must match the codes in the Move implementation.
+pub mod abort_codes {
+    pub const E_POSEIDON_INVALID_NUM_INPUTS: u64 = 0x01_0002;
+    pub const E_POSEIDON_INVALID_INPUT: u64 = 0x01_0003;
+}
+
+/// The size of a serialized scalar field element, in bytes.
+const POSEIDON_SCALAR_NUM_BYTES: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct PoseidonHashGasParameters {
+    pub base: InternalGas,
+    pub per_input: InternalGasPerArg,
+    pub per_parameter: InternalGasPerArg,
+    pub per_field_mul: InternalGasPerArg,
+}
+
+fn native_poseidon<F: PoseidonField>(
+    gas_params: &PoseidonHashGasParameters,
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    safely_assert_eq!(_ty_args.len(), 0);
+    safely_assert_eq!(args.len(), 1);
+
+    let inputs = safely_pop_vec_arg!(args, Vec<u8>);
+
+    context.charge(gas_params.base + gas_params.per_input * NumArgs::new(inputs.len() as u64))?;
+
+    if inputs.is_empty() || inputs.len() > poseidon::MAX_NUM_INPUTS {
+        return Err(SafeNativeError::Abort {
+            abort_code: abort_codes::E_POSEIDON_INVALID_NUM_INPUTS,
+        });
+    }
+
+    // Inputs must be canonically-serialized field elements, with the least significant byte first.
+    let elements = inputs
+        .iter()
+        .map(|bytes| {
+            if bytes.len() != POSEIDON_SCALAR_NUM_BYTES {
+                return None;
+            }
+            F::deserialize_uncompressed(bytes.as_slice()).ok()
+        })
+        .collect::<Option<Vec<F>>>()
+        .ok_or(SafeNativeError::Abort {
+            abort_code: abort_codes::E_POSEIDON_INVALID_INPUT,
+        })?;
+
+    context.charge(
+        gas_params.per_parameter
+            * NumArgs::new(poseidon::num_parameters::<F>(elements.len()) as u64)
+            + gas_params.per_field_mul
+                * NumArgs::new(poseidon::num_field_muls::<F>(elements.len()) as u64),
+    )?;
+
+    let output = poseidon::hash(&elements).into_bigint().to_bytes_le();
+
+    Ok(smallvec![Value::vector_u8(output)])
+}
+
 /***************************************************************************************************
  * module
  *
@@ -196,6 +274,8 @@ pub struct GasParameters {
     pub sha3_512: Sha3_512HashGasParameters,
     pub ripemd160: Ripemd160HashGasParameters,
     pub blake2b_256: Blake2B256HashGasParameters,
+    pub poseidon_bn254: PoseidonHashGasParameters,
+    pub poseidon_bls12381: PoseidonHashGasParameters,
 }
 
 pub fn make_all(
@@ -253,9 +333,27 @@ pub fn make_all(
             "blake2b_256_internal",
             make_safe_native(
                 gas_params.blake2b_256,
+                timed_features.clone(),
+                features.clone(),
+                native_blake2b_256,
+            ),
+        ),
+        (
+            "poseidon_bn254_internal",
+            make_safe_native(
+                gas_params.poseidon_bn254,
+                timed_features.clone(),
+                features.clone(),
+                native_poseidon::<ark_bn254::Fr>,
+            ),
+        ),
+        (
+            "poseidon_bls12381_internal",
+            make_safe_native(
+                gas_params.poseidon_bls12381,
                 timed_features,
                 features,
-                native_blake2b_256,
+                native_poseidon::<ark_bls12_381::Fr>,
             ),
         ),
     ];
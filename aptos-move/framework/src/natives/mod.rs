@@ -207,6 +207,18 @@ impl GasParameters {
                     base: 0.into(),
                     per_byte: 0.into(),
                 },
+                poseidon_bn254: hash::PoseidonHashGasParameters {
+                    base: 0.into(),
+                    per_input: 0.into(),
+                    per_parameter: 0.into(),
+                    per_field_mul: 0.into(),
+                },
+                poseidon_bls12381: hash::PoseidonHashGasParameters {
+                    base: 0.into(),
+                    per_input: 0.into(),
+                    per_parameter: 0.into(),
+                    per_field_mul: 0.into(),
+                },
             },
             type_info: type_info::GasParameters {
                 type_of: type_info::TypeOfGasParameters {
@@ -250,6 +262,9 @@ impl GasParameters {
                 read: aggregator::ReadGasParameters { base: 0.into() },
                 sub: aggregator::SubGasParameters { base: 0.into() },
                 destroy: aggregator::DestroyGasParameters { base: 0.into() },
+                snapshot: aggregator::SnapshotGasParameters { base: 0.into() },
+                read_snapshot: aggregator::ReadSnapshotGasParameters { base: 0.into() },
+                destroy_snapshot: aggregator::DestroySnapshotGasParameters { base: 0.into() },
             },
             aggregator_factory: aggregator_factory::GasParameters {
                 new_aggregator: aggregator_factory::NewAggregatorGasParameters { base: 0.into() },
@@ -92,6 +92,24 @@ impl<K: ModulePath + Hash + Clone + Eq, V: TransactionWrite, X: Executable> MVHa
         self.data.add_delta(key, txn_idx, delta);
     }
 
+    /// Add a snapshot at a specified key, of the aggregator at access path 'source' which
+    /// had the given delta when the snapshot was taken.
+    pub fn add_snapshot(&self, key: &K, version: Version, source: K, delta: DeltaOp) {
+        debug_assert!(
+            key.module_path().is_none(),
+            "Snapshot must be stored at a path corresponding to data"
+        );
+
+        self.data.add_snapshot(key, version, source, delta);
+    }
+
+    /// Returns the access path and the delta of the aggregator of the snapshot at 'key',
+    /// taken by the transaction with the given version, or None if the entry is no longer
+    /// the snapshot.
+    pub fn snapshot_source(&self, key: &K, version: Version) -> Option<(K, DeltaOp)> {
+        self.data.snapshot_source(key, version)
+    }
+
     /// Read data at access path 'key', from the perspective of transaction 'txn_idx'.
     pub fn fetch_data(
         &self,
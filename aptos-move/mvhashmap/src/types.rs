@@ -21,6 +21,11 @@ pub enum MVDataError {
     Dependency(TxnIndex),
     /// Delta application failed, txn execution should fail.
     DeltaApplicationFailure,
+    /// Read resulted in a snapshot of an aggregator, taken by the transaction with
+    /// the given version. The value must be read from the aggregator (whose key and
+    /// delta are provided by `snapshot_source`) from the perspective of the same
+    /// transaction, and the delta applied to it.
+    Snapshot(Version),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -188,3 +188,41 @@ fn create_write_read_placeholder_struct() {
     let r_31 = mvtbl.fetch_data(&ap2, 31);
     assert_eq!(Err(DeltaApplicationFailure), r_31);
 }
+
+#[test]
+fn snapshot_entries() {
+    use MVDataError::*;
+    use MVDataOutput::*;
+
+    let ap = KeyType(b"/foo/b".to_vec());
+    let snapshot_ap = KeyType(b"/foo/snapshot".to_vec());
+
+    let mvtbl: MVHashMap<KeyType<Vec<u8>>, Value, ExecutableTestType> = MVHashMap::new(None);
+
+    mvtbl.write(&ap, (5, 0), value_for(5, 0));
+    mvtbl.add_delta(&ap, 10, add_for(10, u128::MAX));
+    mvtbl.add_snapshot(&snapshot_ap, (10, 1), ap.clone(), add_for(3, u128::MAX));
+
+    // Reads of the snapshot must be redirected to the aggregator, which is read
+    // from the perspective of the snapshotting transaction.
+    let r_snapshot = mvtbl.fetch_data(&snapshot_ap, 15);
+    assert_eq!(Err(Snapshot((10, 1))), r_snapshot);
+    assert!(
+        mvtbl.snapshot_source(&snapshot_ap, (10, 1)) == Some((ap.clone(), add_for(3, u128::MAX)))
+    );
+    let r_source = mvtbl.fetch_data(&ap, 10);
+    assert_eq!(Ok(Versioned((5, 0), arc_value_for(5, 0))), r_source);
+
+    // Reads of lower transactions do not observe the snapshot.
+    let r_db = mvtbl.fetch_data(&snapshot_ap, 10);
+    assert_eq!(Err(NotFound), r_db);
+
+    // Snapshot of a previous incarnation is not returned.
+    mvtbl.add_snapshot(&snapshot_ap, (10, 2), ap.clone(), add_for(3, u128::MAX));
+    assert!(mvtbl.snapshot_source(&snapshot_ap, (10, 1)).is_none());
+
+    // Snapshot entries are marked as estimates like other entries.
+    mvtbl.mark_estimate(&snapshot_ap, 10);
+    let r_snapshot = mvtbl.fetch_data(&snapshot_ap, 15);
+    assert_eq!(Err(Dependency(10)), r_snapshot);
+}
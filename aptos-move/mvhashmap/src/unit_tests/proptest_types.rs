@@ -274,6 +274,7 @@ where
                                     break;
                                 },
                                 Err(Dependency(_i)) => (),
+                                Err(Snapshot(_)) => unreachable!("No snapshots are taken"),
                             }
                             retry_attempts += 1;
                             if retry_attempts > DEFAULT_TIMEOUT {
@@ -24,18 +24,18 @@ const FLAG_ESTIMATE: usize = 1;
 
 /// Every entry in shared multi-version data-structure has an "estimate" flag
 /// and some content.
-struct Entry<V> {
+struct Entry<K, V> {
     /// Used to mark the entry as a "write estimate". Even though the entry
     /// lives inside the DashMap and the entry access will have barriers, we
     /// still make the flag Atomic to provide acq/rel semantics on its own.
     flag: AtomicUsize,
 
     /// Actual contents.
-    pub cell: EntryCell<V>,
+    pub cell: EntryCell<K, V>,
 }
 
 /// Represents the content of a single entry in multi-version data-structure.
-enum EntryCell<V> {
+enum EntryCell<K, V> {
     /// Recorded in the shared multi-version data-structure for each write. It
     /// has: 1) Incarnation number of the transaction that wrote the entry (note
     /// that TxnIndex is part of the key and not recorded here), 2) actual data
@@ -44,12 +44,19 @@ enum EntryCell<V> {
 
     /// Recorded in the shared multi-version data-structure for each delta.
     Delta(DeltaOp),
+
+    /// Recorded in the shared multi-version data-structure for each snapshot of an
+    /// aggregator. It has: 1) Incarnation number of the transaction that took the
+    /// snapshot, 2) the key of the aggregator, 3) the delta of the aggregator when
+    /// the snapshot was taken. The value of the snapshot is the value of the
+    /// aggregator before the transaction, with the delta applied.
+    Snapshot(Incarnation, K, DeltaOp),
 }
 
 /// A VersionedValue internally contains a BTreeMap from indices of transactions
 /// that update the given access path alongside the corresponding entries.
-struct VersionedValue<V> {
-    versioned_map: BTreeMap<TxnIndex, CachePadded<Entry<V>>>,
+struct VersionedValue<K, V> {
+    versioned_map: BTreeMap<TxnIndex, CachePadded<Entry<K, V>>>,
 
     // Note: this can cache base (storage) value in Option<u128> to facilitate
     // aggregator validation & reading in the future, if needed.
@@ -58,25 +65,37 @@ struct VersionedValue<V> {
 
 /// Maps each key (access path) to an interal VersionedValue.
 pub struct VersionedData<K, V> {
-    values: DashMap<K, VersionedValue<V>>,
+    values: DashMap<K, VersionedValue<K, V>>,
     delta_keys: Mutex<Vec<K>>,
 }
 
-impl<V> Entry<V> {
-    pub fn new_write_from(flag: usize, incarnation: Incarnation, data: V) -> Entry<V> {
+impl<K, V> Entry<K, V> {
+    pub fn new_write_from(flag: usize, incarnation: Incarnation, data: V) -> Entry<K, V> {
         Entry {
             flag: AtomicUsize::new(flag),
             cell: EntryCell::Write(incarnation, Arc::new(data)),
         }
     }
 
-    pub fn new_delta_from(flag: usize, data: DeltaOp) -> Entry<V> {
+    pub fn new_delta_from(flag: usize, data: DeltaOp) -> Entry<K, V> {
         Entry {
             flag: AtomicUsize::new(flag),
             cell: EntryCell::Delta(data),
         }
     }
 
+    pub fn new_snapshot_from(
+        flag: usize,
+        incarnation: Incarnation,
+        source: K,
+        delta: DeltaOp,
+    ) -> Entry<K, V> {
+        Entry {
+            flag: AtomicUsize::new(flag),
+            cell: EntryCell::Snapshot(incarnation, source, delta),
+        }
+    }
+
     pub fn flag(&self) -> usize {
         self.flag.load(Ordering::Acquire)
     }
@@ -86,7 +105,7 @@ impl<V> Entry<V> {
     }
 }
 
-impl<V: TransactionWrite> VersionedValue<V> {
+impl<K, V: TransactionWrite> VersionedValue<K, V> {
     pub fn new() -> Self {
         Self {
             versioned_map: BTreeMap::new(),
@@ -95,7 +114,7 @@ impl<V: TransactionWrite> VersionedValue<V> {
     }
 }
 
-impl<V: TransactionWrite> Default for VersionedValue<V> {
+impl<K, V: TransactionWrite> Default for VersionedValue<K, V> {
     fn default() -> Self {
         VersionedValue::new()
     }
@@ -117,11 +136,13 @@ impl<K: Hash + Clone + Eq, V: TransactionWrite> VersionedData<K, V> {
     /// For processing outputs - removes the BTreeMap from the MVHashMap for a given
     /// key - the key should be an aggregator key with at least one delta update during
     /// the block execution (such keys are provided by 'aggregator_keys' function).
-    /// Returns the aggregator values for each transaction index w. a delta update.
+    /// Returns the aggregator values for each transaction index w. a delta update, lower
+    /// than upper_bound (entries of higher indices are discarded without being applied).
     pub fn take_materialized_deltas(
         &self,
         key: &K,
         base_value: Option<u128>,
+        upper_bound: TxnIndex,
     ) -> Vec<(TxnIndex, u128)> {
         let (_, v) = self
             .values
@@ -132,12 +153,16 @@ impl<K: Hash + Clone + Eq, V: TransactionWrite> VersionedData<K, V> {
         let mut latest_value = base_value;
         v.versioned_map
             .into_iter()
+            .take_while(|(idx, _)| *idx < upper_bound)
             .filter_map(|(idx, entry)| {
                 match &entry.cell {
                     EntryCell::Write(_, data) => {
                         latest_value = data.extract_raw_bytes().map(|bytes| deserialize(&bytes));
                         None
                     },
+                    EntryCell::Snapshot(..) => {
+                        unreachable!("Snapshot entry at an aggregator key")
+                    },
                     EntryCell::Delta(delta) => {
                         // Apply to the latest value to obtain the materialized delta value.
                         let aggregator_value = delta
@@ -168,6 +193,37 @@ impl<K: Hash + Clone + Eq, V: TransactionWrite> VersionedData<K, V> {
         }
     }
 
+    pub(crate) fn add_snapshot(&self, key: &K, version: Version, source: K, delta: DeltaOp) {
+        let (txn_idx, incarnation) = version;
+
+        let mut v = self.values.entry(key.clone()).or_default();
+        v.versioned_map.insert(
+            txn_idx,
+            CachePadded::new(Entry::new_snapshot_from(
+                FLAG_DONE,
+                incarnation,
+                source,
+                delta,
+            )),
+        );
+    }
+
+    /// Returns the key of the aggregator of the snapshot at `key`, taken by the
+    /// transaction with the given version, and the delta of the aggregator when the
+    /// snapshot was taken. Returns None if the entry has since been replaced, e.g. by
+    /// a re-execution of the transaction.
+    pub(crate) fn snapshot_source(&self, key: &K, version: Version) -> Option<(K, DeltaOp)> {
+        let (txn_idx, incarnation) = version;
+
+        let v = self.values.get(key)?;
+        match &v.versioned_map.get(&txn_idx)?.cell {
+            EntryCell::Snapshot(i, source, delta) if *i == incarnation => {
+                Some((source.clone(), *delta))
+            },
+            _ => None,
+        }
+    }
+
     pub(crate) fn mark_estimate(&self, key: &K, txn_idx: TxnIndex) {
         let v = self.values.get(key).expect("Path must exist");
         v.versioned_map
@@ -185,7 +241,7 @@ impl<K: Hash + Clone + Eq, V: TransactionWrite> VersionedData<K, V> {
         );
     }
 
-    pub(crate) fn fetch_data(
+    pub fn fetch_data(
         &self,
         key: &K,
         txn_idx: TxnIndex,
@@ -260,6 +316,14 @@ impl<K: Hash + Clone + Eq, V: TransactionWrite> VersionedData<K, V> {
                             // Initialize the accumulator and continue traversal.
                             accumulator = Some(Ok(*delta))
                         },
+                        (EntryCell::Snapshot(incarnation, ..), None) => {
+                            // The value of the snapshot must be read from the aggregator.
+                            return Err(Snapshot((*idx, *incarnation)));
+                        },
+                        (EntryCell::Snapshot(..), Some(_)) => {
+                            // Deltas are never applied to snapshots.
+                            return Err(DeltaApplicationFailure);
+                        },
                     }
                 }
 
@@ -287,10 +351,9 @@ impl<K: Hash + Clone + Eq, V: TransactionWrite> VersionedData<K, V> {
 
         // Assert that the previous entry for txn_idx, if present, had lower incarnation.
         assert!(prev_entry.map_or(true, |entry| -> bool {
-            if let EntryCell::Write(i, _) = entry.cell {
-                i < incarnation
-            } else {
-                true
+            match entry.cell {
+                EntryCell::Write(i, _) | EntryCell::Snapshot(i, ..) => i < incarnation,
+                EntryCell::Delta(_) => true,
             }
         }));
     }
@@ -12,6 +12,8 @@ mod state_sync;
 mod storage;
 pub mod utils;
 
+pub use storage::DbReaderWrapper;
+
 #[cfg(test)]
 mod tests;
 
@@ -124,6 +126,7 @@ impl AptosNodeArgs {
                 self.lazy,
                 &genesis_framework,
                 rng,
+                None,
             )
             .expect("Test node should start correctly!");
         } else {
@@ -170,6 +173,16 @@ pub fn start(
     config: NodeConfig,
     log_file: Option<PathBuf>,
     create_global_rayon_pool: bool,
+) -> anyhow::Result<()> {
+    start_with_db_reader_wrapper(config, log_file, create_global_rayon_pool, None)
+}
+
+/// Start an Aptos node, wrapping its storage reader with the given wrapper (if any)
+pub fn start_with_db_reader_wrapper(
+    config: NodeConfig,
+    log_file: Option<PathBuf>,
+    create_global_rayon_pool: bool,
+    db_reader_wrapper: Option<DbReaderWrapper>,
 ) -> anyhow::Result<()> {
     // Setup panic handler
     aptos_crash_handler::setup_panic_handler();
@@ -203,8 +216,12 @@ pub fn start(
     }
 
     // Set up the node environment and start it
-    let _node_handle =
-        setup_environment_and_start_node(config, remote_log_receiver, Some(logger_filter_update))?;
+    let _node_handle = setup_environment_and_start_node(
+        config,
+        remote_log_receiver,
+        Some(logger_filter_update),
+        db_reader_wrapper,
+    )?;
     let term = Arc::new(AtomicBool::new(false));
     while !term.load(Ordering::Acquire) {
         thread::park();
@@ -221,6 +238,7 @@ pub fn setup_test_environment_and_start_node<R>(
     enable_lazy_mode: bool,
     framework: &ReleaseBundle,
     rng: R,
+    db_reader_wrapper: Option<DbReaderWrapper>,
 ) -> anyhow::Result<()>
 where
     R: rand::RngCore + rand::CryptoRng,
@@ -298,7 +316,7 @@ where
     }
     println!("\nAptos is running, press ctrl-c to exit\n");
 
-    start(config, Some(log_file), false)
+    start_with_db_reader_wrapper(config, Some(log_file), false, db_reader_wrapper)
 }
 
 /// Creates a single node test config, with a few config tweaks to reduce
@@ -370,6 +388,7 @@ pub fn setup_environment_and_start_node(
     mut node_config: NodeConfig,
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
     logger_filter_update_job: Option<LoggerFilterUpdater>,
+    db_reader_wrapper: Option<DbReaderWrapper>,
 ) -> anyhow::Result<AptosHandle> {
     // Log the node config at node startup
     info!("Using node config {:?}", &node_config);
@@ -379,7 +398,7 @@ pub fn setup_environment_and_start_node(
 
     // Set up the storage database and any RocksDB checkpoints
     let (aptos_db, db_rw, backup_service, genesis_waypoint) =
-        storage::initialize_database_and_checkpoints(&mut node_config)?;
+        storage::initialize_database_and_checkpoints(&mut node_config, db_reader_wrapper)?;
 
     // Set the Aptos VM configurations
     utils::set_aptos_vm_configurations(&node_config);
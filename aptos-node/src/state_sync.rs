@@ -27,9 +27,12 @@ use aptos_storage_service_types::StorageServiceMessage;
 use aptos_time_service::TimeService;
 use aptos_types::{on_chain_config::ON_CHAIN_CONFIG_REGISTRY, waypoint::Waypoint};
 use aptos_vm::AptosVM;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::runtime::Runtime;
 
+// The directory (in the storage directory) that holds the storage service response cache
+const STORAGE_SERVICE_RESPONSE_CACHE_DIR: &str = "storage_service_response_cache";
+
 /// Creates the event subscription service and two reconfiguration
 /// notification listeners (for mempool and consensus, respectively).
 pub fn create_event_subscription_service(
@@ -90,6 +93,10 @@ pub fn start_state_sync_and_get_notification_handles(
         node_config.state_sync.storage_service,
         network_service_events,
         &db_rw,
+        node_config
+            .storage
+            .dir()
+            .join(STORAGE_SERVICE_RESPONSE_CACHE_DIR),
     )?;
 
     // Start the data client
@@ -193,6 +200,7 @@ fn setup_state_sync_storage_service(
     config: StorageServiceConfig,
     network_service_events: NetworkServiceEvents<StorageServiceMessage>,
     db_rw: &DbReaderWriter,
+    response_cache_dir: PathBuf,
 ) -> anyhow::Result<Runtime> {
     // Create a new state sync storage service runtime
     let storage_service_runtime = aptos_runtimes::spawn_named_runtime("stor-server".into(), None);
@@ -205,6 +213,7 @@ fn setup_state_sync_storage_service(
         storage_reader,
         TimeService::real(),
         StorageServiceNetworkEvents::new(network_service_events),
+        Some(response_cache_dir),
     );
     storage_service_runtime.spawn(service.start());
 
@@ -12,6 +12,10 @@ use aptos_vm::AptosVM;
 use std::{fs, net::SocketAddr, path::Path, sync::Arc, time::Instant};
 use tokio::runtime::Runtime;
 
+/// Wraps the storage reader of the node, once genesis is committed. This allows serving state
+/// from outside the local ledger, e.g. when running a local fork of a remote network.
+pub type DbReaderWrapper = Box<dyn FnOnce(Arc<dyn DbReader>) -> Arc<dyn DbReader> + Send>;
+
 #[cfg(not(feature = "consensus-only-perf-test"))]
 pub(crate) fn bootstrap_db(
     aptos_db: AptosDB,
@@ -86,6 +90,7 @@ fn create_rocksdb_checkpoint_and_change_working_dir(
 /// the various handles.
 pub fn initialize_database_and_checkpoints(
     node_config: &mut NodeConfig,
+    db_reader_wrapper: Option<DbReaderWrapper>,
 ) -> anyhow::Result<(Arc<dyn DbReader>, DbReaderWriter, Option<Runtime>, Waypoint)> {
     // If required, create RocksDB checkpoints and change the working directory.
     // This is test-only.
@@ -105,7 +110,7 @@ pub fn initialize_database_and_checkpoints(
         node_config.storage.max_num_nodes_per_lru_cache_shard,
     )
     .map_err(|err| anyhow!("DB failed to open {}", err))?;
-    let (aptos_db, db_rw, backup_service) =
+    let (aptos_db, mut db_rw, backup_service) =
         bootstrap_db(aptos_db, node_config.storage.backup_service_address);
 
     // TODO: handle non-genesis waypoints for state sync!
@@ -118,6 +123,15 @@ pub fn initialize_database_and_checkpoints(
         info!("Genesis txn not provided! This is fine only if you don't expect to apply it. Otherwise, the config is incorrect!");
     }
 
+    // Genesis is always executed against the local storage only
+    let aptos_db: Arc<dyn DbReader> = match db_reader_wrapper {
+        Some(db_reader_wrapper) => {
+            db_rw.reader = db_reader_wrapper(db_rw.reader);
+            db_rw.reader.clone()
+        },
+        None => aptos_db,
+    };
+
     // Log the duration to open storage
     debug!(
         "Storage service started in {} ms",
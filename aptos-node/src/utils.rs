@@ -60,4 +60,10 @@ pub fn set_aptos_vm_configurations(node_config: &NodeConfig) {
     {
         AptosVM::set_processed_transactions_detailed_counters();
     }
+
+    if node_config.mempool.speculative_execution_enabled {
+        AptosVM::set_pre_execution_cache_once(
+            node_config.mempool.speculative_execution_cache_capacity,
+        );
+    }
 }
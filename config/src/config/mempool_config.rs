@@ -31,6 +31,14 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    // Whether the highest priority transactions are executed ahead of time against the latest
+    // committed state, so that block execution can reuse their outputs
+    pub speculative_execution_enabled: bool,
+    // max number of transactions executed ahead of time per round
+    pub speculative_execution_batch_size: usize,
+    pub speculative_execution_interval_ms: u64,
+    // max number of cached outputs of transactions executed ahead of time
+    pub speculative_execution_cache_capacity: usize,
 }
 
 impl Default for MempoolConfig {
@@ -54,6 +62,10 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BROADCAST_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            speculative_execution_enabled: false,
+            speculative_execution_batch_size: 200,
+            speculative_execution_interval_ms: 100,
+            speculative_execution_cache_capacity: 10_000,
         }
     }
 }
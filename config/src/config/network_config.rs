@@ -21,7 +21,7 @@ use rand::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     fmt,
     path::PathBuf,
@@ -113,6 +113,8 @@ pub struct NetworkConfig {
     pub outbound_rate_limit_config: Option<RateLimitConfig>,
     // The maximum size of an inbound or outbound message (it may be divided into multiple frame)
     pub max_message_size: usize,
+    // Outbound traffic shaping configuration, if not specified, messages are written in FIFO order
+    pub outbound_traffic_shaping_config: Option<OutboundTrafficShapingConfig>,
 }
 
 impl Default for NetworkConfig {
@@ -149,6 +151,7 @@ impl NetworkConfig {
             inbound_rate_limit_config: None,
             outbound_rate_limit_config: None,
             max_message_size: MAX_MESSAGE_SIZE,
+            outbound_traffic_shaping_config: None,
             inbound_rx_buffer_size_bytes: Some(INBOUND_TCP_RX_BUFFER_SIZE),
             inbound_tx_buffer_size_bytes: Some(INBOUND_TCP_TX_BUFFER_SIZE),
             outbound_rx_buffer_size_bytes: Some(OUTBOUND_TCP_RX_BUFFER_SIZE),
@@ -424,6 +427,55 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Outbound traffic shaping configuration for each peer connection. Protocols
+/// are identified by their names (e.g., "StorageServiceRpc"). Consensus protocols
+/// are always served first and are never bandwidth limited, regardless of the
+/// values specified here.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboundTrafficShapingConfig {
+    /// Allow for disabling the traffic shaping
+    pub enabled: bool,
+    /// The priority class of each protocol (lower values are served first)
+    pub protocol_priorities: BTreeMap<String, u8>,
+    /// The priority class of protocols not found in `protocol_priorities`
+    pub default_priority: u8,
+    /// The outbound bandwidth limits of each protocol (protocols not found here are unlimited)
+    pub protocol_bandwidth_limits: BTreeMap<String, ProtocolBandwidthLimit>,
+}
+
+impl Default for OutboundTrafficShapingConfig {
+    fn default() -> Self {
+        let protocol_priorities = [
+            ("HealthCheckerRpc", 1),
+            ("PeerMonitoringServiceRpc", 1),
+            ("MempoolDirectSend", 2),
+            ("MempoolRpc", 2),
+            ("StorageServiceRpc", 3),
+            ("StateSyncDirectSend", 3),
+        ]
+        .into_iter()
+        .map(|(protocol, priority)| (protocol.to_string(), priority))
+        .collect();
+
+        Self {
+            enabled: true,
+            protocol_priorities,
+            default_priority: 2,
+            protocol_bandwidth_limits: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolBandwidthLimit {
+    /// Maximum number of bytes/s written for the protocol
+    pub byte_bucket_rate: usize,
+    /// Maximum burst of bytes written for the protocol
+    pub byte_bucket_size: usize,
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -30,6 +30,7 @@ pub enum BootstrappingMode {
     DownloadLatestStates, // Downloads the state keys and values (at the latest version)
     ExecuteTransactionsFromGenesis, // Executes transactions (starting at genesis)
     ExecuteOrApplyFromGenesis, // Executes transactions or applies outputs from genesis (whichever is faster)
+    DownloadLatestStatesFromBackup, // Downloads the state keys and values (at the latest version) from backup storage
 }
 
 impl BootstrappingMode {
@@ -43,8 +44,26 @@ impl BootstrappingMode {
                 "execute_transactions_from_genesis"
             },
             BootstrappingMode::ExecuteOrApplyFromGenesis => "execute_or_apply_from_genesis",
+            BootstrappingMode::DownloadLatestStatesFromBackup => {
+                "download_latest_states_from_backup"
+            },
         }
     }
+
+    /// Returns true iff the bootstrapping mode downloads the latest states
+    /// (instead of syncing all transactions or outputs from genesis).
+    pub fn is_fast_sync(&self) -> bool {
+        matches!(
+            self,
+            BootstrappingMode::DownloadLatestStates
+                | BootstrappingMode::DownloadLatestStatesFromBackup
+        )
+    }
+
+    /// Returns true iff the bootstrapping mode reads data from backup storage
+    pub fn is_backup_sync(&self) -> bool {
+        matches!(self, BootstrappingMode::DownloadLatestStatesFromBackup)
+    }
 }
 
 /// The continuous syncing mode determines how the node will stay up-to-date
@@ -112,13 +131,15 @@ impl Default for StateSyncDriverConfig {
 pub struct StorageServiceConfig {
     pub max_concurrent_requests: u64, // Max num of concurrent storage server tasks
     pub max_epoch_chunk_size: u64,    // Max num of epoch ending ledger infos per chunk
-    pub max_lru_cache_size: u64,      // Max num of items in the lru cache before eviction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lru_cache_size: Option<u64>, // Deprecated (and ignored): the response cache is bounded by `response_cache`
     pub max_network_channel_size: u64, // Max num of pending network messages
-    pub max_network_chunk_bytes: u64, // Max num of bytes to send per network message
-    pub max_state_chunk_size: u64,    // Max num of state keys and values per chunk
+    pub max_network_chunk_bytes: u64,  // Max num of bytes to send per network message
+    pub max_state_chunk_size: u64,     // Max num of state keys and values per chunk
     pub max_subscription_period_ms: u64, // Max period (ms) of pending subscription requests
     pub max_transaction_chunk_size: u64, // Max num of transactions per chunk
     pub max_transaction_output_chunk_size: u64, // Max num of transaction outputs per chunk
+    pub response_cache: ResponseCacheConfig, // The config for the storage server response cache
     pub storage_summary_refresh_interval_ms: u64, // The interval (ms) to refresh the storage summary
 }
 
@@ -127,18 +148,51 @@ impl Default for StorageServiceConfig {
         Self {
             max_concurrent_requests: 4000,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
-            max_lru_cache_size: 500, // At ~0.6MiB per chunk, this should take no more than 0.5GiB
+            max_lru_cache_size: None,
             max_network_channel_size: 4000,
             max_network_chunk_bytes: MAX_MESSAGE_SIZE as u64,
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_subscription_period_ms: 5000,
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
+            response_cache: ResponseCacheConfig::default(),
             storage_summary_refresh_interval_ms: 50,
         }
     }
 }
 
+/// The response cache holds serialized (and possibly compressed) storage
+/// server responses. Each request type has its own byte budget, and state
+/// value chunks (the most expensive responses to compute) can also be
+/// stored on disk, so that they survive restarts.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseCacheConfig {
+    pub enable_state_values_disk_cache: bool, // Whether to also store state value chunks on disk
+    pub max_disk_cache_bytes: u64,            // Max num of bytes of state value chunks held on disk
+    pub max_epoch_ending_ledger_infos_bytes: u64, // Max num of bytes of epoch ending ledger info responses
+    pub max_number_of_states_bytes: u64,          // Max num of bytes of number of states responses
+    pub max_state_values_bytes: u64,              // Max num of bytes of state value chunk responses
+    pub max_transaction_outputs_bytes: u64, // Max num of bytes of transaction output responses
+    pub max_transactions_bytes: u64,        // Max num of bytes of transaction responses
+    pub max_transactions_or_outputs_bytes: u64, // Max num of bytes of transaction or output responses
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enable_state_values_disk_cache: false,
+            max_disk_cache_bytes: 4 * 1024 * 1024 * 1024, // 4 GiB
+            max_epoch_ending_ledger_infos_bytes: 32 * 1024 * 1024, // 32 MiB
+            max_number_of_states_bytes: 1024 * 1024,      // 1 MiB
+            max_state_values_bytes: 256 * 1024 * 1024,    // 256 MiB
+            max_transaction_outputs_bytes: 128 * 1024 * 1024, // 128 MiB
+            max_transactions_bytes: 128 * 1024 * 1024,    // 128 MiB
+            max_transactions_or_outputs_bytes: 128 * 1024 * 1024, // 128 MiB
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DataStreamingServiceConfig {
@@ -185,7 +239,9 @@ impl Default for DataStreamingServiceConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct AptosDataClientConfig {
-    pub max_epoch_chunk_size: u64, // Max num of epoch ending ledger infos per chunk
+    pub enable_weighted_peer_selection: bool, // Whether to weigh peers by latency, throughput and score when selecting peers
+    pub latency_selection_weight: u64, // The relative weight of peer latency (from peer monitoring) when selecting peers
+    pub max_epoch_chunk_size: u64,     // Max num of epoch ending ledger infos per chunk
     pub max_num_in_flight_priority_polls: u64, // Max num of in-flight polls for priority peers
     pub max_num_in_flight_regular_polls: u64, // Max num of in-flight polls for regular peers
     pub max_num_output_reductions: u64, // The max num of output reductions before transactions are returned
@@ -193,15 +249,20 @@ pub struct AptosDataClientConfig {
     pub max_state_chunk_size: u64,    // Max num of state keys and values per chunk
     pub max_transaction_chunk_size: u64, // Max num of transactions per chunk
     pub max_transaction_output_chunk_size: u64, // Max num of transaction outputs per chunk
-    pub response_timeout_ms: u64,     // First timeout (in ms) when waiting for a response
+    pub num_peers_for_subscriptions: u64, // Num of peers to send each subscription request to (the first valid response is used)
+    pub response_timeout_ms: u64,         // First timeout (in ms) when waiting for a response
+    pub score_selection_weight: u64, // The relative weight of the peer score when selecting peers
     pub subscription_timeout_ms: u64, // Timeout (in ms) when waiting for a subscription response
     pub summary_poll_interval_ms: u64, // Interval (in ms) between data summary polls
-    pub use_compression: bool,        // Whether or not to request compression for incoming data
+    pub throughput_selection_weight: u64, // The relative weight of observed peer throughput when selecting peers
+    pub use_compression: bool,            // Whether or not to request compression for incoming data
 }
 
 impl Default for AptosDataClientConfig {
     fn default() -> Self {
         Self {
+            enable_weighted_peer_selection: false,
+            latency_selection_weight: 1,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_num_in_flight_priority_polls: 10,
             max_num_in_flight_regular_polls: 10,
@@ -210,9 +271,12 @@ impl Default for AptosDataClientConfig {
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
-            response_timeout_ms: 10000,    // 10 seconds
+            num_peers_for_subscriptions: 1,
+            response_timeout_ms: 10000, // 10 seconds
+            score_selection_weight: 1,
             subscription_timeout_ms: 5000, // 5 seconds
             summary_poll_interval_ms: 200,
+            throughput_selection_weight: 1,
             use_compression: true,
         }
     }
@@ -78,10 +78,41 @@ impl Default for RocksdbConfigs {
     }
 }
 
+/// The backup storage from which the node can bootstrap. This mirrors the
+/// storage types supported by the backup-cli.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupStorageType {
+    CommandAdapter(PathBuf), // The config file for a command adapter backup storage
+    LocalFs(PathBuf),        // The local directory holding the backups (mainly used for tests)
+}
+
+/// The config used by state sync when bootstrapping from backup storage
+/// (i.e., when using `BootstrappingMode::DownloadLatestStatesFromBackup`).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupStorageConfig {
+    pub backup_storage: Option<BackupStorageType>, // The backup storage to read from
+    pub concurrent_downloads: u64, // The max num of concurrent metadata file downloads
+    pub metadata_cache_dir: Option<PathBuf>, // The metadata cache dir (defaults to a temporary dir)
+}
+
+impl Default for BackupStorageConfig {
+    fn default() -> Self {
+        Self {
+            backup_storage: None,
+            concurrent_downloads: 8,
+            metadata_cache_dir: None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageConfig {
     pub backup_service_address: SocketAddr,
+    /// The backup storage from which state sync can bootstrap the node
+    pub backup_storage_config: BackupStorageConfig,
     pub dir: PathBuf,
     pub storage_pruner_config: PrunerConfig,
     #[serde(skip)]
@@ -236,6 +267,7 @@ impl Default for StorageConfig {
     fn default() -> StorageConfig {
         StorageConfig {
             backup_service_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 6186),
+            backup_storage_config: BackupStorageConfig::default(),
             dir: PathBuf::from("db"),
             // The prune window must at least out live a RPC request because its sub requests are
             // to return a consistent view of the DB at exactly same version. Considering a few
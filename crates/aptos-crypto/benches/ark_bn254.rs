@@ -0,0 +1,49 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+extern crate criterion;
+
+use aptos_crypto::{arkworks::hash_to_curve::Bn254G1SvdwMap, test_utils::random_bytes};
+use ark_bn254::G1Projective;
+use ark_ec::hashing::HashToCurve;
+use criterion::{BenchmarkId, Criterion};
+use rand::thread_rng;
+
+fn bench_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ark_bn254");
+
+    let linear_regression_max_num_datapoints = 20;
+    let hash_to_curve_max_msg_len = 1048576;
+
+    for msg_len in (0..hash_to_curve_max_msg_len)
+        .step_by(hash_to_curve_max_msg_len / linear_regression_max_num_datapoints)
+    {
+        group.bench_function(BenchmarkId::new("hash_to_g1_proj", msg_len), |b| {
+            b.iter_with_setup(
+                || {
+                    let dst = random_bytes(&mut thread_rng(), 255);
+                    let msg = random_bytes(&mut thread_rng(), msg_len);
+                    (dst, msg)
+                },
+                |(dst, msg)| {
+                    let mapper = ark_ec::hashing::map_to_curve_hasher::MapToCurveBasedHasher::<
+                        G1Projective,
+                        ark_ff::fields::field_hashers::DefaultFieldHasher<sha2_0_10_6::Sha256, 128>,
+                        Bn254G1SvdwMap,
+                    >::new(dst.as_slice())
+                    .unwrap();
+                    let _new_element = <G1Projective>::from(mapper.hash(msg.as_slice()).unwrap());
+                },
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    name = ark_bn254_benches;
+    config = Criterion::default();
+    targets = bench_group);
+criterion_main!(ark_bn254_benches);
@@ -0,0 +1,83 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maps to curves that ark-ec doesn't implement, for use with
+//! [`MapToCurveBasedHasher`](ark_ec::hashing::map_to_curve_hasher::MapToCurveBasedHasher).
+
+use ark_ec::{
+    hashing::{map_to_curve_hasher::MapToCurve, HashToCurveError},
+    short_weierstrass::SWCurveConfig,
+};
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+
+/// The Shallue-van de Woestijne method for BN254 G1 (i.e., `y^2 = x^3 + 3`) with `Z = 1`, as
+/// defined in https://datatracker.ietf.org/doc/html/rfc9380#name-shallue-van-de-woestijne-met.
+///
+/// NOTE: ark-ec only implements the simplified SWU map (and the WB map built on top of it), which
+/// requires a non-zero `A` coefficient or an isogenous curve that ark-bn254 does not provide.
+pub struct Bn254G1SvdwMap {
+    z: ark_bn254::Fq,
+    c1: ark_bn254::Fq,
+    c2: ark_bn254::Fq,
+    c3: ark_bn254::Fq,
+    c4: ark_bn254::Fq,
+}
+
+impl Bn254G1SvdwMap {
+    /// Evaluates the curve equation `g(x) = x^3 + A * x + B`, where `A = 0`.
+    fn g(x: ark_bn254::Fq) -> ark_bn254::Fq {
+        x.square() * x + ark_bn254::g1::Config::COEFF_B
+    }
+
+    fn is_square(x: &ark_bn254::Fq) -> bool {
+        x.is_zero() || x.legendre().is_qr()
+    }
+
+    fn sgn0(x: &ark_bn254::Fq) -> bool {
+        x.into_bigint().is_odd()
+    }
+}
+
+impl MapToCurve<ark_bn254::G1Projective> for Bn254G1SvdwMap {
+    fn new() -> Result<Self, HashToCurveError> {
+        let z = ark_bn254::Fq::one();
+        let g_z = Self::g(z);
+        // 3 * Z^2 + 4 * A, where A = 0.
+        let three_z_sq = z.square() * ark_bn254::Fq::from(3u64);
+        let c1 = g_z;
+        let c2 = -z / ark_bn254::Fq::from(2u64);
+        let mut c3 = (-g_z * three_z_sq).sqrt().ok_or_else(|| {
+            HashToCurveError::MapToCurveError("-g(Z) * (3 * Z^2 + 4 * A) is not square".to_string())
+        })?;
+        if Self::sgn0(&c3) {
+            c3 = -c3;
+        }
+        let c4 = -g_z * ark_bn254::Fq::from(4u64) / three_z_sq;
+        Ok(Self { z, c1, c2, c3, c4 })
+    }
+
+    fn map_to_curve(&self, u: ark_bn254::Fq) -> Result<ark_bn254::G1Affine, HashToCurveError> {
+        let tv1 = u.square() * self.c1;
+        let tv2 = ark_bn254::Fq::one() + tv1;
+        let tv1 = ark_bn254::Fq::one() - tv1;
+        let tv3 = (tv1 * tv2).inverse().unwrap_or_else(ark_bn254::Fq::zero);
+        let tv4 = u * tv1 * tv3 * self.c3;
+        let x1 = self.c2 - tv4;
+        let x2 = self.c2 + tv4;
+        let x3 = (tv2.square() * tv3).square() * self.c4 + self.z;
+        let x = if Self::is_square(&Self::g(x1)) {
+            x1
+        } else if Self::is_square(&Self::g(x2)) {
+            x2
+        } else {
+            x3
+        };
+        let mut y = Self::g(x)
+            .sqrt()
+            .ok_or_else(|| HashToCurveError::MapToCurveError("g(x) is not square".to_string()))?;
+        if Self::sgn0(&u) != Self::sgn0(&y) {
+            y = -y;
+        }
+        Ok(ark_bn254::G1Affine::new_unchecked(x, y))
+    }
+}
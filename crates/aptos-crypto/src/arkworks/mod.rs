@@ -0,0 +1,7 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extensions of the [arkworks](https://github.com/arkworks-rs) libraries, shared by the Move
+//! algebra natives and their benchmarks.
+
+pub mod hash_to_curve;
@@ -6,6 +6,7 @@
 #![deny(missing_docs)]
 
 //! A library supplying various cryptographic primitives
+pub mod arkworks;
 pub mod bls12381;
 pub mod compat;
 pub mod ed25519;
@@ -14,6 +15,8 @@ pub mod hash;
 pub mod hkdf;
 pub mod multi_ed25519;
 pub mod noise;
+pub mod secp256k1_ecdsa;
+pub mod secp256r1_ecdsa;
 pub mod test_utils;
 pub mod traits;
 pub mod validatable;
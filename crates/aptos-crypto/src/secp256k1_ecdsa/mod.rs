@@ -0,0 +1,51 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for the ECDSA signature scheme over the secp256k1 curve (i.e., the
+//! curve used by Bitcoin and Ethereum keys), as implemented by
+//! [libsecp256k1](https://docs.rs/libsecp256k1).
+//!
+//! Messages are hashed with SHA3-256 before being signed. Signature verification rejects
+//! signatures with a high S component (i.e., S > N/2, where N is the order of the curve), which
+//! prevents third-party signature malleability.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256k1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<PrivateKey, PublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message).unwrap();
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of the Secp256k1EcdsaPrivateKey
+pub const PRIVATE_KEY_LENGTH: usize = libsecp256k1::util::SECRET_KEY_SIZE;
+/// The length of the Secp256k1EcdsaPublicKey (in uncompressed form)
+pub const PUBLIC_KEY_LENGTH: usize = libsecp256k1::util::FULL_PUBLIC_KEY_SIZE;
+/// The length of the Secp256k1EcdsaSignature
+pub const SIGNATURE_LENGTH: usize = libsecp256k1::util::SIGNATURE_SIZE;
+
+pub mod secp256k1_ecdsa_keys;
+pub mod secp256k1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256k1_ecdsa_keys::keypair_strategy;
+pub use secp256k1_ecdsa_keys::{
+    Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPrivateKey as PrivateKey, Secp256k1EcdsaPublicKey,
+    Secp256k1EcdsaPublicKey as PublicKey,
+};
+pub use secp256k1_ecdsa_sigs::{Secp256k1EcdsaSignature, Secp256k1EcdsaSignature as Signature};
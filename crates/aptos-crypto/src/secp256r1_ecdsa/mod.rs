@@ -0,0 +1,51 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for the ECDSA signature scheme over the secp256r1 curve (also
+//! known as NIST P-256), as implemented by [p256](https://docs.rs/p256). This is the scheme used
+//! by WebAuthn authenticators (e.g., passkeys) under the ES256 algorithm.
+//!
+//! Messages are hashed with SHA2-256 before being signed (as mandated by ES256). Signature
+//! verification rejects signatures with a high S component (i.e., S > N/2, where N is the order
+//! of the curve), which prevents third-party signature malleability.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256r1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<PrivateKey, PublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message).unwrap();
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of the Secp256r1EcdsaPrivateKey
+pub const PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of the Secp256r1EcdsaPublicKey (in uncompressed SEC1 form)
+pub const PUBLIC_KEY_LENGTH: usize = 65;
+/// The length of the Secp256r1EcdsaSignature
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub mod secp256r1_ecdsa_keys;
+pub mod secp256r1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256r1_ecdsa_keys::keypair_strategy;
+pub use secp256r1_ecdsa_keys::{
+    Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPrivateKey as PrivateKey, Secp256r1EcdsaPublicKey,
+    Secp256r1EcdsaPublicKey as PublicKey,
+};
+pub use secp256r1_ecdsa_sigs::{Secp256r1EcdsaSignature, Secp256r1EcdsaSignature as Signature};
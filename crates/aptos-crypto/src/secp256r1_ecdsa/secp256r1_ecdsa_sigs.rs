@@ -0,0 +1,175 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256r1 ECDSA signatures.
+
+use crate::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, SIGNATURE_LENGTH},
+    traits::*,
+};
+use anyhow::{anyhow, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
+use core::convert::TryFrom;
+use p256::ecdsa::signature::Verifier;
+use serde::Serialize;
+use std::fmt;
+
+/// A secp256r1 ECDSA signature
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Secp256r1EcdsaSignature(pub(crate) p256::ecdsa::Signature);
+
+impl Secp256r1EcdsaSignature {
+    /// The length of the Secp256r1EcdsaSignature
+    pub const LENGTH: usize = SIGNATURE_LENGTH;
+
+    /// Serialize a Secp256r1EcdsaSignature (as r || s).
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes.copy_from_slice(self.0.as_ref());
+        bytes
+    }
+
+    /// Deserialize a Secp256r1EcdsaSignature without any validation checks (malleability)
+    /// apart from expected signature size and the r and s components being valid scalars.
+    pub(crate) fn from_bytes_unchecked(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        if bytes.len() != SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        match p256::ecdsa::Signature::try_from(bytes) {
+            Ok(signature) => Ok(Secp256r1EcdsaSignature(signature)),
+            Err(_) => Err(CryptoMaterialError::DeserializationError),
+        }
+    }
+
+    /// Check for signature malleability. Given a valid ECDSA signature (r, s), the signature
+    /// (r, N - s) is also valid for the same message and key (where N is the order of the curve).
+    /// To prevent third parties from producing a distinct yet valid signature, we only accept
+    /// signatures with a low S component (i.e., s <= N/2).
+    ///
+    /// Note: some WebAuthn authenticators produce high S signatures. Clients are expected to
+    /// normalize these before submitting them (see `from_bytes_with_normalized_s`).
+    pub fn check_s_malleability(&self) -> std::result::Result<(), CryptoMaterialError> {
+        if self.0.normalize_s().is_some() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(())
+    }
+
+    /// Deserialize a Secp256r1EcdsaSignature, replacing a high S component with N - s. This
+    /// should only be used by clients that receive signatures from external authenticators
+    /// (e.g., WebAuthn), before submitting them.
+    pub fn from_bytes_with_normalized_s(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        let signature = Secp256r1EcdsaSignature::from_bytes_unchecked(bytes)?.0;
+        Ok(Secp256r1EcdsaSignature(
+            signature.normalize_s().unwrap_or(signature),
+        ))
+    }
+
+    /// Deserialize an ASN.1 DER encoded Secp256r1EcdsaSignature (i.e., the format returned by
+    /// WebAuthn authenticators), replacing a high S component with N - s.
+    pub fn from_der_with_normalized_s(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        let signature = p256::ecdsa::Signature::from_der(bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Ok(Secp256r1EcdsaSignature(
+            signature.normalize_s().unwrap_or(signature),
+        ))
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for Secp256r1EcdsaSignature {
+    type SigningKeyMaterial = Secp256r1EcdsaPrivateKey;
+    type VerifyingKeyMaterial = Secp256r1EcdsaPublicKey;
+
+    /// Verifies that the provided signature is valid for the provided message, rejecting
+    /// signatures with a high S component.
+    fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        Self::verify_arbitrary_msg(self, &signing_message(message)?, public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`. The
+    /// message is hashed using SHA2-256 before verification (i.e., as done by ES256).
+    fn verify_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        self.check_s_malleability()?;
+
+        public_key
+            .0
+            .verify(message, &self.0)
+            .map_err(|e| anyhow!("{}", e))
+            .and(Ok(()))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Length for Secp256r1EcdsaSignature {
+    fn length(&self) -> usize {
+        SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1EcdsaSignature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for Secp256r1EcdsaSignature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1EcdsaSignature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        // We leave this check here to detect mauled signatures earlier (this check is
+        // performed again in Secp256r1EcdsaSignature::verify_arbitrary_msg).
+        let signature = Secp256r1EcdsaSignature::from_bytes_unchecked(bytes)?;
+        signature.check_s_malleability()?;
+        Ok(signature)
+    }
+}
+
+// Those are required by the implementation of hash above
+impl PartialEq for Secp256r1EcdsaSignature {
+    fn eq(&self, other: &Secp256r1EcdsaSignature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Secp256r1EcdsaSignature {}
+
+impl fmt::Display for Secp256r1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for Secp256r1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256r1EcdsaSignature({})", self)
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    secp256r1_ecdsa::{
+        Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature,
+        PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
+    },
+    test_utils::{random_serializable_struct, uniform_keypair_strategy},
+    traits::*,
+    CryptoMaterialError,
+};
+use core::convert::TryFrom;
+use proptest::{collection::vec, prelude::*};
+
+proptest! {
+    #[test]
+    fn test_keys_encode(
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        {
+            let encoded = keypair.private_key.to_encoded_string().unwrap();
+            prop_assert_eq!(2 + 2 * PRIVATE_KEY_LENGTH, encoded.len());
+            let decoded = Secp256r1EcdsaPrivateKey::from_encoded_string(&encoded);
+            prop_assert_eq!(Some(keypair.private_key), decoded.ok());
+        }
+        {
+            let encoded = keypair.public_key.to_encoded_string().unwrap();
+            prop_assert_eq!(2 + 2 * PUBLIC_KEY_LENGTH, encoded.len());
+            let decoded = Secp256r1EcdsaPublicKey::from_encoded_string(&encoded);
+            prop_assert_eq!(Some(keypair.public_key), decoded.ok());
+        }
+    }
+
+    #[test]
+    fn test_keys_custom_serialisation(
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        {
+            let serialized: &[u8] = &(keypair.private_key.to_bytes());
+            prop_assert_eq!(PRIVATE_KEY_LENGTH, serialized.len());
+            let deserialized = Secp256r1EcdsaPrivateKey::try_from(serialized);
+            prop_assert_eq!(Some(keypair.private_key), deserialized.ok());
+        }
+        {
+            let serialized: &[u8] = &(keypair.public_key.to_bytes());
+            prop_assert_eq!(PUBLIC_KEY_LENGTH, serialized.len());
+            let deserialized = Secp256r1EcdsaPublicKey::try_from(serialized);
+            prop_assert_eq!(Some(keypair.public_key), deserialized.ok());
+        }
+    }
+
+    #[test]
+    fn test_signature_verification_custom_serialisation(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message).unwrap();
+        let serialized: &[u8] = &(signature.to_bytes());
+        prop_assert_eq!(SIGNATURE_LENGTH, serialized.len());
+        let deserialized = Secp256r1EcdsaSignature::try_from(serialized).unwrap();
+        prop_assert!(deserialized.verify(&message, &keypair.public_key).is_ok());
+    }
+
+    #[test]
+    fn test_signature_verification_from_arbitrary(
+        msg in vec(proptest::num::u8::ANY, 1..128),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign_arbitrary_message(&msg);
+        prop_assert!(signature.verify_arbitrary_msg(&msg, &keypair.public_key).is_ok());
+
+        // Verification should fail for a different message
+        let mut other_msg = msg.clone();
+        other_msg[0] ^= 1;
+        prop_assert!(signature.verify_arbitrary_msg(&other_msg, &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_signature_verification_wrong_key(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>(),
+        other_keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        prop_assume!(keypair.public_key != other_keypair.public_key);
+        let signature = keypair.private_key.sign(&message).unwrap();
+        prop_assert!(signature.verify(&message, &other_keypair.public_key).is_err());
+    }
+
+    // Check for low S.
+    #[test]
+    fn test_signature_malleability(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message).unwrap();
+        prop_assert!(signature.check_s_malleability().is_ok());
+
+        // Negate S to produce the "high S" twin of the signature
+        let (r, s) = signature.0.split_scalars();
+        let mauled_signature = Secp256r1EcdsaSignature(
+            p256::ecdsa::Signature::from_scalars(r, -s).unwrap()
+        );
+
+        // The mauled signature should be rejected during deserialization and verification
+        let serialized: &[u8] = &(mauled_signature.to_bytes());
+        prop_assert_eq!(
+            Secp256r1EcdsaSignature::try_from(serialized),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+        prop_assert!(mauled_signature.verify(&message, &keypair.public_key).is_err());
+
+        // Normalizing the mauled signature should recover the original signature
+        let normalized_signature = Secp256r1EcdsaSignature::from_bytes_with_normalized_s(serialized).unwrap();
+        prop_assert_eq!(&normalized_signature, &signature);
+        prop_assert!(normalized_signature.verify(&message, &keypair.public_key).is_ok());
+
+        // The same applies to DER encoded signatures (e.g., as returned by WebAuthn authenticators)
+        let der_signature = mauled_signature.0.to_der();
+        let normalized_signature = Secp256r1EcdsaSignature::from_der_with_normalized_s(der_signature.as_bytes()).unwrap();
+        prop_assert_eq!(&normalized_signature, &signature);
+    }
+}
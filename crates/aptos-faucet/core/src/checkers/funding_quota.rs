@@ -0,0 +1,644 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckerData, CheckerTrait, CompleteData};
+use crate::{
+    common::RedisConnectionConfig,
+    endpoints::{AptosTapError, AptosTapErrorCode, RejectionReason, RejectionReasonCode},
+};
+use anyhow::{bail, Context, Result};
+use aptos_logger::{info, warn};
+use aptos_sdk::crypto::HashValue;
+use async_trait::async_trait;
+use deadpool_redis::{redis::AsyncCommands, Connection, Pool};
+use lru::LruCache;
+use poem::http::header::AUTHORIZATION;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{sync::Mutex, task::JoinSet};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FundingQuotaCheckerConfig {
+    /// Max total amount a receiver address can be funded within the window.
+    pub max_amount_per_receiver: Option<u64>,
+
+    /// Max total amount that can be funded within the window by requests
+    /// using the same auth token. Requests without an auth token are not
+    /// limited by this.
+    pub max_amount_per_auth_token: Option<u64>,
+
+    /// Length of the sliding window the quotas apply to.
+    #[serde(default = "FundingQuotaCheckerConfig::default_window_secs")]
+    pub window_secs: u64,
+
+    /// Where to keep track of how much has been funded.
+    pub storage: FundingQuotaStorageConfig,
+}
+
+impl FundingQuotaCheckerConfig {
+    fn default_window_secs() -> u64 {
+        86400
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum FundingQuotaStorageConfig {
+    /// Keeps usage in memory, optionally saving it to a file so that it
+    /// survives restarts.
+    Memory(MemoryQuotaStorageConfig),
+
+    /// Keeps usage in Redis, so that multiple faucet instances share quotas.
+    Redis(RedisConnectionConfig),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MemoryQuotaStorageConfig {
+    /// To avoid OOMing the server, we set a limit on how many receivers and
+    /// auth tokens we track.
+    #[serde(default = "MemoryQuotaStorageConfig::default_max_entries_in_map")]
+    pub max_entries_in_map: NonZeroUsize,
+
+    /// If given, usage is loaded from this file on startup and periodically
+    /// written back to it.
+    pub file: Option<PathBuf>,
+
+    /// How often to write usage to `file`.
+    #[serde(default = "MemoryQuotaStorageConfig::default_persist_interval_secs")]
+    pub persist_interval_secs: u64,
+}
+
+impl MemoryQuotaStorageConfig {
+    fn default_max_entries_in_map() -> NonZeroUsize {
+        NonZeroUsize::new(1000000).unwrap()
+    }
+
+    fn default_persist_interval_secs() -> u64 {
+        60
+    }
+}
+
+/// A request counted against a quota.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct QuotaUsage {
+    time_secs: u64,
+    amount: u64,
+    /// Distinguishes requests received at the same time for the same amount,
+    /// even from the same receiver and IP.
+    request_id: String,
+}
+
+impl QuotaUsage {
+    fn from_checker_data(data: &CheckerData) -> Self {
+        Self {
+            time_secs: data.time_request_received_secs,
+            amount: data.amount,
+            request_id: data.request_id.to_string(),
+        }
+    }
+
+    /// The member of the Redis sorted set representing this usage.
+    fn to_member(&self) -> String {
+        format!("{}:{}:{}", self.time_secs, self.amount, self.request_id)
+    }
+
+    fn from_member(member: &str) -> Option<Self> {
+        let mut parts = member.splitn(3, ':');
+        Some(Self {
+            time_secs: parts.next()?.parse().ok()?,
+            amount: parts.next()?.parse().ok()?,
+            request_id: parts.next()?.to_string(),
+        })
+    }
+}
+
+type UsageMap = LruCache<String, VecDeque<QuotaUsage>>;
+
+struct MemoryQuotaStorage {
+    usage: Arc<Mutex<UsageMap>>,
+    config: MemoryQuotaStorageConfig,
+}
+
+impl MemoryQuotaStorage {
+    fn new(config: MemoryQuotaStorageConfig) -> Result<Self> {
+        let mut usage = LruCache::new(config.max_entries_in_map);
+        if let Some(file) = &config.file {
+            if file.exists() {
+                let contents = std::fs::read(file)
+                    .with_context(|| format!("Failed to read {}", file.to_string_lossy()))?;
+                let saved_usage: HashMap<String, VecDeque<QuotaUsage>> =
+                    serde_json::from_slice(&contents).with_context(|| {
+                        format!("Failed to parse usage in {}", file.to_string_lossy())
+                    })?;
+                info!(
+                    "Loaded funding quota usage for {} keys from {}",
+                    saved_usage.len(),
+                    file.to_string_lossy()
+                );
+                for (key, key_usage) in saved_usage {
+                    usage.put(key, key_usage);
+                }
+            }
+        }
+        Ok(Self {
+            usage: Arc::new(Mutex::new(usage)),
+            config,
+        })
+    }
+
+    /// Writes the usage to a temporary file first, so a crash mid-write
+    /// doesn't leave a corrupt file behind.
+    async fn persist(usage: &Mutex<UsageMap>, file: &PathBuf) -> Result<()> {
+        let saved_usage: HashMap<String, VecDeque<QuotaUsage>> = usage
+            .lock()
+            .await
+            .iter()
+            .map(|(key, key_usage)| (key.clone(), key_usage.clone()))
+            .collect();
+        let tmp_file = file.with_extension("tmp");
+        tokio::fs::write(&tmp_file, serde_json::to_vec(&saved_usage)?)
+            .await
+            .with_context(|| format!("Failed to write {}", tmp_file.to_string_lossy()))?;
+        tokio::fs::rename(&tmp_file, file)
+            .await
+            .with_context(|| format!("Failed to move usage to {}", file.to_string_lossy()))?;
+        Ok(())
+    }
+}
+
+/// Redis keeps the usage of each key in a sorted set, scored by time, so
+/// that usage outside the window can be dropped with a single command.
+enum QuotaStorage {
+    Memory(MemoryQuotaStorage),
+    Redis(Pool),
+}
+
+impl QuotaStorage {
+    async fn get_redis_connection(pool: &Pool) -> Result<Connection, AptosTapError> {
+        pool.get().await.map_err(|e| {
+            AptosTapError::new_with_error_code(
+                format!("Failed to connect to redis storage: {}", e),
+                AptosTapErrorCode::StorageError,
+            )
+        })
+    }
+
+    /// Returns the usage of the key after `window_start_secs`.
+    async fn get_usage(
+        &self,
+        key: &str,
+        window_start_secs: u64,
+    ) -> Result<Vec<QuotaUsage>, AptosTapError> {
+        match self {
+            QuotaStorage::Memory(storage) => Ok(storage
+                .usage
+                .lock()
+                .await
+                .peek(key)
+                .map(|key_usage| {
+                    key_usage
+                        .iter()
+                        .filter(|usage| usage.time_secs > window_start_secs)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()),
+            QuotaStorage::Redis(pool) => {
+                let mut conn = Self::get_redis_connection(pool).await?;
+                let members: Vec<String> = conn
+                    .zrangebyscore(key, format!("({}", window_start_secs), "+inf")
+                    .await
+                    .map_err(|e| {
+                        AptosTapError::new_with_error_code(
+                            format!("Failed to get usage for redis key {}: {}", key, e),
+                            AptosTapErrorCode::StorageError,
+                        )
+                    })?;
+                Ok(members
+                    .iter()
+                    .filter_map(|member| QuotaUsage::from_member(member))
+                    .collect())
+            },
+        }
+    }
+
+    /// Adds the usage to the key, dropping usage from before
+    /// `window_start_secs`, and returns the usage of the key including the
+    /// new one.
+    async fn add_usage(
+        &self,
+        key: &str,
+        usage: &QuotaUsage,
+        window_start_secs: u64,
+        window_secs: u64,
+    ) -> Result<Vec<QuotaUsage>, AptosTapError> {
+        match self {
+            QuotaStorage::Memory(storage) => {
+                let mut usage_map = storage.usage.lock().await;
+                let key_usage = usage_map.get_or_insert_mut(key.to_string(), VecDeque::new);
+                key_usage.retain(|usage| usage.time_secs > window_start_secs);
+                key_usage.push_back(usage.clone());
+                Ok(key_usage.iter().cloned().collect())
+            },
+            QuotaStorage::Redis(pool) => {
+                let mut conn = Self::get_redis_connection(pool).await?;
+                let (members,): (Vec<String>,) = redis::pipe()
+                    .atomic()
+                    .zrembyscore(key, "-inf", window_start_secs)
+                    .ignore()
+                    .zadd(key, usage.to_member(), usage.time_secs)
+                    .ignore()
+                    // Nothing in the set matters once the window has passed.
+                    .expire(key, window_secs as usize)
+                    .ignore()
+                    .zrange(key, 0, -1)
+                    .query_async(&mut *conn)
+                    .await
+                    .map_err(|e| {
+                        AptosTapError::new_with_error_code(
+                            format!("Failed to add usage for redis key {}: {}", key, e),
+                            AptosTapErrorCode::StorageError,
+                        )
+                    })?;
+                Ok(members
+                    .iter()
+                    .filter_map(|member| QuotaUsage::from_member(member))
+                    .collect())
+            },
+        }
+    }
+
+    async fn remove_usage(&self, key: &str, usage: &QuotaUsage) -> Result<(), AptosTapError> {
+        match self {
+            QuotaStorage::Memory(storage) => {
+                if let Some(key_usage) = storage.usage.lock().await.get_mut(key) {
+                    if let Some(index) = key_usage.iter().position(|u| u == usage) {
+                        key_usage.remove(index);
+                    }
+                }
+                Ok(())
+            },
+            QuotaStorage::Redis(pool) => {
+                let mut conn = Self::get_redis_connection(pool).await?;
+                let _: i64 = conn.zrem(key, usage.to_member()).await.map_err(|e| {
+                    AptosTapError::new_with_error_code(
+                        format!("Failed to remove usage for redis key {}: {}", key, e),
+                        AptosTapErrorCode::StorageError,
+                    )
+                })?;
+                Ok(())
+            },
+        }
+    }
+}
+
+/// A quota that applies to a request.
+struct Quota {
+    key: String,
+    max_amount: u64,
+    code: RejectionReasonCode,
+    /// What the quota is for, used in the rejection reason.
+    subject: String,
+}
+
+/// The FundingQuotaChecker limits the total amount funded to a receiver, and
+/// by requests using the same auth token, over a sliding window.
+///
+/// Like the ratelimit checkers, usage is recorded when the request is checked,
+/// so that concurrent requests can't all slip under the quota, and removed
+/// again if the request is rejected by this checker or fails because of
+/// something wrong on our end.
+pub struct FundingQuotaChecker {
+    config: FundingQuotaCheckerConfig,
+    storage: QuotaStorage,
+}
+
+impl FundingQuotaChecker {
+    pub async fn new(config: FundingQuotaCheckerConfig) -> Result<Self> {
+        if config.max_amount_per_receiver.is_none() && config.max_amount_per_auth_token.is_none() {
+            bail!(
+                "At least one of max_amount_per_receiver and max_amount_per_auth_token must be set"
+            );
+        }
+        let storage = match &config.storage {
+            FundingQuotaStorageConfig::Memory(storage_config) => {
+                QuotaStorage::Memory(MemoryQuotaStorage::new(storage_config.clone())?)
+            },
+            FundingQuotaStorageConfig::Redis(connection_config) => {
+                let db_pool = connection_config.build_db_pool()?;
+
+                // Ensure we can connect.
+                db_pool
+                    .get()
+                    .await
+                    .context("Failed to connect to redis on startup")?;
+
+                QuotaStorage::Redis(db_pool)
+            },
+        };
+        Ok(Self { config, storage })
+    }
+
+    fn get_quotas(&self, data: &CheckerData) -> Vec<Quota> {
+        let mut quotas = vec![];
+        if let Some(max_amount) = self.config.max_amount_per_receiver {
+            quotas.push(Quota {
+                key: format!("quota:receiver:{}", data.receiver),
+                max_amount,
+                code: RejectionReasonCode::ReceiverQuotaExhausted,
+                subject: format!("Receiver {}", data.receiver),
+            });
+        }
+        if let (Some(max_amount), Some(auth_token)) =
+            (self.config.max_amount_per_auth_token, get_auth_token(data))
+        {
+            // We don't want to store the auth tokens themselves.
+            quotas.push(Quota {
+                key: format!(
+                    "quota:auth_token:{}",
+                    HashValue::sha3_256_of(auth_token.as_bytes()).to_hex()
+                ),
+                max_amount,
+                code: RejectionReasonCode::AuthTokenQuotaExhausted,
+                subject: "The given auth token".to_string(),
+            });
+        }
+        quotas
+    }
+
+    /// Returns how long until enough of the previous usage leaves the window
+    /// for a request of the given amount to fit in the quota. If it'll never
+    /// fit, returns None.
+    fn seconds_until_available(
+        &self,
+        mut previous_usage: Vec<QuotaUsage>,
+        amount: u64,
+        max_amount: u64,
+        now_secs: u64,
+    ) -> Option<u64> {
+        if amount > max_amount {
+            return None;
+        }
+        previous_usage.sort_by_key(|usage| usage.time_secs);
+        let mut available_at_secs = now_secs;
+        for (i, usage) in previous_usage.iter().enumerate() {
+            // Sum what's still in the window rather than subtracting from a
+            // running total, since the total might have saturated.
+            if fits_in_quota(total_amount(&previous_usage[i..]), amount, max_amount) {
+                break;
+            }
+            available_at_secs = usage.time_secs.saturating_add(self.config.window_secs);
+        }
+        Some(available_at_secs.saturating_sub(now_secs))
+    }
+}
+
+/// The total amount of the given usage, saturating at u64::MAX.
+fn total_amount(usage: &[QuotaUsage]) -> u64 {
+    usage
+        .iter()
+        .fold(0u64, |total, usage| total.saturating_add(usage.amount))
+}
+
+/// Whether requesting `amount` on top of `used` stays within `max_amount`.
+/// A total that overflows u64 is never within the quota.
+fn fits_in_quota(used: u64, amount: u64, max_amount: u64) -> bool {
+    used.checked_add(amount)
+        .map_or(false, |total| total <= max_amount)
+}
+
+#[async_trait]
+impl CheckerTrait for FundingQuotaChecker {
+    async fn check(
+        &self,
+        data: CheckerData,
+        dry_run: bool,
+    ) -> Result<Vec<RejectionReason>, AptosTapError> {
+        let now_secs = data.time_request_received_secs;
+        let window_start_secs = now_secs.saturating_sub(self.config.window_secs);
+        let usage = QuotaUsage::from_checker_data(&data);
+
+        let mut rejection_reasons = vec![];
+        let mut recorded_keys = vec![];
+        for quota in self.get_quotas(&data) {
+            let previous_usage: Vec<QuotaUsage> = if dry_run {
+                self.storage
+                    .get_usage(&quota.key, window_start_secs)
+                    .await?
+            } else {
+                let key_usage = self
+                    .storage
+                    .add_usage(
+                        &quota.key,
+                        &usage,
+                        window_start_secs,
+                        self.config.window_secs,
+                    )
+                    .await?;
+                recorded_keys.push(quota.key.clone());
+                key_usage.into_iter().filter(|u| u != &usage).collect()
+            };
+
+            let used = total_amount(&previous_usage);
+            if !fits_in_quota(used, usage.amount, quota.max_amount) {
+                let mut rejection_reason = RejectionReason::new(
+                    format!(
+                        "{} has been funded {} in the last {} seconds, requesting {} more would exceed the quota of {}",
+                        quota.subject, used, self.config.window_secs, usage.amount, quota.max_amount
+                    ),
+                    quota.code,
+                );
+                if let Some(retry_after) = self.seconds_until_available(
+                    previous_usage,
+                    usage.amount,
+                    quota.max_amount,
+                    now_secs,
+                ) {
+                    rejection_reason = rejection_reason.retry_after(retry_after);
+                }
+                rejection_reasons.push(rejection_reason);
+            }
+        }
+
+        // A request we reject won't be funded, so it shouldn't count against
+        // any of its quotas.
+        if !rejection_reasons.is_empty() {
+            for key in recorded_keys {
+                self.storage.remove_usage(&key, &usage).await?;
+            }
+        }
+
+        Ok(rejection_reasons)
+    }
+
+    /// If the request failed because of something on our end, remove its
+    /// usage from its quotas.
+    async fn complete(&self, data: CompleteData) -> Result<(), AptosTapError> {
+        if !data.response_is_500 {
+            return Ok(());
+        }
+        let usage = QuotaUsage::from_checker_data(&data.checker_data);
+        for quota in self.get_quotas(&data.checker_data) {
+            self.storage.remove_usage(&quota.key, &usage).await?;
+        }
+        Ok(())
+    }
+
+    fn cost(&self) -> u8 {
+        match self.storage {
+            QuotaStorage::Memory(_) => 25,
+            QuotaStorage::Redis(_) => 60,
+        }
+    }
+
+    fn spawn_periodic_tasks(&self, join_set: &mut JoinSet<anyhow::Result<()>>) {
+        if let QuotaStorage::Memory(storage) = &self.storage {
+            if let Some(file) = storage.config.file.clone() {
+                let usage = storage.usage.clone();
+                let persist_interval = Duration::from_secs(storage.config.persist_interval_secs);
+                join_set.spawn(async move {
+                    loop {
+                        tokio::time::sleep(persist_interval).await;
+                        if let Err(e) = MemoryQuotaStorage::persist(&usage, &file).await {
+                            warn!("Failed to save funding quota usage: {:#}", e);
+                        }
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Gets the auth token the same way the AuthTokenChecker does.
+fn get_auth_token(data: &CheckerData) -> Option<&str> {
+    data.headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split_whitespace().nth(1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_sdk::types::account_address::AccountAddress;
+    use poem::http::HeaderMap;
+    use std::net::{IpAddr, Ipv4Addr};
+    use uuid::Uuid;
+
+    async fn new_checker(max_amount_per_receiver: u64) -> FundingQuotaChecker {
+        FundingQuotaChecker::new(FundingQuotaCheckerConfig {
+            max_amount_per_receiver: Some(max_amount_per_receiver),
+            max_amount_per_auth_token: None,
+            window_secs: 100,
+            storage: FundingQuotaStorageConfig::Memory(MemoryQuotaStorageConfig {
+                max_entries_in_map: NonZeroUsize::new(10).unwrap(),
+                file: None,
+                persist_interval_secs: 60,
+            }),
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Requests that only differ in their request ID.
+    fn new_checker_data(receiver: AccountAddress) -> CheckerData {
+        CheckerData {
+            request_id: Uuid::new_v4(),
+            time_request_received_secs: 1000,
+            amount: 10,
+            receiver,
+            source_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            headers: Arc::new(HeaderMap::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overflowing_usage_is_rejected() {
+        let checker = new_checker(u64::MAX).await;
+        let receiver = AccountAddress::random();
+        let mut first = new_checker_data(receiver);
+        first.amount = u64::MAX - 5;
+        assert!(checker.check(first, false).await.unwrap().is_empty());
+
+        // The total would wrap around to less than the quota if it weren't
+        // checked.
+        let mut second = new_checker_data(receiver);
+        second.amount = 10;
+        assert_eq!(checker.check(second.clone(), true).await.unwrap().len(), 1);
+        assert_eq!(checker.check(second, false).await.unwrap().len(), 1);
+
+        // The saturated total doesn't hide how long until the quota frees up.
+        assert_eq!(
+            checker.seconds_until_available(
+                vec![
+                    QuotaUsage {
+                        request_id: Uuid::new_v4().to_string(),
+                        time_secs: 950,
+                        amount: u64::MAX,
+                    },
+                    QuotaUsage {
+                        request_id: Uuid::new_v4().to_string(),
+                        time_secs: 980,
+                        amount: u64::MAX,
+                    },
+                ],
+                10,
+                u64::MAX,
+                1000,
+            ),
+            Some(80)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_identical_requests_are_counted_separately() {
+        let checker = new_checker(20).await;
+        let receiver = AccountAddress::random();
+        let first = new_checker_data(receiver);
+        let second = new_checker_data(receiver);
+
+        assert!(checker
+            .check(first.clone(), false)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(checker.check(second, false).await.unwrap().is_empty());
+        assert_eq!(
+            checker
+                .check(new_checker_data(receiver), false)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Removing the usage of a failed request only frees up its own amount.
+        checker
+            .complete(CompleteData {
+                checker_data: first,
+                txn_hashes: vec![],
+                response_is_500: true,
+            })
+            .await
+            .unwrap();
+        assert!(checker
+            .check(new_checker_data(receiver), false)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            checker
+                .check(new_checker_data(receiver), true)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod auth_token;
+mod funding_quota;
 mod google_captcha;
 mod ip_blocklist;
 mod magic_header;
@@ -13,6 +14,7 @@ mod tap_captcha;
 pub use self::tap_captcha::CaptchaManager;
 use self::{
     auth_token::AuthTokenChecker,
+    funding_quota::{FundingQuotaChecker, FundingQuotaCheckerConfig},
     google_captcha::{CaptchaChecker as GoogleCaptchaChecker, GoogleCaptchaCheckerConfig},
     ip_blocklist::IpBlocklistChecker,
     magic_header::{MagicHeaderChecker, MagicHeaderCheckerConfig},
@@ -34,6 +36,7 @@ use poem::http::HeaderMap;
 use serde::{Deserialize, Serialize};
 use std::{net::IpAddr, sync::Arc};
 use tokio::task::JoinSet;
+use uuid::Uuid;
 
 /// Implementers of this trait are responsible for checking something about the
 /// request, and if it doesn't look valid, returning a list of rejection reasons
@@ -84,6 +87,10 @@ pub enum CheckerConfig {
     /// Requires that an auth token is included in the Authorization header.
     AuthToken(ListManagerConfig),
 
+    /// Limits the total amount funded per receiver and per auth token over a
+    /// sliding window.
+    FundingQuota(FundingQuotaCheckerConfig),
+
     /// Requires a legitimate Google ReCaptcha token.
     GoogleCaptcha(GoogleCaptchaCheckerConfig),
 
@@ -110,6 +117,9 @@ impl CheckerConfig {
     pub async fn build(self, captcha_manager: Arc<Mutex<CaptchaManager>>) -> Result<Checker> {
         Ok(match self {
             CheckerConfig::AuthToken(config) => Checker::from(AuthTokenChecker::new(config)?),
+            CheckerConfig::FundingQuota(config) => {
+                Checker::from(FundingQuotaChecker::new(config).await?)
+            },
             CheckerConfig::GoogleCaptcha(config) => {
                 Checker::from(GoogleCaptchaChecker::new(config)?)
             },
@@ -135,6 +145,7 @@ impl CheckerConfig {
 #[enum_dispatch(CheckerTrait)]
 pub enum Checker {
     AuthTokenChecker,
+    FundingQuotaChecker,
     GoogleCaptchaChecker,
     IpBlocklistChecker,
     MagicHeaderChecker,
@@ -146,6 +157,8 @@ pub enum Checker {
 
 #[derive(Clone, Debug)]
 pub struct CheckerData {
+    /// Unique per request, so identical requests can be told apart.
+    pub request_id: Uuid,
     pub time_request_received_secs: u64,
     pub amount: u64,
     pub receiver: AccountAddress,
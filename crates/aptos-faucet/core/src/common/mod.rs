@@ -3,6 +3,8 @@
 
 mod ip_range_manager;
 mod list_manager;
+mod redis_connection;
 
 pub use ip_range_manager::{IpRangeManager, IpRangeManagerConfig};
 pub use list_manager::{ListManager, ListManagerConfig};
+pub use redis_connection::RedisConnectionConfig;
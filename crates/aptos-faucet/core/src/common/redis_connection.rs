@@ -0,0 +1,62 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use deadpool_redis::{
+    redis::{ConnectionAddr, ConnectionInfo, RedisConnectionInfo},
+    Config, Pool, Runtime,
+};
+use serde::{Deserialize, Serialize};
+
+/// This defines how to connect to Redis, for the components that store data there.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RedisConnectionConfig {
+    /// The database address to connect to, not including port,
+    /// e.g. db.example.com or 234.121.222.42.
+    pub database_address: String,
+
+    /// The port to connect to.
+    #[serde(default = "RedisConnectionConfig::default_database_port")]
+    pub database_port: u16,
+
+    /// The number of the database to use. If it doesn't exist, it will be created (todo verify this)
+    #[serde(default = "RedisConnectionConfig::default_database_number")]
+    pub database_number: i64,
+
+    /// The name of the user to use, if necessary.
+    pub database_user: Option<String>,
+
+    /// The password of the given user, if necessary.
+    pub database_password: Option<String>,
+}
+
+impl RedisConnectionConfig {
+    fn default_database_port() -> u16 {
+        6379
+    }
+
+    fn default_database_number() -> i64 {
+        0
+    }
+
+    fn build_connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            addr: ConnectionAddr::Tcp(self.database_address.clone(), self.database_port),
+            redis: RedisConnectionInfo {
+                db: self.database_number,
+                username: self.database_user.clone(),
+                password: self.database_password.clone(),
+            },
+        }
+    }
+
+    pub fn build_db_pool(&self) -> Result<Pool> {
+        let connection_info = self.build_connection_info();
+        let cfg = Config {
+            connection: Some(connection_info.into()),
+            ..Default::default()
+        };
+        cfg.create_pool(Some(Runtime::Tokio1))
+            .context("Failed to build redis connection pool")
+    }
+}
@@ -64,7 +64,12 @@ impl AptosTapError {
     pub fn status_and_retry_after(&self) -> (StatusCode, Option<u64>) {
         let (mut status_code, mut retry_after) = (self.error_code.status(), None);
         for rejection_reason in &self.rejection_reasons {
-            if rejection_reason.code == RejectionReasonCode::IpUsageLimitExhausted {
+            if matches!(
+                rejection_reason.code,
+                RejectionReasonCode::IpUsageLimitExhausted
+                    | RejectionReasonCode::ReceiverQuotaExhausted
+                    | RejectionReasonCode::AuthTokenQuotaExhausted
+            ) {
                 status_code = StatusCode::TOO_MANY_REQUESTS;
                 retry_after = rejection_reason.retry_after;
                 break;
@@ -222,6 +227,12 @@ impl RejectionReason {
     }
 }
 
+impl std::fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:?})", self.reason, self.code)
+    }
+}
+
 // todo explain that the frontend may not want to display specifics here.
 // say this is only for the filters. maybe rename to say filters.
 #[derive(Copy, Clone, Debug, Enum, Eq, Hash, PartialEq)]
@@ -256,4 +267,11 @@ pub enum RejectionReasonCode {
 
     /// Referer was in the blocklist.
     RefererBlocklisted = 108,
+
+    /// Receiver has been funded the maximum amount allowed within the quota window.
+    ReceiverQuotaExhausted = 109,
+
+    /// Auth token has been used to fund the maximum amount allowed within the
+    /// quota window.
+    AuthTokenQuotaExhausted = 110,
 }
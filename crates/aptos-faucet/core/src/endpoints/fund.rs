@@ -27,6 +27,7 @@ use poem_openapi::{
 };
 use std::sync::Arc;
 use tokio::sync::{Semaphore, SemaphorePermit};
+use uuid::Uuid;
 
 #[derive(Clone, Debug, Default, Object)]
 pub struct FundRequest {
@@ -239,6 +240,7 @@ impl FundApiComponents {
         info!(source_ip = source_ip, account = receiver, amount = amount);
 
         let checker_data = CheckerData {
+            request_id: Uuid::new_v4(),
             amount,
             receiver,
             source_ip,
@@ -379,9 +381,9 @@ pub async fn mint(
     header_map: &HeaderMap,
 ) -> poem::Result<MintResponse> {
     // We take the AptosTapError and convert it into an anyhow error with just the
-    // message so this endpoint returns a plaintext response like the faucet does.
-    // We still return the intended status code though, but not any headers that
-    // the /mint endpoint would, e.g. Retry-After.
+    // message and rejection reasons so this endpoint returns a plaintext response
+    // like the faucet does. We still return the intended status code though, but
+    // not any headers that the /mint endpoint would, e.g. Retry-After.
     let fund_request = FundRequest {
         amount,
         auth_key,
@@ -393,7 +395,20 @@ pub async fn mint(
         .fund_inner(fund_request, source_ip, header_map, false)
         .await
         .map_err(|e| {
-            poem::Error::from((e.status_and_retry_after().0, anyhow::anyhow!(e.message)))
+            let message = if e.rejection_reasons.is_empty() {
+                e.message.clone()
+            } else {
+                format!(
+                    "{}: {}",
+                    e.message,
+                    e.rejection_reasons
+                        .iter()
+                        .map(|r| r.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                )
+            };
+            poem::Error::from((e.status_and_retry_after().0, anyhow::anyhow!(message)))
         })?;
     if return_txns.unwrap_or(false) {
         let txn_bcs =
@@ -0,0 +1,870 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    common::{
+        submit_transaction, update_sequence_numbers, ApiConnectionConfig, GasUnitPriceManager,
+        TransactionSubmissionConfig,
+    },
+    transfer::{AmountToFund, MinimumFunds},
+    FunderHealthMessage, FunderTrait,
+};
+use crate::{
+    endpoints::{AptosTapError, AptosTapErrorCode, RejectionReason, RejectionReasonCode},
+    middleware::ACCOUNT_POOL_FUNDER_ACCOUNT_BALANCE,
+};
+use anyhow::{bail, Context, Result};
+use aptos_logger::{info, warn};
+use aptos_sdk::{
+    crypto::{
+        ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+        HashValue,
+    },
+    rest_client::Client,
+    transaction_builder::{aptos_stdlib, TransactionFactory},
+    types::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{authenticator::AuthenticationKey, SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
+
+const APTOS_COIN: &str = "0x1::aptos_coin::AptosCoin";
+
+/// Domain separator for deriving the keys of the funding accounts from the
+/// root key, so the derived keys can't collide with keys derived elsewhere.
+const FUNDING_ACCOUNT_KEY_DERIVATION_DOMAIN: &[u8] =
+    b"APTOS_FAUCET::AccountPoolFunder::FundingAccount";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AccountPoolFunderConfig {
+    /// The key given here is for the root account, which only refills the
+    /// funding accounts, it never funds receivers directly.
+    #[serde(flatten)]
+    pub api_connection_config: ApiConnectionConfig,
+
+    #[serde(flatten)]
+    pub transaction_submission_config: TransactionSubmissionConfig,
+
+    /// Address of the root account. If not given, we use the account address
+    /// corresponding to the given private key.
+    pub root_account_address: Option<AccountAddress>,
+
+    /// How many accounts to spread fund requests across. The keys of these
+    /// accounts are derived from the root key, so the same accounts are used
+    /// across restarts and funds aren't left behind in abandoned accounts.
+    pub num_funding_accounts: usize,
+
+    /// The minimum amount of coins the root account should have. If it gets
+    /// to this point, the funder will report itself as unhealthy.
+    pub minimum_funds: MinimumFunds,
+
+    /// When the balance of a funding account drops below this, we transfer
+    /// `refill_amount` coins to it from the root account.
+    pub refill_threshold: u64,
+
+    /// The amount of coins to transfer to a funding account when refilling it.
+    pub refill_amount: u64,
+
+    /// The amount of coins to fund the receiver account.
+    pub amount_to_fund: AmountToFund,
+}
+
+impl AccountPoolFunderConfig {
+    pub async fn build_funder(&self) -> Result<AccountPoolFunder> {
+        if self.num_funding_accounts == 0 {
+            bail!("num_funding_accounts must be greater than 0");
+        }
+        if self.refill_threshold < self.amount_to_fund.0 {
+            bail!(
+                "refill_threshold ({}) must be at least amount_to_fund ({})",
+                self.refill_threshold,
+                self.amount_to_fund
+            );
+        }
+
+        // Read in private key.
+        let key = self.api_connection_config.get_key()?;
+
+        let funding_accounts = (0..self.num_funding_accounts)
+            .map(|index| derive_funding_account(&key, index).map(FundingAccount::new))
+            .collect::<Result<_>>()?;
+
+        let root_account = LocalAccount::new(
+            self.root_account_address
+                .unwrap_or_else(|| account_address_from_private_key(&key)),
+            key,
+            0,
+        );
+
+        let funder = AccountPoolFunder::new(
+            root_account,
+            funding_accounts,
+            self.api_connection_config.chain_id,
+            self.api_connection_config.node_url.clone(),
+            self.minimum_funds,
+            self.refill_threshold,
+            self.refill_amount,
+            self.amount_to_fund,
+            self.transaction_submission_config
+                .get_gas_unit_price_ttl_secs(),
+            self.transaction_submission_config.gas_unit_price_override,
+            self.transaction_submission_config.max_gas_amount,
+            self.transaction_submission_config
+                .transaction_expiration_secs,
+            self.transaction_submission_config.wait_for_transactions,
+        );
+
+        // Make sure every funding account exists and has funds before we
+        // start taking requests.
+        funder
+            .refill_funding_accounts(true)
+            .await
+            .context("Failed to fill funding accounts")?;
+
+        Ok(funder)
+    }
+}
+
+/// One of the accounts the AccountPoolFunder funds receivers from. Each one
+/// tracks its own sequence number, so requests routed to different accounts
+/// don't contend with each other.
+struct FundingAccount {
+    address: AccountAddress,
+
+    account: RwLock<LocalAccount>,
+
+    /// Same as `outstanding_requests` in the TransferFunder, for this account.
+    outstanding_requests: RwLock<Vec<(AccountAddress, u64)>>,
+
+    /// The balance of the account the last time we checked.
+    balance: AtomicU64,
+
+    /// If a transaction from this account failed, our sequence number can't
+    /// be trusted anymore. We take the account out of rotation until this
+    /// time, after which anything it had in flight has expired and we can
+    /// recover the sequence number from the chain.
+    resync_at: RwLock<Option<Instant>>,
+
+    /// When we last submitted a refill for this account, so that we don't
+    /// submit another one while the first is still in flight.
+    last_refill: RwLock<Option<Instant>>,
+}
+
+impl FundingAccount {
+    fn new(account: LocalAccount) -> Self {
+        Self {
+            address: account.address(),
+            account: RwLock::new(account),
+            outstanding_requests: RwLock::new(vec![]),
+            balance: AtomicU64::new(0),
+            resync_at: RwLock::new(None),
+            last_refill: RwLock::new(None),
+        }
+    }
+
+    /// Whether this account can fund `amount` right now. If the account was
+    /// waiting to recover its sequence number and the wait is over, this does
+    /// the recovery.
+    async fn is_available(&self, client: &Client, amount: u64) -> bool {
+        if self.balance.load(Ordering::Acquire) < amount {
+            return false;
+        }
+        let resync_at = *self.resync_at.read().await;
+        match resync_at {
+            None => true,
+            Some(resync_at) if Instant::now() < resync_at => false,
+            Some(_) => match self.recover_sequence_number(client).await {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!(
+                        address = self.address,
+                        event = "sequence_number_recovery_failure",
+                        error_message = format!("{:#}", e)
+                    );
+                    false
+                },
+            },
+        }
+    }
+
+    async fn mark_for_resync(&self, transaction_expiration: Duration) {
+        *self.resync_at.write().await = Some(Instant::now() + transaction_expiration);
+    }
+
+    async fn recover_sequence_number(&self, client: &Client) -> Result<()> {
+        let sequence_number = client
+            .get_account(self.address)
+            .await?
+            .into_inner()
+            .sequence_number;
+        let mut resync_at = self.resync_at.write().await;
+        // Another request may have done the recovery while we were talking
+        // to the API, in which case it may have already sent transactions.
+        if resync_at.is_some() {
+            *self.account.write().await.sequence_number_mut() = sequence_number;
+            *resync_at = None;
+            info!(
+                address = self.address,
+                sequence_number = sequence_number,
+                event = "sequence_number_recovered"
+            );
+        }
+        Ok(())
+    }
+
+    /// Fetches the balance of the account and records it. If the account
+    /// doesn't exist yet, its balance is 0.
+    async fn update_balance(&self, client: &Client) -> u64 {
+        let balance = client
+            .get_account_balance_bcs(self.address, APTOS_COIN)
+            .await
+            .map(|response| response.into_inner())
+            .unwrap_or(0);
+        self.balance.store(balance, Ordering::Release);
+        ACCOUNT_POOL_FUNDER_ACCOUNT_BALANCE
+            .with_label_values(&[&self.address.to_hex_literal()])
+            .set(balance as i64);
+        balance
+    }
+}
+
+pub struct AccountPoolFunder {
+    /// The account that refills the funding accounts.
+    root_account: RwLock<LocalAccount>,
+
+    /// Same as `outstanding_requests` in the TransferFunder, for the root
+    /// account.
+    root_outstanding_requests: RwLock<Vec<(AccountAddress, u64)>>,
+
+    funding_accounts: Vec<FundingAccount>,
+
+    /// Index of the next funding account to route a request to.
+    next_funding_account: AtomicUsize,
+
+    transaction_factory: TransactionFactory,
+
+    /// URL of an Aptos node API.
+    node_url: Url,
+
+    /// The minimum amount of funds the root account should have.
+    minimum_funds: MinimumFunds,
+
+    /// Funding accounts are refilled when their balance drops below this.
+    refill_threshold: u64,
+
+    /// How much to transfer from the root account when refilling.
+    refill_amount: u64,
+
+    /// Maximum amount we'll fund an account.
+    amount_to_fund: AmountToFund,
+
+    /// See comment of gas_unit_price.
+    gas_unit_price_manager: GasUnitPriceManager,
+
+    /// If this is Some, we'll use this. If not, we'll get the gas_unit_price
+    /// from the gas_unit_price_manager.
+    gas_unit_price_override: Option<u64>,
+
+    /// How long a submitted transaction can be in flight for.
+    transaction_expiration: Duration,
+
+    /// If set, we won't return responses until the transaction is processed.
+    wait_for_transactions: bool,
+}
+
+impl AccountPoolFunder {
+    fn new(
+        root_account: LocalAccount,
+        funding_accounts: Vec<FundingAccount>,
+        chain_id: ChainId,
+        node_url: Url,
+        minimum_funds: MinimumFunds,
+        refill_threshold: u64,
+        refill_amount: u64,
+        amount_to_fund: AmountToFund,
+        gas_unit_price_ttl_secs: Duration,
+        gas_unit_price_override: Option<u64>,
+        max_gas_amount: u64,
+        transaction_expiration_secs: u64,
+        wait_for_transactions: bool,
+    ) -> Self {
+        let gas_unit_price_manager =
+            GasUnitPriceManager::new(node_url.clone(), gas_unit_price_ttl_secs);
+
+        Self {
+            root_account: RwLock::new(root_account),
+            root_outstanding_requests: RwLock::new(vec![]),
+            funding_accounts,
+            next_funding_account: AtomicUsize::new(0),
+            transaction_factory: TransactionFactory::new(chain_id)
+                .with_max_gas_amount(max_gas_amount)
+                .with_transaction_expiration_time(transaction_expiration_secs),
+            node_url,
+            minimum_funds,
+            refill_threshold,
+            refill_amount,
+            amount_to_fund,
+            gas_unit_price_manager,
+            gas_unit_price_override,
+            transaction_expiration: Duration::from_secs(transaction_expiration_secs),
+            wait_for_transactions,
+        }
+    }
+
+    /// Within a single request we should just call this once and use this client
+    /// the entire time because it uses cookies, ensuring we're talking to the same
+    /// node behind the LB every time.
+    pub fn get_api_client(&self) -> Client {
+        Client::new(self.node_url.clone())
+    }
+
+    async fn get_gas_unit_price(&self) -> Result<u64, AptosTapError> {
+        match self.gas_unit_price_override {
+            Some(gas_unit_price) => Ok(gas_unit_price),
+            None => self
+                .gas_unit_price_manager
+                .get_gas_unit_price()
+                .await
+                .map_err(|e| {
+                    AptosTapError::new_with_error_code(e, AptosTapErrorCode::AptosApiError)
+                }),
+        }
+    }
+
+    /// This function builds, signs, submits, and potentially waits for a
+    /// transaction from the given account.
+    async fn execute_transaction(
+        &self,
+        client: &Client,
+        account: &RwLock<LocalAccount>,
+        payload: TransactionPayload,
+        // Only used for logging.
+        receiver_address: &AccountAddress,
+        wait_for_transactions: bool,
+    ) -> Result<SignedTransaction, AptosTapError> {
+        let transaction_factory = self
+            .transaction_factory
+            .clone()
+            .with_gas_unit_price(self.get_gas_unit_price().await?);
+
+        let transaction_builder = transaction_factory.payload(payload);
+
+        let signed_transaction = account
+            .write()
+            .await
+            .sign_with_transaction_builder(transaction_builder);
+
+        submit_transaction(
+            client,
+            account,
+            signed_transaction,
+            receiver_address,
+            wait_for_transactions,
+        )
+        .await
+    }
+
+    /// Picks the next funding account round-robin, skipping accounts that
+    /// are out of funds or recovering their sequence number.
+    async fn select_funding_account(
+        &self,
+        client: &Client,
+        amount: u64,
+    ) -> Result<&FundingAccount, AptosTapError> {
+        let num_funding_accounts = self.funding_accounts.len();
+        for _ in 0..num_funding_accounts {
+            let index =
+                self.next_funding_account.fetch_add(1, Ordering::Relaxed) % num_funding_accounts;
+            let funding_account = &self.funding_accounts[index];
+            if funding_account.is_available(client, amount).await {
+                return Ok(funding_account);
+            }
+        }
+        Err(AptosTapError::new(
+            "None of the funding accounts of the Tap AccountPoolFunder can handle requests right now"
+                .to_string(),
+            AptosTapErrorCode::FunderAccountProblem,
+        ))
+    }
+
+    /// Transfers `refill_amount` from the root account to the funding account
+    /// if its balance is below `refill_threshold` and it doesn't have a refill
+    /// in flight already.
+    async fn refill_if_needed(
+        &self,
+        client: &Client,
+        funding_account: &FundingAccount,
+        wait_for_transactions: bool,
+    ) -> Result<(), AptosTapError> {
+        if funding_account.update_balance(client).await >= self.refill_threshold {
+            return Ok(());
+        }
+
+        {
+            let mut last_refill = funding_account.last_refill.write().await;
+            if let Some(last_refill) = *last_refill {
+                if last_refill.elapsed() < self.transaction_expiration {
+                    return Ok(());
+                }
+            }
+            *last_refill = Some(Instant::now());
+        }
+
+        // If the refill wasn't submitted, the next request should try again
+        // rather than wait for a refill that isn't coming.
+        let result = self
+            .submit_refill(client, funding_account, wait_for_transactions)
+            .await;
+        if result.is_err() {
+            *funding_account.last_refill.write().await = None;
+        }
+        result
+    }
+
+    /// Transfers `refill_amount` from the root account to the funding account.
+    async fn submit_refill(
+        &self,
+        client: &Client,
+        funding_account: &FundingAccount,
+        wait_for_transactions: bool,
+    ) -> Result<(), AptosTapError> {
+        update_sequence_numbers(
+            client,
+            &self.root_account,
+            &self.root_outstanding_requests,
+            funding_account.address,
+            self.refill_amount,
+        )
+        .await?;
+
+        // This creates the funding account if it doesn't exist yet.
+        let txn = self
+            .execute_transaction(
+                client,
+                &self.root_account,
+                aptos_stdlib::aptos_account_transfer(funding_account.address, self.refill_amount),
+                &funding_account.address,
+                wait_for_transactions,
+            )
+            .await?;
+        info!(
+            hash = txn.committed_hash().to_hex_literal(),
+            address = funding_account.address,
+            amount = self.refill_amount,
+            event = "funding_account_refill_submitted"
+        );
+
+        if wait_for_transactions {
+            funding_account.update_balance(client).await;
+        }
+
+        Ok(())
+    }
+
+    /// Refills all the funding accounts that need it. If any refill fails,
+    /// this returns the first error, after trying the rest.
+    async fn refill_funding_accounts(
+        &self,
+        wait_for_transactions: bool,
+    ) -> Result<(), AptosTapError> {
+        let client = self.get_api_client();
+        let results =
+            futures::future::join_all(self.funding_accounts.iter().map(|funding_account| {
+                self.refill_if_needed(&client, funding_account, wait_for_transactions)
+            }))
+            .await;
+        results.into_iter().collect()
+    }
+}
+
+#[async_trait]
+impl FunderTrait for AccountPoolFunder {
+    /// This does the same checks as the TransferFunder, but routes the request
+    /// to one of the funding accounts rather than a single account. If the
+    /// transaction fails, the funding account is taken out of rotation until
+    /// its sequence number is recovered. After funding, we check whether the
+    /// funding account needs a refill.
+    async fn fund(
+        &self,
+        amount: Option<u64>,
+        receiver_address: AccountAddress,
+        check_only: bool,
+    ) -> Result<Vec<SignedTransaction>, AptosTapError> {
+        let client = self.get_api_client();
+
+        // Determine amount to fund.
+        let amount = self.get_amount(amount);
+
+        let funding_account = self.select_funding_account(&client, amount).await?;
+
+        // Update the sequence numbers of the accounts.
+        let (_funder_seq_num, receiver_seq_num) = update_sequence_numbers(
+            &client,
+            &funding_account.account,
+            &funding_account.outstanding_requests,
+            receiver_address,
+            amount,
+        )
+        .await?;
+
+        // When updating the sequence numbers, we expect that the receiver sequence
+        // number should be None, because the account should not exist yet.
+        if receiver_seq_num.is_some() {
+            return Err(AptosTapError::new(
+                "Account ineligible".to_string(),
+                AptosTapErrorCode::Rejected,
+            )
+            .rejection_reasons(vec![RejectionReason::new(
+                format!("Account {} already exists", receiver_address),
+                RejectionReasonCode::AccountAlreadyExists,
+            )]));
+        }
+
+        if check_only {
+            return Ok(vec![]);
+        }
+
+        let txn = match self
+            .execute_transaction(
+                &client,
+                &funding_account.account,
+                aptos_stdlib::aptos_account_transfer(receiver_address, amount),
+                &receiver_address,
+                self.wait_for_transactions,
+            )
+            .await
+        {
+            Ok(txn) => txn,
+            Err(e) => {
+                funding_account
+                    .mark_for_resync(self.transaction_expiration)
+                    .await;
+                return Err(e);
+            },
+        };
+        info!(
+            hash = txn.clone().committed_hash().to_hex_literal(),
+            address = receiver_address,
+            funding_account = funding_account.address,
+            amount = amount,
+            event = "transaction_submitted"
+        );
+
+        // The receiver has been funded at this point, so we don't fail the
+        // request if the refill fails, the health check will try again.
+        if let Err(e) = self.refill_if_needed(&client, funding_account, false).await {
+            warn!(
+                address = funding_account.address,
+                event = "funding_account_refill_failure",
+                error_message = format!("{:#}", e)
+            );
+        }
+
+        Ok(vec![txn])
+    }
+
+    fn get_amount(&self, amount: Option<u64>) -> u64 {
+        match amount {
+            Some(amount) => std::cmp::min(amount, self.amount_to_fund.0),
+            None => self.amount_to_fund.0,
+        }
+    }
+
+    /// Assert the root account has the minimum funds and that at least one
+    /// funding account can handle requests. This also refills any funding
+    /// accounts that need it.
+    async fn is_healthy(&self) -> FunderHealthMessage {
+        let client = self.get_api_client();
+        let root_address = self.root_account.read().await.address();
+        let root_balance = match client
+            .get_account_balance_bcs(root_address, APTOS_COIN)
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(e) => return FunderHealthMessage {
+                can_process_requests: false,
+                message: Some(format!(
+                    "Failed to get account balance to determine whether root account has sufficient funds: {:#}",
+                    e
+                )),
+            },
+        };
+
+        ACCOUNT_POOL_FUNDER_ACCOUNT_BALANCE
+            .with_label_values(&[&root_address.to_hex_literal()])
+            .set(root_balance as i64);
+
+        if root_balance < self.minimum_funds.0 {
+            return FunderHealthMessage {
+                can_process_requests: false,
+                message: Some(format!(
+                    "Root account {} has insufficient funds. It has {}, but the minimum is {}",
+                    root_address, root_balance, self.minimum_funds.0
+                )),
+            };
+        }
+
+        if let Err(e) = self.refill_funding_accounts(false).await {
+            warn!(
+                event = "funding_account_refill_failure",
+                error_message = format!("{:#}", e)
+            );
+        }
+
+        let mut num_available = 0;
+        for funding_account in &self.funding_accounts {
+            if funding_account
+                .is_available(&client, self.amount_to_fund.0)
+                .await
+            {
+                num_available += 1;
+            }
+        }
+
+        if num_available == 0 {
+            FunderHealthMessage {
+                can_process_requests: false,
+                message: Some(format!(
+                    "None of the {} funding accounts can handle requests right now",
+                    self.funding_accounts.len()
+                )),
+            }
+        } else {
+            FunderHealthMessage {
+                can_process_requests: true,
+                message: None,
+            }
+        }
+    }
+}
+
+fn account_address_from_private_key(private_key: &Ed25519PrivateKey) -> AccountAddress {
+    AuthenticationKey::ed25519(&Ed25519PublicKey::from(private_key)).derived_address()
+}
+
+/// Deterministically derives the key of the funding account at the given
+/// index from the root key. The key is the SHA3-256 hash of the root key and
+/// the index, so the derivation doesn't depend on the implementation of any RNG.
+fn derive_funding_account(root_key: &Ed25519PrivateKey, index: usize) -> Result<LocalAccount> {
+    let mut seed = FUNDING_ACCOUNT_KEY_DERIVATION_DOMAIN.to_vec();
+    seed.extend_from_slice(&root_key.to_bytes());
+    seed.extend_from_slice(&(index as u64).to_le_bytes());
+    let key = Ed25519PrivateKey::try_from(HashValue::sha3_256_of(&seed).to_vec().as_slice())
+        .with_context(|| format!("Failed to derive the key of funding account {}", index))?;
+    Ok(LocalAccount::new(
+        account_address_from_private_key(&key),
+        key,
+        0,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_sdk::crypto::Uniform;
+    use httpmock::{Method::GET, MockServer, Then};
+    use rand::{rngs::StdRng, SeedableRng};
+    use serde_json::json;
+
+    fn root_key() -> Ed25519PrivateKey {
+        Ed25519PrivateKey::generate(&mut StdRng::from_seed([0; 32]))
+    }
+
+    fn new_funder(node_url: String, num_funding_accounts: usize) -> AccountPoolFunder {
+        let root_key = root_key();
+        let funding_accounts = (0..num_funding_accounts)
+            .map(|index| FundingAccount::new(derive_funding_account(&root_key, index).unwrap()))
+            .collect();
+        AccountPoolFunder::new(
+            LocalAccount::new(account_address_from_private_key(&root_key), root_key, 0),
+            funding_accounts,
+            ChainId::test(),
+            Url::parse(&node_url).unwrap(),
+            MinimumFunds(0),
+            100,
+            1000,
+            AmountToFund(10),
+            Duration::from_secs(30),
+            Some(100),
+            1000,
+            30,
+            false,
+        )
+    }
+
+    /// The API client requires these headers on every response.
+    fn with_ledger_headers(then: Then) -> Then {
+        then.header("X-Aptos-Chain-Id", "4")
+            .header("X-Aptos-Ledger-Version", "1")
+            .header("X-Aptos-Ledger-TimestampUsec", "1")
+            .header("X-Aptos-Epoch", "1")
+            .header("X-Aptos-Ledger-Oldest-Version", "0")
+            .header("X-Aptos-Block-Height", "1")
+            .header("X-Aptos-Oldest-Block-Height", "0")
+    }
+
+    #[test]
+    fn test_derive_funding_account() {
+        let root_key = root_key();
+        let address = derive_funding_account(&root_key, 0).unwrap().address();
+
+        // The same accounts are derived across restarts.
+        assert_eq!(
+            derive_funding_account(&root_key, 0).unwrap().address(),
+            address
+        );
+        assert_ne!(
+            derive_funding_account(&root_key, 1).unwrap().address(),
+            address
+        );
+        assert_ne!(account_address_from_private_key(&root_key), address);
+    }
+
+    #[tokio::test]
+    async fn test_select_funding_account() {
+        // Selecting an account doesn't talk to the node unless it has to
+        // recover a sequence number.
+        let server = MockServer::start();
+        let funder = new_funder(server.base_url(), 3);
+        let client = funder.get_api_client();
+        let addresses: Vec<AccountAddress> = funder
+            .funding_accounts
+            .iter()
+            .map(|funding_account| funding_account.address)
+            .collect();
+        for funding_account in &funder.funding_accounts {
+            funding_account.balance.store(100, Ordering::Release);
+        }
+
+        // Requests are routed round-robin.
+        let mut selected = vec![];
+        for _ in 0..6 {
+            selected.push(
+                funder
+                    .select_funding_account(&client, 10)
+                    .await
+                    .unwrap()
+                    .address,
+            );
+        }
+        assert_eq!(selected, [addresses.clone(), addresses.clone()].concat());
+
+        // Accounts that can't fund the amount or are waiting to recover their
+        // sequence number are skipped.
+        funder.funding_accounts[1]
+            .balance
+            .store(5, Ordering::Release);
+        funder.funding_accounts[2]
+            .mark_for_resync(Duration::from_secs(60))
+            .await;
+        for _ in 0..3 {
+            assert_eq!(
+                funder
+                    .select_funding_account(&client, 10)
+                    .await
+                    .unwrap()
+                    .address,
+                addresses[0]
+            );
+        }
+        assert_eq!(
+            funder
+                .select_funding_account(&client, 5)
+                .await
+                .unwrap()
+                .address,
+            addresses[1]
+        );
+
+        // If no account can handle the request, it fails.
+        funder.funding_accounts[0]
+            .balance
+            .store(5, Ordering::Release);
+        assert!(funder.select_funding_account(&client, 10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sequence_number_resync() {
+        let server = MockServer::start();
+        let funder = new_funder(server.base_url(), 1);
+        let client = funder.get_api_client();
+        let funding_account = &funder.funding_accounts[0];
+        funding_account.balance.store(100, Ordering::Release);
+        *funding_account.account.write().await.sequence_number_mut() = 10;
+
+        // After a failed transaction, the account is out of rotation until
+        // whatever it had in flight has expired.
+        funding_account
+            .mark_for_resync(Duration::from_secs(60))
+            .await;
+        assert!(!funding_account.is_available(&client, 10).await);
+
+        // If the sequence number can't be recovered, the account stays out
+        // of rotation.
+        funding_account.mark_for_resync(Duration::ZERO).await;
+        assert!(!funding_account.is_available(&client, 10).await);
+        assert!(funding_account.resync_at.read().await.is_some());
+
+        // Once it's recovered, the account is back in rotation with the
+        // sequence number from the chain.
+        let authentication_key = funding_account.account.read().await.authentication_key();
+        let account_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path_contains(funding_account.address.to_hex());
+            with_ledger_headers(then.status(200)).json_body(json!({
+                "authentication_key": authentication_key.to_string(),
+                "sequence_number": "4",
+            }));
+        });
+        assert!(funding_account.is_available(&client, 10).await);
+        account_mock.assert();
+        assert_eq!(funding_account.account.read().await.sequence_number(), 4);
+        assert!(funding_account.resync_at.read().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refill_if_needed() {
+        // The node doesn't know any account, so the balance of the funding
+        // account is 0 and the root account can't submit the refill.
+        let server = MockServer::start();
+        let funder = new_funder(server.base_url(), 1);
+        let client = funder.get_api_client();
+        let funding_account = &funder.funding_accounts[0];
+
+        // A failed refill doesn't hold off the next one.
+        assert!(funder
+            .refill_if_needed(&client, funding_account, false)
+            .await
+            .is_err());
+        assert!(funding_account.last_refill.read().await.is_none());
+        assert!(funder
+            .refill_if_needed(&client, funding_account, false)
+            .await
+            .is_err());
+
+        // While a refill is in flight, no other refill is submitted.
+        *funding_account.last_refill.write().await = Some(Instant::now());
+        funder
+            .refill_if_needed(&client, funding_account, false)
+            .await
+            .unwrap();
+
+        // Once it has expired, the account is refilled again.
+        *funding_account.last_refill.write().await =
+            Some(Instant::now() - funder.transaction_expiration);
+        assert!(funder
+            .refill_if_needed(&client, funding_account, false)
+            .await
+            .is_err());
+    }
+}
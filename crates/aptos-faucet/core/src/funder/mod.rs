@@ -1,17 +1,21 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod account_pool;
 mod common;
 mod fake;
 mod mint;
 mod transfer;
 
+use self::{
+    account_pool::AccountPoolFunderConfig, fake::FakeFunderConfig, transfer::TransferFunderConfig,
+};
 pub use self::{
     common::{ApiConnectionConfig, TransactionSubmissionConfig},
     mint::MintFunderConfig,
 };
-use self::{fake::FakeFunderConfig, transfer::TransferFunderConfig};
 use crate::endpoints::AptosTapError;
+pub use account_pool::AccountPoolFunder;
 use anyhow::{Context, Result};
 use aptos_sdk::types::{account_address::AccountAddress, transaction::SignedTransaction};
 use async_trait::async_trait;
@@ -68,6 +72,12 @@ pub enum FunderConfig {
     /// This funder creates and funds accounts by using + transferring
     /// coins from a pre-funded account provided in configuration.
     TransferFunder(TransferFunderConfig),
+
+    /// This funder creates and funds accounts by transferring coins from a
+    /// pool of funding accounts, which are refilled from a root account
+    /// provided in configuration. This allows for more concurrent requests
+    /// than the TransferFunder.
+    AccountPoolFunder(AccountPoolFunderConfig),
 }
 
 impl FunderConfig {
@@ -86,6 +96,12 @@ impl FunderConfig {
                     .await
                     .context("Failed to build TransferFunder")?,
             ))),
+            FunderConfig::AccountPoolFunder(config) => Ok(Arc::new(Funder::from(
+                config
+                    .build_funder()
+                    .await
+                    .context("Failed to build AccountPoolFunder")?,
+            ))),
         }
     }
 }
@@ -96,6 +112,7 @@ pub enum Funder {
     FakeFunder,
     MintFunder,
     TransferFunder,
+    AccountPoolFunder,
 }
 
 #[derive(Debug, Clone)]
@@ -3,8 +3,8 @@
 
 use crate::endpoints::RejectionReason;
 use aptos_metrics_core::{
-    register_histogram_vec, register_int_counter_vec, register_int_gauge, HistogramVec,
-    IntCounterVec, IntGauge,
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -52,6 +52,15 @@ pub static TRANSFER_FUNDER_ACCOUNT_BALANCE: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static ACCOUNT_POOL_FUNDER_ACCOUNT_BALANCE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_tap_account_pool_funder_account_balance",
+        "Balance of the root and funding accounts used by the tap instance. Only populated for the AccountPoolFunder.",
+        &["account_address"]
+    )
+    .unwrap()
+});
+
 pub fn bump_rejection_reason_counters(rejection_reasons: &[RejectionReason]) {
     for rejection_reason in rejection_reasons {
         REJECTION_REASONS
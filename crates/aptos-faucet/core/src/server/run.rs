@@ -607,6 +607,110 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_funding_quota() -> Result<()> {
+        init();
+        let config_content = include_str!("../../../configs/testing_funding_quota.yaml");
+        let (port, _handle) = start_server(config_content).await?;
+
+        let fund_request = |address: AccountAddress, amount: u64| {
+            FundRequest {
+                amount: Some(amount),
+                address: Some(address.to_string()),
+                ..Default::default()
+            }
+            .to_json_string()
+        };
+
+        // Assert that requests for the same receiver work until they'd exceed
+        // the quota of 25.
+        let receiver = AccountAddress::random();
+        for amount in [10, 10] {
+            unwrap_reqwest_result(
+                reqwest::Client::new()
+                    .post(get_fund_endpoint(port))
+                    .body(fund_request(receiver, amount))
+                    .header(CONTENT_TYPE, "application/json")
+                    .send()
+                    .await,
+            )
+            .await?;
+        }
+        let response = reqwest::Client::new()
+            .post(get_fund_endpoint(port))
+            .body(fund_request(receiver, 10))
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+        assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key("Retry-After"));
+        let aptos_error = AptosTapError::parse_from_json_string(&response.text().await?)
+            .expect("Failed to read response as AptosError");
+        let rejection_reason_codes: HashSet<RejectionReasonCode> = aptos_error
+            .rejection_reasons
+            .into_iter()
+            .map(|r| r.get_code())
+            .collect();
+        assert!(rejection_reason_codes.contains(&RejectionReasonCode::ReceiverQuotaExhausted));
+
+        // The rejected request shouldn't count, so what's left of the quota
+        // can still be used.
+        unwrap_reqwest_result(
+            reqwest::Client::new()
+                .post(get_fund_endpoint(port))
+                .body(fund_request(receiver, 5))
+                .header(CONTENT_TYPE, "application/json")
+                .send()
+                .await,
+        )
+        .await?;
+
+        // Assert that requests using the same auth token share a quota of 35,
+        // even across receivers.
+        for amount in [20, 10] {
+            unwrap_reqwest_result(
+                reqwest::Client::new()
+                    .post(get_fund_endpoint(port))
+                    .body(fund_request(AccountAddress::random(), amount))
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(AUTHORIZATION, "Bearer test_token")
+                    .send()
+                    .await,
+            )
+            .await?;
+        }
+        let response = reqwest::Client::new()
+            .post(get_fund_endpoint(port))
+            .body(fund_request(AccountAddress::random(), 10))
+            .header(CONTENT_TYPE, "application/json")
+            .header(AUTHORIZATION, "Bearer test_token")
+            .send()
+            .await?;
+        assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+        let aptos_error = AptosTapError::parse_from_json_string(&response.text().await?)
+            .expect("Failed to read response as AptosError");
+        let rejection_reason_codes: HashSet<RejectionReasonCode> = aptos_error
+            .rejection_reasons
+            .into_iter()
+            .map(|r| r.get_code())
+            .collect();
+        assert!(rejection_reason_codes.contains(&RejectionReasonCode::AuthTokenQuotaExhausted));
+
+        // Assert that a different auth token has its own quota.
+        unwrap_reqwest_result(
+            reqwest::Client::new()
+                .post(get_fund_endpoint(port))
+                .body(fund_request(AccountAddress::random(), 10))
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, "Bearer other_token")
+                .send()
+                .await,
+        )
+        .await?;
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_redis_ratelimiter() -> Result<()> {
         // Assert that a local testnet is alive.
@@ -889,4 +993,52 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_account_pool_funder() -> Result<()> {
+        // Assert that a local testnet is alive.
+        let aptos_node_api_client = aptos_sdk::rest_client::Client::new(
+            reqwest::Url::from_str("http://127.0.0.1:8080").unwrap(),
+        );
+        aptos_node_api_client
+            .get_index_bcs()
+            .await
+            .context("Local testnet API couldn't be reached at port 8080, have you started one?")?;
+
+        init();
+        let (port, _handle) = {
+            // Ensure this server and those for the MintFunder tests don't start
+            // up simultaneously, since they're using the same root key.
+            let _guard = MUTEX.get().unwrap().lock().await;
+            let config_content =
+                include_str!("../../../configs/testing_account_pool_funder_local.yaml");
+            start_server(config_content).await?
+        };
+
+        // Make more concurrent requests than there are funding accounts.
+        let fund_requests: Vec<FundRequest> = (0..6).map(|_| get_fund_request(Some(10))).collect();
+        let responses = futures::future::join_all(fund_requests.iter().map(|fund_request| {
+            reqwest::Client::new()
+                .post(get_fund_endpoint(port))
+                .body(fund_request.to_json_string())
+                .header(CONTENT_TYPE, "application/json")
+                .send()
+        }))
+        .await;
+        for response in responses {
+            unwrap_reqwest_result(response).await?;
+        }
+
+        // Assert that all the accounts exist now with the expected balance.
+        for fund_request in fund_requests {
+            let response = aptos_node_api_client
+                .get_account_balance(
+                    AccountAddress::from_hex(fund_request.address.unwrap()).unwrap(),
+                )
+                .await?;
+            assert_eq!(response.into_inner().get(), 10);
+        }
+
+        Ok(())
+    }
 }
@@ -1,6 +1,131 @@
 // Copyright © Aptos Foundation
 
 // @generated
+impl serde::Serialize for EntryFunctionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.address.is_empty() {
+            len += 1;
+        }
+        if self.module_name.is_some() {
+            len += 1;
+        }
+        if self.function_name.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.datastream.v1.EntryFunctionFilter", len)?;
+        if !self.address.is_empty() {
+            struct_ser.serialize_field("address", &self.address)?;
+        }
+        if let Some(v) = self.module_name.as_ref() {
+            struct_ser.serialize_field("moduleName", v)?;
+        }
+        if let Some(v) = self.function_name.as_ref() {
+            struct_ser.serialize_field("functionName", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for EntryFunctionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "address",
+            "moduleName",
+            "functionName",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Address,
+            ModuleName,
+            FunctionName,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "address" => Ok(GeneratedField::Address),
+                            "moduleName" => Ok(GeneratedField::ModuleName),
+                            "functionName" => Ok(GeneratedField::FunctionName),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = EntryFunctionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.datastream.v1.EntryFunctionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<EntryFunctionFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut address__ = None;
+                let mut module_name__ = None;
+                let mut function_name__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Address => {
+                            if address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("address"));
+                            }
+                            address__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::ModuleName => {
+                            if module_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("moduleName"));
+                            }
+                            module_name__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FunctionName => {
+                            if function_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("functionName"));
+                            }
+                            function_name__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(EntryFunctionFilter {
+                    address: address__.unwrap_or_default(),
+                    module_name: module_name__,
+                    function_name: function_name__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.datastream.v1.EntryFunctionFilter", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for RawDatastreamRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -15,6 +140,9 @@ impl serde::Serialize for RawDatastreamRequest {
         if self.transactions_count.is_some() {
             len += 1;
         }
+        if self.filter.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("aptos.datastream.v1.RawDatastreamRequest", len)?;
         if let Some(v) = self.starting_version.as_ref() {
             struct_ser.serialize_field("startingVersion", ToString::to_string(&v).as_str())?;
@@ -22,6 +150,9 @@ impl serde::Serialize for RawDatastreamRequest {
         if let Some(v) = self.transactions_count.as_ref() {
             struct_ser.serialize_field("transactionsCount", ToString::to_string(&v).as_str())?;
         }
+        if let Some(v) = self.filter.as_ref() {
+            struct_ser.serialize_field("filter", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -34,12 +165,14 @@ impl<'de> serde::Deserialize<'de> for RawDatastreamRequest {
         const FIELDS: &[&str] = &[
             "startingVersion",
             "transactionsCount",
+            "filter",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             StartingVersion,
             TransactionsCount,
+            Filter,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -63,6 +196,7 @@ impl<'de> serde::Deserialize<'de> for RawDatastreamRequest {
                         match value {
                             "startingVersion" => Ok(GeneratedField::StartingVersion),
                             "transactionsCount" => Ok(GeneratedField::TransactionsCount),
+                            "filter" => Ok(GeneratedField::Filter),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -84,6 +218,7 @@ impl<'de> serde::Deserialize<'de> for RawDatastreamRequest {
             {
                 let mut starting_version__ = None;
                 let mut transactions_count__ = None;
+                let mut filter__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::StartingVersion => {
@@ -102,11 +237,18 @@ impl<'de> serde::Deserialize<'de> for RawDatastreamRequest {
                                 map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
                             );
                         }
+                        GeneratedField::Filter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("filter"));
+                            }
+                            filter__ = Some(map.next_value()?);
+                        }
                     }
                 }
                 Ok(RawDatastreamRequest {
                     starting_version: starting_version__,
                     transactions_count: transactions_count__,
+                    filter: filter__,
                 })
             }
         }
@@ -380,6 +522,7 @@ impl serde::Serialize for stream_status::StatusType {
             Self::Unspecified => "STATUS_TYPE_UNSPECIFIED",
             Self::Init => "STATUS_TYPE_INIT",
             Self::BatchEnd => "STATUS_TYPE_BATCH_END",
+            Self::Heartbeat => "STATUS_TYPE_HEARTBEAT",
         };
         serializer.serialize_str(variant)
     }
@@ -394,6 +537,7 @@ impl<'de> serde::Deserialize<'de> for stream_status::StatusType {
             "STATUS_TYPE_UNSPECIFIED",
             "STATUS_TYPE_INIT",
             "STATUS_TYPE_BATCH_END",
+            "STATUS_TYPE_HEARTBEAT",
         ];
 
         struct GeneratedVisitor;
@@ -439,6 +583,7 @@ impl<'de> serde::Deserialize<'de> for stream_status::StatusType {
                     "STATUS_TYPE_UNSPECIFIED" => Ok(stream_status::StatusType::Unspecified),
                     "STATUS_TYPE_INIT" => Ok(stream_status::StatusType::Init),
                     "STATUS_TYPE_BATCH_END" => Ok(stream_status::StatusType::BatchEnd),
+                    "STATUS_TYPE_HEARTBEAT" => Ok(stream_status::StatusType::Heartbeat),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -446,6 +591,165 @@ impl<'de> serde::Deserialize<'de> for stream_status::StatusType {
         deserializer.deserialize_any(GeneratedVisitor)
     }
 }
+impl serde::Serialize for TransactionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.sender_addresses.is_empty() {
+            len += 1;
+        }
+        if !self.entry_functions.is_empty() {
+            len += 1;
+        }
+        if !self.event_types.is_empty() {
+            len += 1;
+        }
+        if !self.write_resource_types.is_empty() {
+            len += 1;
+        }
+        if self.success_only {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.datastream.v1.TransactionFilter", len)?;
+        if !self.sender_addresses.is_empty() {
+            struct_ser.serialize_field("senderAddresses", &self.sender_addresses)?;
+        }
+        if !self.entry_functions.is_empty() {
+            struct_ser.serialize_field("entryFunctions", &self.entry_functions)?;
+        }
+        if !self.event_types.is_empty() {
+            struct_ser.serialize_field("eventTypes", &self.event_types)?;
+        }
+        if !self.write_resource_types.is_empty() {
+            struct_ser.serialize_field("writeResourceTypes", &self.write_resource_types)?;
+        }
+        if self.success_only {
+            struct_ser.serialize_field("successOnly", &self.success_only)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TransactionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "senderAddresses",
+            "entryFunctions",
+            "eventTypes",
+            "writeResourceTypes",
+            "successOnly",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            SenderAddresses,
+            EntryFunctions,
+            EventTypes,
+            WriteResourceTypes,
+            SuccessOnly,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "senderAddresses" => Ok(GeneratedField::SenderAddresses),
+                            "entryFunctions" => Ok(GeneratedField::EntryFunctions),
+                            "eventTypes" => Ok(GeneratedField::EventTypes),
+                            "writeResourceTypes" => Ok(GeneratedField::WriteResourceTypes),
+                            "successOnly" => Ok(GeneratedField::SuccessOnly),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TransactionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.datastream.v1.TransactionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<TransactionFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sender_addresses__ = None;
+                let mut entry_functions__ = None;
+                let mut event_types__ = None;
+                let mut write_resource_types__ = None;
+                let mut success_only__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::SenderAddresses => {
+                            if sender_addresses__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("senderAddresses"));
+                            }
+                            sender_addresses__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::EntryFunctions => {
+                            if entry_functions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("entryFunctions"));
+                            }
+                            entry_functions__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::EventTypes => {
+                            if event_types__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("eventTypes"));
+                            }
+                            event_types__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::WriteResourceTypes => {
+                            if write_resource_types__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("writeResourceTypes"));
+                            }
+                            write_resource_types__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SuccessOnly => {
+                            if success_only__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("successOnly"));
+                            }
+                            success_only__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(TransactionFilter {
+                    sender_addresses: sender_addresses__.unwrap_or_default(),
+                    entry_functions: entry_functions__.unwrap_or_default(),
+                    event_types: event_types__.unwrap_or_default(),
+                    write_resource_types: write_resource_types__.unwrap_or_default(),
+                    success_only: success_only__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.datastream.v1.TransactionFilter", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for TransactionOutput {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -70,7 +70,10 @@ async fn account_balance(
         &rest_client,
         request.account_identifier,
         balance_version,
-        request.currencies,
+        request
+            .currencies
+            .map(|currencies| currencies.into_iter().map(Currency::normalized).collect()),
+        &server_context.currencies,
     )
     .await?;
 
@@ -92,8 +95,25 @@ async fn get_balances(
     account: AccountIdentifier,
     version: u64,
     maybe_filter_currencies: Option<Vec<Currency>>,
+    currencies: &HashSet<Currency>,
 ) -> ApiResult<(u64, Option<Vec<AccountAddress>>, Vec<Amount>, u64)> {
     let owner_address = account.account_address()?;
+    let maybe_pool_address = account.pool_address()?;
+
+    // Fungible assets are held in the account's primary stores, which can exist without the account
+    let fungible_asset_balances = if account.is_base_account() {
+        get_fungible_asset_balances(
+            rest_client,
+            owner_address,
+            version,
+            currencies,
+            maybe_filter_currencies.as_deref(),
+        )
+        .await?
+    } else {
+        vec![]
+    };
+
     // Retrieve all account resources
     if let Ok(response) = rest_client
         .get_account_resources_at_version_bcs(owner_address, version)
@@ -132,7 +152,8 @@ async fn get_balances(
                     }
                 },
                 (AccountAddress::ONE, STAKING_CONTRACT_MODULE, STORE_RESOURCE) => {
-                    if account.is_base_account() {
+                    // Delegated stake isn't in a staking contract
+                    if account.is_base_account() || maybe_pool_address.is_some() {
                         continue;
                     }
 
@@ -192,6 +213,26 @@ async fn get_balances(
             }
         }
 
+        // Delegated stake is held by the delegation pool, not the delegator's account
+        if let Some(pool_address) = maybe_pool_address {
+            if let Some(balance_result) = get_delegation_stake_balances(
+                rest_client,
+                &account,
+                owner_address,
+                pool_address,
+                version,
+            )
+            .await?
+            {
+                if let Some(balance) = balance_result.balance {
+                    balances.push(balance);
+                }
+                lockup_expiration = balance_result.lockup_expiration;
+            }
+        }
+
+        balances.extend(fungible_asset_balances);
+
         let sequence_number = if let Some(sequence_number) = maybe_sequence_number {
             sequence_number
         } else {
@@ -229,14 +270,36 @@ async fn get_balances(
             lockup_expiration,
         ))
     } else {
-        Ok((
-            0,
-            None,
-            vec![Amount {
-                value: 0.to_string(),
-                currency: native_coin(),
-            }],
-            0,
-        ))
+        let mut balances = vec![Amount {
+            value: 0.to_string(),
+            currency: native_coin(),
+        }];
+        balances.extend(fungible_asset_balances);
+        Ok((0, None, balances, 0))
+    }
+}
+
+/// Retrieve the balances of the configured fungible assets, limited to the requested currencies
+async fn get_fungible_asset_balances(
+    rest_client: &aptos_rest_client::Client,
+    owner_address: AccountAddress,
+    version: u64,
+    currencies: &HashSet<Currency>,
+    maybe_filter_currencies: Option<&[Currency]>,
+) -> ApiResult<Vec<Amount>> {
+    let mut balances = vec![];
+    for currency in currencies {
+        if let Some(filter_currencies) = maybe_filter_currencies {
+            if !filter_currencies.contains(currency) {
+                continue;
+            }
+        }
+
+        if let Some(balance) =
+            get_fungible_asset_balance(rest_client, owner_address, currency, version).await?
+        {
+            balances.push(balance);
+        }
     }
+    Ok(balances)
 }
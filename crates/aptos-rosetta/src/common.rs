@@ -19,7 +19,7 @@ use aptos_sdk::move_types::{
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use futures::future::BoxFuture;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
+use std::{collections::HashSet, convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
 use warp::Filter;
 
 /// The year 2000 in milliseconds, as this is the lower limit for Rosetta API implementations
@@ -144,7 +144,8 @@ pub fn native_coin() -> Currency {
         symbol: DEFAULT_COIN.to_string(),
         decimals: DEFAULT_DECIMALS,
         metadata: Some(CurrencyMetadata {
-            move_type: native_coin_tag().to_string(),
+            move_type: Some(native_coin_tag().to_string()),
+            fa_address: None,
         }),
     }
 }
@@ -158,6 +159,17 @@ pub fn native_coin_tag() -> TypeTag {
     }))
 }
 
+/// Finds the configured currency backed by the fungible asset metadata object
+pub fn find_fa_currency(
+    currencies: &HashSet<Currency>,
+    metadata_address: AccountAddress,
+) -> Option<Currency> {
+    currencies
+        .iter()
+        .find(|currency| currency.fa_address() == Some(metadata_address))
+        .cloned()
+}
+
 pub fn is_native_coin(currency: &Currency) -> ApiResult<()> {
     if currency == &native_coin() {
         Ok(())
@@ -294,9 +306,15 @@ pub fn parse_currency(address: AccountAddress, module: &str, name: &str) -> ApiR
 
 #[cfg(test)]
 mod test {
-    use crate::common::BlockHash;
-    use aptos_types::chain_id::{ChainId, NamedChain};
-    use std::str::FromStr;
+    use crate::{
+        common::{find_fa_currency, native_coin, BlockHash},
+        types::{Currency, CurrencyMetadata},
+    };
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::{ChainId, NamedChain},
+    };
+    use std::{collections::HashSet, str::FromStr};
 
     #[test]
     pub fn chain_id_height_check() {
@@ -339,4 +357,48 @@ mod test {
             BlockHash::from_str(str).expect_err("Invalid block hash");
         }
     }
+
+    #[test]
+    pub fn fa_currency_lookup() {
+        let metadata_address = AccountAddress::from_hex_literal("0xa").unwrap();
+        let fa_currency = Currency {
+            symbol: "FA".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some(metadata_address.to_hex_literal()),
+            }),
+        };
+        let currencies: HashSet<Currency> = vec![native_coin(), fa_currency.clone()]
+            .into_iter()
+            .collect();
+
+        assert_eq!(native_coin().fa_address(), None);
+        assert_eq!(fa_currency.fa_address(), Some(metadata_address));
+        assert_eq!(
+            find_fa_currency(&currencies, metadata_address),
+            Some(fa_currency)
+        );
+        assert_eq!(find_fa_currency(&currencies, AccountAddress::ONE), None);
+
+        // Currencies are configured from YAML, and addresses don't have to be the full length
+        let parsed: Vec<Currency> = serde_yaml::from_str(
+            "- symbol: FA\n  decimals: 6\n  metadata:\n    fa_address: \"0xa\"\n",
+        )
+        .unwrap();
+        assert_eq!(parsed[0].fa_address(), Some(metadata_address));
+
+        // Requested and configured currencies compare equal however the address is written
+        let full_length: Vec<Currency> = serde_yaml::from_str(&format!(
+            "- symbol: FA\n  decimals: 6\n  metadata:\n    fa_address: \"0x{}\"\n",
+            metadata_address.to_hex()
+        ))
+        .unwrap();
+        assert_ne!(parsed[0], full_length[0]);
+        assert_eq!(
+            parsed[0].clone().normalized(),
+            full_length[0].clone().normalized()
+        );
+        assert_eq!(native_coin().normalized(), native_coin());
+    }
 }
@@ -561,6 +561,17 @@ async fn construction_parse(
                     STAKING_CONTRACT_MODULE,
                     DISTRIBUTE_STAKING_REWARDS_FUNCTION,
                 ) => parse_distribute_staking_rewards_operation(sender, &type_args, &args)?,
+                (AccountAddress::ONE, DELEGATION_POOL_MODULE, ADD_DELEGATED_STAKE_FUNCTION) => {
+                    parse_add_delegated_stake_operation(sender, &type_args, &args)?
+                },
+                (AccountAddress::ONE, DELEGATION_POOL_MODULE, UNLOCK_DELEGATED_STAKE_FUNCTION) => {
+                    parse_unlock_delegated_stake_operation(sender, &type_args, &args)?
+                },
+                (
+                    AccountAddress::ONE,
+                    DELEGATION_POOL_MODULE,
+                    WITHDRAW_UNDELEGATED_FUNDS_FUNCTION,
+                ) => parse_withdraw_undelegated_funds_operation(sender, &type_args, &args)?,
                 _ => {
                     return Err(ApiError::TransactionParseError(Some(format!(
                         "Unsupported entry function type {:x}::{}::{}",
@@ -885,6 +896,78 @@ pub fn parse_distribute_staking_rewards_operation(
     )])
 }
 
+pub fn parse_add_delegated_stake_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Add delegated stake should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    let pool_address: AccountAddress = parse_function_arg("add_stake", args, 0)?;
+    let amount: u64 = parse_function_arg("add_stake", args, 1)?;
+
+    Ok(vec![Operation::add_delegated_stake(
+        0,
+        None,
+        sender,
+        AccountIdentifier::base_account(pool_address),
+        Some(amount),
+    )])
+}
+
+pub fn parse_unlock_delegated_stake_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Unlock delegated stake should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    let pool_address: AccountAddress = parse_function_arg("unlock", args, 0)?;
+    let amount: u64 = parse_function_arg("unlock", args, 1)?;
+
+    Ok(vec![Operation::unlock_delegated_stake(
+        0,
+        None,
+        sender,
+        AccountIdentifier::base_account(pool_address),
+        Some(amount),
+    )])
+}
+
+pub fn parse_withdraw_undelegated_funds_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Withdraw undelegated funds should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    let pool_address: AccountAddress = parse_function_arg("withdraw", args, 0)?;
+    let amount: u64 = parse_function_arg("withdraw", args, 1)?;
+
+    Ok(vec![Operation::withdraw_undelegated_funds(
+        0,
+        None,
+        sender,
+        AccountIdentifier::base_account(pool_address),
+        Some(amount),
+    )])
+}
+
 /// Construction payloads command (OFFLINE)
 ///
 /// Constructs payloads for given known operations
@@ -1018,6 +1101,30 @@ async fn construction_payloads(
                 ))));
             }
         },
+        InternalOperation::AddDelegatedStake(_) => {
+            if operation != metadata.internal_operation {
+                return Err(ApiError::InvalidInput(Some(format!(
+                    "Add delegated stake operation doesn't match metadata {:?} vs {:?}",
+                    operation, metadata.internal_operation
+                ))));
+            }
+        },
+        InternalOperation::UnlockDelegatedStake(_) => {
+            if operation != metadata.internal_operation {
+                return Err(ApiError::InvalidInput(Some(format!(
+                    "Unlock delegated stake operation doesn't match metadata {:?} vs {:?}",
+                    operation, metadata.internal_operation
+                ))));
+            }
+        },
+        InternalOperation::WithdrawUndelegatedFunds(_) => {
+            if operation != metadata.internal_operation {
+                return Err(ApiError::InvalidInput(Some(format!(
+                    "Withdraw undelegated funds operation doesn't match metadata {:?} vs {:?}",
+                    operation, metadata.internal_operation
+                ))));
+            }
+        },
     }
 
     // Encode operation
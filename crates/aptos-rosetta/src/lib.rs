@@ -7,15 +7,19 @@
 
 use crate::{
     block::BlockRetriever,
-    common::{handle_request, with_context},
+    common::{handle_request, native_coin, with_context},
     error::{ApiError, ApiResult},
-    types::Store,
+    types::{Currency, Store},
 };
 use aptos_config::config::ApiConfig;
 use aptos_logger::{debug, warn};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use aptos_warp_webserver::{logger, Error, WebServer};
-use std::{collections::BTreeMap, convert::Infallible, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::Infallible,
+    sync::Arc,
+};
 use tokio::task::JoinHandle;
 use warp::{
     http::{HeaderValue, Method, StatusCode},
@@ -46,6 +50,8 @@ pub struct RosettaContext {
     pub block_cache: Option<Arc<BlockRetriever>>,
     pub owner_addresses: Vec<AccountAddress>,
     pub pool_address_to_owner: BTreeMap<AccountAddress, AccountAddress>,
+    /// Currencies shown in balances and operations, always including the native coin
+    pub currencies: HashSet<Currency>,
 }
 
 impl RosettaContext {
@@ -54,7 +60,12 @@ impl RosettaContext {
         chain_id: ChainId,
         block_cache: Option<Arc<BlockRetriever>>,
         owner_addresses: Vec<AccountAddress>,
+        currencies: HashSet<Currency>,
     ) -> Self {
+        let mut currencies: HashSet<Currency> =
+            currencies.into_iter().map(Currency::normalized).collect();
+        currencies.insert(native_coin());
+
         let mut pool_address_to_owner = BTreeMap::new();
         if let Some(ref rest_client) = rest_client {
             // We have to now fill in all of the mappings of owner to pool address
@@ -87,6 +98,7 @@ impl RosettaContext {
             block_cache,
             owner_addresses,
             pool_address_to_owner,
+            currencies,
         }
     }
 
@@ -113,6 +125,7 @@ pub fn bootstrap(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<tokio::runtime::Runtime> {
     let runtime = aptos_runtimes::spawn_named_runtime("rosetta".into(), None);
 
@@ -123,6 +136,7 @@ pub fn bootstrap(
         api_config,
         rest_client,
         owner_addresses,
+        currencies,
     ));
     Ok(runtime)
 }
@@ -133,6 +147,7 @@ pub async fn bootstrap_async(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<JoinHandle<()>> {
     debug!("Starting up Rosetta server with {:?}", api_config);
 
@@ -160,8 +175,14 @@ pub async fn bootstrap_async(
             ))
         });
 
-        let context =
-            RosettaContext::new(rest_client.clone(), chain_id, block_cache, owner_addresses).await;
+        let context = RosettaContext::new(
+            rest_client.clone(),
+            chain_id,
+            block_cache,
+            owner_addresses,
+            currencies,
+        )
+        .await;
         api.serve(routes(context)).await;
     });
     Ok(handle)
@@ -6,11 +6,12 @@
 use aptos_config::config::{ApiConfig, DEFAULT_MAX_PAGE_SIZE};
 use aptos_logger::prelude::*;
 use aptos_node::AptosNodeArgs;
-use aptos_rosetta::bootstrap;
+use aptos_rosetta::{bootstrap, types::Currency};
 use aptos_sdk::move_types::account_address::AccountAddress;
 use aptos_types::chain_id::ChainId;
 use clap::Parser;
 use std::{
+    collections::HashSet,
     fs::read_to_string,
     net::SocketAddr,
     path::PathBuf,
@@ -90,6 +91,7 @@ async fn main() {
         args.api_config(),
         args.rest_client(),
         args.owner_addresses(),
+        args.currencies(),
     )
     .expect("aptos-rosetta: Should bootstrap rosetta server");
 
@@ -114,6 +116,9 @@ trait ServerArgs {
 
     /// Retrieve owner addresses
     fn owner_addresses(&self) -> Vec<AccountAddress>;
+
+    /// Retrieve additional currencies to support, beyond the native coin
+    fn currencies(&self) -> HashSet<Currency>;
 }
 
 /// Aptos Rosetta API Server
@@ -162,6 +167,14 @@ impl ServerArgs for CommandArgs {
             CommandArgs::Online(args) => args.owner_addresses(),
         }
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        match self {
+            CommandArgs::OnlineRemote(args) => args.currencies(),
+            CommandArgs::Offline(args) => args.currencies(),
+            CommandArgs::Online(args) => args.currencies(),
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -212,6 +225,10 @@ impl ServerArgs for OfflineArgs {
     fn owner_addresses(&self) -> Vec<AccountAddress> {
         vec![]
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        HashSet::new()
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -224,6 +241,11 @@ pub struct OnlineRemoteArgs {
     /// Owner addresses file as a YAML file with a list
     #[clap(long, parse(from_os_str))]
     owner_address_file: Option<PathBuf>,
+    /// Additional currencies file as a YAML file with a list of Rosetta currencies
+    ///
+    /// Fungible assets are given by the address of their metadata object in `fa_address`
+    #[clap(long, parse(from_os_str))]
+    currency_config_file: Option<PathBuf>,
 }
 
 impl ServerArgs for OnlineRemoteArgs {
@@ -249,6 +271,17 @@ impl ServerArgs for OnlineRemoteArgs {
             vec![]
         }
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        if let Some(ref path) = self.currency_config_file {
+            serde_yaml::from_str(
+                &read_to_string(path.as_path()).expect("Failed to read currency config file"),
+            )
+            .expect("Currency config file is in an invalid format")
+        } else {
+            HashSet::new()
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -277,4 +310,8 @@ impl ServerArgs for OnlineLocalArgs {
     fn owner_addresses(&self) -> Vec<AccountAddress> {
         self.online_args.owner_addresses()
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        self.online_args.currencies()
+    }
 }
@@ -88,6 +88,54 @@ impl AccountIdentifier {
         }
     }
 
+    pub fn delegated_total_stake_account(
+        address: AccountAddress,
+        pool_address: AccountAddress,
+    ) -> Self {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier::new_delegated_total_stake(
+                pool_address,
+            )),
+        }
+    }
+
+    pub fn delegated_active_stake_account(
+        address: AccountAddress,
+        pool_address: AccountAddress,
+    ) -> Self {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier::new_delegated_active_stake(
+                pool_address,
+            )),
+        }
+    }
+
+    pub fn delegated_pending_inactive_stake_account(
+        address: AccountAddress,
+        pool_address: AccountAddress,
+    ) -> Self {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier::new_delegated_pending_inactive_stake(
+                pool_address,
+            )),
+        }
+    }
+
+    pub fn delegated_inactive_stake_account(
+        address: AccountAddress,
+        pool_address: AccountAddress,
+    ) -> Self {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier::new_delegated_inactive_stake(
+                pool_address,
+            )),
+        }
+    }
+
     pub fn is_base_account(&self) -> bool {
         self.sub_account.is_none()
     }
@@ -153,6 +201,15 @@ impl AccountIdentifier {
             )))
         }
     }
+
+    /// The delegation pool of a delegated stake account, or None for any other account
+    pub fn pool_address(&self) -> ApiResult<Option<AccountAddress>> {
+        if let Some(ref inner) = self.sub_account {
+            inner.pool_address()
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 fn str_to_account_address(address: &str) -> Result<AccountAddress, ApiError> {
@@ -163,10 +220,22 @@ fn str_to_account_address(address: &str) -> Result<AccountAddress, ApiError> {
 /// There are two types of SubAccountIdentifiers
 /// 1. `stake` which is the total stake
 /// 2. `stake-<operator>` which is the stake on the operator
+///
+/// Stake delegated to a delegation pool uses the same names as the stake pool states, with the
+/// pool in the metadata e.g. `active_stake` with a `pool_address`
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct SubAccountIdentifier {
     /// Hex encoded AccountAddress beginning with 0x
     pub address: String,
+    /// Only used for delegated stake
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SubAccountIdentifierMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SubAccountIdentifierMetadata {
+    /// Hex encoded AccountAddress of the delegation pool beginning with 0x
+    pub pool_address: String,
 }
 
 const STAKE: &str = "stake";
@@ -180,39 +249,70 @@ impl SubAccountIdentifier {
     pub fn new_total_stake() -> SubAccountIdentifier {
         SubAccountIdentifier {
             address: STAKE.to_string(),
+            metadata: None,
         }
     }
 
     pub fn new_pending_active_stake() -> SubAccountIdentifier {
         SubAccountIdentifier {
             address: PENDING_ACTIVE_STAKE.to_string(),
+            metadata: None,
         }
     }
 
     pub fn new_active_stake() -> SubAccountIdentifier {
         SubAccountIdentifier {
             address: ACTIVE_STAKE.to_string(),
+            metadata: None,
         }
     }
 
     pub fn new_pending_inactive_stake() -> SubAccountIdentifier {
         SubAccountIdentifier {
             address: PENDING_INACTIVE_STAKE.to_string(),
+            metadata: None,
         }
     }
 
     pub fn new_inactive_stake() -> SubAccountIdentifier {
         SubAccountIdentifier {
             address: INACTIVE_STAKE.to_string(),
+            metadata: None,
         }
     }
 
     pub fn new_operator_stake(operator: AccountAddress) -> SubAccountIdentifier {
         SubAccountIdentifier {
             address: format!("{}-{}", STAKE, to_hex_lower(&operator)),
+            metadata: None,
         }
     }
 
+    pub fn new_delegated_total_stake(pool_address: AccountAddress) -> SubAccountIdentifier {
+        Self::new_total_stake().with_pool_address(pool_address)
+    }
+
+    pub fn new_delegated_active_stake(pool_address: AccountAddress) -> SubAccountIdentifier {
+        Self::new_active_stake().with_pool_address(pool_address)
+    }
+
+    pub fn new_delegated_pending_inactive_stake(
+        pool_address: AccountAddress,
+    ) -> SubAccountIdentifier {
+        Self::new_pending_inactive_stake().with_pool_address(pool_address)
+    }
+
+    pub fn new_delegated_inactive_stake(pool_address: AccountAddress) -> SubAccountIdentifier {
+        Self::new_inactive_stake().with_pool_address(pool_address)
+    }
+
+    fn with_pool_address(mut self, pool_address: AccountAddress) -> SubAccountIdentifier {
+        self.metadata = Some(SubAccountIdentifierMetadata {
+            pool_address: to_hex_lower(&pool_address),
+        });
+        self
+    }
+
     pub fn is_total_stake(&self) -> bool {
         self.address.as_str() == STAKE
     }
@@ -249,6 +349,14 @@ impl SubAccountIdentifier {
             self
         ))))
     }
+
+    pub fn pool_address(&self) -> ApiResult<Option<AccountAddress>> {
+        if let Some(ref metadata) = self.metadata {
+            str_to_account_address(metadata.pool_address.as_str()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 /// Identifier for a "block".  In aptos, we use a transaction model, so the index
@@ -436,6 +544,45 @@ mod test {
         assert!(base_account.operator_address().is_err());
         assert_eq!(Ok(operator), operator_stake_account.operator_address());
         assert!(total_stake_account.operator_address().is_err());
+
+        assert_eq!(Ok(None), base_account.pool_address());
+        assert_eq!(Ok(None), active_stake_account.pool_address());
+        assert_eq!(Ok(None), operator_stake_account.pool_address());
+    }
+
+    #[test]
+    fn test_delegated_account_id() {
+        let account = AccountAddress::ONE;
+        let pool = AccountAddress::TWO;
+
+        let total_stake_account = AccountIdentifier::delegated_total_stake_account(account, pool);
+        let active_stake_account = AccountIdentifier::delegated_active_stake_account(account, pool);
+        let pending_inactive_stake_account =
+            AccountIdentifier::delegated_pending_inactive_stake_account(account, pool);
+        let inactive_stake_account =
+            AccountIdentifier::delegated_inactive_stake_account(account, pool);
+
+        assert!(total_stake_account.is_total_stake());
+        assert!(active_stake_account.is_active_stake());
+        assert!(pending_inactive_stake_account.is_pending_inactive_stake());
+        assert!(inactive_stake_account.is_inactive_stake());
+
+        for account_id in [
+            &total_stake_account,
+            &active_stake_account,
+            &pending_inactive_stake_account,
+            &inactive_stake_account,
+        ] {
+            assert!(!account_id.is_base_account());
+            assert!(!account_id.is_operator_stake());
+            assert_eq!(Ok(account), account_id.account_address());
+            assert_eq!(Ok(Some(pool)), account_id.pool_address());
+        }
+
+        // The pool must survive a round trip through JSON
+        let json = serde_json::to_string(&active_stake_account).unwrap();
+        let parsed: AccountIdentifier = serde_json::from_str(&json).unwrap();
+        assert_eq!(active_stake_account, parsed);
     }
 
     #[test]
@@ -4,10 +4,19 @@
 use crate::{
     common::native_coin,
     error::ApiError,
-    types::{AccountIdentifier, Amount},
+    types::{
+        AccountIdentifier, Amount, Currency, BALANCE_FUNCTION, DELEGATION_POOL_GET_STAKE_FUNCTION,
+        DELEGATION_POOL_MODULE, FUNGIBLE_ASSET_METADATA_RESOURCE, FUNGIBLE_ASSET_MODULE,
+        PRIMARY_FUNGIBLE_STORE_MODULE,
+    },
     AccountAddress, ApiResult,
 };
+use aptos_rest_client::{
+    aptos_api_types::{EntryFunctionId, MoveType, ViewRequest},
+    error::RestError,
+};
 use aptos_types::stake_pool::StakePool;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::{
     convert::TryFrom,
@@ -103,11 +112,15 @@ pub enum OperationType {
     ResetLockup,
     UnlockStake,
     DistributeStakingRewards,
+    AddDelegatedStake,
+    UnlockDelegatedStake,
+    WithdrawUndelegatedFunds,
     // Fee must always be last for ordering
     Fee,
 }
 
 impl OperationType {
+    const ADD_DELEGATED_STAKE: &'static str = "add_delegated_stake";
     const CREATE_ACCOUNT: &'static str = "create_account";
     const DEPOSIT: &'static str = "deposit";
     const DISTRIBUTE_STAKING_REWARDS: &'static str = "distribute_staking_rewards";
@@ -117,8 +130,10 @@ impl OperationType {
     const SET_OPERATOR: &'static str = "set_operator";
     const SET_VOTER: &'static str = "set_voter";
     const STAKING_REWARD: &'static str = "staking_reward";
+    const UNLOCK_DELEGATED_STAKE: &'static str = "unlock_delegated_stake";
     const UNLOCK_STAKE: &'static str = "unlock_stake";
     const WITHDRAW: &'static str = "withdraw";
+    const WITHDRAW_UNDELEGATED_FUNDS: &'static str = "withdraw_undelegated_funds";
 
     pub fn all() -> Vec<OperationType> {
         use OperationType::*;
@@ -134,6 +149,9 @@ impl OperationType {
             ResetLockup,
             UnlockStake,
             DistributeStakingRewards,
+            AddDelegatedStake,
+            UnlockDelegatedStake,
+            WithdrawUndelegatedFunds,
         ]
     }
 }
@@ -154,6 +172,9 @@ impl FromStr for OperationType {
             Self::RESET_LOCKUP => Ok(OperationType::ResetLockup),
             Self::UNLOCK_STAKE => Ok(OperationType::UnlockStake),
             Self::DISTRIBUTE_STAKING_REWARDS => Ok(OperationType::DistributeStakingRewards),
+            Self::ADD_DELEGATED_STAKE => Ok(OperationType::AddDelegatedStake),
+            Self::UNLOCK_DELEGATED_STAKE => Ok(OperationType::UnlockDelegatedStake),
+            Self::WITHDRAW_UNDELEGATED_FUNDS => Ok(OperationType::WithdrawUndelegatedFunds),
             _ => Err(ApiError::DeserializationFailed(Some(format!(
                 "Invalid OperationType: {}",
                 s
@@ -176,6 +197,9 @@ impl Display for OperationType {
             ResetLockup => Self::RESET_LOCKUP,
             UnlockStake => Self::UNLOCK_STAKE,
             DistributeStakingRewards => Self::DISTRIBUTE_STAKING_REWARDS,
+            AddDelegatedStake => Self::ADD_DELEGATED_STAKE,
+            UnlockDelegatedStake => Self::UNLOCK_DELEGATED_STAKE,
+            WithdrawUndelegatedFunds => Self::WITHDRAW_UNDELEGATED_FUNDS,
             Fee => Self::FEE,
         })
     }
@@ -305,3 +329,275 @@ pub async fn get_stake_balances(
         Ok(None)
     }
 }
+
+/// Retrieves the stake a delegator has in a delegation pool
+///
+/// The delegation pool doesn't track `pending_active` stake separately, it's included in `active`
+pub async fn get_delegation_stake_balances(
+    rest_client: &aptos_rest_client::Client,
+    account_identifier: &AccountIdentifier,
+    delegator_address: AccountAddress,
+    pool_address: AccountAddress,
+    version: u64,
+) -> ApiResult<Option<BalanceResult>> {
+    const STAKE_POOL: &str = "0x1::stake::StakePool";
+
+    if account_identifier.is_pending_active_stake() || account_identifier.is_operator_stake() {
+        return Err(ApiError::InvalidInput(Some(
+            "Only total, active, pending_inactive and inactive stake are supported for delegated stake"
+                .to_string(),
+        )));
+    }
+
+    let (active, inactive, pending_inactive) =
+        match get_delegated_stake(rest_client, delegator_address, pool_address, version).await? {
+            Some(stake) => stake,
+            None => return Ok(None),
+        };
+
+    let requested_balance = if account_identifier.is_active_stake() {
+        active
+    } else if account_identifier.is_pending_inactive_stake() {
+        pending_inactive
+    } else if account_identifier.is_inactive_stake() {
+        inactive
+    } else if account_identifier.is_total_stake() {
+        active + inactive + pending_inactive
+    } else {
+        return Ok(None);
+    };
+
+    // The lockup is on the underlying stake pool
+    let lockup_expiration = rest_client
+        .get_account_resource_at_version_bcs::<StakePool>(pool_address, STAKE_POOL, version)
+        .await?
+        .into_inner()
+        .locked_until_secs;
+
+    Ok(Some(BalanceResult {
+        balance: Some(Amount {
+            value: requested_balance.to_string(),
+            currency: native_coin(),
+        }),
+        lockup_expiration,
+    }))
+}
+
+/// Retrieves the `(active, inactive, pending_inactive)` stake of a delegator in a delegation pool
+///
+/// The stake is converted from the delegator's shares with the pool's share price by
+/// `delegation_pool::get_stake`.  Returns `None` if there's no delegation pool at the version.
+pub async fn get_delegated_stake(
+    rest_client: &aptos_rest_client::Client,
+    delegator_address: AccountAddress,
+    pool_address: AccountAddress,
+    version: u64,
+) -> ApiResult<Option<(u64, u64, u64)>> {
+    const DELEGATION_POOL: &str = "0x1::delegation_pool::DelegationPool";
+
+    // If there's no delegation pool at the version, there's no stake in it
+    match rest_client
+        .get_account_resource_at_version_bytes(pool_address, DELEGATION_POOL, version)
+        .await
+    {
+        Ok(_) => {},
+        Err(RestError::Api(error)) if error.status_code == StatusCode::NOT_FOUND => {
+            return Ok(None)
+        },
+        Err(error) => return Err(error.into()),
+    }
+
+    let request = ViewRequest {
+        function: EntryFunctionId::from_str(&format!(
+            "0x1::{}::{}",
+            DELEGATION_POOL_MODULE, DELEGATION_POOL_GET_STAKE_FUNCTION
+        ))?,
+        type_arguments: vec![],
+        arguments: vec![
+            serde_json::Value::String(pool_address.to_hex_literal()),
+            serde_json::Value::String(delegator_address.to_hex_literal()),
+        ],
+    };
+
+    let stake = rest_client
+        .view(&request, Some(version))
+        .await?
+        .into_inner()
+        .iter()
+        .map(|value| {
+            value
+                .as_str()
+                .and_then(|value| u64::from_str(value).ok())
+                .ok_or_else(|| {
+                    ApiError::InternalError(Some(format!(
+                        "Failed to parse delegated stake amount {}",
+                        value
+                    )))
+                })
+        })
+        .collect::<ApiResult<Vec<u64>>>()?;
+
+    // The view function returns (active, inactive, pending_inactive)
+    match stake.as_slice() {
+        [active, inactive, pending_inactive] => Ok(Some((*active, *inactive, *pending_inactive))),
+        _ => Err(ApiError::InternalError(Some(format!(
+            "Unexpected delegated stake result {:?}",
+            stake
+        )))),
+    }
+}
+
+/// Retrieves the balance of a fungible asset in an account's primary store
+///
+/// Returns `None` if the currency isn't a fungible asset, and 0 if the account has no primary store
+/// for it
+pub async fn get_fungible_asset_balance(
+    rest_client: &aptos_rest_client::Client,
+    owner_address: AccountAddress,
+    currency: &Currency,
+    version: u64,
+) -> ApiResult<Option<Amount>> {
+    let metadata_address = if let Some(metadata_address) = currency.fa_address() {
+        metadata_address
+    } else {
+        return Ok(None);
+    };
+
+    let request = ViewRequest {
+        function: EntryFunctionId::from_str(&format!(
+            "0x1::{}::{}",
+            PRIMARY_FUNGIBLE_STORE_MODULE, BALANCE_FUNCTION
+        ))?,
+        type_arguments: vec![MoveType::from_str(&format!(
+            "0x1::{}::{}",
+            FUNGIBLE_ASSET_MODULE, FUNGIBLE_ASSET_METADATA_RESOURCE
+        ))?],
+        arguments: vec![
+            serde_json::Value::String(owner_address.to_hex_literal()),
+            serde_json::Value::String(metadata_address.to_hex_literal()),
+        ],
+    };
+
+    let response = match rest_client.view(&request, Some(version)).await {
+        Ok(response) => response,
+        // If the fungible asset doesn't exist yet at the version, there's no balance
+        Err(RestError::Api(error)) if error.status_code == StatusCode::NOT_FOUND => {
+            return Ok(None)
+        },
+        Err(error) => return Err(error.into()),
+    };
+    let balance = response
+        .into_inner()
+        .first()
+        .and_then(|value| value.as_str())
+        .and_then(|value| u64::from_str(value).ok())
+        .ok_or_else(|| {
+            ApiError::InternalError(Some(format!(
+                "Failed to parse fungible asset balance for {}",
+                currency.symbol
+            )))
+        })?;
+
+    Ok(Some(Amount {
+        value: balance.to_string(),
+        currency: currency.clone(),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::CurrencyMetadata;
+    use httpmock::{Method::POST, MockServer, Then};
+    use serde_json::json;
+
+    /// The API client requires these headers on every response
+    fn with_ledger_headers(then: Then) -> Then {
+        then.header("X-Aptos-Chain-Id", "4")
+            .header("X-Aptos-Ledger-Version", "1")
+            .header("X-Aptos-Ledger-TimestampUsec", "1")
+            .header("X-Aptos-Epoch", "1")
+            .header("X-Aptos-Ledger-Oldest-Version", "0")
+            .header("X-Aptos-Block-Height", "1")
+            .header("X-Aptos-Oldest-Block-Height", "0")
+    }
+
+    fn fa_currency() -> Currency {
+        Currency {
+            symbol: "FA".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some("0xa".to_string()),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_fungible_asset_balance() {
+        let server = MockServer::start();
+        let rest_client = aptos_rest_client::Client::new(server.base_url().parse().unwrap());
+        let owner = AccountAddress::from_hex_literal("0x1234").unwrap();
+        let currency = fa_currency();
+
+        // Currencies that aren't fungible assets don't have a balance here
+        assert_eq!(
+            get_fungible_asset_balance(&rest_client, owner, &native_coin(), 1)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let mut balance_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v1/view")
+                .query_param("ledger_version", "1")
+                .json_body_partial(
+                    json!({ "arguments": [owner.to_hex_literal(), "0xa"] }).to_string(),
+                );
+            with_ledger_headers(then.status(200)).json_body(json!(["100"]));
+        });
+        assert_eq!(
+            get_fungible_asset_balance(&rest_client, owner, &currency, 1)
+                .await
+                .unwrap(),
+            Some(Amount {
+                value: "100".to_string(),
+                currency: currency.clone(),
+            })
+        );
+        balance_mock.assert();
+        balance_mock.delete();
+
+        // If the asset doesn't exist yet at the version, there's no balance
+        let mut not_found_mock = server.mock(|when, then| {
+            when.method(POST).path("/v1/view");
+            with_ledger_headers(then.status(404)).json_body(json!({
+                "message": "Module not found",
+                "error_code": "module_not_found",
+                "vm_error_code": null,
+            }));
+        });
+        assert_eq!(
+            get_fungible_asset_balance(&rest_client, owner, &currency, 1)
+                .await
+                .unwrap(),
+            None
+        );
+        not_found_mock.assert();
+        not_found_mock.delete();
+
+        // Any other failure is surfaced, rather than reported as no balance
+        server.mock(|when, then| {
+            when.method(POST).path("/v1/view");
+            with_ledger_headers(then.status(500)).json_body(json!({
+                "message": "Internal error",
+                "error_code": "internal_error",
+                "vm_error_code": null,
+            }));
+        });
+        get_fungible_asset_balance(&rest_client, owner, &currency, 1)
+            .await
+            .expect_err("Server errors should be returned");
+    }
+}
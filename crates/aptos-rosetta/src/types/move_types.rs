@@ -11,6 +11,10 @@ pub const ACCOUNT_MODULE: &str = "account";
 pub const APTOS_ACCOUNT_MODULE: &str = "aptos_account";
 pub const APTOS_COIN_MODULE: &str = "aptos_coin";
 pub const COIN_MODULE: &str = "coin";
+pub const DELEGATION_POOL_MODULE: &str = "delegation_pool";
+pub const FUNGIBLE_ASSET_MODULE: &str = "fungible_asset";
+pub const OBJECT_MODULE: &str = "object";
+pub const PRIMARY_FUNGIBLE_STORE_MODULE: &str = "primary_fungible_store";
 pub const STAKE_MODULE: &str = "stake";
 pub const STAKING_PROXY_MODULE: &str = "staking_proxy";
 pub const STAKING_CONTRACT_MODULE: &str = "staking_contract";
@@ -20,6 +24,12 @@ pub const ACCOUNT_RESOURCE: &str = "Account";
 pub const APTOS_COIN_RESOURCE: &str = "AptosCoin";
 pub const COIN_INFO_RESOURCE: &str = "CoinInfo";
 pub const COIN_STORE_RESOURCE: &str = "CoinStore";
+pub const DELEGATION_POOL_RESOURCE: &str = "DelegationPool";
+pub const FUNGIBLE_ASSET_EVENTS_RESOURCE: &str = "FungibleAssetEvents";
+pub const FUNGIBLE_ASSET_METADATA_RESOURCE: &str = "Metadata";
+pub const FUNGIBLE_STORE_RESOURCE: &str = "FungibleStore";
+pub const OBJECT_CORE_RESOURCE: &str = "ObjectCore";
+pub const OBJECT_GROUP_RESOURCE: &str = "ObjectGroup";
 pub const STAKE_POOL_RESOURCE: &str = "StakePool";
 pub const STAKING_CONTRACT_RESOURCE: &str = "StakingContract";
 pub const STORE_RESOURCE: &str = "Store";
@@ -34,6 +44,11 @@ pub const SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION: &str =
 pub const UPDATE_VOTER_FUNCTION: &str = "update_voter";
 pub const UNLOCK_STAKE_FUNCTION: &str = "unlock_stake";
 pub const DISTRIBUTE_STAKING_REWARDS_FUNCTION: &str = "distribute";
+pub const ADD_DELEGATED_STAKE_FUNCTION: &str = "add_stake";
+pub const UNLOCK_DELEGATED_STAKE_FUNCTION: &str = "unlock";
+pub const WITHDRAW_UNDELEGATED_FUNDS_FUNCTION: &str = "withdraw";
+pub const DELEGATION_POOL_GET_STAKE_FUNCTION: &str = "get_stake";
+pub const BALANCE_FUNCTION: &str = "balance";
 
 pub const DECIMALS_FIELD: &str = "decimal";
 pub const DEPOSIT_EVENTS_FIELD: &str = "deposit_events";
@@ -163,3 +178,87 @@ impl Pool {
 pub struct Capability {
     pub pool_address: AccountAddress,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DelegationPool {
+    pub active_shares: Pool,
+    pub observed_lockup_cycle: ObservedLockupCycle,
+    pub inactive_shares: Table,
+    pub pending_withdrawals: Table,
+    pub stake_pool_signer_cap: SignerCapability,
+    pub total_coins_inactive: u64,
+    pub operator_commission_percentage: u64,
+    pub add_stake_events: EventHandle,
+    pub reactivate_stake_events: EventHandle,
+    pub unlock_stake_events: EventHandle,
+    pub withdraw_stake_events: EventHandle,
+    pub distribute_commission_events: EventHandle,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct ObservedLockupCycle {
+    pub index: u64,
+}
+
+/// Only the handle of a table is stored in the resource, the items live elsewhere
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Table {
+    pub handle: AccountAddress,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct SignerCapability {
+    pub account: AccountAddress,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddDelegatedStakeEvent {
+    pub pool_address: AccountAddress,
+    pub delegator_address: AccountAddress,
+    pub amount_added: u64,
+    pub add_stake_fee: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReactivateDelegatedStakeEvent {
+    pub pool_address: AccountAddress,
+    pub delegator_address: AccountAddress,
+    pub amount_reactivated: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnlockDelegatedStakeEvent {
+    pub pool_address: AccountAddress,
+    pub delegator_address: AccountAddress,
+    pub amount_unlocked: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WithdrawDelegatedStakeEvent {
+    pub pool_address: AccountAddress,
+    pub delegator_address: AccountAddress,
+    pub amount_withdrawn: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObjectCore {
+    pub guid_creation_num: u64,
+    pub owner: AccountAddress,
+    pub allow_ungated_transfer: bool,
+    pub transfer_events: EventHandle,
+}
+
+/// `Object<Metadata>` is serialized as just the address of the metadata object
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FungibleStore {
+    pub metadata: AccountAddress,
+    pub balance: u64,
+    pub allow_ungated_balance_transfer: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FungibleAssetEvents {
+    pub deposit_events: EventHandle,
+    pub withdraw_events: EventHandle,
+    pub set_ungated_transfer_events: EventHandle,
+}
@@ -6,16 +6,19 @@
 //! [Spec](https://www.rosetta-api.org/docs/api_objects.html)
 
 use crate::{
-    common::{is_native_coin, native_coin, native_coin_tag},
+    common::{find_fa_currency, is_native_coin, native_coin, native_coin_tag},
     construction::{
-        parse_create_stake_pool_operation, parse_distribute_staking_rewards_operation,
-        parse_reset_lockup_operation, parse_set_operator_operation, parse_set_voter_operation,
-        parse_unlock_stake_operation,
+        parse_add_delegated_stake_operation, parse_create_stake_pool_operation,
+        parse_distribute_staking_rewards_operation, parse_reset_lockup_operation,
+        parse_set_operator_operation, parse_set_voter_operation,
+        parse_unlock_delegated_stake_operation, parse_unlock_stake_operation,
+        parse_withdraw_undelegated_funds_operation,
     },
     error::ApiResult,
     types::{
-        move_types::*, AccountIdentifier, BlockIdentifier, Error, OperationIdentifier,
-        OperationStatus, OperationStatusType, OperationType, TransactionIdentifier,
+        get_delegated_stake, move_types::*, AccountIdentifier, BlockIdentifier, Error,
+        OperationIdentifier, OperationStatus, OperationStatusType, OperationType,
+        TransactionIdentifier,
     },
     ApiError, RosettaContext,
 };
@@ -24,8 +27,9 @@ use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{ed25519::Ed25519PublicKey, ValidCryptoMaterialStringExt};
 use aptos_logger::warn;
 use aptos_rest_client::aptos_api_types::{TransactionOnChainData, U64};
+use aptos_sdk::move_types::language_storage::StructTag;
 use aptos_types::{
-    account_address::AccountAddress,
+    account_address::{create_derived_object_address, AccountAddress},
     account_config::{AccountResource, CoinStoreResource, WithdrawEvent},
     contract_event::ContractEvent,
     event::EventKey,
@@ -38,7 +42,7 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     fmt::{Display, Formatter},
     hash::Hash,
@@ -137,9 +141,36 @@ pub struct Currency {
     pub metadata: Option<CurrencyMetadata>,
 }
 
+impl Currency {
+    /// Address of the fungible asset metadata object, if the currency is a fungible asset
+    pub fn fa_address(&self) -> Option<AccountAddress> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.fa_address.as_ref())
+            .and_then(|address| AccountAddress::from_str(address).ok())
+    }
+
+    /// Rewrites the fungible asset address in its short form, so the same asset always compares
+    /// equal regardless of how its address was written e.g. `0xa` and `0x00...0a`
+    pub fn normalized(mut self) -> Currency {
+        if let Some(fa_address) = self.fa_address() {
+            if let Some(metadata) = self.metadata.as_mut() {
+                metadata.fa_address = Some(fa_address.to_hex_literal());
+            }
+        }
+        self
+    }
+}
+
+/// Identifies where a currency lives on chain, either as a coin type or a fungible asset
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct CurrencyMetadata {
-    pub move_type: String,
+    /// Coin type e.g. `0x1::aptos_coin::AptosCoin`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_type: Option<String>,
+    /// Address of the fungible asset metadata object
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fa_address: Option<String>,
 }
 
 /// Various signing curves supported by Rosetta.  We only use [`CurveType::Edwards25519`]
@@ -407,6 +438,57 @@ impl Operation {
             )),
         )
     }
+
+    pub fn add_delegated_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        delegator: AccountAddress,
+        pool_address: AccountIdentifier,
+        amount: Option<u64>,
+    ) -> Operation {
+        Operation::new(
+            OperationType::AddDelegatedStake,
+            operation_index,
+            status,
+            AccountIdentifier::base_account(delegator),
+            None,
+            Some(OperationMetadata::delegated_stake(pool_address, amount)),
+        )
+    }
+
+    pub fn unlock_delegated_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        delegator: AccountAddress,
+        pool_address: AccountIdentifier,
+        amount: Option<u64>,
+    ) -> Operation {
+        Operation::new(
+            OperationType::UnlockDelegatedStake,
+            operation_index,
+            status,
+            AccountIdentifier::base_account(delegator),
+            None,
+            Some(OperationMetadata::delegated_stake(pool_address, amount)),
+        )
+    }
+
+    pub fn withdraw_undelegated_funds(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        delegator: AccountAddress,
+        pool_address: AccountIdentifier,
+        amount: Option<u64>,
+    ) -> Operation {
+        Operation::new(
+            OperationType::WithdrawUndelegatedFunds,
+            operation_index,
+            status,
+            AccountIdentifier::base_account(delegator),
+            None,
+            Some(OperationMetadata::delegated_stake(pool_address, amount)),
+        )
+    }
 }
 
 impl std::cmp::PartialOrd for Operation {
@@ -459,6 +541,8 @@ pub struct OperationMetadata {
     pub amount: Option<U64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub staker: Option<AccountIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_address: Option<AccountIdentifier>,
 }
 
 impl OperationMetadata {
@@ -530,6 +614,14 @@ impl OperationMetadata {
             ..Default::default()
         }
     }
+
+    pub fn delegated_stake(pool_address: AccountIdentifier, amount: Option<u64>) -> Self {
+        OperationMetadata {
+            pool_address: Some(pool_address),
+            amount: amount.map(U64::from),
+            ..Default::default()
+        }
+    }
 }
 
 /// Public key used for the rosetta implementation.  All private keys will never be handled
@@ -853,6 +945,44 @@ fn parse_failed_operations_from_txn_payload(
                     warn!("Failed to parse distribute staking rewards {:?}", inner);
                 }
             },
+            (AccountAddress::ONE, DELEGATION_POOL_MODULE, ADD_DELEGATED_STAKE_FUNCTION) => {
+                if let Ok(mut ops) =
+                    parse_add_delegated_stake_operation(sender, inner.ty_args(), inner.args())
+                {
+                    if let Some(operation) = ops.get_mut(0) {
+                        operation.status = Some(OperationStatusType::Failure.to_string());
+                    }
+                    operations = ops;
+                } else {
+                    warn!("Failed to parse add delegated stake {:?}", inner);
+                }
+            },
+            (AccountAddress::ONE, DELEGATION_POOL_MODULE, UNLOCK_DELEGATED_STAKE_FUNCTION) => {
+                if let Ok(mut ops) =
+                    parse_unlock_delegated_stake_operation(sender, inner.ty_args(), inner.args())
+                {
+                    if let Some(operation) = ops.get_mut(0) {
+                        operation.status = Some(OperationStatusType::Failure.to_string());
+                    }
+                    operations = ops;
+                } else {
+                    warn!("Failed to parse unlock delegated stake {:?}", inner);
+                }
+            },
+            (AccountAddress::ONE, DELEGATION_POOL_MODULE, WITHDRAW_UNDELEGATED_FUNDS_FUNCTION) => {
+                if let Ok(mut ops) = parse_withdraw_undelegated_funds_operation(
+                    sender,
+                    inner.ty_args(),
+                    inner.args(),
+                ) {
+                    if let Some(operation) = ops.get_mut(0) {
+                        operation.status = Some(OperationStatusType::Failure.to_string());
+                    }
+                    operations = ops;
+                } else {
+                    warn!("Failed to parse withdraw undelegated funds {:?}", inner);
+                }
+            },
             _ => {
                 // If we don't recognize the transaction payload, then we can't parse operations
             },
@@ -958,6 +1088,27 @@ async fn parse_operations_from_write_set(
             parse_staking_contract_resource_changes(address, data, events, operation_index, changes)
                 .await
         },
+        (AccountAddress::ONE, DELEGATION_POOL_MODULE, DELEGATION_POOL_RESOURCE, 0) => {
+            parse_delegation_pool_resource_changes(
+                server_context,
+                version,
+                address,
+                data,
+                events,
+                operation_index,
+            )
+            .await
+        },
+        (AccountAddress::ONE, OBJECT_MODULE, OBJECT_GROUP_RESOURCE, 0) => {
+            parse_fungible_store_changes(
+                &server_context.currencies,
+                version,
+                address,
+                data,
+                events,
+                operation_index,
+            )
+        },
         (AccountAddress::ONE, COIN_MODULE, COIN_STORE_RESOURCE, 1) => {
             if let Some(type_tag) = struct_tag.type_params.first() {
                 // TODO: This will need to be updated to support more coins
@@ -1376,6 +1527,203 @@ async fn parse_staking_contract_resource_changes(
 
     Ok(operations)
 }
+
+/// Parses the delegator operations on a delegation pool
+///
+/// Coins moving in and out of the delegator's account are handled by the coin store.  The
+/// delegated stake sub-accounts of every delegator with an event are credited and debited by the
+/// change of their stake over the transaction, as converted from the delegator's shares by the
+/// pool, so that they reconcile with the balances even when shares round or the add stake fee is
+/// kept by the pool.
+async fn parse_delegation_pool_resource_changes(
+    server_context: &RosettaContext,
+    version: u64,
+    pool_address: AccountAddress,
+    data: &[u8],
+    events: &[ContractEvent],
+    mut operation_index: u64,
+) -> ApiResult<Vec<Operation>> {
+    let mut operations = Vec::new();
+    let delegation_pool: DelegationPool = if let Ok(pool) = bcs::from_bytes(data) {
+        pool
+    } else {
+        warn!(
+            "Failed to parse delegation pool {} at version {}",
+            pool_address, version
+        );
+        return Ok(operations);
+    };
+    let pool_account = AccountIdentifier::base_account(pool_address);
+    let mut delegators = BTreeSet::new();
+
+    let add_stake_events = filter_events(
+        events,
+        delegation_pool.add_stake_events.key(),
+        |event_key, event| {
+            if let Ok(event) = bcs::from_bytes::<AddDelegatedStakeEvent>(event.event_data()) {
+                Some(event)
+            } else {
+                warn!(
+                    "Failed to parse add delegated stake event!  Skipping for {}:{}",
+                    event_key.get_creator_address(),
+                    event_key.get_creation_number()
+                );
+                None
+            }
+        },
+    );
+    for event in add_stake_events {
+        delegators.insert(event.delegator_address);
+        operations.push(Operation::add_delegated_stake(
+            operation_index,
+            Some(OperationStatusType::Success),
+            event.delegator_address,
+            pool_account.clone(),
+            Some(event.amount_added),
+        ));
+        operation_index += 1;
+    }
+
+    let unlock_stake_events = filter_events(
+        events,
+        delegation_pool.unlock_stake_events.key(),
+        |event_key, event| {
+            if let Ok(event) = bcs::from_bytes::<UnlockDelegatedStakeEvent>(event.event_data()) {
+                Some(event)
+            } else {
+                warn!(
+                    "Failed to parse unlock delegated stake event!  Skipping for {}:{}",
+                    event_key.get_creator_address(),
+                    event_key.get_creation_number()
+                );
+                None
+            }
+        },
+    );
+    for event in unlock_stake_events {
+        delegators.insert(event.delegator_address);
+        operations.push(Operation::unlock_delegated_stake(
+            operation_index,
+            Some(OperationStatusType::Success),
+            event.delegator_address,
+            pool_account.clone(),
+            Some(event.amount_unlocked),
+        ));
+        operation_index += 1;
+    }
+
+    let withdraw_stake_events = filter_events(
+        events,
+        delegation_pool.withdraw_stake_events.key(),
+        |event_key, event| {
+            if let Ok(event) = bcs::from_bytes::<WithdrawDelegatedStakeEvent>(event.event_data()) {
+                Some(event)
+            } else {
+                warn!(
+                    "Failed to parse withdraw delegated stake event!  Skipping for {}:{}",
+                    event_key.get_creator_address(),
+                    event_key.get_creation_number()
+                );
+                None
+            }
+        },
+    );
+    for event in withdraw_stake_events {
+        delegators.insert(event.delegator_address);
+        operations.push(Operation::withdraw_undelegated_funds(
+            operation_index,
+            Some(OperationStatusType::Success),
+            event.delegator_address,
+            pool_account.clone(),
+            Some(event.amount_withdrawn),
+        ));
+        operation_index += 1;
+    }
+
+    // Reactivating can't be constructed through Rosetta, but it still moves the stake
+    let reactivate_stake_events = filter_events(
+        events,
+        delegation_pool.reactivate_stake_events.key(),
+        |event_key, event| {
+            if let Ok(event) = bcs::from_bytes::<ReactivateDelegatedStakeEvent>(event.event_data())
+            {
+                Some(event)
+            } else {
+                warn!(
+                    "Failed to parse reactivate delegated stake event!  Skipping for {}:{}",
+                    event_key.get_creator_address(),
+                    event_key.get_creation_number()
+                );
+                None
+            }
+        },
+    );
+    for event in reactivate_stake_events {
+        delegators.insert(event.delegator_address);
+    }
+
+    if delegators.is_empty() {
+        return Ok(operations);
+    }
+    let rest_client = server_context.rest_client()?;
+    for delegator in delegators {
+        let (active_before, inactive_before, pending_inactive_before) = get_delegated_stake(
+            &rest_client,
+            delegator,
+            pool_address,
+            version.saturating_sub(1),
+        )
+        .await?
+        .unwrap_or_default();
+        let (active_after, inactive_after, pending_inactive_after) =
+            get_delegated_stake(&rest_client, delegator, pool_address, version)
+                .await?
+                .unwrap_or_default();
+
+        for (account, before, after) in [
+            (
+                AccountIdentifier::delegated_active_stake_account(delegator, pool_address),
+                active_before,
+                active_after,
+            ),
+            (
+                AccountIdentifier::delegated_pending_inactive_stake_account(
+                    delegator,
+                    pool_address,
+                ),
+                pending_inactive_before,
+                pending_inactive_after,
+            ),
+            (
+                AccountIdentifier::delegated_inactive_stake_account(delegator, pool_address),
+                inactive_before,
+                inactive_after,
+            ),
+        ] {
+            match after.cmp(&before) {
+                Ordering::Greater => operations.push(Operation::deposit(
+                    operation_index,
+                    Some(OperationStatusType::Success),
+                    account,
+                    native_coin(),
+                    after - before,
+                )),
+                Ordering::Less => operations.push(Operation::withdraw(
+                    operation_index,
+                    Some(OperationStatusType::Success),
+                    account,
+                    native_coin(),
+                    before - after,
+                )),
+                Ordering::Equal => continue,
+            }
+            operation_index += 1;
+        }
+    }
+
+    Ok(operations)
+}
+
 async fn parse_coinstore_changes(
     currency: Currency,
     version: u64,
@@ -1424,6 +1772,101 @@ async fn parse_coinstore_changes(
     Ok(operations)
 }
 
+/// Parses the balance changes of a fungible store, attributed to the owner of the store
+///
+/// Only primary stores of configured currencies are tracked, matching the account balances.  The
+/// store is in an object resource group, along with its owner and event handles.
+fn parse_fungible_store_changes(
+    currencies: &HashSet<Currency>,
+    version: u64,
+    address: AccountAddress,
+    data: &[u8],
+    events: &[ContractEvent],
+    mut operation_index: u64,
+) -> ApiResult<Vec<Operation>> {
+    let mut operations = vec![];
+    let group: BTreeMap<StructTag, Vec<u8>> = if let Ok(group) = bcs::from_bytes(data) {
+        group
+    } else {
+        warn!(
+            "Object group failed to parse for address {} at version {}",
+            address, version
+        );
+        return Ok(operations);
+    };
+
+    let mut maybe_object_core = None;
+    let mut maybe_fungible_store = None;
+    let mut maybe_fungible_asset_events = None;
+    for (struct_tag, bytes) in group.iter() {
+        match (
+            struct_tag.address,
+            struct_tag.module.as_str(),
+            struct_tag.name.as_str(),
+        ) {
+            (AccountAddress::ONE, OBJECT_MODULE, OBJECT_CORE_RESOURCE) => {
+                maybe_object_core = bcs::from_bytes::<ObjectCore>(bytes).ok();
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FUNGIBLE_STORE_RESOURCE) => {
+                maybe_fungible_store = bcs::from_bytes::<FungibleStore>(bytes).ok();
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FUNGIBLE_ASSET_EVENTS_RESOURCE) => {
+                maybe_fungible_asset_events = bcs::from_bytes::<FungibleAssetEvents>(bytes).ok();
+            },
+            _ => {},
+        }
+    }
+
+    // Objects that aren't fungible stores have nothing to parse
+    let (object_core, fungible_store, fungible_asset_events) = match (
+        maybe_object_core,
+        maybe_fungible_store,
+        maybe_fungible_asset_events,
+    ) {
+        (Some(object_core), Some(fungible_store), Some(fungible_asset_events)) => {
+            (object_core, fungible_store, fungible_asset_events)
+        },
+        _ => return Ok(operations),
+    };
+
+    let currency = if let Some(currency) = find_fa_currency(currencies, fungible_store.metadata) {
+        currency
+    } else {
+        return Ok(operations);
+    };
+    if create_derived_object_address(object_core.owner, fungible_store.metadata) != address {
+        return Ok(operations);
+    }
+
+    // Fungible asset withdraw and deposit events have the same layout as the coin ones
+    let withdraw_amounts =
+        get_amount_from_event(events, fungible_asset_events.withdraw_events.key());
+    for amount in withdraw_amounts {
+        operations.push(Operation::withdraw(
+            operation_index,
+            Some(OperationStatusType::Success),
+            AccountIdentifier::base_account(object_core.owner),
+            currency.clone(),
+            amount,
+        ));
+        operation_index += 1;
+    }
+
+    let deposit_amounts = get_amount_from_event(events, fungible_asset_events.deposit_events.key());
+    for amount in deposit_amounts {
+        operations.push(Operation::deposit(
+            operation_index,
+            Some(OperationStatusType::Success),
+            AccountIdentifier::base_account(object_core.owner),
+            currency.clone(),
+            amount,
+        ));
+        operation_index += 1;
+    }
+
+    Ok(operations)
+}
+
 /// Pulls the balance change from a withdraw or deposit event
 fn get_amount_from_event(events: &[ContractEvent], event_key: &EventKey) -> Vec<u64> {
     filter_events(events, event_key, |event_key, event| {
@@ -1475,6 +1918,9 @@ pub enum InternalOperation {
     ResetLockup(ResetLockup),
     UnlockStake(UnlockStake),
     DistributeStakingRewards(DistributeStakingRewards),
+    AddDelegatedStake(AddDelegatedStake),
+    UnlockDelegatedStake(UnlockDelegatedStake),
+    WithdrawUndelegatedFunds(WithdrawUndelegatedFunds),
 }
 
 impl InternalOperation {
@@ -1638,6 +2084,61 @@ impl InternalOperation {
                                 ));
                             }
                         },
+                        Ok(
+                            operation_type @ (OperationType::AddDelegatedStake
+                            | OperationType::UnlockDelegatedStake
+                            | OperationType::WithdrawUndelegatedFunds),
+                        ) => {
+                            if let (
+                                Some(OperationMetadata {
+                                    pool_address,
+                                    amount,
+                                    ..
+                                }),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                let pool_address = if let Some(pool_address) = pool_address {
+                                    pool_address.account_address()?
+                                } else {
+                                    return Err(ApiError::InvalidInput(Some(format!(
+                                        "{} missing pool_address field",
+                                        operation_type
+                                    ))));
+                                };
+                                let amount = if let Some(amount) = amount {
+                                    u64::from(*amount)
+                                } else {
+                                    return Err(ApiError::InvalidInput(Some(format!(
+                                        "{} missing amount field",
+                                        operation_type
+                                    ))));
+                                };
+                                let delegator = account.account_address()?;
+
+                                return Ok(match operation_type {
+                                    OperationType::AddDelegatedStake => {
+                                        Self::AddDelegatedStake(AddDelegatedStake {
+                                            delegator,
+                                            pool_address,
+                                            amount,
+                                        })
+                                    },
+                                    OperationType::UnlockDelegatedStake => {
+                                        Self::UnlockDelegatedStake(UnlockDelegatedStake {
+                                            delegator,
+                                            pool_address,
+                                            amount,
+                                        })
+                                    },
+                                    _ => Self::WithdrawUndelegatedFunds(WithdrawUndelegatedFunds {
+                                        delegator,
+                                        pool_address,
+                                        amount,
+                                    }),
+                                });
+                            }
+                        },
                         _ => {},
                     }
                 }
@@ -1667,6 +2168,9 @@ impl InternalOperation {
             Self::ResetLockup(inner) => inner.owner,
             Self::UnlockStake(inner) => inner.owner,
             Self::DistributeStakingRewards(inner) => inner.sender,
+            Self::AddDelegatedStake(inner) => inner.delegator,
+            Self::UnlockDelegatedStake(inner) => inner.delegator,
+            Self::WithdrawUndelegatedFunds(inner) => inner.delegator,
         }
     }
 
@@ -1741,6 +2245,27 @@ impl InternalOperation {
                 ),
                 distribute_staking_rewards.sender,
             ),
+            InternalOperation::AddDelegatedStake(add_delegated_stake) => (
+                aptos_stdlib::delegation_pool_add_stake(
+                    add_delegated_stake.pool_address,
+                    add_delegated_stake.amount,
+                ),
+                add_delegated_stake.delegator,
+            ),
+            InternalOperation::UnlockDelegatedStake(unlock_delegated_stake) => (
+                aptos_stdlib::delegation_pool_unlock(
+                    unlock_delegated_stake.pool_address,
+                    unlock_delegated_stake.amount,
+                ),
+                unlock_delegated_stake.delegator,
+            ),
+            InternalOperation::WithdrawUndelegatedFunds(withdraw_undelegated_funds) => (
+                aptos_stdlib::delegation_pool_withdraw(
+                    withdraw_undelegated_funds.pool_address,
+                    withdraw_undelegated_funds.amount,
+                ),
+                withdraw_undelegated_funds.delegator,
+            ),
         })
     }
 }
@@ -1913,3 +2438,183 @@ pub struct DistributeStakingRewards {
     pub operator: AccountAddress,
     pub staker: AccountAddress,
 }
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AddDelegatedStake {
+    pub delegator: AccountAddress,
+    pub pool_address: AccountAddress,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UnlockDelegatedStake {
+    pub delegator: AccountAddress,
+    pub pool_address: AccountAddress,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WithdrawUndelegatedFunds {
+    pub delegator: AccountAddress,
+    pub pool_address: AccountAddress,
+    pub amount: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_types::event::EventHandle;
+    use move_core_types::{identifier::Identifier, language_storage::TypeTag};
+
+    fn struct_tag(module: &str, name: &str) -> StructTag {
+        StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    fn fa_currency(fa_address: &str) -> Currency {
+        Currency {
+            symbol: "FA".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some(fa_address.to_string()),
+            }),
+        }
+    }
+
+    /// Builds the object group of a primary fungible store, and a withdraw and deposit event on it
+    fn fungible_store(
+        owner: AccountAddress,
+        metadata: AccountAddress,
+    ) -> (AccountAddress, Vec<u8>, Vec<ContractEvent>) {
+        let store_address = create_derived_object_address(owner, metadata);
+        let withdraw_key = EventKey::new(1, store_address);
+        let deposit_key = EventKey::new(2, store_address);
+
+        let mut group = BTreeMap::new();
+        group.insert(
+            struct_tag(OBJECT_MODULE, OBJECT_CORE_RESOURCE),
+            bcs::to_bytes(&ObjectCore {
+                guid_creation_num: 3,
+                owner,
+                allow_ungated_transfer: false,
+                transfer_events: EventHandle::new(EventKey::new(0, store_address), 0),
+            })
+            .unwrap(),
+        );
+        group.insert(
+            struct_tag(FUNGIBLE_ASSET_MODULE, FUNGIBLE_STORE_RESOURCE),
+            bcs::to_bytes(&FungibleStore {
+                metadata,
+                balance: 70,
+                allow_ungated_balance_transfer: true,
+            })
+            .unwrap(),
+        );
+        group.insert(
+            struct_tag(FUNGIBLE_ASSET_MODULE, FUNGIBLE_ASSET_EVENTS_RESOURCE),
+            bcs::to_bytes(&FungibleAssetEvents {
+                deposit_events: EventHandle::new(deposit_key, 1),
+                withdraw_events: EventHandle::new(withdraw_key, 1),
+                set_ungated_transfer_events: EventHandle::new(EventKey::new(4, store_address), 0),
+            })
+            .unwrap(),
+        );
+
+        // Withdraw and deposit events are just the amount
+        let events = vec![
+            ContractEvent::new(
+                withdraw_key,
+                0,
+                TypeTag::U64,
+                bcs::to_bytes(&30u64).unwrap(),
+            ),
+            ContractEvent::new(
+                deposit_key,
+                0,
+                TypeTag::U64,
+                bcs::to_bytes(&100u64).unwrap(),
+            ),
+        ];
+        (store_address, bcs::to_bytes(&group).unwrap(), events)
+    }
+
+    #[test]
+    fn test_parse_fungible_store_changes() {
+        let owner = AccountAddress::from_hex_literal("0x1234").unwrap();
+        let metadata = AccountAddress::from_hex_literal("0xa").unwrap();
+        let (store_address, data, events) = fungible_store(owner, metadata);
+
+        // The configured address doesn't have to be in the same form as the one on chain
+        let currency = fa_currency(&format!("0x{}", metadata.to_hex()));
+        let currencies: HashSet<Currency> =
+            vec![native_coin(), currency.clone()].into_iter().collect();
+        let operations =
+            parse_fungible_store_changes(&currencies, 1, store_address, &data, &events, 5).unwrap();
+        assert_eq!(
+            operations,
+            vec![
+                Operation::withdraw(
+                    5,
+                    Some(OperationStatusType::Success),
+                    AccountIdentifier::base_account(owner),
+                    currency.clone(),
+                    30,
+                ),
+                Operation::deposit(
+                    6,
+                    Some(OperationStatusType::Success),
+                    AccountIdentifier::base_account(owner),
+                    currency,
+                    100,
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_fungible_store_changes_skipped() {
+        let owner = AccountAddress::from_hex_literal("0x1234").unwrap();
+        let metadata = AccountAddress::from_hex_literal("0xa").unwrap();
+        let (store_address, data, events) = fungible_store(owner, metadata);
+        let currencies: HashSet<Currency> = vec![native_coin(), fa_currency("0xa")]
+            .into_iter()
+            .collect();
+
+        // Assets that aren't configured aren't tracked
+        let unconfigured: HashSet<Currency> = vec![native_coin(), fa_currency("0xb")]
+            .into_iter()
+            .collect();
+        assert!(
+            parse_fungible_store_changes(&unconfigured, 1, store_address, &data, &events, 0)
+                .unwrap()
+                .is_empty()
+        );
+
+        // Only primary stores are tracked, since they're the only ones in the owner's balance
+        assert!(parse_fungible_store_changes(
+            &currencies,
+            1,
+            AccountAddress::ONE,
+            &data,
+            &events,
+            0
+        )
+        .unwrap()
+        .is_empty());
+
+        // Objects that aren't fungible stores are ignored
+        let mut group: BTreeMap<StructTag, Vec<u8>> = bcs::from_bytes(&data).unwrap();
+        group.remove(&struct_tag(FUNGIBLE_ASSET_MODULE, FUNGIBLE_STORE_RESOURCE));
+        let data = bcs::to_bytes(&group).unwrap();
+        assert!(
+            parse_fungible_store_changes(&currencies, 1, store_address, &data, &events, 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
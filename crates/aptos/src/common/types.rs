@@ -97,6 +97,10 @@ pub enum CliError {
     SimulationError(String),
     #[error("Coverage failed with status: {0}")]
     CoverageError(String),
+    #[error("Package upgrade check failed: {0}")]
+    UpgradeCheckError(String),
+    #[error("Gas usage regressed: {0}")]
+    GasRegressionError(String),
 }
 
 impl CliError {
@@ -117,6 +121,8 @@ impl CliError {
             CliError::UnexpectedError(_) => "UnexpectedError",
             CliError::SimulationError(_) => "SimulationError",
             CliError::CoverageError(_) => "CoverageError",
+            CliError::UpgradeCheckError(_) => "UpgradeCheckError",
+            CliError::GasRegressionError(_) => "GasRegressionError",
         }
     }
 }
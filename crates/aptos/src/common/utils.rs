@@ -414,9 +414,13 @@ pub async fn fund_account(
             .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
         Ok(hashes)
     } else {
+        // The faucet explains why the request was rejected in the body, e.g.
+        // which quota was exhausted, so pass that on to the user.
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
         Err(CliError::ApiError(format!(
-            "Faucet issue: {}",
-            response.status()
+            "Faucet issue: {} {}",
+            status, body
         )))
     }
 }
@@ -0,0 +1,106 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports the gas used by Move unit tests under the Aptos gas schedule, and compares it against
+//! a baseline file so that gas regressions are caught before publishing.
+
+use crate::common::{
+    types::{CliError, CliTypedResult},
+    utils::{read_from_file, write_to_file},
+};
+use aptos_vm::unit_test_gas::TestGasUsage;
+use std::{collections::BTreeMap, path::Path};
+
+/// Gas used by each test, keyed by `<address>::<module>::<function>`.
+pub type GasReport = BTreeMap<String, TestGasUsage>;
+
+pub fn print_gas_report(report: &GasReport) {
+    println!("Gas report (execution and IO in gas units, storage fee in Octas):");
+    for (test, usage) in report {
+        println!(
+            "  {}: execution {}, IO {}, storage fee {}{}",
+            test,
+            usage.execution_gas,
+            usage.io_gas,
+            usage.storage_fee,
+            if usage.completed {
+                ""
+            } else {
+                " (did not complete)"
+            }
+        );
+    }
+}
+
+pub fn read_gas_baseline(path: &Path) -> CliTypedResult<GasReport> {
+    serde_json::from_slice(&read_from_file(path)?)
+        .map_err(|err| CliError::UnableToParse("gas baseline", err.to_string()))
+}
+
+pub fn write_gas_baseline(path: &Path, report: &GasReport) -> CliTypedResult<()> {
+    let bytes = serde_json::to_vec_pretty(report)
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+    write_to_file(path, "gas baseline", &bytes)
+}
+
+/// Returns a description of every metric of a test that went up compared to the baseline.
+/// Tests missing from either report are not compared.
+pub fn find_gas_regressions(baseline: &GasReport, report: &GasReport) -> Vec<String> {
+    let mut regressions = vec![];
+    for (test, usage) in report {
+        let old = match baseline.get(test) {
+            Some(old) => old,
+            None => continue,
+        };
+        for (metric, old_value, new_value) in [
+            ("execution gas", old.execution_gas, usage.execution_gas),
+            ("IO gas", old.io_gas, usage.io_gas),
+            ("storage fee", old.storage_fee, usage.storage_fee),
+        ] {
+            if new_value > old_value {
+                regressions.push(format!(
+                    "{}: {} went from {} to {}",
+                    test, metric, old_value, new_value
+                ));
+            }
+        }
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(execution_gas: u64, io_gas: u64, storage_fee: u64) -> TestGasUsage {
+        TestGasUsage {
+            execution_gas,
+            io_gas,
+            storage_fee,
+            completed: true,
+        }
+    }
+
+    #[test]
+    fn test_find_gas_regressions() {
+        let baseline: GasReport = [
+            ("0x1::m::a".to_string(), usage(10, 10, 10)),
+            ("0x1::m::b".to_string(), usage(10, 10, 10)),
+            ("0x1::m::removed".to_string(), usage(10, 10, 10)),
+        ]
+        .into_iter()
+        .collect();
+        let report: GasReport = [
+            ("0x1::m::a".to_string(), usage(5, 10, 10)),
+            ("0x1::m::b".to_string(), usage(11, 10, 20)),
+            ("0x1::m::added".to_string(), usage(100, 100, 100)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(find_gas_regressions(&baseline, &report), vec![
+            "0x1::m::b: execution gas went from 10 to 11".to_string(),
+            "0x1::m::b: storage fee went from 10 to 20".to_string(),
+        ]);
+    }
+}
@@ -3,11 +3,13 @@
 
 mod aptos_debug_natives;
 pub mod coverage;
+mod gas_report;
 mod manifest;
 pub mod package_hooks;
 mod show;
 pub mod stored_package;
 mod transactional_tests_runner;
+pub mod upgrade_check;
 
 use crate::{
     account::derive_resource_account::ResourceAccountSeed,
@@ -34,13 +36,17 @@ use aptos_framework::{
     build_model, docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy,
     prover::ProverOptions, BuildOptions, BuiltPackage,
 };
-use aptos_gas::{AbstractValueSizeGasParameters, NativeGasParameters};
+use aptos_gas::{
+    AbstractValueSizeGasParameters, AptosGasParameters, InitialGasSchedule, NativeGasParameters,
+    LATEST_GAS_FEATURE_VERSION,
+};
 use aptos_rest_client::aptos_api_types::{EntryFunctionId, MoveType, ViewRequest};
 use aptos_transactional_test_harness::run_aptos_test;
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
     transaction::{EntryFunction, Script, TransactionArgument, TransactionPayload},
 };
+use aptos_vm::unit_test_gas::UnitTestGasProfiler;
 use async_trait::async_trait;
 use clap::{ArgEnum, Parser, Subcommand};
 use codespan_reporting::{
@@ -78,6 +84,7 @@ use transactional_tests_runner::TransactionalTestOpts;
 /// about this code.
 #[derive(Subcommand)]
 pub enum MoveTool {
+    CheckUpgrade(upgrade_check::CheckUpgrade),
     Clean(CleanPackage),
     Compile(CompilePackage),
     CompileScript(CompileScript),
@@ -103,6 +110,7 @@ pub enum MoveTool {
 impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            MoveTool::CheckUpgrade(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
             MoveTool::CompileScript(tool) => tool.execute_serialized().await,
@@ -421,6 +429,23 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Report the gas used by each test under the Aptos gas schedule
+    ///
+    /// Execution and IO gas are reported in gas units, storage fees in Octas.  Tests are
+    /// executed a second time with the real gas meter, so this makes testing slower.
+    #[clap(long)]
+    pub gas_report: bool,
+
+    /// Compare the gas used by each test against a baseline file, and fail if any test uses more
+    ///
+    /// Implies `--gas-report`.  If the file doesn't exist yet, it is created from this run.
+    #[clap(long, parse(from_os_str))]
+    pub gas_baseline: Option<PathBuf>,
+
+    /// Overwrite the `--gas-baseline` file with the gas used by this run instead of comparing
+    #[clap(long, requires = "gas-baseline")]
+    pub update_gas_baseline: bool,
 }
 
 #[async_trait]
@@ -454,6 +479,14 @@ impl CliCommand<&'static str> for TestPackage {
                 ));
             }
         }
+        let gas_profiler = if self.gas_report || self.gas_baseline.is_some() {
+            Some(UnitTestGasProfiler::install(UnitTestGasProfiler::new(
+                LATEST_GAS_FEATURE_VERSION,
+                AptosGasParameters::initial(),
+            )))
+        } else {
+            None
+        };
         let path = self.move_options.get_package_path()?;
         let result = move_cli::base::test::run_move_unit_tests(
             path.as_path(),
@@ -490,10 +523,28 @@ impl CliCommand<&'static str> for TestPackage {
             println!("Please use `aptos move coverage -h` for more detailed source or bytecode test coverage of this package");
         }
 
-        match result {
-            UnitTestResult::Success => Ok("Success"),
-            UnitTestResult::Failure => Err(CliError::MoveTestError),
+        if result == UnitTestResult::Failure {
+            return Err(CliError::MoveTestError);
         }
+
+        if let Some(gas_profiler) = gas_profiler {
+            let report = gas_profiler.usages();
+            gas_report::print_gas_report(&report);
+
+            if let Some(baseline_path) = &self.gas_baseline {
+                if self.update_gas_baseline || !baseline_path.exists() {
+                    gas_report::write_gas_baseline(baseline_path, &report)?;
+                } else {
+                    let baseline = gas_report::read_gas_baseline(baseline_path)?;
+                    let regressions = gas_report::find_gas_regressions(&baseline, &report);
+                    if !regressions.is_empty() {
+                        return Err(CliError::GasRegressionError(regressions.join("; ")));
+                    }
+                }
+            }
+        }
+
+        Ok("Success")
     }
 }
 
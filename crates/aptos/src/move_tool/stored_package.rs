@@ -6,10 +6,10 @@ use aptos_framework::{
     natives::code::{ModuleMetadata, PackageMetadata, PackageRegistry, UpgradePolicy},
     unzip_metadata_str,
 };
-use aptos_rest_client::Client;
+use aptos_rest_client::{error::RestError, Client};
 use aptos_types::account_address::AccountAddress;
 use move_package::compilation::package_layout::CompiledPackageLayout;
-use reqwest::Url;
+use reqwest::{StatusCode, Url};
 use std::{fs, path::Path};
 
 // TODO: this is a first naive implementation of the package registry. Before mainnet
@@ -42,6 +42,22 @@ impl CachedPackageRegistry {
         Ok(Self { inner })
     }
 
+    /// Creates a new registry, or returns `None` if no package was ever published under the
+    /// account.
+    pub async fn create_if_exists(url: Url, addr: AccountAddress) -> anyhow::Result<Option<Self>> {
+        let client = Client::new(url);
+        match client
+            .get_account_resource_bcs::<PackageRegistry>(addr, "0x1::code::PackageRegistry")
+            .await
+        {
+            Ok(response) => Ok(Some(Self {
+                inner: response.into_inner(),
+            })),
+            Err(RestError::Api(error)) if error.status_code == StatusCode::NOT_FOUND => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
     /// Returns the list of packages in this registry by name.
     pub fn package_names(&self) -> Vec<&str> {
         self.inner
@@ -73,16 +89,28 @@ impl CachedPackageRegistry {
         name: impl AsRef<str>,
     ) -> anyhow::Result<CachedPackageMetadata<'_>> {
         let name = name.as_ref();
-        for package in &self.inner.packages {
-            if package.name == name {
-                return Ok(CachedPackageMetadata { metadata: package });
-            }
+        match self.find_package(name) {
+            Some(package) => Ok(package),
+            None => bail!("package `{}` not found", name),
         }
-        bail!("package `{}` not found", name)
+    }
+
+    /// Finds the metadata for the given package in the registry, if the package exists.
+    pub fn find_package(&self, name: impl AsRef<str>) -> Option<CachedPackageMetadata<'_>> {
+        let name = name.as_ref();
+        self.inner
+            .packages
+            .iter()
+            .find(|package| package.name == name)
+            .map(|metadata| CachedPackageMetadata { metadata })
     }
 }
 
 impl<'a> CachedPackageMetadata<'a> {
+    pub fn metadata(&self) -> &PackageMetadata {
+        self.metadata
+    }
+
     pub fn name(&self) -> &str {
         &self.metadata.name
     }
@@ -0,0 +1,827 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CachedPackageRegistry, IncludedArtifacts};
+use crate::common::types::{
+    CliCommand, CliError, CliTypedResult, MovePackageDir, ProfileOptions, RestOptions,
+};
+use aptos_framework::{
+    get_metadata_from_compiled_module,
+    natives::code::{PackageMetadata, UpgradePolicy},
+    BuildOptions, BuiltPackage, ResourceGroupScope,
+};
+use aptos_rest_client::Client;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::{FeatureFlag, Features},
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::{
+    compatibility::Compatibility,
+    file_format::{StructTypeParameter, Visibility},
+    file_format_common::VERSION_5,
+    normalized, CompiledModule,
+};
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Checks that the local package can be published as an upgrade of the package on chain
+///
+/// Downloads the package currently published under the account, compiles the local package, and
+/// reports every change that would make the publish transaction fail (e.g. incompatible struct
+/// layouts, removed or changed public functions and friend changes), as well as the differences
+/// in the package metadata. All checks are run locally once the package is downloaded.
+#[derive(Parser)]
+pub struct CheckUpgrade {
+    /// Address of the account containing the published package
+    #[clap(long, parse(try_from_str = crate::common::types::load_account_arg))]
+    pub(crate) account: AccountAddress,
+
+    /// Artifacts to be generated when building this package.
+    #[clap(long, default_value_t = IncludedArtifacts::Sparse)]
+    pub(crate) included_artifacts: IncludedArtifacts,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The changes between the published package and the local package.
+#[derive(Debug, Default, Serialize)]
+pub struct UpgradeCheckReport {
+    pub package: String,
+    /// Changes that make the upgrade fail on chain.
+    pub incompatibilities: Vec<String>,
+    /// Changes that are allowed, but worth reviewing before upgrading.
+    pub notes: Vec<String>,
+}
+
+impl UpgradeCheckReport {
+    pub fn is_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+
+    fn incompatible(&mut self, message: String) {
+        self.incompatibilities.push(message)
+    }
+
+    fn note(&mut self, message: String) {
+        self.notes.push(message)
+    }
+}
+
+#[async_trait]
+impl CliCommand<UpgradeCheckReport> for CheckUpgrade {
+    fn command_name(&self) -> &'static str {
+        "CheckUpgrade"
+    }
+
+    async fn execute(self) -> CliTypedResult<UpgradeCheckReport> {
+        // First build the package locally to get the new code and package metadata
+        let build_options = BuildOptions {
+            install_dir: self.move_options.output_dir.clone(),
+            bytecode_version: self.move_options.bytecode_version,
+            ..self.included_artifacts.build_options(
+                self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.named_addresses(),
+                self.move_options.bytecode_version,
+            )
+        };
+        let pack = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let new_metadata = pack.extract_metadata()?;
+        let new_modules: Vec<CompiledModule> = pack.modules().cloned().collect();
+
+        // Now download everything needed for the checks
+        let url = self.rest_options.url(&self.profile_options)?;
+        let client = Client::new(url.clone());
+        let registry = match CachedPackageRegistry::create_if_exists(url, self.account).await? {
+            Some(registry) => registry,
+            None => return Ok(first_publish_report(pack.name(), self.account)),
+        };
+        let package = match registry.find_package(pack.name()) {
+            Some(package) => package,
+            None => return Ok(first_publish_report(pack.name(), self.account)),
+        };
+
+        let mut old_modules = vec![];
+        for name in package.module_names() {
+            let bytes = client
+                .get_account_module_bcs(self.account, name)
+                .await?
+                .into_inner();
+            let module = CompiledModule::deserialize(&bytes).map_err(|e| {
+                CliError::UnexpectedError(format!(
+                    "Failed to deserialize published module `{}`: {}",
+                    name, e
+                ))
+            })?;
+            old_modules.push(module);
+        }
+
+        let mut other_package_modules = BTreeMap::new();
+        for name in registry.package_names() {
+            if name != package.name() {
+                let other = registry.get_package(name).await?;
+                for module in other.module_names() {
+                    other_package_modules.insert(module.to_string(), name.to_string());
+                }
+            }
+        }
+
+        let features = client
+            .get_account_resource_bcs::<Features>(CORE_CODE_ADDRESS, "0x1::features::Features")
+            .await?
+            .into_inner();
+
+        let report = check_package_upgrade(
+            package.metadata(),
+            &old_modules,
+            &other_package_modules,
+            &new_metadata,
+            &new_modules,
+            !features.is_enabled(FeatureFlag::TREAT_FRIEND_AS_PRIVATE),
+        );
+
+        for note in &report.notes {
+            eprintln!("Note: {}", note);
+        }
+        if report.is_compatible() {
+            Ok(report)
+        } else {
+            for incompatibility in &report.incompatibilities {
+                eprintln!("Incompatible: {}", incompatibility);
+            }
+            Err(CliError::UpgradeCheckError(format!(
+                "found {} incompatible change(s) in package `{}`",
+                report.incompatibilities.len(),
+                report.package
+            )))
+        }
+    }
+}
+
+fn first_publish_report(package: &str, account: AccountAddress) -> UpgradeCheckReport {
+    let mut report = UpgradeCheckReport {
+        package: package.to_string(),
+        ..Default::default()
+    };
+    report.note(format!(
+        "Package `{}` is not published under {}, it will be published for the first time",
+        package, account
+    ));
+    report
+}
+
+/// Checks whether the new package can replace the old one, following the checks done on chain
+/// by `code::publish_package` and by the VM when publishing the modules.
+/// `other_package_modules` maps the modules of the other packages under the same account to their
+/// package.
+pub fn check_package_upgrade(
+    old_metadata: &PackageMetadata,
+    old_modules: &[CompiledModule],
+    other_package_modules: &BTreeMap<String, String>,
+    new_metadata: &PackageMetadata,
+    new_modules: &[CompiledModule],
+    check_friend_linking: bool,
+) -> UpgradeCheckReport {
+    let mut report = UpgradeCheckReport {
+        package: new_metadata.name.clone(),
+        ..Default::default()
+    };
+
+    check_metadata(
+        old_metadata,
+        other_package_modules,
+        new_metadata,
+        &mut report,
+    );
+
+    let new_modules: BTreeMap<_, _> = new_modules
+        .iter()
+        .map(|module| (module.self_id().name().to_string(), module))
+        .collect();
+    for old_module in old_modules {
+        let name = old_module.self_id().name().to_string();
+        // Missing modules are reported by the metadata checks.
+        if let Some(new_module) = new_modules.get(&name) {
+            check_module(old_module, new_module, check_friend_linking, &mut report);
+        }
+    }
+
+    report
+}
+
+fn check_metadata(
+    old: &PackageMetadata,
+    other_package_modules: &BTreeMap<String, String>,
+    new: &PackageMetadata,
+    report: &mut UpgradeCheckReport,
+) {
+    if old.upgrade_policy.policy >= UpgradePolicy::immutable().policy {
+        report.incompatible(format!(
+            "the published package has upgrade policy `{}` and cannot be upgraded",
+            old.upgrade_policy
+        ));
+    }
+    if new.upgrade_policy.policy == UpgradePolicy::arbitrary().policy {
+        report.incompatible(format!(
+            "upgrade policy `{}` is no longer supported for publishing",
+            new.upgrade_policy
+        ));
+    } else if new.upgrade_policy.policy < old.upgrade_policy.policy {
+        report.incompatible(format!(
+            "upgrade policy cannot be weakened from `{}` to `{}`",
+            old.upgrade_policy, new.upgrade_policy
+        ));
+    } else if new.upgrade_policy != old.upgrade_policy {
+        report.note(format!(
+            "upgrade policy changes from `{}` to `{}`",
+            old.upgrade_policy, new.upgrade_policy
+        ));
+    }
+
+    let old_names: BTreeSet<_> = old.modules.iter().map(|m| m.name.as_str()).collect();
+    let new_names: BTreeSet<_> = new.modules.iter().map(|m| m.name.as_str()).collect();
+    for removed in old_names.difference(&new_names) {
+        report.incompatible(format!("module `{}` is removed", removed));
+    }
+    for added in new_names.difference(&old_names) {
+        match other_package_modules.get(*added) {
+            Some(package) => report.incompatible(format!(
+                "module `{}` is added, but already exists in package `{}`",
+                added, package
+            )),
+            None => report.note(format!("module `{}` is added", added)),
+        }
+    }
+
+    let old_deps: BTreeSet<_> = old.deps.iter().collect();
+    let new_deps: BTreeSet<_> = new.deps.iter().collect();
+    for removed in old_deps.difference(&new_deps) {
+        report.note(format!(
+            "dependency on `{}::{}` is removed",
+            removed.account, removed.package_name
+        ));
+    }
+    for added in new_deps.difference(&old_deps) {
+        report.note(format!(
+            "dependency on `{}::{}` is added",
+            added.account, added.package_name
+        ));
+    }
+
+    if old.source_digest != new.source_digest {
+        report.note(format!(
+            "source digest changes from {} to {}",
+            old.source_digest, new.source_digest
+        ));
+    }
+    if old.manifest != new.manifest {
+        report.note("manifest (Move.toml) changes".to_string());
+    }
+    if old.extension != new.extension {
+        report.note("package extension changes".to_string());
+    }
+}
+
+/// Reports whether `new` passes the compatibility check done by the VM when publishing (see
+/// `move_binary_format::compatibility::Compatibility`), with the changes that make it fail, and
+/// every invalid change of the resource group metadata.
+fn check_module(
+    old: &CompiledModule,
+    new: &CompiledModule,
+    check_friend_linking: bool,
+    report: &mut UpgradeCheckReport,
+) {
+    let module_name = old.self_id().name().to_string();
+    let old_module = normalized::Module::new(old);
+    let new_module = normalized::Module::new(new);
+
+    // The VM has the final say, the changes found here only explain its verdict.
+    let mut changes = UpgradeCheckReport::default();
+    describe_module_changes(
+        &module_name,
+        &old_module,
+        &new_module,
+        check_friend_linking,
+        &mut changes,
+    );
+    report.notes.append(&mut changes.notes);
+    let verdict =
+        Compatibility::new(true, true, check_friend_linking).check(&old_module, &new_module);
+    if verdict.is_ok() {
+        for change in changes.incompatibilities {
+            report.note(format!("{} (accepted by the compatibility check)", change));
+        }
+    } else if changes.incompatibilities.is_empty() {
+        report.incompatible(format!(
+            "module `{}` fails the compatibility check with the published module",
+            module_name
+        ));
+    } else {
+        report
+            .incompatibilities
+            .append(&mut changes.incompatibilities);
+    }
+
+    check_resource_groups(&module_name, old, new, report);
+}
+
+/// Describes every change of `new_module` which breaks the compatibility rules enforced by the
+/// VM, following `Compatibility::check`.
+fn describe_module_changes(
+    module_name: &str,
+    old_module: &normalized::Module,
+    new_module: &normalized::Module,
+    check_friend_linking: bool,
+    report: &mut UpgradeCheckReport,
+) {
+    for (name, old_struct) in &old_module.structs {
+        let struct_name = format!("{}::{}", module_name, name);
+        let new_struct = match new_module.structs.get(name) {
+            Some(new_struct) => new_struct,
+            None => {
+                report.incompatible(format!("struct `{}` is removed", struct_name));
+                continue;
+            },
+        };
+        if !old_struct.abilities.is_subset(new_struct.abilities) {
+            report.incompatible(format!(
+                "struct `{}` abilities change from {:?} to {:?}, abilities cannot be removed",
+                struct_name, old_struct.abilities, new_struct.abilities
+            ));
+        }
+        if !struct_type_parameters_compatible(
+            &old_struct.type_parameters,
+            &new_struct.type_parameters,
+        ) {
+            report.incompatible(format!(
+                "struct `{}` type parameters change from {:?} to {:?}",
+                struct_name, old_struct.type_parameters, new_struct.type_parameters
+            ));
+        }
+        if old_struct.fields != new_struct.fields {
+            report.incompatible(format!(
+                "struct `{}` layout changes from {{ {} }} to {{ {} }}",
+                struct_name,
+                format_fields(&old_struct.fields),
+                format_fields(&new_struct.fields)
+            ));
+        }
+    }
+
+    for (name, old_func) in &old_module.exposed_functions {
+        let func_name = format!("{}::{}", module_name, name);
+        let mut changes = vec![];
+        match new_module.exposed_functions.get(name) {
+            None => changes.push("is removed".to_string()),
+            Some(new_func) => {
+                let is_vis_compatible = match (old_func.visibility, new_func.visibility) {
+                    (Visibility::Public, Visibility::Public) => true,
+                    (Visibility::Public, _) => false,
+                    (Visibility::Friend, Visibility::Public)
+                    | (Visibility::Friend, Visibility::Friend) => true,
+                    (Visibility::Friend, _) => false,
+                    (Visibility::Private, _) => true,
+                };
+                if !is_vis_compatible {
+                    changes.push(format!(
+                        "visibility changes from {} to {}",
+                        format_visibility(old_func.visibility),
+                        format_visibility(new_func.visibility)
+                    ));
+                }
+                let is_entry_compatible = if old_module.file_format_version < VERSION_5
+                    && new_module.file_format_version < VERSION_5
+                {
+                    old_func.is_entry == new_func.is_entry
+                } else {
+                    !old_func.is_entry || new_func.is_entry
+                };
+                if !is_entry_compatible {
+                    changes.push("is no longer an entry function".to_string());
+                }
+                if old_func.parameters != new_func.parameters {
+                    changes.push(format!(
+                        "parameters change from ({}) to ({})",
+                        format_types(&old_func.parameters),
+                        format_types(&new_func.parameters)
+                    ));
+                }
+                if old_func.return_ != new_func.return_ {
+                    changes.push(format!(
+                        "return types change from ({}) to ({})",
+                        format_types(&old_func.return_),
+                        format_types(&new_func.return_)
+                    ));
+                }
+                if old_func.type_parameters.len() != new_func.type_parameters.len()
+                    || old_func
+                        .type_parameters
+                        .iter()
+                        .zip(&new_func.type_parameters)
+                        .any(|(old, new)| !new.is_subset(*old))
+                {
+                    changes.push(format!(
+                        "type parameters change from {:?} to {:?}",
+                        old_func.type_parameters, new_func.type_parameters
+                    ));
+                }
+            },
+        }
+
+        for change in changes {
+            let message = format!(
+                "{} function `{}` {}",
+                format_visibility(old_func.visibility),
+                func_name,
+                change
+            );
+            if matches!(old_func.visibility, Visibility::Friend) && !check_friend_linking {
+                report.note(format!(
+                    "{} (allowed as friends are treated as private)",
+                    message
+                ));
+            } else {
+                report.incompatible(message);
+            }
+        }
+    }
+
+    let new_friends: BTreeSet<_> = new_module.friends.iter().collect();
+    for friend in &old_module.friends {
+        if !new_friends.contains(friend) {
+            let message = format!(
+                "friend declaration of `{}` is removed from module `{}`",
+                friend, module_name
+            );
+            if check_friend_linking {
+                report.incompatible(message);
+            } else {
+                report.note(format!(
+                    "{} (allowed as friends are treated as private)",
+                    message
+                ));
+            }
+        }
+    }
+}
+
+/// Resource group members cannot change their group, and resource groups cannot be removed or
+/// have a stricter scope.
+fn check_resource_groups(
+    module_name: &str,
+    old: &CompiledModule,
+    new: &CompiledModule,
+    report: &mut UpgradeCheckReport,
+) {
+    let (old_groups, old_members) = resource_group_metadata(old);
+    let (new_groups, new_members) = resource_group_metadata(new);
+
+    for (member, group) in old_members {
+        match new_members.get(&member) {
+            Some(new_group) if *new_group == group => (),
+            Some(new_group) => report.incompatible(format!(
+                "resource group member `{}::{}` moves from group `{}` to `{}`",
+                module_name, member, group, new_group
+            )),
+            None => report.incompatible(format!(
+                "struct `{}::{}` is no longer a member of resource group `{}`",
+                module_name, member, group
+            )),
+        }
+    }
+    for (group, scope) in old_groups {
+        match new_groups.get(&group) {
+            Some(new_scope) if !scope.is_less_strict(new_scope) => (),
+            Some(new_scope) => report.incompatible(format!(
+                "resource group `{}::{}` scope changes from {:?} to the stricter {:?}",
+                module_name, group, scope, new_scope
+            )),
+            None => report.incompatible(format!(
+                "struct `{}::{}` is no longer a resource group",
+                module_name, group
+            )),
+        }
+    }
+}
+
+fn resource_group_metadata(
+    module: &CompiledModule,
+) -> (
+    BTreeMap<String, ResourceGroupScope>,
+    BTreeMap<String, StructTag>,
+) {
+    let mut groups = BTreeMap::new();
+    let mut members = BTreeMap::new();
+    if let Some(metadata) = get_metadata_from_compiled_module(module) {
+        for (struct_, attrs) in metadata.struct_attributes {
+            for attr in attrs {
+                if let Some(scope) = attr.get_resource_group() {
+                    groups.insert(struct_.clone(), scope);
+                } else if let Some(group) = attr.get_resource_group_member() {
+                    members.insert(struct_.clone(), group);
+                }
+            }
+        }
+    }
+    (groups, members)
+}
+
+fn struct_type_parameters_compatible(
+    old_type_parameters: &[StructTypeParameter],
+    new_type_parameters: &[StructTypeParameter],
+) -> bool {
+    old_type_parameters.len() == new_type_parameters.len()
+        && old_type_parameters
+            .iter()
+            .zip(new_type_parameters)
+            .all(|(old, new)| {
+                // Phantom declarations cannot be removed, and constraints cannot be added
+                (!old.is_phantom || new.is_phantom) && new.constraints.is_subset(old.constraints)
+            })
+}
+
+fn format_fields(fields: &[normalized::Field]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.type_))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_types(types: &[normalized::Type]) -> String {
+    types
+        .iter()
+        .map(|type_| type_.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Friend => "friend",
+        Visibility::Private => "private",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_framework::natives::code::{ModuleMetadata, MoveOption, PackageDep};
+
+    fn package(policy: UpgradePolicy, modules: &[&str], deps: &[&str]) -> PackageMetadata {
+        PackageMetadata {
+            name: "test".to_string(),
+            upgrade_policy: policy,
+            upgrade_number: 0,
+            source_digest: "digest".to_string(),
+            manifest: vec![],
+            modules: modules
+                .iter()
+                .map(|name| ModuleMetadata {
+                    name: name.to_string(),
+                    source: vec![],
+                    source_map: vec![],
+                    extension: MoveOption::none(),
+                })
+                .collect(),
+            deps: deps
+                .iter()
+                .map(|name| PackageDep {
+                    account: CORE_CODE_ADDRESS,
+                    package_name: name.to_string(),
+                })
+                .collect(),
+            extension: MoveOption::none(),
+        }
+    }
+
+    fn check(old: &PackageMetadata, new: &PackageMetadata) -> UpgradeCheckReport {
+        let other_package_modules = BTreeMap::from([("clash".to_string(), "other".to_string())]);
+        check_package_upgrade(old, &[], &other_package_modules, new, &[], true)
+    }
+
+    /// Compiles the given sources and returns the module `0xcafe::m`.
+    fn compile_module(source: &str) -> CompiledModule {
+        let package_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            package_dir.path().join("Move.toml"),
+            "[package]\nname = \"test\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        let sources_dir = package_dir.path().join("sources");
+        std::fs::create_dir(&sources_dir).unwrap();
+        std::fs::write(sources_dir.join("m.move"), source).unwrap();
+
+        let pack =
+            BuiltPackage::build(package_dir.path().to_path_buf(), BuildOptions::default()).unwrap();
+        pack.modules()
+            .find(|module| module.self_id().name().as_str() == "m")
+            .cloned()
+            .unwrap()
+    }
+
+    /// Also checks that the changes found agree with the verdict of `Compatibility::check`, so
+    /// every fixture covers both.
+    fn check_modules(old: &str, new: &str, check_friend_linking: bool) -> UpgradeCheckReport {
+        let old = compile_module(old);
+        let new = compile_module(new);
+        assert_agrees_with_compatibility_check(&old, &new, check_friend_linking);
+
+        let mut report = UpgradeCheckReport::default();
+        check_module(&old, &new, check_friend_linking, &mut report);
+        report
+    }
+
+    fn assert_agrees_with_compatibility_check(
+        old: &CompiledModule,
+        new: &CompiledModule,
+        check_friend_linking: bool,
+    ) {
+        let old_module = normalized::Module::new(old);
+        let new_module = normalized::Module::new(new);
+        let mut changes = UpgradeCheckReport::default();
+        describe_module_changes(
+            "m",
+            &old_module,
+            &new_module,
+            check_friend_linking,
+            &mut changes,
+        );
+        let verdict =
+            Compatibility::new(true, true, check_friend_linking).check(&old_module, &new_module);
+        assert_eq!(
+            changes.is_compatible(),
+            verdict.is_ok(),
+            "{:?} {:?}",
+            changes,
+            verdict
+        );
+    }
+
+    #[test]
+    fn test_compatible_metadata_changes() {
+        let old = package(UpgradePolicy::compat(), &["a"], &["AptosFramework"]);
+        let new = package(UpgradePolicy::immutable(), &["a", "b"], &["AptosStdlib"]);
+        let report = check(&old, &new);
+        assert!(report.is_compatible(), "{:?}", report);
+        assert_eq!(report.notes.len(), 4, "{:?}", report);
+    }
+
+    #[test]
+    fn test_incompatible_metadata_changes() {
+        let old = package(UpgradePolicy::compat(), &["a", "b"], &[]);
+        let new = package(UpgradePolicy::arbitrary(), &["a", "clash"], &[]);
+        let report = check(&old, &new);
+        // Arbitrary policy, removed module `b` and module `clash` of another package
+        assert_eq!(report.incompatibilities.len(), 3, "{:?}", report);
+
+        let old = package(UpgradePolicy::immutable(), &["a"], &[]);
+        let report = check(&old, &package(UpgradePolicy::compat(), &["a"], &[]));
+        // Immutable package and weaker policy
+        assert_eq!(report.incompatibilities.len(), 2, "{:?}", report);
+    }
+
+    #[test]
+    fn test_struct_changes() {
+        let old = r#"
+            module 0xcafe::m {
+                struct Removed {}
+                struct LosesAbility has copy, drop {}
+                struct GainsAbility has drop {}
+                struct Layout { a: u64 }
+                struct Phantom<phantom T> {}
+                struct Constraint<T> { t: T }
+            }
+        "#;
+        let new = r#"
+            module 0xcafe::m {
+                struct LosesAbility has drop {}
+                struct GainsAbility has copy, drop {}
+                struct Layout { a: u64, b: bool }
+                struct Phantom<T> { t: T }
+                struct Constraint<T: copy> { t: T }
+            }
+        "#;
+        let report = check_modules(old, new, true);
+        // Removed struct, removed ability, changed layout (also of `Phantom`), removed phantom
+        // declaration and added constraint
+        assert_eq!(report.incompatibilities.len(), 6, "{:?}", report);
+        assert!(report.notes.is_empty(), "{:?}", report);
+
+        let report = check_modules(old, old, true);
+        assert!(report.is_compatible(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_function_changes() {
+        let old = r#"
+            module 0xcafe::m {
+                public fun removed() {}
+                public fun parameters(_x: u64) {}
+                public fun return_types(): u64 { 0 }
+                public fun visibility() {}
+                public entry fun entry_function() {}
+                public fun type_parameters<T>() {}
+                public(friend) fun friend_to_public() {}
+                fun private_removed() {}
+                public fun unchanged() {}
+            }
+        "#;
+        let new = r#"
+            module 0xcafe::m {
+                public fun parameters(_x: u64, _y: u64) {}
+                public fun return_types(): bool { false }
+                public(friend) fun visibility() {}
+                public fun entry_function() {}
+                public fun type_parameters<T: drop>() {}
+                public fun friend_to_public() {}
+                public fun unchanged() {}
+                public fun added() {}
+            }
+        "#;
+        let report = check_modules(old, new, true);
+        // Every change but the removal of the private function and the friend function becoming
+        // public
+        assert_eq!(report.incompatibilities.len(), 6, "{:?}", report);
+        assert!(report.notes.is_empty(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_friend_changes() {
+        let old = r#"
+            module 0xcafe::m {
+                friend 0xcafe::other;
+                public(friend) fun removed() {}
+                public(friend) fun parameters(_x: u64) {}
+            }
+            module 0xcafe::other {}
+        "#;
+        let new = r#"
+            module 0xcafe::m {
+                public(friend) fun parameters(_x: bool) {}
+            }
+            module 0xcafe::other {}
+        "#;
+        // Removed friend declaration, removed and changed friend functions
+        let report = check_modules(old, new, true);
+        assert_eq!(report.incompatibilities.len(), 3, "{:?}", report);
+        assert!(report.notes.is_empty(), "{:?}", report);
+
+        let report = check_modules(old, new, false);
+        assert!(report.is_compatible(), "{:?}", report);
+        assert_eq!(report.notes.len(), 3, "{:?}", report);
+    }
+
+    #[test]
+    fn test_resource_group_changes() {
+        let old = r#"
+            module 0xcafe::m {
+                #[resource_group(scope = global)]
+                struct Group {}
+                #[resource_group(scope = global)]
+                struct OtherGroup {}
+                #[resource_group(scope = address)]
+                struct Stricter {}
+                #[resource_group(scope = address)]
+                struct LessStrict {}
+                #[resource_group(scope = global)]
+                struct Removed {}
+                #[resource_group_member(group = 0xcafe::m::Group)]
+                struct Moved has key {}
+                #[resource_group_member(group = 0xcafe::m::Group)]
+                struct NoLongerMember has key {}
+            }
+        "#;
+        let new = r#"
+            module 0xcafe::m {
+                #[resource_group(scope = global)]
+                struct Group {}
+                #[resource_group(scope = global)]
+                struct OtherGroup {}
+                #[resource_group(scope = module_)]
+                struct Stricter {}
+                #[resource_group(scope = global)]
+                struct LessStrict {}
+                struct Removed {}
+                #[resource_group_member(group = 0xcafe::m::OtherGroup)]
+                struct Moved has key {}
+                struct NoLongerMember has key {}
+            }
+        "#;
+        let report = check_modules(old, new, true);
+        // Stricter scope, removed group, moved member and removed member
+        assert_eq!(report.incompatibilities.len(), 4, "{:?}", report);
+
+        let report = check_modules(old, old, true);
+        assert!(report.is_compatible(), "{:?}", report);
+    }
+}
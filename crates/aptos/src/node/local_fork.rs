@@ -0,0 +1,943 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local testnets forking the state of a remote network.
+//!
+//! The local node reads every state value it has not written itself from the remote network at a
+//! pinned version, through the remote REST API. Remote values are fetched lazily on first read and
+//! cached, while new transactions execute and commit locally only. Genesis still runs locally, so
+//! the framework, validator set and chain id of the local testnet are its own, and the faucet can
+//! fund accounts with the local root key.
+//!
+//! The keys written by the local ledger, including deletions, are indexed from its write sets, so
+//! that a value deleted locally stays deleted. Note that iterating over the resources of an
+//! account still only returns local values.
+
+use crate::{
+    common::types::{CliError, CliTypedResult},
+    genesis::git::{from_yaml, to_yaml},
+};
+use anyhow::{anyhow, bail, Result};
+use aptos_crypto::HashValue;
+use aptos_node::DbReaderWrapper;
+use aptos_rest_client::{aptos_api_types::MoveStructTag, error::RestError, Client};
+use aptos_storage_interface::{DbReader, ExecutedTrees, Order};
+use aptos_types::{
+    access_path::Path,
+    account_address::AccountAddress,
+    account_config::NewBlockEvent,
+    contract_event::{ContractEvent, EventWithVersion},
+    epoch_change::EpochChangeProof,
+    epoch_state::EpochState,
+    event::EventKey,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{
+        AccumulatorConsistencyProof, SparseMerkleProofExt, TransactionAccumulatorRangeProof,
+        TransactionAccumulatorSummary,
+    },
+    state_proof::StateProof,
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_key_prefix::StateKeyPrefix,
+        state_storage_usage::StateStorageUsage,
+        state_value::{StateValue, StateValueChunkWithProof},
+        table::{TableHandle, TableInfo},
+    },
+    transaction::{
+        AccountTransactionsWithProof, Transaction, TransactionInfo, TransactionListWithProof,
+        TransactionOutputListWithProof, TransactionWithProof, Version,
+    },
+    write_set::WriteSet,
+};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path as FilePath,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::runtime::Runtime;
+
+/// The file in the test directory recording the forked network, so that a restarted local
+/// testnet keeps forking the same state.
+const FORK_CONFIG_FILE: &str = "fork.yaml";
+
+/// The remote network state forked by a local testnet.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ForkConfig {
+    /// URL of the REST API of the remote network.
+    pub url: String,
+    /// Version of the remote network state.
+    pub version: Version,
+}
+
+impl ForkConfig {
+    /// Loads the fork of the local testnet in `test_dir`, or creates it if the local testnet
+    /// doesn't exist yet. Fails if the local testnet doesn't fork the requested network state.
+    pub async fn load_or_create(
+        test_dir: &FilePath,
+        fork_url: Option<Url>,
+        fork_version: Option<Version>,
+    ) -> CliTypedResult<Option<Self>> {
+        let path = test_dir.join(FORK_CONFIG_FILE);
+        if path.exists() {
+            let existing: Self = from_yaml(
+                &fs::read_to_string(&path)
+                    .map_err(|err| CliError::IO(path.display().to_string(), err))?,
+            )?;
+            let matches = match &fork_url {
+                Some(url) => {
+                    url.as_str() == existing.url
+                        && fork_version.map_or(true, |version| version == existing.version)
+                },
+                None => false,
+            };
+            return if matches {
+                Ok(Some(existing))
+            } else {
+                Err(CliError::CommandArgumentError(format!(
+                    "The local testnet in {} forks {} at version {}, use --force-restart to \
+                    start a new chain",
+                    test_dir.display(),
+                    existing.url,
+                    existing.version
+                )))
+            };
+        }
+
+        let fork_url = match fork_url {
+            Some(fork_url) => fork_url,
+            None => return Ok(None),
+        };
+        if test_dir.join("0").join("node.yaml").exists() {
+            return Err(CliError::CommandArgumentError(format!(
+                "The local testnet in {} is not a fork, use --force-restart to start a new chain",
+                test_dir.display()
+            )));
+        }
+
+        let version = match fork_version {
+            Some(version) => version,
+            None => {
+                Client::new(fork_url.clone())
+                    .get_ledger_information()
+                    .await?
+                    .into_inner()
+                    .version
+            },
+        };
+        let config = Self {
+            url: fork_url.to_string(),
+            version,
+        };
+        fs::create_dir_all(test_dir)
+            .map_err(|err| CliError::IO(test_dir.display().to_string(), err))?;
+        fs::write(&path, to_yaml(&config)?)
+            .map_err(|err| CliError::IO(path.display().to_string(), err))?;
+        Ok(Some(config))
+    }
+
+    /// Returns the wrapper making the local node read the forked state.
+    pub fn db_reader_wrapper(self) -> CliTypedResult<DbReaderWrapper> {
+        let url = Url::parse(&self.url)
+            .map_err(|err| CliError::UnableToParse("fork url", err.to_string()))?;
+        let version = self.version;
+        let runtime = create_fetcher_runtime().map_err(|err| {
+            CliError::UnexpectedError(format!(
+                "Failed to create the runtime fetching the forked state: {}",
+                err
+            ))
+        })?;
+        Ok(Box::new(move |local: Arc<dyn DbReader>| {
+            Arc::new(ForkedDbReader::new(
+                local,
+                Client::new(url),
+                version,
+                runtime,
+            )) as Arc<dyn DbReader>
+        }))
+    }
+}
+
+/// A storage reader serving the local ledger, falling back to the state of a remote network at a
+/// pinned version for the state values missing locally.
+pub struct ForkedDbReader {
+    local: Arc<dyn DbReader>,
+    client: Client,
+    version: Version,
+    /// Runs the requests to the remote network, as the storage is read from sync code.
+    runtime: Runtime,
+    /// The values fetched from the remote network, which never change as the version is pinned.
+    remote_values: Mutex<HashMap<StateKey, Option<StateValue>>>,
+    local_writes: Mutex<LocalWrites>,
+}
+
+/// The keys written by the local ledger, indexed from its write sets as they are needed.
+#[derive(Default)]
+struct LocalWrites {
+    /// The next version whose write set isn't indexed yet.
+    next_version: Version,
+    /// The version of the first local write of each key.
+    first_write_versions: HashMap<StateKey, Version>,
+}
+
+/// Creates the runtime running the requests of a `ForkedDbReader`.
+pub fn create_fetcher_runtime() -> std::io::Result<Runtime> {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("fork-fetcher")
+        .enable_all()
+        .build()
+}
+
+impl ForkedDbReader {
+    pub fn new(
+        local: Arc<dyn DbReader>,
+        client: Client,
+        version: Version,
+        runtime: Runtime,
+    ) -> Self {
+        Self {
+            local,
+            client,
+            version,
+            runtime,
+            remote_values: Mutex::new(HashMap::new()),
+            local_writes: Mutex::new(LocalWrites::default()),
+        }
+    }
+
+    /// Reads a value missing from the local state at `version`. Only the keys the local ledger
+    /// never wrote are read from the remote network, as the others were deleted locally.
+    fn get_missing_state_value(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        if self.is_written_locally(state_key, version)? {
+            Ok(None)
+        } else {
+            self.get_remote_state_value(state_key)
+        }
+    }
+
+    /// Whether the local ledger wrote the key at or before `version`.
+    fn is_written_locally(&self, state_key: &StateKey, version: Version) -> Result<bool> {
+        let mut local_writes = self
+            .local_writes
+            .lock()
+            .map_err(|_| anyhow!("The index of the local writes is poisoned"))?;
+        let start_version = local_writes.next_version;
+        if start_version <= version {
+            let write_sets = self
+                .local
+                .get_write_set_iterator(start_version, version - start_version + 1)?;
+            for (write_set_version, write_set) in (start_version..).zip(write_sets) {
+                let write_set = write_set?;
+                for (key, _) in write_set.iter() {
+                    local_writes
+                        .first_write_versions
+                        .entry(key.clone())
+                        .or_insert(write_set_version);
+                }
+            }
+            local_writes.next_version = version + 1;
+        }
+        Ok(local_writes
+            .first_write_versions
+            .get(state_key)
+            .map_or(false, |first_write_version| *first_write_version <= version))
+    }
+
+    fn get_remote_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        if let Some(value) = self.lock_remote_values()?.get(state_key) {
+            return Ok(value.clone());
+        }
+
+        let client = self.client.clone();
+        let key = state_key.clone();
+        let version = self.version;
+        // Block on the dedicated runtime, as the reader may be called from within another one.
+        let value = futures::executor::block_on(
+            self.runtime
+                .spawn(async move { fetch_remote_state_value(&client, &key, version).await }),
+        )
+        .map_err(|err| anyhow!("Failed to fetch the forked state: {}", err))??;
+
+        self.lock_remote_values()?
+            .insert(state_key.clone(), value.clone());
+        Ok(value)
+    }
+
+    fn lock_remote_values(&self) -> Result<MutexGuard<HashMap<StateKey, Option<StateValue>>>> {
+        self.remote_values
+            .lock()
+            .map_err(|_| anyhow!("The cache of the forked state is poisoned"))
+    }
+}
+
+async fn fetch_remote_state_value(
+    client: &Client,
+    state_key: &StateKey,
+    version: Version,
+) -> Result<Option<StateValue>> {
+    let response = match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) => match access_path.get_path() {
+            Path::Code(module_id) => client
+                .get_account_module_bcs_at_version(
+                    *module_id.address(),
+                    module_id.name().as_str(),
+                    version,
+                )
+                .await
+                .map(|response| response.into_inner().to_vec()),
+            Path::Resource(tag) | Path::ResourceGroup(tag) => client
+                .get_account_resource_at_version_bytes(
+                    access_path.address,
+                    MoveStructTag::from(tag).to_string().as_str(),
+                    version,
+                )
+                .await
+                .map(|response| response.into_inner()),
+        },
+        StateKeyInner::TableItem { handle, key } => client
+            .get_raw_table_item(handle.0, key, version)
+            .await
+            .map(|response| response.into_inner()),
+        StateKeyInner::Raw(_) => bail!("Unexpected raw state key {:?}", state_key),
+    };
+
+    match response {
+        Ok(bytes) => Ok(Some(StateValue::new_legacy(bytes))),
+        Err(RestError::Api(error)) if error.status_code == StatusCode::NOT_FOUND => Ok(None),
+        Err(error) => Err(anyhow!(
+            "Failed to fetch {:?} at version {}: {}",
+            state_key,
+            version,
+            error
+        )),
+    }
+}
+
+impl DbReader for ForkedDbReader {
+    fn get_epoch_ending_ledger_infos(
+        &self,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) -> Result<EpochChangeProof> {
+        self.local
+            .get_epoch_ending_ledger_infos(start_epoch, end_epoch)
+    }
+
+    fn get_transactions(
+        &self,
+        start_version: Version,
+        batch_size: u64,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<TransactionListWithProof> {
+        self.local
+            .get_transactions(start_version, batch_size, ledger_version, fetch_events)
+    }
+
+    fn get_gas_prices(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<u64>> {
+        self.local
+            .get_gas_prices(start_version, limit, ledger_version)
+    }
+
+    fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>> {
+        self.local
+            .get_transaction_by_hash(hash, ledger_version, fetch_events)
+    }
+
+    fn get_transaction_by_version(
+        &self,
+        version: Version,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<TransactionWithProof> {
+        self.local
+            .get_transaction_by_version(version, ledger_version, fetch_events)
+    }
+
+    fn get_first_txn_version(&self) -> Result<Option<Version>> {
+        self.local.get_first_txn_version()
+    }
+
+    fn get_first_viable_txn_version(&self) -> Result<Version> {
+        self.local.get_first_viable_txn_version()
+    }
+
+    fn get_first_write_set_version(&self) -> Result<Option<Version>> {
+        self.local.get_first_write_set_version()
+    }
+
+    fn get_transaction_outputs(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<TransactionOutputListWithProof> {
+        self.local
+            .get_transaction_outputs(start_version, limit, ledger_version)
+    }
+
+    fn get_events(
+        &self,
+        event_key: &EventKey,
+        start: u64,
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        self.local
+            .get_events(event_key, start, order, limit, ledger_version)
+    }
+
+    fn get_transaction_iterator(
+        &self,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Box<dyn Iterator<Item = Result<Transaction>> + '_>> {
+        self.local.get_transaction_iterator(start_version, limit)
+    }
+
+    fn get_transaction_info_iterator(
+        &self,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Box<dyn Iterator<Item = Result<TransactionInfo>> + '_>> {
+        self.local
+            .get_transaction_info_iterator(start_version, limit)
+    }
+
+    fn get_events_iterator(
+        &self,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Box<dyn Iterator<Item = Result<Vec<ContractEvent>>> + '_>> {
+        self.local.get_events_iterator(start_version, limit)
+    }
+
+    fn get_write_set_iterator(
+        &self,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Box<dyn Iterator<Item = Result<WriteSet>> + '_>> {
+        self.local.get_write_set_iterator(start_version, limit)
+    }
+
+    fn get_transaction_accumulator_range_proof(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<TransactionAccumulatorRangeProof> {
+        self.local
+            .get_transaction_accumulator_range_proof(start_version, limit, ledger_version)
+    }
+
+    fn get_block_timestamp(&self, version: Version) -> Result<u64> {
+        self.local.get_block_timestamp(version)
+    }
+
+    fn get_next_block_event(&self, version: Version) -> Result<(Version, NewBlockEvent)> {
+        self.local.get_next_block_event(version)
+    }
+
+    fn get_block_info_by_version(
+        &self,
+        version: Version,
+    ) -> Result<(Version, Version, NewBlockEvent)> {
+        self.local.get_block_info_by_version(version)
+    }
+
+    fn get_block_info_by_height(&self, height: u64) -> Result<(Version, Version, NewBlockEvent)> {
+        self.local.get_block_info_by_height(height)
+    }
+
+    fn get_last_version_before_timestamp(
+        &self,
+        timestamp: u64,
+        ledger_version: Version,
+    ) -> Result<Version> {
+        self.local
+            .get_last_version_before_timestamp(timestamp, ledger_version)
+    }
+
+    fn get_latest_epoch_state(&self) -> Result<EpochState> {
+        self.local.get_latest_epoch_state()
+    }
+
+    fn get_prefixed_state_value_iterator(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        cursor: Option<&StateKey>,
+        version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + '_>> {
+        self.local
+            .get_prefixed_state_value_iterator(key_prefix, cursor, version)
+    }
+
+    fn get_latest_ledger_info_option(&self) -> Result<Option<LedgerInfoWithSignatures>> {
+        self.local.get_latest_ledger_info_option()
+    }
+
+    fn get_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures> {
+        self.local.get_latest_ledger_info()
+    }
+
+    fn get_latest_version(&self) -> Result<Version> {
+        self.local.get_latest_version()
+    }
+
+    fn get_latest_state_checkpoint_version(&self) -> Result<Option<Version>> {
+        self.local.get_latest_state_checkpoint_version()
+    }
+
+    fn get_state_snapshot_before(
+        &self,
+        next_version: Version,
+    ) -> Result<Option<(Version, HashValue)>> {
+        self.local.get_state_snapshot_before(next_version)
+    }
+
+    fn get_latest_commit_metadata(&self) -> Result<(Version, u64)> {
+        self.local.get_latest_commit_metadata()
+    }
+
+    fn get_account_transaction(
+        &self,
+        address: AccountAddress,
+        seq_num: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<Option<TransactionWithProof>> {
+        self.local
+            .get_account_transaction(address, seq_num, include_events, ledger_version)
+    }
+
+    fn get_account_transactions(
+        &self,
+        address: AccountAddress,
+        seq_num: u64,
+        limit: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<AccountTransactionsWithProof> {
+        self.local
+            .get_account_transactions(address, seq_num, limit, include_events, ledger_version)
+    }
+
+    fn get_state_proof_with_ledger_info(
+        &self,
+        known_version: u64,
+        ledger_info: LedgerInfoWithSignatures,
+    ) -> Result<StateProof> {
+        self.local
+            .get_state_proof_with_ledger_info(known_version, ledger_info)
+    }
+
+    fn get_state_proof(&self, known_version: u64) -> Result<StateProof> {
+        self.local.get_state_proof(known_version)
+    }
+
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        match self.local.get_state_value_by_version(state_key, version)? {
+            Some(value) => Ok(Some(value)),
+            None => self.get_missing_state_value(state_key, version),
+        }
+    }
+
+    fn get_state_proof_by_version_ext(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<SparseMerkleProofExt> {
+        self.local
+            .get_state_proof_by_version_ext(state_key, version)
+    }
+
+    fn get_state_value_with_proof_by_version_ext(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<(Option<StateValue>, SparseMerkleProofExt)> {
+        let (value, proof) = self
+            .local
+            .get_state_value_with_proof_by_version_ext(state_key, version)?;
+        match value {
+            Some(value) => Ok((Some(value), proof)),
+            None => Ok((self.get_missing_state_value(state_key, version)?, proof)),
+        }
+    }
+
+    fn get_latest_executed_trees(&self) -> Result<ExecutedTrees> {
+        self.local.get_latest_executed_trees()
+    }
+
+    fn get_epoch_ending_ledger_info(&self, known_version: u64) -> Result<LedgerInfoWithSignatures> {
+        self.local.get_epoch_ending_ledger_info(known_version)
+    }
+
+    fn get_latest_transaction_info_option(&self) -> Result<Option<(Version, TransactionInfo)>> {
+        self.local.get_latest_transaction_info_option()
+    }
+
+    fn get_accumulator_root_hash(&self, version: Version) -> Result<HashValue> {
+        self.local.get_accumulator_root_hash(version)
+    }
+
+    fn get_accumulator_consistency_proof(
+        &self,
+        client_known_version: Option<Version>,
+        ledger_version: Version,
+    ) -> Result<AccumulatorConsistencyProof> {
+        self.local
+            .get_accumulator_consistency_proof(client_known_version, ledger_version)
+    }
+
+    fn get_accumulator_summary(
+        &self,
+        ledger_version: Version,
+    ) -> Result<TransactionAccumulatorSummary> {
+        self.local.get_accumulator_summary(ledger_version)
+    }
+
+    fn get_state_leaf_count(&self, version: Version) -> Result<usize> {
+        self.local.get_state_leaf_count(version)
+    }
+
+    fn get_state_value_chunk_with_proof(
+        &self,
+        version: Version,
+        start_idx: usize,
+        chunk_size: usize,
+    ) -> Result<StateValueChunkWithProof> {
+        self.local
+            .get_state_value_chunk_with_proof(version, start_idx, chunk_size)
+    }
+
+    fn is_state_merkle_pruner_enabled(&self) -> Result<bool> {
+        self.local.is_state_merkle_pruner_enabled()
+    }
+
+    fn get_epoch_snapshot_prune_window(&self) -> Result<usize> {
+        self.local.get_epoch_snapshot_prune_window()
+    }
+
+    fn is_ledger_pruner_enabled(&self) -> Result<bool> {
+        self.local.is_ledger_pruner_enabled()
+    }
+
+    fn get_ledger_prune_window(&self) -> Result<usize> {
+        self.local.get_ledger_prune_window()
+    }
+
+    fn get_table_info(&self, handle: TableHandle) -> Result<TableInfo> {
+        self.local.get_table_info(handle)
+    }
+
+    fn indexer_enabled(&self) -> bool {
+        self.local.indexer_enabled()
+    }
+
+    fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
+        self.local.get_state_storage_usage(version)
+    }
+
+    fn are_state_values_provable(&self) -> bool {
+        // Remote values are not in the local state Merkle tree
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_rest_client::aptos_api_types::{
+        AptosError, AptosErrorCode, X_APTOS_BLOCK_HEIGHT, X_APTOS_CHAIN_ID, X_APTOS_EPOCH,
+        X_APTOS_LEDGER_OLDEST_VERSION, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
+        X_APTOS_OLDEST_BLOCK_HEIGHT,
+    };
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        access_path::AccessPath,
+        write_set::{WriteOp, WriteSetMut},
+    };
+    use httpmock::{Mock, MockServer};
+    use move_core_types::{identifier::Identifier, language_storage::StructTag};
+
+    const FORK_VERSION: Version = 10;
+
+    /// The local ledger, containing only the given state values and write sets.
+    struct LocalDbReader {
+        values: HashMap<StateKey, StateValue>,
+        write_sets: Vec<WriteSet>,
+    }
+
+    impl LocalDbReader {
+        /// A local ledger which created the given values in its first transaction.
+        fn new(values: HashMap<StateKey, StateValue>) -> Self {
+            let write_set = WriteSetMut::new(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), WriteOp::Creation(value.bytes().to_vec()))),
+            )
+            .freeze()
+            .unwrap();
+            Self {
+                values,
+                write_sets: vec![write_set],
+            }
+        }
+    }
+
+    impl DbReader for LocalDbReader {
+        fn get_write_set_iterator(
+            &self,
+            start_version: Version,
+            limit: u64,
+        ) -> Result<Box<dyn Iterator<Item = Result<WriteSet>> + '_>> {
+            Ok(Box::new(
+                self.write_sets
+                    .iter()
+                    .skip(start_version as usize)
+                    .take(limit as usize)
+                    .cloned()
+                    .map(Ok),
+            ))
+        }
+
+        fn get_state_value_by_version(
+            &self,
+            state_key: &StateKey,
+            _version: Version,
+        ) -> Result<Option<StateValue>> {
+            Ok(self.values.get(state_key).cloned())
+        }
+
+        fn get_state_value_with_proof_by_version_ext(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> Result<(Option<StateValue>, SparseMerkleProofExt)> {
+            Ok((
+                self.get_state_value_by_version(state_key, version)?,
+                SparseMerkleProofExt::new(None, vec![]),
+            ))
+        }
+    }
+
+    fn resource_key(name: &str) -> StateKey {
+        let tag = StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new("test").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        };
+        StateKey::access_path(AccessPath::resource_access_path(AccountAddress::ONE, tag).unwrap())
+    }
+
+    /// Serves the resource `0x1::test::<name>` at the forked version.
+    fn mock_resource<'a>(server: &'a MockServer, name: &str, bytes: Vec<u8>) -> Mock<'a> {
+        server.mock(|when, then| {
+            when.method("GET")
+                .path_contains(format!("::test::{}", name))
+                .query_param("ledger_version", FORK_VERSION.to_string());
+            then.status(200)
+                .header(X_APTOS_CHAIN_ID, "4")
+                .header(X_APTOS_LEDGER_VERSION, "100")
+                .header(X_APTOS_LEDGER_OLDEST_VERSION, "0")
+                .header(X_APTOS_LEDGER_TIMESTAMP, "1000")
+                .header(X_APTOS_EPOCH, "2")
+                .header(X_APTOS_BLOCK_HEIGHT, "50")
+                .header(X_APTOS_OLDEST_BLOCK_HEIGHT, "0")
+                .body(bytes);
+        })
+    }
+
+    /// Fails the requests for the resource `0x1::test::<name>` with the given error.
+    fn mock_resource_error<'a>(
+        server: &'a MockServer,
+        name: &str,
+        status: u16,
+        error_code: AptosErrorCode,
+    ) -> Mock<'a> {
+        server.mock(|when, then| {
+            when.method("GET")
+                .path_contains(format!("::test::{}", name));
+            then.status(status)
+                .json_body_obj(&AptosError::new_with_error_code("error", error_code));
+        })
+    }
+
+    fn forked_db_reader(server: &MockServer, local: LocalDbReader) -> ForkedDbReader {
+        ForkedDbReader::new(
+            Arc::new(local),
+            Client::new(Url::parse(&server.base_url()).unwrap()),
+            FORK_VERSION,
+            create_fetcher_runtime().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_forked_db_reader_reads_local_values_first() {
+        let server = MockServer::start();
+        let remote = mock_resource(&server, "Local", vec![1]);
+        let local_value = StateValue::from(vec![2]);
+        let reader = forked_db_reader(
+            &server,
+            LocalDbReader::new(HashMap::from([(
+                resource_key("Local"),
+                local_value.clone(),
+            )])),
+        );
+
+        assert_eq!(
+            reader
+                .get_state_value_by_version(&resource_key("Local"), 0)
+                .unwrap(),
+            Some(local_value)
+        );
+        assert_eq!(remote.hits(), 0);
+        assert!(!reader.are_state_values_provable());
+    }
+
+    #[test]
+    fn test_forked_db_reader_keeps_local_deletions() {
+        let server = MockServer::start();
+        let remote = mock_resource(&server, "Deleted", vec![1]);
+        let mut local = LocalDbReader::new(HashMap::new());
+        local.write_sets.push(
+            WriteSetMut::new(vec![(resource_key("Deleted"), WriteOp::Creation(vec![2]))])
+                .freeze()
+                .unwrap(),
+        );
+        local.write_sets.push(
+            WriteSetMut::new(vec![(resource_key("Deleted"), WriteOp::Deletion)])
+                .freeze()
+                .unwrap(),
+        );
+        let reader = forked_db_reader(&server, local);
+
+        // Before the local ledger wrote it, the value is the remote one
+        assert_eq!(
+            reader
+                .get_state_value_by_version(&resource_key("Deleted"), 0)
+                .unwrap(),
+            Some(StateValue::new_legacy(vec![1]))
+        );
+        // Once deleted locally, it stays deleted
+        for _ in 0..2 {
+            assert_eq!(
+                reader
+                    .get_state_value_by_version(&resource_key("Deleted"), 2)
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                reader
+                    .get_state_value_with_proof_by_version_ext(&resource_key("Deleted"), 2)
+                    .map(|(value, _)| value)
+                    .unwrap(),
+                None
+            );
+        }
+        assert_eq!(remote.hits(), 1);
+    }
+
+    #[test]
+    fn test_forked_db_reader_caches_remote_values() {
+        let server = MockServer::start();
+        let remote = mock_resource(&server, "Remote", vec![1, 2, 3]);
+        let missing =
+            mock_resource_error(&server, "Missing", 404, AptosErrorCode::ResourceNotFound);
+        let reader = forked_db_reader(&server, LocalDbReader::new(HashMap::new()));
+
+        for _ in 0..2 {
+            assert_eq!(
+                reader
+                    .get_state_value_by_version(&resource_key("Remote"), 0)
+                    .unwrap(),
+                Some(StateValue::new_legacy(vec![1, 2, 3]))
+            );
+            // A value missing from the remote state is missing from the forked one
+            assert_eq!(
+                reader
+                    .get_state_value_by_version(&resource_key("Missing"), 0)
+                    .unwrap(),
+                None
+            );
+        }
+        // The remote state is pinned, so each value is fetched once
+        assert_eq!(remote.hits(), 1);
+        assert_eq!(missing.hits(), 1);
+    }
+
+    #[test]
+    fn test_forked_db_reader_fails_on_remote_errors() {
+        let server = MockServer::start();
+        let failing = mock_resource_error(&server, "Failing", 500, AptosErrorCode::InternalError);
+        let reader = forked_db_reader(&server, LocalDbReader::new(HashMap::new()));
+
+        // Errors are not cached, so the value is fetched again
+        for _ in 0..2 {
+            assert!(reader
+                .get_state_value_by_version(&resource_key("Failing"), 0)
+                .is_err());
+        }
+        assert_eq!(failing.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fork_config_is_pinned() {
+        let test_dir = TempPath::new();
+        let url = Url::parse("https://fullnode.mainnet.aptoslabs.com").unwrap();
+
+        // Not a fork
+        assert_eq!(
+            ForkConfig::load_or_create(test_dir.path(), None, None)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let config = ForkConfig::load_or_create(test_dir.path(), Some(url.clone()), Some(10))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.version, 10);
+
+        // Restarting the local testnet keeps forking the same state
+        assert_eq!(
+            ForkConfig::load_or_create(test_dir.path(), Some(url.clone()), None)
+                .await
+                .unwrap(),
+            Some(config)
+        );
+        assert!(
+            ForkConfig::load_or_create(test_dir.path(), Some(url), Some(11))
+                .await
+                .is_err()
+        );
+        assert!(ForkConfig::load_or_create(test_dir.path(), None, None)
+            .await
+            .is_err());
+    }
+}
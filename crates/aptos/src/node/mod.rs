@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod analyze;
+pub mod local_fork;
 
 use crate::{
     common::{
@@ -14,9 +15,12 @@ use crate::{
     },
     config::GlobalConfig,
     genesis::git::from_yaml,
-    node::analyze::{
-        analyze_validators::{AnalyzeValidators, ValidatorStats},
-        fetch_metadata::FetchMetadata,
+    node::{
+        analyze::{
+            analyze_validators::{AnalyzeValidators, ValidatorStats},
+            fetch_metadata::FetchMetadata,
+        },
+        local_fork::ForkConfig,
     },
 };
 use aptos_backup_cli::{
@@ -1086,6 +1090,21 @@ pub struct RunLocalTestnet {
     #[clap(long)]
     do_not_delegate: bool,
 
+    /// Fork the state of a remote network, given the URL of its REST API
+    ///
+    /// e.g. `https://fullnode.mainnet.aptoslabs.com`. Every state value the local testnet has not
+    /// written itself is lazily fetched from the remote network at `--fork-version` and cached,
+    /// while new transactions execute and commit locally only. The local testnet keeps its own
+    /// genesis (framework, validator and chain id), so the faucet works as usual.
+    #[clap(long)]
+    fork_url: Option<Url>,
+
+    /// Version of the remote network state to fork
+    ///
+    /// Defaults to the latest version of the remote network when the local testnet is created.
+    #[clap(long, requires = "fork-url")]
+    fork_version: Option<u64>,
+
     #[clap(flatten)]
     prompt_options: PromptOptions,
 }
@@ -1121,6 +1140,21 @@ impl CliCommand<()> for RunLocalTestnet {
             })?;
         }
 
+        // Set up the fork of the remote network, if any
+        let db_reader_wrapper =
+            match ForkConfig::load_or_create(&test_dir, self.fork_url.clone(), self.fork_version)
+                .await?
+            {
+                Some(fork_config) => {
+                    eprintln!(
+                        "Forking {} at version {}",
+                        fork_config.url, fork_config.version
+                    );
+                    Some(fork_config.db_reader_wrapper()?)
+                },
+                None => None,
+            };
+
         // Spawn the node in a separate thread
         let config_path = self.config_path.clone();
         let test_dir_copy = test_dir.clone();
@@ -1132,6 +1166,7 @@ impl CliCommand<()> for RunLocalTestnet {
                 false,
                 aptos_cached_packages::head_release_bundle(),
                 rng,
+                db_reader_wrapper,
             );
             eprintln!("Node stopped unexpectedly {:#?}", result);
         });
@@ -0,0 +1,21 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS objects;
+DROP INDEX IF EXISTS o_owner_idx;
+DROP INDEX IF EXISTS o_object_version_idx;
+DROP INDEX IF EXISTS o_insat_idx;
+DROP TABLE IF EXISTS current_objects;
+DROP INDEX IF EXISTS co_owner_idx;
+DROP INDEX IF EXISTS co_insat_idx;
+DROP TABLE IF EXISTS fungible_asset_metadata;
+DROP INDEX IF EXISTS fam_insat_idx;
+DROP TABLE IF EXISTS fungible_asset_balances;
+DROP INDEX IF EXISTS fab_owner_at_index;
+DROP INDEX IF EXISTS fab_insat_index;
+DROP TABLE IF EXISTS current_fungible_asset_balances;
+DROP INDEX IF EXISTS cfab_owner_at_index;
+DROP INDEX IF EXISTS cfab_insat_index;
+DROP TABLE IF EXISTS fungible_asset_activities;
+DROP INDEX IF EXISTS faa_owner_type_index;
+DROP INDEX IF EXISTS faa_si_index;
+DROP INDEX IF EXISTS faa_at_index;
+DROP INDEX IF EXISTS faa_insat_idx;
@@ -0,0 +1,100 @@
+-- Your SQL goes here
+-- objects, basically normalizing ObjectCore
+CREATE TABLE IF NOT EXISTS objects (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  object_address VARCHAR(66) NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  guid_creation_num NUMERIC NOT NULL,
+  allow_ungated_transfer BOOLEAN NOT NULL,
+  is_deleted BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- constraints
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+CREATE INDEX IF NOT EXISTS o_owner_idx ON objects (owner_address);
+CREATE INDEX IF NOT EXISTS o_object_version_idx ON objects (object_address, transaction_version);
+CREATE INDEX IF NOT EXISTS o_insat_idx ON objects (inserted_at);
+-- latest instance of objects
+CREATE TABLE IF NOT EXISTS current_objects (
+  object_address VARCHAR(66) UNIQUE PRIMARY KEY NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  allow_ungated_transfer BOOLEAN NOT NULL,
+  last_guid_creation_num NUMERIC NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  is_deleted BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS co_owner_idx ON current_objects (owner_address);
+CREATE INDEX IF NOT EXISTS co_insat_idx ON current_objects (inserted_at);
+-- metadata of both coins (v1) and fungible assets (v2); asset_type is the coin type or the
+-- address of the fungible asset metadata object
+CREATE TABLE IF NOT EXISTS fungible_asset_metadata (
+  asset_type VARCHAR(1000) UNIQUE PRIMARY KEY NOT NULL,
+  name VARCHAR(32) NOT NULL,
+  symbol VARCHAR(10) NOT NULL,
+  decimals INT NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS fam_insat_idx ON fungible_asset_metadata (inserted_at);
+-- balances of coin stores (v1) and fungible stores (v2)
+CREATE TABLE IF NOT EXISTS fungible_asset_balances (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  storage_id VARCHAR(66) NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  asset_type VARCHAR(1000) NOT NULL,
+  is_primary BOOLEAN NOT NULL,
+  is_frozen BOOLEAN NOT NULL,
+  amount NUMERIC NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- constraints
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+CREATE INDEX IF NOT EXISTS fab_owner_at_index ON fungible_asset_balances (owner_address, asset_type);
+CREATE INDEX IF NOT EXISTS fab_insat_index ON fungible_asset_balances (inserted_at);
+-- latest balance of each store, so that coin and fungible asset balances of an owner can be
+-- queried from a single table
+CREATE TABLE IF NOT EXISTS current_fungible_asset_balances (
+  storage_id VARCHAR(66) UNIQUE PRIMARY KEY NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  asset_type VARCHAR(1000) NOT NULL,
+  is_primary BOOLEAN NOT NULL,
+  is_frozen BOOLEAN NOT NULL,
+  amount NUMERIC NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cfab_owner_at_index ON current_fungible_asset_balances (owner_address, asset_type);
+CREATE INDEX IF NOT EXISTS cfab_insat_index ON current_fungible_asset_balances (inserted_at);
+-- coin (v1) and fungible asset (v2) activities, including gas fees
+CREATE TABLE IF NOT EXISTS fungible_asset_activities (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  storage_id VARCHAR(66) NOT NULL,
+  asset_type VARCHAR(1000) NOT NULL,
+  is_frozen BOOLEAN,
+  amount NUMERIC,
+  type VARCHAR NOT NULL,
+  is_gas_fee BOOLEAN NOT NULL,
+  is_transaction_success BOOLEAN NOT NULL,
+  entry_function_id_str VARCHAR(100),
+  block_height BIGINT NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- constraints
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS faa_owner_type_index ON fungible_asset_activities (owner_address, type);
+CREATE INDEX IF NOT EXISTS faa_si_index ON fungible_asset_activities (storage_id);
+CREATE INDEX IF NOT EXISTS faa_at_index ON fungible_asset_activities (asset_type);
+CREATE INDEX IF NOT EXISTS faa_insat_idx ON fungible_asset_activities (inserted_at);
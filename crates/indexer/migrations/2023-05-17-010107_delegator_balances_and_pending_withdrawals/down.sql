@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS delegator_balances;
+DROP INDEX IF EXISTS db_da_pa_index;
+DROP INDEX IF EXISTS db_insat_index;
+DROP INDEX IF EXISTS cdb_th_index;
+DROP TABLE IF EXISTS current_delegator_pending_withdrawals;
+DROP INDEX IF EXISTS cdpw_pa_index;
+DROP INDEX IF EXISTS cdpw_insat_index;
+DROP TABLE IF EXISTS delegated_staking_pool_balances;
+DROP INDEX IF EXISTS dspb_insat_index;
+DROP TABLE IF EXISTS current_delegated_staking_pool_balances;
+DROP INDEX IF EXISTS cdspb_insat_index;
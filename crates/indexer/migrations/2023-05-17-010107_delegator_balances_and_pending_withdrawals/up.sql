@@ -0,0 +1,62 @@
+-- Your SQL goes here
+-- history of delegator shares, both active and inactive
+CREATE TABLE IF NOT EXISTS delegator_balances (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  delegator_address VARCHAR(66) NOT NULL,
+  pool_address VARCHAR(66) NOT NULL,
+  pool_type VARCHAR(100) NOT NULL,
+  table_handle VARCHAR(66) NOT NULL,
+  amount NUMERIC NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- Constraints
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+CREATE INDEX db_da_pa_index ON delegator_balances (
+  delegator_address,
+  pool_address,
+  transaction_version
+);
+CREATE INDEX db_insat_index ON delegator_balances (inserted_at);
+-- inactive share tables are looked up by handle when a lockup cycle's pool is removed
+CREATE INDEX IF NOT EXISTS cdb_th_index ON current_delegator_balances (table_handle);
+-- the lockup cycle each delegator has a pending withdrawal in, null once withdrawn
+CREATE TABLE IF NOT EXISTS current_delegator_pending_withdrawals (
+  delegator_address VARCHAR(66) NOT NULL,
+  pool_address VARCHAR(66) NOT NULL,
+  table_handle VARCHAR(66) NOT NULL,
+  observed_lockup_cycle BIGINT,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- Constraints
+  PRIMARY KEY (delegator_address, pool_address)
+);
+CREATE INDEX cdpw_pa_index ON current_delegator_pending_withdrawals (pool_address);
+CREATE INDEX cdpw_insat_index ON current_delegator_pending_withdrawals (inserted_at);
+-- history of pool totals and commission
+CREATE TABLE IF NOT EXISTS delegated_staking_pool_balances (
+  transaction_version BIGINT NOT NULL,
+  staking_pool_address VARCHAR(66) NOT NULL,
+  total_coins NUMERIC NOT NULL,
+  total_shares NUMERIC NOT NULL,
+  total_coins_inactive NUMERIC NOT NULL,
+  operator_commission_percentage NUMERIC NOT NULL,
+  active_table_handle VARCHAR(66) NOT NULL,
+  inactive_table_handle VARCHAR(66) NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- Constraints
+  PRIMARY KEY (transaction_version, staking_pool_address)
+);
+CREATE INDEX dspb_insat_index ON delegated_staking_pool_balances (inserted_at);
+CREATE TABLE IF NOT EXISTS current_delegated_staking_pool_balances (
+  staking_pool_address VARCHAR(66) UNIQUE PRIMARY KEY NOT NULL,
+  total_coins NUMERIC NOT NULL,
+  total_shares NUMERIC NOT NULL,
+  total_coins_inactive NUMERIC NOT NULL,
+  operator_commission_percentage NUMERIC NOT NULL,
+  active_table_handle VARCHAR(66) NOT NULL,
+  inactive_table_handle VARCHAR(66) NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX cdspb_insat_index ON current_delegated_staking_pool_balances (inserted_at);
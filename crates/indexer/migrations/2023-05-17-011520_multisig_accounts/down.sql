@@ -0,0 +1,13 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS current_multisig_accounts;
+DROP INDEX IF EXISTS cma_insat_index;
+DROP TABLE IF EXISTS current_multisig_owners;
+DROP INDEX IF EXISTS cmo_owner_index;
+DROP INDEX IF EXISTS cmo_insat_index;
+DROP TABLE IF EXISTS current_multisig_transactions;
+DROP INDEX IF EXISTS cmt_status_index;
+DROP INDEX IF EXISTS cmt_insat_index;
+DROP TABLE IF EXISTS multisig_votes;
+DROP INDEX IF EXISTS mv_ma_sn_index;
+DROP INDEX IF EXISTS mv_owner_index;
+DROP INDEX IF EXISTS mv_insat_index;
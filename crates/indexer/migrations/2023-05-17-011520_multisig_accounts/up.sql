@@ -0,0 +1,58 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS current_multisig_accounts (
+  multisig_address VARCHAR(66) UNIQUE PRIMARY KEY NOT NULL,
+  owners JSONB NOT NULL,
+  num_signatures_required BIGINT NOT NULL,
+  last_executed_sequence_number BIGINT NOT NULL,
+  next_sequence_number BIGINT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX cma_insat_index ON current_multisig_accounts (inserted_at);
+-- owners that were removed are kept with is_owner = false
+CREATE TABLE IF NOT EXISTS current_multisig_owners (
+  multisig_address VARCHAR(66) NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  is_owner BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- Constraints
+  PRIMARY KEY (multisig_address, owner_address)
+);
+CREATE INDEX cmo_owner_index ON current_multisig_owners (owner_address);
+CREATE INDEX cmo_insat_index ON current_multisig_owners (inserted_at);
+-- proposals, status is one of pending, executed, execution_failed or rejected
+CREATE TABLE IF NOT EXISTS current_multisig_transactions (
+  multisig_address VARCHAR(66) NOT NULL,
+  sequence_number BIGINT NOT NULL,
+  creator_address VARCHAR(66) NOT NULL,
+  payload TEXT,
+  payload_hash VARCHAR(66),
+  votes JSONB NOT NULL,
+  num_approvals BIGINT NOT NULL,
+  num_rejections BIGINT NOT NULL,
+  status VARCHAR(50) NOT NULL,
+  executor_address VARCHAR(66),
+  creation_timestamp TIMESTAMP NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- Constraints
+  PRIMARY KEY (multisig_address, sequence_number)
+);
+CREATE INDEX cmt_status_index ON current_multisig_transactions (multisig_address, status);
+CREATE INDEX cmt_insat_index ON current_multisig_transactions (inserted_at);
+CREATE TABLE IF NOT EXISTS multisig_votes (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  multisig_address VARCHAR(66) NOT NULL,
+  sequence_number BIGINT NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  approved BOOLEAN NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  -- Constraints
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX mv_ma_sn_index ON multisig_votes (multisig_address, sequence_number);
+CREATE INDEX mv_owner_index ON multisig_votes (owner_address);
+CREATE INDEX mv_insat_index ON multisig_votes (inserted_at);
@@ -0,0 +1,631 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Converts transactions stored by indexer-grpc, e.g. in its file store, back into the API types the processors
+//! consume. This is the inverse of the conversion done by the indexer-grpc fullnode.
+//!
+//! The protobuf schema doesn't keep the raw bytes of table item values, so `WriteTableItem::value` is left
+//! empty; processors only read the decoded table data.
+
+use anyhow::{bail, Context, Result};
+use aptos_api_types::{
+    transaction::{DecodedTableData, DeletedTableData},
+    AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
+    DirectWriteSet, Ed25519Signature, EntryFunctionPayload, Event, EventGuid, GenesisPayload,
+    GenesisTransaction, HashValue, HexEncodedBytes, ModuleBundlePayload, MoveModuleBytecode,
+    MoveModuleId, MoveResource, MoveScriptBytecode, MoveStructTag, MoveType, MultiAgentSignature,
+    MultiEd25519Signature, MultisigPayload, MultisigTransactionPayload, ScriptPayload,
+    ScriptWriteSet, StateCheckpointTransaction, Transaction, TransactionInfo, TransactionPayload,
+    TransactionSignature, UserTransaction, UserTransactionRequest, WriteModule, WriteResource,
+    WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem, U64,
+};
+use aptos_bitvec::BitVec;
+use aptos_protos::{transaction::testing1::v1 as transaction, util::timestamp};
+use prost::Message;
+
+/// Number of bits in the bitmap of a multi-ed25519 signature, i.e. the maximum number of keys.
+const MULTI_ED25519_BITMAP_NUM_OF_BITS: u16 = 32;
+
+/// Decodes a base64 encoded `Transaction` protobuf, as stored in the indexer-grpc cache and file store.
+pub fn decode_transaction(encoded_transaction: &str) -> Result<Transaction> {
+    let bytes = base64::decode(encoded_transaction).context("Invalid base64 transaction")?;
+    let transaction = transaction::Transaction::decode(bytes.as_slice())
+        .context("Invalid transaction protobuf")?;
+    convert_transaction(&transaction)
+        .with_context(|| format!("Failed to convert transaction {}", transaction.version))
+}
+
+pub fn convert_transaction(transaction: &transaction::Transaction) -> Result<Transaction> {
+    let info = convert_transaction_info(transaction)?;
+    let timestamp = convert_timestamp_usecs(transaction.timestamp.as_ref());
+
+    Ok(
+        match transaction
+            .txn_data
+            .as_ref()
+            .context("Transaction data is missing")?
+        {
+            transaction::transaction::TxnData::User(user_transaction) => {
+                Transaction::UserTransaction(Box::new(UserTransaction {
+                    info,
+                    request: convert_user_transaction_request(
+                        user_transaction
+                            .request
+                            .as_ref()
+                            .context("User transaction request is missing")?,
+                    )?,
+                    events: convert_events(&user_transaction.events)?,
+                    timestamp,
+                }))
+            },
+            transaction::transaction::TxnData::Genesis(genesis_transaction) => {
+                Transaction::GenesisTransaction(GenesisTransaction {
+                    info,
+                    payload: GenesisPayload::WriteSetPayload(WriteSetPayload {
+                        write_set: convert_write_set(
+                            genesis_transaction
+                                .payload
+                                .as_ref()
+                                .context("Genesis write set is missing")?,
+                        )?,
+                    }),
+                    events: convert_events(&genesis_transaction.events)?,
+                })
+            },
+            transaction::transaction::TxnData::BlockMetadata(block_metadata) => {
+                Transaction::BlockMetadataTransaction(BlockMetadataTransaction {
+                    info,
+                    id: block_metadata.id.parse()?,
+                    epoch: transaction.epoch.into(),
+                    round: block_metadata.round.into(),
+                    events: convert_events(&block_metadata.events)?,
+                    previous_block_votes_bitvec: block_metadata.previous_block_votes_bitvec.clone(),
+                    proposer: block_metadata.proposer.parse()?,
+                    failed_proposer_indices: block_metadata.failed_proposer_indices.clone(),
+                    timestamp,
+                })
+            },
+            transaction::transaction::TxnData::StateCheckpoint(_) => {
+                Transaction::StateCheckpointTransaction(StateCheckpointTransaction {
+                    info,
+                    timestamp,
+                })
+            },
+        },
+    )
+}
+
+pub fn convert_transaction_info(transaction: &transaction::Transaction) -> Result<TransactionInfo> {
+    let info = transaction
+        .info
+        .as_ref()
+        .context("Transaction info is missing")?;
+    Ok(TransactionInfo {
+        version: transaction.version.into(),
+        hash: convert_hash(&info.hash)?,
+        state_change_hash: convert_hash(&info.state_change_hash)?,
+        event_root_hash: convert_hash(&info.event_root_hash)?,
+        state_checkpoint_hash: info
+            .state_checkpoint_hash
+            .as_deref()
+            .map(convert_hash)
+            .transpose()?,
+        gas_used: info.gas_used.into(),
+        success: info.success,
+        vm_status: info.vm_status.clone(),
+        accumulator_root_hash: convert_hash(&info.accumulator_root_hash)?,
+        changes: convert_write_set_changes(&info.changes)?,
+        block_height: Some(transaction.block_height.into()),
+        epoch: Some(transaction.epoch.into()),
+    })
+}
+
+pub fn convert_user_transaction_request(
+    request: &transaction::UserTransactionRequest,
+) -> Result<UserTransactionRequest> {
+    Ok(UserTransactionRequest {
+        sender: request.sender.parse()?,
+        sequence_number: request.sequence_number.into(),
+        max_gas_amount: request.max_gas_amount.into(),
+        gas_unit_price: request.gas_unit_price.into(),
+        expiration_timestamp_secs: request
+            .expiration_timestamp_secs
+            .as_ref()
+            .map_or(0, |timestamp| timestamp.seconds as u64)
+            .into(),
+        payload: convert_transaction_payload(
+            request
+                .payload
+                .as_ref()
+                .context("Transaction payload is missing")?,
+        )?,
+        signature: request
+            .signature
+            .as_ref()
+            .map(convert_transaction_signature)
+            .transpose()?
+            .flatten(),
+    })
+}
+
+pub fn convert_transaction_payload(
+    payload: &transaction::TransactionPayload,
+) -> Result<TransactionPayload> {
+    Ok(
+        match payload
+            .payload
+            .as_ref()
+            .context("Transaction payload is missing")?
+        {
+            transaction::transaction_payload::Payload::EntryFunctionPayload(payload) => {
+                TransactionPayload::EntryFunctionPayload(convert_entry_function_payload(payload)?)
+            },
+            transaction::transaction_payload::Payload::ScriptPayload(payload) => {
+                TransactionPayload::ScriptPayload(convert_script_payload(payload)?)
+            },
+            // Deprecated. Will be removed in the future.
+            transaction::transaction_payload::Payload::ModuleBundlePayload(payload) => {
+                TransactionPayload::ModuleBundlePayload(ModuleBundlePayload {
+                    modules: payload
+                        .modules
+                        .iter()
+                        .map(convert_move_module_bytecode)
+                        .collect(),
+                })
+            },
+            transaction::transaction_payload::Payload::MultisigPayload(payload) => {
+                TransactionPayload::MultisigPayload(convert_multisig_payload(payload)?)
+            },
+            transaction::transaction_payload::Payload::WriteSetPayload(_) => {
+                bail!("Write set payloads are only valid in the genesis transaction")
+            },
+        },
+    )
+}
+
+pub fn convert_entry_function_payload(
+    payload: &transaction::EntryFunctionPayload,
+) -> Result<EntryFunctionPayload> {
+    Ok(EntryFunctionPayload {
+        function: payload.entry_function_id_str.parse()?,
+        type_arguments: convert_move_types(&payload.type_arguments)?,
+        arguments: convert_arguments(&payload.arguments)?,
+    })
+}
+
+pub fn convert_script_payload(payload: &transaction::ScriptPayload) -> Result<ScriptPayload> {
+    let code = payload.code.as_ref().context("Script code is missing")?;
+    Ok(ScriptPayload {
+        code: MoveScriptBytecode::new(code.bytecode.clone()).try_parse_abi(),
+        type_arguments: convert_move_types(&payload.type_arguments)?,
+        arguments: convert_arguments(&payload.arguments)?,
+    })
+}
+
+pub fn convert_multisig_payload(payload: &transaction::MultisigPayload) -> Result<MultisigPayload> {
+    let transaction_payload = match &payload.transaction_payload {
+        None => None,
+        Some(transaction_payload) => Some(
+            match transaction_payload
+                .payload
+                .as_ref()
+                .context("Multisig transaction payload is missing")?
+            {
+                transaction::multisig_transaction_payload::Payload::EntryFunctionPayload(
+                    payload,
+                ) => MultisigTransactionPayload::EntryFunctionPayload(
+                    convert_entry_function_payload(payload)?,
+                ),
+            },
+        ),
+    };
+    Ok(MultisigPayload {
+        multisig_address: payload.multisig_address.parse()?,
+        transaction_payload,
+    })
+}
+
+/// Arguments are stored as their JSON representation.
+fn convert_arguments(arguments: &[String]) -> Result<Vec<serde_json::Value>> {
+    arguments
+        .iter()
+        .map(|argument| {
+            serde_json::from_str(argument).with_context(|| format!("Invalid argument {}", argument))
+        })
+        .collect()
+}
+
+/// Like the API, the ABI is parsed from the bytecode, and left out if it can't be parsed.
+pub fn convert_move_module_bytecode(
+    move_module_bytecode: &transaction::MoveModuleBytecode,
+) -> MoveModuleBytecode {
+    let move_module_bytecode = MoveModuleBytecode::new(move_module_bytecode.bytecode.clone());
+    move_module_bytecode
+        .clone()
+        .try_parse_abi()
+        .unwrap_or(move_module_bytecode)
+}
+
+pub fn convert_write_set(write_set: &transaction::WriteSet) -> Result<WriteSet> {
+    Ok(
+        match write_set
+            .write_set
+            .as_ref()
+            .context("Write set is missing")?
+        {
+            transaction::write_set::WriteSet::ScriptWriteSet(script_write_set) => {
+                WriteSet::ScriptWriteSet(ScriptWriteSet {
+                    execute_as: script_write_set.execute_as.parse()?,
+                    script: convert_script_payload(
+                        script_write_set
+                            .script
+                            .as_ref()
+                            .context("Script of the write set is missing")?,
+                    )?,
+                })
+            },
+            transaction::write_set::WriteSet::DirectWriteSet(direct_write_set) => {
+                WriteSet::DirectWriteSet(DirectWriteSet {
+                    changes: convert_write_set_changes(&direct_write_set.write_set_change)?,
+                    events: convert_events(&direct_write_set.events)?,
+                })
+            },
+        },
+    )
+}
+
+#[inline]
+pub fn convert_write_set_changes(
+    changes: &[transaction::WriteSetChange],
+) -> Result<Vec<WriteSetChange>> {
+    changes.iter().map(convert_write_set_change).collect()
+}
+
+pub fn convert_write_set_change(change: &transaction::WriteSetChange) -> Result<WriteSetChange> {
+    Ok(
+        match change
+            .change
+            .as_ref()
+            .context("Write set change is missing")?
+        {
+            transaction::write_set_change::Change::DeleteModule(delete_module) => {
+                let module = delete_module
+                    .module
+                    .as_ref()
+                    .context("Deleted module id is missing")?;
+                WriteSetChange::DeleteModule(DeleteModule {
+                    address: delete_module.address.parse()?,
+                    state_key_hash: convert_state_key_hash(&delete_module.state_key_hash),
+                    module: MoveModuleId {
+                        address: module.address.parse()?,
+                        name: module.name.parse()?,
+                    },
+                })
+            },
+            transaction::write_set_change::Change::DeleteResource(delete_resource) => {
+                WriteSetChange::DeleteResource(DeleteResource {
+                    address: delete_resource.address.parse()?,
+                    state_key_hash: convert_state_key_hash(&delete_resource.state_key_hash),
+                    resource: delete_resource.type_str.parse()?,
+                })
+            },
+            transaction::write_set_change::Change::DeleteTableItem(delete_table_item) => {
+                let data = match &delete_table_item.data {
+                    None => None,
+                    Some(data) => Some(DeletedTableData {
+                        key: serde_json::from_str(&data.key)?,
+                        key_type: data.key_type.clone(),
+                    }),
+                };
+                WriteSetChange::DeleteTableItem(DeleteTableItem {
+                    state_key_hash: convert_state_key_hash(&delete_table_item.state_key_hash),
+                    handle: delete_table_item.handle.parse()?,
+                    key: delete_table_item.key.parse()?,
+                    data,
+                })
+            },
+            transaction::write_set_change::Change::WriteModule(write_module) => {
+                WriteSetChange::WriteModule(WriteModule {
+                    address: write_module.address.parse()?,
+                    state_key_hash: convert_state_key_hash(&write_module.state_key_hash),
+                    data: convert_move_module_bytecode(
+                        write_module
+                            .data
+                            .as_ref()
+                            .context("Module bytecode is missing")?,
+                    ),
+                })
+            },
+            transaction::write_set_change::Change::WriteResource(write_resource) => {
+                WriteSetChange::WriteResource(WriteResource {
+                    address: write_resource.address.parse()?,
+                    state_key_hash: convert_state_key_hash(&write_resource.state_key_hash),
+                    data: MoveResource {
+                        typ: write_resource.type_str.parse()?,
+                        data: serde_json::from_str(&write_resource.data)?,
+                    },
+                })
+            },
+            transaction::write_set_change::Change::WriteTableItem(write_table_item) => {
+                let data = match &write_table_item.data {
+                    None => None,
+                    Some(data) => Some(DecodedTableData {
+                        key: serde_json::from_str(&data.key)?,
+                        key_type: data.key_type.clone(),
+                        value: serde_json::from_str(&data.value)?,
+                        value_type: data.value_type.clone(),
+                    }),
+                };
+                WriteSetChange::WriteTableItem(WriteTableItem {
+                    state_key_hash: convert_state_key_hash(&write_table_item.state_key_hash),
+                    handle: write_table_item.handle.parse()?,
+                    key: write_table_item.key.parse()?,
+                    // The raw value isn't part of the protobuf schema
+                    value: HexEncodedBytes(vec![]),
+                    data,
+                })
+            },
+        },
+    )
+}
+
+#[inline]
+pub fn convert_events(events: &[transaction::Event]) -> Result<Vec<Event>> {
+    events.iter().map(convert_event).collect()
+}
+
+pub fn convert_event(event: &transaction::Event) -> Result<Event> {
+    let key = event.key.as_ref().context("Event key is missing")?;
+    Ok(Event {
+        guid: EventGuid {
+            creation_number: key.creation_number.into(),
+            account_address: key.account_address.parse()?,
+        },
+        sequence_number: event.sequence_number.into(),
+        typ: event.type_str.parse()?,
+        data: serde_json::from_str(&event.data)?,
+    })
+}
+
+#[inline]
+fn convert_move_types(move_types: &[transaction::MoveType]) -> Result<Vec<MoveType>> {
+    move_types.iter().map(convert_move_type).collect()
+}
+
+pub fn convert_move_type(move_type: &transaction::MoveType) -> Result<MoveType> {
+    use transaction::{move_type::Content, MoveTypes};
+
+    let r#type = MoveTypes::from_i32(move_type.r#type)
+        .with_context(|| format!("Unknown move type {}", move_type.r#type))?;
+    Ok(match (r#type, &move_type.content) {
+        (MoveTypes::Bool, None) => MoveType::Bool,
+        (MoveTypes::U8, None) => MoveType::U8,
+        (MoveTypes::U16, None) => MoveType::U16,
+        (MoveTypes::U32, None) => MoveType::U32,
+        (MoveTypes::U64, None) => MoveType::U64,
+        (MoveTypes::U128, None) => MoveType::U128,
+        (MoveTypes::U256, None) => MoveType::U256,
+        (MoveTypes::Address, None) => MoveType::Address,
+        (MoveTypes::Signer, None) => MoveType::Signer,
+        (MoveTypes::Vector, Some(Content::Vector(items))) => MoveType::Vector {
+            items: Box::new(convert_move_type(items)?),
+        },
+        (MoveTypes::Struct, Some(Content::Struct(struct_tag))) => {
+            MoveType::Struct(convert_move_struct_tag(struct_tag)?)
+        },
+        (MoveTypes::GenericTypeParam, Some(Content::GenericTypeParamIndex(index))) => {
+            MoveType::GenericTypeParam {
+                index: u16::try_from(*index)?,
+            }
+        },
+        (MoveTypes::Reference, Some(Content::Reference(reference))) => MoveType::Reference {
+            mutable: reference.mutable,
+            to: Box::new(convert_move_type(
+                reference
+                    .to
+                    .as_ref()
+                    .context("Referenced type is missing")?,
+            )?),
+        },
+        (MoveTypes::Unparsable, Some(Content::Unparsable(string))) => {
+            MoveType::Unparsable(string.clone())
+        },
+        (r#type, content) => bail!("Invalid content {:?} for move type {:?}", content, r#type),
+    })
+}
+
+pub fn convert_move_struct_tag(struct_tag: &transaction::MoveStructTag) -> Result<MoveStructTag> {
+    Ok(MoveStructTag {
+        address: struct_tag.address.parse()?,
+        module: struct_tag.module.parse()?,
+        name: struct_tag.name.parse()?,
+        generic_type_params: convert_move_types(&struct_tag.generic_type_params)?,
+    })
+}
+
+/// Returns None for signatures the protobuf schema doesn't support.
+pub fn convert_transaction_signature(
+    signature: &transaction::Signature,
+) -> Result<Option<TransactionSignature>> {
+    Ok(match &signature.signature {
+        None => None,
+        Some(transaction::signature::Signature::Ed25519(signature)) => Some(
+            TransactionSignature::Ed25519Signature(convert_ed25519_signature(signature)),
+        ),
+        Some(transaction::signature::Signature::MultiEd25519(signature)) => {
+            Some(TransactionSignature::MultiEd25519Signature(
+                convert_multi_ed25519_signature(signature)?,
+            ))
+        },
+        Some(transaction::signature::Signature::MultiAgent(signature)) => Some(
+            TransactionSignature::MultiAgentSignature(MultiAgentSignature {
+                sender: convert_account_signature(
+                    signature
+                        .sender
+                        .as_ref()
+                        .context("Sender signature is missing")?,
+                )?,
+                secondary_signer_addresses: signature
+                    .secondary_signer_addresses
+                    .iter()
+                    .map(|address| address.parse())
+                    .collect::<Result<_>>()?,
+                secondary_signers: signature
+                    .secondary_signers
+                    .iter()
+                    .map(convert_account_signature)
+                    .collect::<Result<_>>()?,
+            }),
+        ),
+    })
+}
+
+pub fn convert_account_signature(
+    account_signature: &transaction::AccountSignature,
+) -> Result<AccountSignature> {
+    Ok(
+        match account_signature
+            .signature
+            .as_ref()
+            .context("The account signature scheme isn't supported by the protobuf schema")?
+        {
+            transaction::account_signature::Signature::Ed25519(signature) => {
+                AccountSignature::Ed25519Signature(convert_ed25519_signature(signature))
+            },
+            transaction::account_signature::Signature::MultiEd25519(signature) => {
+                AccountSignature::MultiEd25519Signature(convert_multi_ed25519_signature(signature)?)
+            },
+        },
+    )
+}
+
+pub fn convert_ed25519_signature(signature: &transaction::Ed25519Signature) -> Ed25519Signature {
+    Ed25519Signature {
+        public_key: HexEncodedBytes(signature.public_key.clone()),
+        signature: HexEncodedBytes(signature.signature.clone()),
+    }
+}
+
+pub fn convert_multi_ed25519_signature(
+    signature: &transaction::MultiEd25519Signature,
+) -> Result<MultiEd25519Signature> {
+    let mut bitmap = BitVec::with_num_bits(MULTI_ED25519_BITMAP_NUM_OF_BITS);
+    for index in &signature.public_key_indices {
+        bitmap.set(u16::try_from(*index)?);
+    }
+    Ok(MultiEd25519Signature {
+        public_keys: signature
+            .public_keys
+            .iter()
+            .cloned()
+            .map(HexEncodedBytes)
+            .collect(),
+        signatures: signature
+            .signatures
+            .iter()
+            .cloned()
+            .map(HexEncodedBytes)
+            .collect(),
+        threshold: u8::try_from(signature.threshold)?,
+        bitmap: HexEncodedBytes(bitmap.into()),
+    })
+}
+
+#[inline]
+fn convert_hash(hash: &[u8]) -> Result<HashValue> {
+    hex::encode(hash).parse()
+}
+
+#[inline]
+fn convert_state_key_hash(state_key_hash: &[u8]) -> String {
+    format!("0x{}", hex::encode(state_key_hash))
+}
+
+#[inline]
+fn convert_timestamp_usecs(timestamp: Option<&timestamp::Timestamp>) -> U64 {
+    timestamp
+        .map_or(0, |timestamp| {
+            timestamp.seconds as u64 * 1_000_000 + timestamp.nanos as u64 / 1_000
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_grpc_fullnode::convert::convert_transaction as convert_to_protobuf;
+
+    /// The raw table item values are dropped by the protobuf schema.
+    fn without_raw_table_values(mut transaction: Transaction) -> Transaction {
+        if let Transaction::UserTransaction(user_transaction) = &mut transaction {
+            for change in user_transaction.info.changes.iter_mut() {
+                if let WriteSetChange::WriteTableItem(write_table_item) = change {
+                    write_table_item.value = HexEncodedBytes(vec![]);
+                }
+            }
+        }
+        transaction
+    }
+
+    fn assert_round_trip(transaction: Transaction) {
+        let info = transaction.transaction_info().unwrap();
+        let encoded = base64::encode(
+            convert_to_protobuf(
+                &transaction,
+                info.block_height.unwrap().0,
+                info.epoch.unwrap().0,
+            )
+            .encode_to_vec(),
+        );
+        assert_eq!(
+            decode_transaction(&encoded).unwrap(),
+            without_raw_table_values(transaction)
+        );
+    }
+
+    #[test]
+    fn test_round_trip_fixtures() {
+        let transactions: Vec<Transaction> = [
+            include_str!("../../fixtures/multisig_transactions.json"),
+            include_str!("../../fixtures/fungible_asset_transfer.json"),
+        ]
+        .iter()
+        .flat_map(|fixture| serde_json::from_str::<Vec<Transaction>>(fixture).unwrap())
+        .chain(
+            [
+                include_str!("../../fixtures/delegation_pool_unlock.json"),
+                include_str!("../../fixtures/delegation_pool_withdraw.json"),
+            ]
+            .iter()
+            .map(|fixture| serde_json::from_str::<Transaction>(fixture).unwrap()),
+        )
+        .collect();
+        assert!(!transactions.is_empty());
+        for transaction in transactions {
+            assert_round_trip(transaction);
+        }
+    }
+
+    #[test]
+    fn test_convert_multi_ed25519_signature() {
+        let signature = convert_multi_ed25519_signature(&transaction::MultiEd25519Signature {
+            public_keys: vec![vec![1; 32], vec![2; 32], vec![3; 32]],
+            signatures: vec![vec![4; 64], vec![5; 64]],
+            threshold: 2,
+            public_key_indices: vec![0, 2],
+        })
+        .unwrap();
+        assert_eq!(signature.threshold, 2);
+        assert_eq!(signature.bitmap.0, vec![0b1010_0000, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_invalid_transaction() {
+        assert!(decode_transaction("not base64!").is_err());
+        let transaction = transaction::Transaction {
+            version: 1,
+            ..Default::default()
+        };
+        assert!(decode_transaction(&base64::encode(transaction.encode_to_vec())).is_err());
+    }
+}
@@ -211,7 +211,8 @@ async fn fetch_raw_txns_with_retries(
     }
 }
 
-async fn fetch_nexts(
+/// Fetches and converts up to `num_transactions_to_fetch` transactions starting at `starting_version`
+pub(crate) async fn fetch_nexts(
     context: Arc<Context>,
     starting_version: u64,
     ledger_version: u64,
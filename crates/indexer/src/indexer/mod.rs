@@ -1,8 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod convert;
 pub mod errors;
 pub mod fetcher;
 pub mod processing_result;
+pub mod reprocessor;
 pub mod tailer;
 pub mod transaction_processor;
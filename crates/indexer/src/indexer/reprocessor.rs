@@ -0,0 +1,501 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::PgDbPool,
+    indexer::{
+        convert::decode_transaction, fetcher::fetch_nexts,
+        transaction_processor::TransactionProcessor,
+    },
+    models::ledger_info::LedgerInfo,
+};
+use anyhow::{ensure, Context, Result};
+use aptos_api::context::Context as ApiContext;
+use aptos_api_types::Transaction;
+use aptos_indexer_grpc_utils::file_store_operator::FileStoreOperator;
+use aptos_logger::info;
+use diesel::{result::Error, sql_query, sql_types::BigInt, RunQueryDsl};
+use std::{fmt::Debug, sync::Arc};
+
+/// Where the reprocessor fetches the transactions of the range from.
+#[derive(Clone)]
+pub enum ReprocessorSource {
+    /// The node the indexer runs in. Versions it has pruned can't be reprocessed.
+    Node(Arc<ApiContext>),
+    /// An indexer-grpc file store, which keeps every version it has uploaded.
+    FileStore(Arc<FileStoreOperator>),
+}
+
+impl ReprocessorSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReprocessorSource::Node(_) => "node",
+            ReprocessorSource::FileStore(_) => "file_store",
+        }
+    }
+}
+
+/// Reprocesses a version range that a processor has already indexed, e.g. after a bug fix or to repair rows
+/// written from a bad node. The processor's rows in the range are deleted, then the range is fetched from the
+/// source again and processed in order.
+///
+/// This can run alongside the `Tailer` as long as the range ends before the versions the tailer is processing.
+/// Current tables only accept rows from newer versions, so replaying an old range never overwrites what the
+/// tailer writes.
+#[derive(Clone)]
+pub struct Reprocessor {
+    source: ReprocessorSource,
+    processor: Arc<dyn TransactionProcessor>,
+    connection_pool: PgDbPool,
+    batch_size: u16,
+}
+
+impl Reprocessor {
+    pub fn new(
+        source: ReprocessorSource,
+        connection_pool: PgDbPool,
+        processor: Arc<dyn TransactionProcessor>,
+        batch_size: u16,
+    ) -> Self {
+        Self {
+            source,
+            processor,
+            connection_pool,
+            batch_size,
+        }
+    }
+
+    /// Deletes the processor's rows in the range and processes it again, inclusive on both ends.
+    /// Running it again for the same range gives the same result, so a failed run can simply be restarted.
+    pub async fn reprocess(&self, start_version: u64, end_version: u64) -> Result<()> {
+        ensure!(
+            start_version <= end_version,
+            "Start version {} is after end version {}",
+            start_version,
+            end_version
+        );
+        // Check the whole range is available before deleting anything
+        self.check_range_available(start_version, end_version)
+            .await?;
+
+        self.delete_derived_rows(start_version, end_version)?;
+
+        let mut version = start_version;
+        while version <= end_version {
+            let num_transactions_to_fetch =
+                std::cmp::min(self.batch_size as u64, end_version - version + 1) as u16;
+            let transactions = self
+                .fetch_transactions(version, num_transactions_to_fetch)
+                .await?;
+            let processing_result = self
+                .processor
+                .process_transactions_with_status(transactions)
+                .await
+                .map_err(|tpe| {
+                    let (err, batch_start_version, batch_end_version, _) = tpe.inner();
+                    anyhow::anyhow!(
+                        "Failed to reprocess versions {} to {}: {:?}",
+                        batch_start_version,
+                        batch_end_version,
+                        err
+                    )
+                })?;
+            info!(
+                processor_name = self.processor.name(),
+                source = self.source.name(),
+                batch_start_version = processing_result.start_version,
+                batch_end_version = processing_result.end_version,
+                end_version = end_version,
+                "Reprocessed batch"
+            );
+            version = processing_result.end_version + 1;
+        }
+        info!(
+            processor_name = self.processor.name(),
+            start_version = start_version,
+            end_version = end_version,
+            "Finished reprocessing"
+        );
+        Ok(())
+    }
+
+    async fn check_range_available(&self, start_version: u64, end_version: u64) -> Result<()> {
+        match &self.source {
+            ReprocessorSource::Node(context) => {
+                let ledger_info = context
+                    .get_latest_ledger_info_wrapped()
+                    .context("Failed to get ledger info")?;
+                let ledger_version = ledger_info.ledger_version.0;
+                let oldest_ledger_version = ledger_info.oldest_ledger_version.0;
+                ensure!(
+                    end_version <= ledger_version,
+                    "End version {} is past the latest ledger version {}",
+                    end_version,
+                    ledger_version
+                );
+                // Fetching pruned transactions panics
+                ensure!(
+                    start_version >= oldest_ledger_version,
+                    "Start version {} is pruned, the oldest ledger version is {}",
+                    start_version,
+                    oldest_ledger_version
+                );
+            },
+            ReprocessorSource::FileStore(file_store) => {
+                let metadata = file_store
+                    .get_file_store_metadata()
+                    .await
+                    .context("The file store has no metadata, it is empty")?;
+                ensure!(
+                    end_version < metadata.version,
+                    "End version {} is not in the file store yet, it has the versions before {}",
+                    end_version,
+                    metadata.version
+                );
+                // The tailer checks the chain of the node, the file store may be for any chain
+                let mut conn = self.connection_pool.get()?;
+                if let Some(ledger_info) = LedgerInfo::get(&mut conn)? {
+                    ensure!(
+                        ledger_info.chain_id as u64 == metadata.chain_id,
+                        "Wrong chain detected! The file store is for chain {} but existing data is for chain {}",
+                        metadata.chain_id,
+                        ledger_info.chain_id
+                    );
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Fetches up to `num_transactions` transactions from `version` on. The range must be available.
+    async fn fetch_transactions(
+        &self,
+        version: u64,
+        num_transactions: u16,
+    ) -> Result<Vec<Transaction>> {
+        match &self.source {
+            ReprocessorSource::Node(context) => {
+                let ledger_version = context
+                    .get_latest_ledger_info_wrapped()
+                    .context("Failed to get ledger info")?
+                    .ledger_version
+                    .0;
+                Ok(fetch_nexts(context.clone(), version, ledger_version, num_transactions).await)
+            },
+            ReprocessorSource::FileStore(file_store) => {
+                // Files hold a fixed number of versions, so the batch stops at the end of the file
+                let transactions = file_store
+                    .get_transactions(version)
+                    .await?
+                    .iter()
+                    .take(num_transactions as usize)
+                    .map(|encoded_transaction| decode_transaction(encoded_transaction))
+                    .collect::<Result<Vec<_>>>()?;
+                let first_version = transactions.first().and_then(|txn| txn.version());
+                ensure!(
+                    first_version == Some(version),
+                    "Expected version {} from the file store, got {:?}",
+                    version,
+                    first_version
+                );
+                Ok(transactions)
+            },
+        }
+    }
+
+    /// Deletes everything the processor wrote for the range in a single transaction, so that readers never see
+    /// a partially deleted range and retrying after a failure is safe.
+    /// Current tables lose the rows last written in the range; replaying the range writes them back.
+    pub fn delete_derived_rows(&self, start_version: u64, end_version: u64) -> Result<()> {
+        let mut conn = self.connection_pool.get()?;
+        conn.build_transaction()
+            .read_write()
+            .run::<_, Error, _>(|pg_conn| {
+                for (table, version_column) in self.processor.derived_tables() {
+                    let deleted = sql_query(format!(
+                        "DELETE FROM {} WHERE {} BETWEEN $1 AND $2",
+                        table, version_column
+                    ))
+                    .bind::<BigInt, _>(start_version as i64)
+                    .bind::<BigInt, _>(end_version as i64)
+                    .execute(pg_conn)?;
+                    info!(
+                        processor_name = self.processor.name(),
+                        table = table,
+                        start_version = start_version,
+                        end_version = end_version,
+                        deleted = deleted,
+                        "Deleted rows to reprocess"
+                    );
+                }
+                Ok(())
+            })
+            .context(format!(
+                "Failed to delete rows of {} from versions {} to {}",
+                self.processor.name(),
+                start_version,
+                end_version
+            ))
+    }
+}
+
+impl Debug for Reprocessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Reprocessor {{ source: {}, processor: {}, batch_size: {} }}",
+            self.source.name(),
+            self.processor.name(),
+            self.batch_size
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        database::{new_db_pool, PgPoolConnection},
+        indexer::tailer::MIGRATIONS,
+        processors::{
+            coin_processor::CoinTransactionProcessor,
+            default_processor::DefaultTransactionProcessor,
+            fungible_asset_processor::FungibleAssetTransactionProcessor,
+            multisig_processor::MultisigTransactionProcessor,
+            stake_processor::StakeTransactionProcessor, token_processor::TokenTransactionProcessor,
+        },
+    };
+    use aptos_api_test_context::new_test_context;
+    use aptos_indexer_grpc_utils::{
+        config::IndexerGrpcFileStoreConfig,
+        constants::BLOB_STORAGE_SIZE,
+        file_store_operator::{FileStore, FileStoreMetadata, LocalFileStore},
+    };
+    use aptos_protos::{transaction::testing1::v1 as transaction, util::timestamp::Timestamp};
+    use diesel::QueryableByName;
+    use diesel_migrations::MigrationHarness;
+    use prost::Message;
+
+    const FILE_STORE_CHAIN_ID: u64 = 4;
+
+    #[derive(Debug, QueryableByName)]
+    struct Count {
+        #[diesel(sql_type = BigInt)]
+        count: i64,
+    }
+
+    fn wipe_database(conn: &mut PgPoolConnection) {
+        for command in [
+            "DROP SCHEMA public CASCADE",
+            "CREATE SCHEMA public",
+            "GRANT ALL ON SCHEMA public TO postgres",
+            "GRANT ALL ON SCHEMA public TO public",
+        ] {
+            sql_query(command).execute(conn).unwrap();
+        }
+    }
+
+    fn setup_database() -> PgDbPool {
+        let database_url = std::env::var("INDEXER_DATABASE_URL")
+            .expect("must set 'INDEXER_DATABASE_URL' to run tests!");
+        let conn_pool = new_db_pool(database_url.as_str()).unwrap();
+        let mut conn = conn_pool.get().unwrap();
+        wipe_database(&mut conn);
+        conn.run_pending_migrations(MIGRATIONS).unwrap();
+        conn_pool
+    }
+
+    fn count_rows(conn_pool: &PgDbPool, table: &str) -> i64 {
+        sql_query(format!("SELECT COUNT(*) AS count FROM {}", table))
+            .get_result::<Count>(&mut conn_pool.get().unwrap())
+            .unwrap()
+            .count
+    }
+
+    fn count_derived_rows(
+        conn_pool: &PgDbPool,
+        processor: &dyn TransactionProcessor,
+    ) -> Vec<(&'static str, i64)> {
+        processor
+            .derived_tables()
+            .iter()
+            .map(|(table, _)| (*table, count_rows(conn_pool, table)))
+            .collect()
+    }
+
+    fn encoded_state_checkpoint(version: u64) -> (String, u64) {
+        // Hashes are unique per transaction
+        let hash = [vec![0; 24], version.to_be_bytes().to_vec()].concat();
+        let transaction = transaction::Transaction {
+            timestamp: Some(Timestamp {
+                seconds: 1683200000,
+                nanos: 0,
+            }),
+            version,
+            info: Some(transaction::TransactionInfo {
+                hash: hash.clone(),
+                state_change_hash: hash.clone(),
+                event_root_hash: hash.clone(),
+                state_checkpoint_hash: Some(hash.clone()),
+                gas_used: 0,
+                success: true,
+                vm_status: "Executed successfully".to_string(),
+                accumulator_root_hash: hash,
+                changes: vec![],
+            }),
+            epoch: 1,
+            block_height: version,
+            r#type: transaction::transaction::TransactionType::StateCheckpoint as i32,
+            txn_data: Some(transaction::transaction::TxnData::StateCheckpoint(
+                transaction::StateCheckpointTransaction {},
+            )),
+        };
+        (base64::encode(transaction.encode_to_vec()), version)
+    }
+
+    /// Creates a local file store with the first file of versions uploaded.
+    async fn setup_file_store(dir: &tempfile::TempDir) -> Arc<FileStoreOperator> {
+        let mut file_store_operator =
+            FileStoreOperator::new(IndexerGrpcFileStoreConfig::LocalFileStore {
+                local_file_store_path: dir.path().to_path_buf(),
+            });
+        file_store_operator
+            .upload_transactions(
+                FILE_STORE_CHAIN_ID,
+                (0..BLOB_STORAGE_SIZE as u64)
+                    .map(encoded_state_checkpoint)
+                    .collect(),
+            )
+            .await
+            .unwrap();
+        // The operator throttles metadata updates, so write it directly
+        LocalFileStore::new(dir.path().to_path_buf())
+            .put_blob(
+                "metadata.json",
+                serde_json::to_vec(&FileStoreMetadata::new(
+                    FILE_STORE_CHAIN_ID,
+                    BLOB_STORAGE_SIZE as u64,
+                ))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        Arc::new(file_store_operator)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reprocess_twice() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let conn_pool = setup_database();
+        let context = Arc::new(new_test_context("doesnt_matter".to_string(), true).context);
+        let processor = Arc::new(DefaultTransactionProcessor::new(conn_pool.clone()));
+        let reprocessor = Reprocessor::new(
+            ReprocessorSource::Node(context),
+            conn_pool.clone(),
+            processor.clone(),
+            10,
+        );
+
+        // Genesis is the only transaction in the test context
+        reprocessor.reprocess(0, 0).await.unwrap();
+        let rows = count_derived_rows(&conn_pool, processor.as_ref());
+        assert!(rows.contains(&("transactions", 1)));
+        assert!(rows
+            .iter()
+            .any(|(table, count)| *table == "write_set_changes" && *count > 0));
+
+        // Deleting and replaying again yields exactly the same rows
+        reprocessor.reprocess(0, 0).await.unwrap();
+        assert_eq!(count_derived_rows(&conn_pool, processor.as_ref()), rows);
+
+        // The deletion alone removes every row of the range
+        reprocessor.delete_derived_rows(0, 0).unwrap();
+        assert!(count_derived_rows(&conn_pool, processor.as_ref())
+            .iter()
+            .all(|(_, count)| *count == 0));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reprocess_invalid_range() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let conn_pool = setup_database();
+        let context = Arc::new(new_test_context("doesnt_matter".to_string(), true).context);
+        let processor = Arc::new(DefaultTransactionProcessor::new(conn_pool.clone()));
+        let reprocessor =
+            Reprocessor::new(ReprocessorSource::Node(context), conn_pool, processor, 10);
+
+        assert!(reprocessor.reprocess(1, 0).await.is_err());
+        assert!(reprocessor.reprocess(0, u64::MAX).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reprocess_from_file_store() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let conn_pool = setup_database();
+        let dir = tempfile::tempdir().unwrap();
+        let file_store = setup_file_store(&dir).await;
+        let processor = Arc::new(DefaultTransactionProcessor::new(conn_pool.clone()));
+        let reprocessor = Reprocessor::new(
+            ReprocessorSource::FileStore(file_store),
+            conn_pool.clone(),
+            processor,
+            10,
+        );
+
+        // The range spans several batches
+        reprocessor.reprocess(95, 120).await.unwrap();
+        assert_eq!(count_rows(&conn_pool, "transactions"), 26);
+
+        // Versions that aren't uploaded yet are rejected before anything is deleted
+        assert!(reprocessor.reprocess(95, 1000).await.is_err());
+        assert_eq!(count_rows(&conn_pool, "transactions"), 26);
+
+        // So is a file store for another chain than the indexed one
+        sql_query(format!(
+            "INSERT INTO ledger_infos (chain_id) VALUES ({})",
+            FILE_STORE_CHAIN_ID + 1
+        ))
+        .execute(&mut conn_pool.get().unwrap())
+        .unwrap();
+        assert!(reprocessor.reprocess(95, 120).await.is_err());
+        assert_eq!(count_rows(&conn_pool, "transactions"), 26);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_derived_tables_exist() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let conn_pool = setup_database();
+        let context = Arc::new(new_test_context("doesnt_matter".to_string(), true).context);
+        let processors: Vec<Arc<dyn TransactionProcessor>> = vec![
+            Arc::new(DefaultTransactionProcessor::new(conn_pool.clone())),
+            Arc::new(TokenTransactionProcessor::new(
+                conn_pool.clone(),
+                None,
+                None,
+            )),
+            Arc::new(CoinTransactionProcessor::new(conn_pool.clone())),
+            Arc::new(StakeTransactionProcessor::new(conn_pool.clone())),
+            Arc::new(FungibleAssetTransactionProcessor::new(conn_pool.clone())),
+            Arc::new(MultisigTransactionProcessor::new(conn_pool.clone())),
+        ];
+        // Every table and version column must match the schema, or the deletion fails
+        for processor in processors {
+            Reprocessor::new(
+                ReprocessorSource::Node(context.clone()),
+                conn_pool.clone(),
+                processor,
+                10,
+            )
+            .delete_derived_rows(0, 100)
+            .unwrap();
+        }
+    }
+}
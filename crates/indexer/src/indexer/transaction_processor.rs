@@ -25,6 +25,10 @@ pub trait TransactionProcessor: Send + Sync + Debug {
     /// This will get stored in the database for each (`TransactionProcessor`, transaction_version) pair
     fn name(&self) -> &'static str;
 
+    /// Tables this processor writes to, each with the column holding the version that wrote the row.
+    /// Reprocessing a version range deletes the rows in these tables before replaying it.
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)];
+
     /// Process all transactions within a block and processes it. This method will be called from `process_transaction_with_status`
     /// In case a transaction cannot be processed, we will fail the entire block.
     async fn process_transactions(
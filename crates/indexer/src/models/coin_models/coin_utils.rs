@@ -95,6 +95,7 @@ pub struct IntegerResource {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CoinStoreResource {
     pub coin: Coin,
+    pub frozen: bool,
     pub deposit_events: DepositEventResource,
     pub withdraw_events: WithdrawEventResource,
 }
@@ -5,4 +5,4 @@ pub mod coin_activities;
 pub mod coin_balances;
 pub mod coin_infos;
 pub mod coin_supply;
-mod coin_utils;
+pub mod coin_utils;
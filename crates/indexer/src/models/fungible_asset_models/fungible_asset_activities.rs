@@ -0,0 +1,133 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::{
+    fungible_asset_balances::{
+        get_coin_storage_id, CurrentFungibleAssetBalance, CurrentFungibleAssetBalanceMapping,
+    },
+    fungible_asset_metadata::{COIN_TOKEN_STANDARD, FUNGIBLE_ASSET_TOKEN_STANDARD},
+    fungible_asset_utils::{get_asset_type_trunc, FungibleAssetEvent},
+};
+use crate::{
+    database::PgPoolConnection,
+    models::coin_models::coin_activities::CoinActivity,
+    schema::fungible_asset_activities,
+    util::{standardize_address, truncate_str},
+};
+use anyhow::Context;
+use aptos_api_types::{Event as APIEvent, TransactionPayload, UserTransactionRequest};
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+const MAX_ENTRY_FUNCTION_LENGTH: usize = 100;
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = fungible_asset_activities)]
+pub struct FungibleAssetActivity {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub owner_address: String,
+    pub storage_id: String,
+    pub asset_type: String,
+    pub is_frozen: Option<bool>,
+    pub amount: Option<BigDecimal>,
+    pub type_: String,
+    pub is_gas_fee: bool,
+    pub is_transaction_success: bool,
+    pub entry_function_id_str: Option<String>,
+    pub block_height: i64,
+    pub token_standard: String,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl FungibleAssetActivity {
+    /// Fungible asset events are emitted by the store, so the owner and asset type come from the store's balance,
+    /// either from this batch or from the database (e.g. if the store was deleted in this transaction).
+    pub fn from_event(
+        event: &APIEvent,
+        event_index: i64,
+        txn_version: i64,
+        block_height: i64,
+        entry_function_id_str: &Option<String>,
+        txn_timestamp: chrono::NaiveDateTime,
+        balance_mapping: &CurrentFungibleAssetBalanceMapping,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<Option<Self>> {
+        let event_type = event.typ.to_string();
+        let fungible_asset_event =
+            match FungibleAssetEvent::from_event(event_type.as_str(), &event.data, txn_version)? {
+                Some(inner) => inner,
+                None => return Ok(None),
+            };
+        let storage_id = standardize_address(&event.guid.account_address.to_string());
+        let store =
+            CurrentFungibleAssetBalance::get_by_storage_id(&storage_id, balance_mapping, conn)
+                .context(format!(
+                    "Failed to get fungible store {} for event, version: {}",
+                    storage_id, txn_version
+                ))?;
+        let (amount, is_frozen) = match fungible_asset_event {
+            FungibleAssetEvent::DepositEvent(inner) => (Some(inner.amount), None),
+            FungibleAssetEvent::WithdrawEvent(inner) => (Some(inner.amount), None),
+            FungibleAssetEvent::SetUngatedTransferEvent(inner) => {
+                (None, Some(!inner.transfer_allowed))
+            },
+        };
+
+        Ok(Some(Self {
+            transaction_version: txn_version,
+            event_index,
+            owner_address: store.owner_address,
+            storage_id,
+            asset_type: store.asset_type,
+            is_frozen,
+            amount,
+            type_: event_type,
+            is_gas_fee: false,
+            is_transaction_success: true,
+            entry_function_id_str: entry_function_id_str.clone(),
+            block_height,
+            token_standard: FUNGIBLE_ASSET_TOKEN_STANDARD.to_string(),
+            transaction_timestamp: txn_timestamp,
+        }))
+    }
+
+    /// Coin activities, including gas fees, are tracked alongside fungible asset activities.
+    pub fn from_coin_activity(coin_activity: CoinActivity) -> Self {
+        Self {
+            transaction_version: coin_activity.transaction_version,
+            // Coin activities always have an event index, the gas fee has a placeholder one
+            event_index: coin_activity.event_index.unwrap(),
+            storage_id: get_coin_storage_id(&coin_activity.owner_address, &coin_activity.coin_type),
+            owner_address: coin_activity.owner_address,
+            asset_type: get_asset_type_trunc(&coin_activity.coin_type),
+            is_frozen: None,
+            amount: Some(coin_activity.amount),
+            type_: coin_activity.activity_type,
+            is_gas_fee: coin_activity.is_gas_fee,
+            is_transaction_success: coin_activity.is_transaction_success,
+            entry_function_id_str: coin_activity.entry_function_id_str,
+            block_height: coin_activity.block_height,
+            token_standard: COIN_TOKEN_STANDARD.to_string(),
+            transaction_timestamp: coin_activity.transaction_timestamp,
+        }
+    }
+
+    pub fn get_entry_function_id_str(
+        maybe_user_request: Option<&UserTransactionRequest>,
+    ) -> Option<String> {
+        match &maybe_user_request?.payload {
+            TransactionPayload::EntryFunctionPayload(payload) => Some(truncate_str(
+                &payload.function.to_string(),
+                MAX_ENTRY_FUNCTION_LENGTH,
+            )),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::{
+    fungible_asset_metadata::{COIN_TOKEN_STANDARD, FUNGIBLE_ASSET_TOKEN_STANDARD},
+    fungible_asset_utils::{get_asset_type_trunc, FungibleAssetResource},
+};
+use crate::{
+    database::PgPoolConnection,
+    models::{
+        coin_models::coin_utils::{CoinInfoType, CoinResource},
+        object_models::objects::ObjectMapping,
+    },
+    schema::{current_fungible_asset_balances, fungible_asset_balances},
+    util::{hash_str, standardize_address},
+};
+use anyhow::Context;
+use aptos_api_types::WriteResource as APIWriteResource;
+use bigdecimal::BigDecimal;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const QUERY_RETRIES: u32 = 5;
+const QUERY_RETRY_DELAY_MS: u64 = 500;
+
+type StorageId = String;
+// Latest balance of the stores written in a batch, used to complete fungible asset activities
+pub type CurrentFungibleAssetBalanceMapping = HashMap<StorageId, CurrentFungibleAssetBalance>;
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index))]
+#[diesel(table_name = fungible_asset_balances)]
+pub struct FungibleAssetBalance {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub storage_id: String,
+    pub owner_address: String,
+    pub asset_type: String,
+    pub is_primary: bool,
+    pub is_frozen: bool,
+    pub amount: BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub token_standard: String,
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(storage_id))]
+#[diesel(table_name = current_fungible_asset_balances)]
+pub struct CurrentFungibleAssetBalance {
+    pub storage_id: String,
+    pub owner_address: String,
+    pub asset_type: String,
+    pub is_primary: bool,
+    pub is_frozen: bool,
+    pub amount: BigDecimal,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+    pub last_transaction_version: i64,
+    pub token_standard: String,
+}
+
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(storage_id))]
+#[diesel(table_name = current_fungible_asset_balances)]
+pub struct CurrentFungibleAssetBalanceQuery {
+    pub storage_id: String,
+    pub owner_address: String,
+    pub asset_type: String,
+    pub is_primary: bool,
+    pub is_frozen: bool,
+    pub amount: BigDecimal,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+    pub last_transaction_version: i64,
+    pub token_standard: String,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+/// A CoinStore doesn't have an address of its own, so its storage id is derived from the owner and the coin type.
+pub fn get_coin_storage_id(owner_address: &str, coin_type: &str) -> String {
+    format!(
+        "0x{}",
+        hash_str(&format!("{}::{}", owner_address, coin_type))
+    )
+}
+
+impl FungibleAssetBalance {
+    /// Fungible stores are objects, so the owner comes from the ObjectCore written in the same resource group.
+    pub fn from_write_resource(
+        write_resource: &APIWriteResource,
+        write_set_change_index: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        object_mapping: &ObjectMapping,
+    ) -> anyhow::Result<Option<(Self, CurrentFungibleAssetBalance)>> {
+        match &FungibleAssetResource::from_write_resource(write_resource, txn_version)? {
+            Some(FungibleAssetResource::FungibleAssetStore(inner)) => {
+                let storage_id = standardize_address(&write_resource.address.to_string());
+                let owner_address = object_mapping
+                    .get(&storage_id)
+                    .map(|object| object.owner_address.clone())
+                    .context(format!(
+                        "Could not find object core of fungible store {}, version: {}",
+                        storage_id, txn_version
+                    ))?;
+                let asset_type = inner.metadata.get_reference_address();
+                let is_primary = inner.is_primary_store(&storage_id, &owner_address);
+                let is_frozen = !inner.allow_ungated_balance_transfer;
+
+                Ok(Some(Self::build(
+                    write_set_change_index,
+                    txn_version,
+                    txn_timestamp,
+                    storage_id,
+                    owner_address,
+                    asset_type,
+                    is_primary,
+                    is_frozen,
+                    inner.balance.clone(),
+                    FUNGIBLE_ASSET_TOKEN_STANDARD,
+                )))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Coin balances are tracked alongside fungible asset balances so that both can be queried per owner.
+    pub fn from_coin_write_resource(
+        write_resource: &APIWriteResource,
+        write_set_change_index: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Option<(Self, CurrentFungibleAssetBalance)>> {
+        match &CoinResource::from_write_resource(write_resource, txn_version)? {
+            Some(CoinResource::CoinStoreResource(inner)) => {
+                let coin_info_type = &CoinInfoType::from_move_type(
+                    &write_resource.data.typ.generic_type_params[0],
+                    txn_version,
+                )?;
+                let owner_address = standardize_address(&write_resource.address.to_string());
+                let coin_type = coin_info_type.get_coin_type_trunc();
+                let storage_id = get_coin_storage_id(&owner_address, &coin_type);
+
+                Ok(Some(Self::build(
+                    write_set_change_index,
+                    txn_version,
+                    txn_timestamp,
+                    storage_id,
+                    owner_address,
+                    coin_type,
+                    true,
+                    inner.frozen,
+                    inner.coin.value.clone(),
+                    COIN_TOKEN_STANDARD,
+                )))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn build(
+        write_set_change_index: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        storage_id: String,
+        owner_address: String,
+        asset_type: String,
+        is_primary: bool,
+        is_frozen: bool,
+        amount: BigDecimal,
+        token_standard: &str,
+    ) -> (Self, CurrentFungibleAssetBalance) {
+        let asset_type = get_asset_type_trunc(&asset_type);
+        (
+            Self {
+                transaction_version: txn_version,
+                write_set_change_index,
+                storage_id: storage_id.clone(),
+                owner_address: owner_address.clone(),
+                asset_type: asset_type.clone(),
+                is_primary,
+                is_frozen,
+                amount: amount.clone(),
+                transaction_timestamp: txn_timestamp,
+                token_standard: token_standard.to_string(),
+            },
+            CurrentFungibleAssetBalance {
+                storage_id,
+                owner_address,
+                asset_type,
+                is_primary,
+                is_frozen,
+                amount,
+                last_transaction_timestamp: txn_timestamp,
+                last_transaction_version: txn_version,
+                token_standard: token_standard.to_string(),
+            },
+        )
+    }
+}
+
+impl CurrentFungibleAssetBalance {
+    /// Looks for the store in the balances written earlier in the batch, and then in the database.
+    /// Retrying a few times since this store could've been written in a separate thread.
+    pub fn get_by_storage_id(
+        storage_id: &str,
+        balance_mapping: &CurrentFungibleAssetBalanceMapping,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<Self> {
+        if let Some(current_balance) = balance_mapping.get(storage_id) {
+            return Ok(current_balance.clone());
+        }
+        let mut retried = 0;
+        while retried < QUERY_RETRIES {
+            retried += 1;
+            match CurrentFungibleAssetBalanceQuery::get_by_storage_id(conn, storage_id) {
+                Ok(res) => {
+                    return Ok(Self {
+                        storage_id: res.storage_id,
+                        owner_address: res.owner_address,
+                        asset_type: res.asset_type,
+                        is_primary: res.is_primary,
+                        is_frozen: res.is_frozen,
+                        amount: res.amount,
+                        last_transaction_timestamp: res.last_transaction_timestamp,
+                        last_transaction_version: res.last_transaction_version,
+                        token_standard: res.token_standard,
+                    })
+                },
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(QUERY_RETRY_DELAY_MS));
+                },
+            }
+        }
+        Err(anyhow::anyhow!("Failed to get fungible store"))
+    }
+}
+
+impl CurrentFungibleAssetBalanceQuery {
+    pub fn get_by_storage_id(
+        conn: &mut PgPoolConnection,
+        storage_id: &str,
+    ) -> diesel::QueryResult<Self> {
+        current_fungible_asset_balances::table
+            .filter(current_fungible_asset_balances::storage_id.eq(storage_id))
+            .first::<Self>(conn)
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::fungible_asset_utils::{get_asset_type_trunc, FungibleAssetResource};
+use crate::{
+    models::coin_models::coin_infos::CoinInfo, schema::fungible_asset_metadata,
+    util::standardize_address,
+};
+use aptos_api_types::WriteResource as APIWriteResource;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+pub const COIN_TOKEN_STANDARD: &str = "v1";
+pub const FUNGIBLE_ASSET_TOKEN_STANDARD: &str = "v2";
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(asset_type))]
+#[diesel(table_name = fungible_asset_metadata)]
+pub struct FungibleAssetMetadataModel {
+    pub asset_type: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub token_standard: String,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl FungibleAssetMetadataModel {
+    /// The asset type of a fungible asset is the address of its metadata object.
+    pub fn from_write_resource(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Option<Self>> {
+        match &FungibleAssetResource::from_write_resource(write_resource, txn_version)? {
+            Some(FungibleAssetResource::FungibleAssetMetadata(inner)) => Ok(Some(Self {
+                asset_type: standardize_address(&write_resource.address.to_string()),
+                name: inner.get_name_trunc(),
+                symbol: inner.get_symbol_trunc(),
+                decimals: inner.decimals,
+                token_standard: FUNGIBLE_ASSET_TOKEN_STANDARD.to_string(),
+                last_transaction_version: txn_version,
+                last_transaction_timestamp: txn_timestamp,
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    /// The asset type of a coin is the coin type, so that coins and fungible assets share the table.
+    pub fn from_coin_info(coin_info: &CoinInfo) -> Self {
+        Self {
+            asset_type: get_asset_type_trunc(&coin_info.coin_type),
+            name: coin_info.name.clone(),
+            symbol: coin_info.symbol.clone(),
+            decimals: coin_info.decimals,
+            token_standard: COIN_TOKEN_STANDARD.to_string(),
+            last_transaction_version: coin_info.transaction_version_created,
+            last_transaction_timestamp: coin_info.transaction_created_timestamp,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::util::{standardize_address, truncate_str};
+use anyhow::{Context, Result};
+use aptos_api_types::{deserialize_from_string, WriteResource};
+use aptos_types::account_address::{create_derived_object_address, AccountAddress};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+const ASSET_TYPE_LENGTH: usize = 1000;
+/**
+ * This file defines deserialized fungible asset types as defined in our 0x1 contracts.
+ */
+
+/// Reference to an object, i.e. Object<T> in Move
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResourceReference {
+    inner: String,
+}
+
+impl ResourceReference {
+    pub fn get_reference_address(&self) -> String {
+        standardize_address(&self.inner)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FungibleAssetMetadata {
+    name: String,
+    symbol: String,
+    pub decimals: i32,
+}
+
+impl FungibleAssetMetadata {
+    pub fn get_name_trunc(&self) -> String {
+        truncate_str(&self.name, 32)
+    }
+
+    pub fn get_symbol_trunc(&self) -> String {
+        truncate_str(&self.symbol, 10)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FungibleAssetStore {
+    pub metadata: ResourceReference,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub balance: BigDecimal,
+    pub allow_ungated_balance_transfer: bool,
+}
+
+impl FungibleAssetStore {
+    /// Primary stores are derived from the owner and the metadata address, see 0x1::primary_fungible_store.
+    pub fn is_primary_store(&self, store_address: &str, owner_address: &str) -> bool {
+        let derived_address = match (
+            AccountAddress::from_hex_literal(owner_address),
+            AccountAddress::from_hex_literal(&self.metadata.get_reference_address()),
+        ) {
+            (Ok(owner), Ok(metadata)) => create_derived_object_address(owner, metadata),
+            _ => return false,
+        };
+        standardize_address(&derived_address.to_hex_literal()) == store_address
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DepositEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub amount: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WithdrawEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub amount: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SetUngatedTransferEvent {
+    pub transfer_allowed: bool,
+}
+
+/// Coin types can be arbitrarily long, so asset types are truncated to fit in the primary key.
+pub fn get_asset_type_trunc(asset_type: &str) -> String {
+    truncate_str(asset_type, ASSET_TYPE_LENGTH)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum FungibleAssetResource {
+    FungibleAssetMetadata(FungibleAssetMetadata),
+    FungibleAssetStore(FungibleAssetStore),
+}
+
+impl FungibleAssetResource {
+    pub fn is_resource_supported(data_type: &str) -> bool {
+        matches!(
+            data_type,
+            "0x1::fungible_asset::Metadata" | "0x1::fungible_asset::FungibleStore"
+        )
+    }
+
+    pub fn from_resource(
+        data_type: &str,
+        data: &serde_json::Value,
+        txn_version: i64,
+    ) -> Result<FungibleAssetResource> {
+        match data_type {
+            "0x1::fungible_asset::Metadata" => serde_json::from_value(data.clone())
+                .map(|inner| Some(FungibleAssetResource::FungibleAssetMetadata(inner))),
+            "0x1::fungible_asset::FungibleStore" => serde_json::from_value(data.clone())
+                .map(|inner| Some(FungibleAssetResource::FungibleAssetStore(inner))),
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, data_type, data
+        ))?
+        .context(format!(
+            "Resource unsupported! Call is_resource_supported first. version {} type {}",
+            txn_version, data_type
+        ))
+    }
+
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> Result<Option<FungibleAssetResource>> {
+        let type_str = format!(
+            "{}::{}::{}",
+            write_resource.data.typ.address,
+            write_resource.data.typ.module,
+            write_resource.data.typ.name
+        );
+        if !FungibleAssetResource::is_resource_supported(type_str.as_str()) {
+            return Ok(None);
+        }
+        let data = serde_json::to_value(&write_resource.data.data)?;
+        Ok(Some(Self::from_resource(&type_str, &data, txn_version)?))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum FungibleAssetEvent {
+    DepositEvent(DepositEvent),
+    WithdrawEvent(WithdrawEvent),
+    SetUngatedTransferEvent(SetUngatedTransferEvent),
+}
+
+impl FungibleAssetEvent {
+    pub fn from_event(
+        data_type: &str,
+        data: &serde_json::Value,
+        txn_version: i64,
+    ) -> Result<Option<FungibleAssetEvent>> {
+        match data_type {
+            "0x1::fungible_asset::DepositEvent" => serde_json::from_value(data.clone())
+                .map(|inner| Some(FungibleAssetEvent::DepositEvent(inner))),
+            "0x1::fungible_asset::WithdrawEvent" => serde_json::from_value(data.clone())
+                .map(|inner| Some(FungibleAssetEvent::WithdrawEvent(inner))),
+            "0x1::fungible_asset::SetUngatedTransferEvent" => serde_json::from_value(data.clone())
+                .map(|inner| Some(FungibleAssetEvent::SetUngatedTransferEvent(inner))),
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, data_type, data
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fungible_store() {
+        let data = serde_json::json!({
+            "metadata": {"inner": "0xa"},
+            "balance": "100",
+            "allow_ungated_balance_transfer": true,
+        });
+        let store = match FungibleAssetResource::from_resource(
+            "0x1::fungible_asset::FungibleStore",
+            &data,
+            1,
+        )
+        .unwrap()
+        {
+            FungibleAssetResource::FungibleAssetStore(inner) => inner,
+            _ => panic!("Expected a fungible store"),
+        };
+        assert_eq!(store.balance, BigDecimal::from(100));
+        assert_eq!(
+            store.metadata.get_reference_address(),
+            standardize_address("0xa")
+        );
+    }
+
+    #[test]
+    fn test_is_primary_store() {
+        let owner = standardize_address("0xcafe");
+        let store = FungibleAssetStore {
+            metadata: ResourceReference {
+                inner: "0xa".to_string(),
+            },
+            balance: BigDecimal::from(0),
+            allow_ungated_balance_transfer: true,
+        };
+        let primary_store_address = standardize_address(
+            &create_derived_object_address(
+                AccountAddress::from_hex_literal("0xcafe").unwrap(),
+                AccountAddress::from_hex_literal("0xa").unwrap(),
+            )
+            .to_hex_literal(),
+        );
+        assert!(store.is_primary_store(&primary_store_address, &owner));
+        assert!(!store.is_primary_store(&standardize_address("0xb"), &owner));
+    }
+}
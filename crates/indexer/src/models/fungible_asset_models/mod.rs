@@ -0,0 +1,7 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod fungible_asset_activities;
+pub mod fungible_asset_balances;
+pub mod fungible_asset_metadata;
+mod fungible_asset_utils;
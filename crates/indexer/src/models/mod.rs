@@ -4,10 +4,13 @@
 pub mod block_metadata_transactions;
 pub mod coin_models;
 pub mod events;
+pub mod fungible_asset_models;
 pub mod ledger_info;
 pub mod move_modules;
 pub mod move_resources;
 pub mod move_tables;
+pub mod multisig_models;
+pub mod object_models;
 pub mod processor_status;
 pub mod processor_statuses;
 pub mod property_map;
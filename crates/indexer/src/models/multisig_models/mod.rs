@@ -0,0 +1,6 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod multisig_accounts;
+pub mod multisig_transactions;
+mod multisig_utils;
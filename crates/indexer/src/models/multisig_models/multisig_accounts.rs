@@ -0,0 +1,131 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::multisig_utils::{MultisigAccountResource, MultisigEvent};
+use crate::{
+    schema::{current_multisig_accounts, current_multisig_owners},
+    util::standardize_address,
+};
+use aptos_api_types::{
+    Transaction as APITransaction, WriteResource as APIWriteResource,
+    WriteSetChange as APIWriteSetChange,
+};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+type MultisigAddress = String;
+type OwnerAddress = String;
+pub type CurrentMultisigAccountMap = HashMap<MultisigAddress, CurrentMultisigAccount>;
+pub type CurrentMultisigOwnerPK = (MultisigAddress, OwnerAddress);
+pub type CurrentMultisigOwnerMap = HashMap<CurrentMultisigOwnerPK, CurrentMultisigOwner>;
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(multisig_address))]
+#[diesel(table_name = current_multisig_accounts)]
+pub struct CurrentMultisigAccount {
+    pub multisig_address: String,
+    pub owners: serde_json::Value,
+    pub num_signatures_required: i64,
+    pub last_executed_sequence_number: i64,
+    pub next_sequence_number: i64,
+    pub last_transaction_version: i64,
+}
+
+/// Removed owners are kept with is_owner = false so that wallets can tell why an account disappeared.
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(multisig_address, owner_address))]
+#[diesel(table_name = current_multisig_owners)]
+pub struct CurrentMultisigOwner {
+    pub multisig_address: String,
+    pub owner_address: String,
+    pub is_owner: bool,
+    pub last_transaction_version: i64,
+}
+
+impl CurrentMultisigAccount {
+    pub fn from_write_resource(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+    ) -> anyhow::Result<Option<(Self, Vec<CurrentMultisigOwner>)>> {
+        match MultisigAccountResource::from_write_resource(write_resource, txn_version)? {
+            Some(inner) => {
+                let multisig_address = standardize_address(&write_resource.address.to_string());
+                let owners = inner.get_owners();
+                let current_owners = owners
+                    .iter()
+                    .map(|owner_address| CurrentMultisigOwner {
+                        multisig_address: multisig_address.clone(),
+                        owner_address: owner_address.clone(),
+                        is_owner: true,
+                        last_transaction_version: txn_version,
+                    })
+                    .collect();
+                Ok(Some((
+                    Self {
+                        multisig_address,
+                        owners: serde_json::to_value(owners)?,
+                        num_signatures_required: inner.num_signatures_required,
+                        last_executed_sequence_number: inner.last_executed_sequence_number,
+                        next_sequence_number: inner.next_sequence_number,
+                        last_transaction_version: txn_version,
+                    },
+                    current_owners,
+                )))
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn from_transaction(
+        transaction: &APITransaction,
+    ) -> anyhow::Result<(CurrentMultisigAccountMap, CurrentMultisigOwnerMap)> {
+        let mut current_multisig_accounts: CurrentMultisigAccountMap = HashMap::new();
+        let mut current_multisig_owners: CurrentMultisigOwnerMap = HashMap::new();
+        if let APITransaction::UserTransaction(user_txn) = transaction {
+            let txn_version = user_txn.info.version.0 as i64;
+            // Removed owners first, the owners in the account resource are then the source of truth
+            for event in &user_txn.events {
+                if let Some(MultisigEvent::RemoveOwnersEvent(inner)) =
+                    MultisigEvent::from_event(&event.typ.to_string(), &event.data, txn_version)?
+                {
+                    let multisig_address =
+                        standardize_address(&event.guid.account_address.to_string());
+                    for owner in inner.owners_removed {
+                        let owner_address = standardize_address(&owner);
+                        current_multisig_owners.insert(
+                            (multisig_address.clone(), owner_address.clone()),
+                            CurrentMultisigOwner {
+                                multisig_address: multisig_address.clone(),
+                                owner_address,
+                                is_owner: false,
+                                last_transaction_version: txn_version,
+                            },
+                        );
+                    }
+                }
+            }
+            for wsc in &user_txn.info.changes {
+                if let APIWriteSetChange::WriteResource(write_resource) = wsc {
+                    if let Some((multisig_account, owners)) =
+                        Self::from_write_resource(write_resource, txn_version)?
+                    {
+                        for owner in owners {
+                            current_multisig_owners.insert(
+                                (owner.multisig_address.clone(), owner.owner_address.clone()),
+                                owner,
+                            );
+                        }
+                        current_multisig_accounts
+                            .insert(multisig_account.multisig_address.clone(), multisig_account);
+                    }
+                }
+            }
+        }
+        Ok((current_multisig_accounts, current_multisig_owners))
+    }
+}
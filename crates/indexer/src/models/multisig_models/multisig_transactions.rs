@@ -0,0 +1,409 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::multisig_utils::{CreateTransactionEvent, MultisigEvent};
+use crate::{
+    database::PgPoolConnection,
+    schema::{current_multisig_transactions, multisig_votes},
+    util::{parse_timestamp, parse_timestamp_secs, standardize_address},
+};
+use anyhow::Context;
+use aptos_api_types::Transaction as APITransaction;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const PENDING: &str = "pending";
+pub const EXECUTED: &str = "executed";
+pub const EXECUTION_FAILED: &str = "execution_failed";
+pub const REJECTED: &str = "rejected";
+const QUERY_RETRIES: u32 = 5;
+const QUERY_RETRY_DELAY_MS: u64 = 500;
+
+type MultisigAddress = String;
+type SequenceNumber = i64;
+pub type CurrentMultisigTransactionPK = (MultisigAddress, SequenceNumber);
+pub type CurrentMultisigTransactionMap =
+    HashMap<CurrentMultisigTransactionPK, CurrentMultisigTransaction>;
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(multisig_address, sequence_number))]
+#[diesel(table_name = current_multisig_transactions)]
+pub struct CurrentMultisigTransaction {
+    pub multisig_address: String,
+    pub sequence_number: i64,
+    pub creator_address: String,
+    pub payload: Option<String>,
+    pub payload_hash: Option<String>,
+    pub votes: serde_json::Value,
+    pub num_approvals: i64,
+    pub num_rejections: i64,
+    pub status: String,
+    pub executor_address: Option<String>,
+    pub creation_timestamp: chrono::NaiveDateTime,
+    pub last_transaction_version: i64,
+}
+
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(multisig_address, sequence_number))]
+#[diesel(table_name = current_multisig_transactions)]
+pub struct CurrentMultisigTransactionQuery {
+    pub multisig_address: String,
+    pub sequence_number: i64,
+    pub creator_address: String,
+    pub payload: Option<String>,
+    pub payload_hash: Option<String>,
+    pub votes: serde_json::Value,
+    pub num_approvals: i64,
+    pub num_rejections: i64,
+    pub status: String,
+    pub executor_address: Option<String>,
+    pub creation_timestamp: chrono::NaiveDateTime,
+    pub last_transaction_version: i64,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = multisig_votes)]
+pub struct MultisigVote {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub multisig_address: String,
+    pub sequence_number: i64,
+    pub owner_address: String,
+    pub approved: bool,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl CurrentMultisigTransaction {
+    /// Proposals are tracked through events since executed and rejected proposals are removed from the account's
+    /// transactions table, and votes only write the table item. Votes are kept as a map from owner to approval
+    /// because owners can change their vote.
+    pub fn from_transaction(
+        transaction: &APITransaction,
+        multisig_transaction_map: &CurrentMultisigTransactionMap,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<(Vec<MultisigVote>, CurrentMultisigTransactionMap)> {
+        let mut multisig_votes = vec![];
+        let mut current_multisig_transactions: CurrentMultisigTransactionMap = HashMap::new();
+        if let APITransaction::UserTransaction(user_txn) = transaction {
+            let txn_version = user_txn.info.version.0 as i64;
+            let txn_timestamp = parse_timestamp(user_txn.timestamp.0, txn_version);
+            for (index, event) in user_txn.events.iter().enumerate() {
+                let multisig_event = match MultisigEvent::from_event(
+                    &event.typ.to_string(),
+                    &event.data,
+                    txn_version,
+                )? {
+                    Some(inner) => inner,
+                    None => continue,
+                };
+                let multisig_address = standardize_address(&event.guid.account_address.to_string());
+                let mut multisig_transaction = match multisig_event {
+                    MultisigEvent::CreateTransactionEvent(inner) => {
+                        Self::from_create_transaction_event(&inner, multisig_address, txn_version)?
+                    },
+                    MultisigEvent::VoteEvent(inner) => {
+                        let owner_address = standardize_address(&inner.owner);
+                        multisig_votes.push(MultisigVote {
+                            transaction_version: txn_version,
+                            event_index: index as i64,
+                            multisig_address: multisig_address.clone(),
+                            sequence_number: inner.sequence_number,
+                            owner_address: owner_address.clone(),
+                            approved: inner.approved,
+                            transaction_timestamp: txn_timestamp,
+                        });
+                        Self::get_multisig_transaction(
+                            &multisig_address,
+                            inner.sequence_number,
+                            txn_version,
+                            &current_multisig_transactions,
+                            multisig_transaction_map,
+                            conn,
+                        )?
+                        .with_vote(owner_address, inner.approved)?
+                    },
+                    MultisigEvent::ExecuteRejectedTransactionEvent(inner) => {
+                        Self::get_multisig_transaction(
+                            &multisig_address,
+                            inner.sequence_number,
+                            txn_version,
+                            &current_multisig_transactions,
+                            multisig_transaction_map,
+                            conn,
+                        )?
+                        .with_status(REJECTED, &inner.executor)
+                    },
+                    MultisigEvent::TransactionExecutionSucceededEvent(inner) => {
+                        Self::get_multisig_transaction(
+                            &multisig_address,
+                            inner.sequence_number,
+                            txn_version,
+                            &current_multisig_transactions,
+                            multisig_transaction_map,
+                            conn,
+                        )?
+                        .with_status(EXECUTED, &inner.executor)
+                    },
+                    MultisigEvent::TransactionExecutionFailedEvent(inner) => {
+                        Self::get_multisig_transaction(
+                            &multisig_address,
+                            inner.sequence_number,
+                            txn_version,
+                            &current_multisig_transactions,
+                            multisig_transaction_map,
+                            conn,
+                        )?
+                        .with_status(EXECUTION_FAILED, &inner.executor)
+                    },
+                    MultisigEvent::RemoveOwnersEvent(_) => continue,
+                };
+                multisig_transaction.last_transaction_version = txn_version;
+                current_multisig_transactions.insert(
+                    (
+                        multisig_transaction.multisig_address.clone(),
+                        multisig_transaction.sequence_number,
+                    ),
+                    multisig_transaction,
+                );
+            }
+        }
+        Ok((multisig_votes, current_multisig_transactions))
+    }
+
+    /// The creator's approval is already part of the proposal's votes.
+    fn from_create_transaction_event(
+        create_transaction_event: &CreateTransactionEvent,
+        multisig_address: String,
+        txn_version: i64,
+    ) -> anyhow::Result<Self> {
+        let transaction = &create_transaction_event.transaction;
+        let votes = transaction
+            .votes
+            .data
+            .iter()
+            .map(|vote| (standardize_address(&vote.key), vote.value))
+            .collect::<HashMap<String, bool>>();
+        Self {
+            multisig_address,
+            sequence_number: create_transaction_event.sequence_number,
+            creator_address: standardize_address(&create_transaction_event.creator),
+            payload: transaction.payload.get_bytes(),
+            payload_hash: transaction.payload_hash.get_bytes(),
+            votes: serde_json::Value::Null,
+            num_approvals: 0,
+            num_rejections: 0,
+            status: PENDING.to_string(),
+            executor_address: None,
+            creation_timestamp: parse_timestamp_secs(transaction.creation_time_secs, txn_version),
+            last_transaction_version: txn_version,
+        }
+        .with_votes(votes)
+    }
+
+    fn with_vote(self, owner_address: String, approved: bool) -> anyhow::Result<Self> {
+        let mut votes: HashMap<String, bool> = serde_json::from_value(self.votes.clone())?;
+        votes.insert(owner_address, approved);
+        self.with_votes(votes)
+    }
+
+    fn with_status(mut self, status: &str, executor: &str) -> Self {
+        self.status = status.to_string();
+        self.executor_address = Some(standardize_address(executor));
+        self
+    }
+
+    fn with_votes(mut self, votes: HashMap<String, bool>) -> anyhow::Result<Self> {
+        self.num_approvals = votes.values().filter(|approved| **approved).count() as i64;
+        self.num_rejections = votes.len() as i64 - self.num_approvals;
+        self.votes = serde_json::to_value(votes)?;
+        Ok(self)
+    }
+
+    /// Looks for the proposal in this transaction, then in the batch, and then in the database.
+    fn get_multisig_transaction(
+        multisig_address: &str,
+        sequence_number: i64,
+        txn_version: i64,
+        current_multisig_transactions: &CurrentMultisigTransactionMap,
+        multisig_transaction_map: &CurrentMultisigTransactionMap,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<Self> {
+        let key = (multisig_address.to_string(), sequence_number);
+        if let Some(multisig_transaction) = current_multisig_transactions
+            .get(&key)
+            .or_else(|| multisig_transaction_map.get(&key))
+        {
+            return Ok(multisig_transaction.clone());
+        }
+        Self::get_by_pk(conn, multisig_address, sequence_number).context(format!(
+            "Failed to get multisig transaction {} of {}, version {}",
+            sequence_number, multisig_address, txn_version
+        ))
+    }
+
+    /// Retrying a few times since this proposal could've been written in a separate thread.
+    fn get_by_pk(
+        conn: &mut PgPoolConnection,
+        multisig_address: &str,
+        sequence_number: i64,
+    ) -> anyhow::Result<Self> {
+        let mut retried = 0;
+        while retried < QUERY_RETRIES {
+            retried += 1;
+            match CurrentMultisigTransactionQuery::get_by_pk(
+                conn,
+                multisig_address,
+                sequence_number,
+            ) {
+                Ok(res) => {
+                    return Ok(Self {
+                        multisig_address: res.multisig_address,
+                        sequence_number: res.sequence_number,
+                        creator_address: res.creator_address,
+                        payload: res.payload,
+                        payload_hash: res.payload_hash,
+                        votes: res.votes,
+                        num_approvals: res.num_approvals,
+                        num_rejections: res.num_rejections,
+                        status: res.status,
+                        executor_address: res.executor_address,
+                        creation_timestamp: res.creation_timestamp,
+                        last_transaction_version: res.last_transaction_version,
+                    })
+                },
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(QUERY_RETRY_DELAY_MS));
+                },
+            }
+        }
+        Err(anyhow::anyhow!("Failed to get multisig transaction"))
+    }
+}
+
+impl CurrentMultisigTransactionQuery {
+    pub fn get_by_pk(
+        conn: &mut PgPoolConnection,
+        multisig_address: &str,
+        sequence_number: i64,
+    ) -> diesel::QueryResult<Self> {
+        current_multisig_transactions::table
+            .filter(current_multisig_transactions::multisig_address.eq(multisig_address))
+            .filter(current_multisig_transactions::sequence_number.eq(sequence_number))
+            .first::<Self>(conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::multisig_models::multisig_accounts::CurrentMultisigAccount;
+
+    fn load_transactions() -> Vec<APITransaction> {
+        serde_json::from_str(include_str!("../../../fixtures/multisig_transactions.json")).unwrap()
+    }
+
+    fn get_multisig_events(transaction: &APITransaction) -> Vec<MultisigEvent> {
+        match transaction {
+            APITransaction::UserTransaction(user_txn) => user_txn
+                .events
+                .iter()
+                .filter_map(|event| {
+                    MultisigEvent::from_event(
+                        &event.typ.to_string(),
+                        &event.data,
+                        user_txn.info.version.0 as i64,
+                    )
+                    .unwrap()
+                })
+                .collect(),
+            _ => panic!("Expected a user transaction"),
+        }
+    }
+
+    #[test]
+    fn test_multisig_owners() {
+        let transactions = load_transactions();
+        let multisig_address = standardize_address("0x5151");
+
+        let (accounts, owners) =
+            CurrentMultisigAccount::from_transaction(&transactions[0]).unwrap();
+        let account = accounts.get(&multisig_address).unwrap();
+        assert_eq!(account.num_signatures_required, 2);
+        assert_eq!(account.next_sequence_number, 2);
+        assert_eq!(owners.len(), 3);
+        assert!(owners.values().all(|owner| owner.is_owner));
+
+        let (accounts, owners) =
+            CurrentMultisigAccount::from_transaction(&transactions[2]).unwrap();
+        assert_eq!(
+            accounts
+                .get(&multisig_address)
+                .unwrap()
+                .last_executed_sequence_number,
+            1
+        );
+        let removed_owner = owners
+            .get(&(multisig_address.clone(), standardize_address("0xa3")))
+            .unwrap();
+        assert!(!removed_owner.is_owner);
+        assert!(
+            owners
+                .get(&(multisig_address, standardize_address("0xa2")))
+                .unwrap()
+                .is_owner
+        );
+    }
+
+    #[test]
+    fn test_multisig_transaction_lifecycle() {
+        let transactions = load_transactions();
+        let multisig_address = standardize_address("0x5151");
+
+        let multisig_transaction = match &get_multisig_events(&transactions[0])[..] {
+            [MultisigEvent::CreateTransactionEvent(inner)] => {
+                CurrentMultisigTransaction::from_create_transaction_event(
+                    inner,
+                    multisig_address.clone(),
+                    3000,
+                )
+                .unwrap()
+            },
+            _ => panic!("Expected a create transaction event"),
+        };
+        assert_eq!(multisig_transaction.sequence_number, 1);
+        assert_eq!(multisig_transaction.status, PENDING);
+        assert_eq!(multisig_transaction.num_approvals, 1);
+        assert_eq!(multisig_transaction.num_rejections, 0);
+        assert!(multisig_transaction.payload.is_some());
+        assert!(multisig_transaction.payload_hash.is_none());
+
+        let multisig_transaction = match &get_multisig_events(&transactions[1])[..] {
+            [MultisigEvent::VoteEvent(inner)] => multisig_transaction
+                .with_vote(standardize_address(&inner.owner), inner.approved)
+                .unwrap(),
+            _ => panic!("Expected a vote event"),
+        };
+        assert_eq!(multisig_transaction.num_approvals, 1);
+        assert_eq!(multisig_transaction.num_rejections, 1);
+
+        let multisig_transaction = match &get_multisig_events(&transactions[2])[..] {
+            [MultisigEvent::RemoveOwnersEvent(_), MultisigEvent::TransactionExecutionSucceededEvent(inner)] => {
+                multisig_transaction.with_status(EXECUTED, &inner.executor)
+            },
+            _ => panic!("Expected an execution event"),
+        };
+        assert_eq!(multisig_transaction.status, EXECUTED);
+        assert_eq!(
+            multisig_transaction.executor_address,
+            Some(standardize_address("0xa1"))
+        );
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::util::standardize_address;
+use anyhow::{Context, Result};
+use aptos_api_types::{deserialize_from_string, WriteResource};
+use serde::{Deserialize, Serialize};
+
+/**
+ * This file defines deserialized multisig account types as defined in 0x1::multisig_account.
+ */
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigAccountResource {
+    pub owners: Vec<String>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub num_signatures_required: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub last_executed_sequence_number: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub next_sequence_number: i64,
+}
+
+/// Option<vector<u8>> in Move
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OptionalBytes {
+    vec: Vec<String>,
+}
+
+impl OptionalBytes {
+    pub fn get_bytes(&self) -> Option<String> {
+        self.vec.first().cloned()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VoteEntry {
+    pub key: String,
+    pub value: bool,
+}
+
+/// SimpleMap<address, bool> in Move
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Votes {
+    pub data: Vec<VoteEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigTransactionResource {
+    pub payload: OptionalBytes,
+    pub payload_hash: OptionalBytes,
+    pub votes: Votes,
+    pub creator: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub creation_time_secs: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RemoveOwnersEvent {
+    pub owners_removed: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateTransactionEvent {
+    pub creator: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    pub transaction: MultisigTransactionResource,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VoteEvent {
+    pub owner: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    pub approved: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExecuteRejectedTransactionEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    pub executor: String,
+}
+
+/// Both TransactionExecutionSucceededEvent and TransactionExecutionFailedEvent
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionExecutionEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    pub executor: String,
+}
+
+impl MultisigAccountResource {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> Result<Option<Self>> {
+        let type_str = format!(
+            "{}::{}::{}",
+            write_resource.data.typ.address,
+            write_resource.data.typ.module,
+            write_resource.data.typ.name
+        );
+        if type_str != "0x1::multisig_account::MultisigAccount" {
+            return Ok(None);
+        }
+        let data = serde_json::to_value(&write_resource.data.data)?;
+        serde_json::from_value(data.clone())
+            .map(Some)
+            .context(format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, type_str, data
+            ))
+    }
+
+    pub fn get_owners(&self) -> Vec<String> {
+        self.owners.iter().map(|o| standardize_address(o)).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum MultisigEvent {
+    RemoveOwnersEvent(RemoveOwnersEvent),
+    CreateTransactionEvent(CreateTransactionEvent),
+    VoteEvent(VoteEvent),
+    ExecuteRejectedTransactionEvent(ExecuteRejectedTransactionEvent),
+    TransactionExecutionSucceededEvent(TransactionExecutionEvent),
+    TransactionExecutionFailedEvent(TransactionExecutionEvent),
+}
+
+impl MultisigEvent {
+    pub fn from_event(
+        data_type: &str,
+        data: &serde_json::Value,
+        txn_version: i64,
+    ) -> Result<Option<MultisigEvent>> {
+        match data_type {
+            "0x1::multisig_account::RemoveOwnersEvent" => serde_json::from_value(data.clone())
+                .map(|inner| Some(MultisigEvent::RemoveOwnersEvent(inner))),
+            "0x1::multisig_account::CreateTransactionEvent" => serde_json::from_value(data.clone())
+                .map(|inner| Some(MultisigEvent::CreateTransactionEvent(inner))),
+            "0x1::multisig_account::VoteEvent" => serde_json::from_value(data.clone())
+                .map(|inner| Some(MultisigEvent::VoteEvent(inner))),
+            "0x1::multisig_account::ExecuteRejectedTransactionEvent" => {
+                serde_json::from_value(data.clone())
+                    .map(|inner| Some(MultisigEvent::ExecuteRejectedTransactionEvent(inner)))
+            },
+            "0x1::multisig_account::TransactionExecutionSucceededEvent" => {
+                serde_json::from_value(data.clone())
+                    .map(|inner| Some(MultisigEvent::TransactionExecutionSucceededEvent(inner)))
+            },
+            "0x1::multisig_account::TransactionExecutionFailedEvent" => {
+                serde_json::from_value(data.clone())
+                    .map(|inner| Some(MultisigEvent::TransactionExecutionFailedEvent(inner)))
+            },
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, data_type, data
+        ))
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    database::PgPoolConnection,
+    schema::{current_objects, objects},
+    util::standardize_address,
+};
+use anyhow::Context;
+use aptos_api_types::{
+    deserialize_from_string, DeleteResource as APIDeleteResource, WriteResource as APIWriteResource,
+};
+use bigdecimal::BigDecimal;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const OBJECT_CORE: &str = "0x1::object::ObjectCore";
+const OBJECT_GROUP: &str = "0x1::object::ObjectGroup";
+const QUERY_RETRIES: u32 = 5;
+const QUERY_RETRY_DELAY_MS: u64 = 500;
+
+type ObjectAddress = String;
+// Latest state of the objects written in a batch, used to look up the owner of deleted objects
+pub type ObjectMapping = HashMap<ObjectAddress, CurrentObject>;
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index))]
+#[diesel(table_name = objects)]
+pub struct Object {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub object_address: String,
+    pub owner_address: String,
+    pub guid_creation_num: BigDecimal,
+    pub allow_ungated_transfer: bool,
+    pub is_deleted: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(object_address))]
+#[diesel(table_name = current_objects)]
+pub struct CurrentObject {
+    pub object_address: String,
+    pub owner_address: String,
+    pub allow_ungated_transfer: bool,
+    pub last_guid_creation_num: BigDecimal,
+    pub last_transaction_version: i64,
+    pub is_deleted: bool,
+}
+
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(object_address))]
+#[diesel(table_name = current_objects)]
+pub struct CurrentObjectQuery {
+    pub object_address: String,
+    pub owner_address: String,
+    pub allow_ungated_transfer: bool,
+    pub last_guid_creation_num: BigDecimal,
+    pub last_transaction_version: i64,
+    pub is_deleted: bool,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+/// This is the ObjectCore resource as defined in 0x1::object. It lives in the object resource group.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ObjectCoreResource {
+    pub allow_ungated_transfer: bool,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub guid_creation_num: BigDecimal,
+    pub owner: String,
+}
+
+impl ObjectCoreResource {
+    pub fn from_write_resource(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+    ) -> anyhow::Result<Option<Self>> {
+        let type_str = format!(
+            "{}::{}::{}",
+            write_resource.data.typ.address,
+            write_resource.data.typ.module,
+            write_resource.data.typ.name
+        );
+        if type_str != OBJECT_CORE {
+            return Ok(None);
+        }
+        let data = serde_json::to_value(&write_resource.data.data)?;
+        serde_json::from_value(data.clone())
+            .map(Some)
+            .context(format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, type_str, data
+            ))
+    }
+}
+
+impl Object {
+    /// Every write of an object resource group contains the ObjectCore, so this captures both creation and transfers.
+    pub fn from_write_resource(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+        write_set_change_index: i64,
+    ) -> anyhow::Result<Option<(Self, CurrentObject)>> {
+        match ObjectCoreResource::from_write_resource(write_resource, txn_version)? {
+            Some(inner) => {
+                let object_address = standardize_address(&write_resource.address.to_string());
+                let owner_address = standardize_address(&inner.owner);
+                Ok(Some((
+                    Self {
+                        transaction_version: txn_version,
+                        write_set_change_index,
+                        object_address: object_address.clone(),
+                        owner_address: owner_address.clone(),
+                        guid_creation_num: inner.guid_creation_num.clone(),
+                        allow_ungated_transfer: inner.allow_ungated_transfer,
+                        is_deleted: false,
+                    },
+                    CurrentObject {
+                        object_address,
+                        owner_address,
+                        allow_ungated_transfer: inner.allow_ungated_transfer,
+                        last_guid_creation_num: inner.guid_creation_num,
+                        last_transaction_version: txn_version,
+                        is_deleted: false,
+                    },
+                )))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Deleting an object deletes the whole resource group, which doesn't carry the last owner. We look for it in the
+    /// objects written earlier in the batch, and then in the database.
+    pub fn from_delete_resource(
+        delete_resource: &APIDeleteResource,
+        txn_version: i64,
+        write_set_change_index: i64,
+        object_mapping: &ObjectMapping,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<Option<(Self, CurrentObject)>> {
+        let type_str = format!(
+            "{}::{}::{}",
+            delete_resource.resource.address,
+            delete_resource.resource.module,
+            delete_resource.resource.name
+        );
+        if type_str != OBJECT_GROUP {
+            return Ok(None);
+        }
+        let object_address = standardize_address(&delete_resource.address.to_string());
+        let previous_object = match object_mapping.get(&object_address) {
+            Some(current_object) => current_object.clone(),
+            None => Self::get_current_object(conn, &object_address).context(format!(
+                "Failed to get object {} for deletion, txn version {}",
+                object_address, txn_version
+            ))?,
+        };
+        Ok(Some((
+            Self {
+                transaction_version: txn_version,
+                write_set_change_index,
+                object_address: object_address.clone(),
+                owner_address: previous_object.owner_address.clone(),
+                guid_creation_num: previous_object.last_guid_creation_num.clone(),
+                allow_ungated_transfer: previous_object.allow_ungated_transfer,
+                is_deleted: true,
+            },
+            CurrentObject {
+                object_address,
+                owner_address: previous_object.owner_address,
+                allow_ungated_transfer: previous_object.allow_ungated_transfer,
+                last_guid_creation_num: previous_object.last_guid_creation_num,
+                last_transaction_version: txn_version,
+                is_deleted: true,
+            },
+        )))
+    }
+
+    /// Retrying a few times since this object could've been written in a separate thread.
+    fn get_current_object(
+        conn: &mut PgPoolConnection,
+        object_address: &str,
+    ) -> anyhow::Result<CurrentObject> {
+        let mut retried = 0;
+        while retried < QUERY_RETRIES {
+            retried += 1;
+            match CurrentObjectQuery::get_by_address(conn, object_address) {
+                Ok(res) => {
+                    return Ok(CurrentObject {
+                        object_address: res.object_address,
+                        owner_address: res.owner_address,
+                        allow_ungated_transfer: res.allow_ungated_transfer,
+                        last_guid_creation_num: res.last_guid_creation_num,
+                        last_transaction_version: res.last_transaction_version,
+                        is_deleted: res.is_deleted,
+                    })
+                },
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(QUERY_RETRY_DELAY_MS));
+                },
+            }
+        }
+        Err(anyhow::anyhow!("Failed to get object owner"))
+    }
+}
+
+impl CurrentObjectQuery {
+    pub fn get_by_address(
+        conn: &mut PgPoolConnection,
+        object_address: &str,
+    ) -> diesel::QueryResult<Self> {
+        current_objects::table
+            .filter(current_objects::object_address.eq(object_address))
+            .first::<Self>(conn)
+    }
+}
@@ -8,7 +8,8 @@ use aptos_api_types::{
     AccountSignature as APIAccountSignature, Ed25519Signature as APIEd25519Signature,
     MultiAgentSignature as APIMultiAgentSignature,
     MultiEd25519Signature as APIMultiEd25519Signature,
-    TransactionSignature as APITransactionSignature,
+    Secp256k1EcdsaSignature as APISecp256k1EcdsaSignature,
+    TransactionSignature as APITransactionSignature, WebAuthnSignature as APIWebAuthnSignature,
 };
 use aptos_bitvec::BitVec;
 use field_count::FieldCount;
@@ -74,6 +75,17 @@ impl Signature {
                 transaction_version,
                 transaction_block_height,
             ),
+            APITransactionSignature::SingleSenderSignature(sig) => {
+                Ok(Self::parse_multi_agent_signature_helper(
+                    &sig.sender,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    true,
+                    0,
+                    None,
+                ))
+            },
         }
     }
 
@@ -86,6 +98,9 @@ impl Signature {
             APITransactionSignature::MultiAgentSignature(_) => {
                 String::from("multi_agent_signature")
             },
+            APITransactionSignature::SingleSenderSignature(_) => {
+                String::from("single_sender_signature")
+            },
         }
     }
 
@@ -114,6 +129,58 @@ impl Signature {
         }
     }
 
+    fn parse_secp256k1_ecdsa_signature(
+        s: &APISecp256k1EcdsaSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Self {
+        let signer = standardize_address(override_address.unwrap_or(sender));
+        Self {
+            transaction_version,
+            transaction_block_height,
+            signer,
+            is_sender_primary,
+            type_: String::from("secp256k1_ecdsa_signature"),
+            public_key: s.public_key.to_string(),
+            threshold: 1,
+            public_key_indices: serde_json::Value::Array(vec![]),
+            signature: s.signature.to_string(),
+            multi_agent_index,
+            multi_sig_index: 0,
+        }
+    }
+
+    /// Only the secp256r1 signature of the assertion is recorded (not the authenticator
+    /// and client data, which are available from the raw transaction).
+    fn parse_webauthn_signature(
+        s: &APIWebAuthnSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Self {
+        let signer = standardize_address(override_address.unwrap_or(sender));
+        Self {
+            transaction_version,
+            transaction_block_height,
+            signer,
+            is_sender_primary,
+            type_: String::from("web_authn_signature"),
+            public_key: s.public_key.to_string(),
+            threshold: 1,
+            public_key_indices: serde_json::Value::Array(vec![]),
+            signature: s.signature.to_string(),
+            multi_agent_index,
+            multi_sig_index: 0,
+        }
+    }
+
     fn parse_multi_signature(
         s: &APIMultiEd25519Signature,
         sender: &String,
@@ -220,6 +287,26 @@ impl Signature {
                 multi_agent_index,
                 override_address,
             ),
+            APIAccountSignature::Secp256k1EcdsaSignature(sig) => {
+                vec![Self::parse_secp256k1_ecdsa_signature(
+                    sig,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    is_sender_primary,
+                    multi_agent_index,
+                    override_address,
+                )]
+            },
+            APIAccountSignature::WebAuthnSignature(sig) => vec![Self::parse_webauthn_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                is_sender_primary,
+                multi_agent_index,
+                override_address,
+            )],
         }
     }
 }
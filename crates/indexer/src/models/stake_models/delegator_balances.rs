@@ -3,8 +3,13 @@
 // This is required because a diesel macro makes clippy sad
 #![allow(clippy::extra_unused_lifetimes)]
 
+use super::delegator_pending_withdrawals::{
+    CurrentDelegatorPendingWithdrawal, PendingWithdrawalMapping,
+};
 use crate::{
-    models::stake_models::stake_utils::StakeResource, schema::current_delegator_balances,
+    database::PgPoolConnection,
+    models::stake_models::stake_utils::{SharesResource, StakeResource},
+    schema::{current_delegator_balances, delegator_balances},
     util::standardize_address,
 };
 use anyhow::Context;
@@ -14,16 +19,39 @@ use aptos_api_types::{
     WriteTableItem as APIWriteTableItem,
 };
 use bigdecimal::{BigDecimal, Zero};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+const ACTIVE_SHARES: &str = "active_shares";
+const INACTIVE_SHARES: &str = "inactive_shares";
+const QUERY_RETRIES: u32 = 5;
+const QUERY_RETRY_DELAY_MS: u64 = 500;
+
 pub type TableHandle = String;
 pub type Address = String;
 pub type ActiveShareMapping = HashMap<TableHandle, Address>;
+// Maps the shares table of each lockup cycle's inactive pool to the staking pool address
+pub type InactiveShareMapping = HashMap<TableHandle, Address>;
+// Maps the inactive_shares table (lockup cycle -> inactive pool) to the staking pool address
+pub type InactivePoolMapping = HashMap<TableHandle, Address>;
 pub type CurrentDelegatorBalancePK = (Address, Address, String);
 pub type CurrentDelegatorBalanceMap = HashMap<CurrentDelegatorBalancePK, CurrentDelegatorBalance>;
 
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index))]
+#[diesel(table_name = delegator_balances)]
+pub struct DelegatorBalance {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub delegator_address: String,
+    pub pool_address: String,
+    pub pool_type: String,
+    pub table_handle: String,
+    pub amount: BigDecimal,
+}
+
 #[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
 #[diesel(primary_key(delegator_address, pool_address, pool_type))]
 #[diesel(table_name = current_delegator_balances)]
@@ -36,71 +64,178 @@ pub struct CurrentDelegatorBalance {
     pub last_transaction_version: i64,
 }
 
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(delegator_address, pool_address, pool_type))]
+#[diesel(table_name = current_delegator_balances)]
+pub struct CurrentDelegatorBalanceQuery {
+    pub delegator_address: String,
+    pub pool_address: String,
+    pub pool_type: String,
+    pub table_handle: String,
+    pub amount: BigDecimal,
+    pub last_transaction_version: i64,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
 impl CurrentDelegatorBalance {
-    /// We're only indexing active_shares for now because that's all the UI needs and indexing
-    /// the inactive_shares or pending_withdrawal_shares would be more complicated.
+    /// Amounts are in shares. Active shares are in the pool's active_shares table, and inactive shares are in the
+    /// shares table of the inactive pool of the lockup cycle they were unlocked in. A delegator can only have
+    /// inactive shares in one lockup cycle at a time since unlocking again withdraws the previous ones.
     pub fn from_write_table_item(
         write_table_item: &APIWriteTableItem,
+        write_set_change_index: i64,
         txn_version: i64,
         active_share_mapping: &ActiveShareMapping,
-    ) -> anyhow::Result<Option<Self>> {
+        inactive_share_mapping: &InactiveShareMapping,
+    ) -> anyhow::Result<Option<(DelegatorBalance, Self)>> {
         let table_handle = standardize_address(&write_table_item.handle.to_string());
-        // The mapping will tell us if the table item is an active share table
-        if let Some(pool_address) = active_share_mapping.get(&table_handle) {
-            let pool_address = standardize_address(pool_address);
-            let delegator_address = standardize_address(&write_table_item.key.to_string());
-            let data = write_table_item.data.as_ref().unwrap_or_else(|| {
-                panic!(
-                    "This table item should be an active share item, table_item {:?}, version {}",
-                    write_table_item, txn_version
-                )
-            });
-            let amount = data
-                .value
-                .as_str()
-                .map(|s| s.parse::<BigDecimal>())
-                .context(format!(
-                    "value is not a string: {:?}, table_item {:?}, version {}",
-                    data.value, write_table_item, txn_version
-                ))?
-                .context(format!(
-                    "cannot parse string as u64: {:?}, version {}",
-                    data.value, txn_version
-                ))?;
+        // The mappings will tell us if the table item is a share table
+        let (pool_address, pool_type) = match Self::get_pool_for_share_table(
+            &table_handle,
+            active_share_mapping,
+            inactive_share_mapping,
+        ) {
+            Some(inner) => inner,
+            None => return Ok(None),
+        };
+        let delegator_address = standardize_address(&write_table_item.key.to_string());
+        let data = write_table_item.data.as_ref().unwrap_or_else(|| {
+            panic!(
+                "This table item should be a share item, table_item {:?}, version {}",
+                write_table_item, txn_version
+            )
+        });
+        let amount = data
+            .value
+            .as_str()
+            .map(|s| s.parse::<BigDecimal>())
+            .context(format!(
+                "value is not a string: {:?}, table_item {:?}, version {}",
+                data.value, write_table_item, txn_version
+            ))?
+            .context(format!(
+                "cannot parse string as u64: {:?}, version {}",
+                data.value, txn_version
+            ))?;
 
-            return Ok(Some(Self {
-                delegator_address,
-                pool_address,
-                pool_type: "active_shares".to_string(),
-                table_handle,
-                amount,
-                last_transaction_version: txn_version,
-            }));
-        }
-        Ok(None)
+        Ok(Some(Self::build(
+            write_set_change_index,
+            txn_version,
+            delegator_address,
+            pool_address,
+            pool_type,
+            table_handle,
+            amount,
+        )))
     }
 
     // Setting amount to 0 if table item is deleted
     pub fn from_delete_table_item(
         delete_table_item: &APIDeleteTableItem,
+        write_set_change_index: i64,
         txn_version: i64,
         active_share_mapping: &ActiveShareMapping,
-    ) -> anyhow::Result<Option<Self>> {
+        inactive_share_mapping: &InactiveShareMapping,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<Option<(DelegatorBalance, Self)>> {
         let table_handle = standardize_address(&delete_table_item.handle.to_string());
-        // The mapping will tell us if the table item is an active share table
-        if let Some(pool_address) = active_share_mapping.get(&table_handle) {
-            let delegator_address = standardize_address(&delete_table_item.key.to_string());
+        let (pool_address, pool_type) = match Self::get_pool_for_share_table(
+            &table_handle,
+            active_share_mapping,
+            inactive_share_mapping,
+        ) {
+            Some(inner) => inner,
+            // Withdrawing the last inactive shares of a lockup cycle removes its inactive pool altogether, so the
+            // shares table can only be found in the database
+            None => match Self::get_pool_by_inactive_share_table(conn, &table_handle) {
+                Some(pool_address) => (pool_address, INACTIVE_SHARES),
+                None => return Ok(None),
+            },
+        };
+        let delegator_address = standardize_address(&delete_table_item.key.to_string());
 
-            return Ok(Some(Self {
-                delegator_address,
+        Ok(Some(Self::build(
+            write_set_change_index,
+            txn_version,
+            delegator_address,
+            pool_address,
+            pool_type,
+            table_handle,
+            BigDecimal::zero(),
+        )))
+    }
+
+    fn build(
+        write_set_change_index: i64,
+        txn_version: i64,
+        delegator_address: String,
+        pool_address: String,
+        pool_type: &str,
+        table_handle: String,
+        amount: BigDecimal,
+    ) -> (DelegatorBalance, Self) {
+        (
+            DelegatorBalance {
+                transaction_version: txn_version,
+                write_set_change_index,
+                delegator_address: delegator_address.clone(),
                 pool_address: pool_address.clone(),
-                pool_type: "active_shares".to_string(),
+                pool_type: pool_type.to_string(),
+                table_handle: table_handle.clone(),
+                amount: amount.clone(),
+            },
+            Self {
+                delegator_address,
+                pool_address,
+                pool_type: pool_type.to_string(),
                 table_handle,
-                amount: BigDecimal::zero(),
+                amount,
                 last_transaction_version: txn_version,
-            }));
+            },
+        )
+    }
+
+    /// Deleted items of the delegation pool's other tables can be ruled out without a database lookup.
+    fn may_be_share_item(
+        delete_table_item: &APIDeleteTableItem,
+        inactive_pool_mapping: &InactivePoolMapping,
+        pending_withdrawal_mapping: &PendingWithdrawalMapping,
+    ) -> bool {
+        let table_handle = standardize_address(&delete_table_item.handle.to_string());
+        !inactive_pool_mapping.is_empty()
+            && !inactive_pool_mapping.contains_key(&table_handle)
+            && !pending_withdrawal_mapping.contains_key(&table_handle)
+    }
+
+    fn get_pool_for_share_table(
+        table_handle: &str,
+        active_share_mapping: &ActiveShareMapping,
+        inactive_share_mapping: &InactiveShareMapping,
+    ) -> Option<(String, &'static str)> {
+        if let Some(pool_address) = active_share_mapping.get(table_handle) {
+            return Some((standardize_address(pool_address), ACTIVE_SHARES));
         }
-        Ok(None)
+        inactive_share_mapping
+            .get(table_handle)
+            .map(|pool_address| (standardize_address(pool_address), INACTIVE_SHARES))
+    }
+
+    /// Retrying a few times since the inactive shares could've been written in a separate thread.
+    fn get_pool_by_inactive_share_table(
+        conn: &mut PgPoolConnection,
+        table_handle: &str,
+    ) -> Option<String> {
+        let mut retried = 0;
+        while retried < QUERY_RETRIES {
+            retried += 1;
+            match CurrentDelegatorBalanceQuery::get_by_inactive_share_handle(conn, table_handle) {
+                Ok(res) => return Some(res.pool_address),
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(QUERY_RETRY_DELAY_MS));
+                },
+            }
+        }
+        None
     }
 
     pub fn get_active_share_map(
@@ -117,43 +252,293 @@ impl CurrentDelegatorBalance {
         Ok(None)
     }
 
+    pub fn get_inactive_pool_map(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+    ) -> anyhow::Result<Option<InactivePoolMapping>> {
+        if let Some(StakeResource::DelegationPool(inner)) =
+            StakeResource::from_write_resource(write_resource, txn_version)?
+        {
+            let staking_pool_address = standardize_address(&write_resource.address.to_string());
+            let table_handle = standardize_address(&inner.inactive_shares.handle);
+            return Ok(Some(HashMap::from([(table_handle, staking_pool_address)])));
+        }
+        Ok(None)
+    }
+
+    /// Each lockup cycle's inactive pool is an item of the inactive_shares table, and it's written whenever its
+    /// shares change.
+    pub fn get_inactive_share_map(
+        write_table_item: &APIWriteTableItem,
+        txn_version: i64,
+        inactive_pool_mapping: &InactivePoolMapping,
+    ) -> anyhow::Result<Option<InactiveShareMapping>> {
+        let table_handle = standardize_address(&write_table_item.handle.to_string());
+        if let Some(pool_address) = inactive_pool_mapping.get(&table_handle) {
+            let data = write_table_item.data.as_ref().context(format!(
+                "This table item should be an inactive pool, table_item {:?}, version {}",
+                write_table_item, txn_version
+            ))?;
+            let inactive_pool: SharesResource = serde_json::from_value(data.value.clone())
+                .context(format!(
+                    "version {} failed! failed to parse inactive pool, data {:?}",
+                    txn_version, data.value
+                ))?;
+            let shares_table_handle = standardize_address(&inactive_pool.shares.inner.handle);
+            return Ok(Some(HashMap::from([(
+                shares_table_handle,
+                pool_address.clone(),
+            )])));
+        }
+        Ok(None)
+    }
+
     pub fn from_transaction(
         transaction: &APITransaction,
-    ) -> anyhow::Result<CurrentDelegatorBalanceMap> {
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<(Vec<DelegatorBalance>, CurrentDelegatorBalanceMap)> {
         let mut active_share_mapping: ActiveShareMapping = HashMap::new();
+        let mut inactive_pool_mapping: InactivePoolMapping = HashMap::new();
+        let mut inactive_share_mapping: InactiveShareMapping = HashMap::new();
+        let mut pending_withdrawal_mapping: PendingWithdrawalMapping = HashMap::new();
+        let mut delegator_balances = vec![];
         let mut current_delegator_balances: CurrentDelegatorBalanceMap = HashMap::new();
-        // Do a first pass to get the mapping of active_share table handles to staking pool addresses
         if let APITransaction::UserTransaction(user_txn) = transaction {
             let txn_version = user_txn.info.version.0 as i64;
+            // Do a first pass to get the mapping of share table handles to staking pool addresses
             for wsc in &user_txn.info.changes {
                 if let APIWriteSetChange::WriteResource(write_resource) = wsc {
-                    let maybe_map =
-                        Self::get_active_share_map(write_resource, txn_version).unwrap();
-                    if let Some(map) = maybe_map {
+                    if let Some(map) = Self::get_active_share_map(write_resource, txn_version)? {
                         active_share_mapping.extend(map);
                     }
+                    if let Some(map) = Self::get_inactive_pool_map(write_resource, txn_version)? {
+                        inactive_pool_mapping.extend(map);
+                    }
+                    if let Some(map) =
+                        CurrentDelegatorPendingWithdrawal::get_pending_withdrawal_map(
+                            write_resource,
+                            txn_version,
+                        )?
+                    {
+                        pending_withdrawal_mapping.extend(map);
+                    }
                 }
             }
-            // Now make a pass through table items to get the actual delegator balances
             for wsc in &user_txn.info.changes {
                 if let APIWriteSetChange::WriteTableItem(table_item) = wsc {
-                    let txn_version = user_txn.info.version.0 as i64;
-                    let maybe_delegator_balance =
-                        Self::from_write_table_item(table_item, txn_version, &active_share_mapping)
-                            .unwrap();
-                    if let Some(delegator_balance) = maybe_delegator_balance {
-                        current_delegator_balances.insert(
-                            (
-                                delegator_balance.delegator_address.clone(),
-                                delegator_balance.pool_address.clone(),
-                                delegator_balance.pool_type.clone(),
-                            ),
-                            delegator_balance,
-                        );
+                    if let Some(map) = Self::get_inactive_share_map(
+                        table_item,
+                        txn_version,
+                        &inactive_pool_mapping,
+                    )? {
+                        inactive_share_mapping.extend(map);
                     }
                 }
             }
+            // Now make a pass through table items to get the actual delegator balances
+            for (index, wsc) in user_txn.info.changes.iter().enumerate() {
+                let maybe_delegator_balance = match wsc {
+                    APIWriteSetChange::WriteTableItem(table_item) => Self::from_write_table_item(
+                        table_item,
+                        index as i64,
+                        txn_version,
+                        &active_share_mapping,
+                        &inactive_share_mapping,
+                    )?,
+                    // Shares are only removed when the delegation pool itself is written, which lets us skip
+                    // looking up unrelated deleted table items
+                    APIWriteSetChange::DeleteTableItem(table_item)
+                        if Self::may_be_share_item(
+                            table_item,
+                            &inactive_pool_mapping,
+                            &pending_withdrawal_mapping,
+                        ) =>
+                    {
+                        Self::from_delete_table_item(
+                            table_item,
+                            index as i64,
+                            txn_version,
+                            &active_share_mapping,
+                            &inactive_share_mapping,
+                            conn,
+                        )?
+                    },
+                    _ => None,
+                };
+                if let Some((delegator_balance, current_delegator_balance)) =
+                    maybe_delegator_balance
+                {
+                    delegator_balances.push(delegator_balance);
+                    current_delegator_balances.insert(
+                        (
+                            current_delegator_balance.delegator_address.clone(),
+                            current_delegator_balance.pool_address.clone(),
+                            current_delegator_balance.pool_type.clone(),
+                        ),
+                        current_delegator_balance,
+                    );
+                }
+            }
         }
-        Ok(current_delegator_balances)
+        Ok((delegator_balances, current_delegator_balances))
+    }
+}
+
+impl CurrentDelegatorBalanceQuery {
+    pub fn get_by_inactive_share_handle(
+        conn: &mut PgPoolConnection,
+        table_handle: &str,
+    ) -> diesel::QueryResult<Self> {
+        current_delegator_balances::table
+            .filter(current_delegator_balances::table_handle.eq(table_handle))
+            .filter(current_delegator_balances::pool_type.eq(INACTIVE_SHARES))
+            .first::<Self>(conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::stake_models::{
+        delegator_pending_withdrawals::CurrentDelegatorPendingWithdrawal,
+        delegator_pools::DelegatorPool,
+    };
+
+    fn load_transaction(fixture: &str) -> APITransaction {
+        serde_json::from_str(fixture).unwrap()
+    }
+
+    fn get_changes(transaction: &APITransaction) -> &[APIWriteSetChange] {
+        match transaction {
+            APITransaction::UserTransaction(user_txn) => &user_txn.info.changes,
+            _ => panic!("Expected a user transaction"),
+        }
+    }
+
+    /// Builds the share mappings the same way from_transaction does
+    fn get_share_mappings(
+        changes: &[APIWriteSetChange],
+    ) -> (ActiveShareMapping, InactiveShareMapping) {
+        let mut active_share_mapping = HashMap::new();
+        let mut inactive_pool_mapping = HashMap::new();
+        let mut inactive_share_mapping = HashMap::new();
+        for wsc in changes {
+            if let APIWriteSetChange::WriteResource(write_resource) = wsc {
+                active_share_mapping.extend(
+                    CurrentDelegatorBalance::get_active_share_map(write_resource, 0)
+                        .unwrap()
+                        .unwrap_or_default(),
+                );
+                inactive_pool_mapping.extend(
+                    CurrentDelegatorBalance::get_inactive_pool_map(write_resource, 0)
+                        .unwrap()
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        for wsc in changes {
+            if let APIWriteSetChange::WriteTableItem(table_item) = wsc {
+                inactive_share_mapping.extend(
+                    CurrentDelegatorBalance::get_inactive_share_map(
+                        table_item,
+                        0,
+                        &inactive_pool_mapping,
+                    )
+                    .unwrap()
+                    .unwrap_or_default(),
+                );
+            }
+        }
+        (active_share_mapping, inactive_share_mapping)
+    }
+
+    #[test]
+    fn test_unlock_moves_shares_to_inactive_pool() {
+        let transaction = load_transaction(include_str!(
+            "../../../fixtures/delegation_pool_unlock.json"
+        ));
+        let changes = get_changes(&transaction);
+        let (active_share_mapping, inactive_share_mapping) = get_share_mappings(changes);
+        let pool_address = standardize_address("0xd0");
+        let delegator_address = standardize_address("0xde1e");
+
+        let balances: Vec<CurrentDelegatorBalance> = changes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, wsc)| match wsc {
+                APIWriteSetChange::WriteTableItem(table_item) => {
+                    CurrentDelegatorBalance::from_write_table_item(
+                        table_item,
+                        index as i64,
+                        1000,
+                        &active_share_mapping,
+                        &inactive_share_mapping,
+                    )
+                    .unwrap()
+                    .map(|(_, current_balance)| current_balance)
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(balances.len(), 2);
+        assert!(balances
+            .iter()
+            .all(|balance| balance.pool_address == pool_address
+                && balance.delegator_address == delegator_address));
+        assert_eq!(balances[0].pool_type, ACTIVE_SHARES);
+        assert_eq!(balances[0].amount, BigDecimal::from(7500));
+        assert_eq!(balances[1].pool_type, INACTIVE_SHARES);
+        assert_eq!(balances[1].amount, BigDecimal::from(2500));
+
+        let pending_withdrawals =
+            CurrentDelegatorPendingWithdrawal::from_transaction(&transaction).unwrap();
+        let pending_withdrawal = pending_withdrawals
+            .get(&(delegator_address, pool_address.clone()))
+            .unwrap();
+        assert_eq!(pending_withdrawal.observed_lockup_cycle, Some(3));
+
+        let (_, pool_balances, _) = DelegatorPool::from_transaction(&transaction).unwrap();
+        assert_eq!(pool_balances.len(), 1);
+        assert_eq!(pool_balances[0].staking_pool_address, pool_address);
+        assert_eq!(pool_balances[0].total_coins, BigDecimal::from(750));
+        assert_eq!(
+            pool_balances[0].operator_commission_percentage,
+            BigDecimal::from(1000)
+        );
+    }
+
+    #[test]
+    fn test_withdraw_clears_inactive_shares() {
+        let transaction = load_transaction(include_str!(
+            "../../../fixtures/delegation_pool_withdraw.json"
+        ));
+        let changes = get_changes(&transaction);
+        let (active_share_mapping, inactive_share_mapping) = get_share_mappings(changes);
+        let pool_address = standardize_address("0xd0");
+        let delegator_address = standardize_address("0xde1e");
+
+        // The inactive pool is still around, so its shares table is known without the database
+        let deleted_shares = match &changes[2] {
+            APIWriteSetChange::DeleteTableItem(table_item) => {
+                let table_handle = standardize_address(&table_item.handle.to_string());
+                CurrentDelegatorBalance::get_pool_for_share_table(
+                    &table_handle,
+                    &active_share_mapping,
+                    &inactive_share_mapping,
+                )
+            },
+            _ => panic!("Expected a deleted table item"),
+        };
+        assert_eq!(
+            deleted_shares,
+            Some((pool_address.clone(), INACTIVE_SHARES))
+        );
+
+        let pending_withdrawals =
+            CurrentDelegatorPendingWithdrawal::from_transaction(&transaction).unwrap();
+        let pending_withdrawal = pending_withdrawals
+            .get(&(delegator_address, pool_address))
+            .unwrap();
+        assert_eq!(pending_withdrawal.observed_lockup_cycle, None);
     }
 }
@@ -0,0 +1,145 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use super::stake_utils::{ObservedLockupCycleResource, StakeResource};
+use crate::{schema::current_delegator_pending_withdrawals, util::standardize_address};
+use anyhow::Context;
+use aptos_api_types::{
+    DeleteTableItem as APIDeleteTableItem, Transaction as APITransaction,
+    WriteResource as APIWriteResource, WriteSetChange as APIWriteSetChange,
+    WriteTableItem as APIWriteTableItem,
+};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+type TableHandle = String;
+type Address = String;
+// Maps the pending_withdrawals table (delegator -> lockup cycle) to the staking pool address
+pub type PendingWithdrawalMapping = HashMap<TableHandle, Address>;
+pub type CurrentDelegatorPendingWithdrawalPK = (Address, Address);
+pub type CurrentDelegatorPendingWithdrawalMap =
+    HashMap<CurrentDelegatorPendingWithdrawalPK, CurrentDelegatorPendingWithdrawal>;
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(delegator_address, pool_address))]
+#[diesel(table_name = current_delegator_pending_withdrawals)]
+pub struct CurrentDelegatorPendingWithdrawal {
+    pub delegator_address: String,
+    pub pool_address: String,
+    pub table_handle: String,
+    pub observed_lockup_cycle: Option<i64>,
+    pub last_transaction_version: i64,
+}
+
+impl CurrentDelegatorPendingWithdrawal {
+    /// The lockup cycle tells which inactive pool the delegator's inactive shares are in, and whether they can be
+    /// withdrawn yet.
+    pub fn from_write_table_item(
+        write_table_item: &APIWriteTableItem,
+        txn_version: i64,
+        pending_withdrawal_mapping: &PendingWithdrawalMapping,
+    ) -> anyhow::Result<Option<Self>> {
+        let table_handle = standardize_address(&write_table_item.handle.to_string());
+        if let Some(pool_address) = pending_withdrawal_mapping.get(&table_handle) {
+            let data = write_table_item.data.as_ref().context(format!(
+                "This table item should be a pending withdrawal, table_item {:?}, version {}",
+                write_table_item, txn_version
+            ))?;
+            let observed_lockup_cycle: ObservedLockupCycleResource =
+                serde_json::from_value(data.value.clone()).context(format!(
+                    "version {} failed! failed to parse observed lockup cycle, data {:?}",
+                    txn_version, data.value
+                ))?;
+            return Ok(Some(Self {
+                delegator_address: standardize_address(&write_table_item.key.to_string()),
+                pool_address: pool_address.clone(),
+                table_handle,
+                observed_lockup_cycle: Some(observed_lockup_cycle.index),
+                last_transaction_version: txn_version,
+            }));
+        }
+        Ok(None)
+    }
+
+    // Setting lockup cycle to null once the pending withdrawal has been executed
+    pub fn from_delete_table_item(
+        delete_table_item: &APIDeleteTableItem,
+        txn_version: i64,
+        pending_withdrawal_mapping: &PendingWithdrawalMapping,
+    ) -> anyhow::Result<Option<Self>> {
+        let table_handle = standardize_address(&delete_table_item.handle.to_string());
+        if let Some(pool_address) = pending_withdrawal_mapping.get(&table_handle) {
+            return Ok(Some(Self {
+                delegator_address: standardize_address(&delete_table_item.key.to_string()),
+                pool_address: pool_address.clone(),
+                table_handle,
+                observed_lockup_cycle: None,
+                last_transaction_version: txn_version,
+            }));
+        }
+        Ok(None)
+    }
+
+    pub fn get_pending_withdrawal_map(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+    ) -> anyhow::Result<Option<PendingWithdrawalMapping>> {
+        if let Some(StakeResource::DelegationPool(inner)) =
+            StakeResource::from_write_resource(write_resource, txn_version)?
+        {
+            let staking_pool_address = standardize_address(&write_resource.address.to_string());
+            let table_handle = standardize_address(&inner.pending_withdrawals.handle);
+            return Ok(Some(HashMap::from([(table_handle, staking_pool_address)])));
+        }
+        Ok(None)
+    }
+
+    pub fn from_transaction(
+        transaction: &APITransaction,
+    ) -> anyhow::Result<CurrentDelegatorPendingWithdrawalMap> {
+        let mut pending_withdrawal_mapping: PendingWithdrawalMapping = HashMap::new();
+        let mut current_pending_withdrawals: CurrentDelegatorPendingWithdrawalMap = HashMap::new();
+        if let APITransaction::UserTransaction(user_txn) = transaction {
+            let txn_version = user_txn.info.version.0 as i64;
+            // Pending withdrawals only change along with the delegation pool, so the mapping is always complete
+            for wsc in &user_txn.info.changes {
+                if let APIWriteSetChange::WriteResource(write_resource) = wsc {
+                    if let Some(map) =
+                        Self::get_pending_withdrawal_map(write_resource, txn_version)?
+                    {
+                        pending_withdrawal_mapping.extend(map);
+                    }
+                }
+            }
+            for wsc in &user_txn.info.changes {
+                let maybe_pending_withdrawal = match wsc {
+                    APIWriteSetChange::WriteTableItem(table_item) => Self::from_write_table_item(
+                        table_item,
+                        txn_version,
+                        &pending_withdrawal_mapping,
+                    )?,
+                    APIWriteSetChange::DeleteTableItem(table_item) => Self::from_delete_table_item(
+                        table_item,
+                        txn_version,
+                        &pending_withdrawal_mapping,
+                    )?,
+                    _ => None,
+                };
+                if let Some(pending_withdrawal) = maybe_pending_withdrawal {
+                    current_pending_withdrawals.insert(
+                        (
+                            pending_withdrawal.delegator_address.clone(),
+                            pending_withdrawal.pool_address.clone(),
+                        ),
+                        pending_withdrawal,
+                    );
+                }
+            }
+        }
+        Ok(current_pending_withdrawals)
+    }
+}
@@ -5,14 +5,22 @@
 #![allow(clippy::extra_unused_lifetimes)]
 
 use super::stake_utils::StakeResource;
-use crate::{schema::delegated_staking_pools, util::standardize_address};
+use crate::{
+    schema::{
+        current_delegated_staking_pool_balances, delegated_staking_pool_balances,
+        delegated_staking_pools,
+    },
+    util::standardize_address,
+};
 use aptos_api_types::{Transaction, WriteResource, WriteSetChange};
+use bigdecimal::BigDecimal;
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 type StakingPoolAddress = String;
 pub type DelegatorPoolMap = HashMap<StakingPoolAddress, DelegatorPool>;
+pub type DelegatorPoolBalanceMap = HashMap<StakingPoolAddress, CurrentDelegatorPoolBalance>;
 
 // All pools
 #[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
@@ -23,37 +31,108 @@ pub struct DelegatorPool {
     pub first_transaction_version: i64,
 }
 
+// Pool totals and commission every time the pool changes
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, staking_pool_address))]
+#[diesel(table_name = delegated_staking_pool_balances)]
+pub struct DelegatorPoolBalance {
+    pub transaction_version: i64,
+    pub staking_pool_address: String,
+    pub total_coins: BigDecimal,
+    pub total_shares: BigDecimal,
+    pub total_coins_inactive: BigDecimal,
+    pub operator_commission_percentage: BigDecimal,
+    pub active_table_handle: String,
+    pub inactive_table_handle: String,
+}
+
+#[derive(Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(staking_pool_address))]
+#[diesel(table_name = current_delegated_staking_pool_balances)]
+pub struct CurrentDelegatorPoolBalance {
+    pub staking_pool_address: String,
+    pub total_coins: BigDecimal,
+    pub total_shares: BigDecimal,
+    pub total_coins_inactive: BigDecimal,
+    pub operator_commission_percentage: BigDecimal,
+    pub active_table_handle: String,
+    pub inactive_table_handle: String,
+    pub last_transaction_version: i64,
+}
+
 impl DelegatorPool {
-    pub fn from_transaction(transaction: &Transaction) -> anyhow::Result<DelegatorPoolMap> {
+    pub fn from_transaction(
+        transaction: &Transaction,
+    ) -> anyhow::Result<(
+        DelegatorPoolMap,
+        Vec<DelegatorPoolBalance>,
+        DelegatorPoolBalanceMap,
+    )> {
         let mut delegator_pool_map = HashMap::new();
-        // Do a first pass to get the mapping of active_share table handles to staking pool addresses
+        let mut delegator_pool_balances = vec![];
+        let mut delegator_pool_balances_map = HashMap::new();
         if let Transaction::UserTransaction(user_txn) = transaction {
             let txn_version = user_txn.info.version.0 as i64;
             for wsc in &user_txn.info.changes {
                 if let WriteSetChange::WriteResource(write_resource) = wsc {
                     let maybe_write_resource =
                         Self::from_write_resource(write_resource, txn_version)?;
-                    if let Some(map) = maybe_write_resource {
-                        delegator_pool_map.insert(map.staking_pool_address.clone(), map);
+                    if let Some((pool, pool_balance, current_pool_balance)) = maybe_write_resource {
+                        delegator_pool_map.insert(pool.staking_pool_address.clone(), pool);
+                        delegator_pool_balances.push(pool_balance);
+                        delegator_pool_balances_map.insert(
+                            current_pool_balance.staking_pool_address.clone(),
+                            current_pool_balance,
+                        );
                     }
                 }
             }
         }
-        Ok(delegator_pool_map)
+        Ok((
+            delegator_pool_map,
+            delegator_pool_balances,
+            delegator_pool_balances_map,
+        ))
     }
 
     pub fn from_write_resource(
         write_resource: &WriteResource,
         txn_version: i64,
-    ) -> anyhow::Result<Option<Self>> {
-        if let Some(StakeResource::DelegationPool(_)) =
+    ) -> anyhow::Result<Option<(Self, DelegatorPoolBalance, CurrentDelegatorPoolBalance)>> {
+        if let Some(StakeResource::DelegationPool(inner)) =
             StakeResource::from_write_resource(write_resource, txn_version)?
         {
             let staking_pool_address = standardize_address(&write_resource.address.to_string());
-            return Ok(Some(Self {
-                staking_pool_address,
-                first_transaction_version: txn_version,
-            }));
+            let total_coins = inner.active_shares.total_coins;
+            let total_shares = inner.active_shares.total_shares;
+            let active_table_handle = standardize_address(&inner.active_shares.shares.inner.handle);
+            let inactive_table_handle = standardize_address(&inner.inactive_shares.handle);
+            return Ok(Some((
+                Self {
+                    staking_pool_address: staking_pool_address.clone(),
+                    first_transaction_version: txn_version,
+                },
+                DelegatorPoolBalance {
+                    transaction_version: txn_version,
+                    staking_pool_address: staking_pool_address.clone(),
+                    total_coins: total_coins.clone(),
+                    total_shares: total_shares.clone(),
+                    total_coins_inactive: inner.total_coins_inactive.clone(),
+                    operator_commission_percentage: inner.operator_commission_percentage.clone(),
+                    active_table_handle: active_table_handle.clone(),
+                    inactive_table_handle: inactive_table_handle.clone(),
+                },
+                CurrentDelegatorPoolBalance {
+                    staking_pool_address,
+                    total_coins,
+                    total_shares,
+                    total_coins_inactive: inner.total_coins_inactive,
+                    operator_commission_percentage: inner.operator_commission_percentage,
+                    active_table_handle,
+                    inactive_table_handle,
+                    last_transaction_version: txn_version,
+                },
+            )));
         }
         Ok(None)
     }
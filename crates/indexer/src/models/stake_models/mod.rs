@@ -3,6 +3,7 @@
 
 pub mod delegator_activities;
 pub mod delegator_balances;
+pub mod delegator_pending_withdrawals;
 pub mod delegator_pools;
 pub mod proposal_votes;
 pub mod stake_utils;
@@ -16,11 +16,22 @@ pub struct StakePoolResource {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DelegationPoolResource {
     pub active_shares: SharesResource,
+    pub inactive_shares: Table,
+    pub pending_withdrawals: Table,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub operator_commission_percentage: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub total_coins_inactive: BigDecimal,
 }
 
+/// This is pool_u64_unbound::Pool, used for both the active shares and the inactive shares of each lockup cycle
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SharesResource {
     pub shares: SharesInnerResource,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub total_coins: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub total_shares: BigDecimal,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -28,6 +39,12 @@ pub struct SharesInnerResource {
     pub inner: Table,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ObservedLockupCycleResource {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub index: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GovernanceVoteEvent {
     #[serde(deserialize_with = "deserialize_from_string")]
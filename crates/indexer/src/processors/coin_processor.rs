@@ -25,6 +25,14 @@ use field_count::FieldCount;
 use std::{collections::HashMap, fmt::Debug};
 
 pub const NAME: &str = "coin_processor";
+/// Tables written by this processor, with the column holding the version that wrote each row
+pub const DERIVED_TABLES: &[(&str, &str)] = &[
+    ("coin_activities", "transaction_version"),
+    ("coin_infos", "transaction_version_created"),
+    ("coin_balances", "transaction_version"),
+    ("current_coin_balances", "last_transaction_version"),
+    ("coin_supply", "transaction_version"),
+];
 pub struct CoinTransactionProcessor {
     connection_pool: PgDbPool,
 }
@@ -246,6 +254,10 @@ impl TransactionProcessor for CoinTransactionProcessor {
         NAME
     }
 
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)] {
+        DERIVED_TABLES
+    }
+
     async fn process_transactions(
         &self,
         transactions: Vec<APITransaction>,
@@ -29,6 +29,20 @@ use field_count::FieldCount;
 use std::{collections::HashMap, fmt::Debug};
 
 pub const NAME: &str = "default_processor";
+/// Tables written by this processor, with the column holding the version that wrote each row.
+/// `table_metadatas` isn't versioned, and rewriting it is idempotent, so it's left out.
+pub const DERIVED_TABLES: &[(&str, &str)] = &[
+    ("transactions", "version"),
+    ("user_transactions", "version"),
+    ("signatures", "transaction_version"),
+    ("block_metadata_transactions", "version"),
+    ("events", "transaction_version"),
+    ("write_set_changes", "transaction_version"),
+    ("move_modules", "transaction_version"),
+    ("move_resources", "transaction_version"),
+    ("table_items", "transaction_version"),
+    ("current_table_items", "last_transaction_version"),
+];
 pub struct DefaultTransactionProcessor {
     connection_pool: PgDbPool,
 }
@@ -413,6 +427,10 @@ impl TransactionProcessor for DefaultTransactionProcessor {
         NAME
     }
 
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)] {
+        DERIVED_TABLES
+    }
+
     async fn process_transactions(
         &self,
         transactions: Vec<Transaction>,
@@ -0,0 +1,637 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{
+        clean_data_for_db, execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection,
+    },
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::{
+        coin_models::{coin_activities::CoinActivity, coin_infos::CoinInfo},
+        fungible_asset_models::{
+            fungible_asset_activities::FungibleAssetActivity,
+            fungible_asset_balances::{
+                CurrentFungibleAssetBalance, CurrentFungibleAssetBalanceMapping,
+                FungibleAssetBalance,
+            },
+            fungible_asset_metadata::FungibleAssetMetadataModel,
+        },
+        object_models::objects::{CurrentObject, Object, ObjectMapping},
+    },
+    schema,
+    util::parse_timestamp,
+};
+use aptos_api_types::{Transaction as APITransaction, WriteSetChange as APIWriteSetChange};
+use async_trait::async_trait;
+use diesel::{pg::upsert::excluded, result::Error, ExpressionMethods, PgConnection};
+use field_count::FieldCount;
+use std::{collections::HashMap, fmt::Debug};
+
+pub const NAME: &str = "fungible_asset_processor";
+/// Tables written by this processor, with the column holding the version that wrote each row
+pub const DERIVED_TABLES: &[(&str, &str)] = &[
+    ("objects", "transaction_version"),
+    ("current_objects", "last_transaction_version"),
+    ("fungible_asset_metadata", "last_transaction_version"),
+    ("fungible_asset_balances", "transaction_version"),
+    (
+        "current_fungible_asset_balances",
+        "last_transaction_version",
+    ),
+    ("fungible_asset_activities", "transaction_version"),
+];
+pub struct FungibleAssetTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl FungibleAssetTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for FungibleAssetTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "FungibleAssetTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_to_db_impl(
+    conn: &mut PgConnection,
+    objects: &[Object],
+    current_objects: &[CurrentObject],
+    fungible_asset_metadata: &[FungibleAssetMetadataModel],
+    fungible_asset_balances: &[FungibleAssetBalance],
+    current_fungible_asset_balances: &[CurrentFungibleAssetBalance],
+    fungible_asset_activities: &[FungibleAssetActivity],
+) -> Result<(), diesel::result::Error> {
+    insert_objects(conn, objects)?;
+    insert_current_objects(conn, current_objects)?;
+    insert_fungible_asset_metadata(conn, fungible_asset_metadata)?;
+    insert_fungible_asset_balances(conn, fungible_asset_balances)?;
+    insert_current_fungible_asset_balances(conn, current_fungible_asset_balances)?;
+    insert_fungible_asset_activities(conn, fungible_asset_activities)?;
+    Ok(())
+}
+
+fn insert_to_db(
+    conn: &mut PgPoolConnection,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    objects: Vec<Object>,
+    current_objects: Vec<CurrentObject>,
+    fungible_asset_metadata: Vec<FungibleAssetMetadataModel>,
+    fungible_asset_balances: Vec<FungibleAssetBalance>,
+    current_fungible_asset_balances: Vec<CurrentFungibleAssetBalance>,
+    fungible_asset_activities: Vec<FungibleAssetActivity>,
+) -> Result<(), diesel::result::Error> {
+    aptos_logger::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    match conn
+        .build_transaction()
+        .read_write()
+        .run::<_, Error, _>(|pg_conn| {
+            insert_to_db_impl(
+                pg_conn,
+                &objects,
+                &current_objects,
+                &fungible_asset_metadata,
+                &fungible_asset_balances,
+                &current_fungible_asset_balances,
+                &fungible_asset_activities,
+            )
+        }) {
+        Ok(_) => Ok(()),
+        Err(_) => conn
+            .build_transaction()
+            .read_write()
+            .run::<_, Error, _>(|pg_conn| {
+                let fungible_asset_metadata = clean_data_for_db(fungible_asset_metadata, true);
+                let fungible_asset_activities = clean_data_for_db(fungible_asset_activities, true);
+
+                insert_to_db_impl(
+                    pg_conn,
+                    &objects,
+                    &current_objects,
+                    &fungible_asset_metadata,
+                    &fungible_asset_balances,
+                    &current_fungible_asset_balances,
+                    &fungible_asset_activities,
+                )
+            }),
+    }
+}
+
+fn insert_objects(
+    conn: &mut PgConnection,
+    item_to_insert: &[Object],
+) -> Result<(), diesel::result::Error> {
+    use schema::objects::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), Object::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::objects::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, write_set_change_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_objects(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentObject],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_objects::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), CurrentObject::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_objects::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(object_address)
+                .do_update()
+                .set((
+                    owner_address.eq(excluded(owner_address)),
+                    allow_ungated_transfer.eq(excluded(allow_ungated_transfer)),
+                    last_guid_creation_num.eq(excluded(last_guid_creation_num)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    is_deleted.eq(excluded(is_deleted)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE current_objects.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_fungible_asset_metadata(
+    conn: &mut PgConnection,
+    item_to_insert: &[FungibleAssetMetadataModel],
+) -> Result<(), diesel::result::Error> {
+    use schema::fungible_asset_metadata::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        FungibleAssetMetadataModel::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::fungible_asset_metadata::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(asset_type)
+                .do_update()
+                .set((
+                    name.eq(excluded(name)),
+                    symbol.eq(excluded(symbol)),
+                    decimals.eq(excluded(decimals)),
+                    token_standard.eq(excluded(token_standard)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE fungible_asset_metadata.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_fungible_asset_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[FungibleAssetBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::fungible_asset_balances::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), FungibleAssetBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::fungible_asset_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, write_set_change_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_fungible_asset_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentFungibleAssetBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_fungible_asset_balances::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        CurrentFungibleAssetBalance::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_fungible_asset_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(storage_id)
+                .do_update()
+                .set((
+                    owner_address.eq(excluded(owner_address)),
+                    asset_type.eq(excluded(asset_type)),
+                    is_primary.eq(excluded(is_primary)),
+                    is_frozen.eq(excluded(is_frozen)),
+                    amount.eq(excluded(amount)),
+                    last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    token_standard.eq(excluded(token_standard)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE current_fungible_asset_balances.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_fungible_asset_activities(
+    conn: &mut PgConnection,
+    item_to_insert: &[FungibleAssetActivity],
+) -> Result<(), diesel::result::Error> {
+    use schema::fungible_asset_activities::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), FungibleAssetActivity::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::fungible_asset_activities::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, event_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl TransactionProcessor for FungibleAssetTransactionProcessor {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)] {
+        DERIVED_TABLES
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<APITransaction>,
+        start_version: u64,
+        end_version: u64,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let mut conn = self.get_conn();
+
+        let mut all_objects = vec![];
+        let mut all_current_objects: ObjectMapping = HashMap::new();
+        let mut all_fungible_asset_metadata: HashMap<String, FungibleAssetMetadataModel> =
+            HashMap::new();
+        let mut all_fungible_asset_balances = vec![];
+        let mut all_current_fungible_asset_balances: CurrentFungibleAssetBalanceMapping =
+            HashMap::new();
+        let mut all_fungible_asset_activities = vec![];
+
+        for txn in &transactions {
+            let (txn_info, events, maybe_user_request, txn_timestamp) = match txn {
+                APITransaction::GenesisTransaction(inner) => (
+                    &inner.info,
+                    &inner.events,
+                    None,
+                    chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+                ),
+                APITransaction::UserTransaction(inner) => (
+                    &inner.info,
+                    &inner.events,
+                    Some(&inner.request),
+                    parse_timestamp(inner.timestamp.0, inner.info.version.0 as i64),
+                ),
+                _ => continue,
+            };
+            let txn_version = txn_info.version.0 as i64;
+            let block_height = txn_info.block_height.unwrap().0 as i64;
+            let entry_function_id_str =
+                FungibleAssetActivity::get_entry_function_id_str(maybe_user_request);
+
+            // Coin activities, including gas fees, are parsed by the coin models
+            let (coin_activities, _, _, _, _) = CoinActivity::from_transaction(txn, &None);
+            all_fungible_asset_activities.extend(
+                coin_activities
+                    .into_iter()
+                    .map(FungibleAssetActivity::from_coin_activity),
+            );
+
+            // Objects first, since fungible stores need their owner
+            for (index, wsc) in txn_info.changes.iter().enumerate() {
+                let maybe_object = match wsc {
+                    APIWriteSetChange::WriteResource(write_resource) => {
+                        Object::from_write_resource(write_resource, txn_version, index as i64)
+                            .unwrap()
+                    },
+                    APIWriteSetChange::DeleteResource(delete_resource) => {
+                        Object::from_delete_resource(
+                            delete_resource,
+                            txn_version,
+                            index as i64,
+                            &all_current_objects,
+                            &mut conn,
+                        )
+                        .unwrap()
+                    },
+                    _ => None,
+                };
+                if let Some((object, current_object)) = maybe_object {
+                    all_objects.push(object);
+                    all_current_objects
+                        .insert(current_object.object_address.clone(), current_object);
+                }
+            }
+
+            for (index, wsc) in txn_info.changes.iter().enumerate() {
+                if let APIWriteSetChange::WriteResource(write_resource) = wsc {
+                    if let Some(coin_info) =
+                        CoinInfo::from_write_resource(write_resource, txn_version, txn_timestamp)
+                            .unwrap()
+                    {
+                        let metadata = FungibleAssetMetadataModel::from_coin_info(&coin_info);
+                        all_fungible_asset_metadata.insert(metadata.asset_type.clone(), metadata);
+                    }
+                    if let Some(metadata) = FungibleAssetMetadataModel::from_write_resource(
+                        write_resource,
+                        txn_version,
+                        txn_timestamp,
+                    )
+                    .unwrap()
+                    {
+                        all_fungible_asset_metadata.insert(metadata.asset_type.clone(), metadata);
+                    }
+
+                    let maybe_balance = match FungibleAssetBalance::from_write_resource(
+                        write_resource,
+                        index as i64,
+                        txn_version,
+                        txn_timestamp,
+                        &all_current_objects,
+                    )
+                    .unwrap()
+                    {
+                        Some(balance) => Some(balance),
+                        None => FungibleAssetBalance::from_coin_write_resource(
+                            write_resource,
+                            index as i64,
+                            txn_version,
+                            txn_timestamp,
+                        )
+                        .unwrap(),
+                    };
+                    if let Some((balance, current_balance)) = maybe_balance {
+                        all_fungible_asset_balances.push(balance);
+                        all_current_fungible_asset_balances
+                            .insert(current_balance.storage_id.clone(), current_balance);
+                    }
+                }
+            }
+
+            for (index, event) in events.iter().enumerate() {
+                if let Some(activity) = FungibleAssetActivity::from_event(
+                    event,
+                    index as i64,
+                    txn_version,
+                    block_height,
+                    &entry_function_id_str,
+                    txn_timestamp,
+                    &all_current_fungible_asset_balances,
+                    &mut conn,
+                )
+                .unwrap()
+                {
+                    all_fungible_asset_activities.push(activity);
+                }
+            }
+        }
+
+        // Getting list of values and sorting by pk in order to avoid postgres deadlock since we're doing multi threaded db writes
+        let mut all_current_objects = all_current_objects
+            .into_values()
+            .collect::<Vec<CurrentObject>>();
+        let mut all_fungible_asset_metadata = all_fungible_asset_metadata
+            .into_values()
+            .collect::<Vec<FungibleAssetMetadataModel>>();
+        let mut all_current_fungible_asset_balances = all_current_fungible_asset_balances
+            .into_values()
+            .collect::<Vec<CurrentFungibleAssetBalance>>();
+
+        // Sort by PK
+        all_current_objects.sort_by(|a, b| a.object_address.cmp(&b.object_address));
+        all_fungible_asset_metadata.sort_by(|a, b| a.asset_type.cmp(&b.asset_type));
+        all_current_fungible_asset_balances.sort_by(|a, b| a.storage_id.cmp(&b.storage_id));
+
+        let tx_result = insert_to_db(
+            &mut conn,
+            self.name(),
+            start_version,
+            end_version,
+            all_objects,
+            all_current_objects,
+            all_fungible_asset_metadata,
+            all_fungible_asset_balances,
+            all_current_fungible_asset_balances,
+            all_fungible_asset_activities,
+        );
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(
+                self.name(),
+                start_version,
+                end_version,
+            )),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                start_version,
+                end_version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{database::new_db_pool, indexer::tailer::MIGRATIONS};
+    use bigdecimal::BigDecimal;
+    use diesel::{sql_query, QueryDsl, RunQueryDsl};
+    use diesel_migrations::MigrationHarness;
+
+    const SENDER: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+    const RECEIVER: &str = "0x00000000000000000000000000000000000000000000000000000000000000a2";
+    const METADATA: &str = "0x00000000000000000000000000000000000000000000000000000000000000fa";
+    const SENDER_STORE: &str = "0x046e681f9c9e253f743602988103f882ba97e7690d3a111f8b3c3079586203f6";
+    const RECEIVER_STORE: &str =
+        "0xc844511e8fcdc165522d230adc761e29c0329e3bf01e621588fcaec4ed973a3c";
+
+    fn setup_database() -> PgDbPool {
+        let database_url = std::env::var("INDEXER_DATABASE_URL")
+            .expect("must set 'INDEXER_DATABASE_URL' to run tests!");
+        let conn_pool = new_db_pool(database_url.as_str()).unwrap();
+        let mut conn = conn_pool.get().unwrap();
+        for command in [
+            "DROP SCHEMA public CASCADE",
+            "CREATE SCHEMA public",
+            "GRANT ALL ON SCHEMA public TO postgres",
+            "GRANT ALL ON SCHEMA public TO public",
+        ] {
+            sql_query(command).execute(&mut conn).unwrap();
+        }
+        conn.run_pending_migrations(MIGRATIONS).unwrap();
+        conn_pool
+    }
+
+    fn load_transactions() -> Vec<APITransaction> {
+        serde_json::from_str(include_str!("../../fixtures/fungible_asset_transfer.json")).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_process_fungible_asset_transfer() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let conn_pool = setup_database();
+        let processor = FungibleAssetTransactionProcessor::new(conn_pool.clone());
+        processor
+            .process_transactions(load_transactions(), 5000, 5000)
+            .await
+            .unwrap();
+        let mut conn = conn_pool.get().unwrap();
+
+        // Both primary stores are objects owned by the accounts
+        {
+            use schema::current_objects::dsl::*;
+            let owners = current_objects
+                .select((object_address, owner_address))
+                .order(object_address)
+                .load::<(String, String)>(&mut conn)
+                .unwrap();
+            assert_eq!(owners, vec![
+                (SENDER_STORE.to_string(), SENDER.to_string()),
+                (RECEIVER_STORE.to_string(), RECEIVER.to_string()),
+            ]);
+        }
+        {
+            use schema::objects::dsl::*;
+            let owners = objects
+                .select((write_set_change_index, object_address, owner_address))
+                .order(write_set_change_index)
+                .load::<(i64, String, String)>(&mut conn)
+                .unwrap();
+            assert_eq!(owners, vec![
+                (0, SENDER_STORE.to_string(), SENDER.to_string()),
+                (2, RECEIVER_STORE.to_string(), RECEIVER.to_string()),
+            ]);
+        }
+
+        // Balances are attributed to the store owners
+        {
+            use schema::current_fungible_asset_balances::dsl::*;
+            let balances = current_fungible_asset_balances
+                .select((storage_id, owner_address, asset_type, is_primary, amount))
+                .order(storage_id)
+                .load::<(String, String, String, bool, BigDecimal)>(&mut conn)
+                .unwrap();
+            assert_eq!(balances, vec![
+                (
+                    SENDER_STORE.to_string(),
+                    SENDER.to_string(),
+                    METADATA.to_string(),
+                    true,
+                    BigDecimal::from(900),
+                ),
+                (
+                    RECEIVER_STORE.to_string(),
+                    RECEIVER.to_string(),
+                    METADATA.to_string(),
+                    true,
+                    BigDecimal::from(100),
+                ),
+            ]);
+        }
+        {
+            use schema::fungible_asset_balances::dsl::*;
+            let count = fungible_asset_balances
+                .count()
+                .get_result::<i64>(&mut conn)
+                .unwrap();
+            assert_eq!(count, 2);
+        }
+
+        // The gas fee comes from the coin models, the deposit and withdraw from the store events
+        {
+            use schema::fungible_asset_activities::dsl::*;
+            let activities = fungible_asset_activities
+                .select((
+                    event_index,
+                    owner_address,
+                    asset_type,
+                    amount,
+                    type_,
+                    is_gas_fee,
+                ))
+                .order(event_index)
+                .load::<(i64, String, String, Option<BigDecimal>, String, bool)>(&mut conn)
+                .unwrap();
+            assert_eq!(activities, vec![
+                (
+                    -1,
+                    SENDER.to_string(),
+                    "0x1::aptos_coin::AptosCoin".to_string(),
+                    Some(BigDecimal::from(1200 * 100)),
+                    "0x1::aptos_coin::GasFeeEvent".to_string(),
+                    true,
+                ),
+                (
+                    0,
+                    SENDER.to_string(),
+                    METADATA.to_string(),
+                    Some(BigDecimal::from(100)),
+                    "0x1::fungible_asset::WithdrawEvent".to_string(),
+                    false,
+                ),
+                (
+                    1,
+                    RECEIVER.to_string(),
+                    METADATA.to_string(),
+                    Some(BigDecimal::from(100)),
+                    "0x1::fungible_asset::DepositEvent".to_string(),
+                    false,
+                ),
+            ]);
+        }
+    }
+}
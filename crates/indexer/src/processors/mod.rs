@@ -3,11 +3,15 @@
 
 pub mod coin_processor;
 pub mod default_processor;
+pub mod fungible_asset_processor;
+pub mod multisig_processor;
 pub mod stake_processor;
 pub mod token_processor;
 
 use self::{
     coin_processor::NAME as COIN_PROCESSOR_NAME, default_processor::NAME as DEFAULT_PROCESSOR_NAME,
+    fungible_asset_processor::NAME as FUNGIBLE_ASSET_PROCESSOR_NAME,
+    multisig_processor::NAME as MULTISIG_PROCESSOR_NAME,
     stake_processor::NAME as STAKE_PROCESSOR_NAME, token_processor::NAME as TOKEN_PROCESSOR_NAME,
 };
 
@@ -16,6 +20,8 @@ pub enum Processor {
     DefaultProcessor,
     TokenProcessor,
     StakeProcessor,
+    FungibleAssetProcessor,
+    MultisigProcessor,
 }
 
 impl Processor {
@@ -25,6 +31,8 @@ impl Processor {
             TOKEN_PROCESSOR_NAME => Self::TokenProcessor,
             COIN_PROCESSOR_NAME => Self::CoinProcessor,
             STAKE_PROCESSOR_NAME => Self::StakeProcessor,
+            FUNGIBLE_ASSET_PROCESSOR_NAME => Self::FungibleAssetProcessor,
+            MULTISIG_PROCESSOR_NAME => Self::MultisigProcessor,
             _ => panic!("Processor unsupported {}", input_str),
         }
     }
@@ -0,0 +1,317 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{
+        clean_data_for_db, execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection,
+    },
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::multisig_models::{
+        multisig_accounts::{
+            CurrentMultisigAccount, CurrentMultisigAccountMap, CurrentMultisigOwner,
+            CurrentMultisigOwnerMap,
+        },
+        multisig_transactions::{
+            CurrentMultisigTransaction, CurrentMultisigTransactionMap, MultisigVote,
+        },
+    },
+    schema,
+};
+use aptos_api_types::Transaction as APITransaction;
+use async_trait::async_trait;
+use diesel::{pg::upsert::excluded, result::Error, ExpressionMethods, PgConnection};
+use field_count::FieldCount;
+use std::{collections::HashMap, fmt::Debug};
+
+pub const NAME: &str = "multisig_processor";
+/// Tables written by this processor, with the column holding the version that wrote each row
+pub const DERIVED_TABLES: &[(&str, &str)] = &[
+    ("current_multisig_accounts", "last_transaction_version"),
+    ("current_multisig_owners", "last_transaction_version"),
+    ("current_multisig_transactions", "last_transaction_version"),
+    ("multisig_votes", "transaction_version"),
+];
+pub struct MultisigTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl MultisigTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for MultisigTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "MultisigTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_to_db_impl(
+    conn: &mut PgConnection,
+    current_multisig_accounts: &[CurrentMultisigAccount],
+    current_multisig_owners: &[CurrentMultisigOwner],
+    current_multisig_transactions: &[CurrentMultisigTransaction],
+    multisig_votes: &[MultisigVote],
+) -> Result<(), diesel::result::Error> {
+    insert_current_multisig_accounts(conn, current_multisig_accounts)?;
+    insert_current_multisig_owners(conn, current_multisig_owners)?;
+    insert_current_multisig_transactions(conn, current_multisig_transactions)?;
+    insert_multisig_votes(conn, multisig_votes)?;
+    Ok(())
+}
+
+fn insert_to_db(
+    conn: &mut PgPoolConnection,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    current_multisig_accounts: Vec<CurrentMultisigAccount>,
+    current_multisig_owners: Vec<CurrentMultisigOwner>,
+    current_multisig_transactions: Vec<CurrentMultisigTransaction>,
+    multisig_votes: Vec<MultisigVote>,
+) -> Result<(), diesel::result::Error> {
+    aptos_logger::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    match conn
+        .build_transaction()
+        .read_write()
+        .run::<_, Error, _>(|pg_conn| {
+            insert_to_db_impl(
+                pg_conn,
+                &current_multisig_accounts,
+                &current_multisig_owners,
+                &current_multisig_transactions,
+                &multisig_votes,
+            )
+        }) {
+        Ok(_) => Ok(()),
+        Err(_) => conn
+            .build_transaction()
+            .read_write()
+            .run::<_, Error, _>(|pg_conn| {
+                let current_multisig_transactions =
+                    clean_data_for_db(current_multisig_transactions, true);
+
+                insert_to_db_impl(
+                    pg_conn,
+                    &current_multisig_accounts,
+                    &current_multisig_owners,
+                    &current_multisig_transactions,
+                    &multisig_votes,
+                )
+            }),
+    }
+}
+
+fn insert_current_multisig_accounts(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentMultisigAccount],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_multisig_accounts::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), CurrentMultisigAccount::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_multisig_accounts::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(multisig_address)
+                .do_update()
+                .set((
+                    owners.eq(excluded(owners)),
+                    num_signatures_required.eq(excluded(num_signatures_required)),
+                    last_executed_sequence_number.eq(excluded(last_executed_sequence_number)),
+                    next_sequence_number.eq(excluded(next_sequence_number)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE current_multisig_accounts.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_multisig_owners(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentMultisigOwner],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_multisig_owners::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), CurrentMultisigOwner::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_multisig_owners::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((multisig_address, owner_address))
+                .do_update()
+                .set((
+                    is_owner.eq(excluded(is_owner)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE current_multisig_owners.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_multisig_transactions(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentMultisigTransaction],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_multisig_transactions::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        CurrentMultisigTransaction::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_multisig_transactions::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((multisig_address, sequence_number))
+                .do_update()
+                .set((
+                    creator_address.eq(excluded(creator_address)),
+                    payload.eq(excluded(payload)),
+                    payload_hash.eq(excluded(payload_hash)),
+                    votes.eq(excluded(votes)),
+                    num_approvals.eq(excluded(num_approvals)),
+                    num_rejections.eq(excluded(num_rejections)),
+                    status.eq(excluded(status)),
+                    executor_address.eq(excluded(executor_address)),
+                    creation_timestamp.eq(excluded(creation_timestamp)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE current_multisig_transactions.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_multisig_votes(
+    conn: &mut PgConnection,
+    item_to_insert: &[MultisigVote],
+) -> Result<(), diesel::result::Error> {
+    use schema::multisig_votes::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), MultisigVote::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::multisig_votes::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, event_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl TransactionProcessor for MultisigTransactionProcessor {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)] {
+        DERIVED_TABLES
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<APITransaction>,
+        start_version: u64,
+        end_version: u64,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let mut conn = self.get_conn();
+
+        let mut all_current_multisig_accounts: CurrentMultisigAccountMap = HashMap::new();
+        let mut all_current_multisig_owners: CurrentMultisigOwnerMap = HashMap::new();
+        let mut all_current_multisig_transactions: CurrentMultisigTransactionMap = HashMap::new();
+        let mut all_multisig_votes = vec![];
+
+        for txn in &transactions {
+            let (current_multisig_accounts, current_multisig_owners) =
+                CurrentMultisigAccount::from_transaction(txn).unwrap();
+            all_current_multisig_accounts.extend(current_multisig_accounts);
+            all_current_multisig_owners.extend(current_multisig_owners);
+
+            // Votes and executions need the proposals created earlier in the batch
+            let (mut multisig_votes, current_multisig_transactions) =
+                CurrentMultisigTransaction::from_transaction(
+                    txn,
+                    &all_current_multisig_transactions,
+                    &mut conn,
+                )
+                .unwrap();
+            all_multisig_votes.append(&mut multisig_votes);
+            all_current_multisig_transactions.extend(current_multisig_transactions);
+        }
+
+        // Getting list of values and sorting by pk in order to avoid postgres deadlock since we're doing multi threaded db writes
+        let mut all_current_multisig_accounts = all_current_multisig_accounts
+            .into_values()
+            .collect::<Vec<CurrentMultisigAccount>>();
+        let mut all_current_multisig_owners = all_current_multisig_owners
+            .into_values()
+            .collect::<Vec<CurrentMultisigOwner>>();
+        let mut all_current_multisig_transactions = all_current_multisig_transactions
+            .into_values()
+            .collect::<Vec<CurrentMultisigTransaction>>();
+
+        // Sort by PK
+        all_current_multisig_accounts.sort_by(|a, b| a.multisig_address.cmp(&b.multisig_address));
+        all_current_multisig_owners.sort_by(|a, b| {
+            (&a.multisig_address, &a.owner_address).cmp(&(&b.multisig_address, &b.owner_address))
+        });
+        all_current_multisig_transactions.sort_by(|a, b| {
+            (&a.multisig_address, a.sequence_number).cmp(&(&b.multisig_address, b.sequence_number))
+        });
+
+        let tx_result = insert_to_db(
+            &mut conn,
+            self.name(),
+            start_version,
+            end_version,
+            all_current_multisig_accounts,
+            all_current_multisig_owners,
+            all_current_multisig_transactions,
+            all_multisig_votes,
+        );
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(
+                self.name(),
+                start_version,
+                end_version,
+            )),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                start_version,
+                end_version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+}
@@ -11,8 +11,16 @@ use crate::{
     },
     models::stake_models::{
         delegator_activities::DelegatedStakingActivity,
-        delegator_balances::{CurrentDelegatorBalance, CurrentDelegatorBalanceMap},
-        delegator_pools::{DelegatorPool, DelegatorPoolMap},
+        delegator_balances::{
+            CurrentDelegatorBalance, CurrentDelegatorBalanceMap, DelegatorBalance,
+        },
+        delegator_pending_withdrawals::{
+            CurrentDelegatorPendingWithdrawal, CurrentDelegatorPendingWithdrawalMap,
+        },
+        delegator_pools::{
+            CurrentDelegatorPoolBalance, DelegatorPool, DelegatorPoolBalance,
+            DelegatorPoolBalanceMap, DelegatorPoolMap,
+        },
         proposal_votes::ProposalVote,
         staking_pool_voter::{CurrentStakingPoolVoter, StakingPoolVoterMap},
     },
@@ -25,6 +33,24 @@ use field_count::FieldCount;
 use std::{collections::HashMap, fmt::Debug};
 
 pub const NAME: &str = "stake_processor";
+/// Tables written by this processor, with the column holding the version that wrote each row
+pub const DERIVED_TABLES: &[(&str, &str)] = &[
+    ("current_staking_pool_voter", "last_transaction_version"),
+    ("proposal_votes", "transaction_version"),
+    ("delegated_staking_activities", "transaction_version"),
+    ("delegator_balances", "transaction_version"),
+    ("current_delegator_balances", "last_transaction_version"),
+    (
+        "current_delegator_pending_withdrawals",
+        "last_transaction_version",
+    ),
+    ("delegated_staking_pools", "first_transaction_version"),
+    ("delegated_staking_pool_balances", "transaction_version"),
+    (
+        "current_delegated_staking_pool_balances",
+        "last_transaction_version",
+    ),
+];
 pub struct StakeTransactionProcessor {
     connection_pool: PgDbPool,
 }
@@ -51,14 +77,22 @@ fn insert_to_db_impl(
     current_stake_pool_voters: &[CurrentStakingPoolVoter],
     proposal_votes: &[ProposalVote],
     delegator_actvities: &[DelegatedStakingActivity],
-    delegator_balances: &[CurrentDelegatorBalance],
+    delegator_balances: &[DelegatorBalance],
+    current_delegator_balances: &[CurrentDelegatorBalance],
+    current_pending_withdrawals: &[CurrentDelegatorPendingWithdrawal],
     delegator_pools: &[DelegatorPool],
+    delegator_pool_balances: &[DelegatorPoolBalance],
+    current_delegator_pool_balances: &[CurrentDelegatorPoolBalance],
 ) -> Result<(), diesel::result::Error> {
     insert_current_stake_pool_voter(conn, current_stake_pool_voters)?;
     insert_proposal_votes(conn, proposal_votes)?;
     insert_delegator_activities(conn, delegator_actvities)?;
     insert_delegator_balances(conn, delegator_balances)?;
+    insert_current_delegator_balances(conn, current_delegator_balances)?;
+    insert_current_pending_withdrawals(conn, current_pending_withdrawals)?;
     insert_delegator_pools(conn, delegator_pools)?;
+    insert_delegator_pool_balances(conn, delegator_pool_balances)?;
+    insert_current_delegator_pool_balances(conn, current_delegator_pool_balances)?;
     Ok(())
 }
 
@@ -70,8 +104,12 @@ fn insert_to_db(
     current_stake_pool_voters: Vec<CurrentStakingPoolVoter>,
     proposal_votes: Vec<ProposalVote>,
     delegator_actvities: Vec<DelegatedStakingActivity>,
-    delegator_balances: Vec<CurrentDelegatorBalance>,
+    delegator_balances: Vec<DelegatorBalance>,
+    current_delegator_balances: Vec<CurrentDelegatorBalance>,
+    current_pending_withdrawals: Vec<CurrentDelegatorPendingWithdrawal>,
     delegator_pools: Vec<DelegatorPool>,
+    delegator_pool_balances: Vec<DelegatorPoolBalance>,
+    current_delegator_pool_balances: Vec<CurrentDelegatorPoolBalance>,
 ) -> Result<(), diesel::result::Error> {
     aptos_logger::trace!(
         name = name,
@@ -89,7 +127,11 @@ fn insert_to_db(
                 &proposal_votes,
                 &delegator_actvities,
                 &delegator_balances,
+                &current_delegator_balances,
+                &current_pending_withdrawals,
                 &delegator_pools,
+                &delegator_pool_balances,
+                &current_delegator_pool_balances,
             )
         }) {
         Ok(_) => Ok(()),
@@ -101,7 +143,14 @@ fn insert_to_db(
                 let proposal_votes = clean_data_for_db(proposal_votes, true);
                 let delegator_actvities = clean_data_for_db(delegator_actvities, true);
                 let delegator_balances = clean_data_for_db(delegator_balances, true);
+                let current_delegator_balances =
+                    clean_data_for_db(current_delegator_balances, true);
+                let current_pending_withdrawals =
+                    clean_data_for_db(current_pending_withdrawals, true);
                 let delegator_pools = clean_data_for_db(delegator_pools, true);
+                let delegator_pool_balances = clean_data_for_db(delegator_pool_balances, true);
+                let current_delegator_pool_balances =
+                    clean_data_for_db(current_delegator_pool_balances, true);
 
                 insert_to_db_impl(
                     pg_conn,
@@ -109,7 +158,11 @@ fn insert_to_db(
                     &proposal_votes,
                     &delegator_actvities,
                     &delegator_balances,
+                    &current_delegator_balances,
+                    &current_pending_withdrawals,
                     &delegator_pools,
+                    &delegator_pool_balances,
+                    &current_delegator_pool_balances,
                 )
             }),
     }
@@ -188,6 +241,26 @@ fn insert_delegator_activities(
 }
 
 fn insert_delegator_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[DelegatorBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::delegator_balances::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), DelegatorBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::delegator_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, write_set_change_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_delegator_balances(
     conn: &mut PgConnection,
     item_to_insert: &[CurrentDelegatorBalance],
 ) -> Result<(), diesel::result::Error> {
@@ -215,6 +288,37 @@ fn insert_delegator_balances(
     Ok(())
 }
 
+fn insert_current_pending_withdrawals(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentDelegatorPendingWithdrawal],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_delegator_pending_withdrawals::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        CurrentDelegatorPendingWithdrawal::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_delegator_pending_withdrawals::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((delegator_address, pool_address))
+                .do_update()
+                .set((
+                    table_handle.eq(excluded(table_handle)),
+                    observed_lockup_cycle.eq(excluded(observed_lockup_cycle)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(
+                " WHERE current_delegator_pending_withdrawals.last_transaction_version <= EXCLUDED.last_transaction_version ",
+            ),
+        )?;
+    }
+    Ok(())
+}
+
 fn insert_delegator_pools(
     conn: &mut PgConnection,
     item_to_insert: &[DelegatorPool],
@@ -241,12 +345,71 @@ fn insert_delegator_pools(
     Ok(())
 }
 
+fn insert_delegator_pool_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[DelegatorPoolBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::delegated_staking_pool_balances::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), DelegatorPoolBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::delegated_staking_pool_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, staking_pool_address))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_delegator_pool_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentDelegatorPoolBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_delegated_staking_pool_balances::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        CurrentDelegatorPoolBalance::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_delegated_staking_pool_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(staking_pool_address)
+                .do_update()
+                .set((
+                    total_coins.eq(excluded(total_coins)),
+                    total_shares.eq(excluded(total_shares)),
+                    total_coins_inactive.eq(excluded(total_coins_inactive)),
+                    operator_commission_percentage.eq(excluded(operator_commission_percentage)),
+                    active_table_handle.eq(excluded(active_table_handle)),
+                    inactive_table_handle.eq(excluded(inactive_table_handle)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(
+                " WHERE current_delegated_staking_pool_balances.last_transaction_version <= EXCLUDED.last_transaction_version ",
+            ),
+        )?;
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl TransactionProcessor for StakeTransactionProcessor {
     fn name(&self) -> &'static str {
         NAME
     }
 
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)] {
+        DERIVED_TABLES
+    }
+
     async fn process_transactions(
         &self,
         transactions: Vec<APITransaction>,
@@ -256,9 +419,15 @@ impl TransactionProcessor for StakeTransactionProcessor {
         let mut all_current_stake_pool_voters: StakingPoolVoterMap = HashMap::new();
         let mut all_proposal_votes = vec![];
         let mut all_delegator_activities = vec![];
-        let mut all_delegator_balances: CurrentDelegatorBalanceMap = HashMap::new();
+        let mut all_delegator_balances = vec![];
+        let mut all_current_delegator_balances: CurrentDelegatorBalanceMap = HashMap::new();
+        let mut all_current_pending_withdrawals: CurrentDelegatorPendingWithdrawalMap =
+            HashMap::new();
         let mut all_delegator_pools: DelegatorPoolMap = HashMap::new();
+        let mut all_delegator_pool_balances = vec![];
+        let mut all_current_delegator_pool_balances: DelegatorPoolBalanceMap = HashMap::new();
 
+        let mut conn = self.get_conn();
         for txn in &transactions {
             // Add votes data
             let current_stake_pool_voter = CurrentStakingPoolVoter::from_transaction(txn).unwrap();
@@ -271,38 +440,58 @@ impl TransactionProcessor for StakeTransactionProcessor {
             all_delegator_activities.append(&mut delegator_activities);
 
             // Add delegator balances
-            let delegator_balances = CurrentDelegatorBalance::from_transaction(txn).unwrap();
-            all_delegator_balances.extend(delegator_balances);
+            let (mut delegator_balances, current_delegator_balances) =
+                CurrentDelegatorBalance::from_transaction(txn, &mut conn).unwrap();
+            all_delegator_balances.append(&mut delegator_balances);
+            all_current_delegator_balances.extend(current_delegator_balances);
+
+            // Add pending withdrawals
+            let current_pending_withdrawals =
+                CurrentDelegatorPendingWithdrawal::from_transaction(txn).unwrap();
+            all_current_pending_withdrawals.extend(current_pending_withdrawals);
 
             // Add delegator pools
-            let delegator_pools = DelegatorPool::from_transaction(txn).unwrap();
+            let (delegator_pools, mut delegator_pool_balances, current_delegator_pool_balances) =
+                DelegatorPool::from_transaction(txn).unwrap();
             all_delegator_pools.extend(delegator_pools);
+            all_delegator_pool_balances.append(&mut delegator_pool_balances);
+            all_current_delegator_pool_balances.extend(current_delegator_pool_balances);
         }
 
         // Getting list of values and sorting by pk in order to avoid postgres deadlock since we're doing multi threaded db writes
         let mut all_current_stake_pool_voters = all_current_stake_pool_voters
             .into_values()
             .collect::<Vec<CurrentStakingPoolVoter>>();
-        let mut all_delegator_balances = all_delegator_balances
+        let mut all_current_delegator_balances = all_current_delegator_balances
             .into_values()
             .collect::<Vec<CurrentDelegatorBalance>>();
+        let mut all_current_pending_withdrawals = all_current_pending_withdrawals
+            .into_values()
+            .collect::<Vec<CurrentDelegatorPendingWithdrawal>>();
         let mut all_delegator_pools = all_delegator_pools
             .into_values()
             .collect::<Vec<DelegatorPool>>();
+        let mut all_current_delegator_pool_balances = all_current_delegator_pool_balances
+            .into_values()
+            .collect::<Vec<CurrentDelegatorPoolBalance>>();
 
         // Sort by PK
         all_current_stake_pool_voters
             .sort_by(|a, b| a.staking_pool_address.cmp(&b.staking_pool_address));
-        all_delegator_balances.sort_by(|a, b| {
+        all_current_delegator_balances.sort_by(|a, b| {
             (&a.delegator_address, &a.pool_address, &a.pool_type).cmp(&(
                 &b.delegator_address,
                 &b.pool_address,
                 &b.pool_type,
             ))
         });
+        all_current_pending_withdrawals.sort_by(|a, b| {
+            (&a.delegator_address, &a.pool_address).cmp(&(&b.delegator_address, &b.pool_address))
+        });
         all_delegator_pools.sort_by(|a, b| a.staking_pool_address.cmp(&b.staking_pool_address));
+        all_current_delegator_pool_balances
+            .sort_by(|a, b| a.staking_pool_address.cmp(&b.staking_pool_address));
 
-        let mut conn = self.get_conn();
         let tx_result = insert_to_db(
             &mut conn,
             self.name(),
@@ -312,7 +501,11 @@ impl TransactionProcessor for StakeTransactionProcessor {
             all_proposal_votes,
             all_delegator_activities,
             all_delegator_balances,
+            all_current_delegator_balances,
+            all_current_pending_withdrawals,
             all_delegator_pools,
+            all_delegator_pool_balances,
+            all_current_delegator_pool_balances,
         );
         match tx_result {
             Ok(_) => Ok(ProcessingResult::new(
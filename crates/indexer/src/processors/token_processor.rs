@@ -31,6 +31,20 @@ use field_count::FieldCount;
 use std::{collections::HashMap, fmt::Debug};
 
 pub const NAME: &str = "token_processor";
+/// Tables written by this processor, with the column holding the version that wrote each row
+pub const DERIVED_TABLES: &[(&str, &str)] = &[
+    ("tokens", "transaction_version"),
+    ("token_ownerships", "transaction_version"),
+    ("token_datas", "transaction_version"),
+    ("collection_datas", "transaction_version"),
+    ("current_token_ownerships", "last_transaction_version"),
+    ("current_token_datas", "last_transaction_version"),
+    ("current_collection_datas", "last_transaction_version"),
+    ("token_activities", "transaction_version"),
+    ("current_token_pending_claims", "last_transaction_version"),
+    ("current_ans_lookup", "last_transaction_version"),
+    ("nft_points", "transaction_version"),
+];
 pub struct TokenTransactionProcessor {
     connection_pool: PgDbPool,
     ans_contract_address: Option<String>,
@@ -507,6 +521,10 @@ impl TransactionProcessor for TokenTransactionProcessor {
         NAME
     }
 
+    fn derived_tables(&self) -> &'static [(&'static str, &'static str)] {
+        DERIVED_TABLES
+    }
+
     async fn process_transactions(
         &self,
         transactions: Vec<Transaction>,
@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    database::new_db_pool,
+    database::{new_db_pool, PgDbPool},
     indexer::{
         fetcher::TransactionFetcherOptions, processing_result::ProcessingResult, tailer::Tailer,
         transaction_processor::TransactionProcessor,
     },
     processors::{
         coin_processor::CoinTransactionProcessor, default_processor::DefaultTransactionProcessor,
+        fungible_asset_processor::FungibleAssetTransactionProcessor,
+        multisig_processor::MultisigTransactionProcessor,
         stake_processor::StakeTransactionProcessor, token_processor::TokenTransactionProcessor,
         Processor,
     },
@@ -97,6 +99,29 @@ pub fn bootstrap(
     Some(Ok(runtime))
 }
 
+/// Instantiates the processor with the given name, writing to the given pool
+pub fn build_processor(
+    processor_name: &String,
+    conn_pool: PgDbPool,
+    ans_contract_address: Option<String>,
+    nft_points_contract: Option<String>,
+) -> Arc<dyn TransactionProcessor> {
+    match Processor::from_string(processor_name) {
+        Processor::DefaultProcessor => Arc::new(DefaultTransactionProcessor::new(conn_pool)),
+        Processor::TokenProcessor => Arc::new(TokenTransactionProcessor::new(
+            conn_pool,
+            ans_contract_address,
+            nft_points_contract,
+        )),
+        Processor::CoinProcessor => Arc::new(CoinTransactionProcessor::new(conn_pool)),
+        Processor::StakeProcessor => Arc::new(StakeTransactionProcessor::new(conn_pool)),
+        Processor::FungibleAssetProcessor => {
+            Arc::new(FungibleAssetTransactionProcessor::new(conn_pool))
+        },
+        Processor::MultisigProcessor => Arc::new(MultisigTransactionProcessor::new(conn_pool)),
+    }
+}
+
 pub async fn run_forever(config: IndexerConfig, context: Arc<Context>) {
     // All of these options should be filled already with defaults
     let processor_name = config.processor.clone().unwrap();
@@ -123,19 +148,12 @@ pub async fn run_forever(config: IndexerConfig, context: Arc<Context>) {
 
     info!(processor_name = processor_name, "Instantiating tailer... ");
 
-    let processor_enum = Processor::from_string(&processor_name);
-    let processor: Arc<dyn TransactionProcessor> = match processor_enum {
-        Processor::DefaultProcessor => {
-            Arc::new(DefaultTransactionProcessor::new(conn_pool.clone()))
-        },
-        Processor::TokenProcessor => Arc::new(TokenTransactionProcessor::new(
-            conn_pool.clone(),
-            config.ans_contract_address,
-            config.nft_points_contract,
-        )),
-        Processor::CoinProcessor => Arc::new(CoinTransactionProcessor::new(conn_pool.clone())),
-        Processor::StakeProcessor => Arc::new(StakeTransactionProcessor::new(conn_pool.clone())),
-    };
+    let processor = build_processor(
+        &processor_name,
+        conn_pool.clone(),
+        config.ans_contract_address,
+        config.nft_points_contract,
+    );
 
     let options =
         TransactionFetcherOptions::new(None, None, Some(batch_size), None, fetch_tasks as usize);
@@ -139,6 +139,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    current_delegated_staking_pool_balances (staking_pool_address) {
+        staking_pool_address -> Varchar,
+        total_coins -> Numeric,
+        total_shares -> Numeric,
+        total_coins_inactive -> Numeric,
+        operator_commission_percentage -> Numeric,
+        active_table_handle -> Varchar,
+        inactive_table_handle -> Varchar,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     current_delegator_balances (delegator_address, pool_address, pool_type) {
         delegator_address -> Varchar,
@@ -151,6 +165,84 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    current_delegator_pending_withdrawals (delegator_address, pool_address) {
+        delegator_address -> Varchar,
+        pool_address -> Varchar,
+        table_handle -> Varchar,
+        observed_lockup_cycle -> Nullable<Int8>,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_fungible_asset_balances (storage_id) {
+        storage_id -> Varchar,
+        owner_address -> Varchar,
+        asset_type -> Varchar,
+        is_primary -> Bool,
+        is_frozen -> Bool,
+        amount -> Numeric,
+        last_transaction_timestamp -> Timestamp,
+        last_transaction_version -> Int8,
+        token_standard -> Varchar,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_multisig_accounts (multisig_address) {
+        multisig_address -> Varchar,
+        owners -> Jsonb,
+        num_signatures_required -> Int8,
+        last_executed_sequence_number -> Int8,
+        next_sequence_number -> Int8,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_multisig_owners (multisig_address, owner_address) {
+        multisig_address -> Varchar,
+        owner_address -> Varchar,
+        is_owner -> Bool,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_multisig_transactions (multisig_address, sequence_number) {
+        multisig_address -> Varchar,
+        sequence_number -> Int8,
+        creator_address -> Varchar,
+        payload -> Nullable<Text>,
+        payload_hash -> Nullable<Varchar>,
+        votes -> Jsonb,
+        num_approvals -> Int8,
+        num_rejections -> Int8,
+        status -> Varchar,
+        executor_address -> Nullable<Varchar>,
+        creation_timestamp -> Timestamp,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_objects (object_address) {
+        object_address -> Varchar,
+        owner_address -> Varchar,
+        allow_ungated_transfer -> Bool,
+        last_guid_creation_num -> Numeric,
+        last_transaction_version -> Int8,
+        is_deleted -> Bool,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     current_staking_pool_voter (staking_pool_address) {
         staking_pool_address -> Varchar,
@@ -249,6 +341,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    delegated_staking_pool_balances (transaction_version, staking_pool_address) {
+        transaction_version -> Int8,
+        staking_pool_address -> Varchar,
+        total_coins -> Numeric,
+        total_shares -> Numeric,
+        total_coins_inactive -> Numeric,
+        operator_commission_percentage -> Numeric,
+        active_table_handle -> Varchar,
+        inactive_table_handle -> Varchar,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     delegated_staking_pools (staking_pool_address) {
         staking_pool_address -> Varchar,
@@ -257,6 +363,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    delegator_balances (transaction_version, write_set_change_index) {
+        transaction_version -> Int8,
+        write_set_change_index -> Int8,
+        delegator_address -> Varchar,
+        pool_address -> Varchar,
+        pool_type -> Varchar,
+        table_handle -> Varchar,
+        amount -> Numeric,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     events (account_address, creation_number, sequence_number) {
         sequence_number -> Int8,
@@ -272,6 +391,56 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    fungible_asset_activities (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        owner_address -> Varchar,
+        storage_id -> Varchar,
+        asset_type -> Varchar,
+        is_frozen -> Nullable<Bool>,
+        amount -> Nullable<Numeric>,
+        #[sql_name = "type"]
+        type_ -> Varchar,
+        is_gas_fee -> Bool,
+        is_transaction_success -> Bool,
+        entry_function_id_str -> Nullable<Varchar>,
+        block_height -> Int8,
+        token_standard -> Varchar,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    fungible_asset_balances (transaction_version, write_set_change_index) {
+        transaction_version -> Int8,
+        write_set_change_index -> Int8,
+        storage_id -> Varchar,
+        owner_address -> Varchar,
+        asset_type -> Varchar,
+        is_primary -> Bool,
+        is_frozen -> Bool,
+        amount -> Numeric,
+        transaction_timestamp -> Timestamp,
+        token_standard -> Varchar,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    fungible_asset_metadata (asset_type) {
+        asset_type -> Varchar,
+        name -> Varchar,
+        symbol -> Varchar,
+        decimals -> Int4,
+        token_standard -> Varchar,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     indexer_status (db) {
         db -> Varchar,
@@ -319,6 +488,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    multisig_votes (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        multisig_address -> Varchar,
+        sequence_number -> Int8,
+        owner_address -> Varchar,
+        approved -> Bool,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     nft_points (transaction_version) {
         transaction_version -> Int8,
@@ -331,6 +513,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    objects (transaction_version, write_set_change_index) {
+        transaction_version -> Int8,
+        write_set_change_index -> Int8,
+        object_address -> Varchar,
+        owner_address -> Varchar,
+        guid_creation_num -> Numeric,
+        allow_ungated_transfer -> Bool,
+        is_deleted -> Bool,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     processor_status (processor) {
         processor -> Varchar,
@@ -549,20 +744,34 @@ diesel::allow_tables_to_appear_in_same_query!(
     current_ans_lookup,
     current_coin_balances,
     current_collection_datas,
+    current_delegated_staking_pool_balances,
     current_delegator_balances,
+    current_delegator_pending_withdrawals,
+    current_fungible_asset_balances,
+    current_multisig_accounts,
+    current_multisig_owners,
+    current_multisig_transactions,
+    current_objects,
     current_staking_pool_voter,
     current_table_items,
     current_token_datas,
     current_token_ownerships,
     current_token_pending_claims,
     delegated_staking_activities,
+    delegated_staking_pool_balances,
     delegated_staking_pools,
+    delegator_balances,
     events,
+    fungible_asset_activities,
+    fungible_asset_balances,
+    fungible_asset_metadata,
     indexer_status,
     ledger_infos,
     move_modules,
     move_resources,
+    multisig_votes,
     nft_points,
+    objects,
     processor_status,
     processor_statuses,
     proposal_votes,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_indexer_grpc_cache_worker::worker::Worker;
+use aptos_indexer_grpc_utils::cache_operator::CacheClient;
 use clap::Parser;
 use std::{
     sync::{
@@ -29,13 +30,19 @@ fn main() {
     )
     .unwrap();
 
+    // The in-memory cache can't be shared with other processes.
+    assert!(
+        !config.cache_config.is_in_memory(),
+        "In-memory cache is only supported by the data service."
+    );
+    let cache_client = CacheClient::new(&config.cache_config);
     let health_port = config.health_check_port;
 
     let runtime = aptos_runtimes::spawn_named_runtime("indexercache".to_string(), None);
 
     // Start processing.
     runtime.spawn(async move {
-        let mut worker = Worker::new(config).await;
+        let mut worker = Worker::new(config, cache_client).await;
         worker.run().await;
     });
 
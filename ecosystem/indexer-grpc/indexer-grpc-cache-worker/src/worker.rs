@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_indexer_grpc_utils::{
-    cache_operator::CacheOperator,
-    config::IndexerGrpcConfig,
+    cache_operator::{Cache, CacheClient},
+    config::{IndexerGrpcConfig, IndexerGrpcFileStoreConfig},
     create_grpc_client,
     file_store_operator::{FileStoreMetadata, FileStoreOperator},
 };
@@ -21,12 +21,12 @@ type StartingVersion = u64;
 const WORKER_RESTART_DELAY_IF_METADATA_NOT_FOUND_IN_SECS: u64 = 60;
 
 pub struct Worker {
-    /// Redis client.
-    redis_client: redis::Client,
+    /// Cache client, i.e., Redis or in-memory cache.
+    cache_client: CacheClient,
     /// Fullnode grpc address.
     fullnode_grpc_address: String,
-    /// File store bucket name.
-    pub file_store_bucket_name: String,
+    /// File store config.
+    pub file_store_config: IndexerGrpcFileStoreConfig,
 }
 
 /// GRPC data status enum is to identify the data frame.
@@ -51,19 +51,19 @@ pub(crate) enum GrpcDataStatus {
 }
 
 impl Worker {
-    pub async fn new(config: IndexerGrpcConfig) -> Self {
-        let redis_client = redis::Client::open(format!("redis://{}", config.redis_address))
-            .expect("Create redis client failed.");
+    pub async fn new(config: IndexerGrpcConfig, cache_client: CacheClient) -> Self {
         Self {
-            redis_client,
+            cache_client,
             // The fullnode grpc address is required.
             fullnode_grpc_address: format!("http://{}", config.fullnode_grpc_address.unwrap()),
-            file_store_bucket_name: config.file_store_bucket_name,
+            file_store_config: config.file_store_config,
         }
     }
 
     /// The main loop of the worker is:
-    /// 1. Fetch metadata from file store; if not present, exit after 1 minute.
+    /// 1. Fetch metadata from file store; if not present, exit after 1 minute, unless the
+    ///    in-memory cache is used, in which case the file store processor in the same process
+    ///    bootstraps the file store. The in-memory cache is resumed from the file store version.
     /// 2. Start the streaming RPC with version from file store or 0 if not present.
     /// 3. Handle the INIT frame from RawDatastreamResponse:
     ///    * If metadata is not present and cache is empty, start from 0.
@@ -73,16 +73,16 @@ impl Worker {
     pub async fn run(&mut self) {
         // Re-connect if lost.
         loop {
-            let conn = self
-                .redis_client
-                .get_tokio_connection()
+            let cache = self
+                .cache_client
+                .get_cache()
                 .await
-                .expect("Get redis connection failed.");
+                .expect("Get cache connection failed.");
 
             let mut rpc_client = create_grpc_client(self.fullnode_grpc_address.clone()).await;
 
             // 1. Fetch metadata.
-            let file_store_operator = FileStoreOperator::new(self.file_store_bucket_name.clone());
+            let file_store_operator = FileStoreOperator::new(self.file_store_config.clone());
             file_store_operator.verify_storage_bucket_existence().await;
             let mut starting_version = 0;
             let file_store_metadata = file_store_operator.get_file_store_metadata().await;
@@ -90,6 +90,12 @@ impl Worker {
             if let Some(metadata) = file_store_metadata {
                 info!("[Indexer Cache] File store metadata: {:?}", metadata);
                 starting_version = metadata.version;
+                // The in-memory cache is empty after a restart.
+                if let CacheClient::InMemory(in_memory_cache) = &self.cache_client {
+                    in_memory_cache.seed_latest_version(metadata.version);
+                }
+            } else if let CacheClient::InMemory(_) = &self.cache_client {
+                info!("[Indexer Cache] File store is empty. Start from version 0.");
             } else {
                 error!("[Indexer Cache] File store is empty. Exit after 1 minute.");
                 tokio::spawn(async move {
@@ -110,14 +116,14 @@ impl Worker {
             let response = rpc_client.raw_datastream(request).await.unwrap();
 
             // 3&4. Infinite streaming until error happens. Either stream ends or worker crashes.
-            process_streaming_response(conn, file_store_metadata, response.into_inner()).await;
+            process_streaming_response(cache, file_store_metadata, response.into_inner()).await;
         }
     }
 }
 
 async fn process_raw_datastream_response(
     response: RawDatastreamResponse,
-    cache_operator: &mut CacheOperator<redis::aio::Connection>,
+    cache_operator: &mut dyn Cache,
 ) -> anyhow::Result<GrpcDataStatus> {
     match response.response.unwrap() {
         datastream::raw_datastream_response::Response::Status(status) => {
@@ -136,6 +142,8 @@ async fn process_raw_datastream_response(
                         num_of_transactions,
                     })
                 },
+                // The cache worker never sets a filter, so no heartbeats are sent.
+                StatusType::Heartbeat => unreachable!("Heartbeat for unfiltered stream."),
                 StatusType::Unspecified => unreachable!("Unspecified status type."),
             }
         },
@@ -171,13 +179,9 @@ async fn process_raw_datastream_response(
 
 /// Setup the cache operator with init signal, includeing chain id and starting version from fullnode.
 async fn setup_cache_with_init_signal(
-    conn: redis::aio::Connection,
+    mut cache_operator: Box<dyn Cache>,
     init_signal: RawDatastreamResponse,
-) -> (
-    CacheOperator<redis::aio::Connection>,
-    ChainID,
-    StartingVersion,
-) {
+) -> (Box<dyn Cache>, ChainID, StartingVersion) {
     let (fullnode_chain_id, starting_version) =
         match init_signal.response.expect("Response type not exists.") {
             Response::Status(status_frame) => {
@@ -193,7 +197,6 @@ async fn setup_cache_with_init_signal(
             },
         };
 
-    let mut cache_operator = CacheOperator::new(conn);
     cache_operator.cache_setup_if_needed().await;
     cache_operator
         .update_or_verify_chain_id(fullnode_chain_id as u64)
@@ -205,7 +208,7 @@ async fn setup_cache_with_init_signal(
 
 // Infinite streaming processing. Retry if error happens; crash if fatal.
 async fn process_streaming_response(
-    conn: redis::aio::Connection,
+    cache_operator: Box<dyn Cache>,
     file_store_metadata: Option<FileStoreMetadata>,
     mut resp_stream: impl futures_core::Stream<Item = Result<RawDatastreamResponse, tonic::Status>>
         + std::marker::Unpin,
@@ -220,7 +223,7 @@ async fn process_streaming_response(
         },
     };
     let (mut cache_operator, fullnode_chain_id, starting_version) =
-        setup_cache_with_init_signal(conn, init_signal).await;
+        setup_cache_with_init_signal(cache_operator, init_signal).await;
     // It's required to start the worker with the same version as file store.
     if let Some(file_store_metadata) = file_store_metadata {
         if file_store_metadata.version != starting_version {
@@ -246,7 +249,7 @@ async fn process_streaming_response(
             panic!("[Indexer Cache] Chain id mismatch happens during data streaming.");
         }
 
-        match process_raw_datastream_response(received, &mut cache_operator).await {
+        match process_raw_datastream_response(received, cache_operator.as_mut()).await {
             Ok(status) => match status {
                 GrpcDataStatus::ChunkDataOk {
                     start_version,
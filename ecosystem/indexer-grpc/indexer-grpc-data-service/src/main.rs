@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use aptos_indexer_grpc_cache_worker::worker::Worker;
 use aptos_indexer_grpc_data_service::service::DatastreamServer;
+use aptos_indexer_grpc_file_store::processor::Processor;
+use aptos_indexer_grpc_utils::cache_operator::{Cache, CacheClient};
 use aptos_protos::{
     datastream::v1::{
         indexer_stream_server::IndexerStreamServer,
@@ -78,9 +81,32 @@ fn main() {
         .build()
         .expect("Failed to build reflection service");
 
+    let cache_client = CacheClient::new(&config.cache_config);
+    // The in-memory cache is only reachable in-process, so the cache worker and file store
+    // processor run in the data service as well.
+    if config.cache_config.is_in_memory() {
+        let worker_config = config.clone();
+        let worker_cache_client = cache_client.clone();
+        runtime.spawn(async move {
+            let mut worker = Worker::new(worker_config, worker_cache_client).await;
+            worker.run().await;
+        });
+        let processor_config = config.clone();
+        let processor_cache_client = cache_client.clone();
+        runtime.spawn(async move {
+            // The file store processor requires the cache to be set up by the cache worker.
+            let mut cache = processor_cache_client.get_cache().await.unwrap();
+            while cache.get_chain_id().await.is_err() {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            }
+            let mut processor = Processor::new(processor_config, processor_cache_client);
+            processor.run().await;
+        });
+    }
+
     // Add authentication interceptor.
     runtime.spawn(async move {
-        let server = DatastreamServer::new(config);
+        let server = DatastreamServer::new(config, cache_client);
         let svc = IndexerStreamServer::with_interceptor(server, authentication_inceptor);
         Server::builder()
             .add_service(reflection_service)
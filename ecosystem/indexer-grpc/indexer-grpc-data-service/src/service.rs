@@ -3,21 +3,23 @@
 
 use aptos_indexer_grpc_utils::{
     build_protobuf_encoded_transaction_wrappers,
-    cache_operator::{CacheBatchGetStatus, CacheOperator},
+    cache_operator::{Cache, CacheBatchGetStatus, CacheClient},
     config::IndexerGrpcConfig,
     constants::GRPC_AUTH_TOKEN_HEADER,
     file_store_operator::FileStoreOperator,
+    filter::TransactionFilter,
     EncodedTransactionWithVersion,
 };
 use aptos_logger::{error, info, warn};
 use aptos_moving_average::MovingAverage;
 use aptos_protos::datastream::v1::{
     indexer_stream_server::IndexerStream,
-    raw_datastream_response::Response as DatastreamProtoResponse, RawDatastreamRequest,
-    RawDatastreamResponse, StreamStatus, TransactionOutput, TransactionsOutput,
+    raw_datastream_response::Response as DatastreamProtoResponse, stream_status::StatusType,
+    RawDatastreamRequest, RawDatastreamResponse, StreamStatus, TransactionOutput,
+    TransactionsOutput,
 };
 use futures::Stream;
-use std::{pin::Pin, sync::Arc, time::Duration};
+use std::{pin::Pin, time::Duration};
 use tokio::sync::mpsc::{channel, error::TrySendError};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
@@ -40,17 +42,14 @@ const RESPONSE_CHANNEL_FULL_BACKOFF_DURATION_MS: u64 = 1000;
 const MAX_RESPONSE_CHANNEL_SIZE: usize = 40;
 
 pub struct DatastreamServer {
-    pub redis_client: Arc<redis::Client>,
+    pub cache_client: CacheClient,
     pub server_config: IndexerGrpcConfig,
 }
 
 impl DatastreamServer {
-    pub fn new(config: IndexerGrpcConfig) -> Self {
+    pub fn new(config: IndexerGrpcConfig, cache_client: CacheClient) -> Self {
         Self {
-            redis_client: Arc::new(
-                redis::Client::open(format!("redis://{}", config.redis_address))
-                    .expect("Create redis client failed."),
-            ),
+            cache_client,
             server_config: config,
         }
     }
@@ -77,8 +76,11 @@ impl IndexerStream for DatastreamServer {
     ///    1.2. If the data is not in cache, fetch the data from file store.
     ///    1.3. If the data is not in file store, stream connection will break.
     ///    1.4  If error happens, retry after a short sleep.
-    /// 2. Push data into channel to stream to the client.
-    ///    2.1. If the channel is full, do not fetch and retry after a short sleep.
+    /// 2. Filter the data if the request sets a filter.
+    /// 3. Push data into channel to stream to the client.
+    ///    3.1. If the channel is full, do not fetch and retry after a short sleep.
+    ///    3.2. If the request sets a filter, a heartbeat with the version range of the batch
+    ///         follows the data, which is skipped if no transaction matches.
     async fn raw_datastream(
         &self,
         req: Request<RawDatastreamRequest>,
@@ -95,33 +97,34 @@ impl IndexerStream for DatastreamServer {
         };
         // Response channel to stream the data to the client.
         let (tx, rx) = channel(MAX_RESPONSE_CHANNEL_SIZE);
-        let mut current_version = match req.into_inner().starting_version {
+        let request = req.into_inner();
+        let mut current_version = match request.starting_version {
             Some(version) => version,
             None => return Result::Err(Status::aborted("Starting version is not set")),
         };
+        let transaction_filter = request.filter.map(TransactionFilter::from);
 
-        let file_store_bucket_name = self.server_config.file_store_bucket_name.clone();
-        let redis_client = self.redis_client.clone();
+        let file_store_config = self.server_config.file_store_config.clone();
+        let cache_client = self.cache_client.clone();
 
         tokio::spawn(async move {
-            let conn = match redis_client.get_async_connection().await {
-                Ok(conn) => conn,
+            let mut cache_operator = match cache_client.get_cache().await {
+                Ok(cache_operator) => cache_operator,
                 Err(e) => {
                     tx.send(Err(Status::unavailable(
-                        "[Indexer Data] Cannot connect to Redis; please retry.",
+                        "[Indexer Data] Cannot connect to cache; please retry.",
                     )))
                     .await
                     .unwrap();
                     error!(
                         token_id = request_token.as_str(),
                         error = e.to_string(),
-                        "[Indexer Data] Failed to get redis connection."
+                        "[Indexer Data] Failed to get cache connection."
                     );
                     return;
                 },
             };
-            let mut cache_operator = CacheOperator::new(conn);
-            let file_store_operator = FileStoreOperator::new(file_store_bucket_name);
+            let file_store_operator = FileStoreOperator::new(file_store_config);
             file_store_operator.verify_storage_bucket_existence().await;
             let request_token = request_token.to_string();
 
@@ -164,21 +167,52 @@ impl IndexerStream for DatastreamServer {
             .unwrap();
             loop {
                 // 1. Fetch data from cache and file store.
-                let transaction_data =
-                    match data_fetch(current_version, &mut cache_operator, &file_store_operator)
-                        .await
-                    {
-                        Ok(TransactionsDataStatus::Success(transactions)) => transactions,
-                        Ok(TransactionsDataStatus::AheadOfCache) => {
-                            ahead_of_cache_data_handling().await;
-                            // Retry after a short sleep.
-                            continue;
-                        },
-                        Ok(TransactionsDataStatus::DataGap) => {
-                            data_gap_handling(request_token.as_str(), current_version);
-                            // End the data stream.
-                            break;
-                        },
+                let transaction_data = match data_fetch(
+                    current_version,
+                    cache_operator.as_mut(),
+                    &file_store_operator,
+                )
+                .await
+                {
+                    Ok(TransactionsDataStatus::Success(transactions)) => transactions,
+                    Ok(TransactionsDataStatus::AheadOfCache) => {
+                        ahead_of_cache_data_handling().await;
+                        // Retry after a short sleep.
+                        continue;
+                    },
+                    Ok(TransactionsDataStatus::DataGap) => {
+                        data_gap_handling(request_token.as_str(), current_version);
+                        // End the data stream.
+                        break;
+                    },
+                    Err(e) => {
+                        data_fetch_error_handling(
+                            e,
+                            request_token.as_str(),
+                            current_version,
+                            chain_id,
+                            request_id.as_str(),
+                        )
+                        .await;
+                        // Retry after a short sleep.
+                        continue;
+                    },
+                };
+
+                let current_batch_size = transaction_data.len();
+                let end_of_batch_version = transaction_data.last().unwrap().1;
+
+                // 2. Filter the data; the version range is kept for the heartbeat.
+                let (transaction_data, mut heartbeat) = match &transaction_filter {
+                    Some(filter) => match filter.filter_encoded(transaction_data) {
+                        Ok(transactions) => (
+                            transactions,
+                            Some(heartbeat_response_builder(
+                                current_version,
+                                end_of_batch_version,
+                                chain_id as u32,
+                            )),
+                        ),
                         Err(e) => {
                             data_fetch_error_handling(
                                 e,
@@ -191,12 +225,18 @@ impl IndexerStream for DatastreamServer {
                             // Retry after a short sleep.
                             continue;
                         },
-                    };
+                    },
+                    None => (transaction_data, None),
+                };
+                let num_matched_transactions = transaction_data.len();
 
-                // 2. Push the data to the response channel, i.e. stream the data to the client.
-                let current_batch_size = transaction_data.len();
-                let end_of_batch_version = transaction_data.last().unwrap().1;
-                let resp_item = raw_datastream_response_builder(transaction_data, chain_id as u32);
+                // 3. Push the data to the response channel, i.e. stream the data to the client.
+                let resp_item = if transaction_data.is_empty() {
+                    // Nothing matches the filter; only the heartbeat is sent.
+                    heartbeat.take().unwrap()
+                } else {
+                    raw_datastream_response_builder(transaction_data, chain_id as u32)
+                };
                 match tx.try_send(Result::<RawDatastreamResponse, Status>::Ok(resp_item)) {
                     Ok(_) => {},
                     Err(TrySendError::Full(_)) => {
@@ -220,7 +260,19 @@ impl IndexerStream for DatastreamServer {
                         break;
                     },
                 }
-                // 3. Update the current version and record current tps.
+                if let Some(heartbeat) = heartbeat {
+                    // The data is already in the channel, so wait for the heartbeat to be sent
+                    // instead of retrying the batch.
+                    if tx.send(Ok(heartbeat)).await.is_err() {
+                        warn!(
+                            token_id = request_token.as_str(),
+                            request_id = request_id.as_str(),
+                            "[Indexer Data] Receiver is closed; exiting."
+                        );
+                        break;
+                    }
+                }
+                // 4. Update the current version and record current tps.
                 tps_calculator.tick_now(current_batch_size as u64);
                 current_version = end_of_batch_version + 1;
                 info!(
@@ -228,6 +280,7 @@ impl IndexerStream for DatastreamServer {
                     request_id = request_id.as_str(),
                     current_version = current_version,
                     batch_size = current_batch_size,
+                    num_matched_transactions = num_matched_transactions,
                     tps = (tps_calculator.avg() * 1000.0) as u64,
                     "[Indexer Data] Sending batch."
                 );
@@ -266,11 +319,28 @@ fn raw_datastream_response_builder(
     }
 }
 
+/// Builds the heartbeat for a filtered raw datastream request, i.e. all transactions from
+/// `start_version` to `end_version` were processed, including the ones filtered out.
+fn heartbeat_response_builder(
+    start_version: u64,
+    end_version: u64,
+    chain_id: u32,
+) -> RawDatastreamResponse {
+    RawDatastreamResponse {
+        response: Some(DatastreamProtoResponse::Status(StreamStatus {
+            r#type: StatusType::Heartbeat as i32,
+            start_version,
+            end_version: Some(end_version),
+        })),
+        chain_id,
+    }
+}
+
 /// Fetches data from cache or the file store. It returns the data if it is ready in the cache or file store.
 /// Otherwise, it returns the status of the data fetching.
 async fn data_fetch(
     starting_version: u64,
-    cache_operator: &mut CacheOperator<redis::aio::Connection>,
+    cache_operator: &mut dyn Cache,
     file_store_operator: &FileStoreOperator,
 ) -> anyhow::Result<TransactionsDataStatus> {
     let batch_get_result = cache_operator
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_indexer_grpc_file_store::processor::Processor;
+use aptos_indexer_grpc_utils::cache_operator::CacheClient;
 use clap::Parser;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -25,11 +26,18 @@ fn main() {
     )
     .unwrap();
 
+    // The in-memory cache can't be shared with other processes.
+    assert!(
+        !config.cache_config.is_in_memory(),
+        "In-memory cache is only supported by the data service."
+    );
+    let cache_client = CacheClient::new(&config.cache_config);
+
     let runtime = aptos_runtimes::spawn_named_runtime("indexerfile".to_string(), None);
 
     let health_port = config.health_check_port;
     runtime.spawn(async move {
-        let mut processor = Processor::new(config);
+        let mut processor = Processor::new(config, cache_client);
         processor.run().await;
     });
 
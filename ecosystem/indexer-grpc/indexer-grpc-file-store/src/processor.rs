@@ -3,7 +3,7 @@
 
 use aptos_indexer_grpc_utils::{
     build_protobuf_encoded_transaction_wrappers,
-    cache_operator::{CacheBatchGetStatus, CacheOperator},
+    cache_operator::{Cache, CacheBatchGetStatus, CacheClient},
     config::IndexerGrpcConfig,
     constants::BLOB_STORAGE_SIZE,
     file_store_operator::FileStoreOperator,
@@ -17,15 +17,17 @@ const AHEAD_OF_CACHE_SLEEP_DURATION_IN_MILLIS: u64 = 1000;
 
 /// Processor tails the data in cache and stores the data in file store.
 pub struct Processor {
-    cache_operator: Option<CacheOperator<redis::aio::Connection>>,
+    cache_client: CacheClient,
+    cache_operator: Option<Box<dyn Cache>>,
     file_store_processor: Option<FileStoreOperator>,
     cache_chain_id: Option<u64>,
     config: IndexerGrpcConfig,
 }
 
 impl Processor {
-    pub fn new(config: IndexerGrpcConfig) -> Self {
+    pub fn new(config: IndexerGrpcConfig, cache_client: CacheClient) -> Self {
         Self {
+            cache_client,
             cache_operator: None,
             file_store_processor: None,
             cache_chain_id: None,
@@ -33,23 +35,20 @@ impl Processor {
         }
     }
 
-    /// Init the processor, including creating the redis connection and file store operator.
+    /// Init the processor, including creating the cache connection and file store operator.
     async fn init(&mut self) {
-        // Connection to redis is a hard dependency for file store processor.
-        let conn = redis::Client::open(format!("redis://{}", self.config.redis_address))
-            .expect("Create redis client failed.")
-            .get_async_connection()
+        // Connection to cache is a hard dependency for file store processor.
+        let mut cache_operator = self
+            .cache_client
+            .get_cache()
             .await
-            .expect("Create redis connection failed.");
-
-        let mut cache_operator = CacheOperator::new(conn);
+            .expect("Create cache connection failed.");
         let chain_id = cache_operator
             .get_chain_id()
             .await
             .expect("Get chain id failed.");
 
-        let file_store_operator =
-            FileStoreOperator::new(self.config.file_store_bucket_name.clone());
+        let file_store_operator = FileStoreOperator::new(self.config.file_store_config.clone());
         file_store_operator.verify_storage_bucket_existence().await;
 
         self.cache_operator = Some(cache_operator);
@@ -559,25 +559,27 @@ pub fn convert_multi_ed25519_signature(
 pub fn convert_account_signature(
     account_signature: &AccountSignature,
 ) -> transaction::AccountSignature {
-    let r#type = match account_signature {
-        AccountSignature::Ed25519Signature(_) => transaction::account_signature::Type::Ed25519,
-        AccountSignature::MultiEd25519Signature(_) => {
-            transaction::account_signature::Type::MultiEd25519
-        },
-    };
-    let signature = match account_signature {
-        AccountSignature::Ed25519Signature(s) => {
-            transaction::account_signature::Signature::Ed25519(convert_ed25519_signature(s))
-        },
-        AccountSignature::MultiEd25519Signature(s) => {
-            transaction::account_signature::Signature::MultiEd25519(
+    let (r#type, signature) = match account_signature {
+        AccountSignature::Ed25519Signature(s) => (
+            transaction::account_signature::Type::Ed25519,
+            Some(transaction::account_signature::Signature::Ed25519(
+                convert_ed25519_signature(s),
+            )),
+        ),
+        AccountSignature::MultiEd25519Signature(s) => (
+            transaction::account_signature::Type::MultiEd25519,
+            Some(transaction::account_signature::Signature::MultiEd25519(
                 convert_multi_ed25519_signature(s),
-            )
+            )),
+        ),
+        // The protobuf schema doesn't support these signature schemes yet
+        AccountSignature::Secp256k1EcdsaSignature(_) | AccountSignature::WebAuthnSignature(_) => {
+            (transaction::account_signature::Type::Unspecified, None)
         },
     };
     transaction::AccountSignature {
         r#type: r#type as i32,
-        signature: Some(signature),
+        signature,
     }
 }
 
@@ -588,41 +590,62 @@ pub fn convert_transaction_signature(
         None => return None,
         Some(s) => s,
     };
-    let r#type = match signature {
-        TransactionSignature::Ed25519Signature(_) => transaction::signature::Type::Ed25519,
-        TransactionSignature::MultiEd25519Signature(_) => {
-            transaction::signature::Type::MultiEd25519
-        },
-        TransactionSignature::MultiAgentSignature(_) => transaction::signature::Type::MultiAgent,
-    };
-
-    let signature = match signature {
-        TransactionSignature::Ed25519Signature(s) => {
-            transaction::signature::Signature::Ed25519(convert_ed25519_signature(s))
-        },
-        TransactionSignature::MultiEd25519Signature(s) => {
-            transaction::signature::Signature::MultiEd25519(convert_multi_ed25519_signature(s))
-        },
-        TransactionSignature::MultiAgentSignature(s) => {
-            transaction::signature::Signature::MultiAgent(transaction::MultiAgentSignature {
-                sender: Some(convert_account_signature(&s.sender)),
-                secondary_signer_addresses: s
-                    .secondary_signer_addresses
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect(),
-                secondary_signers: s
-                    .secondary_signers
-                    .iter()
-                    .map(convert_account_signature)
-                    .collect(),
-            })
+    let (r#type, signature) = match signature {
+        TransactionSignature::Ed25519Signature(s) => (
+            transaction::signature::Type::Ed25519,
+            Some(transaction::signature::Signature::Ed25519(
+                convert_ed25519_signature(s),
+            )),
+        ),
+        TransactionSignature::MultiEd25519Signature(s) => (
+            transaction::signature::Type::MultiEd25519,
+            Some(transaction::signature::Signature::MultiEd25519(
+                convert_multi_ed25519_signature(s),
+            )),
+        ),
+        TransactionSignature::MultiAgentSignature(s) => (
+            transaction::signature::Type::MultiAgent,
+            Some(transaction::signature::Signature::MultiAgent(
+                transaction::MultiAgentSignature {
+                    sender: Some(convert_account_signature(&s.sender)),
+                    secondary_signer_addresses: s
+                        .secondary_signer_addresses
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect(),
+                    secondary_signers: s
+                        .secondary_signers
+                        .iter()
+                        .map(convert_account_signature)
+                        .collect(),
+                },
+            )),
+        ),
+        // Single sender signatures are represented by the signature of the sender
+        TransactionSignature::SingleSenderSignature(s) => match &s.sender {
+            AccountSignature::Ed25519Signature(s) => (
+                transaction::signature::Type::Ed25519,
+                Some(transaction::signature::Signature::Ed25519(
+                    convert_ed25519_signature(s),
+                )),
+            ),
+            AccountSignature::MultiEd25519Signature(s) => (
+                transaction::signature::Type::MultiEd25519,
+                Some(transaction::signature::Signature::MultiEd25519(
+                    convert_multi_ed25519_signature(s),
+                )),
+            ),
+            // The protobuf schema doesn't support these signature schemes yet
+            AccountSignature::Secp256k1EcdsaSignature(_)
+            | AccountSignature::WebAuthnSignature(_) => {
+                (transaction::signature::Type::Unspecified, None)
+            },
         },
     };
 
     Some(transaction::Signature {
         r#type: r#type as i32,
-        signature: Some(signature),
+        signature,
     })
 }
 
@@ -4,6 +4,7 @@
 use crate::stream_coordinator::IndexerStreamCoordinator;
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
+use aptos_indexer_grpc_utils::filter::TransactionFilter;
 use aptos_logger::{error, info};
 use aptos_mempool::MempoolClientSender;
 use aptos_moving_average::MovingAverage;
@@ -97,6 +98,7 @@ impl IndexerStream for IndexerStreamService {
         // Gets configs for the stream, partly from the request and partly from the node config
         let r = req.into_inner();
         let starting_version = r.starting_version.expect("Starting version must be set");
+        let transaction_filter = r.filter.map(TransactionFilter::from);
         let processor_task_count = self.processor_task_count;
         let processor_batch_size = self.processor_batch_size;
         let output_batch_size = self.output_batch_size;
@@ -120,6 +122,7 @@ impl IndexerStream for IndexerStreamService {
                 processor_task_count,
                 processor_batch_size,
                 output_batch_size,
+                transaction_filter,
                 tx.clone(),
             );
             // Sends init message (one time per request) to the client in the with chain id and starting version. Basically a handshake
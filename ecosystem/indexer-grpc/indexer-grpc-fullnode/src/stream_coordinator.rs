@@ -8,6 +8,7 @@ use crate::{
 };
 use aptos_api::context::Context;
 use aptos_api_types::{AsConverter, Transaction as APITransaction, TransactionOnChainData};
+use aptos_indexer_grpc_utils::filter::TransactionFilter;
 use aptos_logger::{error, info, sample, sample::SampleRate};
 use aptos_protos::{
     datastream::v1::{
@@ -29,6 +30,7 @@ pub struct IndexerStreamCoordinator {
     pub processor_batch_size: u16,
     pub output_batch_size: u16,
     pub highest_known_version: u64,
+    pub transaction_filter: Option<Arc<TransactionFilter>>,
     pub context: Arc<Context>,
     pub transactions_sender: mpsc::Sender<Result<RawDatastreamResponse, tonic::Status>>,
 }
@@ -47,6 +49,7 @@ impl IndexerStreamCoordinator {
         processor_task_count: u16,
         processor_batch_size: u16,
         output_batch_size: u16,
+        transaction_filter: Option<TransactionFilter>,
         transactions_sender: mpsc::Sender<Result<RawDatastreamResponse, tonic::Status>>,
     ) -> Self {
         Self {
@@ -55,6 +58,7 @@ impl IndexerStreamCoordinator {
             processor_batch_size,
             output_batch_size,
             highest_known_version: 0,
+            transaction_filter: transaction_filter.map(Arc::new),
             context,
             transactions_sender,
         }
@@ -66,8 +70,10 @@ impl IndexerStreamCoordinator {
     /// Processing transactions in 4 stages:
     /// 1. Fetch transactions from storage
     /// 2. Convert transactions to rust objects (for example stringifying move structs into json)
-    /// 3. Convert into protobuf objects
+    /// 3. Convert into protobuf objects and drop the ones not matching the filter, if any
     /// 4. Encode protobuf objects (base64)
+    /// The returned end version is the end of the batch even if its last transactions are
+    /// filtered out, so the batch end status tells the client how far the stream progressed.
     pub async fn process_next_batch(&mut self) -> Vec<Result<EndVersion, Status>> {
         let ledger_chain_id = self.context.chain_id().id();
         let mut tasks = vec![];
@@ -78,13 +84,18 @@ impl IndexerStreamCoordinator {
             let context = self.context.clone();
             let ledger_version = self.highest_known_version;
             let transaction_sender = self.transactions_sender.clone();
+            let transaction_filter = self.transaction_filter.clone();
+            let end_version = batch.start_version + batch.num_transactions_to_fetch as u64 - 1;
 
             let task = tokio::spawn(async move {
                 // Fetch and convert transactions from API
                 let raw_txns =
                     Self::fetch_raw_txns_with_retries(context.clone(), ledger_version, batch).await;
                 let api_txns = Self::convert_to_api_txns(context, raw_txns).await;
-                let pb_txns = Self::convert_to_pb_txns(api_txns);
+                let mut pb_txns = Self::convert_to_pb_txns(api_txns);
+                if let Some(filter) = transaction_filter {
+                    pb_txns.retain(|txn| filter.matches(txn));
+                }
                 let encoded = Self::encode_pb_txns(pb_txns);
                 // Wrap in stream response object and send to channel
                 for chunk in encoded.chunks(output_batch_size as usize) {
@@ -104,7 +115,7 @@ impl IndexerStreamCoordinator {
                         },
                     }
                 }
-                Ok(encoded.last().unwrap().version)
+                Ok(end_version)
             });
             tasks.push(task);
         }
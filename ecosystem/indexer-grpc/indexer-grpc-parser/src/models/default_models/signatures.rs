@@ -41,14 +41,25 @@ pub struct Signature {
 }
 
 impl Signature {
-    /// Returns a flattened list of signatures. If signature is a Ed25519Signature, then return a vector of 1 signature
+    /// Returns a flattened list of signatures. If signature is a Ed25519Signature, then return a vector of 1 signature.
+    /// Signature schemes that aren't supported by the protobuf schema (i.e., unset signatures) are skipped.
     pub fn from_user_transaction(
         s: &TransactionSignaturePB,
         sender: &String,
         transaction_version: i64,
         transaction_block_height: i64,
     ) -> Result<Vec<Self>> {
-        match s.signature.as_ref().unwrap() {
+        let signature = match s.signature.as_ref() {
+            Some(signature) => signature,
+            None => {
+                aptos_logger::warn!(
+                    transaction_version = transaction_version,
+                    "Skipping the unsupported signature of the user transaction"
+                );
+                return Ok(vec![]);
+            },
+        };
+        match signature {
             SignatureEnum::Ed25519(sig) => Ok(vec![Self::parse_single_signature(
                 sig,
                 sender,
@@ -77,10 +88,11 @@ impl Signature {
     }
 
     pub fn get_signature_type(t: &TransactionSignaturePB) -> String {
-        match t.signature.as_ref().unwrap() {
-            SignatureEnum::Ed25519(_) => String::from("ed25519_signature"),
-            SignatureEnum::MultiEd25519(_) => String::from("multi_ed25519_signature"),
-            SignatureEnum::MultiAgent(_) => String::from("multi_agent_signature"),
+        match t.signature.as_ref() {
+            Some(SignatureEnum::Ed25519(_)) => String::from("ed25519_signature"),
+            Some(SignatureEnum::MultiEd25519(_)) => String::from("multi_ed25519_signature"),
+            Some(SignatureEnum::MultiAgent(_)) => String::from("multi_agent_signature"),
+            None => String::from("unknown_signature"),
         }
     }
 
@@ -164,8 +176,12 @@ impl Signature {
     ) -> Result<Vec<Self>> {
         let mut signatures = Vec::default();
         // process sender signature
+        let sender_sig = s
+            .sender
+            .as_ref()
+            .context("Failed to parse the sender signature for multi agent signature")?;
         signatures.append(&mut Self::parse_multi_agent_signature_helper(
-            s.sender.as_ref().unwrap(),
+            sender_sig,
             sender,
             transaction_version,
             transaction_block_height,
@@ -200,7 +216,17 @@ impl Signature {
         multi_agent_index: i64,
         override_address: Option<&String>,
     ) -> Vec<Self> {
-        let signature = s.signature.as_ref().unwrap();
+        let signature = match s.signature.as_ref() {
+            Some(signature) => signature,
+            None => {
+                aptos_logger::warn!(
+                    transaction_version = transaction_version,
+                    multi_agent_index = multi_agent_index,
+                    "Skipping the unsupported account signature of the multi agent signature"
+                );
+                return vec![];
+            },
+        };
         match signature {
             AccountSignatureEnum::Ed25519(sig) => vec![Self::parse_single_signature(
                 sig,
@@ -421,6 +421,7 @@ pub fn grpc_request_builder(
     let mut request = tonic::Request::new(RawDatastreamRequest {
         starting_version: Some(starting_version),
         transactions_count: None,
+        filter: None,
     });
     request.metadata_mut().insert(
         aptos_indexer_grpc_utils::constants::GRPC_AUTH_TOKEN_HEADER,
@@ -1,20 +1,23 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::constants::BLOB_STORAGE_SIZE;
+use crate::{
+    config::IndexerGrpcCacheConfig, constants::BLOB_STORAGE_SIZE, in_memory_cache::InMemoryCache,
+};
 use redis::{AsyncCommands, RedisError, RedisResult};
+use std::sync::Arc;
 
 // Configurations for cache.
 // The cache size is estimated to be 10M transactions.
 // For 10M transactions, the cache size is about 40GB.
-const CACHE_SIZE_ESTIMATION: u64 = 10_000_000_u64;
+pub(crate) const CACHE_SIZE_ESTIMATION: u64 = 10_000_000_u64;
 
 // Hard limit for cache lower bound. Only used for active eviction.
 // Cache worker actively evicts the cache entries if the cache entry version is
 // lower than the latest version - CACHE_SIZE_EVICTION_LOWER_BOUND.
 // The gap between CACHE_SIZE_ESTIMATION and this is to give buffer since
 // reading latest version and actual data not atomic(two operations).
-const CACHE_SIZE_EVICTION_LOWER_BOUND: u64 = 12_000_000_u64;
+pub(crate) const CACHE_SIZE_EVICTION_LOWER_BOUND: u64 = 12_000_000_u64;
 
 // Keys for cache.
 const CACHE_KEY_LATEST_VERSION: &str = "latest_version";
@@ -96,6 +99,72 @@ pub(crate) enum CacheCoverageStatus {
     CacheEvicted,
 }
 
+/// Cache is the hot transaction cache between the cache worker and its readers, i.e., file store
+/// processor and data service. Backends, i.e., Redis and in-memory cache, share the same
+/// versioning and eviction semantics.
+#[async_trait::async_trait]
+pub trait Cache: Send {
+    /// Sets up the cache if needed. Returns true if the cache is initialized.
+    async fn cache_setup_if_needed(&mut self) -> bool;
+
+    /// Updates the chain id in cache if missing; otherwise, verifies the chain id.
+    async fn update_or_verify_chain_id(&mut self, chain_id: u64) -> anyhow::Result<()>;
+
+    /// Gets the chain id from cache.
+    async fn get_chain_id(&mut self) -> anyhow::Result<u64>;
+
+    /// Adds the transactions, i.e., (version, encoded proto data, timestamp in seconds), to cache.
+    async fn update_cache_transactions(
+        &mut self,
+        transactions: Vec<(u64, String, u64)>,
+    ) -> anyhow::Result<()>;
+
+    /// Updates the latest version in cache once all transactions before it are cached.
+    async fn update_cache_latest_version(
+        &mut self,
+        num_of_versions: u64,
+        version: u64,
+    ) -> anyhow::Result<()>;
+
+    /// Gets up to BLOB_STORAGE_SIZE transactions starting from the version.
+    async fn batch_get_encoded_proto_data(
+        &mut self,
+        start_version: u64,
+    ) -> anyhow::Result<CacheBatchGetStatus>;
+}
+
+/// CacheClient creates the connections to the cache. The in-memory cache is shared by all the
+/// connections created by clones of the same client.
+#[derive(Clone)]
+pub enum CacheClient {
+    Redis(Arc<redis::Client>),
+    InMemory(InMemoryCache),
+}
+
+impl CacheClient {
+    pub fn new(cache_config: &IndexerGrpcCacheConfig) -> Self {
+        match cache_config {
+            IndexerGrpcCacheConfig::RedisCache { redis_address } => CacheClient::Redis(Arc::new(
+                redis::Client::open(format!("redis://{}", redis_address))
+                    .expect("Create redis client failed."),
+            )),
+            IndexerGrpcCacheConfig::InMemoryCache {
+                in_memory_cache_size_in_bytes,
+            } => CacheClient::InMemory(InMemoryCache::new(*in_memory_cache_size_in_bytes)),
+        }
+    }
+
+    /// Gets a connection to the cache.
+    pub async fn get_cache(&self) -> anyhow::Result<Box<dyn Cache>> {
+        match self {
+            CacheClient::Redis(client) => Ok(Box::new(CacheOperator::new(
+                client.get_tokio_connection().await?,
+            ))),
+            CacheClient::InMemory(cache) => Ok(Box::new(cache.clone())),
+        }
+    }
+}
+
 /// Get the TTL in seconds for a given timestamp.
 pub fn get_ttl_in_seconds(timestamp_in_seconds: u64) -> u64 {
     let current_time = std::time::SystemTime::now()
@@ -292,6 +361,43 @@ impl<T: redis::aio::ConnectionLike + Send> CacheOperator<T> {
     }
 }
 
+#[async_trait::async_trait]
+impl<T: redis::aio::ConnectionLike + Send> Cache for CacheOperator<T> {
+    async fn cache_setup_if_needed(&mut self) -> bool {
+        CacheOperator::cache_setup_if_needed(self).await
+    }
+
+    async fn update_or_verify_chain_id(&mut self, chain_id: u64) -> anyhow::Result<()> {
+        CacheOperator::update_or_verify_chain_id(self, chain_id).await
+    }
+
+    async fn get_chain_id(&mut self) -> anyhow::Result<u64> {
+        CacheOperator::get_chain_id(self).await
+    }
+
+    async fn update_cache_transactions(
+        &mut self,
+        transactions: Vec<(u64, String, u64)>,
+    ) -> anyhow::Result<()> {
+        CacheOperator::update_cache_transactions(self, transactions).await
+    }
+
+    async fn update_cache_latest_version(
+        &mut self,
+        num_of_versions: u64,
+        version: u64,
+    ) -> anyhow::Result<()> {
+        CacheOperator::update_cache_latest_version(self, num_of_versions, version).await
+    }
+
+    async fn batch_get_encoded_proto_data(
+        &mut self,
+        start_version: u64,
+    ) -> anyhow::Result<CacheBatchGetStatus> {
+        CacheOperator::batch_get_encoded_proto_data(self, start_version).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,16 +8,16 @@ use std::{fs::File, io::Read, path::PathBuf};
 /// Indexer GRPC configuration. This is to configure the Indexer GRPC server.
 /// This configuration is intende to share between Indexer GRPC(cache, file store, etc.).
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
-#[serde(deny_unknown_fields)]
+#[serde(try_from = "RawIndexerGrpcConfig")]
 pub struct IndexerGrpcConfig {
     /// GRPC address of Indexer, e.g. "127.0.0.1:50051".
     pub fullnode_grpc_address: Option<String>,
     // GRPC address listening to, e.g., "0.0.0.0:50051"
     pub data_service_grpc_listen_address: Option<String>,
-    /// Redis address, e.g. "127.0.0.1:6379".
-    pub redis_address: String,
-    /// File store bucket name, e.g., "indexer-grpc-file-store".
-    pub file_store_bucket_name: String,
+    /// Cache configuration, i.e., Redis or in-memory cache.
+    pub cache_config: IndexerGrpcCacheConfig,
+    /// File store configuration, i.e., GCS bucket or local directory.
+    pub file_store_config: IndexerGrpcFileStoreConfig,
     /// Health check port.
     pub health_check_port: u16,
     /// Whitelisted auth tokens, e.g., "token1,token2". Only used by Data Service.
@@ -25,6 +25,116 @@ pub struct IndexerGrpcConfig {
     pub whitelisted_auth_tokens: Option<Vec<String>>,
 }
 
+/// Indexer GRPC configuration as written in the config file, which also accepts deprecated
+/// fields.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawIndexerGrpcConfig {
+    fullnode_grpc_address: Option<String>,
+    data_service_grpc_listen_address: Option<String>,
+    cache_config: Option<IndexerGrpcCacheConfig>,
+    /// Deprecated: use `cache_config` with `cache_type: RedisCache` instead.
+    redis_address: Option<String>,
+    file_store_config: Option<IndexerGrpcFileStoreConfig>,
+    /// Deprecated: use `file_store_config` with `file_store_type: GcsFileStore` instead.
+    file_store_bucket_name: Option<String>,
+    health_check_port: u16,
+    whitelisted_auth_tokens: Option<Vec<String>>,
+}
+
+impl TryFrom<RawIndexerGrpcConfig> for IndexerGrpcConfig {
+    type Error = String;
+
+    fn try_from(raw: RawIndexerGrpcConfig) -> Result<Self, Self::Error> {
+        let cache_config = match (raw.cache_config, raw.redis_address) {
+            (Some(cache_config), None) => cache_config,
+            (None, Some(redis_address)) => IndexerGrpcCacheConfig::RedisCache { redis_address },
+            (Some(_), Some(_)) => {
+                return Err("`cache_config` and `redis_address` cannot both be set".into())
+            },
+            (None, None) => return Err("missing field `cache_config`".into()),
+        };
+        let file_store_config = match (raw.file_store_config, raw.file_store_bucket_name) {
+            (Some(file_store_config), None) => file_store_config,
+            (None, Some(gcs_file_store_bucket_name)) => IndexerGrpcFileStoreConfig::GcsFileStore {
+                gcs_file_store_bucket_name,
+            },
+            (Some(_), Some(_)) => {
+                return Err(
+                    "`file_store_config` and `file_store_bucket_name` cannot both be set".into(),
+                )
+            },
+            (None, None) => return Err("missing field `file_store_config`".into()),
+        };
+        Ok(Self {
+            fullnode_grpc_address: raw.fullnode_grpc_address,
+            data_service_grpc_listen_address: raw.data_service_grpc_listen_address,
+            cache_config,
+            file_store_config,
+            health_check_port: raw.health_check_port,
+            whitelisted_auth_tokens: raw.whitelisted_auth_tokens,
+        })
+    }
+}
+
+/// Cache configuration shared by the cache worker, file store processor and data service.
+/// The `cache_type` field selects the backend, e.g.,
+/// ```yaml
+/// cache_config:
+///   cache_type: RedisCache
+///   redis_address: 127.0.0.1:6379
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "cache_type")]
+pub enum IndexerGrpcCacheConfig {
+    /// Redis address, e.g. "127.0.0.1:6379".
+    RedisCache { redis_address: String },
+    /// In-memory cache bounded by the total size of the cached transactions, e.g., 1GB.
+    /// The cache is in-process, so it's only supported by the data service, which then runs
+    /// the cache worker and file store processor in the same process.
+    InMemoryCache {
+        in_memory_cache_size_in_bytes: usize,
+    },
+}
+
+impl IndexerGrpcCacheConfig {
+    pub fn is_in_memory(&self) -> bool {
+        matches!(self, IndexerGrpcCacheConfig::InMemoryCache { .. })
+    }
+}
+
+impl Default for IndexerGrpcCacheConfig {
+    fn default() -> Self {
+        Self::RedisCache {
+            redis_address: String::default(),
+        }
+    }
+}
+
+/// File store configuration shared by the cache worker, file store processor and data service.
+/// The `file_store_type` field selects the backend, e.g.,
+/// ```yaml
+/// file_store_config:
+///   file_store_type: LocalFileStore
+///   local_file_store_path: /tmp/indexer-grpc-file-store
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "file_store_type")]
+pub enum IndexerGrpcFileStoreConfig {
+    /// Google Cloud Storage bucket, e.g., "indexer-grpc-file-store".
+    GcsFileStore { gcs_file_store_bucket_name: String },
+    /// Local directory, e.g., "/tmp/indexer-grpc-file-store". The directory must exist.
+    LocalFileStore { local_file_store_path: PathBuf },
+}
+
+impl Default for IndexerGrpcFileStoreConfig {
+    fn default() -> Self {
+        Self::GcsFileStore {
+            gcs_file_store_bucket_name: String::default(),
+        }
+    }
+}
+
 /// Indexer GRPC Processor configuration. This is to configure the processors,
 /// e.g., `default_processor` to process data from Indexer GRPC.
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -85,3 +195,182 @@ pub fn load<T: for<'de> serde::Deserialize<'de>>(path: PathBuf) -> Result<T, any
         )
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_file_store_config_parsing() {
+        let config: IndexerGrpcConfig = serde_yaml::from_str(
+            r#"
+            cache_config:
+              cache_type: RedisCache
+              redis_address: 127.0.0.1:6379
+            health_check_port: 8081
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            "#,
+        )
+        .unwrap();
+        match config.file_store_config {
+            IndexerGrpcFileStoreConfig::LocalFileStore {
+                local_file_store_path,
+            } => assert_eq!(
+                local_file_store_path,
+                PathBuf::from("/tmp/indexer-grpc-file-store")
+            ),
+            _ => panic!("Expected local file store config."),
+        }
+
+        let config: IndexerGrpcConfig = serde_yaml::from_str(
+            r#"
+            cache_config:
+              cache_type: RedisCache
+              redis_address: 127.0.0.1:6379
+            health_check_port: 8081
+            file_store_config:
+              file_store_type: GcsFileStore
+              gcs_file_store_bucket_name: indexer-grpc-file-store-testnet
+            "#,
+        )
+        .unwrap();
+        match config.file_store_config {
+            IndexerGrpcFileStoreConfig::GcsFileStore {
+                gcs_file_store_bucket_name,
+            } => assert_eq!(
+                gcs_file_store_bucket_name,
+                "indexer-grpc-file-store-testnet"
+            ),
+            _ => panic!("Expected GCS file store config."),
+        }
+    }
+
+    #[test]
+    fn verify_deprecated_file_store_bucket_name_parsing() {
+        let config: IndexerGrpcConfig = serde_yaml::from_str(
+            r#"
+            cache_config:
+              cache_type: RedisCache
+              redis_address: 127.0.0.1:6379
+            health_check_port: 8081
+            file_store_bucket_name: indexer-grpc-file-store-testnet
+            "#,
+        )
+        .unwrap();
+        match config.file_store_config {
+            IndexerGrpcFileStoreConfig::GcsFileStore {
+                gcs_file_store_bucket_name,
+            } => assert_eq!(
+                gcs_file_store_bucket_name,
+                "indexer-grpc-file-store-testnet"
+            ),
+            _ => panic!("Expected GCS file store config."),
+        }
+
+        // The deprecated field cannot be combined with the file store config.
+        assert!(serde_yaml::from_str::<IndexerGrpcConfig>(
+            r#"
+            cache_config:
+              cache_type: RedisCache
+              redis_address: 127.0.0.1:6379
+            health_check_port: 8081
+            file_store_bucket_name: indexer-grpc-file-store-testnet
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            "#,
+        )
+        .is_err());
+        assert!(serde_yaml::from_str::<IndexerGrpcConfig>(
+            r#"
+            cache_config:
+              cache_type: RedisCache
+              redis_address: 127.0.0.1:6379
+            health_check_port: 8081
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_deprecated_redis_address_parsing() {
+        let config: IndexerGrpcConfig = serde_yaml::from_str(
+            r#"
+            redis_address: 127.0.0.1:6379
+            health_check_port: 8081
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            "#,
+        )
+        .unwrap();
+        match config.cache_config {
+            IndexerGrpcCacheConfig::RedisCache { redis_address } => {
+                assert_eq!(redis_address, "127.0.0.1:6379")
+            },
+            _ => panic!("Expected Redis cache config."),
+        }
+
+        // The deprecated field cannot be combined with the cache config.
+        assert!(serde_yaml::from_str::<IndexerGrpcConfig>(
+            r#"
+            redis_address: 127.0.0.1:6379
+            cache_config:
+              cache_type: InMemoryCache
+              in_memory_cache_size_in_bytes: 1000000000
+            health_check_port: 8081
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            "#,
+        )
+        .is_err());
+        assert!(serde_yaml::from_str::<IndexerGrpcConfig>(
+            r#"
+            health_check_port: 8081
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_cache_config_parsing() {
+        let config: IndexerGrpcConfig = serde_yaml::from_str(
+            r#"
+            cache_config:
+              cache_type: InMemoryCache
+              in_memory_cache_size_in_bytes: 1000000000
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            health_check_port: 8081
+            "#,
+        )
+        .unwrap();
+        assert!(config.cache_config.is_in_memory());
+
+        let config: IndexerGrpcConfig = serde_yaml::from_str(
+            r#"
+            cache_config:
+              cache_type: RedisCache
+              redis_address: 127.0.0.1:6379
+            file_store_config:
+              file_store_type: LocalFileStore
+              local_file_store_path: /tmp/indexer-grpc-file-store
+            health_check_port: 8081
+            "#,
+        )
+        .unwrap();
+        match config.cache_config {
+            IndexerGrpcCacheConfig::RedisCache { redis_address } => {
+                assert_eq!(redis_address, "127.0.0.1:6379")
+            },
+            _ => panic!("Expected Redis cache config."),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::FileStore;
+use cloud_storage::{Bucket, Object};
+
+const JSON_FILE_TYPE: &str = "application/json";
+
+/// File store backed by a Google Cloud Storage bucket. The credentials are read from the
+/// service account json file pointed by the `SERVICE_ACCOUNT` env var.
+pub struct GcsFileStore {
+    bucket_name: String,
+}
+
+impl GcsFileStore {
+    pub fn new(bucket_name: String) -> Self {
+        Self { bucket_name }
+    }
+}
+
+#[async_trait::async_trait]
+impl FileStore for GcsFileStore {
+    async fn verify_existence(&self) {
+        aptos_logger::info!(
+            bucket_name = self.bucket_name,
+            "Before file store operator starts, verify the bucket exists."
+        );
+        // Verifies the bucket exists.
+        Bucket::read(&self.bucket_name)
+            .await
+            .expect("Failed to read bucket.");
+    }
+
+    async fn get_blob(&self, blob_name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match Object::download(&self.bucket_name, blob_name).await {
+            Ok(blob) => Ok(Some(blob)),
+            Err(cloud_storage::Error::Other(err)) if err.contains("No such object: ") => Ok(None),
+            Err(err) => Err(anyhow::Error::from(err)),
+        }
+    }
+
+    async fn put_blob(&self, blob_name: &str, data: Vec<u8>) -> anyhow::Result<()> {
+        Object::create(&self.bucket_name, data, blob_name, JSON_FILE_TYPE).await?;
+        Ok(())
+    }
+}
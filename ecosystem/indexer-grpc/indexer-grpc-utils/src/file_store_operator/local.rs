@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::FileStore;
+use std::{io::ErrorKind, path::PathBuf};
+
+/// File store backed by a local directory, e.g., for local development or self-hosted
+/// environments. Blob names are paths relative to the directory.
+pub struct LocalFileStore {
+    path: PathBuf,
+}
+
+impl LocalFileStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait::async_trait]
+impl FileStore for LocalFileStore {
+    async fn verify_existence(&self) {
+        aptos_logger::info!(
+            path = self.path.display().to_string(),
+            "Before file store operator starts, verify the directory exists."
+        );
+        assert!(
+            tokio::fs::metadata(&self.path)
+                .await
+                .map_or(false, |metadata| metadata.is_dir()),
+            "Local file store directory {:?} does not exist.",
+            self.path
+        );
+    }
+
+    async fn get_blob(&self, blob_name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path.join(blob_name)).await {
+            Ok(blob) => Ok(Some(blob)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(anyhow::Error::from(err)),
+        }
+    }
+
+    async fn put_blob(&self, blob_name: &str, data: Vec<u8>) -> anyhow::Result<()> {
+        let blob_path = self.path.join(blob_name);
+        if let Some(parent) = blob_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Writes to a temporary file first so readers never see a partially written blob.
+        let temp_path = blob_path.with_extension("tmp");
+        tokio::fs::write(&temp_path, data).await?;
+        tokio::fs::rename(&temp_path, &blob_path).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn verify_local_file_store_blobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_store = LocalFileStore::new(dir.path().to_path_buf());
+        file_store.verify_existence().await;
+
+        assert!(file_store.get_blob("files/0.json").await.unwrap().is_none());
+        file_store
+            .put_blob("files/0.json", b"transactions".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            file_store.get_blob("files/0.json").await.unwrap(),
+            Some(b"transactions".to_vec())
+        );
+        file_store
+            .put_blob("files/0.json", b"overwritten".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            file_store.get_blob("files/0.json").await.unwrap(),
+            Some(b"overwritten".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn verify_local_file_store_missing_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        LocalFileStore::new(dir.path().join("missing"))
+            .verify_existence()
+            .await;
+    }
+}
@@ -1,14 +1,36 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{constants::BLOB_STORAGE_SIZE, EncodedTransactionWithVersion};
-use cloud_storage::{Bucket, Object};
+use crate::{
+    config::IndexerGrpcFileStoreConfig, constants::BLOB_STORAGE_SIZE, EncodedTransactionWithVersion,
+};
 use itertools::{any, Itertools};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+mod gcs;
+mod local;
+
+pub use gcs::GcsFileStore;
+pub use local::LocalFileStore;
 
 const FILE_FOLDER_NAME: &str = "files";
 const METADATA_FILE_NAME: &str = "metadata.json";
-const JSON_FILE_TYPE: &str = "application/json";
+
+/// FileStore is the storage backend of the file store, e.g., a GCS bucket or a local directory.
+/// All backends share the same layout: `metadata.json` and the transactions files named by
+/// `generate_blob_name`.
+#[async_trait::async_trait]
+pub trait FileStore: Send + Sync {
+    /// Verifies the file store exists, e.g., the bucket or the directory. Panics otherwise.
+    async fn verify_existence(&self);
+
+    /// Gets the blob with the given name. Returns None if the blob is not found.
+    async fn get_blob(&self, blob_name: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Creates or overwrites the blob with the given name.
+    async fn put_blob(&self, blob_name: &str, data: Vec<u8>) -> anyhow::Result<()>;
+}
 
 #[inline]
 pub fn generate_blob_name(starting_version: u64) -> String {
@@ -52,37 +74,38 @@ impl FileStoreMetadata {
 /// The only state it maintains is the latest metadata update timestamp.
 /// The file store operator is not thread safe and is intended to be used in a single thread.
 pub struct FileStoreOperator {
-    bucket_name: String,
+    file_store: Arc<dyn FileStore>,
     /// The timestamp of the latest metadata update; this is to avoid too frequent metadata update.
     latest_metadata_update_timestamp: std::time::Instant,
 }
 
 impl FileStoreOperator {
-    pub fn new(bucket_name: String) -> Self {
+    pub fn new(file_store_config: IndexerGrpcFileStoreConfig) -> Self {
+        let file_store: Arc<dyn FileStore> = match file_store_config {
+            IndexerGrpcFileStoreConfig::GcsFileStore {
+                gcs_file_store_bucket_name,
+            } => Arc::new(GcsFileStore::new(gcs_file_store_bucket_name)),
+            IndexerGrpcFileStoreConfig::LocalFileStore {
+                local_file_store_path,
+            } => Arc::new(LocalFileStore::new(local_file_store_path)),
+        };
         Self {
-            bucket_name,
+            file_store,
             latest_metadata_update_timestamp: std::time::Instant::now(),
         }
     }
 
     /// Bootstraps the file store operator. This is required before any other operations.
     pub async fn verify_storage_bucket_existence(&self) {
-        aptos_logger::info!(
-            bucket_name = self.bucket_name,
-            "Before file store operator starts, verify the bucket exists."
-        );
-        // Verifies the bucket exists.
-        Bucket::read(&self.bucket_name)
-            .await
-            .expect("Failed to read bucket.");
+        self.file_store.verify_existence().await;
     }
 
     /// Gets the transactions files from the file store. version has to be a multiple of BLOB_STORAGE_SIZE.
     pub async fn get_transactions(&self, version: u64) -> anyhow::Result<Vec<String>> {
         let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
         let current_file_name = generate_blob_name(batch_start_version);
-        match Object::download(&self.bucket_name, current_file_name.as_str()).await {
-            Ok(file) => {
+        match self.file_store.get_blob(current_file_name.as_str()).await {
+            Ok(Some(file)) => {
                 let file: TransactionsFile =
                     serde_json::from_slice(&file).expect("Expected file to be valid JSON.");
                 Ok(file
@@ -91,15 +114,8 @@ impl FileStoreOperator {
                     .skip((version % BLOB_STORAGE_SIZE as u64) as usize)
                     .collect())
             },
-            Err(cloud_storage::Error::Other(err)) => {
-                if err.contains("No such object: ") {
-                    anyhow::bail!("[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}", err)
-                } else {
-                    anyhow::bail!(
-                        "[Indexer File] Error happens when transaction file. {}",
-                        err
-                    );
-                }
+            Ok(None) => {
+                anyhow::bail!("[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}", current_file_name)
             },
             Err(err) => {
                 anyhow::bail!(
@@ -112,23 +128,14 @@ impl FileStoreOperator {
 
     /// Gets the metadata from the file store. Operator will panic if error happens when accessing the metadata file(except not found).
     pub async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
-        match Object::download(&self.bucket_name, METADATA_FILE_NAME).await {
-            Ok(metadata) => {
+        match self.file_store.get_blob(METADATA_FILE_NAME).await {
+            Ok(Some(metadata)) => {
                 let metadata: FileStoreMetadata =
                     serde_json::from_slice(&metadata).expect("Expected metadata to be valid JSON.");
                 Some(metadata)
             },
-            Err(cloud_storage::Error::Other(err)) => {
-                if err.contains("No such object: ") {
-                    // Metadata is not found.
-                    None
-                } else {
-                    panic!(
-                        "[Indexer File] Error happens when accessing metadata file. {}",
-                        err
-                    );
-                }
-            },
+            // Metadata is not found.
+            Ok(None) => None,
             Err(e) => {
                 panic!(
                     "[Indexer File] Error happens when accessing metadata file. {}",
@@ -143,30 +150,23 @@ impl FileStoreOperator {
         &mut self,
         expected_chain_id: u64,
     ) -> anyhow::Result<FileStoreMetadata> {
-        match Object::download(&self.bucket_name, METADATA_FILE_NAME).await {
-            Ok(metadata) => {
+        match self.file_store.get_blob(METADATA_FILE_NAME).await? {
+            Some(metadata) => {
                 let metadata: FileStoreMetadata =
                     serde_json::from_slice(&metadata).expect("Expected metadata to be valid JSON.");
                 anyhow::ensure!(metadata.chain_id == expected_chain_id, "Chain ID mismatch.");
                 Ok(metadata)
             },
-            Err(cloud_storage::Error::Other(err)) => {
-                let is_file_missing = err.contains("No such object: ");
-                if is_file_missing {
-                    // If the metadata is not found, it means the file store is empty.
-                    self.update_file_store_metadata(expected_chain_id, 0)
-                        .await
-                        .expect("[Indexer File] Update metadata failed.");
-                    Ok(FileStoreMetadata::new(expected_chain_id, 0))
-                } else {
-                    // If not in write mode, the metadata must exist.
-                    Err(anyhow::Error::msg(format!(
-                        "Metadata not found or file store operator is not in write mode. {}",
-                        err
-                    )))
-                }
+            None => {
+                // If the metadata is not found, it means the file store is empty.
+                // The metadata is written regardless of the latest update, since other
+                // components, e.g., cache worker, wait for it.
+                let metadata = FileStoreMetadata::new(expected_chain_id, 0);
+                self.write_file_store_metadata(metadata)
+                    .await
+                    .expect("[Indexer File] Update metadata failed.");
+                Ok(metadata)
             },
-            Err(err) => Err(anyhow::Error::from(err)),
         }
     }
 
@@ -179,23 +179,20 @@ impl FileStoreOperator {
         if (std::time::Instant::now() - self.latest_metadata_update_timestamp).as_secs() < 5 {
             return Ok(());
         }
+        self.write_file_store_metadata(FileStoreMetadata::new(chain_id, version))
+            .await
+    }
 
-        let metadata = FileStoreMetadata::new(chain_id, version);
+    async fn write_file_store_metadata(
+        &mut self,
+        metadata: FileStoreMetadata,
+    ) -> anyhow::Result<()> {
         // If the metadata is not updated, the indexer will be restarted.
-        match Object::create(
-            self.bucket_name.as_str(),
-            serde_json::to_vec(&metadata).unwrap(),
-            METADATA_FILE_NAME,
-            JSON_FILE_TYPE,
-        )
-        .await
-        {
-            Ok(_) => {
-                self.latest_metadata_update_timestamp = std::time::Instant::now();
-                Ok(())
-            },
-            Err(err) => Err(anyhow::Error::from(err)),
-        }
+        self.file_store
+            .put_blob(METADATA_FILE_NAME, serde_json::to_vec(&metadata).unwrap())
+            .await?;
+        self.latest_metadata_update_timestamp = std::time::Instant::now();
+        Ok(())
     }
 
     /// Uploads the transactions to the file store. The transactions are grouped into batches of BLOB_STORAGE_SIZE.
@@ -219,21 +216,16 @@ impl FileStoreOperator {
 
         // Split the transactions into batches of BLOB_STORAGE_SIZE.
         for i in transactions.chunks(BLOB_STORAGE_SIZE) {
-            let bucket_name = self.bucket_name.clone();
+            let file_store = self.file_store.clone();
             let current_batch = i.iter().cloned().collect_vec();
             let transactions_file = build_transactions_file(current_batch).unwrap();
             let task = tokio::spawn(async move {
-                match Object::create(
-                    bucket_name.clone().as_str(),
-                    serde_json::to_vec(&transactions_file).unwrap(),
-                    generate_blob_name(transactions_file.starting_version).as_str(),
-                    JSON_FILE_TYPE,
-                )
-                .await
-                {
-                    Ok(_) => Ok(()),
-                    Err(err) => Err(anyhow::Error::from(err)),
-                }
+                file_store
+                    .put_blob(
+                        generate_blob_name(transactions_file.starting_version).as_str(),
+                        serde_json::to_vec(&transactions_file).unwrap(),
+                    )
+                    .await
             });
             tasks.push(task);
         }
@@ -325,4 +317,55 @@ mod tests {
 
         assert!(build_transactions_file(transactions).is_err());
     }
+
+    #[tokio::test]
+    async fn verify_local_file_store_operator() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file_store_operator =
+            FileStoreOperator::new(IndexerGrpcFileStoreConfig::LocalFileStore {
+                local_file_store_path: dir.path().to_path_buf(),
+            });
+        file_store_operator.verify_storage_bucket_existence().await;
+        assert!(file_store_operator
+            .get_file_store_metadata()
+            .await
+            .is_none());
+
+        // Metadata is created right away for an empty file store.
+        let metadata = file_store_operator
+            .create_default_file_store_metadata_if_absent(42)
+            .await
+            .unwrap();
+        assert_eq!(metadata.version, 0);
+        assert_eq!(
+            file_store_operator
+                .get_file_store_metadata()
+                .await
+                .unwrap()
+                .chain_id,
+            42
+        );
+        assert!(file_store_operator
+            .create_default_file_store_metadata_if_absent(43)
+            .await
+            .is_err());
+
+        let transactions = (0..2 * BLOB_STORAGE_SIZE as u64)
+            .map(|version| (version.to_string(), version))
+            .collect();
+        file_store_operator
+            .upload_transactions(42, transactions)
+            .await
+            .unwrap();
+        assert!(dir.path().join(generate_blob_name(1000)).exists());
+        let transactions = file_store_operator.get_transactions(1500).await.unwrap();
+        assert_eq!(transactions.len(), 500);
+        assert_eq!(transactions[0], "1500");
+        assert!(file_store_operator
+            .get_transactions(2000)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Transactions file not found"));
+    }
 }
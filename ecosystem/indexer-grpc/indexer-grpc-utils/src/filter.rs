@@ -0,0 +1,395 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::EncodedTransactionWithVersion;
+use aptos_protos::{
+    datastream::v1::TransactionFilter as TransactionFilterPB,
+    transaction::testing1::v1::{
+        multisig_transaction_payload, transaction::TxnData, transaction_payload,
+        write_set_change::Change, EntryFunctionId, Event, Transaction,
+    },
+};
+use prost::Message;
+
+/// Filter of the transactions streamed to a client, built from the `TransactionFilter` of a
+/// `RawDatastreamRequest`. Addresses are normalized, so e.g. "0x1" and "0x0...01" are equal.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionFilter {
+    sender_addresses: Vec<String>,
+    entry_functions: Vec<EntryFunctionFilter>,
+    event_types: Vec<String>,
+    write_resource_types: Vec<String>,
+    success_only: bool,
+}
+
+#[derive(Clone, Debug)]
+struct EntryFunctionFilter {
+    address: String,
+    module_name: Option<String>,
+    function_name: Option<String>,
+}
+
+impl From<TransactionFilterPB> for TransactionFilter {
+    fn from(filter: TransactionFilterPB) -> Self {
+        Self {
+            sender_addresses: filter
+                .sender_addresses
+                .iter()
+                .map(|address| normalize_address(address))
+                .collect(),
+            entry_functions: filter
+                .entry_functions
+                .into_iter()
+                .map(|entry_function| EntryFunctionFilter {
+                    address: normalize_address(&entry_function.address),
+                    module_name: entry_function.module_name,
+                    function_name: entry_function.function_name,
+                })
+                .collect(),
+            event_types: filter
+                .event_types
+                .iter()
+                .map(|type_str| normalize_type(type_str))
+                .collect(),
+            write_resource_types: filter
+                .write_resource_types
+                .iter()
+                .map(|type_str| normalize_type(type_str))
+                .collect(),
+            success_only: filter.success_only,
+        }
+    }
+}
+
+impl TransactionFilter {
+    /// Returns true if the transaction matches all the criteria of the filter.
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        if self.success_only && !transaction.info.as_ref().map_or(false, |info| info.success) {
+            return false;
+        }
+        if !self.sender_addresses.is_empty() && !self.matches_sender(transaction) {
+            return false;
+        }
+        if !self.entry_functions.is_empty() && !self.matches_entry_function(transaction) {
+            return false;
+        }
+        if !self.event_types.is_empty()
+            && !events(transaction)
+                .iter()
+                .any(|event| matches_any_type(&self.event_types, &event.type_str))
+        {
+            return false;
+        }
+        if !self.write_resource_types.is_empty() && !self.matches_write_resource(transaction) {
+            return false;
+        }
+        true
+    }
+
+    /// Decodes the base64 encoded transactions and retains the ones matching the filter.
+    pub fn filter_encoded(
+        &self,
+        transactions: Vec<EncodedTransactionWithVersion>,
+    ) -> anyhow::Result<Vec<EncodedTransactionWithVersion>> {
+        let mut filtered = vec![];
+        for (encoded, version) in transactions {
+            let transaction = Transaction::decode(base64::decode(&encoded)?.as_slice())?;
+            if self.matches(&transaction) {
+                filtered.push((encoded, version));
+            }
+        }
+        Ok(filtered)
+    }
+
+    fn matches_sender(&self, transaction: &Transaction) -> bool {
+        match &transaction.txn_data {
+            Some(TxnData::User(user_transaction)) => {
+                user_transaction.request.as_ref().map_or(false, |request| {
+                    let sender = normalize_address(&request.sender);
+                    self.sender_addresses
+                        .iter()
+                        .any(|address| *address == sender)
+                })
+            },
+            _ => false,
+        }
+    }
+
+    fn matches_entry_function(&self, transaction: &Transaction) -> bool {
+        let payload = match &transaction.txn_data {
+            Some(TxnData::User(user_transaction)) => user_transaction
+                .request
+                .as_ref()
+                .and_then(|request| request.payload.as_ref())
+                .and_then(|payload| payload.payload.as_ref()),
+            _ => None,
+        };
+        let function = match payload {
+            Some(transaction_payload::Payload::EntryFunctionPayload(payload)) => {
+                payload.function.as_ref()
+            },
+            // The entry function executed by a multisig account.
+            Some(transaction_payload::Payload::MultisigPayload(payload)) => payload
+                .transaction_payload
+                .as_ref()
+                .and_then(|payload| payload.payload.as_ref())
+                .and_then(|payload| match payload {
+                    multisig_transaction_payload::Payload::EntryFunctionPayload(payload) => {
+                        payload.function.as_ref()
+                    },
+                }),
+            _ => None,
+        };
+        function.map_or(false, |function| {
+            self.entry_functions
+                .iter()
+                .any(|filter| filter.matches(function))
+        })
+    }
+
+    fn matches_write_resource(&self, transaction: &Transaction) -> bool {
+        let changes = match &transaction.info {
+            Some(info) => &info.changes,
+            None => return false,
+        };
+        changes.iter().any(|change| match &change.change {
+            Some(Change::WriteResource(resource)) => {
+                matches_any_type(&self.write_resource_types, &resource.type_str)
+            },
+            Some(Change::DeleteResource(resource)) => {
+                matches_any_type(&self.write_resource_types, &resource.type_str)
+            },
+            _ => false,
+        })
+    }
+}
+
+impl EntryFunctionFilter {
+    fn matches(&self, function: &EntryFunctionId) -> bool {
+        let module = match &function.module {
+            Some(module) => module,
+            None => return false,
+        };
+        normalize_address(&module.address) == self.address
+            && self
+                .module_name
+                .as_ref()
+                .map_or(true, |name| *name == module.name)
+            && self
+                .function_name
+                .as_ref()
+                .map_or(true, |name| *name == function.name)
+    }
+}
+
+fn events(transaction: &Transaction) -> &[Event] {
+    match &transaction.txn_data {
+        Some(TxnData::User(user_transaction)) => &user_transaction.events,
+        Some(TxnData::BlockMetadata(block_metadata)) => &block_metadata.events,
+        Some(TxnData::Genesis(genesis)) => &genesis.events,
+        Some(TxnData::StateCheckpoint(_)) | None => &[],
+    }
+}
+
+/// Types without generic parameters in the filter match all instantiations of the type.
+fn matches_any_type(filter_types: &[String], type_str: &str) -> bool {
+    let type_str = normalize_type(type_str);
+    let base_type = type_str.split('<').next().unwrap_or_default();
+    filter_types.iter().any(|filter_type| {
+        *filter_type == type_str || (!filter_type.contains('<') && filter_type == base_type)
+    })
+}
+
+/// Normalizes an address to lowercase without the "0x" prefix and leading zeros.
+fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    let address = address.strip_prefix("0x").unwrap_or(address);
+    let address = address.trim_start_matches('0').to_lowercase();
+    if address.is_empty() {
+        "0".to_string()
+    } else {
+        address
+    }
+}
+
+/// Normalizes the address of a type, e.g. "0x01::coin::CoinStore" to "1::coin::CoinStore".
+/// Addresses of the generic type parameters are not normalized.
+fn normalize_type(type_str: &str) -> String {
+    let type_str = type_str.trim();
+    match type_str.split_once("::") {
+        Some((address, rest)) => format!("{}::{}", normalize_address(address), rest),
+        None => type_str.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::{
+        datastream::v1::EntryFunctionFilter as EntryFunctionFilterPB,
+        transaction::testing1::v1::{
+            EntryFunctionPayload, MoveModuleId, TransactionInfo, TransactionPayload,
+            UserTransaction, UserTransactionRequest, WriteResource, WriteSetChange,
+        },
+    };
+
+    fn user_transaction(
+        sender: &str,
+        function: (&str, &str, &str),
+        event_type: &str,
+        resource_type: &str,
+        success: bool,
+    ) -> Transaction {
+        let (address, module, name) = function;
+        Transaction {
+            info: Some(TransactionInfo {
+                success,
+                changes: vec![WriteSetChange {
+                    change: Some(Change::WriteResource(WriteResource {
+                        type_str: resource_type.to_string(),
+                        ..WriteResource::default()
+                    })),
+                    ..WriteSetChange::default()
+                }],
+                ..TransactionInfo::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(transaction_payload::Payload::EntryFunctionPayload(
+                            EntryFunctionPayload {
+                                function: Some(EntryFunctionId {
+                                    module: Some(MoveModuleId {
+                                        address: address.to_string(),
+                                        name: module.to_string(),
+                                    }),
+                                    name: name.to_string(),
+                                }),
+                                ..EntryFunctionPayload::default()
+                            },
+                        )),
+                        ..TransactionPayload::default()
+                    }),
+                    ..UserTransactionRequest::default()
+                }),
+                events: vec![Event {
+                    type_str: event_type.to_string(),
+                    ..Event::default()
+                }],
+            })),
+            ..Transaction::default()
+        }
+    }
+
+    fn transfer(sender: &str, success: bool) -> Transaction {
+        user_transaction(
+            sender,
+            ("0x1", "coin", "transfer"),
+            "0x1::coin::DepositEvent",
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            success,
+        )
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = TransactionFilter::from(TransactionFilterPB::default());
+        assert!(filter.matches(&transfer("0xa", true)));
+        assert!(filter.matches(&transfer("0xa", false)));
+        assert!(filter.matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_sender_and_success() {
+        let filter = TransactionFilter::from(TransactionFilterPB {
+            sender_addresses: vec!["0x000a".to_string(), "0xB".to_string()],
+            success_only: true,
+            ..TransactionFilterPB::default()
+        });
+        assert!(filter.matches(&transfer("0xa", true)));
+        assert!(filter.matches(&transfer("0x0b", true)));
+        assert!(!filter.matches(&transfer("0xa", false)));
+        assert!(!filter.matches(&transfer("0xc", true)));
+        assert!(!filter.matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_entry_functions() {
+        let entry_function = |address: &str, module: Option<&str>, function: Option<&str>| {
+            TransactionFilter::from(TransactionFilterPB {
+                entry_functions: vec![EntryFunctionFilterPB {
+                    address: address.to_string(),
+                    module_name: module.map(|m| m.to_string()),
+                    function_name: function.map(|f| f.to_string()),
+                }],
+                ..TransactionFilterPB::default()
+            })
+        };
+        let txn = transfer("0xa", true);
+        assert!(entry_function("0x1", None, None).matches(&txn));
+        assert!(entry_function("0x01", Some("coin"), None).matches(&txn));
+        assert!(entry_function("0x1", Some("coin"), Some("transfer")).matches(&txn));
+        assert!(!entry_function("0x1", Some("coin"), Some("register")).matches(&txn));
+        assert!(!entry_function("0x1", Some("account"), None).matches(&txn));
+        assert!(!entry_function("0x2", None, None).matches(&txn));
+    }
+
+    #[test]
+    fn test_event_and_resource_types() {
+        let txn = transfer("0xa", true);
+        let types = |events: Vec<&str>, resources: Vec<&str>| {
+            TransactionFilter::from(TransactionFilterPB {
+                event_types: events.into_iter().map(|t| t.to_string()).collect(),
+                write_resource_types: resources.into_iter().map(|t| t.to_string()).collect(),
+                ..TransactionFilterPB::default()
+            })
+        };
+        assert!(types(vec!["0x1::coin::DepositEvent"], vec![]).matches(&txn));
+        assert!(types(
+            vec!["0x01::coin::WithdrawEvent", "0x1::coin::DepositEvent"],
+            vec![]
+        )
+        .matches(&txn));
+        assert!(!types(vec!["0x1::coin::WithdrawEvent"], vec![]).matches(&txn));
+        assert!(types(vec![], vec!["0x1::coin::CoinStore"]).matches(&txn));
+        assert!(types(
+            vec![],
+            vec!["0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"]
+        )
+        .matches(&txn));
+        assert!(!types(vec![], vec!["0x1::coin::CoinStore<0x2::foo::Foo>"]).matches(&txn));
+        assert!(!types(
+            vec!["0x1::coin::DepositEvent"],
+            vec!["0x1::account::Account"]
+        )
+        .matches(&txn));
+    }
+
+    #[test]
+    fn test_filter_encoded() {
+        let encode = |txn: Transaction, version: u64| {
+            let mut buf = vec![];
+            txn.encode(&mut buf).unwrap();
+            (base64::encode(buf), version)
+        };
+        let filter = TransactionFilter::from(TransactionFilterPB {
+            sender_addresses: vec!["0xa".to_string()],
+            ..TransactionFilterPB::default()
+        });
+        let filtered = filter
+            .filter_encoded(vec![
+                encode(transfer("0xa", true), 1),
+                encode(transfer("0xb", true), 2),
+                encode(transfer("0xa", false), 3),
+            ])
+            .unwrap();
+        assert_eq!(
+            filtered.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(filter
+            .filter_encoded(vec![("not base64!".to_string(), 4)])
+            .is_err());
+    }
+}
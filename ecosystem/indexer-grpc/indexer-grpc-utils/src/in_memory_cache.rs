@@ -0,0 +1,411 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cache_operator::{
+        Cache, CacheBatchGetStatus, CacheCoverageStatus, CACHE_SIZE_ESTIMATION,
+        CACHE_SIZE_EVICTION_LOWER_BOUND,
+    },
+    constants::BLOB_STORAGE_SIZE,
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, RwLock},
+};
+
+/// In-memory cache, an alternative to Redis when the cache worker and its readers run in the
+/// same process. Clones share the same cache.
+///
+/// Transactions are kept in a ring buffer of consecutive versions, which is bounded by the total
+/// size of the encoded transactions. On top of the Redis eviction semantics, i.e., versions older
+/// than the latest version - CACHE_SIZE_ESTIMATION are evicted, the oldest versions are evicted
+/// once the cache is full; both are reported as `EvictedFromCache`.
+#[derive(Clone)]
+pub struct InMemoryCache {
+    state: Arc<RwLock<InMemoryCacheState>>,
+}
+
+struct InMemoryCacheState {
+    chain_id: Option<u64>,
+    // Set up by `cache_setup_if_needed`, like the `latest_version` key in Redis.
+    latest_version: Option<u64>,
+    // Version of the first slot in the ring buffer.
+    start_version: u64,
+    // Chunks can be received out of order, so slots may be empty until the whole batch is received.
+    transactions: VecDeque<Option<String>>,
+    // All versions below are evicted.
+    evicted_below_version: u64,
+    size_in_bytes: usize,
+    max_size_in_bytes: usize,
+}
+
+impl InMemoryCache {
+    pub fn new(max_size_in_bytes: usize) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(InMemoryCacheState {
+                chain_id: None,
+                latest_version: None,
+                start_version: 0,
+                transactions: VecDeque::new(),
+                evicted_below_version: 0,
+                size_in_bytes: 0,
+                max_size_in_bytes,
+            })),
+        }
+    }
+
+    /// Resumes a cache that is not set up yet from the version, i.e., the file store version
+    /// after a restart. Versions below it are reported as `EvictedFromCache`, so readers fetch
+    /// them from the file store. Returns false if the cache is already set up.
+    pub fn seed_latest_version(&self, version: u64) -> bool {
+        let mut state = self.state.write().unwrap();
+        if state.latest_version.is_some() {
+            return false;
+        }
+        state.latest_version = Some(version);
+        state.start_version = version;
+        state.evicted_below_version = version;
+        aptos_logger::info!(
+            initialized_latest_version = version,
+            "Cache latest version is initialized."
+        );
+        true
+    }
+}
+
+impl InMemoryCacheState {
+    fn insert(&mut self, version: u64, encoded_proto_data: String) {
+        if version < self.evicted_below_version {
+            return;
+        }
+        if self.transactions.is_empty() {
+            self.start_version = version;
+        }
+        while version < self.start_version {
+            self.transactions.push_front(None);
+            self.start_version -= 1;
+        }
+        let index = (version - self.start_version) as usize;
+        if index >= self.transactions.len() {
+            self.transactions.resize(index + 1, None);
+        }
+        self.size_in_bytes += encoded_proto_data.len();
+        if let Some(replaced) = self.transactions[index].replace(encoded_proto_data) {
+            self.size_in_bytes -= replaced.len();
+        }
+
+        // Actively evict the old versions, same as the cache worker does with Redis.
+        if version >= CACHE_SIZE_EVICTION_LOWER_BOUND {
+            self.evict_below(version - CACHE_SIZE_EVICTION_LOWER_BOUND + 1);
+        }
+        while self.size_in_bytes > self.max_size_in_bytes && !self.transactions.is_empty() {
+            self.evict_below(self.start_version + 1);
+        }
+    }
+
+    fn evict_below(&mut self, version: u64) {
+        while self.start_version < version {
+            match self.transactions.pop_front() {
+                Some(transaction) => {
+                    self.size_in_bytes -= transaction.map_or(0, |t| t.len());
+                    self.start_version += 1;
+                },
+                None => {
+                    self.start_version = version;
+                },
+            }
+        }
+        self.evicted_below_version = std::cmp::max(self.evicted_below_version, version);
+    }
+
+    fn check_cache_coverage_status(
+        &self,
+        requested_version: u64,
+    ) -> anyhow::Result<CacheCoverageStatus> {
+        let latest_version = match self.latest_version {
+            Some(latest_version) => latest_version,
+            None => anyhow::bail!("In-memory cache is not set up."),
+        };
+
+        if requested_version >= latest_version {
+            Ok(CacheCoverageStatus::DataNotReady)
+        } else if requested_version + CACHE_SIZE_ESTIMATION < latest_version
+            || requested_version < self.evicted_below_version
+        {
+            Ok(CacheCoverageStatus::CacheEvicted)
+        } else {
+            Ok(CacheCoverageStatus::CacheHit(std::cmp::min(
+                latest_version - requested_version,
+                BLOB_STORAGE_SIZE as u64,
+            )))
+        }
+    }
+
+    fn get(&self, version: u64) -> anyhow::Result<String> {
+        version
+            .checked_sub(self.start_version)
+            .and_then(|index| self.transactions.get(index as usize))
+            .cloned()
+            .flatten()
+            .ok_or_else(|| anyhow::anyhow!("Transaction {} is missing in cache.", version))
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for InMemoryCache {
+    async fn cache_setup_if_needed(&mut self) -> bool {
+        let mut state = self.state.write().unwrap();
+        if state.latest_version.is_some() {
+            return false;
+        }
+        state.latest_version = Some(0);
+        aptos_logger::info!(
+            initialized_latest_version = 0,
+            "Cache latest version is initialized."
+        );
+        true
+    }
+
+    async fn update_or_verify_chain_id(&mut self, chain_id: u64) -> anyhow::Result<()> {
+        let mut state = self.state.write().unwrap();
+        match state.chain_id {
+            Some(cached_chain_id) if cached_chain_id != chain_id => {
+                anyhow::bail!("Chain id is not correct.")
+            },
+            Some(_) => Ok(()),
+            None => {
+                state.chain_id = Some(chain_id);
+                Ok(())
+            },
+        }
+    }
+
+    async fn get_chain_id(&mut self) -> anyhow::Result<u64> {
+        self.state
+            .read()
+            .unwrap()
+            .chain_id
+            .ok_or_else(|| anyhow::anyhow!("Chain id is not in cache."))
+    }
+
+    async fn update_cache_transactions(
+        &mut self,
+        transactions: Vec<(u64, String, u64)>,
+    ) -> anyhow::Result<()> {
+        let mut state = self.state.write().unwrap();
+        for (version, encoded_proto_data, _timestamp_in_seconds) in transactions {
+            state.insert(version, encoded_proto_data);
+        }
+        Ok(())
+    }
+
+    async fn update_cache_latest_version(
+        &mut self,
+        num_of_versions: u64,
+        version: u64,
+    ) -> anyhow::Result<()> {
+        aptos_logger::info!(
+            num_of_versions = num_of_versions,
+            version = version,
+            "Updating latest version in cache."
+        );
+        let mut state = self.state.write().unwrap();
+        match state.latest_version {
+            Some(latest_version) if latest_version + num_of_versions < version => {
+                aptos_logger::error!(
+                    version = version,
+                    "In-memory cache latest version update failed. The version is beyond the next expected version."
+                );
+                panic!("version is not right.");
+            },
+            Some(latest_version) => {
+                state.latest_version = Some(std::cmp::max(latest_version, version));
+            },
+            None => {
+                state.latest_version = Some(version);
+            },
+        }
+        Ok(())
+    }
+
+    async fn batch_get_encoded_proto_data(
+        &mut self,
+        start_version: u64,
+    ) -> anyhow::Result<CacheBatchGetStatus> {
+        let state = self.state.read().unwrap();
+        match state.check_cache_coverage_status(start_version)? {
+            CacheCoverageStatus::CacheHit(v) => Ok(CacheBatchGetStatus::Ok(
+                (start_version..start_version + v)
+                    .map(|version| state.get(version))
+                    .collect::<anyhow::Result<_>>()?,
+            )),
+            CacheCoverageStatus::CacheEvicted => Ok(CacheBatchGetStatus::EvictedFromCache),
+            CacheCoverageStatus::DataNotReady => Ok(CacheBatchGetStatus::NotReady),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transactions(versions: std::ops::Range<u64>) -> Vec<(u64, String, u64)> {
+        versions.map(|v| (v, format!("t{}", v), 0)).collect()
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_setup_and_chain_id() {
+        let mut cache = InMemoryCache::new(1_000);
+        assert!(cache.get_chain_id().await.is_err());
+        assert!(cache.batch_get_encoded_proto_data(0).await.is_err());
+        assert!(cache.cache_setup_if_needed().await);
+        assert!(!cache.cache_setup_if_needed().await);
+
+        cache.update_or_verify_chain_id(1).await.unwrap();
+        cache.update_or_verify_chain_id(1).await.unwrap();
+        assert!(cache.update_or_verify_chain_id(2).await.is_err());
+        // Clones share the same cache.
+        assert_eq!(cache.clone().get_chain_id().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_batch_get() {
+        let mut cache = InMemoryCache::new(usize::MAX);
+        cache.cache_setup_if_needed().await;
+        // Chunks are received out of order; nothing is ready until the latest version is updated.
+        cache
+            .update_cache_transactions(transactions(1000..1500))
+            .await
+            .unwrap();
+        cache
+            .update_cache_transactions(transactions(0..1000))
+            .await
+            .unwrap();
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(0).await.unwrap(),
+            CacheBatchGetStatus::NotReady
+        );
+        cache.update_cache_latest_version(1500, 1500).await.unwrap();
+
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(1).await.unwrap(),
+            CacheBatchGetStatus::Ok((1..1001).map(|v| format!("t{}", v)).collect())
+        );
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(1497).await.unwrap(),
+            CacheBatchGetStatus::Ok(vec![
+                "t1497".to_string(),
+                "t1498".to_string(),
+                "t1499".to_string()
+            ])
+        );
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(1500).await.unwrap(),
+            CacheBatchGetStatus::NotReady
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_is_bounded_by_size() {
+        // Each transaction is 4 bytes, e.g., "t100", so the cache holds 100 transactions.
+        let mut cache = InMemoryCache::new(400);
+        cache.cache_setup_if_needed().await;
+        cache
+            .update_cache_transactions(transactions(100..300))
+            .await
+            .unwrap();
+        cache.update_cache_latest_version(300, 300).await.unwrap();
+
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(100).await.unwrap(),
+            CacheBatchGetStatus::EvictedFromCache
+        );
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(199).await.unwrap(),
+            CacheBatchGetStatus::EvictedFromCache
+        );
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(200).await.unwrap(),
+            CacheBatchGetStatus::Ok((200..300).map(|v| format!("t{}", v)).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_evicts_old_versions() {
+        let mut cache = InMemoryCache::new(usize::MAX);
+        cache.cache_setup_if_needed().await;
+        let start_version = CACHE_SIZE_EVICTION_LOWER_BOUND;
+        cache
+            .update_cache_transactions(transactions(start_version..start_version + 10))
+            .await
+            .unwrap();
+        cache
+            .update_cache_latest_version(start_version + 10, start_version + 10)
+            .await
+            .unwrap();
+        // Same as Redis, versions beyond the cache size estimation are reported as evicted.
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(1).await.unwrap(),
+            CacheBatchGetStatus::EvictedFromCache
+        );
+        assert_eq!(
+            cache
+                .batch_get_encoded_proto_data(start_version)
+                .await
+                .unwrap(),
+            CacheBatchGetStatus::Ok(
+                (start_version..start_version + 10)
+                    .map(|v| format!("t{}", v))
+                    .collect()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_resumes_from_seeded_version() {
+        // After a restart, the cache is empty while the file store is at version 2000.
+        let mut cache = InMemoryCache::new(usize::MAX);
+        assert!(cache.seed_latest_version(2000));
+        assert!(!cache.seed_latest_version(3000));
+        assert!(!cache.cache_setup_if_needed().await);
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(2000).await.unwrap(),
+            CacheBatchGetStatus::NotReady
+        );
+
+        // The cache worker resumes streaming from the file store version.
+        cache
+            .update_cache_transactions(transactions(2000..2500))
+            .await
+            .unwrap();
+        cache.update_cache_latest_version(500, 2500).await.unwrap();
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(2000).await.unwrap(),
+            CacheBatchGetStatus::Ok((2000..2500).map(|v| format!("t{}", v)).collect())
+        );
+        // Versions before the restart are served by the file store.
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(1000).await.unwrap(),
+            CacheBatchGetStatus::EvictedFromCache
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_seed_after_setup() {
+        let mut cache = InMemoryCache::new(usize::MAX);
+        assert!(cache.cache_setup_if_needed().await);
+        assert!(!cache.seed_latest_version(2000));
+        assert_eq!(
+            cache.batch_get_encoded_proto_data(0).await.unwrap(),
+            CacheBatchGetStatus::NotReady
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn in_memory_cache_latest_version_gap() {
+        let mut cache = InMemoryCache::new(usize::MAX);
+        cache.cache_setup_if_needed().await;
+        cache.update_cache_latest_version(10, 20).await.unwrap();
+    }
+}
@@ -5,6 +5,8 @@ pub mod cache_operator;
 pub mod config;
 pub mod constants;
 pub mod file_store_operator;
+pub mod filter;
+pub mod in_memory_cache;
 
 use aptos_protos::datastream::v1::indexer_stream_client::IndexerStreamClient;
 
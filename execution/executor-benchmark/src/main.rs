@@ -112,6 +112,12 @@ struct Opt {
 
     #[structopt(long)]
     use_fake_executor: bool,
+
+    #[structopt(
+        long,
+        about = "Log a report of the parallel execution statistics and conflicts of every block"
+    )]
+    block_execution_reports: bool,
 }
 
 impl Opt {
@@ -247,6 +253,9 @@ fn main() {
         .expect("Failed to build rayon global thread pool.");
     AptosVM::set_concurrency_level_once(opt.concurrency_level());
     FakeExecutor::set_concurrency_level_once(opt.concurrency_level());
+    if opt.block_execution_reports {
+        AptosVM::set_block_execution_reports_once();
+    }
 
     if opt.use_fake_executor {
         run::<FakeExecutor>(opt);
@@ -22,7 +22,10 @@ use aptos_storage_interface::ExecutedTrees;
 use aptos_types::{
     proof::accumulator::InMemoryAccumulator,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, TransactionInfo, TransactionOutput, TransactionStatus},
+    transaction::{
+        ExecutionStatus, Transaction, TransactionInfo, TransactionOutput, TransactionStatus,
+    },
+    write_set::WriteSet,
 };
 use rayon::prelude::*;
 use std::{collections::HashMap, iter::repeat, sync::Arc};
@@ -99,16 +102,27 @@ impl ApplyChunkOutput {
             .position(|o| o.is_reconfig())
             .map(|idx| idx + 1);
 
-        // Transactions after the epoch ending are all to be retried.
-        let to_retry = if let Some(pos) = new_epoch_marker {
-            transaction_outputs.drain(pos..);
-            transactions.drain(pos..).collect()
+        // Once the block gas limit is reached, the VM skips the rest of the block.
+        let block_gas_limit_marker = if new_epoch_marker.is_none() {
+            transaction_outputs
+                .iter()
+                .position(|o| matches!(o.status(), TransactionStatus::Retry))
         } else {
-            vec![]
+            None
         };
 
+        // Transactions after the epoch ending, or skipped because of the block gas limit, are all
+        // to be retried.
+        let mut to_retry: Vec<Transaction> =
+            if let Some(pos) = new_epoch_marker.or(block_gas_limit_marker) {
+                transaction_outputs.drain(pos..);
+                transactions.drain(pos..).collect()
+            } else {
+                vec![]
+            };
+
         // N.B. Transaction status after the epoch marker are ignored and set to Retry forcibly.
-        let status = transaction_outputs
+        let mut status: Vec<TransactionStatus> = transaction_outputs
             .iter()
             .map(|t| t.status())
             .cloned()
@@ -116,6 +130,26 @@ impl ApplyChunkOutput {
             .take(num_txns)
             .collect();
 
+        // The block still ends with a state checkpoint, even if the one it came with was skipped
+        // because of the block gas limit.
+        if block_gas_limit_marker.is_some()
+            && matches!(to_retry.last(), Some(Transaction::StateCheckpoint(_)))
+        {
+            transactions.extend(to_retry.pop());
+            transaction_outputs.push(
+                TransactionOutput::new(
+                    WriteSet::default(),
+                    vec![],
+                    0,
+                    TransactionStatus::Keep(ExecutionStatus::Success),
+                )
+                .into(),
+            );
+            if let Some(last) = status.last_mut() {
+                *last = TransactionStatus::Keep(ExecutionStatus::Success);
+            }
+        }
+
         // Separate transactions with the Keep status out.
         let (to_keep, to_discard) =
             itertools::zip_eq(transactions.into_iter(), transaction_outputs.into_iter())
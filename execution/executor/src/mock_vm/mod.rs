@@ -46,6 +46,8 @@ enum MockVMTransaction {
         recipient: AccountAddress,
         amount: u64,
     },
+    /// Uses up the rest of the block gas limit, so that the rest of the block is skipped.
+    ReachBlockGasLimit,
 }
 
 pub static KEEP_STATUS: Lazy<TransactionStatus> =
@@ -96,8 +98,20 @@ impl VMExecutor for MockVM {
         // transactions.
         let mut output_cache = HashMap::new();
         let mut outputs = vec![];
+        let mut block_gas_limit_reached = false;
 
         for txn in transactions {
+            if block_gas_limit_reached {
+                // Like the block executor, also skip the state checkpoint ending the block.
+                outputs.push(TransactionOutput::new(
+                    WriteSet::default(),
+                    vec![],
+                    0,
+                    TransactionStatus::Retry,
+                ));
+                continue;
+            }
+
             if matches!(txn, Transaction::StateCheckpoint(_)) {
                 outputs.push(TransactionOutput::new(
                     WriteSet::default(),
@@ -194,6 +208,15 @@ impl VMExecutor for MockVM {
                         TransactionStatus::Keep(ExecutionStatus::Success),
                     ));
                 },
+                MockVMTransaction::ReachBlockGasLimit => {
+                    block_gas_limit_reached = true;
+                    outputs.push(TransactionOutput::new(
+                        WriteSet::default(),
+                        vec![],
+                        0,
+                        KEEP_STATUS.clone(),
+                    ));
+                },
             }
         }
 
@@ -341,6 +364,10 @@ pub fn encode_transfer_program(recipient: AccountAddress, amount: u64) -> Script
     Script::new(vec![], vec![], vec![argument1, argument2])
 }
 
+pub fn encode_reach_block_gas_limit_program() -> Script {
+    Script::new(vec![], vec![], vec![])
+}
+
 pub fn encode_mint_transaction(sender: AccountAddress, amount: u64) -> Transaction {
     encode_transaction(sender, encode_mint_program(amount))
 }
@@ -365,6 +392,10 @@ fn encode_transaction(sender: AccountAddress, program: Script) -> Transaction {
     )
 }
 
+pub fn encode_reach_block_gas_limit_transaction(sender: AccountAddress) -> Transaction {
+    encode_transaction(sender, encode_reach_block_gas_limit_program())
+}
+
 pub fn encode_reconfiguration_transaction() -> Transaction {
     Transaction::GenesisTransaction(WriteSetPayload::Direct(
         ChangeSet::new(WriteSet::default(), vec![], &NoOpChangeSetChecker).unwrap(),
@@ -377,6 +408,7 @@ fn decode_transaction(txn: &SignedTransaction) -> MockVMTransaction {
         TransactionPayload::Script(script) => {
             assert!(script.code().is_empty(), "Code should be empty.");
             match script.args().len() {
+                0 => MockVMTransaction::ReachBlockGasLimit,
                 1 => match script.args()[0] {
                     TransactionArgument::U64(amount) => MockVMTransaction::Mint { sender, amount },
                     _ => unimplemented!(
@@ -396,7 +428,7 @@ fn decode_transaction(txn: &SignedTransaction) -> MockVMTransaction {
                          and the second argument must be amount."
                     ),
                 },
-                _ => unimplemented!("Transaction must have one or two arguments."),
+                _ => unimplemented!("Transaction must have at most two arguments."),
             }
         },
         TransactionPayload::EntryFunction(_) => {
@@ -8,8 +8,9 @@ use crate::{
     components::chunk_output::ChunkOutput,
     db_bootstrapper::{generate_waypoint, maybe_bootstrap},
     mock_vm::{
-        encode_mint_transaction, encode_reconfiguration_transaction, encode_transfer_transaction,
-        MockVM, DISCARD_STATUS, KEEP_STATUS,
+        encode_mint_transaction, encode_reach_block_gas_limit_transaction,
+        encode_reconfiguration_transaction, encode_transfer_transaction, MockVM, DISCARD_STATUS,
+        KEEP_STATUS,
     },
 };
 use anyhow::Result;
@@ -161,6 +162,55 @@ fn test_executor_status() {
     );
 }
 
+#[test]
+#[cfg_attr(feature = "consensus-only-perf-test", ignore)]
+fn test_executor_block_gas_limit_keeps_state_checkpoint() {
+    let executor = TestExecutor::new();
+    let parent_block_id = executor.committed_block_id();
+    let block_id = gen_block_id(1);
+
+    let txn0 = encode_mint_transaction(gen_address(0), 100);
+    let txn1 = encode_reach_block_gas_limit_transaction(gen_address(1));
+    let txn2 = encode_mint_transaction(gen_address(2), 100);
+    let txn3 = encode_mint_transaction(gen_address(3), 100);
+
+    // The VM skips the rest of the block, including its state checkpoint, once the block gas
+    // limit is reached.
+    let output = executor
+        .execute_block(
+            (block_id, block(vec![txn0, txn1, txn2, txn3])),
+            parent_block_id,
+        )
+        .unwrap();
+
+    assert_eq!(
+        &vec![
+            KEEP_STATUS.clone(),
+            KEEP_STATUS.clone(),
+            TransactionStatus::Retry,
+            TransactionStatus::Retry,
+            KEEP_STATUS.clone(),
+        ],
+        output.compute_status()
+    );
+
+    // The two kept transactions are followed by the state checkpoint.
+    let version = 3;
+    assert_eq!(output.version(), version);
+    let ledger_info = gen_ledger_info(version, output.root_hash(), block_id, 1);
+    executor.commit_blocks(vec![block_id], ledger_info).unwrap();
+
+    let committed = executor
+        .db
+        .reader
+        .get_transactions(1, 3, version, false)
+        .unwrap();
+    assert!(matches!(
+        committed.transactions.last(),
+        Some(Transaction::StateCheckpoint(_))
+    ));
+}
+
 #[cfg(feature = "consensus-only-perf-test")]
 #[test]
 fn test_executor_status_consensus_only() {
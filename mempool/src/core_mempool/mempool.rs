@@ -296,6 +296,23 @@ impl Mempool {
         block
     }
 
+    /// Returns up to `max_txns` of the highest priority transactions that can be executed against
+    /// the latest committed state, i.e. at most one (the next) transaction per account.
+    pub(crate) fn get_pre_execution_candidates(&self, max_txns: usize) -> Vec<SignedTransaction> {
+        self.transactions
+            .iter_queue()
+            .filter_map(|txn| {
+                let sequence_number = txn.sequence_number.transaction_sequence_number;
+                if self.transactions.get_sequence_number(&txn.address) == Some(&sequence_number) {
+                    self.transactions.get(&txn.address, sequence_number)
+                } else {
+                    None
+                }
+            })
+            .take(max_txns)
+            .collect()
+    }
+
     /// Periodic core mempool garbage collection.
     /// Removes all expired transactions and clears expired entries in metrics
     /// cache and sequence number cache.
@@ -511,6 +511,17 @@ pub static CORE_MEMPOOL_INVARIANT_VIOLATION_COUNT: Lazy<IntCounter> = Lazy::new(
     .unwrap()
 });
 
+/// Counter of transactions executed ahead of time whose outputs were cached, and of failed
+/// rounds of execution ahead of time
+pub static PRE_EXECUTION_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_mempool_pre_execution_count",
+        "Number of transactions executed ahead of time with cached outputs, and failed rounds",
+        &["result"]
+    )
+    .unwrap()
+});
+
 pub static VM_RECONFIG_UPDATE_FAIL_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
         "aptos_mempool_vm_reconfig_update_fail_count",
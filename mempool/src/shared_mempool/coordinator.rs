@@ -28,7 +28,9 @@ use aptos_network::{
     protocols::network::Event,
 };
 use aptos_types::on_chain_config::OnChainConfigPayload;
-use aptos_vm_validator::vm_validator::TransactionValidation;
+use aptos_vm_validator::{
+    pre_executor::TransactionPreExecution, vm_validator::TransactionValidation,
+};
 use futures::{
     channel::mpsc,
     stream::{select_all, FuturesUnordered},
@@ -371,6 +373,42 @@ pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval
     ));
 }
 
+/// Periodically executes the highest priority transactions in core mempool ahead of time, so that
+/// block execution can reuse their outputs.
+pub(crate) async fn pre_execution_job<PreExecutor: TransactionPreExecution + 'static>(
+    mempool: Arc<Mutex<CoreMempool>>,
+    pre_executor: Arc<PreExecutor>,
+    batch_size: usize,
+    interval_ms: u64,
+) {
+    let mut interval = IntervalStream::new(interval(Duration::from_millis(interval_ms)));
+    while let Some(_interval) = interval.next().await {
+        let txns = mempool.lock().get_pre_execution_candidates(batch_size);
+        if txns.is_empty() {
+            continue;
+        }
+
+        let pre_executor = pre_executor.clone();
+        match tokio::task::spawn_blocking(move || pre_executor.pre_execute(txns)).await {
+            Ok(Ok(num_cached)) => counters::PRE_EXECUTION_COUNT
+                .with_label_values(&[counters::SUCCESS_LABEL])
+                .inc_by(num_cached as u64),
+            Ok(Err(error)) => {
+                counters::PRE_EXECUTION_COUNT
+                    .with_label_values(&[counters::REQUEST_FAIL_LABEL])
+                    .inc();
+                warn!("Failed to execute transactions ahead of time: {:?}", error);
+            },
+            Err(error) => {
+                counters::PRE_EXECUTION_COUNT
+                    .with_label_values(&[counters::REQUEST_FAIL_LABEL])
+                    .inc();
+                error!("Transaction pre-execution task failed: {:?}", error);
+            },
+        }
+    }
+}
+
 /// Periodically logs a snapshot of transactions in core mempool.
 /// In the future we may want an interactive way to directly query mempool's internal state.
 /// For now, we will rely on this periodic snapshot to observe the internal state.
@@ -6,7 +6,7 @@ use crate::{
     core_mempool::CoreMempool,
     network::MempoolSyncMsg,
     shared_mempool::{
-        coordinator::{coordinator, gc_coordinator, snapshot_job},
+        coordinator::{coordinator, gc_coordinator, pre_execution_job, snapshot_job},
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
@@ -18,7 +18,10 @@ use aptos_logger::Level;
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_storage_interface::DbReader;
-use aptos_vm_validator::vm_validator::{TransactionValidation, VMValidator};
+use aptos_vm_validator::{
+    pre_executor::TransactionPreExecutor,
+    vm_validator::{TransactionValidation, VMValidator},
+};
 use futures::channel::mpsc::{Receiver, UnboundedSender};
 use std::sync::Arc;
 use tokio::runtime::{Handle, Runtime};
@@ -28,6 +31,8 @@ use tokio::runtime::{Handle, Runtime};
 ///   - outbound_sync_task (task that periodically broadcasts transactions to peers).
 ///   - inbound_network_task (task that handles inbound mempool messages and network events).
 ///   - gc_task (task that performs GC of all expired transactions by SystemTTL).
+///   - pre_execution_task (if enabled, task that periodically executes the highest priority
+///     transactions ahead of time, caching their outputs for block execution).
 pub(crate) fn start_shared_mempool<TransactionValidator>(
     executor: &Handle,
     config: &NodeConfig,
@@ -49,7 +54,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator>(
             mempool.clone(),
             config.mempool.clone(),
             network_client,
-            db,
+            db.clone(),
             validator,
             subscribers,
             config.base.role,
@@ -70,6 +75,15 @@ pub(crate) fn start_shared_mempool<TransactionValidator>(
         config.mempool.system_transaction_gc_interval_ms,
     ));
 
+    if config.mempool.speculative_execution_enabled {
+        executor.spawn(pre_execution_job(
+            mempool.clone(),
+            Arc::new(TransactionPreExecutor::new(db)),
+            config.mempool.speculative_execution_batch_size,
+            config.mempool.speculative_execution_interval_ms,
+        ));
+    }
+
     if aptos_logger::enabled!(Level::Trace) {
         executor.spawn(snapshot_job(
             mempool,
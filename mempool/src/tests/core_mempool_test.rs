@@ -65,6 +65,37 @@ fn test_transaction_ordering_only_seqnos() {
     }
 }
 
+#[test]
+fn test_pre_execution_candidates() {
+    let (mut mempool, _) = setup_mempool();
+    let txns = add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 5),
+        TestTransaction::new(1, 0, 3),
+        // Not executable against the committed state, as it depends on a missing transaction.
+        TestTransaction::new(2, 1, 10),
+    ]);
+
+    // Only the next transaction of every account is returned, ordered by gas price.
+    assert_eq!(mempool.get_pre_execution_candidates(10), vec![
+        txns[2].clone(),
+        txns[0].clone()
+    ]);
+    assert_eq!(mempool.get_pre_execution_candidates(1), vec![
+        txns[2].clone()
+    ]);
+
+    // Candidates are not removed from mempool.
+    assert_eq!(mempool.get_pre_execution_candidates(10).len(), 2);
+
+    // Once the next transaction is committed, the following one becomes a candidate.
+    mempool.commit_transaction(&TestTransaction::get_address(0), 0);
+    assert_eq!(mempool.get_pre_execution_candidates(10), vec![
+        txns[1].clone(),
+        txns[2].clone()
+    ]);
+}
+
 #[test]
 fn test_transaction_metrics() {
     let (mut mempool, _) = setup_mempool();
@@ -4,13 +4,24 @@
 
 use crate::{
     mocks::MockSharedMempool,
-    tests::common::{batch_add_signed_txn, TestTransaction},
+    shared_mempool::coordinator::pre_execution_job,
+    tests::common::{add_txns_to_mempool, batch_add_signed_txn, setup_mempool, TestTransaction},
     QuorumStoreRequest,
 };
+use anyhow::Result;
 use aptos_consensus_types::common::RejectedTransactionSummary;
+use aptos_infallible::Mutex;
 use aptos_mempool_notifications::MempoolNotificationSender;
-use aptos_types::{transaction::Transaction, vm_status::DiscardedVMStatus};
+use aptos_types::{
+    transaction::{SignedTransaction, Transaction},
+    vm_status::DiscardedVMStatus,
+};
+use aptos_vm_validator::pre_executor::TransactionPreExecution;
 use futures::{channel::oneshot, executor::block_on, sink::SinkExt};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 #[test]
 fn test_consensus_events_rejected_txns() {
@@ -96,3 +107,87 @@ fn test_mempool_notify_committed_txns() {
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
+
+/// Records the batches of transactions it is asked to execute ahead of time.
+#[derive(Default)]
+struct MockPreExecutor {
+    batches: Mutex<Vec<Vec<SignedTransaction>>>,
+}
+
+impl TransactionPreExecution for MockPreExecutor {
+    fn pre_execute(&self, txns: Vec<SignedTransaction>) -> Result<usize> {
+        let num_txns = txns.len();
+        self.batches.lock().push(txns);
+        Ok(num_txns)
+    }
+}
+
+#[test]
+fn test_pre_execution_job() {
+    let runtime = aptos_runtimes::spawn_named_runtime("pre-execution".into(), None);
+
+    let (mut pool, _) = setup_mempool();
+    let txns = add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(1, 0, 5),
+        TestTransaction::new(1, 1, 5),
+    ]);
+    let mempool = Arc::new(Mutex::new(pool));
+    let pre_executor = Arc::new(MockPreExecutor::default());
+    runtime.spawn(pre_execution_job(
+        mempool.clone(),
+        pre_executor.clone(),
+        1,
+        10,
+    ));
+
+    // An empty mempool is never asked to execute anything.
+    let (empty_pool, _) = setup_mempool();
+    let empty_pre_executor = Arc::new(MockPreExecutor::default());
+    runtime.spawn(pre_execution_job(
+        Arc::new(Mutex::new(empty_pool)),
+        empty_pre_executor.clone(),
+        1,
+        10,
+    ));
+
+    let start = Instant::now();
+    while pre_executor.batches.lock().len() < 2 {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "Timed out waiting for the transactions to be executed ahead of time"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // Only the highest priority candidate is executed, as the batch size is 1, and it keeps
+    // being executed as long as it is in mempool (the cache skips it once executed).
+    for batch in pre_executor.batches.lock().iter() {
+        assert_eq!(batch, &vec![txns[1].clone()]);
+    }
+
+    // Once it is committed, the next transaction of the same account has the highest priority.
+    mempool
+        .lock()
+        .commit_transaction(&TestTransaction::get_address(1), 0);
+    let num_batches = pre_executor.batches.lock().len();
+    let start = Instant::now();
+    loop {
+        // A batch may have been taken before the commit.
+        let next_batch = pre_executor.batches.lock()[num_batches..]
+            .iter()
+            .find(|batch| **batch != vec![txns[1].clone()])
+            .cloned();
+        if let Some(batch) = next_batch {
+            assert_eq!(batch, vec![txns[2].clone()]);
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "Timed out waiting for the next transaction to be executed ahead of time"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    assert!(empty_pre_executor.batches.lock().is_empty());
+}
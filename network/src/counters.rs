@@ -12,6 +12,7 @@ use aptos_netcore::transport::ConnectionOrigin;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_types::PeerId;
 use once_cell::sync::Lazy;
+use std::time::Duration;
 
 // some type labels
 pub const REQUEST_LABEL: &str = "request";
@@ -557,3 +558,66 @@ pub fn network_application_outbound_traffic(
         ])
         .observe(size as f64);
 }
+
+pub static APTOS_NETWORK_OUTBOUND_QUEUE_DELAY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_network_outbound_queue_delay",
+        "Time (in seconds) outbound messages spend queued in the peer writer, by protocol",
+        &["role_type", "network_id", "protocol_id"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_queue_delay(
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+    delay: Duration,
+) {
+    APTOS_NETWORK_OUTBOUND_QUEUE_DELAY
+        .with_label_values(&[
+            network_context.role().as_str(),
+            network_context.network_id().as_str(),
+            protocol_id.as_str(),
+        ])
+        .observe(delay.as_secs_f64());
+}
+
+pub static APTOS_NETWORK_OUTBOUND_THROTTLED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_outbound_throttled_messages",
+        "Number of times an outbound message was held back by the protocol bandwidth limit",
+        &["role_type", "network_id", "protocol_id"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_throttled_messages(
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+) -> IntCounter {
+    APTOS_NETWORK_OUTBOUND_THROTTLED_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        protocol_id.as_str(),
+    ])
+}
+
+pub static APTOS_NETWORK_OUTBOUND_DROPPED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_outbound_dropped_messages",
+        "Number of buffered outbound messages dropped by the traffic shaper when the writer closed",
+        &["role_type", "network_id", "protocol_id"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_dropped_messages(
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+) -> IntCounter {
+    APTOS_NETWORK_OUTBOUND_DROPPED_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        protocol_id.as_str(),
+    ])
+}
@@ -35,7 +35,8 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::OutboundTrafficShapingConfig, network_id::NetworkContext};
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
@@ -50,17 +51,19 @@ use futures::{
 };
 use futures_util::stream::select;
 use serde::Serialize;
-use std::{fmt, panic, time::Duration};
+use std::{collections::HashMap, fmt, panic, sync::Arc, time::Duration};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
 };
+use traffic_shaping::{is_priority_message, OutboundTrafficShaper, ResponseProtocols};
 
 #[cfg(test)]
 mod test;
 
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
+pub mod traffic_shaping;
 
 /// Requests [`Peer`] receives from the [`PeerManager`](crate::peer_manager::PeerManager).
 #[derive(Debug)]
@@ -138,6 +141,10 @@ pub struct Peer<TSocket> {
     max_message_size: usize,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Outbound traffic shaping configuration (if enabled)
+    outbound_traffic_shaping_config: Option<OutboundTrafficShapingConfig>,
+    /// The protocols of inbound rpcs with pending responses (used for traffic shaping)
+    response_protocols: Option<ResponseProtocols>,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -158,6 +165,7 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        outbound_traffic_shaping_config: Option<OutboundTrafficShapingConfig>,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
@@ -165,6 +173,11 @@ where
         } = connection;
         let remote_peer_id = connection_metadata.remote_peer_id;
         let max_fragments = max_message_size / max_frame_size;
+        let outbound_traffic_shaping_config =
+            outbound_traffic_shaping_config.filter(|config| config.enabled);
+        let response_protocols: Option<ResponseProtocols> = outbound_traffic_shaping_config
+            .as_ref()
+            .map(|_| Arc::new(Mutex::new(HashMap::new())));
         Self {
             network_context,
             executor,
@@ -180,6 +193,7 @@ where
                 remote_peer_id,
                 inbound_rpc_timeout,
                 max_concurrent_inbound_rpcs,
+                response_protocols.clone(),
             ),
             outbound_rpcs: OutboundRpcs::new(
                 network_context,
@@ -191,6 +205,8 @@ where
             max_frame_size,
             max_message_size,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            outbound_traffic_shaping_config,
+            response_protocols,
         }
     }
 
@@ -215,6 +231,18 @@ where
         let mut reader =
             MultiplexMessageStream::new(read_socket.compat(), self.max_frame_size).fuse();
         let writer = MultiplexMessageSink::new(write_socket.compat_write(), self.max_frame_size);
+        let traffic_shaper = self
+            .outbound_traffic_shaping_config
+            .as_ref()
+            .zip(self.response_protocols.clone())
+            .map(|(config, response_protocols)| {
+                OutboundTrafficShaper::new(
+                    self.network_context,
+                    self.time_service.clone(),
+                    config,
+                    response_protocols,
+                )
+            });
 
         // Start writer "process" as a separate task. We receive two handles to
         // communicate with the task:
@@ -228,6 +256,7 @@ where
             writer,
             self.max_frame_size,
             self.max_message_size,
+            traffic_shaper,
         );
 
         // Start main Peer event loop.
@@ -297,7 +326,9 @@ where
     // 1. The first channel is used to send outbound NetworkMessages to the task
     // 2. The second channel is used to instruct the task to close the connection and terminate.
     // If outbound messages are queued when the task receives a close instruction, it discards
-    // them and immediately closes the connection.
+    // them and immediately closes the connection. If a traffic shaper is provided, it decides
+    // the order in which queued messages are written (see `traffic_shaping`).
+    #[allow(clippy::too_many_arguments)]
     fn start_writer_task(
         executor: &Handle,
         time_service: TimeService,
@@ -306,6 +337,7 @@ where
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
         max_frame_size: usize,
         max_message_size: usize,
+        mut traffic_shaper: Option<OutboundTrafficShaper>,
     ) -> (aptos_channels::Sender<NetworkMessage>, oneshot::Sender<()>) {
         let remote_peer_id = connection_metadata.remote_peer_id;
         let (write_reqs_tx, mut write_reqs_rx): (aptos_channels::Sender<NetworkMessage>, _) =
@@ -313,15 +345,33 @@ where
         let (close_tx, mut close_rx) = oneshot::channel();
 
         let (mut msg_tx, msg_rx) = aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
+        // Consensus messages bypass the traffic shaper's buffer limit (only used when shaping)
+        let (mut priority_msg_tx, mut priority_msg_rx) =
+            aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
         let (stream_msg_tx, stream_msg_rx) =
             aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_STREAM);
+        let response_protocols = traffic_shaper
+            .as_ref()
+            .map(|traffic_shaper| traffic_shaper.response_protocols());
 
         // this task ends when the multiplex task ends (by dropping the senders)
         let writer_task = async move {
             let mut stream = select(msg_rx, stream_msg_rx);
             let log_context =
                 NetworkSchema::new(&network_context).connection_metadata(&connection_metadata);
-            while let Some(message) = stream.next().await {
+            loop {
+                let maybe_message = match traffic_shaper.as_mut() {
+                    Some(traffic_shaper) => {
+                        traffic_shaper
+                            .next_message(&mut priority_msg_rx, &mut stream)
+                            .await
+                    },
+                    None => stream.next().await,
+                };
+                let message = match maybe_message {
+                    Some(message) => message,
+                    None => break,
+                };
                 if let Err(err) = writer.send(&message).await {
                     warn!(
                         log_context,
@@ -381,13 +431,28 @@ where
             loop {
                 futures::select! {
                     message = write_reqs_rx.select_next_some() => {
+                        let response_request_id = match &message {
+                            NetworkMessage::RpcResponse(response) => Some(response.request_id),
+                            _ => None,
+                        };
                         // either channel full would block the other one
+                        let is_priority = response_protocols
+                            .as_ref()
+                            .map_or(false, |response_protocols| is_priority_message(&message, response_protocols));
                         let result = if outbound_stream.should_stream(&message) {
                             outbound_stream.stream_message(message).await
+                        } else if is_priority {
+                            priority_msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
                         } else {
                             msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
                         };
                         if let Err(err) = result {
+                            // A response that never reaches the writer must not leave its protocol behind
+                            if let (Some(request_id), Some(response_protocols)) =
+                                (response_request_id, &response_protocols)
+                            {
+                                response_protocols.lock().remove(&request_id);
+                            }
                             warn!(
                                 error = %err,
                                 "{} Error in sending message to peer: {}",
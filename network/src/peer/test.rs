@@ -7,7 +7,10 @@ use crate::{
         INBOUND_RPC_TIMEOUT_MS, MAX_CONCURRENT_INBOUND_RPCS, MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE, MAX_MESSAGE_SIZE, NETWORK_CHANNEL_SIZE,
     },
-    peer::{DisconnectReason, Peer, PeerNotification, PeerRequest},
+    peer::{
+        traffic_shaping::{is_priority_message, OutboundTrafficShaper, MAX_BUFFERED_MESSAGES},
+        DisconnectReason, Peer, PeerNotification, PeerRequest,
+    },
     peer_manager::TransportNotification,
     protocols::{
         direct_send::Message,
@@ -24,7 +27,11 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{OutboundTrafficShapingConfig, PeerRole, ProtocolBandwidthLimit},
+    network_id::NetworkContext,
+};
+use aptos_infallible::Mutex;
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_time_service::{MockTimeService, TimeService};
@@ -37,7 +44,12 @@ use futures::{
     stream::{StreamExt, TryStreamExt},
     SinkExt,
 };
-use std::{collections::HashSet, str::FromStr, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 use tokio::runtime::{Handle, Runtime};
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
@@ -90,6 +102,7 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        None,
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -947,3 +960,194 @@ fn peers_send_multiplex() {
 
     rt.block_on(future::join3(peer_a.start(), peer_b.start(), test));
 }
+
+fn create_direct_send(protocol_id: ProtocolId, num_bytes: usize) -> MultiplexMessage {
+    MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id,
+        priority: 0,
+        raw_msg: vec![0; num_bytes],
+    }))
+}
+
+fn create_traffic_shaper(
+    time_service: TimeService,
+    config: &OutboundTrafficShapingConfig,
+) -> OutboundTrafficShaper {
+    OutboundTrafficShaper::new(
+        NetworkContext::mock(),
+        time_service,
+        config,
+        Arc::new(Mutex::new(HashMap::new())),
+    )
+}
+
+#[test]
+fn traffic_shaper_serves_consensus_first() {
+    let mut config = OutboundTrafficShapingConfig::default();
+    config
+        .protocol_priorities
+        .insert(ProtocolId::StorageServiceRpc.as_str().into(), 0);
+    let mut traffic_shaper = create_traffic_shaper(TimeService::mock(), &config);
+
+    // Buffer state sync, mempool and consensus messages (in that order)
+    let storage_message = create_direct_send(ProtocolId::StorageServiceRpc, 1024);
+    let mempool_message = create_direct_send(ProtocolId::MempoolDirectSend, 10);
+    let consensus_message = create_direct_send(ProtocolId::ConsensusDirectSendCompressed, 10);
+    traffic_shaper.push(storage_message.clone());
+    traffic_shaper.push(mempool_message.clone());
+    traffic_shaper.push(consensus_message.clone());
+
+    // Consensus is served first (even though state sync was configured with
+    // the same priority), followed by state sync and then mempool.
+    assert_eq!(traffic_shaper.pop(), Ok(Some(consensus_message)));
+    assert_eq!(traffic_shaper.pop(), Ok(Some(storage_message)));
+    assert_eq!(traffic_shaper.pop(), Ok(Some(mempool_message)));
+    assert_eq!(traffic_shaper.pop(), Ok(None));
+}
+
+#[test]
+fn traffic_shaper_enforces_bandwidth_limits() {
+    let mut config = OutboundTrafficShapingConfig::default();
+    config.protocol_bandwidth_limits.insert(
+        ProtocolId::StorageServiceRpc.as_str().into(),
+        ProtocolBandwidthLimit {
+            byte_bucket_rate: 1000,
+            byte_bucket_size: 1000,
+        },
+    );
+    let time_service = TimeService::mock();
+    let mut traffic_shaper = create_traffic_shaper(time_service.clone(), &config);
+
+    // The first message fits in the bucket, but the second must wait for a refill
+    let storage_message = create_direct_send(ProtocolId::StorageServiceRpc, 1000);
+    traffic_shaper.push(storage_message.clone());
+    traffic_shaper.push(storage_message.clone());
+    assert_eq!(traffic_shaper.pop(), Ok(Some(storage_message.clone())));
+    let wait_time = traffic_shaper.pop().unwrap_err();
+    assert!(wait_time > Duration::from_millis(900));
+
+    // Messages of other protocols are not held back by the limit
+    let mempool_message = create_direct_send(ProtocolId::MempoolDirectSend, 1000);
+    traffic_shaper.push(mempool_message.clone());
+    assert_eq!(traffic_shaper.pop(), Ok(Some(mempool_message)));
+
+    // Once the bucket has refilled, the second message is written
+    time_service.into_mock().advance(wait_time);
+    assert_eq!(traffic_shaper.pop(), Ok(Some(storage_message)));
+    assert_eq!(traffic_shaper.pop(), Ok(None));
+}
+
+#[test]
+fn traffic_shaper_drops_throttled_messages_on_close() {
+    let mut config = OutboundTrafficShapingConfig::default();
+    config.protocol_bandwidth_limits.insert(
+        ProtocolId::StorageServiceRpc.as_str().into(),
+        ProtocolBandwidthLimit {
+            byte_bucket_rate: 1000,
+            byte_bucket_size: 1000,
+        },
+    );
+    let mut traffic_shaper = create_traffic_shaper(TimeService::mock(), &config);
+
+    // Exhaust the bucket so that the second message is held back
+    let storage_message = create_direct_send(ProtocolId::StorageServiceRpc, 1000);
+    traffic_shaper.push(storage_message.clone());
+    traffic_shaper.push(storage_message.clone());
+    assert_eq!(traffic_shaper.pop(), Ok(Some(storage_message)));
+    assert_eq!(traffic_shaper.num_buffered_messages(), 1);
+
+    // Once the channels close, the throttled message is dropped (not waited on)
+    let mut priority_messages = futures::stream::empty::<MultiplexMessage>();
+    let mut messages = futures::stream::empty::<MultiplexMessage>();
+    let next_message = futures::executor::block_on(
+        traffic_shaper.next_message(&mut priority_messages, &mut messages),
+    );
+    assert_eq!(next_message, None);
+    assert_eq!(traffic_shaper.num_buffered_messages(), 0);
+}
+
+#[test]
+fn traffic_shaper_delivers_consensus_when_saturated() {
+    let mut config = OutboundTrafficShapingConfig::default();
+    config.protocol_bandwidth_limits.insert(
+        ProtocolId::StorageServiceRpc.as_str().into(),
+        ProtocolBandwidthLimit {
+            byte_bucket_rate: 1000,
+            byte_bucket_size: 1000,
+        },
+    );
+    let mut traffic_shaper = create_traffic_shaper(TimeService::mock(), &config);
+
+    // Exhaust the bucket and fill the buffer with throttled state sync messages
+    let storage_message = create_direct_send(ProtocolId::StorageServiceRpc, 1000);
+    traffic_shaper.push(storage_message.clone());
+    assert_eq!(traffic_shaper.pop(), Ok(Some(storage_message.clone())));
+    for _ in 0..MAX_BUFFERED_MESSAGES {
+        traffic_shaper.push(storage_message.clone());
+    }
+
+    // More state sync messages are queued, but the full buffer doesn't drain them
+    let (priority_tx, mut priority_rx) = futures::channel::mpsc::unbounded();
+    let (message_tx, mut message_rx) = futures::channel::mpsc::unbounded();
+    message_tx.unbounded_send(storage_message.clone()).unwrap();
+
+    let consensus_message = create_direct_send(ProtocolId::ConsensusDirectSendCompressed, 10);
+    {
+        let next_message = traffic_shaper.next_message(&mut priority_rx, &mut message_rx);
+        futures::pin_mut!(next_message);
+        assert_eq!(next_message.as_mut().now_or_never(), None);
+
+        // A consensus message is written immediately, without waiting for the bandwidth limit
+        priority_tx
+            .unbounded_send(consensus_message.clone())
+            .unwrap();
+        assert_eq!(
+            next_message.now_or_never(),
+            Some(Some(consensus_message.clone()))
+        );
+    }
+    assert_eq!(
+        traffic_shaper.num_buffered_messages(),
+        MAX_BUFFERED_MESSAGES
+    );
+    assert_eq!(
+        message_rx.next().now_or_never(),
+        Some(Some(storage_message))
+    );
+}
+
+#[test]
+fn traffic_shaper_classifies_priority_messages() {
+    let response_protocols = Arc::new(Mutex::new(HashMap::new()));
+    let network_message = |message: MultiplexMessage| match message {
+        MultiplexMessage::Message(message) => message,
+        MultiplexMessage::Stream(_) => unreachable!(),
+    };
+
+    // Consensus messages are sent on the priority channel, other protocols aren't
+    let consensus_message =
+        network_message(create_direct_send(ProtocolId::ConsensusDirectSendBcs, 10));
+    let mempool_message = network_message(create_direct_send(ProtocolId::MempoolDirectSend, 10));
+    assert!(is_priority_message(&consensus_message, &response_protocols));
+    assert!(!is_priority_message(&mempool_message, &response_protocols));
+
+    // Responses are classified by the protocol of their request
+    let response = |request_id| {
+        NetworkMessage::RpcResponse(RpcResponse {
+            request_id,
+            priority: 0,
+            raw_response: vec![],
+        })
+    };
+    response_protocols
+        .lock()
+        .insert(1, ProtocolId::ConsensusRpcCompressed);
+    response_protocols
+        .lock()
+        .insert(2, ProtocolId::StorageServiceRpc);
+    assert!(is_priority_message(&response(1), &response_protocols));
+    assert!(!is_priority_message(&response(2), &response_protocols));
+
+    // Classifying a response doesn't consume its protocol, which the shaper needs later
+    assert_eq!(response_protocols.lock().len(), 2);
+}
@@ -0,0 +1,549 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Outbound traffic shaping for the [`Peer`](crate::peer::Peer) writer task.
+//!
+//! Without traffic shaping, the writer task writes messages to the wire in the
+//! order they were queued. With traffic shaping, the writer task buffers queued
+//! messages per [`ProtocolId`] and always writes the oldest message of the highest
+//! priority class whose bandwidth limit (a token bucket) permits it.
+//!
+//! Consensus protocols are always assigned the highest priority class and are never
+//! bandwidth limited. Note: streamed (fragmented) messages share a single FIFO queue,
+//! because the remote peer can only reassemble one stream at a time.
+//!
+//! Messages of the highest priority class are queued on a separate priority channel
+//! (see [`is_priority_message`]), which the shaper keeps draining even when its buffer
+//! is full of throttled messages. Otherwise, consensus would be starved by bulk traffic.
+//!
+//! RPC responses don't carry a protocol on the wire, so [`InboundRpcs`] records the
+//! protocol of each completed inbound request in the shared [`ResponseProtocols`]
+//! map, which the shaper consumes when the response is buffered.
+//!
+//! [`InboundRpcs`]: crate::protocols::rpc::InboundRpcs
+
+use crate::{
+    counters,
+    protocols::{
+        stream::StreamMessage,
+        wire::messaging::v1::{MultiplexMessage, NetworkMessage, RequestId},
+    },
+    ProtocolId,
+};
+use aptos_config::{config::OutboundTrafficShapingConfig, network_id::NetworkContext};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::{
+    future::{self, Either},
+    pin_mut,
+    stream::{Stream, StreamExt},
+    FutureExt,
+};
+use std::{
+    cmp::{max, min},
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The protocols of inbound rpc requests whose responses are queued for writing
+pub type ResponseProtocols = Arc<Mutex<HashMap<RequestId, ProtocolId>>>;
+
+/// The priority class reserved for consensus protocols (lower values are served first)
+pub const CONSENSUS_PRIORITY: u8 = 0;
+
+/// The maximum number of messages buffered by the shaper. Once reached, the shaper
+/// stops draining the writer channels so that back pressure is preserved.
+pub const MAX_BUFFERED_MESSAGES: usize = 1024;
+
+/// Returns true iff the given protocol is used by consensus
+fn is_consensus_protocol(protocol_id: ProtocolId) -> bool {
+    matches!(
+        protocol_id,
+        ProtocolId::ConsensusRpcBcs
+            | ProtocolId::ConsensusDirectSendBcs
+            | ProtocolId::ConsensusDirectSendJson
+            | ProtocolId::ConsensusRpcJson
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendCompressed
+    )
+}
+
+/// Returns true iff the given message belongs to the consensus priority class, and
+/// should be queued on the priority channel of the writer. This includes messages
+/// without a known protocol (e.g., errors), as the shaper always writes those first.
+pub fn is_priority_message(
+    message: &NetworkMessage,
+    response_protocols: &ResponseProtocols,
+) -> bool {
+    match message {
+        NetworkMessage::Error(_) => true,
+        NetworkMessage::RpcRequest(request) => is_consensus_protocol(request.protocol_id),
+        NetworkMessage::RpcResponse(response) => response_protocols
+            .lock()
+            .get(&response.request_id)
+            .map_or(true, |protocol_id| is_consensus_protocol(*protocol_id)),
+        NetworkMessage::DirectSendMsg(message) => is_consensus_protocol(message.protocol_id),
+    }
+}
+
+/// Returns the protocol with the given name (if one exists)
+fn protocol_from_name(name: &str) -> Option<ProtocolId> {
+    ProtocolId::all()
+        .iter()
+        .find(|protocol_id| protocol_id.as_str() == name)
+        .copied()
+}
+
+/// A simple token bucket (in bytes) used to limit the outbound bandwidth of a protocol
+struct TokenBucket {
+    /// The number of available tokens (negative if the last message overdrew the bucket)
+    tokens: i64,
+    /// The maximum number of tokens in the bucket
+    size: i64,
+    /// The fill rate of the bucket (tokens per second)
+    rate: u64,
+    /// The last time the bucket was refilled
+    last_refill_time: Instant,
+}
+
+impl TokenBucket {
+    fn new(size: usize, rate: usize, now: Instant) -> Self {
+        Self {
+            tokens: size as i64,
+            size: max(size, 1) as i64,
+            rate: max(rate, 1) as u64,
+            last_refill_time: now,
+        }
+    }
+
+    /// Refills the bucket based on the time elapsed since the last refill
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill_time);
+        let new_tokens = elapsed.as_micros() * self.rate as u128 / 1_000_000;
+        if new_tokens > 0 {
+            let new_tokens = min(new_tokens, self.size as u128) as i64;
+            self.tokens = min(self.size, self.tokens.saturating_add(new_tokens));
+            self.last_refill_time = now;
+        }
+    }
+
+    /// Returns the time to wait until a message of the given size can be
+    /// written, or `None` if it can be written immediately. Messages larger
+    /// than the bucket only require a full bucket (and overdraw it).
+    fn time_until_available(&self, num_bytes: usize) -> Option<Duration> {
+        let required_tokens = min(num_bytes as i64, self.size);
+        if self.tokens >= required_tokens {
+            None
+        } else {
+            let missing_tokens = (required_tokens - self.tokens) as u64;
+            let wait_micros = missing_tokens.saturating_mul(1_000_000) / self.rate;
+            Some(Duration::from_micros(wait_micros + 1))
+        }
+    }
+
+    fn consume(&mut self, num_bytes: usize) {
+        self.tokens = self.tokens.saturating_sub(num_bytes as i64);
+    }
+}
+
+/// A message buffered by the shaper
+struct BufferedMessage {
+    message: MultiplexMessage,
+    protocol_id: Option<ProtocolId>,
+    num_bytes: usize,
+    sequence_number: u64,
+    buffered_time: Instant,
+}
+
+/// The queue a candidate message is taken from
+#[derive(Clone, Copy)]
+enum QueueId {
+    Message(ProtocolId),
+    Stream,
+}
+
+/// Buffers outbound messages and decides which message to write next
+pub struct OutboundTrafficShaper {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    protocol_priorities: HashMap<ProtocolId, u8>,
+    default_priority: u8,
+    token_buckets: HashMap<ProtocolId, TokenBucket>,
+    response_protocols: ResponseProtocols,
+    /// Messages without a known protocol, e.g., error messages (always written first)
+    unclassified_queue: VecDeque<BufferedMessage>,
+    /// Non-streamed messages, buffered per protocol
+    message_queues: HashMap<ProtocolId, VecDeque<BufferedMessage>>,
+    /// Stream headers and fragments, in the order they must be written
+    stream_queue: VecDeque<BufferedMessage>,
+    /// The protocol of the last buffered stream header
+    current_stream_protocol: Option<ProtocolId>,
+    num_buffered_messages: usize,
+    next_sequence_number: u64,
+    /// True iff the priority channel has closed
+    priority_channel_closed: bool,
+    /// True iff the (non-priority) message channels have closed
+    channels_closed: bool,
+}
+
+impl OutboundTrafficShaper {
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        config: &OutboundTrafficShapingConfig,
+        response_protocols: ResponseProtocols,
+    ) -> Self {
+        let mut protocol_priorities = HashMap::new();
+        for (protocol_name, priority) in &config.protocol_priorities {
+            match protocol_from_name(protocol_name) {
+                Some(protocol_id) => {
+                    protocol_priorities.insert(protocol_id, *priority);
+                },
+                None => warn!(
+                    "{} Ignoring the priority of unknown protocol: {}",
+                    network_context, protocol_name
+                ),
+            }
+        }
+
+        let now = time_service.now();
+        let mut token_buckets = HashMap::new();
+        for (protocol_name, limit) in &config.protocol_bandwidth_limits {
+            match protocol_from_name(protocol_name) {
+                Some(protocol_id) if is_consensus_protocol(protocol_id) => warn!(
+                    "{} Ignoring the bandwidth limit of consensus protocol: {}",
+                    network_context, protocol_name
+                ),
+                Some(protocol_id) => {
+                    let bucket =
+                        TokenBucket::new(limit.byte_bucket_size, limit.byte_bucket_rate, now);
+                    token_buckets.insert(protocol_id, bucket);
+                },
+                None => warn!(
+                    "{} Ignoring the bandwidth limit of unknown protocol: {}",
+                    network_context, protocol_name
+                ),
+            }
+        }
+
+        Self {
+            network_context,
+            time_service,
+            protocol_priorities,
+            default_priority: config.default_priority,
+            token_buckets,
+            response_protocols,
+            unclassified_queue: VecDeque::new(),
+            message_queues: HashMap::new(),
+            stream_queue: VecDeque::new(),
+            current_stream_protocol: None,
+            num_buffered_messages: 0,
+            next_sequence_number: 0,
+            priority_channel_closed: false,
+            channels_closed: false,
+        }
+    }
+
+    /// Returns the priority class of the given protocol. Consensus is always
+    /// served first, so no other protocol may share its priority class.
+    fn priority(&self, protocol_id: Option<ProtocolId>) -> u8 {
+        match protocol_id {
+            None => CONSENSUS_PRIORITY,
+            Some(protocol_id) if is_consensus_protocol(protocol_id) => CONSENSUS_PRIORITY,
+            Some(protocol_id) => {
+                let priority = self
+                    .protocol_priorities
+                    .get(&protocol_id)
+                    .copied()
+                    .unwrap_or(self.default_priority);
+                max(priority, CONSENSUS_PRIORITY + 1)
+            },
+        }
+    }
+
+    pub fn num_buffered_messages(&self) -> usize {
+        self.num_buffered_messages
+    }
+
+    pub fn response_protocols(&self) -> ResponseProtocols {
+        self.response_protocols.clone()
+    }
+
+    /// Returns the protocol of the given message (if it is known)
+    fn message_protocol(&self, message: &NetworkMessage) -> Option<ProtocolId> {
+        match message {
+            NetworkMessage::Error(_) => None,
+            NetworkMessage::RpcRequest(request) => Some(request.protocol_id),
+            NetworkMessage::RpcResponse(response) => {
+                self.response_protocols.lock().remove(&response.request_id)
+            },
+            NetworkMessage::DirectSendMsg(message) => Some(message.protocol_id),
+        }
+    }
+
+    /// Buffers the given message until it is selected for writing
+    pub fn push(&mut self, message: MultiplexMessage) {
+        let (protocol_id, num_bytes, is_stream) = match &message {
+            MultiplexMessage::Message(message) => {
+                (self.message_protocol(message), message.data_len(), false)
+            },
+            MultiplexMessage::Stream(StreamMessage::Header(header)) => {
+                self.current_stream_protocol = self.message_protocol(&header.message);
+                (
+                    self.current_stream_protocol,
+                    header.message.data_len(),
+                    true,
+                )
+            },
+            MultiplexMessage::Stream(StreamMessage::Fragment(fragment)) => {
+                (self.current_stream_protocol, fragment.raw_data.len(), true)
+            },
+        };
+
+        let buffered_message = BufferedMessage {
+            message,
+            protocol_id,
+            num_bytes,
+            sequence_number: self.next_sequence_number,
+            buffered_time: self.time_service.now(),
+        };
+        self.next_sequence_number += 1;
+        self.num_buffered_messages += 1;
+
+        match (is_stream, protocol_id) {
+            (true, _) => self.stream_queue.push_back(buffered_message),
+            (false, Some(protocol_id)) => self
+                .message_queues
+                .entry(protocol_id)
+                .or_insert_with(VecDeque::new)
+                .push_back(buffered_message),
+            (false, None) => self.unclassified_queue.push_back(buffered_message),
+        }
+    }
+
+    /// Removes and returns the next message to write. Returns `Ok(None)` if no
+    /// messages are buffered, and `Err(delay)` if every buffered message is
+    /// currently held back by a bandwidth limit (and `delay` is the time until
+    /// the first of them becomes available).
+    pub fn pop(&mut self) -> Result<Option<MultiplexMessage>, Duration> {
+        if let Some(buffered_message) = self.unclassified_queue.pop_front() {
+            return Ok(Some(self.take_message(buffered_message)));
+        }
+
+        let now = self.time_service.now();
+        for token_bucket in self.token_buckets.values_mut() {
+            token_bucket.refill(now);
+        }
+
+        // Identify the oldest available message of the highest priority class
+        let candidates = self
+            .message_queues
+            .iter()
+            .filter_map(|(protocol_id, queue)| {
+                queue
+                    .front()
+                    .map(|message| (QueueId::Message(*protocol_id), message))
+            })
+            .chain(
+                self.stream_queue
+                    .front()
+                    .map(|message| (QueueId::Stream, message)),
+            );
+        let mut selected_candidate: Option<(u8, u64, QueueId)> = None;
+        let mut min_wait_time: Option<Duration> = None;
+        for (queue_id, message) in candidates {
+            let wait_time = message.protocol_id.and_then(|protocol_id| {
+                self.token_buckets
+                    .get(&protocol_id)
+                    .and_then(|bucket| bucket.time_until_available(message.num_bytes))
+            });
+            if let Some(wait_time) = wait_time {
+                min_wait_time = Some(min_wait_time.map_or(wait_time, |min| min.min(wait_time)));
+                continue;
+            }
+
+            let candidate = (
+                self.priority(message.protocol_id),
+                message.sequence_number,
+                queue_id,
+            );
+            if selected_candidate.map_or(true, |(priority, sequence_number, _)| {
+                (candidate.0, candidate.1) < (priority, sequence_number)
+            }) {
+                selected_candidate = Some(candidate);
+            }
+        }
+
+        let buffered_message = match selected_candidate {
+            Some((_, _, QueueId::Message(protocol_id))) => self
+                .message_queues
+                .get_mut(&protocol_id)
+                .and_then(|queue| queue.pop_front()),
+            Some((_, _, QueueId::Stream)) => self.stream_queue.pop_front(),
+            None => {
+                return match min_wait_time {
+                    Some(wait_time) => Err(wait_time),
+                    None => Ok(None),
+                };
+            },
+        };
+        Ok(buffered_message.map(|buffered_message| self.take_message(buffered_message)))
+    }
+
+    /// Updates the bandwidth limit and metrics for a message about to be written
+    fn take_message(&mut self, buffered_message: BufferedMessage) -> MultiplexMessage {
+        self.num_buffered_messages -= 1;
+        if let Some(protocol_id) = buffered_message.protocol_id {
+            if let Some(token_bucket) = self.token_buckets.get_mut(&protocol_id) {
+                token_bucket.consume(buffered_message.num_bytes);
+            }
+            let queue_delay = self
+                .time_service
+                .now()
+                .saturating_duration_since(buffered_message.buffered_time);
+            counters::outbound_queue_delay(&self.network_context, protocol_id, queue_delay);
+        }
+        buffered_message.message
+    }
+
+    /// Returns the next message that should be written to the wire, waiting
+    /// for new messages (or bandwidth) as required. Returns `None` once all of
+    /// the given message channels have closed. Any messages still held back by
+    /// a bandwidth limit at that point are dropped.
+    ///
+    /// Messages on the priority channel are buffered regardless of the buffer
+    /// limit, as they are never held back by a bandwidth limit.
+    pub async fn next_message<P, S>(
+        &mut self,
+        priority_messages: &mut P,
+        messages: &mut S,
+    ) -> Option<MultiplexMessage>
+    where
+        P: Stream<Item = MultiplexMessage> + Unpin,
+        S: Stream<Item = MultiplexMessage> + Unpin,
+    {
+        loop {
+            // Buffer all messages that are immediately available
+            while !self.priority_channel_closed {
+                match priority_messages.next().now_or_never() {
+                    Some(Some(message)) => self.push(message),
+                    Some(None) => self.priority_channel_closed = true,
+                    None => break,
+                }
+            }
+            while !self.channels_closed && self.num_buffered_messages < MAX_BUFFERED_MESSAGES {
+                match messages.next().now_or_never() {
+                    Some(Some(message)) => self.push(message),
+                    Some(None) => self.channels_closed = true,
+                    None => break,
+                }
+            }
+
+            let all_channels_closed = self.priority_channel_closed && self.channels_closed;
+            match self.pop() {
+                Ok(Some(message)) => return Some(message),
+                Ok(None) => {
+                    if all_channels_closed {
+                        return None;
+                    }
+                    // Nothing is buffered, so wait for the next message
+                    self.wait_for_messages(priority_messages, messages, None)
+                        .await;
+                },
+                Err(wait_time) => {
+                    if all_channels_closed {
+                        self.drop_buffered_messages();
+                        return None;
+                    }
+                    self.record_throttled_messages();
+
+                    // Wait for bandwidth to become available (or a new message to arrive)
+                    self.wait_for_messages(priority_messages, messages, Some(wait_time))
+                        .await;
+                },
+            }
+        }
+    }
+
+    /// Waits until a message arrives on any open channel (and buffers it), or
+    /// until the given wait time has elapsed. The non-priority channels are only
+    /// polled while the buffer has room, so that back pressure is preserved.
+    async fn wait_for_messages<P, S>(
+        &mut self,
+        priority_messages: &mut P,
+        messages: &mut S,
+        wait_time: Option<Duration>,
+    ) where
+        P: Stream<Item = MultiplexMessage> + Unpin,
+        S: Stream<Item = MultiplexMessage> + Unpin,
+    {
+        let sleep = match wait_time {
+            Some(wait_time) => Either::Left(self.time_service.sleep(wait_time)),
+            None => Either::Right(future::pending::<()>()),
+        };
+        let priority_message = if self.priority_channel_closed {
+            Either::Left(future::pending::<Option<MultiplexMessage>>())
+        } else {
+            Either::Right(priority_messages.next())
+        };
+        let has_capacity = self.num_buffered_messages < MAX_BUFFERED_MESSAGES;
+        let message = if self.channels_closed || !has_capacity {
+            Either::Left(future::pending::<Option<MultiplexMessage>>())
+        } else {
+            Either::Right(messages.next())
+        };
+        pin_mut!(sleep, priority_message, message);
+
+        match future::select(sleep, future::select(priority_message, message)).await {
+            Either::Left(_) => {},
+            Either::Right((Either::Left((Some(message), _)), _)) => self.push(message),
+            Either::Right((Either::Left((None, _)), _)) => self.priority_channel_closed = true,
+            Either::Right((Either::Right((Some(message), _)), _)) => self.push(message),
+            Either::Right((Either::Right((None, _)), _)) => self.channels_closed = true,
+        }
+    }
+
+    /// Drops all buffered messages (e.g., because the connection is closing),
+    /// recording how many messages of each protocol were dropped.
+    fn drop_buffered_messages(&mut self) {
+        let mut num_dropped_messages = 0;
+        let dropped_messages = self
+            .message_queues
+            .values_mut()
+            .flat_map(|queue| queue.drain(..))
+            .chain(self.stream_queue.drain(..))
+            .chain(self.unclassified_queue.drain(..));
+        for buffered_message in dropped_messages {
+            num_dropped_messages += 1;
+            if let Some(protocol_id) = buffered_message.protocol_id {
+                counters::outbound_dropped_messages(&self.network_context, protocol_id).inc();
+            }
+        }
+        self.num_buffered_messages = 0;
+
+        if num_dropped_messages > 0 {
+            warn!(
+                "{} Dropped {} throttled outbound messages as the writer is closing",
+                self.network_context, num_dropped_messages
+            );
+        }
+    }
+
+    fn record_throttled_messages(&self) {
+        for protocol_id in self
+            .message_queues
+            .iter()
+            .filter(|(_, queue)| !queue.is_empty())
+            .map(|(protocol_id, _)| *protocol_id)
+            .chain(
+                self.stream_queue
+                    .front()
+                    .and_then(|message| message.protocol_id),
+            )
+        {
+            counters::outbound_throttled_messages(&self.network_context, protocol_id).inc();
+        }
+    }
+}
@@ -51,7 +51,7 @@ use crate::{
         RESPONSE_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
-    peer::PeerNotification,
+    peer::{traffic_shaping::ResponseProtocols, PeerNotification},
     protocols::{
         network::SerializedRequest,
         wire::messaging::v1::{NetworkMessage, Priority, RequestId, RpcRequest, RpcResponse},
@@ -183,6 +183,9 @@ pub struct InboundRpcs {
     /// Only allow this many concurrent inbound rpcs at one time from this remote
     /// peer.  New inbound requests exceeding this limit will be dropped.
     max_concurrent_inbound_rpcs: u32,
+    /// If outbound traffic shaping is enabled, the protocols of completed inbound
+    /// requests are recorded here so the writer can classify their responses.
+    response_protocols: Option<ResponseProtocols>,
 }
 
 impl InboundRpcs {
@@ -192,6 +195,7 @@ impl InboundRpcs {
         remote_peer_id: PeerId,
         inbound_rpc_timeout: Duration,
         max_concurrent_inbound_rpcs: u32,
+        response_protocols: Option<ResponseProtocols>,
     ) -> Self {
         Self {
             network_context,
@@ -200,6 +204,7 @@ impl InboundRpcs {
             inbound_rpc_tasks: FuturesUnordered::new(),
             inbound_rpc_timeout,
             max_concurrent_inbound_rpcs,
+            response_protocols,
         }
     }
 
@@ -252,6 +257,7 @@ impl InboundRpcs {
         }
 
         // Create a new task that waits for a response from the upper layer with a timeout.
+        let response_protocols = self.response_protocols.clone();
         let inbound_rpc_task = self
             .time_service
             .timeout(self.inbound_rpc_timeout, response_rx)
@@ -269,7 +275,12 @@ impl InboundRpcs {
                 };
                 // Only record latency of successful requests
                 match maybe_response {
-                    Ok(_) => timer.stop_and_record(),
+                    Ok(_) => {
+                        timer.stop_and_record();
+                        if let Some(response_protocols) = &response_protocols {
+                            response_protocols.lock().insert(request_id, protocol_id);
+                        }
+                    },
                     Err(_) => timer.stop_and_discard(),
                 };
                 maybe_response
@@ -318,8 +329,15 @@ impl InboundRpcs {
             self.remote_peer_id.short_str(),
             response.request_id,
         );
+        let request_id = response.request_id;
         let message = NetworkMessage::RpcResponse(response);
-        write_reqs_tx.send(message).await?;
+        if let Err(err) = write_reqs_tx.send(message).await {
+            // The response will never reach the writer, so forget its protocol
+            if let Some(response_protocols) = &self.response_protocols {
+                response_protocols.lock().remove(&request_id);
+            }
+            return Err(err.into());
+        }
 
         // Collect counters for sent response.
         counters::rpc_messages(network_context, RESPONSE_LABEL, SENT_LABEL).inc();
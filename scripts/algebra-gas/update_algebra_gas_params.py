@@ -18,6 +18,9 @@ from time import time
 # so this should be larger than `LATEST_GAS_FEATURE_VERSION` in `aptos-move/aptos-gas/src/gas_meter.rs`.
 TARGET_GAS_VERSION = 8
 
+# The BN254 parameters were introduced in a later gas version than the BLS12-381 ones.
+BN254_TARGET_GAS_VERSION = 9
+
 def get_bench_ns_linear(bench_path):
     datapoints = load_bench_datapoints.main(bench_path)
     X,Y,k,b = fit_linear_model.main(datapoints)
@@ -95,6 +98,13 @@ def get_algebra_lines(gas_per_ns):
     lines = [f'    [.algebra.{k}, {{ {TARGET_GAS_VERSION}.. => "algebra.{k}" }}, {prettify_number(v)} * MUL],' for k,v in sorted(gas_units.items())]
     return lines
 
+def get_bn254_algebra_lines(gas_per_ns):
+    '''Only the BN254 parameters that are benchmarked, keyed by parameter name.'''
+    nanoseconds = {}
+    _,_,nanoseconds['ark_h2c_bn254g1_xmd_sha256_svdw_per_msg_byte'],nanoseconds['ark_h2c_bn254g1_xmd_sha256_svdw_base'] = get_bench_ns_linear('target/criterion/ark_bn254/hash_to_g1_proj')
+    gas_units = {k:ceil(gas_per_ns*v) for k,v in nanoseconds.items()}
+    return {k:f'    [.algebra.{k}, {{ {BN254_TARGET_GAS_VERSION}.. => "algebra.{k}" }}, {prettify_number(v)} * MUL],' for k,v in gas_units.items()}
+
 def main(gas_per_ns):
     path = Path('aptos-move/aptos-gas/src/aptos_framework.rs')
     lines = path.read_text().split('\n')
@@ -102,6 +112,13 @@ def main(gas_per_ns):
     line_id_end = lines.index('    // Algebra gas parameters end.')
     generator_note_line = f'    // Generated at time {time()} by `scripts/algebra-gas/update_algebra_gas_params.py` with gas_per_ns={gas_per_ns}.'
     new_lines = lines[:line_id_begin+1] + [generator_note_line] + get_algebra_lines(gas_per_ns) + lines[line_id_end:]
+
+    # The BN254 section is only partially benchmarked, so the benchmarked lines are replaced in place.
+    bn254_lines = get_bn254_algebra_lines(gas_per_ns)
+    for i, line in enumerate(new_lines):
+        for k, new_line in bn254_lines.items():
+            if line.startswith(f'    [.algebra.{k},'):
+                new_lines[i] = new_line
     path.write_text('\n'.join(new_lines))
 
 if __name__=='__main__':
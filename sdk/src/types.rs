@@ -5,12 +5,19 @@
 use crate::{
     crypto::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        traits::Uniform,
+        secp256k1_ecdsa::{Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey},
+        secp256r1_ecdsa::{Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature},
+        traits::{signing_message, Uniform},
+        HashValue,
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey},
+            webauthn::{generate_challenge, PartialAuthenticatorAssertionResponse},
+            RawTransaction, SignedTransaction,
+        },
     },
 };
 use anyhow::Result;
@@ -211,6 +218,152 @@ impl From<Ed25519PrivateKey> for AccountKey {
     }
 }
 
+/// A local account whose transactions are signed with a secp256k1 ECDSA key (e.g., a key
+/// shared with an Ethereum or Bitcoin wallet).
+#[derive(Debug)]
+pub struct Secp256k1EcdsaLocalAccount {
+    /// Address of the account.
+    address: AccountAddress,
+    private_key: Secp256k1EcdsaPrivateKey,
+    public_key: Secp256k1EcdsaPublicKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl Secp256k1EcdsaLocalAccount {
+    /// Create a new representation of an account locally, using the address derived
+    /// from the authentication key of the given private key.
+    pub fn new(private_key: Secp256k1EcdsaPrivateKey, sequence_number: u64) -> Self {
+        let public_key = Secp256k1EcdsaPublicKey::from(&private_key);
+        let address = AuthenticationKey::secp256k1_ecdsa(&public_key).derived_address();
+        Self {
+            address,
+            private_key,
+            public_key,
+            sequence_number,
+        }
+    }
+
+    /// Generate a new account locally. Note: This function does not actually
+    /// create an account on the Aptos blockchain, it just generates a new
+    /// account locally.
+    pub fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        Self::new(Secp256k1EcdsaPrivateKey::generate(rng), 0)
+    }
+
+    pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
+        txn.sign_secp256k1_ecdsa(&self.private_key, self.public_key.clone())
+            .expect("Signing a txn can't fail")
+            .into_inner()
+    }
+
+    pub fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> SignedTransaction {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        self.sign_transaction(raw_txn)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn public_key(&self) -> &Secp256k1EcdsaPublicKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::secp256k1_ecdsa(&self.public_key)
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
+
+/// An account whose transactions are signed by a WebAuthn authenticator (e.g., a passkey).
+/// The private key never leaves the authenticator, so signing happens in two steps:
+/// (1) the challenge for the transaction (see `challenge`) is passed to the authenticator
+/// (e.g., via `navigator.credentials.get()`); and (2) the returned assertion is used to
+/// create the signed transaction (see `signed_transaction`).
+#[derive(Debug)]
+pub struct WebAuthnAccount {
+    /// Address of the account.
+    address: AccountAddress,
+    public_key: Secp256r1EcdsaPublicKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl WebAuthnAccount {
+    /// Create a new representation of an account locally, using the address derived
+    /// from the authentication key of the given (authenticator) public key.
+    pub fn new(public_key: Secp256r1EcdsaPublicKey, sequence_number: u64) -> Self {
+        let address = AuthenticationKey::webauthn(&public_key).derived_address();
+        Self {
+            address,
+            public_key,
+            sequence_number,
+        }
+    }
+
+    /// Returns the challenge that the authenticator must sign for the given transaction
+    pub fn challenge(&self, txn: &RawTransaction) -> Result<HashValue> {
+        Ok(generate_challenge(&signing_message(txn)?))
+    }
+
+    /// Creates a signed transaction from the assertion returned by the authenticator. The
+    /// signature is expected to be ASN.1 DER encoded (as returned by authenticators).
+    pub fn signed_transaction(
+        &self,
+        txn: RawTransaction,
+        der_signature: &[u8],
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Result<SignedTransaction> {
+        let signature = Secp256r1EcdsaSignature::from_der_with_normalized_s(der_signature)?;
+        let assertion = PartialAuthenticatorAssertionResponse::new(
+            signature,
+            authenticator_data,
+            client_data_json,
+        );
+        let authenticator = AccountAuthenticator::webauthn(self.public_key.clone(), assertion);
+        Ok(SignedTransaction::new_single_sender(txn, authenticator))
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn public_key(&self) -> &Secp256r1EcdsaPublicKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::webauthn(&self.public_key)
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,4 +387,23 @@ mod tests {
         // Return an error for empty mnemonic phrase.
         assert!(LocalAccount::from_derive_path(derive_path, "", 0).is_err());
     }
+
+    #[test]
+    fn test_secp256k1_ecdsa_local_account_signs_transactions() {
+        let mut account = Secp256k1EcdsaLocalAccount::generate(&mut rand::rngs::OsRng);
+        assert_eq!(
+            account.address(),
+            account.authentication_key().derived_address()
+        );
+
+        // Sign a transaction and verify the signature and sequence number
+        let transaction_factory = crate::transaction_builder::TransactionFactory::new(
+            crate::types::chain_id::ChainId::test(),
+        );
+        let signed_txn = account
+            .sign_with_transaction_builder(transaction_factory.transfer(AccountAddress::ONE, 100));
+        assert_eq!(signed_txn.sender(), account.address());
+        assert!(signed_txn.signature_is_valid());
+        assert_eq!(account.sequence_number(), 1);
+    }
 }
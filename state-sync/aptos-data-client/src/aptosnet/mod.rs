@@ -24,8 +24,9 @@ use aptos_network::{application::interface::NetworkClient, protocols::rpc::error
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
-        NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
+        AccountStateValuesWithProofRequest, DataRequest, EpochEndingLedgerInfoRequest,
+        NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
+        NewTransactionsWithProofRequest, StateValuesByKeysWithProofRequest,
         StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
@@ -34,15 +35,23 @@ use aptos_storage_service_types::{
 };
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
+    account_address::AccountAddress,
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueChunkWithProof, StateValueListWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
-use futures::StreamExt;
-use rand::seq::SliceRandom;
-use std::{convert::TryFrom, fmt, sync::Arc, time::Duration};
+use futures::{future, StreamExt};
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    Rng,
+};
+use std::{cmp::max, convert::TryFrom, fmt, sync::Arc, time::Duration};
 use tokio::{runtime::Handle, task::JoinHandle};
 
 mod logging;
@@ -89,6 +98,8 @@ pub struct AptosNetDataClient {
     global_summary_cache: Arc<RwLock<GlobalDataSummary>>,
     /// Used for generating the next request/response id.
     response_id_generator: Arc<U64IdGenerator>,
+    /// Used to measure the response time of requests.
+    time_service: TimeService,
 }
 
 impl AptosNetDataClient {
@@ -109,6 +120,7 @@ impl AptosNetDataClient {
             ))),
             global_summary_cache: Arc::new(RwLock::new(GlobalDataSummary::empty())),
             response_id_generator: Arc::new(U64IdGenerator::new()),
+            time_service: time_service.clone(),
         };
         let poller = DataSummaryPoller::new(
             client.clone(),
@@ -171,6 +183,17 @@ impl AptosNetDataClient {
         &self,
         request: &StorageServiceRequest,
     ) -> Result<PeerNetworkId, Error> {
+        let peers = self.choose_peers_for_request(request, 1)?;
+        Ok(peers[0])
+    }
+
+    /// Choose up to `num_peers` distinct connected peers that can service
+    /// the given request. Returns an error if no such peer can be found.
+    fn choose_peers_for_request(
+        &self,
+        request: &StorageServiceRequest,
+        num_peers: usize,
+    ) -> Result<Vec<PeerNetworkId>, Error> {
         // All requests should be sent to prioritized peers (if possible).
         // If none can handle the request, fall back to the regular peers.
         let (priority_peers, regular_peers) = self.get_priority_and_regular_peers()?;
@@ -181,15 +204,56 @@ impl AptosNetDataClient {
             self.identify_serviceable(regular_peers, request)
         };
 
-        // Randomly select a peer to handle the request
-        serviceable_peers
-            .choose(&mut rand::thread_rng())
-            .copied()
-            .ok_or_else(|| {
-                Error::DataIsUnavailable(
-                    format!("No connected peers are advertising that they can serve this data! Request: {:?}",request),
-                )
-            })
+        // Ensure at least one peer can handle the request
+        if serviceable_peers.is_empty() {
+            return Err(Error::DataIsUnavailable(format!(
+                "No connected peers are advertising that they can serve this data! Request: {:?}",
+                request
+            )));
+        }
+
+        // Randomly select the peers to handle the request
+        Ok(self.select_peers(serviceable_peers, num_peers))
+    }
+
+    /// Randomly selects (without replacement) up to `num_peers` of the given
+    /// peers. If weighted peer selection is enabled, each peer is selected with
+    /// a probability proportional to its selection weight (see
+    /// `PeerStates::calculate_selection_weights`). Otherwise, peers are
+    /// selected uniformly at random.
+    fn select_peers(&self, peers: Vec<PeerNetworkId>, num_peers: usize) -> Vec<PeerNetworkId> {
+        let mut rng = rand::thread_rng();
+        if !self.data_client_config.enable_weighted_peer_selection {
+            return peers
+                .choose_multiple(&mut rng, num_peers)
+                .copied()
+                .collect();
+        }
+
+        // Select the peers one at a time using their weights
+        let weights = self.peer_states.read().calculate_selection_weights(&peers);
+        let mut weighted_peers: Vec<(PeerNetworkId, f64)> =
+            peers.into_iter().zip(weights).collect();
+        let mut selected_peers = vec![];
+        while selected_peers.len() < num_peers && !weighted_peers.is_empty() {
+            let index = match WeightedIndex::new(weighted_peers.iter().map(|(_, weight)| *weight)) {
+                Ok(weighted_index) => weighted_index.sample(&mut rng),
+                Err(error) => {
+                    warn!(
+                        (LogSchema::new(LogEntry::StorageServiceRequest)
+                            .event(LogEvent::PeerSelectionError)
+                            .message(&format!(
+                                "Invalid peer selection weights, selecting uniformly! Error: {:?}",
+                                error
+                            )))
+                    );
+                    rng.gen_range(0, weighted_peers.len())
+                },
+            };
+            let (peer, _) = weighted_peers.swap_remove(index);
+            selected_peers.push(peer);
+        }
+        selected_peers
     }
 
     /// Identifies the peers in the given set of prospective peers
@@ -301,7 +365,18 @@ impl AptosNetDataClient {
         Ok((priority_peers, regular_peers))
     }
 
-    /// Sends a request (to an undecided peer) and decodes the response
+    /// Returns the number of peers the given request should be sent to.
+    /// Subscription requests are latency critical, so they may be sent to
+    /// multiple peers (in which case, the first valid response is used).
+    fn get_num_peers_for_request(&self, request: &StorageServiceRequest) -> usize {
+        if request.data_request.is_data_subscription_request() {
+            max(self.data_client_config.num_peers_for_subscriptions, 1) as usize
+        } else {
+            1
+        }
+    }
+
+    /// Sends a request (to undecided peers) and decodes the response
     async fn send_request_and_decode<T, E>(
         &self,
         request: StorageServiceRequest,
@@ -311,18 +386,31 @@ impl AptosNetDataClient {
         T: TryFrom<StorageServiceResponse, Error = E>,
         E: Into<Error>,
     {
-        let peer = self.choose_peer_for_request(&request).map_err(|error| {
-            debug!(
-                (LogSchema::new(LogEntry::StorageServiceRequest)
-                    .event(LogEvent::PeerSelectionError)
-                    .message("Unable to select peer")
-                    .error(&error))
-            );
-            error
-        })?;
-        let _timer = start_request_timer(&metrics::REQUEST_LATENCIES, &request.get_label(), peer);
-        self.send_request_to_peer_and_decode(peer, request, request_timeout_ms)
-            .await
+        let num_peers = self.get_num_peers_for_request(&request);
+        let peers = self
+            .choose_peers_for_request(&request, num_peers)
+            .map_err(|error| {
+                debug!(
+                    (LogSchema::new(LogEntry::StorageServiceRequest)
+                        .event(LogEvent::PeerSelectionError)
+                        .message("Unable to select peer")
+                        .error(&error))
+                );
+                error
+            })?;
+
+        // Send the request to each peer and return the first valid response
+        let pending_responses = peers.into_iter().map(|peer| {
+            let request = request.clone();
+            Box::pin(async move {
+                let _timer =
+                    start_request_timer(&metrics::REQUEST_LATENCIES, &request.get_label(), peer);
+                self.send_request_to_peer_and_decode(peer, request, request_timeout_ms)
+                    .await
+            })
+        });
+        let (response, _) = future::select_ok(pending_responses).await?;
+        Ok(response)
     }
 
     /// Sends a request to a specific peer and decodes the response
@@ -387,6 +475,7 @@ impl AptosNetDataClient {
         increment_request_counter(&metrics::SENT_REQUESTS, &request.get_label(), peer);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -415,6 +504,39 @@ impl AptosNetDataClient {
                 // feels simpler for the consumer.
                 self.peer_states.write().update_score_success(peer);
 
+                // Record the response throughput. Summary and version requests are
+                // too small to be meaningful, and subscriptions wait for new data.
+                // The serialized size is used, so that compressed and uncompressed
+                // responses are measured in the same way.
+                let data_request = &request.data_request;
+                if !data_request.is_storage_summary_request()
+                    && !data_request.is_protocol_version_request()
+                    && !data_request.is_data_subscription_request()
+                {
+                    let response_time = self
+                        .time_service
+                        .now()
+                        .saturating_duration_since(request_start_time);
+                    match bcs::serialized_size(&response) {
+                        Ok(num_bytes) => self.peer_states.write().update_response_throughput(
+                            peer,
+                            num_bytes as u64,
+                            response_time,
+                        ),
+                        Err(error) => warn!(
+                            (LogSchema::new(LogEntry::StorageServiceResponse)
+                                .event(LogEvent::ResponseError)
+                                .request_type(&request.get_label())
+                                .request_id(id)
+                                .peer(&peer)
+                                .message(&format!(
+                                    "Failed to measure the response size: {:?}",
+                                    error
+                                )))
+                        ),
+                    }
+                }
+
                 // Package up all of the context needed to fully report an error
                 // with this RPC.
                 let response_callback = AptosNetResponseCallback {
@@ -506,6 +628,23 @@ impl AptosDataClient for AptosNetDataClient {
         self.global_summary_cache.read().clone()
     }
 
+    async fn get_account_state_values_with_proof(
+        &self,
+        version: Version,
+        account: AccountAddress,
+        start_key: Option<StateKey>,
+        request_timeout_ms: u64,
+    ) -> Result<Response<StateValueListWithProof>> {
+        let data_request =
+            DataRequest::GetAccountStateValuesWithProof(AccountStateValuesWithProofRequest {
+                version,
+                account,
+                start_key,
+            });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_epoch_ending_ledger_infos(
         &self,
         start_epoch: Epoch,
@@ -583,6 +722,21 @@ impl AptosDataClient for AptosNetDataClient {
             .await
     }
 
+    async fn get_state_values_by_keys_with_proof(
+        &self,
+        version: Version,
+        state_keys: Vec<StateKey>,
+        request_timeout_ms: u64,
+    ) -> Result<Response<StateValueListWithProof>> {
+        let data_request =
+            DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+                version,
+                state_keys,
+            });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_state_values_with_proof(
         &self,
         version: u64,
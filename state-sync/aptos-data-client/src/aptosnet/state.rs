@@ -20,6 +20,7 @@ use std::{
     cmp::min,
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::Duration,
 };
 
 /// Scores for peer rankings based on preferences and behavior.
@@ -35,6 +36,13 @@ const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
 
+/// The weight of new throughput observations in the moving average.
+const THROUGHPUT_SMOOTHING_FACTOR: f64 = 0.2;
+/// The selection component assigned to peers with unknown latency or throughput.
+const UNKNOWN_METRIC_COMPONENT: f64 = 0.5;
+/// The minimum selection weight, so that every serviceable peer can be selected.
+const MIN_SELECTION_WEIGHT: f64 = 0.01;
+
 pub(crate) enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
     /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
@@ -62,6 +70,9 @@ pub struct PeerState {
     storage_summary: Option<StorageServerSummary>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// The moving average of the observed response throughput (bytes per second),
+    /// or `None` if we haven't received any data responses yet.
+    response_throughput: Option<f64>,
 }
 
 impl Default for PeerState {
@@ -69,6 +80,7 @@ impl Default for PeerState {
         Self {
             storage_summary: None,
             score: STARTING_SCORE,
+            response_throughput: None,
         }
     }
 }
@@ -101,6 +113,22 @@ impl PeerState {
         };
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
+
+    /// Updates the throughput moving average using a new response observation
+    fn update_response_throughput(&mut self, num_bytes: u64, response_time: Duration) {
+        let response_time_secs = f64::max(response_time.as_secs_f64(), f64::EPSILON);
+        let throughput = num_bytes as f64 / response_time_secs;
+        self.response_throughput = Some(match self.response_throughput {
+            Some(average) => average + THROUGHPUT_SMOOTHING_FACTOR * (throughput - average),
+            None => throughput,
+        });
+    }
+
+    #[cfg(test)]
+    /// Returns the response throughput moving average for test purposes
+    pub fn get_response_throughput(&self) -> Option<f64> {
+        self.response_throughput
+    }
 }
 
 /// Contains all of the unbanned peers' most recent [`StorageServerSummary`] data
@@ -287,6 +315,93 @@ impl PeerStates {
         false
     }
 
+    /// Records the throughput of a data response sent by the given peer
+    pub fn update_response_throughput(
+        &mut self,
+        peer: PeerNetworkId,
+        num_bytes: u64,
+        response_time: Duration,
+    ) {
+        self.peer_to_state
+            .entry(peer)
+            .or_default()
+            .update_response_throughput(num_bytes, response_time);
+    }
+
+    /// Returns the average ping latency of the given peer (as measured by
+    /// the peer monitoring service), if one is known.
+    fn get_average_ping_latency_secs(&self, peer: &PeerNetworkId) -> Option<f64> {
+        self.peers_and_metadata
+            .get_metadata_for_peer(*peer)
+            .ok()
+            .and_then(|peer_metadata| {
+                peer_metadata
+                    .get_peer_monitoring_metadata()
+                    .average_ping_latency_secs
+            })
+            .filter(|latency| *latency > 0.0)
+    }
+
+    /// Calculates the selection weight of each of the given peers. The weight
+    /// combines the peer latency (relative to the lowest latency), the observed
+    /// throughput (relative to the highest throughput) and the peer score
+    /// (relative to the max score), each in the range (0, 1], using the relative
+    /// weights specified in the config.
+    pub fn calculate_selection_weights(&self, peers: &[PeerNetworkId]) -> Vec<f64> {
+        let latencies: Vec<Option<f64>> = peers
+            .iter()
+            .map(|peer| self.get_average_ping_latency_secs(peer))
+            .collect();
+        let throughputs: Vec<Option<f64>> = peers
+            .iter()
+            .map(|peer| {
+                self.peer_to_state
+                    .get(peer)
+                    .and_then(|peer_state| peer_state.response_throughput)
+                    .filter(|throughput| *throughput > 0.0)
+            })
+            .collect();
+        let min_latency = latencies.iter().flatten().copied().reduce(f64::min);
+        let max_throughput = throughputs.iter().flatten().copied().reduce(f64::max);
+
+        let latency_weight = self.data_client_config.latency_selection_weight as f64;
+        let throughput_weight = self.data_client_config.throughput_selection_weight as f64;
+        let score_weight = self.data_client_config.score_selection_weight as f64;
+        let total_weight = latency_weight + throughput_weight + score_weight;
+
+        peers
+            .iter()
+            .zip(latencies)
+            .zip(throughputs)
+            .map(|((peer, latency), throughput)| {
+                if total_weight == 0.0 {
+                    return 1.0; // All peers are equally likely to be selected
+                }
+
+                let latency_component = match (latency, min_latency) {
+                    (Some(latency), Some(min_latency)) => min_latency / latency,
+                    _ => UNKNOWN_METRIC_COMPONENT,
+                };
+                let throughput_component = match (throughput, max_throughput) {
+                    (Some(throughput), Some(max_throughput)) => throughput / max_throughput,
+                    _ => UNKNOWN_METRIC_COMPONENT,
+                };
+                let score = self
+                    .peer_to_state
+                    .get(peer)
+                    .map(|peer_state| peer_state.score)
+                    .unwrap_or(STARTING_SCORE);
+                let score_component = score / MAX_SCORE;
+
+                let weight = (latency_weight * latency_component
+                    + throughput_weight * throughput_component
+                    + score_weight * score_component)
+                    / total_weight;
+                f64::max(weight, MIN_SELECTION_WEIGHT)
+            })
+            .collect()
+    }
+
     /// Updates the storage summary for the given peer
     pub fn update_summary(&mut self, peer: PeerNetworkId, summary: StorageServerSummary) {
         self.peer_to_state
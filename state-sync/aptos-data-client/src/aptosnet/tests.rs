@@ -19,6 +19,7 @@ use aptos_network::{
     },
     transport::ConnectionMetadata,
 };
+use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_server::network::{NetworkRequest, ResponseSender};
 use aptos_storage_service_types::{
@@ -901,6 +902,143 @@ async fn all_peer_request_selection() {
     assert!(peer_for_request == priority_peer_1 || peer_for_request == priority_peer_2);
 }
 
+#[tokio::test]
+async fn subscription_request_multiple_peers() {
+    ::aptos_logger::Logger::init_for_testing();
+    let data_client_config = AptosDataClientConfig {
+        num_peers_for_subscriptions: 2,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add three priority peers and advertise the data for each
+    let known_version = 10000000;
+    let mut priority_peers = vec![];
+    for _ in 0..3 {
+        let priority_peer = mock_network.add_peer(true);
+        client.update_summary(priority_peer, mock_storage_summary(known_version));
+        priority_peers.push(priority_peer);
+    }
+
+    // Verify that subscription requests are sent to two distinct peers
+    let subscription_request = StorageServiceRequest::new(
+        DataRequest::GetNewTransactionsWithProof(NewTransactionsWithProofRequest {
+            known_version,
+            known_epoch: 10,
+            include_events: false,
+        }),
+        true,
+    );
+    let num_peers = client.get_num_peers_for_request(&subscription_request);
+    assert_eq!(num_peers, 2);
+    let selected_peers = client
+        .choose_peers_for_request(&subscription_request, num_peers)
+        .unwrap();
+    assert_eq!(selected_peers.len(), 2);
+    assert_ne!(selected_peers[0], selected_peers[1]);
+    for selected_peer in selected_peers {
+        assert!(priority_peers.contains(&selected_peer));
+    }
+
+    // Verify that all other requests are only sent to a single peer
+    let output_data_request =
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+        });
+    let storage_request = StorageServiceRequest::new(output_data_request, true);
+    assert_eq!(client.get_num_peers_for_request(&storage_request), 1);
+}
+
+#[tokio::test]
+async fn weighted_peer_selection_prefers_low_latency() {
+    ::aptos_logger::Logger::init_for_testing();
+    let data_client_config = AptosDataClientConfig {
+        enable_weighted_peer_selection: true,
+        latency_selection_weight: 1,
+        score_selection_weight: 0,
+        throughput_selection_weight: 0,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add two priority peers that advertise the same data
+    let fast_peer = mock_network.add_peer(true);
+    let slow_peer = mock_network.add_peer(true);
+    for peer in [fast_peer, slow_peer] {
+        client.update_summary(peer, mock_storage_summary(100));
+    }
+
+    // Update the peer latencies (the slow peer is 1000x slower)
+    for (peer, latency) in [(fast_peer, 0.01), (slow_peer, 10.0)] {
+        mock_network
+            .peers_and_metadata
+            .update_peer_monitoring_metadata(
+                peer,
+                PeerMonitoringMetadata::new(Some(latency), None, None),
+            )
+            .unwrap();
+    }
+
+    // Select a peer many times and verify the fast peer is strongly preferred
+    let output_data_request =
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+        });
+    let storage_request = StorageServiceRequest::new(output_data_request, true);
+    let num_selections = 1000;
+    let mut num_fast_peer_selections = 0;
+    for _ in 0..num_selections {
+        if client.choose_peer_for_request(&storage_request).unwrap() == fast_peer {
+            num_fast_peer_selections += 1;
+        }
+    }
+    assert!(num_fast_peer_selections > num_selections * 9 / 10);
+}
+
+#[tokio::test]
+async fn response_throughput_uses_serialized_size() {
+    ::aptos_logger::Logger::init_for_testing();
+    for use_compression in [true, false] {
+        let data_client_config = AptosDataClientConfig {
+            use_compression,
+            ..Default::default()
+        };
+        let (mut mock_network, mock_time, client, _) =
+            MockNetwork::new(None, Some(data_client_config), None);
+
+        // Add a peer that advertises the requested data
+        let peer = mock_network.add_peer(true);
+        client.update_summary(peer, mock_storage_summary(200));
+
+        // Create the response that the peer will send
+        let data_response =
+            DataResponse::TransactionsWithProof(TransactionListWithProof::new_empty());
+        let storage_response = StorageServiceResponse::new(data_response, use_compression).unwrap();
+        let num_bytes = bcs::serialized_size(&storage_response).unwrap();
+
+        // Handle the client's request, taking exactly one second to respond
+        tokio::spawn(async move {
+            let network_request = mock_network.next_request().await.unwrap();
+            mock_time.advance_async(Duration::from_secs(1)).await;
+            network_request.response_sender.send(Ok(storage_response));
+        });
+
+        // Send the request and verify the throughput is measured using the mock time
+        let request_timeout = client.data_client_config.response_timeout_ms;
+        client
+            .get_transactions_with_proof(100, 50, 100, false, request_timeout)
+            .await
+            .unwrap();
+        let peer_to_states = client.peer_states.read().get_peer_to_states();
+        let response_throughput = peer_to_states.get(&peer).unwrap().get_response_throughput();
+        assert_eq!(response_throughput, Some(num_bytes as f64));
+    }
+}
+
 #[tokio::test]
 async fn validator_peer_prioritization() {
     ::aptos_logger::Logger::init_for_testing();
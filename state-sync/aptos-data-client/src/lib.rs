@@ -8,8 +8,12 @@ use aptos_storage_service_types::{
     Epoch,
 };
 use aptos_types::{
+    account_address::AccountAddress,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueChunkWithProof, StateValueListWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
@@ -77,6 +81,20 @@ pub trait AptosDataClient {
     /// cached view of this data client's available data.
     fn get_global_data_summary(&self) -> GlobalDataSummary;
 
+    /// Fetches the state values (each with a proof) stored under the given
+    /// account at the specified version, starting at `start_key` (if
+    /// specified). In some cases, fewer state values may be returned (e.g.,
+    /// to tolerate network or chunk limits), in which case the next state
+    /// key to fetch is also returned. If the data cannot be fetched, an
+    /// error is returned.
+    async fn get_account_state_values_with_proof(
+        &self,
+        version: Version,
+        account: AccountAddress,
+        start_key: Option<StateKey>,
+        request_timeout_ms: u64,
+    ) -> Result<Response<StateValueListWithProof>>;
+
     /// Fetches the epoch ending ledger infos between start and end
     /// (inclusive). In some cases, fewer ledger infos may be returned (e.g.,
     /// to tolerate network or chunk limits). If the data cannot be fetched,
@@ -130,6 +148,18 @@ pub trait AptosDataClient {
         request_timeout_ms: u64,
     ) -> Result<Response<u64>>;
 
+    /// Fetches the state values (each with a proof) for the given state keys
+    /// at the specified version. In some cases, fewer state values may be
+    /// returned (e.g., to tolerate network or chunk limits), in which case the
+    /// next state key to fetch is also returned. If the data cannot be
+    /// fetched, an error is returned.
+    async fn get_state_values_by_keys_with_proof(
+        &self,
+        version: Version,
+        state_keys: Vec<StateKey>,
+        request_timeout_ms: u64,
+    ) -> Result<Response<StateValueListWithProof>>;
+
     /// Fetches a single state value chunk with proof, containing the values
     /// from start to end index (inclusive) at the specified version. The proof
     /// version is the same as the specified version. In some cases, fewer
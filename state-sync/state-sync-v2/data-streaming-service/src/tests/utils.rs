@@ -31,7 +31,7 @@ use aptos_types::{
     proof::SparseMerkleRangeProof,
     state_store::{
         state_key::StateKey,
-        state_value::{StateValue, StateValueChunkWithProof},
+        state_value::{StateValue, StateValueChunkWithProof, StateValueListWithProof},
     },
     transaction::{
         RawTransaction, Script, SignedTransaction, Transaction, TransactionListWithProof,
@@ -236,6 +236,25 @@ impl AptosDataClient for MockAptosDataClient {
         }
     }
 
+    async fn get_account_state_values_with_proof(
+        &self,
+        _version: Version,
+        _account: AccountAddress,
+        _start_key: Option<StateKey>,
+        _request_timeout_ms: u64,
+    ) -> Result<Response<StateValueListWithProof>, aptos_data_client::Error> {
+        unimplemented!("Account state value requests are not issued by the streaming service!")
+    }
+
+    async fn get_state_values_by_keys_with_proof(
+        &self,
+        _version: Version,
+        _state_keys: Vec<StateKey>,
+        _request_timeout_ms: u64,
+    ) -> Result<Response<StateValueListWithProof>, aptos_data_client::Error> {
+        unimplemented!("State value by key requests are not issued by the streaming service!")
+    }
+
     async fn get_state_values_with_proof(
         &self,
         version: Version,
@@ -0,0 +1,286 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use aptos_backup_cli::{
+    backup_types::{
+        epoch_ending::manifest::EpochEndingBackup,
+        state_snapshot::manifest::{StateSnapshotBackup, StateSnapshotChunk},
+    },
+    metadata::{cache, cache::MetadataCacheOpt, view::MetadataView},
+    storage::{
+        command_adapter::{config::CommandAdapterConfig, CommandAdapter},
+        local_fs::LocalFs,
+        BackupStorage, FileHandleRef,
+    },
+    utils::{read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt},
+};
+use aptos_config::config::{BackupStorageConfig, BackupStorageType};
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures, proof::SparseMerkleRangeProof,
+    state_store::state_value::StateValueChunkWithProof, transaction::Version,
+};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+/// A simple reader for the data (held in backup storage) that is required
+/// to bootstrap the node, i.e., epoch ending ledger infos and state snapshots.
+/// The data is not verified by the reader (this is done by the bootstrapper).
+pub struct BackupStorageReader {
+    // The backup storage to read from
+    backup_storage: Arc<dyn BackupStorage>,
+
+    // The view of all backups in the backup storage
+    metadata_view: MetadataView,
+
+    // The state snapshot manifest (and version) currently being read
+    state_snapshot: Option<(Version, StateSnapshotBackup)>,
+}
+
+impl BackupStorageReader {
+    /// Creates a new backup storage reader by syncing and loading the backup
+    /// metadata from the backup storage specified in the given config.
+    pub async fn new(backup_storage_config: &BackupStorageConfig) -> Result<Self, Error> {
+        // Create the backup storage
+        let backup_storage: Arc<dyn BackupStorage> = match &backup_storage_config.backup_storage {
+            Some(BackupStorageType::CommandAdapter(config_path)) => {
+                let config = CommandAdapterConfig::load_from_file(config_path)
+                    .await
+                    .map_err(|error| {
+                        Error::BackupStorageError(format!(
+                            "Failed to load the command adapter config: {:?}! Error: {:?}",
+                            config_path, error
+                        ))
+                    })?;
+                Arc::new(CommandAdapter::new(config))
+            },
+            Some(BackupStorageType::LocalFs(dir)) => Arc::new(LocalFs::new(dir.clone())),
+            None => {
+                return Err(Error::BackupStorageError(
+                    "No backup storage has been configured!".into(),
+                ))
+            },
+        };
+
+        // Sync and load the backup metadata
+        let metadata_cache_opt =
+            MetadataCacheOpt::new(backup_storage_config.metadata_cache_dir.clone());
+        let metadata_view = cache::sync_and_load(
+            &metadata_cache_opt,
+            backup_storage.clone(),
+            backup_storage_config.concurrent_downloads as usize,
+        )
+        .await
+        .map_err(|error| {
+            Error::BackupStorageError(format!(
+                "Failed to sync and load the backup metadata! Error: {:?}",
+                error
+            ))
+        })?;
+        if let Ok(backup_storage_state) = metadata_view.get_storage_state() {
+            info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                "Loaded the backup metadata! Backup storage state: {}",
+                backup_storage_state
+            )));
+        }
+
+        Ok(Self {
+            backup_storage,
+            metadata_view,
+            state_snapshot: None,
+        })
+    }
+
+    /// Returns all epoch ending ledger infos in backup storage that end
+    /// epochs greater than or equal to `start_epoch` (ordered by epoch).
+    pub async fn get_epoch_ending_ledger_infos(
+        &self,
+        start_epoch: u64,
+    ) -> Result<Vec<LedgerInfoWithSignatures>, Error> {
+        let epoch_ending_backups = self
+            .metadata_view
+            .select_epoch_ending_backups(Version::MAX)
+            .map_err(|error| {
+                Error::BackupStorageError(format!(
+                    "Failed to select the epoch ending backups! Error: {:?}",
+                    error
+                ))
+            })?;
+
+        let mut epoch_ending_ledger_infos = vec![];
+        for epoch_ending_backup in epoch_ending_backups {
+            if epoch_ending_backup.last_epoch < start_epoch {
+                continue; // We already have all epochs in this backup
+            }
+
+            // Load and verify the backup manifest
+            let manifest: EpochEndingBackup =
+                self.load_json_file(&epoch_ending_backup.manifest).await?;
+            manifest.verify().map_err(|error| {
+                Error::BackupStorageError(format!(
+                    "The epoch ending backup manifest is malformed: {:?}! Error: {:?}",
+                    epoch_ending_backup.manifest, error
+                ))
+            })?;
+
+            // Read the ledger infos in each chunk
+            for chunk in manifest.chunks {
+                if chunk.last_epoch < start_epoch {
+                    continue; // We already have all epochs in this chunk
+                }
+                let ledger_infos: Vec<LedgerInfoWithSignatures> =
+                    self.read_records(&chunk.ledger_infos).await?;
+                epoch_ending_ledger_infos.extend(
+                    ledger_infos
+                        .into_iter()
+                        .filter(|ledger_info| ledger_info.ledger_info().epoch() >= start_epoch),
+                );
+            }
+        }
+
+        Ok(epoch_ending_ledger_infos)
+    }
+
+    /// Returns the versions of all state snapshots in backup storage at or
+    /// below the given version (ordered from highest to lowest).
+    pub fn get_state_snapshot_versions(&self, max_version: Version) -> Vec<Version> {
+        let mut state_snapshot_versions = vec![];
+        let mut max_version = Some(max_version);
+        while let Some(version) = max_version {
+            match self.metadata_view.select_state_snapshot(version) {
+                Ok(Some(state_snapshot)) => {
+                    state_snapshot_versions.push(state_snapshot.version);
+                    max_version = state_snapshot.version.checked_sub(1);
+                },
+                _ => break,
+            }
+        }
+        state_snapshot_versions
+    }
+
+    /// Returns the state value chunk (of the state snapshot at the given
+    /// version) that contains the state value at the given index. The chunk
+    /// may include state values before the given index (these should be
+    /// ignored by the state snapshot receiver). Returns `None` if the index
+    /// is beyond the last state value in the snapshot.
+    pub async fn get_state_value_chunk(
+        &mut self,
+        version: Version,
+        state_index: u64,
+    ) -> Result<Option<StateValueChunkWithProof>, Error> {
+        // Load the state snapshot manifest (if we haven't already)
+        if !matches!(&self.state_snapshot, Some((snapshot_version, _)) if *snapshot_version == version)
+        {
+            let state_snapshot = self
+                .metadata_view
+                .expect_state_snapshot(version)
+                .map_err(|error| Error::BackupStorageError(error.to_string()))?;
+            let manifest: StateSnapshotBackup =
+                self.load_json_file(&state_snapshot.manifest).await?;
+            if manifest.version != version {
+                return Err(Error::BackupStorageError(format!(
+                    "The state snapshot manifest has an unexpected version! Expected: {:?}, found: {:?}",
+                    version, manifest.version
+                )));
+            }
+            self.state_snapshot = Some((version, manifest));
+        }
+        let (_, manifest) = self
+            .state_snapshot
+            .as_ref()
+            .expect("Manifest should exist!");
+
+        // Identify the chunk holding the state index (if any)
+        let chunk = match manifest
+            .chunks
+            .iter()
+            .find(|chunk| (chunk.last_idx as u64) >= state_index)
+        {
+            Some(chunk) => chunk,
+            None => return Ok(None), // All state values have already been read
+        };
+
+        // Read the chunk values and proof
+        self.read_state_value_chunk(chunk, manifest.root_hash)
+            .await
+            .map(Some)
+    }
+
+    /// Reads the given state snapshot chunk from backup storage
+    async fn read_state_value_chunk(
+        &self,
+        chunk: &StateSnapshotChunk,
+        root_hash: HashValue,
+    ) -> Result<StateValueChunkWithProof, Error> {
+        let raw_values = self.read_records(&chunk.blobs).await?;
+        let proof: SparseMerkleRangeProof = self.load_bcs_file(&chunk.proof).await?;
+        Ok(StateValueChunkWithProof {
+            first_index: chunk.first_idx as u64,
+            last_index: chunk.last_idx as u64,
+            first_key: chunk.first_key,
+            last_key: chunk.last_key,
+            raw_values,
+            proof,
+            root_hash,
+        })
+    }
+
+    /// Reads and deserializes all BCS records in the given file
+    async fn read_records<T: DeserializeOwned>(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<Vec<T>, Error> {
+        let mut file = self
+            .backup_storage
+            .open_for_read(file_handle)
+            .await
+            .map_err(|error| read_error(file_handle, error))?;
+
+        let mut records = vec![];
+        while let Some(record_bytes) = file
+            .read_record_bytes()
+            .await
+            .map_err(|error| read_error(file_handle, error))?
+        {
+            let record = bcs::from_bytes(&record_bytes)
+                .map_err(|error| read_error(file_handle, error.into()))?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Loads the given BCS file from backup storage
+    async fn load_bcs_file<T: DeserializeOwned>(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<T, Error> {
+        self.backup_storage
+            .load_bcs_file(file_handle)
+            .await
+            .map_err(|error| read_error(file_handle, error))
+    }
+
+    /// Loads the given JSON file from backup storage
+    async fn load_json_file<T: DeserializeOwned>(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<T, Error> {
+        self.backup_storage
+            .load_json_file(file_handle)
+            .await
+            .map_err(|error| read_error(file_handle, error))
+    }
+}
+
+/// Returns a backup storage error for a failed file read
+fn read_error(file_handle: &FileHandleRef, error: anyhow::Error) -> Error {
+    Error::BackupStorageError(format!(
+        "Failed to read the file: {:?} from backup storage! Error: {:?}",
+        file_handle, error
+    ))
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_storage::BackupStorageReader,
     driver::DriverConfiguration,
     error::Error,
     logging::{LogEntry, LogSchema},
@@ -284,6 +285,12 @@ pub struct Bootstrapper<MetadataStorage, StorageSyncer, StreamingClient> {
     // The currently active data stream (provided by the data streaming service)
     active_data_stream: Option<DataStreamListener>,
 
+    // The reader for backup storage (if bootstrapping from backups)
+    backup_storage_reader: Option<BackupStorageReader>,
+
+    // The notification ID to assign to the next chunk read from backup storage
+    next_backup_notification_id: NotificationId,
+
     // The channel used to notify a listener of successful bootstrapping
     bootstrap_notifier_channel: Option<oneshot::Sender<Result<(), Error>>>,
 
@@ -340,6 +347,8 @@ impl<
         Self {
             state_value_syncer: StateValueSyncer::new(),
             active_data_stream: None,
+            backup_storage_reader: None,
+            next_backup_notification_id: 0,
             bootstrap_notifier_channel: None,
             bootstrapped: false,
             driver_configuration,
@@ -453,9 +462,12 @@ impl<
 
         // Always fetch the new epoch ending ledger infos first
         if self.should_fetch_epoch_ending_ledger_infos() {
-            return self
-                .fetch_epoch_ending_ledger_infos(global_data_summary)
-                .await;
+            return if self.get_bootstrapping_mode().is_backup_sync() {
+                self.fetch_epoch_ending_ledger_infos_from_backup().await
+            } else {
+                self.fetch_epoch_ending_ledger_infos(global_data_summary)
+                    .await
+            };
         }
 
         // Get the highest synced and known ledger info versions
@@ -476,22 +488,16 @@ impl<
             highest_synced_version, highest_known_ledger_info, self.get_bootstrapping_mode())));
 
         // Bootstrap according to the mode
-        match self.get_bootstrapping_mode() {
-            BootstrappingMode::DownloadLatestStates => {
-                self.fetch_missing_state_snapshot_data(
-                    highest_synced_version,
-                    highest_known_ledger_info,
-                )
-                .await
-            },
-            _ => {
-                // We're either transaction or output syncing
-                self.fetch_missing_transaction_data(
-                    highest_synced_version,
-                    highest_known_ledger_info,
-                )
+        if self.get_bootstrapping_mode().is_fast_sync() {
+            self.fetch_missing_state_snapshot_data(
+                highest_synced_version,
+                highest_known_ledger_info,
+            )
+            .await
+        } else {
+            // We're either transaction or output syncing
+            self.fetch_missing_transaction_data(highest_synced_version, highest_known_ledger_info)
                 .await
-            },
         }
     }
 
@@ -513,6 +519,12 @@ impl<
                     )));
                 }
                 self.fetch_missing_state_values(target, true).await
+            } else if self.get_bootstrapping_mode().is_backup_sync() {
+                // No snapshot sync has started. Start a new sync for the latest backup snapshot.
+                let target = self
+                    .get_backup_state_snapshot_target(&highest_known_ledger_info)
+                    .await?;
+                self.fetch_missing_state_values(target, false).await
             } else {
                 // No snapshot sync has started. Start a new sync for the highest known ledger info.
                 self.fetch_missing_state_values(highest_known_ledger_info, false)
@@ -540,6 +552,15 @@ impl<
                 // continuous syncer will take control and get the node up-to-date. If this is a
                 // validator, consensus will take control and sync depending on how it sees fit.
                 self.bootstrapping_complete().await
+            } else if self.get_bootstrapping_mode().is_backup_sync() {
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "The node has synced the latest state snapshot in backup storage, but is still \
+                    {} versions behind. The remaining versions will be synced from peers.",
+                    num_versions_behind
+                )));
+                // The backups may trail the latest epoch ending ledger infos, so we let the
+                // continuous syncer (or consensus) sync the remaining versions from peers.
+                self.bootstrapping_complete().await
             } else {
                 panic!("Fast syncing is currently unsupported for nodes with existing state! \
                         You are currently {:?} versions behind the latest snapshot version ({:?}). Either \
@@ -673,6 +694,24 @@ impl<
                 0 // We need to start the snapshot sync from index 0
             };
 
+            // If we're bootstrapping from backups, read the next state value chunk
+            if self.get_bootstrapping_mode().is_backup_sync() {
+                // State value chunks are processed one at a time (with no stream resets),
+                // so the next index only needs to be recalculated after a reboot.
+                let next_state_index_to_process =
+                    if self.state_value_syncer.initialized_state_snapshot_receiver {
+                        self.state_value_syncer.next_state_index_to_process
+                    } else {
+                        next_state_index_to_process
+                    };
+                return self
+                    .fetch_state_values_from_backup(
+                        target_ledger_info_version,
+                        next_state_index_to_process,
+                    )
+                    .await;
+            }
+
             // Fetch the missing state values
             self.state_value_syncer
                 .update_next_state_index_to_process(next_state_index_to_process);
@@ -837,6 +876,144 @@ impl<
         Ok(())
     }
 
+    /// Fetches and verifies all epoch ending ledger infos held in backup
+    /// storage (from the current epoch onwards).
+    async fn fetch_epoch_ending_ledger_infos_from_backup(&mut self) -> Result<(), Error> {
+        // If our storage has already synced beyond our waypoint, the waypoint is verified
+        let latest_ledger_info = utils::fetch_latest_synced_ledger_info(self.storage.clone())?;
+        let waypoint_version = self.driver_configuration.waypoint.version();
+        if latest_ledger_info.ledger_info().version() >= waypoint_version {
+            self.verified_epoch_states.set_verified_waypoint();
+        }
+
+        // Read the epoch ending ledger infos from the current epoch onwards
+        let next_epoch = self.verified_epoch_states.latest_epoch_state.epoch;
+        let epoch_ending_ledger_infos = self
+            .get_backup_storage_reader()
+            .await?
+            .get_epoch_ending_ledger_infos(next_epoch)
+            .await?;
+        info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+            "Read {} epoch ending ledger infos from backup storage, starting at epoch: {}",
+            epoch_ending_ledger_infos.len(),
+            next_epoch
+        )));
+
+        // Verify the epoch change proofs, update our latest epoch state and
+        // verify our waypoint.
+        for epoch_ending_ledger_info in epoch_ending_ledger_infos {
+            self.verified_epoch_states.update_verified_epoch_states(
+                &epoch_ending_ledger_info,
+                &self.driver_configuration.waypoint,
+            )?;
+        }
+        if !self.verified_epoch_states.verified_waypoint() {
+            return Err(Error::BackupStorageError(format!(
+                "Our waypoint is unverified, but there are no higher epoch ending ledger infos in backup storage! Waypoint version: {:?}",
+                waypoint_version
+            )));
+        }
+        self.verified_epoch_states
+            .set_fetched_epoch_ending_ledger_infos();
+
+        Ok(())
+    }
+
+    /// Returns the epoch ending ledger info for the latest state snapshot in
+    /// backup storage that can be verified. Note: state snapshots are only
+    /// backed up at epoch ending versions.
+    async fn get_backup_state_snapshot_target(
+        &mut self,
+        highest_known_ledger_info: &LedgerInfoWithSignatures,
+    ) -> Result<LedgerInfoWithSignatures, Error> {
+        let highest_known_ledger_version = highest_known_ledger_info.ledger_info().version();
+        let state_snapshot_versions = self
+            .get_backup_storage_reader()
+            .await?
+            .get_state_snapshot_versions(highest_known_ledger_version);
+        state_snapshot_versions
+            .into_iter()
+            .find_map(|version| {
+                self.verified_epoch_states
+                    .get_epoch_ending_ledger_info(version)
+            })
+            .ok_or_else(|| {
+                Error::BackupStorageError(format!(
+                    "No state snapshot found in backup storage at a verified epoch ending version! Highest known version: {:?}",
+                    highest_known_ledger_version
+                ))
+            })
+    }
+
+    /// Reads the state value chunks from backup storage (starting at the chunk
+    /// holding the given index) and processes them. At most one batch of chunks
+    /// is processed each time this is called, so that the storage synchronizer
+    /// is never sent more chunks than it can buffer.
+    async fn fetch_state_values_from_backup(
+        &mut self,
+        target_ledger_info_version: Version,
+        next_state_index_to_process: u64,
+    ) -> Result<(), Error> {
+        let driver_config = self.driver_configuration.config;
+        let max_num_chunks = std::cmp::min(
+            driver_config.max_consecutive_stream_notifications,
+            driver_config.max_pending_data_chunks,
+        );
+
+        let mut next_state_index_to_process = next_state_index_to_process;
+        for _ in 0..max_num_chunks {
+            let state_value_chunk_with_proof = match self
+                .get_backup_storage_reader()
+                .await?
+                .get_state_value_chunk(target_ledger_info_version, next_state_index_to_process)
+                .await?
+            {
+                Some(state_value_chunk_with_proof) => state_value_chunk_with_proof,
+                None => {
+                    // All chunks have been read. We now wait for the storage
+                    // synchronizer to finish committing the state snapshot.
+                    sample!(
+                        SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
+                        info!(LogSchema::new(LogEntry::Bootstrapper).message(
+                            "All state value chunks have been read from backup storage! \
+                            Waiting for the state snapshot to be committed."
+                        ))
+                    );
+                    return Ok(());
+                },
+            };
+
+            // The chunk may start before the next index (e.g., when resuming a snapshot
+            // sync after a reboot). This is fine, because the state snapshot receiver
+            // skips any state values that have already been committed.
+            self.state_value_syncer
+                .update_next_state_index_to_process(state_value_chunk_with_proof.first_index);
+
+            // Process the chunk (as if it was received from a data stream)
+            let notification_id = self.next_backup_notification_id;
+            self.next_backup_notification_id = notification_id.checked_add(1).ok_or_else(|| {
+                Error::IntegerOverflow("The next backup notification ID has overflown!".into())
+            })?;
+            self.process_state_values_payload(notification_id, state_value_chunk_with_proof)
+                .await?;
+            next_state_index_to_process = self.state_value_syncer.next_state_index_to_process;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the backup storage reader (initializing it if required)
+    async fn get_backup_storage_reader(&mut self) -> Result<&mut BackupStorageReader, Error> {
+        if self.backup_storage_reader.is_none() {
+            let backup_storage_reader =
+                BackupStorageReader::new(&self.driver_configuration.backup_storage_config).await?;
+            self.backup_storage_reader = Some(backup_storage_reader);
+        }
+        self.backup_storage_reader
+            .as_mut()
+            .ok_or_else(|| Error::UnexpectedError("The backup storage reader is missing!".into()))
+    }
+
     /// Verifies that connected peers have advertised data beyond our waypoint
     /// or that our waypoint is trivially satisfiable.
     fn verify_waypoint_is_satisfiable(
@@ -927,9 +1104,7 @@ impl<
     ) -> Result<(), Error> {
         // Verify that we're expecting state value payloads
         let bootstrapping_mode = self.get_bootstrapping_mode();
-        if self.should_fetch_epoch_ending_ledger_infos()
-            || !matches!(bootstrapping_mode, BootstrappingMode::DownloadLatestStates)
-        {
+        if self.should_fetch_epoch_ending_ledger_infos() || !bootstrapping_mode.is_fast_sync() {
             self.reset_active_stream(Some(NotificationAndFeedback::new(
                 notification_id,
                 NotificationFeedback::InvalidPayloadData,
@@ -946,8 +1121,16 @@ impl<
 
         // Initialize the state value synchronizer (if not already done)
         if !self.state_value_syncer.initialized_state_snapshot_receiver {
-            // Fetch all verified epoch change proofs
-            let epoch_change_proofs = self.verified_epoch_states.all_epoch_ending_ledger_infos();
+            // Fetch all verified epoch change proofs. If we're bootstrapping from
+            // backups, the snapshot may trail the epoch ending ledger infos, so
+            // we only include the proofs up to the snapshot version.
+            let mut epoch_change_proofs =
+                self.verified_epoch_states.all_epoch_ending_ledger_infos();
+            if bootstrapping_mode.is_backup_sync() {
+                let target_version = ledger_info_to_sync.ledger_info().version();
+                epoch_change_proofs
+                    .retain(|ledger_info| ledger_info.ledger_info().version() <= target_version);
+            }
 
             // Initialize the state value synchronizer
             let _join_handle = self.storage_synchronizer.initialize_state_synchronizer(
@@ -1078,7 +1261,7 @@ impl<
         // Verify that we're expecting transaction or output payloads
         let bootstrapping_mode = self.get_bootstrapping_mode();
         if self.should_fetch_epoch_ending_ledger_infos()
-            || (matches!(bootstrapping_mode, BootstrappingMode::DownloadLatestStates)
+            || (bootstrapping_mode.is_fast_sync()
                 && self.state_value_syncer.transaction_output_to_sync.is_some())
         {
             self.reset_active_stream(Some(NotificationAndFeedback::new(
@@ -1092,7 +1275,7 @@ impl<
         }
 
         // If we're state syncing, we expect a single transaction info
-        if matches!(bootstrapping_mode, BootstrappingMode::DownloadLatestStates) {
+        if bootstrapping_mode.is_fast_sync() {
             return self
                 .verify_transaction_info_to_sync(
                     notification_id,
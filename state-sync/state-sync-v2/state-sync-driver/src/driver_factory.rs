@@ -105,6 +105,7 @@ impl DriverFactory {
         // Create the driver configuration
         let driver_configuration = DriverConfiguration::new(
             node_config.state_sync.state_sync_driver,
+            node_config.storage.backup_storage_config.clone(),
             node_config.base.role,
             waypoint,
         );
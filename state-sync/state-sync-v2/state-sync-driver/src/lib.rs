@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod backup_storage;
 mod bootstrapper;
 mod continuous_syncer;
 mod driver;
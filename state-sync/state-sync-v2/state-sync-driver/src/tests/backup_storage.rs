@@ -0,0 +1,185 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::backup_storage::BackupStorageReader;
+use aptos_backup_cli::{
+    backup_types::state_snapshot::manifest::{StateSnapshotBackup, StateSnapshotChunk},
+    storage::{
+        local_fs::LocalFs, BackupHandleRef, BackupStorage, FileHandle, ShellSafeName, TextLine,
+    },
+};
+use aptos_config::config::{BackupStorageConfig, BackupStorageType};
+use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    proof::SparseMerkleRangeProof,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
+};
+use claims::assert_none;
+use std::{str::FromStr, sync::Arc};
+use tokio::io::AsyncWriteExt;
+
+// Useful test constants
+const SNAPSHOT_EPOCH: u64 = 5;
+const SNAPSHOT_VERSION: Version = 1000;
+
+#[tokio::test]
+async fn test_read_state_snapshot_from_local_fs() {
+    // Create a local backup storage holding a state snapshot with two chunks
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let backup_storage: Arc<dyn BackupStorage> =
+        Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+    let state_values = create_state_values(4);
+    let root_hash = HashValue::random();
+    write_state_snapshot(&backup_storage, &state_values, root_hash).await;
+
+    // Create a backup storage reader for the local backup storage
+    let metadata_cache_dir = TempPath::new();
+    let backup_storage_config = BackupStorageConfig {
+        backup_storage: Some(BackupStorageType::LocalFs(backup_dir.path().to_path_buf())),
+        concurrent_downloads: 1,
+        metadata_cache_dir: Some(metadata_cache_dir.path().to_path_buf()),
+    };
+    let mut backup_storage_reader = BackupStorageReader::new(&backup_storage_config)
+        .await
+        .unwrap();
+
+    // Verify the state snapshot versions are found
+    assert_eq!(
+        backup_storage_reader.get_state_snapshot_versions(SNAPSHOT_VERSION + 1),
+        vec![SNAPSHOT_VERSION]
+    );
+    assert!(backup_storage_reader
+        .get_state_snapshot_versions(SNAPSHOT_VERSION - 1)
+        .is_empty());
+
+    // Verify each state index is served by the chunk that holds it
+    for (state_index, expected_chunk_index) in [(0, 0), (1, 0), (2, 1), (3, 1)] {
+        let state_value_chunk = backup_storage_reader
+            .get_state_value_chunk(SNAPSHOT_VERSION, state_index)
+            .await
+            .unwrap()
+            .unwrap();
+        let first_index = expected_chunk_index * 2;
+        assert_eq!(state_value_chunk.first_index, first_index as u64);
+        assert_eq!(state_value_chunk.last_index, (first_index + 1) as u64);
+        assert_eq!(
+            state_value_chunk.raw_values,
+            state_values[first_index..first_index + 2].to_vec()
+        );
+        assert_eq!(state_value_chunk.root_hash, root_hash);
+    }
+
+    // Verify no chunk is returned once all state values have been read
+    assert_none!(backup_storage_reader
+        .get_state_value_chunk(SNAPSHOT_VERSION, 4)
+        .await
+        .unwrap());
+}
+
+/// Creates the given number of test state values
+fn create_state_values(num_values: u8) -> Vec<(StateKey, StateValue)> {
+    (0..num_values)
+        .map(|index| {
+            (
+                StateKey::raw(vec![index]),
+                StateValue::from(vec![index; 10]),
+            )
+        })
+        .collect()
+}
+
+/// Writes a state snapshot (holding the given state values in chunks of two)
+/// and the corresponding metadata to the given backup storage.
+async fn write_state_snapshot(
+    backup_storage: &Arc<dyn BackupStorage>,
+    state_values: &[(StateKey, StateValue)],
+    root_hash: HashValue,
+) {
+    let backup_handle = backup_storage
+        .create_backup(&ShellSafeName::from_str("state_snapshot").unwrap())
+        .await
+        .unwrap();
+
+    // Write the chunks
+    let mut chunks = vec![];
+    for (chunk_index, chunk_values) in state_values.chunks(2).enumerate() {
+        let mut blobs = vec![];
+        for state_value in chunk_values {
+            let record = bcs::to_bytes(state_value).unwrap();
+            blobs.extend((record.len() as u32).to_be_bytes());
+            blobs.extend(record);
+        }
+        let blobs = write_file(
+            backup_storage,
+            &backup_handle,
+            &format!("{}.chunk", chunk_index),
+            &blobs,
+        )
+        .await;
+        let proof = write_file(
+            backup_storage,
+            &backup_handle,
+            &format!("{}.proof", chunk_index),
+            &bcs::to_bytes(&SparseMerkleRangeProof::new(vec![])).unwrap(),
+        )
+        .await;
+        chunks.push(StateSnapshotChunk {
+            first_idx: chunk_index * 2,
+            last_idx: chunk_index * 2 + chunk_values.len() - 1,
+            first_key: HashValue::random(),
+            last_key: HashValue::random(),
+            blobs,
+            proof,
+        });
+    }
+
+    // Write the manifest and the metadata
+    let proof = write_file(backup_storage, &backup_handle, "proof", &[]).await;
+    let manifest = StateSnapshotBackup {
+        version: SNAPSHOT_VERSION,
+        epoch: SNAPSHOT_EPOCH,
+        root_hash,
+        chunks,
+        proof,
+    };
+    let manifest = write_file(
+        backup_storage,
+        &backup_handle,
+        "state.manifest",
+        &serde_json::to_vec(&manifest).unwrap(),
+    )
+    .await;
+    let metadata = serde_json::json!({
+        "StateSnapshotBackup": {
+            "epoch": SNAPSHOT_EPOCH,
+            "version": SNAPSHOT_VERSION,
+            "manifest": manifest,
+        }
+    });
+    backup_storage
+        .save_metadata_line(
+            &ShellSafeName::from_str("state_snapshot.meta").unwrap(),
+            &TextLine::new(&metadata.to_string()).unwrap(),
+        )
+        .await
+        .unwrap();
+}
+
+/// Writes the given bytes to a new file in the given backup
+async fn write_file(
+    backup_storage: &Arc<dyn BackupStorage>,
+    backup_handle: &BackupHandleRef,
+    name: &str,
+    bytes: &[u8],
+) -> FileHandle {
+    let (file_handle, mut file) = backup_storage
+        .create_for_write(backup_handle, &ShellSafeName::from_str(name).unwrap())
+        .await
+        .unwrap();
+    file.write_all(bytes).await.unwrap();
+    file.shutdown().await.unwrap();
+    file_handle
+}
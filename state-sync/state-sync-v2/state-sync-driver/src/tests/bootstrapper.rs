@@ -35,6 +35,78 @@ use futures::{channel::oneshot, FutureExt, SinkExt};
 use mockall::{predicate::eq, Sequence};
 use std::{sync::Arc, time::Duration};
 
+#[tokio::test]
+async fn test_backup_sync_lag() {
+    // Create test data
+    let num_versions_behind = 10000;
+    let highest_version = 1000000;
+    let synced_version = highest_version - num_versions_behind;
+    let highest_ledger_info = create_random_epoch_ending_ledger_info(highest_version, 1);
+
+    // Create a driver configuration with a genesis waypoint and backup syncing
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode =
+        BootstrappingMode::DownloadLatestStatesFromBackup;
+    driver_configuration
+        .config
+        .num_versions_to_skip_snapshot_sync = num_versions_behind;
+
+    // Create the mock streaming client
+    let mock_streaming_client = create_mock_streaming_client();
+
+    // Create the mock metadata storage
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_snapshot_sync_target()
+        .returning(|| Ok(None));
+
+    // Create the bootstrapper
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        mock_streaming_client,
+        metadata_storage,
+        synced_version,
+        true,
+    );
+
+    // Insert an epoch ending ledger info into the verified states of the bootstrapper
+    manipulate_verified_epoch_states(&mut bootstrapper, true, true, Some(highest_version));
+
+    // Create a global data summary
+    let mut global_data_summary = create_global_summary(1);
+    global_data_summary.advertised_data.synced_ledger_infos = vec![highest_ledger_info.clone()];
+
+    // Drive progress to mark bootstrapping complete (the remaining versions are synced from peers)
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+
+    // Verify the bootstrapper has completed
+    assert!(bootstrapper.is_bootstrapped());
+}
+
+#[tokio::test]
+async fn test_backup_sync_missing_storage() {
+    // Create a driver configuration with backup syncing (but no backup storage)
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode =
+        BootstrappingMode::DownloadLatestStatesFromBackup;
+
+    // Create the mock streaming client
+    let mock_streaming_client = create_mock_streaming_client();
+
+    // Create the bootstrapper
+    let (mut bootstrapper, _) =
+        create_bootstrapper(driver_configuration, mock_streaming_client, None, true);
+
+    // Drive progress and verify we get a backup storage error
+    let global_data_summary = create_global_summary(1);
+    let error = drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::BackupStorageError(_));
+}
+
 #[tokio::test]
 async fn test_bootstrap_genesis_waypoint() {
     // Create a driver configuration with a genesis waypoint
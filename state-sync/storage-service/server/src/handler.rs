@@ -5,8 +5,9 @@ use crate::{
     error::Error,
     logging::{LogEntry, LogSchema},
     metrics,
-    metrics::{increment_counter, start_timer, LRU_CACHE_HIT, LRU_CACHE_PROBE},
+    metrics::{increment_counter, start_timer},
     network::ResponseSender,
+    response_cache::ResponseCache,
     storage::StorageReaderInterface,
     subscription::DataSubscriptionRequest,
 };
@@ -16,9 +17,10 @@ use aptos_logger::{debug, error, sample, sample::SampleRate};
 use aptos_network::ProtocolId;
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest,
-        StorageServiceRequest, TransactionOutputsWithProofRequest,
-        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+        AccountStateValuesWithProofRequest, DataRequest, EpochEndingLedgerInfoRequest,
+        StateValuesByKeysWithProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+        TransactionsWithProofRequest,
     },
     responses::{
         DataResponse, ServerProtocolVersion, StorageServerSummary, StorageServiceResponse,
@@ -27,7 +29,6 @@ use aptos_storage_service_types::{
 };
 use aptos_time_service::TimeService;
 use aptos_types::transaction::Version;
-use lru::LruCache;
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 /// Storage server constants.
@@ -41,7 +42,7 @@ const SUMMARY_LOG_FREQUENCY_SECS: u64 = 5;
 pub struct Handler<T> {
     cached_storage_server_summary: Arc<RwLock<StorageServerSummary>>,
     data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
-    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    response_cache: Arc<ResponseCache>,
     storage: T,
     time_service: TimeService,
 }
@@ -50,7 +51,7 @@ impl<T: StorageReaderInterface> Handler<T> {
     pub fn new(
         cached_storage_server_summary: Arc<RwLock<StorageServerSummary>>,
         data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
-        lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+        response_cache: Arc<ResponseCache>,
         storage: T,
         time_service: TimeService,
     ) -> Self {
@@ -58,7 +59,7 @@ impl<T: StorageReaderInterface> Handler<T> {
             storage,
             cached_storage_server_summary,
             data_subscriptions,
-            lru_response_cache,
+            response_cache,
             time_service,
         }
     }
@@ -120,7 +121,7 @@ impl<T: StorageReaderInterface> Handler<T> {
                 StorageServiceResponse::new(data_response, request.use_compression)
                     .map_err(|error| error.into())
             },
-            _ => self.process_cachable_request(protocol, &request),
+            _ => self.process_cachable_request(&request),
         };
 
         // Process the response and handle any errors
@@ -191,15 +192,11 @@ impl<T: StorageReaderInterface> Handler<T> {
     /// might already be cached.
     fn process_cachable_request(
         &self,
-        protocol: ProtocolId,
         request: &StorageServiceRequest,
     ) -> aptos_storage_service_types::Result<StorageServiceResponse, Error> {
-        increment_counter(&metrics::LRU_CACHE_EVENT, protocol, LRU_CACHE_PROBE.into());
-
         // Check if the response is already in the cache
-        if let Some(response) = self.lru_response_cache.lock().get(request) {
-            increment_counter(&metrics::LRU_CACHE_EVENT, protocol, LRU_CACHE_HIT.into());
-            return Ok(response.clone());
+        if let Some(response) = self.response_cache.get(request) {
+            return Ok(response);
         }
 
         // Fetch the data response from storage
@@ -222,6 +219,12 @@ impl<T: StorageReaderInterface> Handler<T> {
             DataRequest::GetTransactionsOrOutputsWithProof(request) => {
                 self.get_transactions_or_outputs_with_proof(request)
             },
+            DataRequest::GetAccountStateValuesWithProof(request) => {
+                self.get_account_state_values_with_proof(request)
+            },
+            DataRequest::GetStateValuesByKeysWithProof(request) => {
+                self.get_state_values_by_keys_with_proof(request)
+            },
             _ => Err(Error::UnexpectedErrorEncountered(format!(
                 "Received an unexpected request: {:?}",
                 request
@@ -230,10 +233,7 @@ impl<T: StorageReaderInterface> Handler<T> {
         let storage_response = StorageServiceResponse::new(data_response, request.use_compression)?;
 
         // Cache the response before returning
-        let _ = self
-            .lru_response_cache
-            .lock()
-            .put(request.clone(), storage_response.clone());
+        self.response_cache.insert(request, &storage_response);
 
         Ok(storage_response)
     }
@@ -253,6 +253,21 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_account_state_values_with_proof(
+        &self,
+        request: &AccountStateValuesWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let state_value_list_with_proof = self.storage.get_account_state_values_with_proof(
+            request.version,
+            request.account,
+            request.start_key.clone(),
+        )?;
+
+        Ok(DataResponse::StateValueListWithProof(
+            state_value_list_with_proof,
+        ))
+    }
+
     fn get_epoch_ending_ledger_infos(
         &self,
         request: &EpochEndingLedgerInfoRequest,
@@ -285,6 +300,19 @@ impl<T: StorageReaderInterface> Handler<T> {
         DataResponse::StorageServerSummary(storage_server_summary)
     }
 
+    fn get_state_values_by_keys_with_proof(
+        &self,
+        request: &StateValuesByKeysWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let state_value_list_with_proof = self
+            .storage
+            .get_state_values_by_keys_with_proof(request.version, request.state_keys.clone())?;
+
+        Ok(DataResponse::StateValueListWithProof(
+            state_value_list_with_proof,
+        ))
+    }
+
     fn get_transaction_outputs_with_proof(
         &self,
         request: &TransactionOutputsWithProofRequest,
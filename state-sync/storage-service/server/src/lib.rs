@@ -13,16 +13,15 @@ use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
 use aptos_storage_service_types::{
-    requests::StorageServiceRequest,
-    responses::{ProtocolMetadata, StorageServerSummary, StorageServiceResponse},
+    responses::{ProtocolMetadata, StorageServerSummary},
     Result, StorageServiceError,
 };
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use error::Error;
 use futures::stream::StreamExt;
 use handler::Handler;
-use lru::LruCache;
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use response_cache::ResponseCache;
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 use storage::StorageReaderInterface;
 use subscription::DataSubscriptionRequest;
 use thiserror::Error;
@@ -33,6 +32,7 @@ mod handler;
 mod logging;
 pub mod metrics;
 pub mod network;
+mod response_cache;
 pub mod storage;
 mod subscription;
 
@@ -55,10 +55,10 @@ pub struct StorageServiceServer<T> {
     // A set of active subscriptions for peers waiting for new data
     data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
 
-    // An LRU cache for commonly requested data items.
+    // A (byte bounded) cache for commonly requested data items.
     // Note: This is not just a database cache because it contains
     // responses that have already been serialized and compressed.
-    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    response_cache: Arc<ResponseCache>,
 }
 
 impl<T: StorageReaderInterface> StorageServiceServer<T> {
@@ -68,14 +68,23 @@ impl<T: StorageReaderInterface> StorageServiceServer<T> {
         storage: T,
         time_service: TimeService,
         network_requests: StorageServiceNetworkEvents,
+        response_cache_dir: Option<PathBuf>,
     ) -> Self {
+        if config.max_lru_cache_size.is_some() {
+            warn!(LogSchema::new(LogEntry::ResponseCache).message(
+                "The max_lru_cache_size config is deprecated and will be ignored! \
+                The response cache is now configured using the response_cache config."
+            ));
+        }
+
         let bounded_executor =
             BoundedExecutor::new(config.max_concurrent_requests as usize, executor);
         let cached_storage_server_summary = Arc::new(RwLock::new(StorageServerSummary::default()));
         let data_subscriptions = Arc::new(Mutex::new(HashMap::new()));
-        let lru_response_cache = Arc::new(Mutex::new(LruCache::new(
-            config.max_lru_cache_size as usize,
-        )));
+        let response_cache = Arc::new(ResponseCache::new(
+            config.response_cache,
+            response_cache_dir,
+        ));
 
         Self {
             config,
@@ -85,7 +94,7 @@ impl<T: StorageReaderInterface> StorageServiceServer<T> {
             time_service,
             cached_storage_server_summary,
             data_subscriptions,
-            lru_response_cache,
+            response_cache,
         }
     }
 
@@ -130,7 +139,7 @@ impl<T: StorageReaderInterface> StorageServiceServer<T> {
         let cached_storage_server_summary = self.cached_storage_server_summary.clone();
         let config = self.config;
         let data_subscriptions = self.data_subscriptions.clone();
-        let lru_response_cache = self.lru_response_cache.clone();
+        let response_cache = self.response_cache.clone();
         let storage = self.storage.clone();
         let time_service = self.time_service.clone();
 
@@ -151,7 +160,7 @@ impl<T: StorageReaderInterface> StorageServiceServer<T> {
                         cached_storage_server_summary.clone(),
                         config,
                         data_subscriptions.clone(),
-                        lru_response_cache.clone(),
+                        response_cache.clone(),
                         storage.clone(),
                         time_service.clone(),
                     )
@@ -187,14 +196,14 @@ impl<T: StorageReaderInterface> StorageServiceServer<T> {
             let storage = self.storage.clone();
             let cached_storage_server_summary = self.cached_storage_server_summary.clone();
             let data_subscriptions = self.data_subscriptions.clone();
-            let lru_response_cache = self.lru_response_cache.clone();
+            let response_cache = self.response_cache.clone();
             let time_service = self.time_service.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
                     Handler::new(
                         cached_storage_server_summary,
                         data_subscriptions,
-                        lru_response_cache,
+                        response_cache,
                         storage,
                         time_service,
                     )
@@ -32,6 +32,7 @@ impl<'a> LogSchema<'a> {
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
     ReceivedStorageRequest,
+    ResponseCache,
     SentStorageResponse,
     StorageServiceError,
     StorageSummaryRefresh,
@@ -3,21 +3,42 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    register_histogram_vec, register_int_counter_vec, HistogramTimer, HistogramVec, IntCounterVec,
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    HistogramTimer, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
 };
 use aptos_network::ProtocolId;
 use once_cell::sync::Lazy;
 
 /// Useful metric constants for the storage service
-pub const LRU_CACHE_HIT: &str = "lru_cache_hit";
-pub const LRU_CACHE_PROBE: &str = "lru_cache_probe";
+pub const CACHE_DISK_HIT: &str = "cache_disk_hit";
+pub const CACHE_MEMORY_HIT: &str = "cache_memory_hit";
+pub const CACHE_PROBE: &str = "cache_probe";
 
-/// Counter for lru cache events in the storage service (server-side)
-pub static LRU_CACHE_EVENT: Lazy<IntCounterVec> = Lazy::new(|| {
+/// Counter for response cache events in the storage service (server-side)
+pub static RESPONSE_CACHE_EVENT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
-        "aptos_storage_service_server_lru_cache",
-        "Counters for lru cache events in the storage server",
-        &["protocol", "event"]
+        "aptos_storage_service_server_response_cache",
+        "Counters for response cache events in the storage server",
+        &["request_type", "event"]
+    )
+    .unwrap()
+});
+
+/// Gauge for the number of bytes held by the in-memory response caches
+pub static RESPONSE_MEMORY_CACHE_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_storage_service_server_response_memory_cache_bytes",
+        "Gauge for the number of bytes held by the in-memory response caches",
+        &["request_type"]
+    )
+    .unwrap()
+});
+
+/// Gauge for the number of bytes held by the on-disk response cache
+pub static RESPONSE_DISK_CACHE_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_service_server_response_disk_cache_bytes",
+        "Gauge for the number of bytes held by the on-disk response cache"
     )
     .unwrap()
 });
@@ -83,6 +104,25 @@ pub static STORAGE_REQUEST_PROCESSING_LATENCY: Lazy<HistogramVec> = Lazy::new(||
     .unwrap()
 });
 
+/// Increments the response cache event counter for the given request type
+pub fn increment_cache_event(request_type: &str, event: &str) {
+    RESPONSE_CACHE_EVENT
+        .with_label_values(&[request_type, event])
+        .inc()
+}
+
+/// Sets the number of bytes held by the in-memory cache for the given request type
+pub fn set_memory_cache_bytes(request_type: &str, num_bytes: u64) {
+    RESPONSE_MEMORY_CACHE_BYTES
+        .with_label_values(&[request_type])
+        .set(num_bytes as i64)
+}
+
+/// Sets the value of the given gauge
+pub fn set_gauge(gauge: &Lazy<IntGauge>, value: u64) {
+    gauge.set(value as i64)
+}
+
 /// Increments the network frame overflow counter for the given response
 pub fn increment_network_frame_overflow(response_type: &str) {
     NETWORK_FRAME_OVERFLOW
@@ -0,0 +1,501 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+    metrics,
+};
+use aptos_config::config::ResponseCacheConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_storage_service_types::{
+    requests::{DataRequest, StorageServiceRequest},
+    responses::StorageServiceResponse,
+};
+use lru::LruCache;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// The max number of responses waiting to be written to the disk cache
+const MAX_PENDING_DISK_WRITES: usize = 100;
+
+// The frequency (secs) at which to log dropped disk cache writes
+const DROPPED_DISK_WRITE_LOG_FREQ_SECS: u64 = 10;
+
+// The file extension used for temporary (partially written) cache files
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+
+// A response (and request) waiting to be written to the disk cache
+type PendingDiskWrite = (StorageServiceRequest, StorageServiceResponse);
+
+/// A cache for storage server responses (i.e., responses that have already
+/// been serialized and compressed). The cache is bounded by bytes (not items),
+/// and each request type has its own budget, e.g., so that large state value
+/// chunks don't evict small (but frequently requested) responses. State value
+/// chunks can also be stored on disk, so that they survive restarts. Disk
+/// writes are handled by a dedicated thread (off the request path).
+pub struct ResponseCache {
+    config: ResponseCacheConfig,
+    disk_cache: Option<Arc<DiskCache>>,
+    disk_writer: Option<DiskCacheWriter>,
+    memory_caches: Mutex<HashMap<&'static str, MemoryCache>>,
+}
+
+impl ResponseCache {
+    /// Creates a new response cache. If a disk cache directory is provided
+    /// (and the disk cache is enabled), any previously cached state value
+    /// chunks are loaded from the directory.
+    pub fn new(config: ResponseCacheConfig, disk_cache_dir: Option<PathBuf>) -> Self {
+        let disk_cache = match disk_cache_dir {
+            Some(disk_cache_dir) if config.enable_state_values_disk_cache => {
+                match DiskCache::new(disk_cache_dir.clone(), config.max_disk_cache_bytes) {
+                    Ok(disk_cache) => Some(disk_cache),
+                    Err(error) => {
+                        error!(LogSchema::new(LogEntry::ResponseCache)
+                            .error(&error)
+                            .message(&format!(
+                                "Failed to initialize the disk cache at: {:?}. Only the memory cache will be used!",
+                                disk_cache_dir
+                            )));
+                        None
+                    },
+                }
+            },
+            _ => None,
+        }
+        .map(Arc::new);
+        let disk_writer = disk_cache.clone().map(DiskCacheWriter::new);
+
+        Self {
+            config,
+            disk_cache,
+            disk_writer,
+            memory_caches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached response for the given request (if one exists)
+    pub fn get(&self, request: &StorageServiceRequest) -> Option<StorageServiceResponse> {
+        let request_label = request.data_request.get_label();
+        metrics::increment_cache_event(request_label, metrics::CACHE_PROBE);
+
+        // Check the memory cache
+        if let Some(response) = self
+            .memory_caches
+            .lock()
+            .get_mut(request_label)
+            .and_then(|memory_cache| memory_cache.get(request))
+        {
+            metrics::increment_cache_event(request_label, metrics::CACHE_MEMORY_HIT);
+            return Some(response);
+        }
+
+        // Check the disk cache
+        if let Some(disk_cache) = self.get_disk_cache(&request.data_request) {
+            if let Some(response) = disk_cache.get(request) {
+                metrics::increment_cache_event(request_label, metrics::CACHE_DISK_HIT);
+                self.insert_into_memory(request, &response);
+                return Some(response);
+            }
+        }
+
+        None
+    }
+
+    /// Inserts the given response into the cache. Responses that should be
+    /// cached on disk are written asynchronously by the disk cache writer.
+    pub fn insert(&self, request: &StorageServiceRequest, response: &StorageServiceResponse) {
+        self.insert_into_memory(request, response);
+        if self.get_disk_cache(&request.data_request).is_some() {
+            if let Some(disk_writer) = &self.disk_writer {
+                disk_writer.write(request, response);
+            }
+        }
+    }
+
+    /// Inserts the given response into the memory cache for the request type
+    fn insert_into_memory(
+        &self,
+        request: &StorageServiceRequest,
+        response: &StorageServiceResponse,
+    ) {
+        let max_bytes = self.get_max_memory_bytes(&request.data_request);
+        if max_bytes == 0 {
+            return; // The request type is not cached
+        }
+
+        // Calculate the response size
+        let num_bytes = match bcs::serialized_size(response) {
+            Ok(num_bytes) => num_bytes as u64,
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::ResponseCache).message(&format!(
+                    "Failed to calculate the size of the response! Error: {:?}",
+                    error
+                )));
+                return;
+            },
+        };
+
+        // Insert the response into the memory cache
+        let request_label = request.data_request.get_label();
+        let mut memory_caches = self.memory_caches.lock();
+        let memory_cache = memory_caches
+            .entry(request_label)
+            .or_insert_with(|| MemoryCache::new(max_bytes));
+        memory_cache.insert(request.clone(), response.clone(), num_bytes);
+        metrics::set_memory_cache_bytes(request_label, memory_cache.num_bytes);
+    }
+
+    /// Returns the disk cache for the given request type (if the type
+    /// should be cached on disk).
+    fn get_disk_cache(&self, data_request: &DataRequest) -> Option<&DiskCache> {
+        if matches!(data_request, DataRequest::GetStateValuesWithProof(_)) {
+            self.disk_cache.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the memory budget (in bytes) for the given request type
+    fn get_max_memory_bytes(&self, data_request: &DataRequest) -> u64 {
+        match data_request {
+            DataRequest::GetEpochEndingLedgerInfos(_) => {
+                self.config.max_epoch_ending_ledger_infos_bytes
+            },
+            DataRequest::GetNumberOfStatesAtVersion(_) => self.config.max_number_of_states_bytes,
+            DataRequest::GetStateValuesWithProof(_) => self.config.max_state_values_bytes,
+            DataRequest::GetTransactionOutputsWithProof(_) => {
+                self.config.max_transaction_outputs_bytes
+            },
+            DataRequest::GetTransactionsWithProof(_) => self.config.max_transactions_bytes,
+            DataRequest::GetTransactionsOrOutputsWithProof(_) => {
+                self.config.max_transactions_or_outputs_bytes
+            },
+            _ => 0, // Subscriptions, summaries and protocol versions are never cached
+        }
+    }
+}
+
+/// A background writer that persists responses to the disk cache. If the
+/// writer falls behind, new responses are dropped (and not cached on disk).
+struct DiskCacheWriter {
+    write_sender: Mutex<Option<mpsc::SyncSender<PendingDiskWrite>>>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl DiskCacheWriter {
+    fn new(disk_cache: Arc<DiskCache>) -> Self {
+        let (write_sender, write_receiver) =
+            mpsc::sync_channel::<PendingDiskWrite>(MAX_PENDING_DISK_WRITES);
+        let join_handle = thread::Builder::new()
+            .name("stor-cache-writer".into())
+            .spawn(move || {
+                for (request, response) in write_receiver {
+                    disk_cache.insert(&request, &response);
+                }
+            })
+            .map_err(|error| {
+                error!(LogSchema::new(LogEntry::ResponseCache).message(&format!(
+                    "Failed to spawn the disk cache writer! Error: {:?}",
+                    error
+                )));
+            })
+            .ok();
+
+        Self {
+            write_sender: Mutex::new(join_handle.as_ref().map(|_| write_sender)),
+            join_handle,
+        }
+    }
+
+    /// Queues the response (and request) to be written to disk
+    fn write(&self, request: &StorageServiceRequest, response: &StorageServiceResponse) {
+        if let Some(write_sender) = self.write_sender.lock().as_ref() {
+            if let Err(error) = write_sender.try_send((request.clone(), response.clone())) {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(DROPPED_DISK_WRITE_LOG_FREQ_SECS)),
+                    warn!(LogSchema::new(LogEntry::ResponseCache).message(&format!(
+                        "Dropped a response write to the disk cache! Error: {:?}",
+                        error
+                    )))
+                );
+            }
+        }
+    }
+}
+
+impl Drop for DiskCacheWriter {
+    /// Waits for all pending writes to complete
+    fn drop(&mut self) {
+        self.write_sender.lock().take();
+        if let Some(join_handle) = self.join_handle.take() {
+            if join_handle.join().is_err() {
+                error!(LogSchema::new(LogEntry::ResponseCache)
+                    .message("The disk cache writer panicked!"));
+            }
+        }
+    }
+}
+
+/// An in-memory LRU cache of responses that is bounded by bytes
+struct MemoryCache {
+    cache: LruCache<StorageServiceRequest, (StorageServiceResponse, u64)>,
+    max_bytes: u64,
+    num_bytes: u64,
+}
+
+impl MemoryCache {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            cache: LruCache::unbounded(),
+            max_bytes,
+            num_bytes: 0,
+        }
+    }
+
+    /// Returns the cached response for the request (if one exists)
+    fn get(&mut self, request: &StorageServiceRequest) -> Option<StorageServiceResponse> {
+        self.cache
+            .get(request)
+            .map(|(response, _)| response.clone())
+    }
+
+    /// Inserts the response into the cache and evicts the least recently
+    /// used responses until the cache is within budget.
+    fn insert(
+        &mut self,
+        request: StorageServiceRequest,
+        response: StorageServiceResponse,
+        num_bytes: u64,
+    ) {
+        if num_bytes > self.max_bytes {
+            return; // The response will never fit in the cache
+        }
+
+        // Insert the response
+        if let Some((_, replaced_num_bytes)) = self.cache.put(request, (response, num_bytes)) {
+            self.num_bytes = self.num_bytes.saturating_sub(replaced_num_bytes);
+        }
+        self.num_bytes = self.num_bytes.saturating_add(num_bytes);
+
+        // Evict responses until we're within budget
+        while self.num_bytes > self.max_bytes {
+            match self.cache.pop_lru() {
+                Some((_, (_, evicted_num_bytes))) => {
+                    self.num_bytes = self.num_bytes.saturating_sub(evicted_num_bytes);
+                },
+                None => break,
+            }
+        }
+    }
+}
+
+/// A simple on-disk LRU cache of responses. Each response is stored
+/// (alongside the request) in a file named by the hash of the request.
+struct DiskCache {
+    cache_dir: PathBuf,
+    max_bytes: u64,
+    index: Mutex<DiskCacheIndex>,
+}
+
+/// The in-memory index of the files held by the disk cache
+struct DiskCacheIndex {
+    files: LruCache<HashValue, u64>, // The request hash to file size
+    num_bytes: u64,
+}
+
+impl DiskCache {
+    /// Creates a new disk cache in the given directory and indexes
+    /// all existing cache files (oldest files are evicted first).
+    fn new(cache_dir: PathBuf, max_bytes: u64) -> Result<Self, Error> {
+        fs::create_dir_all(&cache_dir).map_err(|error| disk_error(&cache_dir, error))?;
+
+        // Identify all existing cache files (removing any partially written files)
+        let mut existing_files = vec![];
+        for entry in fs::read_dir(&cache_dir).map_err(|error| disk_error(&cache_dir, error))? {
+            let path = entry.map_err(|error| disk_error(&cache_dir, error))?.path();
+            if path.extension().and_then(|extension| extension.to_str())
+                == Some(TEMPORARY_FILE_EXTENSION)
+            {
+                remove_file(&path);
+                continue;
+            }
+            let request_hash = match path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|file_name| HashValue::from_hex(file_name).ok())
+            {
+                Some(request_hash) => request_hash,
+                None => continue, // This isn't a cache file
+            };
+            let metadata = fs::metadata(&path).map_err(|error| disk_error(&path, error))?;
+            let modified_time = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            existing_files.push((modified_time, request_hash, metadata.len()));
+        }
+        existing_files.sort();
+
+        // Index the existing files
+        let disk_cache = Self {
+            cache_dir,
+            max_bytes,
+            index: Mutex::new(DiskCacheIndex {
+                files: LruCache::unbounded(),
+                num_bytes: 0,
+            }),
+        };
+        let num_existing_files = existing_files.len();
+        for (_, request_hash, num_bytes) in existing_files {
+            disk_cache.insert_into_index(request_hash, num_bytes);
+        }
+        info!(LogSchema::new(LogEntry::ResponseCache).message(&format!(
+            "Initialized the disk cache at: {:?}. Found {} existing cache files.",
+            disk_cache.cache_dir, num_existing_files
+        )));
+
+        Ok(disk_cache)
+    }
+
+    /// Returns the cached response for the request (if one exists)
+    fn get(&self, request: &StorageServiceRequest) -> Option<StorageServiceResponse> {
+        let request_hash = hash_request(request)?;
+        self.index.lock().files.get(&request_hash)?;
+
+        // Read the file and verify the stored request matches
+        let path = self.get_file_path(&request_hash);
+        let response = fs::read(&path)
+            .map_err(|error| disk_error(&path, error))
+            .and_then(|bytes| {
+                bcs::from_bytes::<(StorageServiceRequest, StorageServiceResponse)>(&bytes)
+                    .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))
+            });
+        match response {
+            Ok((stored_request, response)) if &stored_request == request => Some(response),
+            Ok(_) => None, // The request hashes collided
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::ResponseCache)
+                    .error(&error)
+                    .message("Failed to read the cached response from disk!"));
+                self.remove_from_index(&request_hash);
+                remove_file(&path);
+                None
+            },
+        }
+    }
+
+    /// Writes the response (and request) to disk and evicts the least
+    /// recently used files until the cache is within budget.
+    fn insert(&self, request: &StorageServiceRequest, response: &StorageServiceResponse) {
+        let request_hash = match hash_request(request) {
+            Some(request_hash) => request_hash,
+            None => return,
+        };
+        if self.index.lock().files.contains(&request_hash) {
+            return; // The response is already on disk
+        }
+
+        // Serialize the request and response
+        let bytes = match bcs::to_bytes(&(request, response)) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::ResponseCache).message(&format!(
+                    "Failed to serialize the response for the disk cache! Error: {:?}",
+                    error
+                )));
+                return;
+            },
+        };
+        let num_bytes = bytes.len() as u64;
+        if num_bytes > self.max_bytes {
+            return; // The response will never fit in the cache
+        }
+
+        // Write the file (via a temporary file, to avoid partial writes)
+        let path = self.get_file_path(&request_hash);
+        let temporary_path = path.with_extension(TEMPORARY_FILE_EXTENSION);
+        if let Err(error) = fs::write(&temporary_path, bytes)
+            .and_then(|_| fs::rename(&temporary_path, &path))
+            .map_err(|error| disk_error(&path, error))
+        {
+            warn!(LogSchema::new(LogEntry::ResponseCache)
+                .error(&error)
+                .message("Failed to write the response to the disk cache!"));
+            remove_file(&temporary_path);
+            return;
+        }
+
+        self.insert_into_index(request_hash, num_bytes);
+    }
+
+    /// Inserts the file into the index and removes the least recently
+    /// used files until the cache is within budget.
+    fn insert_into_index(&self, request_hash: HashValue, num_bytes: u64) {
+        let mut evicted_files = vec![];
+        {
+            let mut index = self.index.lock();
+            if let Some(replaced_num_bytes) = index.files.put(request_hash, num_bytes) {
+                index.num_bytes = index.num_bytes.saturating_sub(replaced_num_bytes);
+            }
+            index.num_bytes = index.num_bytes.saturating_add(num_bytes);
+            while index.num_bytes > self.max_bytes {
+                match index.files.pop_lru() {
+                    Some((evicted_hash, evicted_num_bytes)) => {
+                        index.num_bytes = index.num_bytes.saturating_sub(evicted_num_bytes);
+                        evicted_files.push(evicted_hash);
+                    },
+                    None => break,
+                }
+            }
+            metrics::set_gauge(&metrics::RESPONSE_DISK_CACHE_BYTES, index.num_bytes);
+        }
+
+        // Remove the evicted files (outside the lock)
+        for evicted_hash in evicted_files {
+            remove_file(&self.get_file_path(&evicted_hash));
+        }
+    }
+
+    /// Removes the file from the index
+    fn remove_from_index(&self, request_hash: &HashValue) {
+        let mut index = self.index.lock();
+        if let Some(num_bytes) = index.files.pop(request_hash) {
+            index.num_bytes = index.num_bytes.saturating_sub(num_bytes);
+        }
+    }
+
+    /// Returns the path of the cache file for the given request hash
+    fn get_file_path(&self, request_hash: &HashValue) -> PathBuf {
+        self.cache_dir.join(request_hash.to_hex())
+    }
+}
+
+/// Returns the hash of the serialized request
+fn hash_request(request: &StorageServiceRequest) -> Option<HashValue> {
+    bcs::to_bytes(request)
+        .ok()
+        .map(|bytes| HashValue::sha3_256_of(&bytes))
+}
+
+/// Removes the given file from disk (logging any failures)
+fn remove_file(path: &Path) {
+    if let Err(error) = fs::remove_file(path) {
+        warn!(LogSchema::new(LogEntry::ResponseCache)
+            .error(&disk_error(path, error))
+            .message("Failed to remove the file from the disk cache!"));
+    }
+}
+
+/// Returns an error for a failed disk cache operation
+fn disk_error(path: &Path, error: std::io::Error) -> Error {
+    Error::StorageErrorEncountered(format!(
+        "Disk cache operation failed for: {:?}. Error: {:?}",
+        path, error
+    ))
+}
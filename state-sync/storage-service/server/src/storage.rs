@@ -9,8 +9,13 @@ use aptos_storage_service_types::responses::{
     CompleteDataRange, DataResponse, DataSummary, TransactionOrOutputListWithProof,
 };
 use aptos_types::{
+    account_address::AccountAddress,
     epoch_change::EpochChangeProof,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_key_prefix::StateKeyPrefix,
+        state_value::{StateValueChunkWithProof, StateValueListWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use serde::Serialize;
@@ -88,6 +93,28 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         start_index: u64,
         end_index: u64,
     ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error>;
+
+    /// Returns the state values under the given `account` at the specified
+    /// version (each with a proof), in key order, starting at `start_key`
+    /// (if specified). In some cases, not all state values may be returned
+    /// (e.g., due to network or chunk limits). If so, the next state key
+    /// to fetch is also returned.
+    fn get_account_state_values_with_proof(
+        &self,
+        version: u64,
+        account: AccountAddress,
+        start_key: Option<StateKey>,
+    ) -> aptos_storage_service_types::Result<StateValueListWithProof, Error>;
+
+    /// Returns the state values for the given `state_keys` at the specified
+    /// version (each with a proof). In some cases, not all state values may
+    /// be returned (e.g., due to network or chunk limits). If so, the next
+    /// state key to fetch is also returned.
+    fn get_state_values_by_keys_with_proof(
+        &self,
+        version: u64,
+        state_keys: Vec<StateKey>,
+    ) -> aptos_storage_service_types::Result<StateValueListWithProof, Error>;
 }
 
 /// The underlying implementation of the StorageReaderInterface, used by the
@@ -103,6 +130,67 @@ impl StorageReader {
         Self { config, storage }
     }
 
+    /// Returns the state values (each with a proof) for the given state keys at
+    /// the specified version. The list is truncated to respect the chunk and
+    /// network frame limits (in which case, the next state key is returned).
+    fn fetch_state_value_list_with_proof(
+        &self,
+        version: Version,
+        state_keys: Vec<StateKey>,
+    ) -> aptos_storage_service_types::Result<StateValueListWithProof, Error> {
+        // Fetch the transaction info at the version (the state values are proven against it)
+        let transaction_info_with_proof = self
+            .storage
+            .get_transaction_by_version(version, version, false)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?
+            .proof;
+
+        // Fetch the state values and proofs (up to the max chunk size)
+        let max_num_state_values = self.config.max_state_chunk_size as usize;
+        let next_state_key = state_keys.get(max_num_state_values).cloned();
+        let mut state_values = vec![];
+        for state_key in state_keys.into_iter().take(max_num_state_values) {
+            let (state_value, proof) = self
+                .storage
+                .get_state_value_with_proof_by_version(&state_key, version)
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            state_values.push((state_key, state_value, proof));
+        }
+        let mut state_value_list_with_proof = StateValueListWithProof {
+            version,
+            state_values,
+            next_state_key,
+            transaction_info_with_proof,
+        };
+
+        // Truncate the list until it fits into a single network frame
+        while state_value_list_with_proof.state_values.len() > 1 {
+            let (overflow_frame, num_bytes) = check_overflow_network_frame(
+                &state_value_list_with_proof,
+                self.config.max_network_chunk_bytes,
+            )?;
+            if !overflow_frame {
+                break;
+            }
+
+            increment_network_frame_overflow(STATE_VALUE_LIST_WITH_PROOF_LABEL);
+            let num_state_values = state_value_list_with_proof.state_values.len();
+            let new_num_state_values = num_state_values / 2;
+            debug!("The request for {:?} state values (with proofs) was too large (num bytes: {:?}). Truncating to {:?}.",
+                num_state_values, num_bytes, new_num_state_values);
+            state_value_list_with_proof.next_state_key = Some(
+                state_value_list_with_proof.state_values[new_num_state_values]
+                    .0
+                    .clone(),
+            );
+            state_value_list_with_proof
+                .state_values
+                .truncate(new_num_state_values);
+        }
+
+        Ok(state_value_list_with_proof)
+    }
+
     /// Returns the state values range held in the database (lowest to highest).
     /// Note: it is currently assumed that if a node contains a transaction at a
     /// version, V, the node also contains all state values at V.
@@ -494,8 +582,45 @@ impl StorageReaderInterface for StorageReader {
             version, start_index, end_index
         )))
     }
+
+    fn get_account_state_values_with_proof(
+        &self,
+        version: u64,
+        account: AccountAddress,
+        start_key: Option<StateKey>,
+    ) -> aptos_storage_service_types::Result<StateValueListWithProof, Error> {
+        // Fetch the state keys under the account (including the next key, if one exists)
+        let max_num_state_keys = (self.config.max_state_chunk_size as usize).saturating_add(1);
+        let state_value_iterator = self
+            .storage
+            .get_prefixed_state_value_iterator(
+                &StateKeyPrefix::from(account),
+                start_key.as_ref(),
+                version,
+            )
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let state_keys = state_value_iterator
+            .take(max_num_state_keys)
+            .map(|result| result.map(|(state_key, _)| state_key))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+
+        // Fetch the state values with proofs
+        self.fetch_state_value_list_with_proof(version, state_keys)
+    }
+
+    fn get_state_values_by_keys_with_proof(
+        &self,
+        version: u64,
+        state_keys: Vec<StateKey>,
+    ) -> aptos_storage_service_types::Result<StateValueListWithProof, Error> {
+        self.fetch_state_value_list_with_proof(version, state_keys)
+    }
 }
 
+/// The label used for state value list overflows
+const STATE_VALUE_LIST_WITH_PROOF_LABEL: &str = "state_value_list_with_proof";
+
 /// Calculate `(start..=end).len()`. Returns an error if `end < start` or
 /// `end == u64::MAX`.
 fn inclusive_range_len(start: u64, end: u64) -> aptos_storage_service_types::Result<u64, Error> {
@@ -3,7 +3,8 @@
 
 use crate::{
     error::Error, handler::Handler, metrics::increment_network_frame_overflow,
-    network::ResponseSender, storage::StorageReaderInterface, LogEntry, LogSchema,
+    network::ResponseSender, response_cache::ResponseCache, storage::StorageReaderInterface,
+    LogEntry, LogSchema,
 };
 use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
 use aptos_infallible::{Mutex, RwLock};
@@ -19,7 +20,6 @@ use aptos_storage_service_types::{
 };
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::ledger_info::LedgerInfoWithSignatures;
-use lru::LruCache;
 use std::{cmp::min, collections::HashMap, sync::Arc, time::Instant};
 
 /// A subscription for data received by a client
@@ -165,7 +165,7 @@ pub(crate) fn handle_active_data_subscriptions<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<RwLock<StorageServerSummary>>,
     config: StorageServiceConfig,
     data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
-    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    response_cache: Arc<ResponseCache>,
     storage: T,
     time_service: TimeService,
 ) {
@@ -176,7 +176,7 @@ pub(crate) fn handle_active_data_subscriptions<T: StorageReaderInterface>(
     let peers_with_ready_subscriptions = match get_peers_with_ready_subscriptions(
         cached_storage_server_summary.clone(),
         data_subscriptions.clone(),
-        lru_response_cache.clone(),
+        response_cache.clone(),
         storage.clone(),
         time_service.clone(),
     ) {
@@ -195,7 +195,7 @@ pub(crate) fn handle_active_data_subscriptions<T: StorageReaderInterface>(
                 cached_storage_server_summary.clone(),
                 config,
                 data_subscriptions.clone(),
-                lru_response_cache.clone(),
+                response_cache.clone(),
                 storage.clone(),
                 time_service.clone(),
                 data_subscription,
@@ -214,7 +214,7 @@ pub(crate) fn handle_active_data_subscriptions<T: StorageReaderInterface>(
 pub(crate) fn get_peers_with_ready_subscriptions<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<RwLock<StorageServerSummary>>,
     data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
-    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    response_cache: Arc<ResponseCache>,
     storage: T,
     time_service: TimeService,
 ) -> aptos_storage_service_types::Result<Vec<(PeerNetworkId, LedgerInfoWithSignatures)>, Error> {
@@ -240,7 +240,7 @@ pub(crate) fn get_peers_with_ready_subscriptions<T: StorageReaderInterface>(
                     cached_storage_server_summary.clone(),
                     data_subscriptions.clone(),
                     highest_known_epoch,
-                    lru_response_cache.clone(),
+                    response_cache.clone(),
                     data_subscription.protocol,
                     storage.clone(),
                     time_service.clone(),
@@ -280,7 +280,7 @@ fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<RwLock<StorageServerSummary>>,
     data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
     epoch: u64,
-    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    response_cache: Arc<ResponseCache>,
     protocol: ProtocolId,
     storage: T,
     time_service: TimeService,
@@ -299,7 +299,7 @@ fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
     let handler = Handler::new(
         cached_storage_server_summary,
         data_subscriptions,
-        lru_response_cache,
+        response_cache,
         storage,
         time_service,
     );
@@ -334,7 +334,7 @@ fn notify_peer_of_new_data<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<RwLock<StorageServerSummary>>,
     config: StorageServiceConfig,
     data_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, DataSubscriptionRequest>>>,
-    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    response_cache: Arc<ResponseCache>,
     storage: T,
     time_service: TimeService,
     subscription: DataSubscriptionRequest,
@@ -347,7 +347,7 @@ fn notify_peer_of_new_data<T: StorageReaderInterface>(
             let handler = Handler::new(
                 cached_storage_server_summary,
                 data_subscriptions,
-                lru_response_cache,
+                response_cache,
                 storage,
                 time_service,
             );
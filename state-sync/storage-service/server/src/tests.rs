@@ -7,6 +7,7 @@
 use crate::{
     metrics,
     network::{ResponseSender, StorageServiceNetworkEvents},
+    response_cache::ResponseCache,
     storage::StorageReader,
     subscription::{
         get_peers_with_ready_subscriptions, remove_expired_data_subscriptions,
@@ -38,8 +39,9 @@ use aptos_storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
-        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+        StateValuesByKeysWithProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+        TransactionsWithProofRequest,
     },
     responses::{
         CompleteDataRange, DataResponse, DataSummary, ProtocolMetadata, ServerProtocolVersion,
@@ -61,12 +63,12 @@ use aptos_types::{
     on_chain_config::ValidatorSet,
     proof::{
         AccumulatorConsistencyProof, SparseMerkleProof, SparseMerkleRangeProof,
-        TransactionAccumulatorSummary,
+        TransactionAccumulatorProof, TransactionAccumulatorSummary, TransactionInfoWithProof,
     },
     state_proof::StateProof,
     state_store::{
         state_key::StateKey,
-        state_value::{StateValue, StateValueChunkWithProof},
+        state_value::{StateValue, StateValueChunkWithProof, StateValueListWithProof},
     },
     transaction::{
         AccountTransactionsWithProof, ExecutionStatus, RawTransaction, Script, SignedTransaction,
@@ -80,7 +82,6 @@ use aptos_types::{
 };
 use claims::{assert_matches, assert_none};
 use futures::channel::{oneshot, oneshot::Receiver};
-use lru::LruCache;
 use mockall::{
     mock,
     predicate::{always, eq},
@@ -130,13 +131,16 @@ async fn test_peers_with_ready_subscriptions() {
 
     // Create test data with an empty storage server summary
     let cached_storage_server_summary = Arc::new(RwLock::new(StorageServerSummary::default()));
-    let lru_response_cache = Arc::new(Mutex::new(LruCache::new(0)));
+    let response_cache = Arc::new(ResponseCache::new(
+        StorageServiceConfig::default().response_cache,
+        None,
+    ));
 
     // Verify that there are no peers with ready subscriptions
     let peers_with_ready_subscriptions = get_peers_with_ready_subscriptions(
         cached_storage_server_summary.clone(),
         data_subscriptions.clone(),
-        lru_response_cache.clone(),
+        response_cache.clone(),
         storage_reader.clone(),
         time_service.clone(),
     )
@@ -153,7 +157,7 @@ async fn test_peers_with_ready_subscriptions() {
     let peers_with_ready_subscriptions = get_peers_with_ready_subscriptions(
         cached_storage_server_summary.clone(),
         data_subscriptions.clone(),
-        lru_response_cache.clone(),
+        response_cache.clone(),
         storage_reader.clone(),
         time_service.clone(),
     )
@@ -177,7 +181,7 @@ async fn test_peers_with_ready_subscriptions() {
     let peers_with_ready_subscriptions = get_peers_with_ready_subscriptions(
         cached_storage_server_summary,
         data_subscriptions,
-        lru_response_cache,
+        response_cache,
         storage_reader,
         time_service,
     )
@@ -330,8 +334,8 @@ async fn test_cachable_requests_compression() {
 #[tokio::test]
 async fn test_cachable_requests_eviction() {
     // Create test data
-    let max_lru_cache_size = StorageServiceConfig::default().max_lru_cache_size;
-    let version = 101;
+    let num_states_requests = 500;
+    let versions = [101, 102];
     let start_index = 100;
     let end_index = 199;
     let state_value_chunk_with_proof = StateValueChunkWithProof {
@@ -344,15 +348,25 @@ async fn test_cachable_requests_eviction() {
         root_hash: HashValue::random(),
     };
 
+    // Create a storage config where the cache only fits a single state chunk
+    let storage_service_response = StorageServiceResponse::new(
+        DataResponse::StateValueChunkWithProof(state_value_chunk_with_proof.clone()),
+        true,
+    )
+    .unwrap();
+    let mut storage_config = StorageServiceConfig::default();
+    storage_config.response_cache.max_state_values_bytes =
+        bcs::serialized_size(&storage_service_response).unwrap() as u64;
+
     // Create the mock db reader
     let mut db_reader = create_mock_db_reader();
     let mut expectation_sequence = Sequence::new();
     db_reader
         .expect_get_state_leaf_count()
-        .times(max_lru_cache_size as usize)
+        .times(num_states_requests as usize)
         .with(always())
         .returning(move |_| Ok(165));
-    for _ in 0..2 {
+    for version in [versions[0], versions[1], versions[0]] {
         let state_value_chunk_with_proof_clone = state_value_chunk_with_proof.clone();
         db_reader
             .expect_get_state_value_chunk_with_proof()
@@ -367,24 +381,103 @@ async fn test_cachable_requests_eviction() {
     }
 
     // Create the storage client and server
-    let (mut mock_client, service, _) = MockClient::new(Some(db_reader), None);
+    let (mut mock_client, service, _) = MockClient::new(Some(db_reader), Some(storage_config));
     tokio::spawn(service.start());
 
     // Process a request to fetch a state chunk. This should cache and serve the response.
     for _ in 0..2 {
-        let _ =
-            get_state_values_with_proof(&mut mock_client, version, start_index, end_index, true)
-                .await;
+        let _ = get_state_values_with_proof(
+            &mut mock_client,
+            versions[0],
+            start_index,
+            end_index,
+            true,
+        )
+        .await;
     }
 
-    // Process enough requests to evict the previously cached response
-    for version in 0..max_lru_cache_size {
+    // Process many number of states requests (these have their own cache budget)
+    for version in 0..num_states_requests {
         let _ = get_number_of_states(&mut mock_client, version, true).await;
     }
 
-    // Process a request to fetch the state chunk again. This requires refetching the data.
+    // Process a request to fetch the state chunk again. This should be served from the cache.
     let _ =
-        get_state_values_with_proof(&mut mock_client, version, start_index, end_index, true).await;
+        get_state_values_with_proof(&mut mock_client, versions[0], start_index, end_index, true)
+            .await;
+
+    // Process a request to fetch a different state chunk. This should evict the first chunk.
+    let _ =
+        get_state_values_with_proof(&mut mock_client, versions[1], start_index, end_index, true)
+            .await;
+
+    // Process a request to fetch the first state chunk again. This requires refetching the data.
+    let _ =
+        get_state_values_with_proof(&mut mock_client, versions[0], start_index, end_index, true)
+            .await;
+}
+
+#[tokio::test]
+async fn test_cachable_requests_disk_cache() {
+    // Create test data
+    let version = 101;
+    let start_index = 100;
+    let end_index = 199;
+    let data_request = DataRequest::GetStateValuesWithProof(StateValuesWithProofRequest {
+        version,
+        start_index,
+        end_index,
+    });
+    let storage_request = StorageServiceRequest::new(data_request, true);
+    let state_value_chunk_with_proof = StateValueChunkWithProof {
+        first_index: start_index,
+        last_index: end_index,
+        first_key: HashValue::random(),
+        last_key: HashValue::random(),
+        raw_values: vec![],
+        proof: SparseMerkleRangeProof::new(vec![]),
+        root_hash: HashValue::random(),
+    };
+    let storage_response = StorageServiceResponse::new(
+        DataResponse::StateValueChunkWithProof(state_value_chunk_with_proof),
+        true,
+    )
+    .unwrap();
+
+    // Create a response cache config with the disk cache enabled
+    let mut response_cache_config = StorageServiceConfig::default().response_cache;
+    response_cache_config.enable_state_values_disk_cache = true;
+    let disk_cache_dir = aptos_temppath::TempPath::new();
+    disk_cache_dir.create_as_dir().unwrap();
+
+    // Create a response cache and insert the response
+    let response_cache = ResponseCache::new(
+        response_cache_config,
+        Some(disk_cache_dir.path().to_path_buf()),
+    );
+    assert_none!(response_cache.get(&storage_request));
+    response_cache.insert(&storage_request, &storage_response);
+    assert_eq!(
+        response_cache.get(&storage_request),
+        Some(storage_response.clone())
+    );
+
+    // Create a new response cache (i.e., emulate a restart) and verify the response is still cached.
+    // Dropping the response cache waits for all pending disk writes to complete.
+    drop(response_cache);
+    let response_cache = ResponseCache::new(
+        response_cache_config,
+        Some(disk_cache_dir.path().to_path_buf()),
+    );
+    assert_eq!(response_cache.get(&storage_request), Some(storage_response));
+
+    // Verify the response isn't found when the disk cache is disabled
+    response_cache_config.enable_state_values_disk_cache = false;
+    let response_cache = ResponseCache::new(
+        response_cache_config,
+        Some(disk_cache_dir.path().to_path_buf()),
+    );
+    assert_none!(response_cache.get(&storage_request));
 }
 
 #[tokio::test]
@@ -603,6 +696,116 @@ async fn test_get_states_with_proof_invalid() {
     }
 }
 
+#[tokio::test]
+async fn test_get_state_values_by_keys_with_proof() {
+    // Create test data
+    let version = 101;
+    let state_keys: Vec<_> = (0..10)
+        .map(|_| StateKey::raw(HashValue::random().to_vec()))
+        .collect();
+    let transaction_with_proof = create_transaction_with_proof(version);
+
+    // Create the mock db reader (every other state key is missing)
+    let mut db_reader = create_mock_db_reader();
+    expect_get_transaction_by_version(&mut db_reader, version, transaction_with_proof.clone());
+    let mut expected_state_values = vec![];
+    for (index, state_key) in state_keys.iter().enumerate() {
+        let state_value = if index % 2 == 0 {
+            Some(StateValue::new_legacy(HashValue::random().to_vec()))
+        } else {
+            None
+        };
+        expect_get_state_value_with_proof_by_version(
+            &mut db_reader,
+            version,
+            state_key.clone(),
+            state_value.clone(),
+        );
+        expected_state_values.push((
+            state_key.clone(),
+            state_value,
+            SparseMerkleProof::new(None, vec![]),
+        ));
+    }
+
+    // Create the storage client and server
+    let (mut mock_client, service, _) = MockClient::new(Some(db_reader), None);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by key
+    let response =
+        get_state_values_by_keys_with_proof(&mut mock_client, version, state_keys, false)
+            .await
+            .unwrap();
+
+    // Verify the response is correct
+    let expected_state_value_list_with_proof = StateValueListWithProof {
+        version,
+        state_values: expected_state_values,
+        next_state_key: None,
+        transaction_info_with_proof: transaction_with_proof.proof,
+    };
+    assert_matches!(response, StorageServiceResponse::RawResponse(_));
+    assert_eq!(
+        response.get_data_response().unwrap(),
+        DataResponse::StateValueListWithProof(expected_state_value_list_with_proof)
+    );
+}
+
+#[tokio::test]
+async fn test_get_state_values_by_keys_with_proof_chunk_limit() {
+    // Create a storage config with a small state chunk size
+    let max_state_chunk_size = 5;
+    let storage_config = StorageServiceConfig {
+        max_state_chunk_size,
+        ..Default::default()
+    };
+
+    // Create test data (with more keys than the max chunk size)
+    let version = 10;
+    let state_keys: Vec<_> = (0..max_state_chunk_size * 2)
+        .map(|_| StateKey::raw(HashValue::random().to_vec()))
+        .collect();
+    let transaction_with_proof = create_transaction_with_proof(version);
+
+    // Create the mock db reader (only the first chunk should be fetched)
+    let mut db_reader = create_mock_db_reader();
+    expect_get_transaction_by_version(&mut db_reader, version, transaction_with_proof);
+    for state_key in state_keys.iter().take(max_state_chunk_size as usize) {
+        expect_get_state_value_with_proof_by_version(
+            &mut db_reader,
+            version,
+            state_key.clone(),
+            None,
+        );
+    }
+
+    // Create the storage client and server
+    let (mut mock_client, service, _) = MockClient::new(Some(db_reader), Some(storage_config));
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by key
+    let response =
+        get_state_values_by_keys_with_proof(&mut mock_client, version, state_keys.clone(), false)
+            .await
+            .unwrap();
+
+    // Verify the response is truncated and the next state key is returned
+    match response.get_data_response().unwrap() {
+        DataResponse::StateValueListWithProof(state_value_list_with_proof) => {
+            assert_eq!(
+                state_value_list_with_proof.state_values.len() as u64,
+                max_state_chunk_size
+            );
+            assert_eq!(
+                state_value_list_with_proof.next_state_key,
+                Some(state_keys[max_state_chunk_size as usize].clone())
+            );
+        },
+        data_response => panic!("Unexpected data response: {:?}", data_response),
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_get_new_transactions() {
     // Test small and large chunk sizes
@@ -2343,11 +2546,12 @@ impl MockClient {
         let executor = tokio::runtime::Handle::current();
         let mock_time_service = TimeService::mock();
         let storage_server = StorageServiceServer::new(
-            StorageServiceConfig::default(),
+            storage_config,
             executor,
             storage_reader,
             mock_time_service.clone(),
             storage_service_network_events,
+            None,
         );
 
         // Return the client and service
@@ -2925,6 +3129,21 @@ async fn get_state_values_with_proof(
     send_storage_request(mock_client, use_compression, data_request).await
 }
 
+/// Sends a state values by keys request and processes the response
+async fn get_state_values_by_keys_with_proof(
+    mock_client: &mut MockClient,
+    version: u64,
+    state_keys: Vec<StateKey>,
+    use_compression: bool,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request =
+        DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+            version,
+            state_keys,
+        });
+    send_storage_request(mock_client, use_compression, data_request).await
+}
+
 /// Sends a number of states request and processes the response
 async fn get_number_of_states(
     mock_client: &mut MockClient,
@@ -3212,6 +3431,33 @@ fn expect_get_state_values_with_proof(
         .returning(move |_, _, _| Ok(state_value_chunk_with_proof.clone()));
 }
 
+/// Sets an expectation on the given mock db for a call to fetch a state value with proof
+fn expect_get_state_value_with_proof_by_version(
+    mock_db: &mut MockDatabaseReader,
+    version: u64,
+    state_key: StateKey,
+    state_value: Option<StateValue>,
+) {
+    mock_db
+        .expect_get_state_value_with_proof_by_version()
+        .times(1)
+        .with(eq(state_key), eq(version))
+        .returning(move |_, _| Ok((state_value.clone(), SparseMerkleProof::new(None, vec![]))));
+}
+
+/// Sets an expectation on the given mock db for a call to fetch a transaction by version
+fn expect_get_transaction_by_version(
+    mock_db: &mut MockDatabaseReader,
+    version: u64,
+    transaction_with_proof: TransactionWithProof,
+) {
+    mock_db
+        .expect_get_transaction_by_version()
+        .times(1)
+        .with(eq(version), eq(version), eq(false))
+        .returning(move |_, _, _| Ok(transaction_with_proof.clone()));
+}
+
 /// Creates a new storage service config with the limit
 /// configured to be the size of an output list or transaction
 /// list (depending on if `fallback_to_transactions` is set).
@@ -3288,6 +3534,26 @@ fn create_output_list_with_proof(
     )
 }
 
+/// Creates a test transaction with proof at the specified version
+fn create_transaction_with_proof(version: Version) -> TransactionWithProof {
+    let transaction_info = TransactionInfo::new(
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+        Some(HashValue::random()),
+        0,
+        ExecutionStatus::Success,
+    );
+    let transaction_info_with_proof =
+        TransactionInfoWithProof::new(TransactionAccumulatorProof::new(vec![]), transaction_info);
+    TransactionWithProof::new(
+        version,
+        create_test_transaction(0, vec![]),
+        None,
+        transaction_info_with_proof,
+    )
+}
+
 /// Creates a set of state keys and values using the specified number and size
 fn create_state_keys_and_values(
     num_keys_and_values: u64,
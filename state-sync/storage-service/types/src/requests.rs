@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::COMPRESSION_SUFFIX_LABEL;
-use aptos_types::transaction::Version;
+use aptos_types::{
+    account_address::AccountAddress, state_store::state_key::StateKey, transaction::Version,
+};
 use serde::{Deserialize, Serialize};
 
 /// A storage service request.
@@ -44,6 +46,8 @@ pub enum DataRequest {
     GetTransactionsWithProof(TransactionsWithProofRequest), // Fetches a list of transactions with a proof
     GetNewTransactionsOrOutputsWithProof(NewTransactionsOrOutputsWithProofRequest), // Subscribes to new transactions or outputs with a proof
     GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest), // Fetches a list of transactions or outputs with a proof
+    GetAccountStateValuesWithProof(AccountStateValuesWithProofRequest), // Fetches the state values under an account (each with a proof)
+    GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest), // Fetches the state values for a list of keys (each with a proof)
 }
 
 impl DataRequest {
@@ -63,6 +67,8 @@ impl DataRequest {
                 "get_new_transactions_or_outputs_with_proof"
             },
             Self::GetTransactionsOrOutputsWithProof(_) => "get_transactions_or_outputs_with_proof",
+            Self::GetAccountStateValuesWithProof(_) => "get_account_state_values_with_proof",
+            Self::GetStateValuesByKeysWithProof(_) => "get_state_values_by_keys_with_proof",
         }
     }
 
@@ -81,6 +87,17 @@ impl DataRequest {
     }
 }
 
+/// A storage service request for fetching the state values under an account
+/// at a specified version (each with a proof). The state values are returned
+/// in key order, starting at `start_key` (if specified). The version must be
+/// the version of a ledger info (i.e., a state checkpoint).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct AccountStateValuesWithProofRequest {
+    pub version: u64,                // The version to fetch the state values at
+    pub account: AccountAddress,     // The account to fetch the state values for
+    pub start_key: Option<StateKey>, // The state key to start fetching at (inclusive)
+}
+
 /// A storage service request for fetching a list of epoch ending ledger infos.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct EpochEndingLedgerInfoRequest {
@@ -114,6 +131,15 @@ pub struct StateValuesWithProofRequest {
     pub end_index: u64,   // The index to stop fetching state values (inclusive)
 }
 
+/// A storage service request for fetching the state values of the given
+/// state keys at a specified version (each with a proof). The version must
+/// be the version of a ledger info (i.e., a state checkpoint).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct StateValuesByKeysWithProofRequest {
+    pub version: u64,              // The version to fetch the state values at
+    pub state_keys: Vec<StateKey>, // The state keys to fetch the state values for
+}
+
 /// A storage service request for fetching a transaction output list with a
 /// corresponding proof.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
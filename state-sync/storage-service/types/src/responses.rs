@@ -3,10 +3,11 @@
 
 use crate::{
     requests::DataRequest::{
-        GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
-        GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
-        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
-        GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
+        GetAccountStateValuesWithProof, GetEpochEndingLedgerInfos,
+        GetNewTransactionOutputsWithProof, GetNewTransactionsOrOutputsWithProof,
+        GetNewTransactionsWithProof, GetNumberOfStatesAtVersion, GetServerProtocolVersion,
+        GetStateValuesByKeysWithProof, GetStateValuesWithProof, GetStorageServerSummary,
+        GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
         GetTransactionsWithProof,
     },
     responses::Error::DegenerateRangeError,
@@ -17,7 +18,7 @@ use aptos_config::config::{StorageServiceConfig, MAX_APPLICATION_MESSAGE_SIZE};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::state_value::{StateValueChunkWithProof, StateValueListWithProof},
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use num_traits::{PrimInt, Zero};
@@ -134,6 +135,7 @@ pub enum DataResponse {
     TransactionsWithProof(TransactionListWithProof),
     NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
     TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
+    StateValueListWithProof(StateValueListWithProof),
 }
 
 impl DataResponse {
@@ -151,6 +153,7 @@ impl DataResponse {
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::StateValueListWithProof(_) => "state_value_list_with_proof",
         }
     }
 }
@@ -188,6 +191,21 @@ impl TryFrom<StorageServiceResponse> for StateValueChunkWithProof {
     }
 }
 
+impl TryFrom<StorageServiceResponse> for StateValueListWithProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::StateValueListWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected state_value_list_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for EpochChangeProof {
     type Error = crate::responses::Error;
 
@@ -527,9 +545,39 @@ impl DataSummary {
 
                 can_serve_txns && can_serve_outputs && can_create_proof
             },
+            GetAccountStateValuesWithProof(request) => {
+                self.can_service_state_value_list_request(request.version)
+            },
+            GetStateValuesByKeysWithProof(request) => {
+                self.can_service_state_value_list_request(request.version)
+            },
         }
     }
 
+    /// Returns true iff the state values (and their proofs) at the given
+    /// version can be serviced. The state values are proven relative to
+    /// the transaction info at the version, so the transaction must also
+    /// be held in storage.
+    fn can_service_state_value_list_request(&self, version: Version) -> bool {
+        let can_serve_states = self
+            .states
+            .map(|range| range.contains(version))
+            .unwrap_or(false);
+
+        let can_serve_txn_info = self
+            .transactions
+            .map(|range| range.contains(version))
+            .unwrap_or(false);
+
+        let can_create_proof = self
+            .synced_ledger_info
+            .as_ref()
+            .map(|li| li.ledger_info().version() >= version)
+            .unwrap_or(false);
+
+        can_serve_states && can_serve_txn_info && can_create_proof
+    }
+
     /// Returns true iff the optimistic data request can be serviced
     fn can_service_optimistic_request(&self, known_version: u64) -> bool {
         self.synced_ledger_info
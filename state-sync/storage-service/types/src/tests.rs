@@ -3,7 +3,8 @@
 
 use crate::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest,
+        AccountStateValuesWithProofRequest, DataRequest, EpochEndingLedgerInfoRequest,
+        StateValuesByKeysWithProofRequest, StateValuesWithProofRequest,
         TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
     },
@@ -12,6 +13,7 @@ use crate::{
 };
 use aptos_crypto::hash::HashValue;
 use aptos_types::{
+    account_address::AccountAddress,
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
@@ -209,6 +211,35 @@ fn test_data_summary_can_service_state_chunk_request() {
     }
 }
 
+#[test]
+fn test_data_summary_can_service_state_value_list_request() {
+    let summary = DataSummary {
+        synced_ledger_info: Some(create_mock_ledger_info(250)),
+        states: Some(create_range(100, 300)),
+        transactions: Some(create_range(150, 300)),
+        ..Default::default()
+    };
+
+    for compression in [true, false] {
+        for request_fn in [account_state_values_request, state_values_by_keys_request] {
+            // in range and can provide proof => can service
+            assert!(summary.can_service(&request_fn(150, compression)));
+            assert!(summary.can_service(&request_fn(250, compression)));
+
+            // in range, but cannot provide proof => cannot service
+            assert!(!summary.can_service(&request_fn(251, compression)));
+            assert!(!summary.can_service(&request_fn(300, compression)));
+
+            // states are in range, but the transaction info is missing => cannot service
+            assert!(!summary.can_service(&request_fn(100, compression)));
+            assert!(!summary.can_service(&request_fn(149, compression)));
+
+            // can provide proof, but out of range ==> cannot service
+            assert!(!summary.can_service(&request_fn(50, compression)));
+        }
+    }
+}
+
 #[test]
 fn test_protocol_metadata_can_service() {
     let metadata = ProtocolMetadata {
@@ -257,6 +288,16 @@ fn create_range(lowest: u64, highest: u64) -> CompleteDataRange<u64> {
     CompleteDataRange::new(lowest, highest).unwrap()
 }
 
+fn account_state_values_request(version: Version, use_compression: bool) -> StorageServiceRequest {
+    let data_request =
+        DataRequest::GetAccountStateValuesWithProof(AccountStateValuesWithProofRequest {
+            version,
+            account: AccountAddress::random(),
+            start_key: None,
+        });
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 fn epochs_request(start: Epoch, end: Epoch, use_compression: bool) -> StorageServiceRequest {
     let data_request = DataRequest::GetEpochEndingLedgerInfos(EpochEndingLedgerInfoRequest {
         start_epoch: start,
@@ -326,6 +367,15 @@ fn state_values_request(
     StorageServiceRequest::new(data_request, use_compression)
 }
 
+fn state_values_by_keys_request(version: Version, use_compression: bool) -> StorageServiceRequest {
+    let data_request =
+        DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+            version,
+            state_keys: vec![],
+        });
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 fn states_request(version: Version, use_compression: bool) -> StorageServiceRequest {
     state_values_request(version, 0, 1000, use_compression)
 }
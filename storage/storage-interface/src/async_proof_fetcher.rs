@@ -94,7 +94,11 @@ impl AsyncProofFetcher {
             let proof = reader
                 .get_state_proof_by_version_ext(&state_key, version)
                 .expect("Proof reading should succeed.");
-            if let Some(root_hash) = root_hash {
+            // Only values read from the state Merkle tree of the reader can be verified, which
+            // is the case unless the reader says otherwise (`are_state_values_provable` is true by
+            // default). The proof is still valid for updating the state Merkle tree, as it proves
+            // the position of the key in the tree, whatever the value returned by the reader.
+            if let Some(root_hash) = root_hash.filter(|_| reader.are_state_values_provable()) {
                 proof
                     .verify_by_hash(root_hash, state_key.hash(), value_hash)
                     .map_err(|err| {
@@ -178,4 +182,49 @@ mod tests {
         assert_eq!(proofs.len(), 10);
         assert_eq_unordered!(proofs.into_keys().collect::<Vec<_>>(), expected_key_hashes);
     }
+
+    /// A reader returning values which are not in its state Merkle tree.
+    struct UnprovableDbReader;
+
+    impl DbReader for UnprovableDbReader {
+        fn get_state_proof_by_version_ext(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> Result<SparseMerkleProofExt> {
+            MockDbReaderWriter.get_state_proof_by_version_ext(state_key, version)
+        }
+
+        fn get_state_value_by_version(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> Result<Option<StateValue>> {
+            MockDbReaderWriter.get_state_value_by_version(state_key, version)
+        }
+
+        fn are_state_values_provable(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_fetch_unprovable_values() {
+        assert!(MockDbReaderWriter.are_state_values_provable());
+
+        let fetcher = AsyncProofFetcher::new(Arc::new(UnprovableDbReader));
+        let state_key = StateKey::raw(b"test_key".to_vec());
+        // The proofs of the mock reader do not match the root hash, which is not checked as the
+        // values are not provable.
+        let result = fetcher
+            .fetch_state_value_and_proof(&state_key, 0, Some(HashValue::random()))
+            .expect("Should not fail.");
+        assert_eq!(result.0, Some(StateValue::from(b"test_key".to_vec())));
+
+        let proofs = fetcher.get_proof_cache();
+        assert_eq!(
+            proofs.into_keys().collect::<Vec<_>>(),
+            vec![state_key.hash()]
+        );
+    }
 }
@@ -534,6 +534,14 @@ pub trait DbReader: Send + Sync {
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         unimplemented!()
     }
+
+    /// Returns whether all the state values returned by this reader are in its state Merkle tree,
+    /// i.e. whether they can be verified against the state proofs it returns. This is not the case
+    /// for a reader serving values from outside the local ledger (e.g. a local fork of a remote
+    /// network).
+    fn are_state_values_provable(&self) -> bool {
+        true
+    }
 }
 
 impl MoveStorage for &dyn DbReader {
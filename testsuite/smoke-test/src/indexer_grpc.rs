@@ -0,0 +1,183 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::smoke_test_environment::SwarmBuilder;
+use aptos_config::utils::get_available_port;
+use aptos_forge::Swarm;
+use aptos_indexer_grpc_cache_worker::worker::Worker;
+use aptos_indexer_grpc_data_service::service::DatastreamServer;
+use aptos_indexer_grpc_file_store::processor::Processor;
+use aptos_indexer_grpc_utils::{
+    cache_operator::{Cache, CacheClient},
+    config::{IndexerGrpcCacheConfig, IndexerGrpcConfig, IndexerGrpcFileStoreConfig},
+    constants::{BLOB_STORAGE_SIZE, GRPC_AUTH_TOKEN_HEADER},
+    file_store_operator::FileStoreOperator,
+};
+use aptos_protos::datastream::v1::{
+    indexer_stream_client::IndexerStreamClient, indexer_stream_server::IndexerStreamServer,
+    raw_datastream_response::Response, RawDatastreamRequest,
+};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const PIPELINE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Returns the address of the Redis used by the cache, or None to skip the test.
+fn get_redis_address() -> Option<String> {
+    std::env::var("INDEXER_GRPC_REDIS_ADDRESS").ok()
+}
+
+#[tokio::test]
+async fn test_indexer_grpc_with_redis_cache() {
+    let redis_address = match get_redis_address() {
+        Some(redis_address) => redis_address,
+        None => {
+            println!("Skipping: set 'INDEXER_GRPC_REDIS_ADDRESS' to run indexer grpc tests!");
+            return;
+        },
+    };
+    // Start from an empty cache.
+    let mut conn = redis::Client::open(format!("redis://{}", redis_address))
+        .unwrap()
+        .get_async_connection()
+        .await
+        .unwrap();
+    redis::cmd("FLUSHDB")
+        .query_async::<_, ()>(&mut conn)
+        .await
+        .unwrap();
+
+    run_indexer_grpc_pipeline(IndexerGrpcCacheConfig::RedisCache { redis_address }).await;
+}
+
+#[tokio::test]
+async fn test_indexer_grpc_with_in_memory_cache() {
+    run_indexer_grpc_pipeline(IndexerGrpcCacheConfig::InMemoryCache {
+        in_memory_cache_size_in_bytes: 100_000_000,
+    })
+    .await;
+}
+
+/// Runs the cache worker, file store processor and data service against a local node, with the
+/// file store in a local directory, and checks the first file of transactions is served.
+async fn run_indexer_grpc_pipeline(cache_config: IndexerGrpcCacheConfig) {
+    let fullnode_grpc_address = format!("127.0.0.1:{}", get_available_port());
+    let node_grpc_address = fullnode_grpc_address.clone();
+    let swarm = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .with_init_config(Arc::new(move |_, config, _| {
+            config.indexer_grpc.enabled = true;
+            config.indexer_grpc.address = Some(node_grpc_address.clone());
+        }))
+        .build()
+        .await;
+    let chain_id = swarm.chain_id().id() as u64;
+
+    let file_store_dir = tempfile::tempdir().unwrap();
+    let data_service_grpc_address = format!("127.0.0.1:{}", get_available_port());
+    let config = IndexerGrpcConfig {
+        fullnode_grpc_address: Some(fullnode_grpc_address),
+        data_service_grpc_listen_address: Some(data_service_grpc_address.clone()),
+        cache_config,
+        file_store_config: IndexerGrpcFileStoreConfig::LocalFileStore {
+            local_file_store_path: file_store_dir.path().to_path_buf(),
+        },
+        ..IndexerGrpcConfig::default()
+    };
+
+    // The cache worker requires the file store metadata to start.
+    let mut file_store_operator = FileStoreOperator::new(config.file_store_config.clone());
+    file_store_operator
+        .create_default_file_store_metadata_if_absent(chain_id)
+        .await
+        .unwrap();
+
+    // All the components share the cache client, i.e., the same in-memory cache.
+    let cache_client = CacheClient::new(&config.cache_config);
+    let worker_config = config.clone();
+    let worker_cache_client = cache_client.clone();
+    tokio::spawn(async move {
+        Worker::new(worker_config, worker_cache_client)
+            .await
+            .run()
+            .await;
+    });
+
+    // The file store processor requires the chain id in cache.
+    let mut cache = cache_client.get_cache().await.unwrap();
+    let start = Instant::now();
+    while cache.get_chain_id().await.is_err() {
+        assert!(start.elapsed() < PIPELINE_TIMEOUT, "Cache is not set up.");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    let processor_config = config.clone();
+    let processor_cache_client = cache_client.clone();
+    tokio::spawn(async move {
+        Processor::new(processor_config, processor_cache_client)
+            .run()
+            .await;
+    });
+
+    let data_service_config = config.clone();
+    let data_service_socket_address: SocketAddr = data_service_grpc_address.parse().unwrap();
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(IndexerStreamServer::new(DatastreamServer::new(
+                data_service_config,
+                cache_client,
+            )))
+            .serve(data_service_socket_address)
+            .await
+            .unwrap();
+    });
+
+    // Wait for the first file of transactions to be uploaded; the chain produces block metadata
+    // and state checkpoint transactions by itself.
+    let start = Instant::now();
+    loop {
+        let metadata = file_store_operator.get_file_store_metadata().await.unwrap();
+        assert_eq!(metadata.chain_id, chain_id);
+        if metadata.version >= BLOB_STORAGE_SIZE as u64 {
+            break;
+        }
+        assert!(
+            start.elapsed() < PIPELINE_TIMEOUT,
+            "Transactions are not uploaded to the file store."
+        );
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    let file_store_transactions = file_store_operator.get_transactions(0).await.unwrap();
+    assert_eq!(file_store_transactions.len(), BLOB_STORAGE_SIZE);
+
+    // Stream the same transactions from the data service.
+    let mut client = IndexerStreamClient::connect(format!("http://{}", data_service_grpc_address))
+        .await
+        .unwrap();
+    let mut request = tonic::Request::new(RawDatastreamRequest {
+        starting_version: Some(0),
+        ..RawDatastreamRequest::default()
+    });
+    request
+        .metadata_mut()
+        .insert(GRPC_AUTH_TOKEN_HEADER, "test-token".parse().unwrap());
+    let mut stream = client.raw_datastream(request).await.unwrap().into_inner();
+    let mut streamed_transactions = vec![];
+    while streamed_transactions.len() < BLOB_STORAGE_SIZE {
+        let response = stream.message().await.unwrap().unwrap();
+        if let Some(Response::Data(data)) = response.response {
+            assert_eq!(response.chain_id as u64, chain_id);
+            streamed_transactions.extend(data.transactions);
+        }
+    }
+    for (version, (transaction, encoded)) in streamed_transactions
+        .iter()
+        .zip(file_store_transactions.iter())
+        .enumerate()
+    {
+        assert_eq!(transaction.version, version as u64);
+        assert_eq!(&transaction.encoded_proto_data, encoded);
+    }
+}
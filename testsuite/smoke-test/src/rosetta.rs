@@ -18,7 +18,7 @@ use aptos_forge::{AptosPublicInfo, LocalSwarm, Node, NodeExt, Swarm};
 use aptos_gas::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_global_constants::GAS_UNIT_PRICE;
 use aptos_rest_client::{
-    aptos_api_types::{TransactionOnChainData, UserTransaction},
+    aptos_api_types::{EntryFunctionId, TransactionOnChainData, UserTransaction, ViewRequest},
     Response, Transaction,
 };
 use aptos_rosetta::{
@@ -94,6 +94,7 @@ pub async fn setup_test(
             validator.rest_api_endpoint(),
         )),
         cli.addresses(),
+        HashSet::new(),
     )
     .await
     .unwrap();
@@ -145,6 +146,7 @@ async fn test_block_transactions() {
             validator.rest_api_endpoint(),
         )),
         cli.addresses(),
+        HashSet::new(),
     )
     .await
     .unwrap();
@@ -166,10 +168,13 @@ async fn test_block_transactions() {
     )
     .await
     .unwrap();
-    assert_eq!(response.block_identifier, BlockIdentifier {
-        index: 0,
-        hash: BlockHash::new(chain_id, 0).to_string()
-    });
+    assert_eq!(
+        response.block_identifier,
+        BlockIdentifier {
+            index: 0,
+            hash: BlockHash::new(chain_id, 0).to_string()
+        }
+    );
 
     // First fund account 1 with lots more gas
     cli.fund_account(0, Some(DEFAULT_FUNDED_COINS * 10))
@@ -295,10 +300,13 @@ async fn test_account_balance() {
     )
     .await
     .unwrap();
-    assert_eq!(response.block_identifier, BlockIdentifier {
-        index: 0,
-        hash: BlockHash::new(chain_id, 0).to_string()
-    });
+    assert_eq!(
+        response.block_identifier,
+        BlockIdentifier {
+            index: 0,
+            hash: BlockHash::new(chain_id, 0).to_string()
+        }
+    );
 
     // First fund account 1 with lots more gas
     cli.fund_account(0, Some(DEFAULT_FUNDED_COINS * 2))
@@ -493,6 +501,261 @@ async fn test_account_balance() {
     .unwrap();*/
 }
 
+#[tokio::test]
+async fn test_delegation_pool_operations() {
+    // Delegators need at least 10 APT active and pending inactive in a delegation pool
+    const DELEGATED_AMOUNT: u64 = 2_000_000_000;
+    const UNLOCK_AMOUNT: u64 = 1_000_000_000;
+
+    let (mut swarm, cli, _faucet, rosetta_client) = setup_test(1, 1).await;
+    let chain_id = swarm.chain_id();
+    let network_identifier: NetworkIdentifier = chain_id.into();
+    let rest_client = swarm.validators().next().unwrap().rest_client();
+    let delegator = cli.account_id(0);
+    let delegator_key = cli.private_key(0);
+    cli.fund_account(0, Some(DEFAULT_FUNDED_COINS * 30))
+        .await
+        .unwrap();
+
+    let root_address = swarm.aptos_public_info().root_account().address();
+    let root_sequence_number = swarm
+        .aptos_public_info()
+        .client()
+        .get_account_bcs(root_address)
+        .await
+        .unwrap()
+        .into_inner()
+        .sequence_number();
+    *swarm
+        .aptos_public_info()
+        .root_account()
+        .sequence_number_mut() = root_sequence_number;
+    let mut pool_owner = swarm
+        .aptos_public_info()
+        .create_and_fund_user_account(10_000_000_000)
+        .await
+        .unwrap();
+    let pool_address = create_delegation_pool(&swarm.aptos_public_info(), &mut pool_owner).await;
+
+    let add_stake_txn = add_delegated_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        delegator_key,
+        pool_address,
+        DELEGATED_AMOUNT,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Should successfully add delegated stake");
+    assert_delegated_stake_operations_reconcile(
+        &rosetta_client,
+        &rest_client,
+        chain_id,
+        delegator,
+        pool_address,
+        &add_stake_txn,
+    )
+    .await;
+
+    // The pool isn't a validator yet, so there's no add stake fee
+    account_has_balance(
+        &rosetta_client,
+        chain_id,
+        AccountIdentifier::delegated_active_stake_account(delegator, pool_address),
+        DELEGATED_AMOUNT,
+        1,
+    )
+    .await
+    .unwrap();
+    account_has_balance(
+        &rosetta_client,
+        chain_id,
+        AccountIdentifier::delegated_total_stake_account(delegator, pool_address),
+        DELEGATED_AMOUNT,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let unlock_txn = unlock_delegated_stake_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        delegator_key,
+        pool_address,
+        UNLOCK_AMOUNT,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Should successfully unlock delegated stake");
+    assert_delegated_stake_operations_reconcile(
+        &rosetta_client,
+        &rest_client,
+        chain_id,
+        delegator,
+        pool_address,
+        &unlock_txn,
+    )
+    .await;
+
+    // Unlocked stake stays in the pool until it's withdrawn
+    account_has_balance(
+        &rosetta_client,
+        chain_id,
+        AccountIdentifier::delegated_active_stake_account(delegator, pool_address),
+        DELEGATED_AMOUNT - UNLOCK_AMOUNT,
+        2,
+    )
+    .await
+    .unwrap();
+    account_has_balance(
+        &rosetta_client,
+        chain_id,
+        AccountIdentifier::delegated_total_stake_account(delegator, pool_address),
+        DELEGATED_AMOUNT,
+        2,
+    )
+    .await
+    .unwrap();
+
+    // The pool isn't a validator, so unlocked stake can be withdrawn right away
+    let withdraw_txn = withdraw_undelegated_funds_and_wait(
+        &rosetta_client,
+        &rest_client,
+        &network_identifier,
+        delegator_key,
+        pool_address,
+        UNLOCK_AMOUNT,
+        Duration::from_secs(5),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Should successfully withdraw undelegated funds");
+    assert_delegated_stake_operations_reconcile(
+        &rosetta_client,
+        &rest_client,
+        chain_id,
+        delegator,
+        pool_address,
+        &withdraw_txn,
+    )
+    .await;
+
+    account_has_balance(
+        &rosetta_client,
+        chain_id,
+        AccountIdentifier::delegated_total_stake_account(delegator, pool_address),
+        DELEGATED_AMOUNT - UNLOCK_AMOUNT,
+        3,
+    )
+    .await
+    .unwrap();
+}
+
+/// Checks that the operations on the delegated stake sub-accounts in the transaction add up to
+/// the changes of their balances over its block
+async fn assert_delegated_stake_operations_reconcile(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    chain_id: ChainId,
+    delegator: AccountAddress,
+    pool_address: AccountAddress,
+    txn: &UserTransaction,
+) {
+    let block_height = rest_client
+        .get_block_by_version_bcs(txn.info.version.0, false)
+        .await
+        .unwrap()
+        .into_inner()
+        .block_height;
+    let block = rosetta_client
+        .block(&BlockRequest::by_index(chain_id, block_height))
+        .await
+        .unwrap()
+        .block;
+    let transaction = block
+        .transactions
+        .iter()
+        .find(|transaction| transaction.metadata.version == txn.info.version)
+        .expect("Transaction should be in its block");
+
+    for account in [
+        AccountIdentifier::delegated_active_stake_account(delegator, pool_address),
+        AccountIdentifier::delegated_pending_inactive_stake_account(delegator, pool_address),
+        AccountIdentifier::delegated_inactive_stake_account(delegator, pool_address),
+    ] {
+        let operations_sum: i128 = transaction
+            .operations
+            .iter()
+            .filter(|operation| operation.account.as_ref() == Some(&account))
+            .map(|operation| {
+                operation
+                    .amount
+                    .as_ref()
+                    .expect("Should have an amount in a stake operation")
+                    .value
+                    .parse::<i128>()
+                    .expect("Should be able to parse amount value")
+            })
+            .sum();
+
+        let mut balances = vec![];
+        for index in [block_height - 1, block_height] {
+            let response = get_balance(rosetta_client, chain_id, account.clone(), Some(index))
+                .await
+                .unwrap();
+            let balance = response
+                .balances
+                .iter()
+                .find(|amount| amount.currency == native_coin())
+                .map_or(0, |amount| amount.value.parse::<i128>().unwrap());
+            balances.push(balance);
+        }
+        assert_eq!(
+            balances[1] - balances[0],
+            operations_sum,
+            "Operations on {:?} should add up to its balance change",
+            account
+        );
+    }
+}
+
+async fn create_delegation_pool(
+    info: &AptosPublicInfo<'_>,
+    account: &mut LocalAccount,
+) -> AccountAddress {
+    let pool_creation = info
+        .transaction_factory()
+        .payload(aptos_stdlib::delegation_pool_initialize_delegation_pool(
+            10,
+            vec![],
+        ))
+        .sequence_number(0);
+
+    let txn = account.sign_with_transaction_builder(pool_creation);
+    info.client().submit_and_wait(&txn).await.unwrap();
+
+    let request = ViewRequest {
+        function: EntryFunctionId::from_str("0x1::delegation_pool::get_owned_pool_address")
+            .unwrap(),
+        type_arguments: vec![],
+        arguments: vec![serde_json::Value::String(
+            account.address().to_hex_literal(),
+        )],
+    };
+    let response = info.client().view(&request, None).await.unwrap();
+    AccountAddress::from_hex_literal(response.into_inner()[0].as_str().unwrap()).unwrap()
+}
+
 async fn create_staking_contract(
     info: &AptosPublicInfo<'_>,
     account: &mut LocalAccount,
@@ -1312,10 +1575,15 @@ async fn parse_operations(
                 }
             },
             OperationType::Deposit => {
-                let account = operation
+                let account_identifier = operation
                     .account
                     .as_ref()
-                    .expect("There should be an account in a deposit operation")
+                    .expect("There should be an account in a deposit operation");
+                // Only coin balances are tracked, stake moves between sub-accounts
+                if !account_identifier.is_base_account() {
+                    continue;
+                }
+                let account = account_identifier
                     .account_address()
                     .expect("Account address should be parsable");
 
@@ -1356,10 +1624,15 @@ async fn parse_operations(
                 // Gas is always successful
                 if actual_successful {
                     assert_eq!(OperationStatusType::Success, status);
-                    let account = operation
+                    let account_identifier = operation
                         .account
                         .as_ref()
-                        .expect("There should be an account in a withdraw operation")
+                        .expect("There should be an account in a withdraw operation");
+                    // Only coin balances are tracked, stake moves between sub-accounts
+                    if !account_identifier.is_base_account() {
+                        continue;
+                    }
+                    let account = account_identifier
                         .account_address()
                         .expect("Account address should be parsable");
 
@@ -1818,6 +2091,67 @@ async fn parse_operations(
                     panic!("Not a user transaction");
                 }
             },
+            OperationType::AddDelegatedStake
+            | OperationType::UnlockDelegatedStake
+            | OperationType::WithdrawUndelegatedFunds => {
+                if actual_successful {
+                    assert_eq!(
+                        OperationStatusType::Success,
+                        status,
+                        "Successful transaction should have successful {} operation",
+                        operation_type
+                    );
+                } else {
+                    assert_eq!(
+                        OperationStatusType::Failure,
+                        status,
+                        "Failed transaction should have failed {} operation",
+                        operation_type
+                    );
+                }
+
+                // Check that the pool and amount were set the same
+                if let aptos_types::transaction::Transaction::UserTransaction(ref txn) =
+                    actual_txn.transaction
+                {
+                    if let aptos_types::transaction::TransactionPayload::EntryFunction(
+                        ref payload,
+                    ) = txn.payload()
+                    {
+                        let actual_pool_address: AccountAddress =
+                            bcs::from_bytes(payload.args().first().unwrap()).unwrap();
+                        let pool_address = operation
+                            .metadata
+                            .as_ref()
+                            .unwrap()
+                            .pool_address
+                            .as_ref()
+                            .unwrap()
+                            .account_address()
+                            .unwrap();
+                        assert_eq!(actual_pool_address, pool_address);
+
+                        // Successful operations carry the amount that actually moved
+                        if !actual_successful {
+                            let actual_amount: u64 =
+                                bcs::from_bytes(payload.args().get(1).unwrap()).unwrap();
+                            let amount = operation
+                                .metadata
+                                .as_ref()
+                                .unwrap()
+                                .amount
+                                .as_ref()
+                                .unwrap()
+                                .0;
+                            assert_eq!(actual_amount, amount);
+                        }
+                    } else {
+                        panic!("Not an entry function");
+                    }
+                } else {
+                    panic!("Not a user transaction");
+                }
+            },
         }
     }
 
@@ -2365,6 +2699,102 @@ async fn distribute_staking_rewards_and_wait(
         .map_err(ErrorWrapper::AfterSubmission)
 }
 
+async fn add_delegated_stake_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    pool_address: AccountAddress,
+    amount: u64,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .add_delegated_stake(
+            network_identifier,
+            sender_key,
+            pool_address,
+            amount,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+async fn unlock_delegated_stake_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    pool_address: AccountAddress,
+    amount: u64,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .unlock_delegated_stake(
+            network_identifier,
+            sender_key,
+            pool_address,
+            amount,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
+async fn withdraw_undelegated_funds_and_wait(
+    rosetta_client: &RosettaClient,
+    rest_client: &aptos_rest_client::Client,
+    network_identifier: &NetworkIdentifier,
+    sender_key: &Ed25519PrivateKey,
+    pool_address: AccountAddress,
+    amount: u64,
+    txn_expiry_duration: Duration,
+    sequence_number: Option<u64>,
+    max_gas: Option<u64>,
+    gas_unit_price: Option<u64>,
+) -> Result<Box<UserTransaction>, ErrorWrapper> {
+    let expiry_time = expiry_time(txn_expiry_duration);
+    let txn_hash = rosetta_client
+        .withdraw_undelegated_funds(
+            network_identifier,
+            sender_key,
+            pool_address,
+            amount,
+            expiry_time.as_secs(),
+            sequence_number,
+            max_gas,
+            gas_unit_price,
+        )
+        .await
+        .map_err(ErrorWrapper::BeforeSubmission)?
+        .hash;
+    wait_for_transaction(rest_client, expiry_time, txn_hash)
+        .await
+        .map_err(ErrorWrapper::AfterSubmission)
+}
+
 async fn wait_for_transaction(
     rest_client: &aptos_rest_client::Client,
     expiry_time: Duration,
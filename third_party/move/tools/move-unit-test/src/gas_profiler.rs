@@ -0,0 +1,49 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module manages an optional gas profiler for unit tests. The unit testing framework
+//! bounds test execution with its own instruction-counting cost table, which says nothing about
+//! what a test would cost on a real chain. Environments embedding the framework can install a
+//! profiler which re-executes every test under their own gas meter.
+
+use move_binary_format::errors::VMResult;
+use move_core_types::{identifier::IdentStr, language_storage::ModuleId};
+use move_vm_runtime::native_extensions::NativeContextExtensions;
+use move_vm_test_utils::InMemoryStorage;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// A profiler which is invoked once for every unit test executed by the Move VM.
+pub trait TestGasProfiler: Send + Sync {
+    /// Executes the test function `module_id::function_name` with the serialized `args` against
+    /// `storage`, which contains all modules of the package under test. `extensions` are the
+    /// native extensions which would have been used for the regular test run.
+    ///
+    /// This is called after the regular run, independently of its outcome, and may be called
+    /// from multiple threads at the same time. Returns an error if the test could not be profiled
+    /// (e.g. because of an invariant violation in the VM), which fails the test.
+    fn profile_test<'a>(
+        &self,
+        storage: &'a InMemoryStorage,
+        extensions: NativeContextExtensions<'a>,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        args: Vec<Vec<u8>>,
+    ) -> VMResult<()>;
+}
+
+static GAS_PROFILER_HOOK: Lazy<Mutex<Option<Arc<dyn TestGasProfiler>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Sets a profiler which is called for every unit test, in addition to running the test itself.
+///
+/// Like `extensions::set_extension_hook`, this needs to be called before
+/// `cli::run_move_unit_tests`. The profiler stays installed until it is reset.
+pub fn set_gas_profiler_hook(profiler: Option<Arc<dyn TestGasProfiler>>) {
+    *GAS_PROFILER_HOOK.lock().unwrap() = profiler
+}
+
+/// Returns the currently installed profiler, if any.
+pub(crate) fn gas_profiler() -> Option<Arc<dyn TestGasProfiler>> {
+    GAS_PROFILER_HOOK.lock().unwrap().clone()
+}
@@ -46,6 +46,8 @@ pub enum FailureReason {
     },
     // Property checking failed
     Property(String),
+    // The gas profiler failed to execute the test
+    GasProfiling(String),
 
     // Failed to compile Move code into EVM bytecode.
     #[cfg(feature = "evm-backend")]
@@ -137,6 +139,10 @@ impl FailureReason {
         FailureReason::Property(details)
     }
 
+    pub fn gas_profiling() -> Self {
+        FailureReason::GasProfiling("Test could not be profiled for gas".to_string())
+    }
+
     #[cfg(feature = "evm-backend")]
     pub fn move_to_evm_error(diagnostics: String) -> Self {
         FailureReason::MoveToEVMError(diagnostics)
@@ -224,6 +230,10 @@ impl TestFailure {
                 )
             },
             FailureReason::Property(message) => message.clone(),
+            FailureReason::GasProfiling(message) => {
+                let base_message = format!("{}, as it failed with an error rooted here", message);
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+            },
 
             #[cfg(feature = "evm-backend")]
             FailureReason::MoveToEVMError(diagnostics) => {
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    extensions, format_module_id,
+    extensions, format_module_id, gas_profiler,
     test_reporter::{
         FailureReason, MoveError, TestFailure, TestResults, TestRunInfo, TestStatistics,
     },
@@ -419,6 +419,28 @@ impl SharedTestingConfig {
             let (cs_result, ext_result, exec_result, test_run_info) =
                 self.execute_via_move_vm(test_plan, function_name, test_info);
 
+            if let Some(profiler) = gas_profiler::gas_profiler() {
+                if let Err(err) = profiler.profile_test(
+                    &self.starting_storage_state,
+                    extensions::new_extensions(),
+                    &test_plan.module_id,
+                    IdentStr::new(function_name).unwrap(),
+                    serialize_values(test_info.arguments.iter()),
+                ) {
+                    output.fail(function_name);
+                    stats.test_failure(
+                        TestFailure::new(
+                            FailureReason::gas_profiling(),
+                            test_run_info,
+                            Some(err),
+                            None,
+                        ),
+                        test_plan,
+                    );
+                    continue;
+                }
+            }
+
             if self.record_writeset {
                 stats.test_output(
                     function_name.to_string(),
@@ -170,6 +170,19 @@ pub fn create_object_address(creator: AccountAddress, seed: &[u8]) -> AccountAdd
     AccountAddress::from_bytes(hash.as_ref()).unwrap()
 }
 
+/// Address of the object derived from `derive_from` for `source`, e.g., the primary fungible
+/// store of an account.
+pub fn create_derived_object_address(
+    source: AccountAddress,
+    derive_from: AccountAddress,
+) -> AccountAddress {
+    let mut input = bcs::to_bytes(&source).unwrap();
+    input.extend(bcs::to_bytes(&derive_from).unwrap());
+    input.push(Scheme::DeriveObjectAddressFromObject as u8);
+    let hash = HashValue::sha3_256_of(&input);
+    AccountAddress::from_bytes(hash.as_ref()).unwrap()
+}
+
 pub fn default_owner_stake_pool_address(owner: AccountAddress) -> AccountAddress {
     default_stake_pool_address(owner, owner)
 }
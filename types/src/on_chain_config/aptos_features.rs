@@ -25,6 +25,11 @@ pub enum FeatureFlag {
     STRUCT_CONSTRUCTORS = 15,
     PERIODICAL_REWARD_RATE_DECREASE = 16,
     PARTIAL_GOVERNANCE_VOTING = 17,
+    SECP256K1_ECDSA_AUTHENTICATOR = 18,
+    WEBAUTHN_AUTHENTICATOR = 19,
+    BN254_STRUCTURES = 20,
+    POSEIDON_HASH_NATIVES = 21,
+    AGGREGATOR_API = 22,
 }
 
 /// Representation of features on chain as a bitset.
@@ -37,7 +42,7 @@ pub struct Features {
 impl Default for Features {
     fn default() -> Self {
         Features {
-            features: vec![0b00100000, 0b00100000],
+            features: vec![0b00100000, 0b00100000, 0b00010000],
         }
     }
 }
@@ -2,8 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    proof::SparseMerkleRangeProof, state_store::state_key::StateKey, transaction::Version,
+    ledger_info::LedgerInfo,
+    proof::{SparseMerkleProof, SparseMerkleRangeProof, TransactionInfoWithProof},
+    state_store::state_key::StateKey,
+    transaction::Version,
 };
+use anyhow::ensure;
 use aptos_crypto::{
     hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
     HashValue,
@@ -178,6 +182,47 @@ impl StateValueChunkWithProof {
     }
 }
 
+/// A list of state values at a specific version, where each state value is
+/// accompanied by a sparse merkle proof (i.e., the value is proven to exist,
+/// or is proven to be missing if `None`). The proofs are relative to the state
+/// checkpoint hash in the transaction info at the version, which is itself
+/// proven relative to a ledger info at the same version.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueListWithProof {
+    pub version: Version, // The version of the state values
+    pub state_values: Vec<(StateKey, Option<StateValue>, SparseMerkleProof)>, // The state values and proofs
+    pub next_state_key: Option<StateKey>, // The next state key to fetch (if the list was truncated)
+    pub transaction_info_with_proof: TransactionInfoWithProof, // The transaction info at the version
+}
+
+impl StateValueListWithProof {
+    /// Verifies the state values (and the transaction info) against the
+    /// given ledger info. The ledger info must be at the same version.
+    pub fn verify(&self, ledger_info: &LedgerInfo) -> anyhow::Result<()> {
+        ensure!(
+            ledger_info.version() == self.version,
+            "The ledger info version ({}) does not match the state values version ({})!",
+            ledger_info.version(),
+            self.version
+        );
+
+        // Verify the transaction info and fetch the state root hash
+        self.transaction_info_with_proof
+            .verify(ledger_info, self.version)?;
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()?;
+
+        // Verify each state value against the state root hash
+        for (state_key, state_value, proof) in &self.state_values {
+            proof.verify(state_root_hash, state_key.hash(), state_value.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Indicates a state value becomes stale since `stale_since_version`.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
@@ -4,13 +4,17 @@
 
 use crate::{
     account_address::AccountAddress,
-    transaction::{RawTransaction, RawTransactionWithData},
+    transaction::{
+        webauthn::PartialAuthenticatorAssertionResponse, RawTransaction, RawTransactionWithData,
+    },
 };
 use anyhow::{ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256k1_ecdsa::{Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature},
+    secp256r1_ecdsa::Secp256r1EcdsaPublicKey,
     traits::Signature,
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
@@ -58,6 +62,8 @@ pub enum TransactionAuthenticator {
         secondary_signer_addresses: Vec<AccountAddress>,
         secondary_signers: Vec<AccountAuthenticator>,
     },
+    /// Single sender transaction, authenticated by any supported `AccountAuthenticator`
+    SingleSender { sender: AccountAuthenticator },
 }
 
 impl TransactionAuthenticator {
@@ -93,6 +99,11 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a single sender authenticator
+    pub fn single_sender(sender: AccountAuthenticator) -> Self {
+        Self::SingleSender { sender }
+    }
+
     /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
     pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
         let num_sigs: usize = self.sender().number_of_signatures()
@@ -128,6 +139,7 @@ impl TransactionAuthenticator {
                 }
                 Ok(())
             },
+            Self::SingleSender { sender } => sender.verify(raw_txn),
         }
     }
 
@@ -144,7 +156,7 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
-            Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::MultiAgent { sender, .. } | Self::SingleSender { sender } => sender.clone(),
         }
     }
 
@@ -154,7 +166,8 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::SingleSender { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses,
@@ -169,7 +182,8 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::SingleSender { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses: _,
@@ -225,6 +239,13 @@ impl fmt::Display for TransactionAuthenticator {
                     sender, sec_addrs, sec_signers,
                 )
             },
+            Self::SingleSender { sender } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: SingleSender, sender: {}]",
+                    sender
+                )
+            },
         }
     }
 }
@@ -242,11 +263,14 @@ impl fmt::Display for TransactionAuthenticator {
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    Secp256k1Ecdsa = 2,
+    WebAuthn = 3,
     // ... add more schemes here
     /// Scheme identifier used to derive addresses (not the authentication key) of objects and
     /// resources accounts. This application serves to domain separate hashes. Without such
     /// separation, an adversary could create (and get a signer for) a these accounts
     /// when a their address matches matches an existing address of a MultiEd25519 wallet.
+    DeriveObjectAddressFromObject = 252,
     DeriveObjectAddressFromGuid = 253,
     DeriveObjectAddressFromSeed = 254,
     DeriveResourceAccountAddress = 255,
@@ -257,6 +281,9 @@ impl fmt::Display for Scheme {
         let display = match self {
             Scheme::Ed25519 => "Ed25519",
             Scheme::MultiEd25519 => "MultiEd25519",
+            Scheme::Secp256k1Ecdsa => "Secp256k1Ecdsa",
+            Scheme::WebAuthn => "WebAuthn",
+            Scheme::DeriveObjectAddressFromObject => "DeriveObjectAddressFromObject",
             Scheme::DeriveObjectAddressFromGuid => "DeriveObjectAddressFromGuid",
             Scheme::DeriveObjectAddressFromSeed => "DeriveObjectAddressFromSeed",
             Scheme::DeriveResourceAccountAddress => "DeriveResourceAccountAddress",
@@ -277,6 +304,16 @@ pub enum AccountAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// Single secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    },
+    /// WebAuthn (e.g., passkey) assertion, signed with a secp256r1 ECDSA key
+    WebAuthn {
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    },
     // ... add more schemes here
 }
 
@@ -286,6 +323,8 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+            Self::WebAuthn { .. } => Scheme::WebAuthn,
         }
     }
 
@@ -308,6 +347,28 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    ) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a WebAuthn authenticator
+    pub fn webauthn(
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    ) -> Self {
+        Self::WebAuthn {
+            public_key,
+            signature,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -319,6 +380,14 @@ impl AccountAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::WebAuthn {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
         }
     }
 
@@ -327,6 +396,8 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::WebAuthn { public_key, .. } => public_key.to_bytes().to_vec(),
         }
     }
 
@@ -335,6 +406,8 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { signature, .. } => signature.to_bytes().to_vec(),
+            Self::WebAuthn { signature, .. } => signature.to_bytes(),
         }
     }
 
@@ -351,7 +424,7 @@ impl AccountAuthenticator {
     /// Return the number of signatures included in this account authenticator.
     pub fn number_of_signatures(&self) -> usize {
         match self {
-            Self::Ed25519 { .. } => 1,
+            Self::Ed25519 { .. } | Self::Secp256k1Ecdsa { .. } | Self::WebAuthn { .. } => 1,
             Self::MultiEd25519 { signature, .. } => signature.signatures().len(),
         }
     }
@@ -405,6 +478,16 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from a secp256k1 ECDSA public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1EcdsaPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256k1_ecdsa(public_key))
+    }
+
+    /// Create an authentication key from a WebAuthn (secp256r1 ECDSA) public key
+    pub fn webauthn(public_key: &Secp256r1EcdsaPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::webauthn(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -460,6 +543,16 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from a secp256k1 ECDSA public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1EcdsaPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::Secp256k1Ecdsa)
+    }
+
+    /// Construct a preimage from a WebAuthn (secp256r1 ECDSA) public key
+    pub fn webauthn(public_key: &Secp256r1EcdsaPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::WebAuthn)
+    }
+
     /// Construct a vector from this authentication key
     pub fn into_vec(self) -> Vec<u8> {
         self.0
@@ -534,11 +627,88 @@ impl fmt::Display for AuthenticationKey {
 
 #[cfg(test)]
 mod tests {
-    use crate::transaction::authenticator::AuthenticationKey;
+    use crate::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey, TransactionAuthenticator},
+            webauthn::create_assertion_for_testing,
+            RawTransaction, Script, SignedTransaction,
+        },
+    };
+    use aptos_crypto::{
+        secp256k1_ecdsa::{Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey},
+        secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey},
+        traits::{SigningKey, Uniform},
+    };
     use std::str::FromStr;
 
+    fn create_raw_transaction(sender: AccountAddress) -> RawTransaction {
+        RawTransaction::new_script(
+            sender,
+            0,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        )
+    }
+
     #[test]
     fn test_from_str_should_not_panic_by_given_empty_string() {
         assert!(AuthenticationKey::from_str("").is_err());
     }
+
+    #[test]
+    fn test_single_sender_secp256k1_ecdsa() {
+        let private_key = Secp256k1EcdsaPrivateKey::generate_for_testing();
+        let public_key = Secp256k1EcdsaPublicKey::from(&private_key);
+        let sender = AuthenticationKey::secp256k1_ecdsa(&public_key).derived_address();
+        let raw_txn = create_raw_transaction(sender);
+
+        // Verify the signed transaction and its authentication key
+        let signed_txn = raw_txn
+            .clone()
+            .sign_secp256k1_ecdsa(&private_key, public_key.clone())
+            .unwrap()
+            .into_inner();
+        assert!(signed_txn.signature_is_valid());
+        assert_eq!(
+            signed_txn.authenticator().sender().authentication_key(),
+            AuthenticationKey::secp256k1_ecdsa(&public_key)
+        );
+
+        // Verify a signature by a different key fails
+        let other_private_key = Secp256k1EcdsaPrivateKey::generate_for_testing();
+        let signature = other_private_key.sign(&raw_txn).unwrap();
+        let authenticator = AccountAuthenticator::secp256k1_ecdsa(public_key, signature);
+        let signed_txn = SignedTransaction::new_single_sender(raw_txn, authenticator);
+        assert!(!signed_txn.signature_is_valid());
+    }
+
+    #[test]
+    fn test_single_sender_webauthn() {
+        let private_key = Secp256r1EcdsaPrivateKey::generate_for_testing();
+        let public_key = Secp256r1EcdsaPublicKey::from(&private_key);
+        let sender = AuthenticationKey::webauthn(&public_key).derived_address();
+        let raw_txn = create_raw_transaction(sender);
+
+        // Verify the signed transaction
+        let assertion = create_assertion_for_testing(&private_key, &raw_txn);
+        let authenticator = AccountAuthenticator::webauthn(public_key.clone(), assertion);
+        let signed_txn = SignedTransaction::new_single_sender(raw_txn.clone(), authenticator);
+        assert!(signed_txn.signature_is_valid());
+        assert!(matches!(
+            signed_txn.authenticator(),
+            TransactionAuthenticator::SingleSender { .. }
+        ));
+
+        // Verify an assertion for a different transaction fails
+        let other_raw_txn = create_raw_transaction(AccountAddress::random());
+        let assertion = create_assertion_for_testing(&private_key, &other_raw_txn);
+        let authenticator = AccountAuthenticator::webauthn(public_key, assertion);
+        let signed_txn = SignedTransaction::new_single_sender(raw_txn, authenticator);
+        assert!(!signed_txn.signature_is_valid());
+    }
 }
@@ -0,0 +1,235 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for authenticating transactions with WebAuthn assertions (e.g., produced by passkeys).
+//!
+//! A WebAuthn authenticator never signs the transaction directly. Instead, the client passes a
+//! challenge to the authenticator (the SHA3-256 hash of the transaction signing message), and the
+//! authenticator signs `authenticator_data || SHA2-256(client_data_json)` using a secp256r1 key,
+//! where `client_data_json` embeds the (base64url encoded) challenge. See the WebAuthn spec for
+//! more details: https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion.
+
+use anyhow::{ensure, Result};
+use aptos_crypto::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature},
+    signing_message,
+    traits::Signature,
+    HashValue,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The type of the client data for assertions (i.e., `navigator.credentials.get()`)
+pub const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+
+/// The minimum length of the authenticator data (i.e., RP ID hash, flags and sign count)
+const MIN_AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// The index of the flags byte in the authenticator data
+const AUTHENTICATOR_DATA_FLAGS_INDEX: usize = 32;
+
+/// The flag (bit) in the authenticator data that indicates the user was present
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// The subset of the WebAuthn `CollectedClientData` that is required for verification
+#[derive(Debug, Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    client_data_type: String,
+    challenge: String,
+}
+
+/// A WebAuthn assertion over a transaction, holding the fields of an `AuthenticatorAssertionResponse`
+/// required for signature verification.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PartialAuthenticatorAssertionResponse {
+    /// The secp256r1 signature over `authenticator_data || SHA2-256(client_data_json)`
+    signature: Secp256r1EcdsaSignature,
+    /// The raw authenticator data returned by the authenticator
+    #[serde(with = "serde_bytes")]
+    authenticator_data: Vec<u8>,
+    /// The raw (UTF-8 encoded) client data JSON returned by the client
+    #[serde(with = "serde_bytes")]
+    client_data_json: Vec<u8>,
+}
+
+impl PartialAuthenticatorAssertionResponse {
+    pub fn new(
+        signature: Secp256r1EcdsaSignature,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            signature,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    pub fn signature(&self) -> &Secp256r1EcdsaSignature {
+        &self.signature
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &[u8] {
+        &self.client_data_json
+    }
+
+    /// Returns the BCS serialized bytes of the assertion
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Unable to serialize the WebAuthn assertion!")
+    }
+
+    /// Returns Ok iff the assertion was produced (by the owner of `public_key`)
+    /// for the challenge derived from `message`.
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        // Verify the client data is for an assertion over the expected challenge
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)?;
+        ensure!(
+            client_data.client_data_type == WEBAUTHN_GET_TYPE,
+            "Unexpected WebAuthn client data type: {}",
+            client_data.client_data_type
+        );
+        let challenge = base64::decode_config(&client_data.challenge, base64::URL_SAFE_NO_PAD)?;
+        let expected_challenge = generate_challenge(&signing_message(message)?);
+        ensure!(
+            challenge == expected_challenge.to_vec(),
+            "The WebAuthn challenge does not match the expected challenge!"
+        );
+
+        // Verify the authenticator data is well-formed and the user was present
+        ensure!(
+            self.authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LENGTH,
+            "The WebAuthn authenticator data is too short! Length: {}",
+            self.authenticator_data.len()
+        );
+        ensure!(
+            self.authenticator_data[AUTHENTICATOR_DATA_FLAGS_INDEX] & USER_PRESENT_FLAG != 0,
+            "The WebAuthn authenticator data does not indicate user presence!"
+        );
+
+        // Verify the signature over the authenticator data and client data hash
+        let verification_data = verification_data(&self.authenticator_data, &self.client_data_json);
+        self.signature
+            .verify_arbitrary_msg(&verification_data, public_key)
+    }
+}
+
+/// Returns the data signed by the authenticator: `authenticator_data || SHA2-256(client_data_json)`
+fn verification_data(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    let client_data_hash = Sha256::digest(client_data_json);
+    [authenticator_data, client_data_hash.as_slice()].concat()
+}
+
+/// Returns the WebAuthn challenge for the given signing message. This is the
+/// value clients should pass to the authenticator when signing a transaction.
+pub fn generate_challenge(signing_message: &[u8]) -> HashValue {
+    HashValue::sha3_256_of(signing_message)
+}
+
+/// Creates a WebAuthn assertion for the given message by emulating an authenticator with the
+/// given private key (for testing only).
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn create_assertion_for_testing<T: Serialize + CryptoHash>(
+    private_key: &aptos_crypto::secp256r1_ecdsa::Secp256r1EcdsaPrivateKey,
+    message: &T,
+) -> PartialAuthenticatorAssertionResponse {
+    use aptos_crypto::traits::SigningKey;
+
+    // Create the client data for the challenge
+    let challenge = generate_challenge(&signing_message(message).unwrap());
+    let client_data_json = format!(
+        r#"{{"type":"{}","challenge":"{}","origin":"https://aptoslabs.com"}}"#,
+        WEBAUTHN_GET_TYPE,
+        base64::encode_config(challenge.to_vec(), base64::URL_SAFE_NO_PAD)
+    )
+    .into_bytes();
+
+    // Create the authenticator data (RP ID hash, flags and sign count)
+    let mut authenticator_data = HashValue::sha3_256_of(b"aptoslabs.com").to_vec();
+    authenticator_data.push(USER_PRESENT_FLAG);
+    authenticator_data.extend_from_slice(&[0u8; 4]);
+
+    // Sign the verification data
+    let signature = private_key
+        .sign_arbitrary_message(&verification_data(&authenticator_data, &client_data_json));
+    PartialAuthenticatorAssertionResponse::new(signature, authenticator_data, client_data_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{
+        secp256r1_ecdsa::Secp256r1EcdsaPrivateKey, test_utils::TestAptosCrypto, Uniform,
+    };
+
+    fn create_test_message() -> TestAptosCrypto {
+        TestAptosCrypto("Test message".to_string())
+    }
+
+    #[test]
+    fn test_verify_assertion() {
+        let private_key = Secp256r1EcdsaPrivateKey::generate_for_testing();
+        let public_key = Secp256r1EcdsaPublicKey::from(&private_key);
+        let message = create_test_message();
+
+        // Verify a valid assertion
+        let assertion = create_assertion_for_testing(&private_key, &message);
+        assert!(assertion.verify(&message, &public_key).is_ok());
+
+        // Verify the assertion fails for a different message
+        let other_message = TestAptosCrypto("Other message".to_string());
+        assert!(assertion.verify(&other_message, &public_key).is_err());
+
+        // Verify the assertion fails for a different public key
+        let other_private_key = Secp256r1EcdsaPrivateKey::generate_for_testing();
+        let other_public_key = Secp256r1EcdsaPublicKey::from(&other_private_key);
+        assert!(assertion.verify(&message, &other_public_key).is_err());
+    }
+
+    #[test]
+    fn test_verify_assertion_invalid_data() {
+        let private_key = Secp256r1EcdsaPrivateKey::generate_for_testing();
+        let public_key = Secp256r1EcdsaPublicKey::from(&private_key);
+        let message = create_test_message();
+        let assertion = create_assertion_for_testing(&private_key, &message);
+
+        // Verify the assertion fails if the user was not present
+        let mut authenticator_data = assertion.authenticator_data().to_vec();
+        authenticator_data[AUTHENTICATOR_DATA_FLAGS_INDEX] = 0;
+        let invalid_assertion = PartialAuthenticatorAssertionResponse::new(
+            assertion.signature().clone(),
+            authenticator_data,
+            assertion.client_data_json().to_vec(),
+        );
+        assert!(invalid_assertion.verify(&message, &public_key).is_err());
+
+        // Verify the assertion fails if the client data is modified
+        let client_data_json = String::from_utf8(assertion.client_data_json().to_vec())
+            .unwrap()
+            .replace(WEBAUTHN_GET_TYPE, "webauthn.create")
+            .into_bytes();
+        let invalid_assertion = PartialAuthenticatorAssertionResponse::new(
+            assertion.signature().clone(),
+            assertion.authenticator_data().to_vec(),
+            client_data_json,
+        );
+        assert!(invalid_assertion.verify(&message, &public_key).is_err());
+
+        // Verify the assertion fails if the authenticator data is truncated
+        let invalid_assertion = PartialAuthenticatorAssertionResponse::new(
+            assertion.signature().clone(),
+            assertion.authenticator_data()[..MIN_AUTHENTICATOR_DATA_LENGTH - 1].to_vec(),
+            assertion.client_data_json().to_vec(),
+        );
+        assert!(invalid_assertion.verify(&message, &public_key).is_err());
+    }
+}
@@ -5,4 +5,5 @@
 #![forbid(unsafe_code)]
 
 pub mod mocks;
+pub mod pre_executor;
 pub mod vm_validator;
@@ -0,0 +1,61 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_storage_interface::{
+    cached_state_view::CachedDbStateView, state_view::LatestDbStateCheckpointView, DbReader,
+};
+use aptos_types::transaction::SignedTransaction;
+use aptos_vm::{pre_execution_cache::PreExecutor, AptosVM};
+use std::sync::Arc;
+
+#[cfg(test)]
+#[path = "unit_tests/pre_executor_test.rs"]
+mod pre_executor_test;
+
+pub trait TransactionPreExecution: Send + Sync {
+    /// Executes the transactions ahead of time, and returns the number of newly cached outputs.
+    fn pre_execute(&self, txns: Vec<SignedTransaction>) -> Result<usize>;
+}
+
+/// Executes transactions ahead of time (i.e., while they wait in mempool) against the latest
+/// committed state, and caches their outputs so that block execution can reuse them.
+pub struct TransactionPreExecutor {
+    db_reader: Arc<dyn DbReader>,
+}
+
+impl TransactionPreExecutor {
+    pub fn new(db_reader: Arc<dyn DbReader>) -> Self {
+        Self { db_reader }
+    }
+}
+
+impl TransactionPreExecution for TransactionPreExecutor {
+    /// Executes the transactions that were not yet executed against the latest committed state,
+    /// and returns the number of newly cached outputs. Does nothing if the cache is not enabled
+    /// (see `AptosVM::set_pre_execution_cache_once`).
+    fn pre_execute(&self, txns: Vec<SignedTransaction>) -> Result<usize> {
+        let cache = match AptosVM::get_pre_execution_cache() {
+            Some(cache) => cache,
+            None => return Ok(0),
+        };
+
+        let db_state_view = self.db_reader.latest_state_checkpoint_view()?;
+        let version = db_state_view.version;
+        let state_view: CachedDbStateView = db_state_view.into();
+        let pre_executor = PreExecutor::new(&state_view, version);
+
+        let mut num_cached = 0;
+        for txn in txns {
+            let txn_hash = txn.clone().committed_hash();
+            if cache.contains(&txn_hash, version) {
+                continue;
+            }
+            if let Some(output) = pre_executor.execute(txn) {
+                cache.insert(txn_hash, output);
+                num_cached += 1;
+            }
+        }
+        Ok(num_cached)
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    pre_executor::{TransactionPreExecution, TransactionPreExecutor},
+    vm_validator::get_account_sequence_number,
+};
+use anyhow::Result;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_db::AptosDB;
+use aptos_state_view::TStateView;
+use aptos_storage_interface::{
+    cached_state_view::CachedDbStateView, state_view::LatestDbStateCheckpointView, DbReader,
+    DbReaderWriter,
+};
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::{self, CORE_CODE_ADDRESS},
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    test_helpers::transaction_test_helpers,
+    timestamp::{Timestamp, TimestampResource},
+    transaction::{SignedTransaction, TransactionStatus},
+    write_set::WriteOp,
+};
+use aptos_vm::{pre_execution_cache::PreExecutionCache, AptosVM};
+use move_core_types::move_resource::MoveResource;
+use std::{collections::HashMap, sync::Arc};
+
+struct TestPreExecutor {
+    db_reader: Arc<dyn DbReader>,
+    pre_executor: TransactionPreExecutor,
+    _db_path: aptos_temppath::TempPath,
+}
+
+impl TestPreExecutor {
+    fn new() -> Self {
+        let _db_path = aptos_temppath::TempPath::new();
+        _db_path.create_as_dir().unwrap();
+        let (db, db_rw) = DbReaderWriter::wrap(AptosDB::new_for_test(_db_path.path()));
+        aptos_executor_test_helpers::bootstrap_genesis::<AptosVM>(
+            &db_rw,
+            &aptos_vm_genesis::test_genesis_transaction(),
+        )
+        .expect("Db-bootstrapper should not fail.");
+
+        AptosVM::set_pre_execution_cache_once(1000);
+        TestPreExecutor {
+            db_reader: db_rw.reader.clone(),
+            pre_executor: TransactionPreExecutor::new(db),
+            _db_path,
+        }
+    }
+
+    fn cache(&self) -> &'static PreExecutionCache {
+        AptosVM::get_pre_execution_cache().unwrap()
+    }
+
+    fn state_view(&self) -> CachedDbStateView {
+        self.db_reader
+            .latest_state_checkpoint_view()
+            .unwrap()
+            .into()
+    }
+
+    /// Returns a transaction from the root account minting `amount` coins, which is unique to the
+    /// test as the outputs of all tests share the same cache.
+    fn mint_txn(&self, amount: u64) -> SignedTransaction {
+        let address = account_config::aptos_test_root_address();
+        let sequence_number = get_account_sequence_number(
+            &self.db_reader.latest_state_checkpoint_view().unwrap(),
+            address,
+        )
+        .unwrap();
+        transaction_test_helpers::get_test_signed_txn(
+            address,
+            sequence_number,
+            &aptos_vm_genesis::GENESIS_KEYPAIR.0,
+            aptos_vm_genesis::GENESIS_KEYPAIR.1.clone(),
+            Some(aptos_stdlib::aptos_coin_mint(address, amount)),
+        )
+    }
+}
+
+/// A state view with some values replaced, standing for a later state.
+struct OverriddenStateView<'a> {
+    base_view: &'a CachedDbStateView,
+    overrides: HashMap<StateKey, Option<StateValue>>,
+}
+
+impl<'a> OverriddenStateView<'a> {
+    fn new(base_view: &'a CachedDbStateView) -> Self {
+        Self {
+            base_view,
+            overrides: HashMap::new(),
+        }
+    }
+
+    fn with_timestamp_secs(mut self, timestamp_secs: u64) -> Self {
+        let resource = TimestampResource {
+            timestamp: Timestamp {
+                microseconds: timestamp_secs * 1_000_000,
+            },
+        };
+        self.overrides.insert(
+            StateKey::access_path(AccessPath::new(
+                CORE_CODE_ADDRESS,
+                TimestampResource::resource_path(),
+            )),
+            Some(StateValue::from(bcs::to_bytes(&resource).unwrap())),
+        );
+        self
+    }
+}
+
+impl<'a> TStateView for OverriddenStateView<'a> {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.overrides.get(state_key) {
+            Some(value) => Ok(value.clone()),
+            None => self.base_view.get_state_value(state_key),
+        }
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base_view.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base_view.get_usage()
+    }
+}
+
+#[test]
+fn test_pre_execute_caches_outputs() {
+    let test = TestPreExecutor::new();
+    let txn = test.mint_txn(101);
+    let txn_hash = txn.clone().committed_hash();
+    let version = test
+        .db_reader
+        .latest_state_checkpoint_view()
+        .unwrap()
+        .version;
+
+    assert_eq!(test.pre_executor.pre_execute(vec![txn.clone()]).unwrap(), 1);
+    assert!(test.cache().contains(&txn_hash, version));
+    // The transaction was already executed against the latest committed state.
+    assert_eq!(test.pre_executor.pre_execute(vec![txn]).unwrap(), 0);
+
+    let output = test
+        .cache()
+        .get_valid_output(&txn_hash, &test.state_view())
+        .unwrap();
+    assert!(matches!(
+        output.txn_output().status(),
+        TransactionStatus::Keep(_)
+    ));
+    assert!(!output.txn_output().write_set().is_empty());
+}
+
+#[test]
+fn test_pre_execute_skips_invalid_transactions() {
+    let test = TestPreExecutor::new();
+    // The sequence number is too new, so the transaction is discarded.
+    let address = account_config::aptos_test_root_address();
+    let txn = transaction_test_helpers::get_test_signed_txn(
+        address,
+        1000,
+        &aptos_vm_genesis::GENESIS_KEYPAIR.0,
+        aptos_vm_genesis::GENESIS_KEYPAIR.1.clone(),
+        Some(aptos_stdlib::aptos_coin_mint(address, 102)),
+    );
+    let txn_hash = txn.clone().committed_hash();
+
+    assert_eq!(test.pre_executor.pre_execute(vec![txn]).unwrap(), 0);
+    assert!(test
+        .cache()
+        .get_valid_output(&txn_hash, &test.state_view())
+        .is_none());
+}
+
+#[test]
+fn test_pre_executed_output_valid_in_later_blocks() {
+    let test = TestPreExecutor::new();
+    let txn = test.mint_txn(103);
+    let txn_hash = txn.clone().committed_hash();
+    let expiration_timestamp_secs = txn.expiration_timestamp_secs();
+    assert_eq!(test.pre_executor.pre_execute(vec![txn]).unwrap(), 1);
+
+    // The current time changes with every block, which does not matter until the transaction
+    // expires.
+    let state_view = test.state_view();
+    let later_view =
+        OverriddenStateView::new(&state_view).with_timestamp_secs(expiration_timestamp_secs - 1);
+    assert!(test
+        .cache()
+        .get_valid_output(&txn_hash, &later_view)
+        .is_some());
+
+    let expired_view =
+        OverriddenStateView::new(&state_view).with_timestamp_secs(expiration_timestamp_secs);
+    assert!(test
+        .cache()
+        .get_valid_output(&txn_hash, &expired_view)
+        .is_none());
+}
+
+#[test]
+fn test_pre_executed_output_stale_after_read_value_changes() {
+    let test = TestPreExecutor::new();
+    let txn = test.mint_txn(104);
+    let txn_hash = txn.clone().committed_hash();
+    assert_eq!(test.pre_executor.pre_execute(vec![txn]).unwrap(), 1);
+
+    let state_view = test.state_view();
+    let output = test
+        .cache()
+        .get_valid_output(&txn_hash, &state_view)
+        .unwrap();
+
+    // Apply the writes of the transaction itself, which modify values it read (e.g., the
+    // sequence number of the sender).
+    let mut committed_view = OverriddenStateView::new(&state_view);
+    for (state_key, write_op) in output.txn_output().write_set() {
+        let value = match write_op {
+            WriteOp::Creation(bytes) | WriteOp::Modification(bytes) => {
+                Some(StateValue::from(bytes.clone()))
+            },
+            WriteOp::CreationWithMetadata { data, .. }
+            | WriteOp::ModificationWithMetadata { data, .. } => {
+                Some(StateValue::from(data.clone()))
+            },
+            WriteOp::Deletion | WriteOp::DeletionWithMetadata { .. } => None,
+        };
+        committed_view.overrides.insert(state_key.clone(), value);
+    }
+    assert!(test
+        .cache()
+        .get_valid_output(&txn_hash, &committed_view)
+        .is_none());
+}